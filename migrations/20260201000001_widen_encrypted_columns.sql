@@ -0,0 +1,6 @@
+-- Widen columns that may hold application-encrypted values
+-- Ciphertext (enc:v1:<key_id>:<base64>) is longer than the plaintext limits,
+-- which are still enforced by request validation
+
+ALTER TABLE transactions ALTER COLUMN description TYPE TEXT;
+ALTER TABLE accounts ALTER COLUMN name TYPE TEXT;
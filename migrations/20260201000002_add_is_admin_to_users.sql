@@ -0,0 +1,7 @@
+-- Operators allowed to trigger maintenance endpoints
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_admin BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Speeds up the refresh token cleanup job for revoked tokens
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_revoked
+    ON refresh_tokens(revoked_at)
+    WHERE revoked_at IS NOT NULL;
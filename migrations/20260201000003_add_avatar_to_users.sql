@@ -0,0 +1,3 @@
+-- Profile avatar: storage key prefix of the current rendition set and its public URL
+ALTER TABLE users ADD COLUMN IF NOT EXISTS avatar_key VARCHAR(255);
+ALTER TABLE users ADD COLUMN IF NOT EXISTS avatar_url TEXT;
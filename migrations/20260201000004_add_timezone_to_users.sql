@@ -0,0 +1,2 @@
+-- IANA timezone used to bucket transactions into calendar months for the user
+ALTER TABLE users ADD COLUMN IF NOT EXISTS timezone VARCHAR(64) NOT NULL DEFAULT 'UTC';
@@ -0,0 +1,23 @@
+-- Create audit_log table
+-- Append-only record of user-initiated changes, used for history and undo
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+
+    -- What happened, e.g. 'budget.move_allocation'
+    action VARCHAR(50) NOT NULL,
+    entity_type VARCHAR(30) NOT NULL,
+    entity_id UUID NOT NULL,
+
+    -- Action-specific payload (amounts, before/after values)
+    details JSONB NOT NULL DEFAULT '{}'::jsonb,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Primary query: a user's recent history
+CREATE INDEX idx_audit_log_user_created ON audit_log(user_id, created_at DESC);
+
+-- History for a single entity
+CREATE INDEX idx_audit_log_entity ON audit_log(entity_type, entity_id);
@@ -0,0 +1,28 @@
+-- Per-user naive Bayes model for category suggestions
+-- Categories are per-budget, so the model is keyed by lowercase category name.
+-- Description tokens are stored as salted hashes so the model does not expose
+-- encrypted transaction descriptions.
+
+CREATE TABLE IF NOT EXISTS category_model_stats (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_key VARCHAR(50) NOT NULL,
+
+    transaction_count INTEGER NOT NULL DEFAULT 0,
+    token_count INTEGER NOT NULL DEFAULT 0,
+    -- Sum of ln(amount), for the per-category typical amount
+    log_amount_sum DOUBLE PRECISION NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (user_id, category_key)
+);
+
+CREATE TABLE IF NOT EXISTS category_model_tokens (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_key VARCHAR(50) NOT NULL,
+    token_hash CHAR(16) NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (user_id, category_key, token_hash)
+);
+
+-- Lookup of a description's tokens across all categories
+CREATE INDEX idx_category_model_tokens_user_token ON category_model_tokens(user_id, token_hash);
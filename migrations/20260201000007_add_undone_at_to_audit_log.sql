@@ -0,0 +1,2 @@
+-- Track which audit entries have been reversed by POST /undo
+ALTER TABLE audit_log ADD COLUMN IF NOT EXISTS undone_at TIMESTAMPTZ;
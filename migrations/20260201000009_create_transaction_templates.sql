@@ -0,0 +1,34 @@
+-- Saved transactions for one-tap entry of frequent purchases
+-- Categories belong to a single month's budget, so templates keep the category
+-- name and resolve it against the current month's budget when used
+
+CREATE TABLE IF NOT EXISTS transaction_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    category_name VARCHAR(50) NOT NULL,
+
+    -- SET NULL keeps the template usable if the account is deleted
+    account_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+
+    -- Optional; when absent the amount must be given each time the template is used
+    amount NUMERIC(12,2),
+    transaction_type VARCHAR(10) NOT NULL DEFAULT 'expense',
+    -- May hold an application-encrypted value
+    description TEXT,
+
+    use_count INTEGER NOT NULL DEFAULT 0,
+    last_used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_transaction_templates_amount CHECK (amount IS NULL OR amount > 0),
+    CONSTRAINT chk_transaction_templates_type CHECK (transaction_type IN ('expense', 'income'))
+);
+
+CREATE INDEX idx_transaction_templates_owner ON transaction_templates(owner_id, use_count DESC);
+
+CREATE TRIGGER trg_transaction_templates_updated_at
+    BEFORE UPDATE ON transaction_templates
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,7 @@
+-- Trigram index for description autocomplete (GET /transactions/suggest)
+-- Encrypted descriptions can't be matched in SQL, so only plaintext rows are indexed
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_description_trgm
+    ON transactions USING GIN (LOWER(description) gin_trgm_ops)
+    WHERE description IS NOT NULL AND description NOT LIKE 'enc:v1:%';
@@ -0,0 +1,28 @@
+-- Outgoing webhook subscriptions
+-- Payloads are signed with a per-endpoint HMAC secret so receivers can verify
+-- that deliveries came from this backend
+
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+
+    -- Signing secrets; may hold application-encrypted values
+    secret TEXT NOT NULL,
+    -- Kept for a grace period after rotation so receivers can switch over
+    previous_secret TEXT,
+    secret_rotated_at TIMESTAMPTZ,
+
+    -- Subscribed event types; empty means every event
+    event_types TEXT[] NOT NULL DEFAULT '{}',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_webhook_endpoints_owner ON webhook_endpoints(owner_id) WHERE enabled;
+
+CREATE TRIGGER trg_webhook_endpoints_updated_at
+    BEFORE UPDATE ON webhook_endpoints
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,29 @@
+-- Append-only log of domain events, read by GET /events and webhook replay
+-- so consumers can catch up on anything missed during an outage
+
+CREATE TABLE IF NOT EXISTS events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Monotonic order for cursors; timestamps can tie
+    sequence BIGSERIAL NOT NULL UNIQUE,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    event_type VARCHAR(50) NOT NULL,
+    -- JSON payload; may hold an application-encrypted value
+    payload TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_events_owner_sequence ON events(owner_id, sequence);
+CREATE INDEX idx_events_owner_created ON events(owner_id, created_at);
+
+-- Events are never edited; rows only go away with their user
+CREATE OR REPLACE FUNCTION reject_event_update()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'events are append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_events_append_only
+    BEFORE UPDATE ON events
+    FOR EACH ROW
+    EXECUTE FUNCTION reject_event_update();
@@ -0,0 +1,122 @@
+-- Pre-aggregated monthly totals for reports and dashboards
+-- Months are calendar months in the owner's timezone (0-11, like budgets).
+-- Writes to transactions only mark the affected months dirty; a background job
+-- (and report reads, for the requesting user) recompute just those months.
+
+CREATE TABLE IF NOT EXISTS monthly_category_totals (
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    year SMALLINT NOT NULL,
+    month SMALLINT NOT NULL,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    transaction_type VARCHAR(10) NOT NULL,
+    total NUMERIC NOT NULL,
+    transaction_count INTEGER NOT NULL,
+    PRIMARY KEY (owner_id, year, month, category_id, transaction_type)
+);
+
+CREATE INDEX idx_monthly_category_totals_category ON monthly_category_totals(category_id);
+
+CREATE TABLE IF NOT EXISTS monthly_account_totals (
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    year SMALLINT NOT NULL,
+    month SMALLINT NOT NULL,
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- Kept per category so account-filtered breakdowns need no transaction scan
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    transaction_type VARCHAR(10) NOT NULL,
+    total NUMERIC NOT NULL,
+    transaction_count INTEGER NOT NULL,
+    PRIMARY KEY (owner_id, year, month, account_id, category_id, transaction_type)
+);
+
+CREATE INDEX idx_monthly_account_totals_account ON monthly_account_totals(account_id);
+CREATE INDEX idx_monthly_account_totals_category ON monthly_account_totals(category_id);
+
+-- No foreign key: rows may be queued while their user is being deleted
+CREATE TABLE IF NOT EXISTS summary_dirty_months (
+    owner_id UUID NOT NULL,
+    year SMALLINT NOT NULL,
+    month SMALLINT NOT NULL,
+    marked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (owner_id, year, month)
+);
+
+CREATE OR REPLACE FUNCTION mark_transaction_month_dirty(p_category_id UUID, p_date TIMESTAMPTZ)
+RETURNS VOID AS $$
+BEGIN
+    INSERT INTO summary_dirty_months (owner_id, year, month)
+    SELECT
+        b.owner_id,
+        EXTRACT(YEAR FROM p_date AT TIME ZONE u.timezone)::SMALLINT,
+        (EXTRACT(MONTH FROM p_date AT TIME ZONE u.timezone) - 1)::SMALLINT
+    FROM categories c
+    INNER JOIN budgets b ON c.budget_id = b.id
+    INNER JOIN users u ON u.id = b.owner_id
+    WHERE c.id = p_category_id
+    ON CONFLICT DO NOTHING;
+END;
+$$ LANGUAGE plpgsql;
+
+-- When a category or budget is deleted the lookup finds nothing, but the
+-- summary rows go with the category through ON DELETE CASCADE anyway
+CREATE OR REPLACE FUNCTION mark_summary_dirty_on_transaction_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        PERFORM mark_transaction_month_dirty(OLD.category_id, OLD.transaction_date);
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') THEN
+        PERFORM mark_transaction_month_dirty(NEW.category_id, NEW.transaction_date);
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_transactions_summary_dirty
+    AFTER INSERT OR DELETE ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION mark_summary_dirty_on_transaction_change();
+
+CREATE TRIGGER trg_transactions_summary_dirty_update
+    AFTER UPDATE OF amount, transaction_type, transaction_date, category_id, account_id ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION mark_summary_dirty_on_transaction_change();
+
+-- A timezone change moves transactions between months, so every month the
+-- user has data in, before or after the change, is recomputed
+CREATE OR REPLACE FUNCTION mark_summary_dirty_on_timezone_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    INSERT INTO summary_dirty_months (owner_id, year, month)
+    SELECT owner_id, year, month FROM monthly_category_totals WHERE owner_id = NEW.id
+    UNION
+    SELECT
+        b.owner_id,
+        EXTRACT(YEAR FROM t.transaction_date AT TIME ZONE NEW.timezone)::SMALLINT,
+        (EXTRACT(MONTH FROM t.transaction_date AT TIME ZONE NEW.timezone) - 1)::SMALLINT
+    FROM transactions t
+    INNER JOIN categories c ON t.category_id = c.id
+    INNER JOIN budgets b ON c.budget_id = b.id
+    WHERE b.owner_id = NEW.id
+    ON CONFLICT DO NOTHING;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_users_summary_dirty_timezone
+    AFTER UPDATE OF timezone ON users
+    FOR EACH ROW
+    WHEN (OLD.timezone IS DISTINCT FROM NEW.timezone)
+    EXECUTE FUNCTION mark_summary_dirty_on_timezone_change();
+
+-- Backfill: queue every month with existing transactions
+INSERT INTO summary_dirty_months (owner_id, year, month)
+SELECT DISTINCT
+    b.owner_id,
+    EXTRACT(YEAR FROM t.transaction_date AT TIME ZONE u.timezone)::SMALLINT,
+    (EXTRACT(MONTH FROM t.transaction_date AT TIME ZONE u.timezone) - 1)::SMALLINT
+FROM transactions t
+INNER JOIN categories c ON t.category_id = c.id
+INNER JOIN budgets b ON c.budget_id = b.id
+INNER JOIN users u ON u.id = b.owner_id
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,120 @@
+-- Denormalized spent_amount on categories: expenses dated within the
+-- category's budget month in the owner's timezone. Maintained by triggers in
+-- the same transaction as every transaction write, so category reads no
+-- longer aggregate the transactions table.
+
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS spent_amount NUMERIC(14,2) NOT NULL DEFAULT 0;
+
+-- Whether a transaction counts toward its category's spent_amount
+CREATE OR REPLACE FUNCTION counts_toward_category_spent(
+    p_category_id UUID,
+    p_transaction_type VARCHAR,
+    p_transaction_date TIMESTAMPTZ
+)
+RETURNS BOOLEAN AS $$
+    SELECT p_transaction_type = 'expense' AND EXISTS (
+        SELECT 1
+        FROM categories c
+        INNER JOIN budgets b ON c.budget_id = b.id
+        INNER JOIN users u ON u.id = b.owner_id
+        WHERE c.id = p_category_id
+          AND p_transaction_date >= make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+          AND p_transaction_date < (make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+    );
+$$ LANGUAGE sql STABLE;
+
+-- Recalculate spent_amount from scratch for the given categories
+CREATE OR REPLACE FUNCTION recompute_category_spent(p_category_ids UUID[])
+RETURNS VOID AS $$
+    UPDATE categories c
+    SET spent_amount = totals.spent
+    FROM (
+        SELECT c2.id, COALESCE(SUM(t.amount), 0) AS spent
+        FROM categories c2
+        INNER JOIN budgets b ON c2.budget_id = b.id
+        INNER JOIN users u ON u.id = b.owner_id
+        LEFT JOIN transactions t ON t.category_id = c2.id
+            AND t.transaction_type = 'expense'
+            AND t.transaction_date >= make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+            AND t.transaction_date < (make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+        WHERE c2.id = ANY(p_category_ids)
+        GROUP BY c2.id
+    ) totals
+    WHERE c.id = totals.id
+      AND c.spent_amount <> totals.spent;
+$$ LANGUAGE sql;
+
+-- Apply each transaction write as a delta. When a category is deleted its
+-- transactions cascade after it, the lookup finds nothing and no update runs.
+CREATE OR REPLACE FUNCTION maintain_category_spent()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE')
+        AND counts_toward_category_spent(OLD.category_id, OLD.transaction_type, OLD.transaction_date) THEN
+        UPDATE categories SET spent_amount = spent_amount - OLD.amount WHERE id = OLD.category_id;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE')
+        AND counts_toward_category_spent(NEW.category_id, NEW.transaction_type, NEW.transaction_date) THEN
+        UPDATE categories SET spent_amount = spent_amount + NEW.amount WHERE id = NEW.category_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_transactions_category_spent
+    AFTER INSERT OR DELETE ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION maintain_category_spent();
+
+CREATE TRIGGER trg_transactions_category_spent_update
+    AFTER UPDATE OF amount, transaction_type, transaction_date, category_id ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION maintain_category_spent();
+
+-- Changes that move the budget month window recompute the affected categories
+CREATE OR REPLACE FUNCTION recompute_spent_on_window_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_TABLE_NAME = 'categories' THEN
+        PERFORM recompute_category_spent(ARRAY[NEW.id]);
+    ELSIF TG_TABLE_NAME = 'budgets' THEN
+        PERFORM recompute_category_spent(ARRAY(SELECT id FROM categories WHERE budget_id = NEW.id));
+    ELSE
+        PERFORM recompute_category_spent(ARRAY(
+            SELECT c.id FROM categories c
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = NEW.id
+        ));
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_categories_spent_budget_change
+    AFTER UPDATE OF budget_id ON categories
+    FOR EACH ROW
+    WHEN (OLD.budget_id IS DISTINCT FROM NEW.budget_id)
+    EXECUTE FUNCTION recompute_spent_on_window_change();
+
+CREATE TRIGGER trg_budgets_spent_period_change
+    AFTER UPDATE OF month, year ON budgets
+    FOR EACH ROW
+    WHEN (OLD.month IS DISTINCT FROM NEW.month OR OLD.year IS DISTINCT FROM NEW.year)
+    EXECUTE FUNCTION recompute_spent_on_window_change();
+
+CREATE TRIGGER trg_users_spent_timezone_change
+    AFTER UPDATE OF timezone ON users
+    FOR EACH ROW
+    WHEN (OLD.timezone IS DISTINCT FROM NEW.timezone)
+    EXECUTE FUNCTION recompute_spent_on_window_change();
+
+-- Spending counters are not edits to the category itself
+DROP TRIGGER IF EXISTS trg_categories_updated_at ON categories;
+CREATE TRIGGER trg_categories_updated_at
+    BEFORE UPDATE ON categories
+    FOR EACH ROW
+    WHEN (OLD.spent_amount IS NOT DISTINCT FROM NEW.spent_amount)
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Backfill existing categories
+SELECT recompute_category_spent(ARRAY(SELECT id FROM categories));
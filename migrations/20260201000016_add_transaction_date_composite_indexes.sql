@@ -0,0 +1,9 @@
+-- Composite indexes for filtered transaction lists, which match on category or
+-- account and order by date. The category index already exists on databases
+-- created from the initial transactions migration.
+CREATE INDEX IF NOT EXISTS idx_transactions_category_date
+    ON transactions(category_id, transaction_date DESC);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_account_date
+    ON transactions(account_id, transaction_date DESC)
+    WHERE account_id IS NOT NULL;
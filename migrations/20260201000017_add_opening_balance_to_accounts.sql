@@ -0,0 +1,27 @@
+-- Opening balance: the part of an account's balance not explained by its
+-- transactions (the initial balance plus manual balance adjustments). With it,
+-- the stored balance can be recomputed from history and checked for drift.
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS opening_balance NUMERIC(12,2) NOT NULL DEFAULT 0;
+
+-- Net effect of every transaction on an account: income adds, expenses and
+-- outgoing transfers subtract, incoming transfers add
+CREATE OR REPLACE FUNCTION account_transaction_net(p_account_id UUID)
+RETURNS NUMERIC AS $$
+    SELECT COALESCE(SUM(
+        CASE
+            WHEN t.account_id = p_account_id AND t.transaction_type = 'income' THEN t.amount
+            WHEN t.account_id = p_account_id THEN -t.amount
+            ELSE 0
+        END
+        + CASE
+            WHEN t.destination_account_id = p_account_id AND t.transaction_type = 'transfer' THEN t.amount
+            ELSE 0
+        END
+    ), 0)
+    FROM transactions t
+    WHERE t.account_id = p_account_id OR t.destination_account_id = p_account_id;
+$$ LANGUAGE sql STABLE;
+
+-- Existing balances become the baseline: whatever history doesn't explain is
+-- treated as opening balance, so drift is measured from here on
+UPDATE accounts SET opening_balance = balance - account_transaction_net(id);
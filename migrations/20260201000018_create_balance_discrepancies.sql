@@ -0,0 +1,16 @@
+-- Balance drift found by the integrity audit job: the stored balance differed
+-- from opening balance plus transaction history when the audit ran
+CREATE TABLE IF NOT EXISTS balance_discrepancies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    stored_balance NUMERIC(12,2) NOT NULL,
+    opening_balance NUMERIC(12,2) NOT NULL,
+    transaction_net NUMERIC(12,2) NOT NULL,
+    computed_balance NUMERIC(12,2) NOT NULL,
+    drift NUMERIC(12,2) NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_balance_discrepancies_account ON balance_discrepancies(account_id, detected_at DESC);
+CREATE INDEX idx_balance_discrepancies_detected ON balance_discrepancies(detected_at DESC);
@@ -0,0 +1,72 @@
+-- Refunds and reversals: a transaction of the opposite type linked to the one
+-- it reverses. Balances move as for any income or expense, but reports count
+-- a refund as a negative amount of the original's type, so a returned purchase
+-- lowers spending instead of showing up as income.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS reversal_of_id UUID REFERENCES transactions(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_transactions_reversal_of ON transactions(reversal_of_id)
+    WHERE reversal_of_id IS NOT NULL;
+
+-- Type and signed amount a transaction contributes to summaries
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS reporting_type VARCHAR(10) GENERATED ALWAYS AS (
+        CASE
+            WHEN reversal_of_id IS NULL THEN transaction_type
+            WHEN transaction_type = 'income' THEN 'expense'
+            WHEN transaction_type = 'expense' THEN 'income'
+            ELSE transaction_type
+        END
+    ) STORED,
+    ADD COLUMN IF NOT EXISTS reporting_amount NUMERIC(12,2) GENERATED ALWAYS AS (
+        CASE WHEN reversal_of_id IS NULL THEN amount ELSE -amount END
+    ) STORED;
+
+-- Category spending nets refunds against the expenses they reverse
+CREATE OR REPLACE FUNCTION recompute_category_spent(p_category_ids UUID[])
+RETURNS VOID AS $$
+    UPDATE categories c
+    SET spent_amount = totals.spent
+    FROM (
+        SELECT c2.id, COALESCE(SUM(t.reporting_amount), 0) AS spent
+        FROM categories c2
+        INNER JOIN budgets b ON c2.budget_id = b.id
+        INNER JOIN users u ON u.id = b.owner_id
+        LEFT JOIN transactions t ON t.category_id = c2.id
+            AND t.reporting_type = 'expense'
+            AND t.transaction_date >= make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+            AND t.transaction_date < (make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+        WHERE c2.id = ANY(p_category_ids)
+        GROUP BY c2.id
+    ) totals
+    WHERE c.id = totals.id
+      AND c.spent_amount <> totals.spent;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION maintain_category_spent()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE')
+        AND counts_toward_category_spent(OLD.category_id, OLD.reporting_type, OLD.transaction_date) THEN
+        UPDATE categories SET spent_amount = spent_amount - OLD.reporting_amount WHERE id = OLD.category_id;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE')
+        AND counts_toward_category_spent(NEW.category_id, NEW.reporting_type, NEW.transaction_date) THEN
+        UPDATE categories SET spent_amount = spent_amount + NEW.reporting_amount WHERE id = NEW.category_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Unlinking a refund (its original was deleted) changes how it is counted
+DROP TRIGGER IF EXISTS trg_transactions_category_spent_update ON transactions;
+CREATE TRIGGER trg_transactions_category_spent_update
+    AFTER UPDATE OF amount, transaction_type, transaction_date, category_id, reversal_of_id ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION maintain_category_spent();
+
+DROP TRIGGER IF EXISTS trg_transactions_summary_dirty_update ON transactions;
+CREATE TRIGGER trg_transactions_summary_dirty_update
+    AFTER UPDATE OF amount, transaction_type, transaction_date, category_id, account_id, reversal_of_id ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION mark_summary_dirty_on_transaction_change();
@@ -0,0 +1,39 @@
+-- Reimbursable expenses: spending someone else (an employer, a friend) owes
+-- back. The status moves pending -> submitted -> settled (or rejected), and
+-- the income that paid an item back is linked through reimbursed_by_id.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS reimbursable BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS reimbursement_status VARCHAR(10),
+    -- Who owes the money; may hold an application-encrypted value
+    ADD COLUMN IF NOT EXISTS reimbursement_payer TEXT,
+    ADD COLUMN IF NOT EXISTS reimbursed_by_id UUID REFERENCES transactions(id) ON DELETE SET NULL;
+
+ALTER TABLE transactions ADD CONSTRAINT chk_transactions_reimbursement CHECK (
+    (reimbursable AND reimbursement_status IN ('pending', 'submitted', 'settled', 'rejected'))
+    OR (NOT reimbursable AND reimbursement_status IS NULL
+        AND reimbursement_payer IS NULL AND reimbursed_by_id IS NULL)
+);
+
+-- Query pattern: outstanding items for the report and income matching
+CREATE INDEX IF NOT EXISTS idx_transactions_reimbursement_outstanding
+    ON transactions(category_id, transaction_date)
+    WHERE reimbursement_status IN ('pending', 'submitted');
+
+CREATE INDEX IF NOT EXISTS idx_transactions_reimbursed_by ON transactions(reimbursed_by_id)
+    WHERE reimbursed_by_id IS NOT NULL;
+
+-- Deleting the income that paid an item back reopens the item
+CREATE OR REPLACE FUNCTION reopen_unlinked_reimbursement()
+RETURNS TRIGGER AS $$
+BEGIN
+    NEW.reimbursement_status := 'pending';
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_transactions_reimbursement_unlinked
+    BEFORE UPDATE OF reimbursed_by_id ON transactions
+    FOR EACH ROW
+    WHEN (OLD.reimbursed_by_id IS NOT NULL AND NEW.reimbursed_by_id IS NULL
+          AND NEW.reimbursement_status = 'settled')
+    EXECUTE FUNCTION reopen_unlinked_reimbursement();
@@ -0,0 +1,8 @@
+-- Tax tagging for the yearly tax report: expenses in a category count toward
+-- its tax category (e.g. "Charitable donations"), and a single transaction can
+-- name its own to override the category's.
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS tax_category VARCHAR(50);
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS tax_category VARCHAR(50);
+
+CREATE INDEX IF NOT EXISTS idx_categories_tax_category ON categories(tax_category)
+    WHERE tax_category IS NOT NULL;
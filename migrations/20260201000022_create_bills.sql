@@ -0,0 +1,60 @@
+-- Recurring bills due on a fixed day of the month
+-- Like templates, bills keep the category name and resolve it against the
+-- current month's budget when a payment is recorded
+
+CREATE TABLE IF NOT EXISTS bills (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    amount NUMERIC(12,2) NOT NULL,
+    -- Months shorter than the due day fall due on their last day
+    due_day SMALLINT NOT NULL,
+    category_name VARCHAR(50) NOT NULL,
+
+    -- SET NULL keeps the bill if the paying account is deleted
+    account_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+
+    -- Paid automatically: the payment is recorded on the due date
+    autopay BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Days before the due date the reminder goes out
+    reminder_days SMALLINT NOT NULL DEFAULT 3,
+
+    -- Due date of the oldest unpaid occurrence
+    next_due_date DATE NOT NULL,
+    -- Due dates the reminder and the overdue notice were last sent for
+    reminded_for DATE,
+    overdue_notified_for DATE,
+
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_bills_amount CHECK (amount > 0),
+    CONSTRAINT chk_bills_due_day CHECK (due_day BETWEEN 1 AND 31),
+    CONSTRAINT chk_bills_reminder_days CHECK (reminder_days BETWEEN 0 AND 30)
+);
+
+CREATE INDEX idx_bills_owner_due ON bills(owner_id, next_due_date);
+-- Query pattern: the reminder job scans every bill by due date
+CREATE INDEX idx_bills_next_due ON bills(next_due_date);
+
+CREATE TRIGGER trg_bills_updated_at
+    BEFORE UPDATE ON bills
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- One row per paid occurrence
+CREATE TABLE IF NOT EXISTS bill_payments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    bill_id UUID NOT NULL REFERENCES bills(id) ON DELETE CASCADE,
+    -- SET NULL keeps the payment history if the transaction is deleted
+    transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL,
+    due_date DATE NOT NULL,
+    amount NUMERIC(12,2) NOT NULL,
+    autopay BOOLEAN NOT NULL DEFAULT FALSE,
+    paid_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_bill_payments_due UNIQUE (bill_id, due_date)
+);
+
+CREATE UNIQUE INDEX idx_bill_payments_transaction ON bill_payments(transaction_id)
+    WHERE transaction_id IS NOT NULL;
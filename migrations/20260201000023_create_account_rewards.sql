@@ -0,0 +1,31 @@
+-- Cashback on credit accounts: a flat rate plus optional per-category rates,
+-- estimated per statement period. Categories belong to a single month's
+-- budget, so category rates match by category name.
+CREATE TABLE IF NOT EXISTS account_rewards (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    -- Percent of eligible spending earned back
+    flat_rate NUMERIC(5,2) NOT NULL DEFAULT 0,
+    -- Day of the month statements close; later days close on the month's last day
+    statement_day SMALLINT NOT NULL DEFAULT 31,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_account_rewards_flat_rate CHECK (flat_rate BETWEEN 0 AND 100),
+    CONSTRAINT chk_account_rewards_statement_day CHECK (statement_day BETWEEN 1 AND 31)
+);
+
+CREATE TRIGGER trg_account_rewards_updated_at
+    BEFORE UPDATE ON account_rewards
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS account_reward_categories (
+    account_id UUID NOT NULL REFERENCES account_rewards(account_id) ON DELETE CASCADE,
+    category_name VARCHAR(50) NOT NULL,
+    rate NUMERIC(5,2) NOT NULL,
+
+    CONSTRAINT chk_account_reward_categories_rate CHECK (rate BETWEEN 0 AND 100)
+);
+
+CREATE UNIQUE INDEX idx_account_reward_categories_name
+    ON account_reward_categories(account_id, LOWER(category_name));
@@ -0,0 +1,41 @@
+-- Investment accounts: securities held in the account, priced by the
+-- configured price provider (or by hand), and a daily valuation snapshot
+ALTER TABLE accounts DROP CONSTRAINT chk_accounts_type;
+ALTER TABLE accounts ADD CONSTRAINT chk_accounts_type
+    CHECK (account_type IN ('checking', 'savings', 'credit', 'investment'));
+
+CREATE TABLE IF NOT EXISTS account_holdings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    -- Stored upper-case
+    ticker VARCHAR(20) NOT NULL,
+    quantity NUMERIC(20,8) NOT NULL CHECK (quantity > 0),
+    -- Total amount paid for the position, in the account currency
+    cost_basis NUMERIC(14,2) NOT NULL CHECK (cost_basis >= 0),
+    -- Latest unit price and when it was set; NULL until first priced
+    price NUMERIC(18,6) CHECK (price >= 0),
+    priced_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_account_holdings_ticker UNIQUE (account_id, ticker)
+);
+
+-- Query pattern: the valuation job prices every holding of a ticker at once
+CREATE INDEX IF NOT EXISTS idx_account_holdings_ticker ON account_holdings(ticker);
+
+CREATE TRIGGER update_account_holdings_updated_at
+    BEFORE UPDATE ON account_holdings
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- One snapshot per investment account per local day, overwritten by later
+-- valuation runs on the same day
+CREATE TABLE IF NOT EXISTS account_valuations (
+    account_id UUID NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    valued_on DATE NOT NULL,
+    cash_balance NUMERIC(12,2) NOT NULL,
+    holdings_value NUMERIC(16,2) NOT NULL,
+    cost_basis NUMERIC(16,2) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (account_id, valued_on)
+);
@@ -0,0 +1,32 @@
+-- Loan accounts: the balance is what is owed (negative, like credit cards),
+-- and the terms drive the amortization schedule and optional autoposting
+ALTER TABLE accounts DROP CONSTRAINT chk_accounts_type;
+ALTER TABLE accounts ADD CONSTRAINT chk_accounts_type
+    CHECK (account_type IN ('checking', 'savings', 'credit', 'investment', 'loan'));
+
+CREATE TABLE IF NOT EXISTS account_loans (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    principal NUMERIC(14,2) NOT NULL CHECK (principal > 0),
+    apr NUMERIC(6,3) NOT NULL CHECK (apr >= 0 AND apr <= 100),
+    term_months SMALLINT NOT NULL CHECK (term_months BETWEEN 1 AND 600),
+    first_payment_date DATE NOT NULL,
+    -- Autoposting books each payment as an interest expense and a principal
+    -- transfer from the payment account when it comes due
+    auto_post BOOLEAN NOT NULL DEFAULT FALSE,
+    payment_account_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    category_name VARCHAR(50),
+    -- Payments already posted (or skipped because they were due before
+    -- autoposting was turned on)
+    payments_posted SMALLINT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Query pattern: the posting job scans autoposting loans
+CREATE INDEX IF NOT EXISTS idx_account_loans_auto_post ON account_loans(account_id)
+    WHERE auto_post;
+
+CREATE TRIGGER update_account_loans_updated_at
+    BEFORE UPDATE ON account_loans
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
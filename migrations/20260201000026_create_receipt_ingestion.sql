@@ -0,0 +1,33 @@
+-- Receipt ingestion by email: each user gets a private address
+-- (receipts+<token>@<domain>); forwarded receipts become drafts that the user
+-- confirms into transactions or dismisses
+CREATE TABLE IF NOT EXISTS receipt_inboxes (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    token VARCHAR(32) NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS receipt_drafts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Parsed fields; NULL when the receipt didn't yield them
+    merchant TEXT,
+    amount NUMERIC(12,2) CHECK (amount > 0),
+    receipt_date DATE,
+    -- Original sender and subject; may hold application-encrypted values
+    sender TEXT,
+    subject TEXT,
+    status VARCHAR(10) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'confirmed')),
+    transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Query pattern: a user's drafts by status, newest first
+CREATE INDEX IF NOT EXISTS idx_receipt_drafts_user ON receipt_drafts(user_id, status, received_at DESC);
+
+CREATE TRIGGER update_receipt_drafts_updated_at
+    BEFORE UPDATE ON receipt_drafts
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,25 @@
+-- Interest and monthly fees on credit and savings accounts, optionally posted
+-- as transactions at the end of each cycle
+CREATE TABLE IF NOT EXISTS account_charges (
+    account_id UUID PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+    apr NUMERIC(6,3) NOT NULL DEFAULT 0 CHECK (apr >= 0 AND apr <= 100),
+    monthly_fee NUMERIC(12,2) NOT NULL DEFAULT 0 CHECK (monthly_fee >= 0),
+    -- Day of the month cycles end, clamped to short months
+    cycle_day SMALLINT NOT NULL DEFAULT 31 CHECK (cycle_day BETWEEN 1 AND 31),
+    auto_post BOOLEAN NOT NULL DEFAULT FALSE,
+    category_name VARCHAR(50),
+    -- End of the last cycle posted (or skipped because it ended before
+    -- autoposting was turned on)
+    last_posted_cycle DATE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Query pattern: the posting job scans autoposting accounts
+CREATE INDEX IF NOT EXISTS idx_account_charges_auto_post ON account_charges(account_id)
+    WHERE auto_post;
+
+CREATE TRIGGER update_account_charges_updated_at
+    BEFORE UPDATE ON account_charges
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
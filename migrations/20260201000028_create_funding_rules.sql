@@ -0,0 +1,38 @@
+-- Rules splitting incoming income across categories and savings accounts.
+-- Categories belong to a single month's budget, so category targets keep the
+-- category name and resolve it against the income's budget when applied.
+
+CREATE TABLE IF NOT EXISTS funding_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+
+    -- Exactly one target: a category's allocation or a transfer to an account
+    category_name VARCHAR(50),
+    target_account_id UUID REFERENCES accounts(id) ON DELETE CASCADE,
+
+    -- Exactly one share: a percentage of the income or a fixed amount
+    percentage NUMERIC(5,2),
+    fixed_amount NUMERIC(12,2),
+
+    -- Only income into this account is split (any account when absent)
+    source_account_id UUID REFERENCES accounts(id) ON DELETE CASCADE,
+
+    -- Rules apply in ascending position until the income is used up
+    position INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_funding_rules_target CHECK ((category_name IS NULL) <> (target_account_id IS NULL)),
+    CONSTRAINT chk_funding_rules_share CHECK ((percentage IS NULL) <> (fixed_amount IS NULL)),
+    CONSTRAINT chk_funding_rules_percentage CHECK (percentage IS NULL OR (percentage > 0 AND percentage <= 100)),
+    CONSTRAINT chk_funding_rules_fixed_amount CHECK (fixed_amount IS NULL OR fixed_amount > 0)
+);
+
+CREATE INDEX idx_funding_rules_owner ON funding_rules(owner_id, position) WHERE is_active;
+
+CREATE TRIGGER trg_funding_rules_updated_at
+    BEFORE UPDATE ON funding_rules
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
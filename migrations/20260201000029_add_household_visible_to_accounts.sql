@@ -0,0 +1,3 @@
+-- Whether an account counts toward household reports seen by the people the
+-- owner shares budgets with
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS household_visible BOOLEAN NOT NULL DEFAULT TRUE;
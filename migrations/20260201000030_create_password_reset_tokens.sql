@@ -0,0 +1,18 @@
+-- Create password_reset_tokens table for emailed password reset links
+-- Stores hashed tokens (SHA-256) like refresh_tokens; each token works once
+
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash VARCHAR(64) NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    used_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX idx_password_reset_tokens_hash ON password_reset_tokens(token_hash);
+
+-- Index for invalidating a user's outstanding tokens when a new one is issued
+CREATE INDEX idx_password_reset_tokens_user_active
+    ON password_reset_tokens(user_id)
+    WHERE used_at IS NULL;
@@ -0,0 +1,28 @@
+-- User-defined labels on transactions, independent of categories (e.g.
+-- "vacation" across groceries, dining and transport).
+
+CREATE TABLE IF NOT EXISTS tags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(30) NOT NULL,
+    color_hex VARCHAR(7) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Names are unique per user, ignoring case
+CREATE UNIQUE INDEX idx_tags_owner_name ON tags(owner_id, LOWER(name));
+
+CREATE TRIGGER trg_tags_updated_at
+    BEFORE UPDATE ON tags
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+CREATE TABLE IF NOT EXISTS transaction_tags (
+    transaction_id UUID NOT NULL REFERENCES transactions(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (transaction_id, tag_id)
+);
+
+-- Filtering transactions by tag starts from the tag
+CREATE INDEX idx_transaction_tags_tag ON transaction_tags(tag_id, transaction_id);
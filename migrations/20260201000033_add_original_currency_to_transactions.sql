@@ -0,0 +1,12 @@
+-- Transactions entered in a currency other than their account's keep the
+-- amount as entered and the rate used to convert it into `amount`
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS original_amount NUMERIC(12,2),
+    ADD COLUMN IF NOT EXISTS original_currency CHAR(3) REFERENCES currencies(code),
+    ADD COLUMN IF NOT EXISTS exchange_rate NUMERIC(20,10);
+
+ALTER TABLE transactions
+    ADD CONSTRAINT chk_transactions_original_currency CHECK (
+        (original_amount IS NULL AND original_currency IS NULL AND exchange_rate IS NULL)
+        OR (original_amount > 0 AND original_currency IS NOT NULL AND exchange_rate > 0)
+    );
@@ -0,0 +1,6 @@
+-- Record where each refresh token was issued so users can review and revoke
+-- individual sessions
+ALTER TABLE refresh_tokens
+    ADD COLUMN IF NOT EXISTS device_name VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS user_agent VARCHAR(500),
+    ADD COLUMN IF NOT EXISTS ip_address VARCHAR(45);
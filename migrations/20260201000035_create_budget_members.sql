@@ -0,0 +1,48 @@
+-- Budgets shared with other users. The owner stays on budgets.owner_id;
+-- everyone else is a member with an editor or viewer role.
+
+CREATE TABLE IF NOT EXISTS budget_members (
+    budget_id UUID NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role VARCHAR(10) NOT NULL CHECK (role IN ('editor', 'viewer')),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (budget_id, user_id)
+);
+
+-- Listing the budgets shared with a user starts from the user
+CREATE INDEX idx_budget_members_user ON budget_members(user_id);
+
+-- Invitations are addressed by email so they can be sent before the invitee
+-- has an account; they are accepted by the user signed in with that email
+CREATE TABLE IF NOT EXISTS budget_invitations (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    budget_id UUID NOT NULL REFERENCES budgets(id) ON DELETE CASCADE,
+    email VARCHAR(255) NOT NULL,
+    role VARCHAR(10) NOT NULL CHECK (role IN ('editor', 'viewer')),
+    invited_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL DEFAULT NOW() + INTERVAL '14 days',
+    accepted_at TIMESTAMPTZ
+);
+
+-- At most one pending invitation per budget and email, ignoring case
+CREATE UNIQUE INDEX idx_budget_invitations_pending
+    ON budget_invitations(budget_id, LOWER(email))
+    WHERE accepted_at IS NULL;
+
+CREATE INDEX idx_budget_invitations_email ON budget_invitations(LOWER(email));
+
+-- Whether a user may read (p_write = FALSE) or change (p_write = TRUE) a
+-- budget and everything under it: owners and editors can write, viewers can
+-- only read
+CREATE OR REPLACE FUNCTION budget_access(p_budget_id UUID, p_user_id UUID, p_write BOOLEAN)
+RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM budgets WHERE id = p_budget_id AND owner_id = p_user_id
+    ) OR EXISTS (
+        SELECT 1 FROM budget_members
+        WHERE budget_id = p_budget_id
+          AND user_id = p_user_id
+          AND (NOT p_write OR role = 'editor')
+    )
+$$ LANGUAGE sql STABLE;
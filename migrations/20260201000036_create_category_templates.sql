@@ -0,0 +1,35 @@
+-- Named sets of categories saved for reuse, applied when setting up a budget
+
+CREATE TABLE IF NOT EXISTS category_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_category_templates_name_length CHECK (LENGTH(TRIM(name)) >= 1)
+);
+
+CREATE INDEX idx_category_templates_owner ON category_templates(owner_id);
+
+CREATE TRIGGER trg_category_templates_updated_at
+    BEFORE UPDATE ON category_templates
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- The categories a template creates, in the order they were saved
+CREATE TABLE IF NOT EXISTS category_template_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    template_id UUID NOT NULL REFERENCES category_templates(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    name VARCHAR(50) NOT NULL,
+    allocated_amount NUMERIC(12,2) NOT NULL DEFAULT 0,
+    color_hex CHAR(7) NOT NULL DEFAULT '#64748b',
+    tax_category VARCHAR(50),
+
+    CONSTRAINT chk_category_template_items_allocated CHECK (allocated_amount >= 0),
+    CONSTRAINT chk_category_template_items_color CHECK (color_hex ~ '^#[0-9A-Fa-f]{6}$')
+);
+
+CREATE UNIQUE INDEX idx_category_template_items_position
+    ON category_template_items(template_id, position);
@@ -0,0 +1,3 @@
+-- Whether transactions may take a checking or savings account below zero;
+-- when disabled they are rejected with the shortfall instead
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS allow_negative_balance BOOLEAN NOT NULL DEFAULT TRUE;
@@ -0,0 +1,15 @@
+-- User-arranged account ordering: pinned accounts first, then by position
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS display_order INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE accounts ADD COLUMN IF NOT EXISTS is_pinned BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Keep the order accounts were listed in until now (newest first)
+UPDATE accounts a
+SET display_order = ordered.position
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY owner_id ORDER BY created_at DESC) - 1 AS position
+    FROM accounts
+) ordered
+WHERE a.id = ordered.id;
+
+CREATE INDEX IF NOT EXISTS idx_accounts_owner_order
+    ON accounts(owner_id, is_pinned DESC, display_order);
@@ -0,0 +1,37 @@
+-- Per-category spending alerts: each user picks the percentages of a
+-- category's allocation they want to hear about (e.g. 80 and 100)
+
+CREATE TABLE IF NOT EXISTS category_alert_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id UUID NOT NULL REFERENCES categories(id) ON DELETE CASCADE,
+    threshold_percent INTEGER NOT NULL,
+    -- Set when the threshold is reached, cleared once spending drops back
+    -- below it, so each crossing notifies once
+    notified_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_category_alert_rules_threshold CHECK (threshold_percent BETWEEN 1 AND 1000)
+);
+
+CREATE UNIQUE INDEX idx_category_alert_rules_owner_category_threshold
+    ON category_alert_rules(owner_id, category_id, threshold_percent);
+-- Evaluation starts from the categories a transaction touched
+CREATE INDEX idx_category_alert_rules_category ON category_alert_rules(category_id);
+
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(30) NOT NULL,
+    category_id UUID REFERENCES categories(id) ON DELETE CASCADE,
+    threshold_percent INTEGER,
+    -- Category spending and allocation when the notification was raised
+    spent_amount NUMERIC(14,2),
+    allocated_amount NUMERIC(12,2),
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Newest first, unread first when filtering
+CREATE INDEX idx_notifications_owner_created ON notifications(owner_id, created_at DESC);
+CREATE INDEX idx_notifications_owner_unread ON notifications(owner_id) WHERE read_at IS NULL;
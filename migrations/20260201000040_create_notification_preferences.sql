@@ -0,0 +1,19 @@
+-- Per-user notification preferences; a missing row means the defaults
+-- (no spending digest)
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    owner_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    digest_frequency VARCHAR(10) NOT NULL DEFAULT 'off',
+    -- End (exclusive, in the owner's timezone) of the last period a digest
+    -- covered, so each week or month is sent once
+    digest_period_end DATE,
+    digest_sent_at TIMESTAMPTZ,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_notification_preferences_digest_frequency
+        CHECK (digest_frequency IN ('off', 'weekly', 'monthly'))
+);
+
+-- The digest job only looks at users who opted in
+CREATE INDEX idx_notification_preferences_digest
+    ON notification_preferences(digest_frequency) WHERE digest_frequency <> 'off';
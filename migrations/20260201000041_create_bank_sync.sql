@@ -0,0 +1,67 @@
+-- Bank sync: institutions linked through an aggregator (Plaid), their
+-- accounts mapped onto local ones, and pulled transactions waiting for review
+
+CREATE TABLE IF NOT EXISTS bank_connections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider VARCHAR(20) NOT NULL,
+    -- The provider's id for the linked institution login
+    item_id VARCHAR(100) NOT NULL,
+    -- Encrypted when DATA_ENCRYPTION_KEY is set
+    access_token TEXT NOT NULL,
+    institution_name VARCHAR(100),
+    -- Where the next incremental sync resumes
+    sync_cursor TEXT,
+    last_synced_at TIMESTAMPTZ,
+    -- Error from the latest sync, cleared by the next successful one
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_bank_connections_provider_item UNIQUE (provider, item_id)
+);
+
+CREATE INDEX idx_bank_connections_owner ON bank_connections(owner_id);
+
+CREATE TABLE IF NOT EXISTS bank_account_links (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    connection_id UUID NOT NULL REFERENCES bank_connections(id) ON DELETE CASCADE,
+    external_account_id VARCHAR(100) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    -- Last digits of the account number
+    mask VARCHAR(10),
+    currency CHAR(3),
+    -- Local account imported transactions are booked to
+    account_id UUID REFERENCES accounts(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_bank_account_links_external UNIQUE (connection_id, external_account_id)
+);
+
+CREATE TABLE IF NOT EXISTS bank_imports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    link_id UUID NOT NULL REFERENCES bank_account_links(id) ON DELETE CASCADE,
+    -- The provider's transaction id; reviewed imports are kept so a re-sync
+    -- doesn't offer them again
+    external_id VARCHAR(100) NOT NULL,
+    amount NUMERIC(12,2) NOT NULL,
+    transaction_type VARCHAR(10) NOT NULL,
+    transaction_date DATE NOT NULL,
+    -- Merchant or statement text; encrypted when DATA_ENCRYPTION_KEY is set
+    description TEXT,
+    status VARCHAR(10) NOT NULL DEFAULT 'pending',
+    transaction_id UUID REFERENCES transactions(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_bank_imports_external UNIQUE (link_id, external_id),
+    CONSTRAINT chk_bank_imports_amount CHECK (amount > 0),
+    CONSTRAINT chk_bank_imports_type CHECK (transaction_type IN ('expense', 'income')),
+    CONSTRAINT chk_bank_imports_status CHECK (status IN ('pending', 'imported', 'dismissed'))
+);
+
+CREATE INDEX idx_bank_imports_owner_status ON bank_imports(owner_id, status, transaction_date DESC);
+-- Duplicate checks skip transactions an import already created
+CREATE INDEX idx_bank_imports_transaction ON bank_imports(transaction_id)
+    WHERE transaction_id IS NOT NULL;
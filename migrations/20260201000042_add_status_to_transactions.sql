@@ -0,0 +1,79 @@
+-- Imported transactions can wait for review: a pending transaction touches no
+-- balance, spending or report until it is approved (posted) or rejected.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS status TEXT NOT NULL DEFAULT 'posted'
+        CHECK (status IN ('pending', 'posted', 'rejected'));
+
+CREATE INDEX IF NOT EXISTS idx_transactions_pending ON transactions(category_id)
+    WHERE status = 'pending';
+
+-- Only posted transactions count toward category spending
+CREATE OR REPLACE FUNCTION recompute_category_spent(p_category_ids UUID[])
+RETURNS VOID AS $$
+    UPDATE categories c
+    SET spent_amount = totals.spent
+    FROM (
+        SELECT c2.id, COALESCE(SUM(t.reporting_amount), 0) AS spent
+        FROM categories c2
+        INNER JOIN budgets b ON c2.budget_id = b.id
+        INNER JOIN users u ON u.id = b.owner_id
+        LEFT JOIN transactions t ON t.category_id = c2.id
+            AND t.deleted_at IS NULL
+            AND t.status = 'posted'
+            AND t.reporting_type = 'expense'
+            AND t.transaction_date >= make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+            AND t.transaction_date < (make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+        WHERE c2.id = ANY(p_category_ids)
+        GROUP BY c2.id
+    ) totals
+    WHERE c.id = totals.id
+      AND c.spent_amount <> totals.spent;
+$$ LANGUAGE sql;
+
+CREATE OR REPLACE FUNCTION maintain_category_spent()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP IN ('UPDATE', 'DELETE') AND OLD.deleted_at IS NULL AND OLD.status = 'posted'
+        AND counts_toward_category_spent(OLD.category_id, OLD.reporting_type, OLD.transaction_date) THEN
+        UPDATE categories SET spent_amount = spent_amount - OLD.reporting_amount WHERE id = OLD.category_id;
+    END IF;
+    IF TG_OP IN ('INSERT', 'UPDATE') AND NEW.deleted_at IS NULL AND NEW.status = 'posted'
+        AND counts_toward_category_spent(NEW.category_id, NEW.reporting_type, NEW.transaction_date) THEN
+        UPDATE categories SET spent_amount = spent_amount + NEW.reporting_amount WHERE id = NEW.category_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Approving or rejecting changes what a transaction counts toward
+DROP TRIGGER IF EXISTS trg_transactions_category_spent_update ON transactions;
+CREATE TRIGGER trg_transactions_category_spent_update
+    AFTER UPDATE OF amount, transaction_type, transaction_date, category_id, reversal_of_id, deleted_at, status ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION maintain_category_spent();
+
+DROP TRIGGER IF EXISTS trg_transactions_summary_dirty_update ON transactions;
+CREATE TRIGGER trg_transactions_summary_dirty_update
+    AFTER UPDATE OF amount, transaction_type, transaction_date, category_id, account_id, reversal_of_id, deleted_at, status ON transactions
+    FOR EACH ROW
+    EXECUTE FUNCTION mark_summary_dirty_on_transaction_change();
+
+-- Balance integrity checks only see posted transactions, the ones applied to balances
+CREATE OR REPLACE FUNCTION account_transaction_net(p_account_id UUID)
+RETURNS NUMERIC AS $$
+    SELECT COALESCE(SUM(
+        CASE
+            WHEN t.account_id = p_account_id AND t.transaction_type = 'income' THEN t.amount
+            WHEN t.account_id = p_account_id THEN -t.amount
+            ELSE 0
+        END
+        + CASE
+            WHEN t.destination_account_id = p_account_id AND t.transaction_type = 'transfer' THEN t.amount
+            ELSE 0
+        END
+    ), 0)
+    FROM transactions t
+    WHERE (t.account_id = p_account_id OR t.destination_account_id = p_account_id)
+      AND t.deleted_at IS NULL
+      AND t.status = 'posted';
+$$ LANGUAGE sql STABLE;
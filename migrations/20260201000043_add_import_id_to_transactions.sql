@@ -0,0 +1,9 @@
+-- Transactions imported from a bank statement file remember which statement
+-- entry they came from, so importing an overlapping file again skips them.
+-- Rejected and trashed imports keep their id and are skipped too.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS import_id TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_account_import_id
+    ON transactions(account_id, import_id)
+    WHERE import_id IS NOT NULL;
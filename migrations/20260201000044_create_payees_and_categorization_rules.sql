@@ -0,0 +1,50 @@
+-- Payees tracked separately from descriptions, and rules that assign a
+-- category and payee to transactions whose description matches a pattern
+
+CREATE TABLE IF NOT EXISTS payees (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_payees_name_length CHECK (LENGTH(TRIM(name)) >= 1)
+);
+
+CREATE UNIQUE INDEX idx_payees_owner_name ON payees(owner_id, LOWER(name));
+
+CREATE TRIGGER trg_payees_updated_at
+    BEFORE UPDATE ON payees
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+ALTER TABLE transactions
+    ADD COLUMN payee_id UUID REFERENCES payees(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_transactions_payee ON transactions(payee_id) WHERE payee_id IS NOT NULL;
+
+-- Descriptions are encrypted at rest, so patterns are matched in the
+-- application. Categories belong to a monthly budget, so a rule names the
+-- category and is resolved within the budget of each transaction.
+CREATE TABLE IF NOT EXISTS categorization_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    pattern VARCHAR(100) NOT NULL,
+    category_name VARCHAR(50),
+    -- Rules assigning a payee are removed along with it
+    payee_id UUID REFERENCES payees(id) ON DELETE CASCADE,
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_categorization_rules_pattern_length CHECK (LENGTH(TRIM(pattern)) >= 1),
+    CONSTRAINT chk_categorization_rules_action
+        CHECK (category_name IS NOT NULL OR payee_id IS NOT NULL)
+);
+
+CREATE INDEX idx_categorization_rules_owner ON categorization_rules(owner_id);
+
+CREATE TRIGGER trg_categorization_rules_updated_at
+    BEFORE UPDATE ON categorization_rules
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
@@ -0,0 +1,34 @@
+-- Transfers can lose a fee on the way out and arrive as a different amount,
+-- e.g. when the accounts are in different currencies. The source is charged
+-- amount + fee_amount; the destination receives destination_amount, or
+-- amount when it is not set.
+ALTER TABLE transactions
+    ADD COLUMN IF NOT EXISTS fee_amount NUMERIC(12,2),
+    ADD COLUMN IF NOT EXISTS destination_amount NUMERIC(12,2);
+
+ALTER TABLE transactions
+    ADD CONSTRAINT chk_transactions_transfer_amounts CHECK (
+        (fee_amount IS NULL OR fee_amount > 0)
+        AND (destination_amount IS NULL OR destination_amount > 0)
+        AND (transaction_type = 'transfer' OR (fee_amount IS NULL AND destination_amount IS NULL))
+    );
+
+CREATE OR REPLACE FUNCTION account_transaction_net(p_account_id UUID)
+RETURNS NUMERIC AS $$
+    SELECT COALESCE(SUM(
+        CASE
+            WHEN t.account_id = p_account_id AND t.transaction_type = 'income' THEN t.amount
+            WHEN t.account_id = p_account_id THEN -(t.amount + COALESCE(t.fee_amount, 0))
+            ELSE 0
+        END
+        + CASE
+            WHEN t.destination_account_id = p_account_id AND t.transaction_type = 'transfer'
+                THEN COALESCE(t.destination_amount, t.amount)
+            ELSE 0
+        END
+    ), 0)
+    FROM transactions t
+    WHERE (t.account_id = p_account_id OR t.destination_account_id = p_account_id)
+      AND t.deleted_at IS NULL
+      AND t.status = 'posted';
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,4 @@
+-- UI and behavior preferences (first day of week, date format, default
+-- account, ...), validated by the application; missing keys read as defaults
+ALTER TABLE user_settings
+    ADD COLUMN IF NOT EXISTS preferences JSONB NOT NULL DEFAULT '{}'::jsonb;
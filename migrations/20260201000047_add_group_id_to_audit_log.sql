@@ -0,0 +1,6 @@
+-- Entries written by one bulk operation point at the entry that groups them,
+-- so undo reverses the operation as a whole rather than row by row
+ALTER TABLE audit_log
+    ADD COLUMN IF NOT EXISTS group_id UUID REFERENCES audit_log(id) ON DELETE CASCADE;
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_group ON audit_log(group_id) WHERE group_id IS NOT NULL;
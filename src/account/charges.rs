@@ -0,0 +1,194 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::models::AccountType;
+use crate::bill::models::{due_date_in, next_due_on_or_after};
+use crate::transaction::models::TransactionType;
+
+fn validate_apr(apr: &Decimal) -> Result<(), ValidationError> {
+    if *apr < Decimal::ZERO || *apr > Decimal::from(100) {
+        return Err(ValidationError::new("apr_out_of_range"));
+    }
+    Ok(())
+}
+
+fn validate_fee(fee: &Decimal) -> Result<(), ValidationError> {
+    if *fee < Decimal::ZERO {
+        return Err(ValidationError::new("fee_must_not_be_negative"));
+    }
+    Ok(())
+}
+
+fn default_cycle_day() -> i16 {
+    31
+}
+
+/// Request body for setting an account's interest rate and monthly fee
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateChargesDto {
+    /// Annual percentage rate (0-100): earned on a savings balance, charged
+    /// on what is owed on a credit account
+    #[validate(custom(function = "validate_apr", message = "APR must be between 0 and 100"))]
+    #[serde(default)]
+    #[schema(example = 4.25)]
+    pub apr: Decimal,
+
+    /// Fee charged at the end of every cycle
+    #[validate(custom(function = "validate_fee", message = "Fee must not be negative"))]
+    #[serde(default)]
+    #[schema(example = 5.00)]
+    pub monthly_fee: Decimal,
+
+    /// Day of the month cycles end (1-31, defaults to 31: calendar months)
+    #[validate(range(min = 1, max = 31, message = "Cycle day must be between 1 and 31"))]
+    #[serde(default = "default_cycle_day")]
+    #[schema(example = 15)]
+    pub cycle_day: i16,
+
+    /// Post each cycle's interest and fee as transactions when it ends.
+    /// Only cycles ending from today on are posted.
+    #[serde(default)]
+    pub auto_post: bool,
+
+    /// Category, by name in the current month's budget, for posted charges
+    /// (required for autoposting)
+    #[validate(length(min = 1, max = 50, message = "Category name must be 1-50 characters"))]
+    #[schema(example = "Bank charges")]
+    pub category_name: Option<String>,
+}
+
+impl UpdateChargesDto {
+    /// Autoposting needs a category to book into
+    pub fn validate_auto_post(&self) -> Result<(), ValidationError> {
+        if self.auto_post && self.category_name.is_none() {
+            return Err(ValidationError::new("auto_post_incomplete"));
+        }
+        Ok(())
+    }
+}
+
+/// An account's interest rate and monthly fee
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChargesConfig {
+    pub account_id: Uuid,
+    #[schema(example = 4.25)]
+    pub apr: Decimal,
+    #[schema(example = 5.00)]
+    pub monthly_fee: Decimal,
+    #[schema(example = 31)]
+    pub cycle_day: i16,
+    pub auto_post: bool,
+    pub category_name: Option<String>,
+    /// End of the last cycle posted as transactions (or skipped when
+    /// autoposting was turned on after it ended)
+    pub last_posted_cycle: Option<NaiveDate>,
+}
+
+/// An account due for charge posting, with its owner and the owner's current date
+#[derive(Debug, Clone, FromRow)]
+pub struct DueCharges {
+    #[sqlx(flatten)]
+    pub config: ChargesConfig,
+    pub owner_id: Uuid,
+    pub account_type: String,
+    pub today: NaiveDate,
+}
+
+/// End of the last cycle that ended before `date`
+pub fn last_cycle_end_before(date: NaiveDate, cycle_day: i16) -> NaiveDate {
+    let this_month = due_date_in(date.year(), date.month(), cycle_day);
+    if this_month < date {
+        return this_month;
+    }
+    let previous = date.with_day(1).expect("valid date") - Months::new(1);
+    due_date_in(previous.year(), previous.month(), cycle_day)
+}
+
+/// End of the cycle after the one ending on `last_end`
+pub fn next_cycle_end(last_end: NaiveDate, cycle_day: i16) -> NaiveDate {
+    next_due_on_or_after(last_end.succ_opt().expect("valid date"), cycle_day)
+}
+
+/// Interest for a cycle of `days` on `balance`, as the transaction posting it:
+/// income on a positive savings balance, an expense on a negative credit
+/// balance. `None` when no interest is due.
+pub fn cycle_interest(
+    account_type: AccountType,
+    balance: Decimal,
+    apr: Decimal,
+    days: i64,
+) -> Option<(TransactionType, Decimal)> {
+    let (transaction_type, principal) = match account_type {
+        AccountType::Savings if balance > Decimal::ZERO => (TransactionType::Income, balance),
+        AccountType::Credit if balance < Decimal::ZERO => (TransactionType::Expense, -balance),
+        _ => return None,
+    };
+    let interest = (principal * apr / Decimal::from(100) * Decimal::from(days)
+        / Decimal::from(365))
+    .round_dp(2);
+
+    (interest > Decimal::ZERO).then_some((transaction_type, interest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_cycle_ends() {
+        assert_eq!(
+            last_cycle_end_before(date(2026, 3, 10), 31),
+            date(2026, 2, 28)
+        );
+        assert_eq!(
+            last_cycle_end_before(date(2026, 3, 16), 15),
+            date(2026, 3, 15)
+        );
+        // A cycle ending today hasn't been posted yet
+        assert_eq!(
+            last_cycle_end_before(date(2026, 3, 15), 15),
+            date(2026, 2, 15)
+        );
+
+        assert_eq!(next_cycle_end(date(2026, 1, 31), 31), date(2026, 2, 28));
+        assert_eq!(next_cycle_end(date(2026, 2, 28), 31), date(2026, 3, 31));
+        assert_eq!(next_cycle_end(date(2026, 2, 15), 15), date(2026, 3, 15));
+    }
+
+    #[test]
+    fn test_cycle_interest() {
+        let apr = Decimal::from(12);
+        assert_eq!(
+            cycle_interest(AccountType::Savings, Decimal::from(10_000), apr, 31),
+            Some((TransactionType::Income, Decimal::new(10192, 2)))
+        );
+        assert_eq!(
+            cycle_interest(AccountType::Credit, Decimal::from(-1_000), apr, 30),
+            Some((TransactionType::Expense, Decimal::new(986, 2)))
+        );
+        // Nothing owed, or nothing saved
+        assert_eq!(
+            cycle_interest(AccountType::Credit, Decimal::from(50), apr, 30),
+            None
+        );
+        assert_eq!(
+            cycle_interest(AccountType::Savings, Decimal::ZERO, apr, 30),
+            None
+        );
+        assert_eq!(
+            cycle_interest(AccountType::Savings, Decimal::from(100), Decimal::ZERO, 30),
+            None
+        );
+    }
+}
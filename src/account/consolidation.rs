@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::models::CurrencySummary;
+use super::redenomination::convert;
+
+/// One currency's totals converted into the user's default currency
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyConversion {
+    /// Rate from this currency into the default currency
+    #[schema(example = 1.0870)]
+    pub rate: Decimal,
+    /// Date of the stored rate used
+    pub rate_date: NaiveDate,
+    #[schema(example = 5435.00)]
+    pub total_savings: Decimal,
+    #[schema(example = 1087.00)]
+    pub total_spending: Decimal,
+    #[schema(example = 6522.00)]
+    pub net_worth: Decimal,
+}
+
+/// Totals over every currency that could be converted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsolidatedTotals {
+    pub total_savings: Decimal,
+    pub total_spending: Decimal,
+    /// Currencies with no stored rate, left out of the totals
+    pub unconverted_currencies: Vec<String>,
+}
+
+/// Convert each currency's totals at its rate (`None` when no rate is stored)
+/// and add them up, rounding to the default currency's decimal places.
+/// Fills in each summary's `conversion`.
+pub fn consolidate(
+    summaries: &mut [CurrencySummary],
+    rates: &[Option<(Decimal, NaiveDate)>],
+    decimal_places: u32,
+) -> ConsolidatedTotals {
+    let mut totals = ConsolidatedTotals {
+        total_savings: Decimal::ZERO,
+        total_spending: Decimal::ZERO,
+        unconverted_currencies: Vec::new(),
+    };
+
+    for (summary, rate) in summaries.iter_mut().zip(rates) {
+        let Some((rate, rate_date)) = *rate else {
+            summary.conversion = None;
+            totals.unconverted_currencies.push(summary.currency.clone());
+            continue;
+        };
+        let total_savings = convert(summary.total_savings, rate, decimal_places);
+        let total_spending = convert(summary.total_spending, rate, decimal_places);
+        totals.total_savings += total_savings;
+        totals.total_spending += total_spending;
+        summary.conversion = Some(CurrencyConversion {
+            rate: rate.normalize(),
+            rate_date,
+            total_savings,
+            total_spending,
+            net_worth: total_savings + total_spending,
+        });
+    }
+
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(currency: &str, savings: i64, spending: i64) -> CurrencySummary {
+        CurrencySummary {
+            currency: currency.to_string(),
+            total_savings: Decimal::from(savings),
+            total_spending: Decimal::from(spending),
+            net_worth: Decimal::from(savings + spending),
+            accounts_count: 1,
+            conversion: None,
+        }
+    }
+
+    #[test]
+    fn test_consolidate_converts_and_skips_currencies_without_rates() {
+        let date = NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+        let mut summaries = vec![
+            summary("EUR", 1000, 100),
+            summary("USD", 500, 50),
+            summary("XYZ", 999, 0),
+        ];
+        let rates = [
+            Some((Decimal::new(10870, 4), date)),
+            Some((Decimal::ONE, date)),
+            None,
+        ];
+
+        let totals = consolidate(&mut summaries, &rates, 2);
+
+        assert_eq!(totals.total_savings, Decimal::new(158700, 2));
+        assert_eq!(totals.total_spending, Decimal::new(15870, 2));
+        assert_eq!(totals.unconverted_currencies, vec!["XYZ".to_string()]);
+        let eur = summaries[0].conversion.as_ref().unwrap();
+        assert_eq!(eur.net_worth, Decimal::new(119570, 2));
+        assert_eq!(eur.rate_date, date);
+        assert!(summaries[2].conversion.is_none());
+    }
+}
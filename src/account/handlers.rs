@@ -1,15 +1,25 @@
-use actix_web::{delete, get, patch, post, web, HttpResponse};
+use actix_web::http::header::{self, ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{delete, get, patch, post, put, web, HttpResponse};
 use sqlx::PgPool;
 use validator::Validate;
 
+use crate::auth::scopes::Scope;
+use crate::budget::models::MonthBaseQuery;
+use crate::cache::{CacheKey, ResponseCache};
 use crate::errors::{AppError, ErrorResponse};
-use crate::extractors::AuthenticatedUser;
+use crate::extractors::{etag, AuthenticatedUser, IfMatch};
 
+use super::charges::{ChargesConfig, UpdateChargesDto};
+use super::loan::{AmortizationResponse, LoanTerms, UpdateLoanDto};
 use super::models::{
     AccountIdPath, AccountResponse, AccountTypePath, AccountsListResponse, AccountsSummaryResponse,
-    CreateAccountDto, DeleteResponse, UpdateAccountDto, UpdateBalanceDto,
+    BalanceCheck, BalanceRecomputeSummary, CreateAccountDto, DeleteResponse, RecomputeBalanceQuery,
+    ReorderAccountsDto, UpdateAccountDto, UpdateBalanceDto,
 };
+use super::redenomination::{ChangeCurrencyDto, CurrencyChange};
+use super::rewards::{RewardsConfig, RewardsQuery, RewardsReport, UpdateRewardsDto};
 use super::service::AccountService;
+use super::statement::{Statement, StatementFormat, StatementPath, StatementQuery};
 
 /// GET /accounts - List all accounts for the authenticated user
 #[utoipa::path(
@@ -18,7 +28,8 @@ use super::service::AccountService;
     tag = "Accounts",
     responses(
         (status = 200, description = "List of accounts", body = AccountsListResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -27,6 +38,8 @@ pub async fn list_accounts(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
     let accounts = AccountService::list_accounts(pool.get_ref(), auth.user_id).await?;
 
     let response = AccountsListResponse {
@@ -47,7 +60,8 @@ pub async fn list_accounts(
     tag = "Accounts",
     responses(
         (status = 200, description = "Accounts with financial summary", body = AccountsSummaryResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -56,19 +70,22 @@ pub async fn get_accounts_summary(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, AppError> {
-    let (accounts, summary, summaries) =
-        AccountService::get_accounts_summary(pool.get_ref(), auth.user_id).await?;
+    auth.require_scope(Scope::AccountsRead)?;
 
-    let response = AccountsSummaryResponse {
-        accounts: accounts
-            .into_iter()
-            .map(AccountResponse::from_account)
-            .collect(),
-        summary,
-        summaries,
-    };
+    ResponseCache::json(CacheKey::AccountsSummary(auth.user_id), || async {
+        let (accounts, summary, summaries) =
+            AccountService::get_accounts_summary(pool.get_ref(), auth.user_id).await?;
 
-    Ok(HttpResponse::Ok().json(response))
+        Ok(AccountsSummaryResponse {
+            accounts: accounts
+                .into_iter()
+                .map(AccountResponse::from_account)
+                .collect(),
+            summary,
+            summaries,
+        })
+    })
+    .await
 }
 
 /// GET /accounts/type/{type} - Get accounts by type
@@ -79,7 +96,8 @@ pub async fn get_accounts_summary(
     params(AccountTypePath),
     responses(
         (status = 200, description = "Accounts of specified type", body = AccountsListResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -89,6 +107,8 @@ pub async fn get_accounts_by_type(
     auth: AuthenticatedUser,
     path: web::Path<AccountTypePath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
     let accounts =
         AccountService::get_accounts_by_type(pool.get_ref(), auth.user_id, &path.account_type)
             .await?;
@@ -113,7 +133,8 @@ pub async fn get_accounts_by_type(
     responses(
         (status = 200, description = "Account details", body = AccountResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -123,9 +144,14 @@ pub async fn get_account(
     auth: AuthenticatedUser,
     path: web::Path<AccountIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
     let account = AccountService::get_account_by_id(pool.get_ref(), path.id, auth.user_id).await?;
 
-    Ok(HttpResponse::Ok().json(AccountResponse::from_account(account)))
+    let response = AccountResponse::from_account(account);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// POST /accounts - Create a new account
@@ -137,7 +163,8 @@ pub async fn get_account(
     responses(
         (status = 201, description = "Account created", body = AccountResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -147,12 +174,53 @@ pub async fn create_account(
     auth: AuthenticatedUser,
     body: web::Json<CreateAccountDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
 
     let account = AccountService::create_account(pool.get_ref(), auth.user_id, &body).await?;
 
-    Ok(HttpResponse::Created().json(AccountResponse::from_account(account)))
+    let response = AccountResponse::from_account(account);
+    Ok(HttpResponse::Created()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
+}
+
+/// PATCH /accounts/reorder - Save the order accounts are listed in
+#[utoipa::path(
+    patch,
+    path = "/accounts/reorder",
+    tag = "Accounts",
+    request_body = ReorderAccountsDto,
+    responses(
+        (status = 200, description = "Accounts in their new order", body = AccountsListResponse),
+        (status = 400, description = "Validation error or the list doesn't name every account once", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/accounts/reorder")]
+pub async fn reorder_accounts(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<ReorderAccountsDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+    body.validate_unique()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let accounts = AccountService::reorder_accounts(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(AccountsListResponse {
+        count: accounts.len(),
+        accounts: accounts
+            .into_iter()
+            .map(AccountResponse::from_account)
+            .collect(),
+    }))
 }
 
 /// PATCH /accounts/{id} - Update an account (partial update)
@@ -166,7 +234,9 @@ pub async fn create_account(
         (status = 200, description = "Account updated", body = AccountResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -174,18 +244,24 @@ pub async fn create_account(
 pub async fn update_account(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
     path: web::Path<AccountIdPath>,
     body: web::Json<UpdateAccountDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
     body.validate_color_hex()
         .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
     let account =
-        AccountService::update_account(pool.get_ref(), path.id, auth.user_id, &body).await?;
+        AccountService::update_account(pool.get_ref(), path.id, auth.user_id, &body, &if_match)
+            .await?;
 
-    Ok(HttpResponse::Ok().json(AccountResponse::from_account(account)))
+    let response = AccountResponse::from_account(account);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// PATCH /accounts/{id}/balance - Update account balance only
@@ -198,7 +274,9 @@ pub async fn update_account(
     responses(
         (status = 200, description = "Balance updated", body = AccountResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -206,13 +284,130 @@ pub async fn update_account(
 pub async fn update_account_balance(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
     path: web::Path<AccountIdPath>,
     body: web::Json<UpdateBalanceDto>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
     let account =
-        AccountService::update_balance(pool.get_ref(), path.id, auth.user_id, &body).await?;
+        AccountService::update_balance(pool.get_ref(), path.id, auth.user_id, &body, &if_match)
+            .await?;
+
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &[account.id]).await;
 
-    Ok(HttpResponse::Ok().json(AccountResponse::from_account(account)))
+    let response = AccountResponse::from_account(account);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
+}
+
+/// PATCH /accounts/{id}/currency - Move an account to another currency
+#[utoipa::path(
+    patch,
+    path = "/accounts/{id}/currency",
+    tag = "Accounts",
+    params(AccountIdPath),
+    request_body = ChangeCurrencyDto,
+    responses(
+        (status = 200, description = "Preview, or the applied change when confirmed", body = CurrencyChange),
+        (status = 400, description = "Validation error or no stored exchange rate", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/accounts/{id}/currency")]
+pub async fn change_account_currency(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    if_match: IfMatch,
+    path: web::Path<AccountIdPath>,
+    body: web::Json<ChangeCurrencyDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+    if body.convert_transactions {
+        auth.require_scope(Scope::TransactionsWrite)?;
+    }
+
+    body.validate()?;
+
+    let change =
+        AccountService::change_currency(pool.get_ref(), path.id, auth.user_id, &body, &if_match)
+            .await?;
+    if change.applied {
+        AccountService::publish_balances(pool.get_ref(), auth.user_id, &[change.account_id]).await;
+    }
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&change.updated_at)))
+        .json(change))
+}
+
+/// POST /accounts/{id}/recompute-balance - Check the balance against transaction history
+#[utoipa::path(
+    post,
+    path = "/accounts/{id}/recompute-balance",
+    tag = "Accounts",
+    params(AccountIdPath, RecomputeBalanceQuery),
+    responses(
+        (status = 200, description = "Stored and recomputed balance, with drift", body = BalanceCheck),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/accounts/{id}/recompute-balance")]
+pub async fn recompute_account_balance(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    query: web::Query<RecomputeBalanceQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(if query.fix {
+        Scope::AccountsWrite
+    } else {
+        Scope::AccountsRead
+    })?;
+
+    let check =
+        AccountService::recompute_balance(pool.get_ref(), path.id, Some(auth.user_id), query.fix)
+            .await?;
+    if check.fixed {
+        AccountService::publish_balances(pool.get_ref(), auth.user_id, &[check.account_id]).await;
+    }
+
+    Ok(HttpResponse::Ok().json(check))
+}
+
+/// POST /admin/accounts/recompute-balances - Check every account's balance against history
+#[utoipa::path(
+    post,
+    path = "/admin/accounts/recompute-balances",
+    tag = "Admin",
+    params(RecomputeBalanceQuery),
+    responses(
+        (status = 200, description = "Accounts checked and those that drifted", body = BalanceRecomputeSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/accounts/recompute-balances")]
+pub async fn recompute_all_balances(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<RecomputeBalanceQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = AccountService::recompute_all_balances(pool.get_ref(), None, query.fix).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
 }
 
 /// DELETE /accounts/{id} - Delete an account
@@ -224,7 +419,8 @@ pub async fn update_account_balance(
     responses(
         (status = 200, description = "Account deleted", body = DeleteResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -234,6 +430,8 @@ pub async fn delete_account(
     auth: AuthenticatedUser,
     path: web::Path<AccountIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
     AccountService::delete_account(pool.get_ref(), path.id, auth.user_id).await?;
 
     Ok(HttpResponse::Ok().json(DeleteResponse {
@@ -241,3 +439,286 @@ pub async fn delete_account(
         id: path.id,
     }))
 }
+
+/// GET /accounts/{id}/rewards - Estimated cashback per statement period
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/rewards",
+    tag = "Accounts",
+    params(AccountIdPath, RewardsQuery),
+    responses(
+        (status = 200, description = "Cashback settings and estimated rewards per statement period", body = RewardsReport),
+        (status = 400, description = "Validation error or not a credit account", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/rewards")]
+pub async fn get_account_rewards(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    query: web::Query<RewardsQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
+    query.validate()?;
+
+    let report =
+        AccountService::rewards_report(pool.get_ref(), path.id, auth.user_id, query.periods)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// PUT /accounts/{id}/rewards - Set a credit account's cashback rates
+#[utoipa::path(
+    put,
+    path = "/accounts/{id}/rewards",
+    tag = "Accounts",
+    params(AccountIdPath),
+    request_body = UpdateRewardsDto,
+    responses(
+        (status = 200, description = "Cashback settings updated", body = RewardsConfig),
+        (status = 400, description = "Validation error or not a credit account", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/accounts/{id}/rewards")]
+pub async fn update_account_rewards(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    body: web::Json<UpdateRewardsDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+    body.validate_categories().map_err(|_| {
+        AppError::ValidationError("Category rates must name distinct categories".to_string())
+    })?;
+
+    let config =
+        AccountService::update_rewards_config(pool.get_ref(), path.id, auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(config))
+}
+
+/// GET /accounts/{id}/amortization - Payment schedule and payoff position of a loan
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/amortization",
+    tag = "Accounts",
+    params(AccountIdPath),
+    responses(
+        (status = 200, description = "Loan terms, full schedule and remaining payoff", body = AmortizationResponse),
+        (status = 400, description = "Not a loan account", body = ErrorResponse),
+        (status = 404, description = "Account not found or loan terms not set", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/amortization")]
+pub async fn get_account_amortization(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
+    let amortization = AccountService::amortization(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(amortization))
+}
+
+/// PUT /accounts/{id}/loan - Set a loan account's terms
+#[utoipa::path(
+    put,
+    path = "/accounts/{id}/loan",
+    tag = "Accounts",
+    params(AccountIdPath),
+    request_body = UpdateLoanDto,
+    responses(
+        (status = 200, description = "Loan terms saved", body = LoanTerms),
+        (status = 400, description = "Validation error or not a loan account", body = ErrorResponse),
+        (status = 404, description = "Account or payment account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/accounts/{id}/loan")]
+pub async fn update_account_loan(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    body: web::Json<UpdateLoanDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+    body.validate_auto_post().map_err(|_| {
+        AppError::ValidationError(
+            "Autoposting requires paymentAccountId and categoryName".to_string(),
+        )
+    })?;
+
+    let terms =
+        AccountService::set_loan_terms(pool.get_ref(), path.id, auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(terms))
+}
+
+/// GET /accounts/{id}/charges - Interest rate and monthly fee of an account
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/charges",
+    tag = "Accounts",
+    params(AccountIdPath),
+    responses(
+        (status = 200, description = "Interest and fee settings", body = ChargesConfig),
+        (status = 400, description = "Not a credit or savings account", body = ErrorResponse),
+        (status = 404, description = "Account not found or settings not set", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/charges")]
+pub async fn get_account_charges(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
+    let config = AccountService::get_charges(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(config))
+}
+
+/// PUT /accounts/{id}/charges - Set an account's interest rate and monthly fee
+#[utoipa::path(
+    put,
+    path = "/accounts/{id}/charges",
+    tag = "Accounts",
+    params(AccountIdPath),
+    request_body = UpdateChargesDto,
+    responses(
+        (status = 200, description = "Interest and fee settings saved", body = ChargesConfig),
+        (status = 400, description = "Validation error or not a credit or savings account", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/accounts/{id}/charges")]
+pub async fn update_account_charges(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    body: web::Json<UpdateChargesDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+    body.validate_auto_post()
+        .map_err(|_| AppError::ValidationError("Autoposting requires categoryName".to_string()))?;
+
+    let config = AccountService::set_charges(pool.get_ref(), path.id, auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(config))
+}
+
+/// GET /accounts/{id}/statements/{year}/{month} - Monthly statement with running balance
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/statements/{year}/{month}",
+    tag = "Accounts",
+    params(StatementPath, StatementQuery, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Opening balance, the month's transactions with running balance, and closing balance; with format=csv or pdf as an attachment",
+            content((Statement = "application/json"), (String = "text/csv"), (Vec<u8> = "application/pdf"))),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/statements/{year}/{month}")]
+pub async fn get_account_statement(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<StatementPath>,
+    query: web::Query<StatementQuery>,
+    month_base: MonthBaseQuery,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    path.validate()?;
+    let month = month_base.to_internal(path.month)?;
+
+    let statement = AccountService::statement(
+        pool.get_ref(),
+        path.id,
+        auth.user_id,
+        path.year,
+        month,
+        path.month,
+    )
+    .await?;
+
+    let (content_type, extension, body) = match query.format {
+        StatementFormat::Json => return Ok(HttpResponse::Ok().json(statement)),
+        StatementFormat::Csv => (
+            "text/csv; charset=utf-8",
+            "csv",
+            statement.to_csv().into_bytes(),
+        ),
+        StatementFormat::Pdf => ("application/pdf", "pdf", statement.to_pdf()),
+    };
+    Ok(HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{}.{}",
+                statement.file_stem(),
+                extension
+            ))],
+        })
+        .body(body))
+}
+
+/// Register the account routes, `/accounts/summary` and the other fixed
+/// paths ahead of `/accounts/{id}`
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_accounts)
+        .service(get_accounts_summary)
+        .service(get_accounts_by_type)
+        .service(reorder_accounts)
+        .service(get_account)
+        .service(create_account)
+        .service(update_account_balance)
+        .service(change_account_currency)
+        .service(recompute_account_balance)
+        .service(recompute_all_balances)
+        .service(update_account)
+        .service(delete_account)
+        .service(get_account_rewards)
+        .service(update_account_rewards)
+        .service(get_account_amortization)
+        .service(update_account_loan)
+        .service(get_account_charges)
+        .service(update_account_charges)
+        .service(get_account_statement);
+}
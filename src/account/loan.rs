@@ -0,0 +1,296 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::bill::models::due_date_in;
+
+fn validate_positive_principal(principal: &Decimal) -> Result<(), ValidationError> {
+    if *principal <= Decimal::ZERO {
+        return Err(ValidationError::new("principal_must_be_positive"));
+    }
+    Ok(())
+}
+
+fn validate_apr(apr: &Decimal) -> Result<(), ValidationError> {
+    if *apr < Decimal::ZERO || *apr > Decimal::from(100) {
+        return Err(ValidationError::new("apr_out_of_range"));
+    }
+    Ok(())
+}
+
+/// Request body for setting a loan account's terms
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateLoanDto {
+    /// Amount borrowed
+    #[validate(custom(
+        function = "validate_positive_principal",
+        message = "Principal must be positive"
+    ))]
+    #[schema(example = 20000.00)]
+    pub principal: Decimal,
+
+    /// Annual percentage rate (0-100)
+    #[validate(custom(function = "validate_apr", message = "APR must be between 0 and 100"))]
+    #[schema(example = 6.5)]
+    pub apr: Decimal,
+
+    /// Number of monthly payments (1-600)
+    #[validate(range(min = 1, max = 600, message = "Term must be between 1 and 600 months"))]
+    #[schema(example = 60)]
+    pub term_months: i16,
+
+    /// Due date of the first payment; later payments fall on the same day of the month
+    pub first_payment_date: NaiveDate,
+
+    /// Post each payment's principal and interest as transactions when it comes due.
+    /// Only payments due from today on are posted.
+    #[serde(default)]
+    pub auto_post: bool,
+
+    /// Account the payments are made from (required for autoposting)
+    pub payment_account_id: Option<Uuid>,
+
+    /// Category, by name in the current month's budget, for posted payments
+    /// (required for autoposting)
+    #[validate(length(min = 1, max = 50, message = "Category name must be 1-50 characters"))]
+    #[schema(example = "Car loan")]
+    pub category_name: Option<String>,
+}
+
+impl UpdateLoanDto {
+    /// Autoposting needs somewhere to pay from and a category to book into
+    pub fn validate_auto_post(&self) -> Result<(), ValidationError> {
+        if self.auto_post && (self.payment_account_id.is_none() || self.category_name.is_none()) {
+            return Err(ValidationError::new("auto_post_incomplete"));
+        }
+        Ok(())
+    }
+}
+
+/// A loan account's terms
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanTerms {
+    pub account_id: Uuid,
+    #[schema(example = 20000.00)]
+    pub principal: Decimal,
+    #[schema(example = 6.5)]
+    pub apr: Decimal,
+    #[schema(example = 60)]
+    pub term_months: i16,
+    pub first_payment_date: NaiveDate,
+    pub auto_post: bool,
+    pub payment_account_id: Option<Uuid>,
+    pub category_name: Option<String>,
+    /// Payments posted as transactions so far (or skipped when autoposting was
+    /// turned on after they came due)
+    #[schema(example = 0)]
+    pub payments_posted: i16,
+}
+
+/// A loan due for autoposting, with its owner and the owner's current date
+#[derive(Debug, Clone, FromRow)]
+pub struct DueLoan {
+    #[sqlx(flatten)]
+    pub terms: LoanTerms,
+    pub owner_id: Uuid,
+    pub today: NaiveDate,
+}
+
+/// One payment of the schedule
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AmortizationRow {
+    /// Payment number, from 1
+    #[schema(example = 1)]
+    pub number: i16,
+    pub due_date: NaiveDate,
+    #[schema(example = 391.32)]
+    pub payment: Decimal,
+    #[schema(example = 282.99)]
+    pub principal: Decimal,
+    #[schema(example = 108.33)]
+    pub interest: Decimal,
+    /// Principal left after this payment
+    #[schema(example = 19717.01)]
+    pub remaining_balance: Decimal,
+}
+
+/// Where the loan stands today
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoffSummary {
+    /// Payments due on or before today
+    #[schema(example = 12)]
+    pub payments_made: i16,
+    #[schema(example = 48)]
+    pub payments_remaining: i16,
+    /// Principal still owed
+    #[schema(example = 16325.40)]
+    pub remaining_principal: Decimal,
+    /// Interest still to be paid on schedule
+    #[schema(example = 2458.96)]
+    pub remaining_interest: Decimal,
+    /// Due date of the next payment, if any remain
+    pub next_payment_date: Option<NaiveDate>,
+    /// Due date of the last payment
+    pub payoff_date: NaiveDate,
+}
+
+/// Response for GET /accounts/{id}/amortization
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AmortizationResponse {
+    #[serde(flatten)]
+    pub terms: LoanTerms,
+    /// Regular monthly payment (the last one may differ by rounding)
+    #[schema(example = 391.32)]
+    pub monthly_payment: Decimal,
+    #[schema(example = 3479.20)]
+    pub total_interest: Decimal,
+    #[schema(example = 23479.20)]
+    pub total_paid: Decimal,
+    pub payoff: PayoffSummary,
+    pub schedule: Vec<AmortizationRow>,
+}
+
+/// Level monthly payment that repays `principal` over `term_months` at `apr`
+pub fn monthly_payment(principal: Decimal, apr: Decimal, term_months: i16) -> Decimal {
+    let rate = apr / Decimal::from(1200);
+    if rate.is_zero() {
+        return (principal / Decimal::from(term_months)).round_dp(2);
+    }
+    let growth = (0..term_months).fold(Decimal::ONE, |acc, _| acc * (Decimal::ONE + rate));
+    (principal * rate * growth / (growth - Decimal::ONE)).round_dp(2)
+}
+
+/// Full payment schedule. Interest is charged on the remaining balance each
+/// month and rounded to cents; the last payment clears what is left.
+pub fn amortization_schedule(terms: &LoanTerms) -> (Decimal, Vec<AmortizationRow>) {
+    let payment = monthly_payment(terms.principal, terms.apr, terms.term_months);
+    let rate = terms.apr / Decimal::from(1200);
+    let due_day = terms.first_payment_date.day() as i16;
+
+    let mut balance = terms.principal;
+    let mut schedule = Vec::with_capacity(terms.term_months as usize);
+    for number in 1..=terms.term_months {
+        if balance <= Decimal::ZERO {
+            break;
+        }
+        let month = terms.first_payment_date.with_day(1).expect("valid date")
+            + Months::new(number as u32 - 1);
+        let interest = (balance * rate).round_dp(2);
+        let principal = if number == terms.term_months {
+            balance
+        } else {
+            (payment - interest).min(balance)
+        };
+        balance -= principal;
+        schedule.push(AmortizationRow {
+            number,
+            due_date: due_date_in(month.year(), month.month(), due_day),
+            payment: principal + interest,
+            principal,
+            interest,
+            remaining_balance: balance,
+        });
+    }
+
+    (payment, schedule)
+}
+
+impl AmortizationResponse {
+    pub fn build(terms: LoanTerms, today: NaiveDate) -> Self {
+        let (monthly_payment, schedule) = amortization_schedule(&terms);
+        let (made, remaining): (Vec<&AmortizationRow>, Vec<&AmortizationRow>) =
+            schedule.iter().partition(|row| row.due_date <= today);
+
+        let total_interest: Decimal = schedule.iter().map(|row| row.interest).sum();
+        let payoff = PayoffSummary {
+            payments_made: made.len() as i16,
+            payments_remaining: remaining.len() as i16,
+            remaining_principal: made
+                .last()
+                .map_or(terms.principal, |row| row.remaining_balance),
+            remaining_interest: remaining.iter().map(|row| row.interest).sum(),
+            next_payment_date: remaining.first().map(|row| row.due_date),
+            payoff_date: schedule
+                .last()
+                .map_or(terms.first_payment_date, |row| row.due_date),
+        };
+
+        Self {
+            total_paid: terms.principal + total_interest,
+            terms,
+            monthly_payment,
+            total_interest,
+            payoff,
+            schedule,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(principal: i64, apr: Decimal, term_months: i16) -> LoanTerms {
+        LoanTerms {
+            account_id: Uuid::new_v4(),
+            principal: Decimal::from(principal),
+            apr,
+            term_months,
+            first_payment_date: NaiveDate::from_ymd_opt(2026, 1, 31).unwrap(),
+            auto_post: false,
+            payment_account_id: None,
+            category_name: None,
+            payments_posted: 0,
+        }
+    }
+
+    #[test]
+    fn test_amortization_schedule() {
+        let loan = terms(1000, Decimal::from(12), 12);
+        let (payment, schedule) = amortization_schedule(&loan);
+        assert_eq!(payment, Decimal::new(8885, 2));
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[0].interest, Decimal::from(10));
+        assert_eq!(schedule[0].principal, Decimal::new(7885, 2));
+        // Month-end due dates clamp in short months
+        assert_eq!(
+            schedule[1].due_date,
+            NaiveDate::from_ymd_opt(2026, 2, 28).unwrap()
+        );
+        assert_eq!(
+            schedule.iter().map(|row| row.principal).sum::<Decimal>(),
+            Decimal::from(1000)
+        );
+        assert_eq!(schedule[11].remaining_balance, Decimal::ZERO);
+
+        let response =
+            AmortizationResponse::build(loan, NaiveDate::from_ymd_opt(2026, 3, 1).unwrap());
+        assert_eq!(response.payoff.payments_made, 2);
+        assert_eq!(
+            response.payoff.remaining_principal,
+            schedule[1].remaining_balance
+        );
+        assert_eq!(
+            response.payoff.next_payment_date,
+            NaiveDate::from_ymd_opt(2026, 3, 31)
+        );
+        assert_eq!(
+            response.total_paid,
+            schedule.iter().map(|row| row.payment).sum::<Decimal>()
+        );
+
+        // Interest-free loans split evenly
+        let (payment, schedule) = amortization_schedule(&terms(100, Decimal::ZERO, 3));
+        assert_eq!(payment, Decimal::new(3333, 2));
+        assert_eq!(schedule[2].principal, Decimal::new(3334, 2));
+    }
+}
@@ -1,5 +1,11 @@
+pub mod charges;
+pub mod consolidation;
 pub mod handlers;
+pub mod loan;
 pub mod models;
+pub mod redenomination;
+pub mod rewards;
 pub mod service;
+pub mod statement;
 
 pub use handlers::*;
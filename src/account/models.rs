@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -6,6 +6,10 @@ use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
+use super::consolidation::CurrencyConversion;
+use crate::crypto::decrypt_field;
+use crate::errors::AppError;
+
 /// Account type enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -16,6 +20,10 @@ pub enum AccountType {
     Savings,
     /// Credit card account
     Credit,
+    /// Brokerage account holding securities
+    Investment,
+    /// Loan account; the balance is what is owed
+    Loan,
 }
 
 impl AccountType {
@@ -24,15 +32,18 @@ impl AccountType {
             AccountType::Checking => "checking",
             AccountType::Savings => "savings",
             AccountType::Credit => "credit",
+            AccountType::Investment => "investment",
+            AccountType::Loan => "loan",
         }
     }
 
-    #[allow(dead_code)]
     pub fn parse(s: &str) -> Option<Self> {
         match s {
             "checking" => Some(AccountType::Checking),
             "savings" => Some(AccountType::Savings),
             "credit" => Some(AccountType::Credit),
+            "investment" => Some(AccountType::Investment),
+            "loan" => Some(AccountType::Loan),
             _ => None,
         }
     }
@@ -52,8 +63,9 @@ fn validate_color_hex(color: &str) -> Result<(), ValidationError> {
     Ok(())
 }
 
-/// Database entity for accounts
-#[derive(Debug, Clone, FromRow)]
+/// Database entity for accounts. Serialized as-loaded (name still
+/// encrypted) into audit snapshots.
+#[derive(Debug, Clone, Serialize, FromRow)]
 pub struct Account {
     pub id: Uuid,
     #[allow(dead_code)]
@@ -64,10 +76,22 @@ pub struct Account {
     pub balance: Decimal,
     pub color_hex: String,
     pub currency: String,
+    pub household_visible: bool,
+    pub allow_negative_balance: bool,
+    pub display_order: i32,
+    pub is_pinned: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Account {
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.name = decrypt_field(&self.name)?;
+        Ok(self)
+    }
+}
+
 /// Account information returned in responses
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -77,7 +101,7 @@ pub struct AccountResponse {
     /// Account name
     #[schema(example = "My Checking")]
     pub name: String,
-    /// Account type (checking, savings, credit)
+    /// Account type (checking, savings, credit, investment, loan)
     #[serde(rename = "type")]
     #[schema(example = "checking")]
     pub account_type: String,
@@ -90,6 +114,15 @@ pub struct AccountResponse {
     /// ISO 4217 currency code
     #[schema(example = "USD")]
     pub currency: String,
+    /// Whether household reports include the account
+    pub household_visible: bool,
+    /// Whether transactions may take a checking or savings account below zero
+    pub allow_negative_balance: bool,
+    /// Position in the user's account list (pinned accounts come first)
+    #[schema(example = 0)]
+    pub display_order: i32,
+    /// Whether the account is pinned to the top of the list
+    pub is_pinned: bool,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -105,6 +138,10 @@ impl AccountResponse {
             balance: account.balance,
             color_hex: account.color_hex,
             currency: account.currency,
+            household_visible: account.household_visible,
+            allow_negative_balance: account.allow_negative_balance,
+            display_order: account.display_order,
+            is_pinned: account.is_pinned,
             created_at: account.created_at,
             updated_at: account.updated_at,
         }
@@ -122,14 +159,17 @@ pub struct AccountsListResponse {
     pub count: usize,
 }
 
-/// Summary statistics for accounts
+/// Summary statistics for accounts, converted into the user's default currency
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountsSummary {
-    /// Total balance in savings accounts
+    /// Currency the totals are in (the user's default currency)
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// Total balance in savings and investment accounts
     #[schema(example = 10000.00)]
     pub total_savings: Decimal,
-    /// Total balance in checking/credit accounts
+    /// Total balance in checking, credit and loan accounts
     #[schema(example = 2500.00)]
     pub total_spending: Decimal,
     /// Net worth (savings + spending)
@@ -138,6 +178,11 @@ pub struct AccountsSummary {
     /// Number of accounts
     #[schema(example = 3)]
     pub accounts_count: i64,
+    /// Date the exchange rates were looked up for (today in the user's timezone)
+    pub rates_as_of: NaiveDate,
+    /// Currencies with no stored exchange rate; their balances are left out
+    /// of the totals
+    pub unconverted_currencies: Vec<String>,
 }
 
 /// Per-currency summary statistics
@@ -147,14 +192,17 @@ pub struct CurrencySummary {
     /// Currency code
     #[schema(example = "USD")]
     pub currency: String,
-    /// Total in savings accounts
+    /// Total in savings and investment accounts
     pub total_savings: Decimal,
-    /// Total in checking/credit accounts
+    /// Total in checking, credit and loan accounts
     pub total_spending: Decimal,
     /// Net worth in this currency
     pub net_worth: Decimal,
     /// Number of accounts
     pub accounts_count: i64,
+    /// The totals in the default currency, with the rate used (absent when
+    /// no rate is stored)
+    pub conversion: Option<CurrencyConversion>,
 }
 
 /// Response for accounts with summary
@@ -169,14 +217,6 @@ pub struct AccountsSummaryResponse {
     pub summaries: Vec<CurrencySummary>,
 }
 
-/// Summary row from database query
-#[derive(Debug, FromRow)]
-pub struct SummaryRow {
-    pub total_savings: Option<Decimal>,
-    pub total_spending: Option<Decimal>,
-    pub accounts_count: Option<i64>,
-}
-
 /// Currency summary row from database query
 #[derive(Debug, FromRow)]
 pub struct CurrencySummaryRow {
@@ -226,6 +266,9 @@ pub struct CreateAccountDto {
     /// Currency code (optional, defaults to user's default_currency)
     #[schema(example = "USD")]
     pub currency: Option<String>,
+
+    /// Allow transactions to take a checking or savings account below zero (defaults to true)
+    pub allow_negative_balance: Option<bool>,
 }
 
 /// Request body for updating an account (PATCH - all fields optional)
@@ -244,6 +287,15 @@ pub struct UpdateAccountDto {
     /// Display color in hex format
     #[schema(example = "#2196F3")]
     pub color_hex: Option<String>,
+
+    /// Include the account in household reports
+    pub household_visible: Option<bool>,
+
+    /// Allow transactions to take a checking or savings account below zero
+    pub allow_negative_balance: Option<bool>,
+
+    /// Pin the account to the top of the list
+    pub is_pinned: Option<bool>,
 }
 
 impl UpdateAccountDto {
@@ -256,6 +308,26 @@ impl UpdateAccountDto {
     }
 }
 
+/// Request body for reordering accounts
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderAccountsDto {
+    /// Every account ID, in the order to list them
+    #[validate(length(min = 1, max = 500, message = "accountIds must list 1-500 accounts"))]
+    pub account_ids: Vec<Uuid>,
+}
+
+impl ReorderAccountsDto {
+    /// Each account may appear only once
+    pub fn validate_unique(&self) -> Result<(), ValidationError> {
+        let mut seen = std::collections::HashSet::new();
+        if !self.account_ids.iter().all(|id| seen.insert(id)) {
+            return Err(ValidationError::new("duplicate_account_id"));
+        }
+        Ok(())
+    }
+}
+
 /// Request body for updating balance only
 #[derive(Debug, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -265,6 +337,64 @@ pub struct UpdateBalanceDto {
     pub balance: Decimal,
 }
 
+/// Payload of `account.balance_changed` events
+#[derive(Debug, FromRow, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChanged {
+    pub account_id: Uuid,
+    pub balance: Decimal,
+    pub currency: String,
+}
+
+/// Query parameters for recomputing balances from transaction history
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct RecomputeBalanceQuery {
+    /// Overwrite the stored balance with the recomputed one when they differ
+    #[serde(default)]
+    pub fix: bool,
+}
+
+/// Stored balance checked against opening balance plus transaction history
+#[derive(Debug, Clone, FromRow, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceCheck {
+    /// Account UUID
+    pub account_id: Uuid,
+    /// Account owner
+    #[serde(skip)]
+    pub owner_id: Uuid,
+    /// Balance stored on the account before any fix
+    #[schema(example = 1250.00)]
+    pub stored_balance: Decimal,
+    /// Initial balance plus manual adjustments
+    #[schema(example = 1000.00)]
+    pub opening_balance: Decimal,
+    /// Net effect of all transactions on the account
+    #[schema(example = 200.00)]
+    pub transaction_net: Decimal,
+    /// Opening balance plus transaction net
+    #[schema(example = 1200.00)]
+    pub computed_balance: Decimal,
+    /// Stored minus computed balance
+    #[schema(example = 50.00)]
+    pub drift: Decimal,
+    /// Whether the stored balance was overwritten
+    #[sqlx(default)]
+    pub fixed: bool,
+}
+
+/// Result of recomputing every account's balance
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceRecomputeSummary {
+    /// Number of accounts checked
+    #[schema(example = 120)]
+    pub checked: usize,
+    /// Accounts whose stored balance drifted from history
+    pub drifted: Vec<BalanceCheck>,
+}
+
 /// Path parameters for account ID
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct AccountIdPath {
@@ -275,7 +405,7 @@ pub struct AccountIdPath {
 /// Path parameters for account type
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct AccountTypePath {
-    /// Account type (checking, savings, credit)
+    /// Account type (checking, savings, credit, investment, loan)
     #[serde(rename = "type")]
     #[param(example = "checking")]
     pub account_type: String,
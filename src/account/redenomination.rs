@@ -0,0 +1,75 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Request body for moving an account to another currency
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeCurrencyDto {
+    /// ISO 4217 code of the new currency
+    #[validate(length(equal = 3, message = "Currency must be a 3-letter code"))]
+    #[schema(example = "EUR")]
+    pub currency: String,
+
+    /// Also convert the account's expenses and income, each at the rate of
+    /// its own date. Transfers keep their amounts, since they are shared with
+    /// the other account.
+    #[serde(default)]
+    pub convert_transactions: bool,
+
+    /// Apply the change; without it the response is only a preview
+    #[serde(default)]
+    pub confirm: bool,
+}
+
+/// Preview or result of changing an account's currency
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencyChange {
+    pub account_id: Uuid,
+    #[schema(example = "USD")]
+    pub from_currency: String,
+    #[schema(example = "EUR")]
+    pub to_currency: String,
+    /// Rate used for the balance
+    #[schema(example = 0.92)]
+    pub rate: Decimal,
+    /// Date of the stored rate used for the balance
+    pub rate_date: NaiveDate,
+    #[schema(example = 1500.00)]
+    pub balance_before: Decimal,
+    #[schema(example = 1380.00)]
+    pub balance_after: Decimal,
+    /// Expenses and income converted (or to be converted)
+    #[schema(example = 240)]
+    pub transactions_converted: i64,
+    /// Transfers left in their original amounts
+    #[schema(example = 12)]
+    pub transfers_kept: i64,
+    /// Whether the change was applied, or this is a preview
+    pub applied: bool,
+    /// Account version after the change, or its current version for a preview
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Convert an amount at a rate, rounded half away from zero to the target
+/// currency's decimal places
+pub fn convert(amount: Decimal, rate: Decimal, decimal_places: u32) -> Decimal {
+    (amount * rate).round_dp_with_strategy(decimal_places, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_and_rounds_to_target_decimals() {
+        let d = |s: &str| s.parse::<Decimal>().unwrap();
+        assert_eq!(convert(d("100.00"), d("0.92345678"), 2), d("92.35"));
+        assert_eq!(convert(d("-10.00"), d("1.0005"), 2), d("-10.01"));
+        assert_eq!(convert(d("10.00"), d("149.505"), 0), d("1495"));
+    }
+}
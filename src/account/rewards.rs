@@ -0,0 +1,257 @@
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::bill::models::{due_date_in, next_due_on_or_after};
+
+/// Validate a cashback percentage
+fn validate_rate(rate: &Decimal) -> Result<(), ValidationError> {
+    if *rate < Decimal::ZERO || *rate > Decimal::from(100) {
+        return Err(ValidationError::new("rate_out_of_range"));
+    }
+    Ok(())
+}
+
+fn default_statement_day() -> i16 {
+    31
+}
+
+/// Cashback rate for spending in categories with a given name
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryRewardRate {
+    /// Category name, matched case-insensitively in every month's budget
+    #[validate(length(min = 1, max = 50, message = "Category name must be 1-50 characters"))]
+    #[schema(example = "Dining")]
+    pub category_name: String,
+
+    /// Percent earned back, replacing the flat rate (0-100)
+    #[validate(custom(function = "validate_rate", message = "Rate must be between 0 and 100"))]
+    #[schema(example = 3.0)]
+    pub rate: Decimal,
+}
+
+/// Request body for setting a credit account's cashback rates
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRewardsDto {
+    /// Percent earned back on eligible spending (0-100)
+    #[validate(custom(function = "validate_rate", message = "Rate must be between 0 and 100"))]
+    #[schema(example = 1.5)]
+    pub flat_rate: Decimal,
+
+    /// Day of the month statements close (1-31, defaults to 31: calendar months)
+    #[validate(range(min = 1, max = 31, message = "Statement day must be between 1 and 31"))]
+    #[serde(default = "default_statement_day")]
+    #[schema(example = 15)]
+    pub statement_day: i16,
+
+    /// Category rates overriding the flat rate (replaces the existing list)
+    #[validate(length(max = 50, message = "At most 50 category rates are allowed"))]
+    #[validate(nested)]
+    #[serde(default)]
+    pub category_rates: Vec<CategoryRewardRate>,
+}
+
+impl UpdateRewardsDto {
+    /// Category names must be unique, ignoring case
+    pub fn validate_categories(&self) -> Result<(), ValidationError> {
+        let mut seen: Vec<String> = Vec::new();
+        for rate in &self.category_rates {
+            let key = rate.category_name.trim().to_lowercase();
+            if key.is_empty() || seen.contains(&key) {
+                return Err(ValidationError::new("duplicate_category_rate"));
+            }
+            seen.push(key);
+        }
+        Ok(())
+    }
+}
+
+/// A credit account's cashback settings
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsConfig {
+    pub account_id: Uuid,
+    /// Percent earned back on spending without a category rate
+    #[schema(example = 1.5)]
+    pub flat_rate: Decimal,
+    /// Day of the month statements close
+    #[schema(example = 31)]
+    pub statement_day: i16,
+    pub category_rates: Vec<CategoryRewardRate>,
+}
+
+/// Eligible spending on the account, with the rate it earns
+#[derive(Debug, Clone, FromRow)]
+pub struct RewardSpendRow {
+    /// Local date in the owner's timezone
+    pub local_date: NaiveDate,
+    /// Negative for refunds, which take the reward back
+    pub amount: Decimal,
+    pub rate: Decimal,
+}
+
+/// Estimated rewards for one statement period
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardPeriod {
+    /// First day of the period
+    pub period_start: NaiveDate,
+    /// Statement closing date
+    pub period_end: NaiveDate,
+    /// Whether the period is still open
+    pub current: bool,
+    /// Expenses net of refunds
+    #[schema(example = 820.00)]
+    pub eligible_spend: Decimal,
+    /// Estimated cashback earned
+    #[schema(example = 14.35)]
+    pub rewards: Decimal,
+}
+
+/// Response for GET /accounts/{id}/rewards
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsReport {
+    #[serde(flatten)]
+    pub config: RewardsConfig,
+    /// Statement periods, newest first
+    pub periods: Vec<RewardPeriod>,
+    /// Sum of the periods' rewards
+    #[schema(example = 42.10)]
+    pub total_rewards: Decimal,
+}
+
+/// The `count` statement periods up to the one containing `today`, newest
+/// first, as inclusive (start, end) local dates
+pub fn statement_periods(
+    today: NaiveDate,
+    statement_day: i16,
+    count: u32,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let close_month = next_due_on_or_after(today, statement_day)
+        .with_day(1)
+        .expect("valid date");
+    let close_in = |months_back: u32| {
+        let month = close_month - Months::new(months_back);
+        due_date_in(month.year(), month.month(), statement_day)
+    };
+
+    (0..count)
+        .map(|i| {
+            let start = close_in(i + 1).succ_opt().expect("valid date");
+            (start, close_in(i))
+        })
+        .collect()
+}
+
+impl RewardsReport {
+    /// Bucket spending into statement periods and apply each row's rate
+    pub fn build(
+        config: RewardsConfig,
+        periods: &[(NaiveDate, NaiveDate)],
+        today: NaiveDate,
+        rows: &[RewardSpendRow],
+    ) -> Self {
+        let hundred = Decimal::from(100);
+        let periods: Vec<RewardPeriod> = periods
+            .iter()
+            .map(|&(start, end)| {
+                let in_period = rows
+                    .iter()
+                    .filter(|row| row.local_date >= start && row.local_date <= end);
+                RewardPeriod {
+                    period_start: start,
+                    period_end: end,
+                    current: today >= start && today <= end,
+                    eligible_spend: in_period.clone().map(|row| row.amount).sum(),
+                    rewards: in_period
+                        .map(|row| row.amount * row.rate / hundred)
+                        .sum::<Decimal>()
+                        .round_dp(2),
+                }
+            })
+            .collect();
+
+        Self {
+            config,
+            total_rewards: periods.iter().map(|p| p.rewards).sum(),
+            periods,
+        }
+    }
+}
+
+fn default_reward_periods() -> u32 {
+    6
+}
+
+/// Query parameters for the rewards estimate
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct RewardsQuery {
+    /// Number of statement periods, newest first (1-24, default 6)
+    #[validate(range(min = 1, max = 24, message = "periods must be between 1 and 24"))]
+    #[serde(default = "default_reward_periods")]
+    #[param(example = 6)]
+    pub periods: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_rewards_bucket_by_statement_period() {
+        let today = date(2026, 3, 20);
+        let periods = statement_periods(today, 15, 3);
+        assert_eq!(
+            periods,
+            vec![
+                (date(2026, 3, 16), date(2026, 4, 15)),
+                (date(2026, 2, 16), date(2026, 3, 15)),
+                (date(2026, 1, 16), date(2026, 2, 15)),
+            ]
+        );
+        // Calendar months by default
+        assert_eq!(
+            statement_periods(date(2026, 2, 10), 31, 1),
+            vec![(date(2026, 2, 1), date(2026, 2, 28))]
+        );
+
+        let row = |d: NaiveDate, amount: i64, rate: i64| RewardSpendRow {
+            local_date: d,
+            amount: Decimal::from(amount),
+            rate: Decimal::from(rate),
+        };
+        let config = RewardsConfig {
+            account_id: Uuid::new_v4(),
+            flat_rate: Decimal::ONE,
+            statement_day: 15,
+            category_rates: Vec::new(),
+        };
+        let report = RewardsReport::build(
+            config,
+            &periods,
+            today,
+            &[
+                row(date(2026, 3, 16), 200, 3),
+                row(date(2026, 3, 15), 100, 1),
+                row(date(2026, 3, 1), -40, 1),
+            ],
+        );
+
+        assert!(report.periods[0].current);
+        assert_eq!(report.periods[0].rewards, Decimal::from(6));
+        assert_eq!(report.periods[1].eligible_spend, Decimal::from(60));
+        assert_eq!(report.periods[1].rewards, Decimal::new(60, 2));
+        assert_eq!(report.total_rewards, Decimal::new(660, 2));
+    }
+}
@@ -1,13 +1,43 @@
+use chrono::{DateTime, Months, NaiveDate, NaiveTime, Utc};
 use rust_decimal::Decimal;
+use serde_json::json;
 use sqlx::PgPool;
+use tracing::warn;
 use uuid::Uuid;
 
+use super::charges::{
+    cycle_interest, last_cycle_end_before, next_cycle_end, ChargesConfig, DueCharges,
+    UpdateChargesDto,
+};
+use super::consolidation::consolidate;
+use super::loan::{amortization_schedule, AmortizationResponse, DueLoan, LoanTerms, UpdateLoanDto};
 use super::models::{
-    Account, AccountsSummary, CreateAccountDto, CurrencySummary, CurrencySummaryRow, SummaryRow,
-    UpdateAccountDto, UpdateBalanceDto,
+    Account, AccountType, AccountsSummary, BalanceChanged, BalanceCheck, BalanceRecomputeSummary,
+    CreateAccountDto, CurrencySummary, CurrencySummaryRow, ReorderAccountsDto, UpdateAccountDto,
+    UpdateBalanceDto,
+};
+use super::redenomination::{convert, ChangeCurrencyDto, CurrencyChange};
+use super::rewards::{
+    statement_periods, CategoryRewardRate, RewardSpendRow, RewardsConfig, RewardsReport,
+    UpdateRewardsDto,
 };
+use super::statement::{Statement, StatementPeriod, StatementRow};
+use crate::audit::models::{actions, entities};
+use crate::audit::service::AuditService;
+use crate::cache::ResponseCache;
+use crate::category::service::CategoryService;
+use crate::crypto::encrypt_field;
 use crate::currency::service::CurrencyService;
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorCode};
+use crate::extractors::IfMatch;
+use crate::transaction::models::{CreateTransactionDto, Transaction, TransactionType};
+use crate::transaction::service::TransactionService;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+const LOAN_COLUMNS: &str = "account_id, principal, apr, term_months, first_payment_date, auto_post, payment_account_id, category_name, payments_posted";
+const CHARGES_COLUMNS: &str =
+    "account_id, apr, monthly_fee, cycle_day, auto_post, category_name, last_posted_cycle";
 
 /// Service layer for account business logic.
 pub struct AccountService;
@@ -17,16 +47,19 @@ impl AccountService {
     pub async fn list_accounts(pool: &PgPool, owner_id: Uuid) -> Result<Vec<Account>, AppError> {
         sqlx::query_as::<_, Account>(
             r#"
-            SELECT id, owner_id, name, account_type, balance, color_hex, currency, created_at, updated_at
+            SELECT id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
             FROM accounts
             WHERE owner_id = $1
-            ORDER BY created_at DESC
+            ORDER BY is_pinned DESC, display_order, created_at DESC
             "#,
         )
         .bind(owner_id)
         .fetch_all(pool)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(Account::decrypted)
+        .collect()
     }
 
     /// Get an account by ID, ensuring the requesting user owns it.
@@ -37,7 +70,7 @@ impl AccountService {
     ) -> Result<Account, AppError> {
         sqlx::query_as::<_, Account>(
             r#"
-            SELECT id, owner_id, name, account_type, balance, color_hex, currency, created_at, updated_at
+            SELECT id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
             FROM accounts
             WHERE id = $1 AND owner_id = $2
             "#,
@@ -47,6 +80,30 @@ impl AccountService {
         .fetch_optional(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Account not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Lock an owned account for a change, as stored (name still encrypted)
+    /// so it can go into the audit snapshot.
+    async fn lock_account(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Account, AppError> {
+        sqlx::query_as::<_, Account>(
+            r#"
+            SELECT id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
+            FROM accounts
+            WHERE id = $1 AND owner_id = $2
+            FOR UPDATE
+            "#,
+        )
+        .bind(account_id)
+        .bind(owner_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
         .ok_or_else(|| AppError::NotFound("Account not found".to_string()))
     }
 
@@ -57,7 +114,7 @@ impl AccountService {
         account_type: &str,
     ) -> Result<Vec<Account>, AppError> {
         // Validate type
-        let valid_types = ["checking", "savings", "credit"];
+        let valid_types = ["checking", "savings", "credit", "investment", "loan"];
         if !valid_types.contains(&account_type) {
             return Err(AppError::ValidationError(format!(
                 "Invalid account type '{}'. Must be one of: {}",
@@ -68,61 +125,40 @@ impl AccountService {
 
         sqlx::query_as::<_, Account>(
             r#"
-            SELECT id, owner_id, name, account_type, balance, color_hex, currency, created_at, updated_at
+            SELECT id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
             FROM accounts
             WHERE owner_id = $1 AND account_type = $2
-            ORDER BY created_at DESC
+            ORDER BY is_pinned DESC, display_order, created_at DESC
             "#,
         )
         .bind(owner_id)
         .bind(account_type)
         .fetch_all(pool)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(Account::decrypted)
+        .collect()
     }
 
-    /// Get accounts with financial summary for a user.
+    /// Get accounts with financial summary for a user. Totals are converted
+    /// into the user's default currency at the latest stored rates; the
+    /// per-currency summaries keep the unconverted amounts alongside.
     pub async fn get_accounts_summary(
         pool: &PgPool,
         owner_id: Uuid,
     ) -> Result<(Vec<Account>, AccountsSummary, Vec<CurrencySummary>), AppError> {
-        // Fetch all accounts
-        let accounts = Self::list_accounts(pool, owner_id).await?;
-
-        // Compute overall summary with a single aggregation query
-        let summary_row = sqlx::query_as::<_, SummaryRow>(
-            r#"
-            SELECT
-                COALESCE(SUM(CASE WHEN account_type = 'savings' THEN balance ELSE 0 END), 0) as total_savings,
-                COALESCE(SUM(CASE WHEN account_type IN ('checking', 'credit') THEN balance ELSE 0 END), 0) as total_spending,
-                COUNT(*) as accounts_count
-            FROM accounts
-            WHERE owner_id = $1
-            "#,
-        )
-        .bind(owner_id)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
-
-        let total_savings = summary_row.total_savings.unwrap_or(Decimal::ZERO);
-        let total_spending = summary_row.total_spending.unwrap_or(Decimal::ZERO);
-        let net_worth = total_savings + total_spending;
-
-        let summary = AccountsSummary {
-            total_savings,
-            total_spending,
-            net_worth,
-            accounts_count: summary_row.accounts_count.unwrap_or(0),
-        };
+        // The account list, the aggregation and the user's currency are
+        // independent, so they run concurrently on separate connections
+        let accounts = Self::list_accounts(pool, owner_id);
 
         // Compute per-currency summaries
         let currency_rows = sqlx::query_as::<_, CurrencySummaryRow>(
             r#"
             SELECT
                 currency,
-                COALESCE(SUM(CASE WHEN account_type = 'savings' THEN balance ELSE 0 END), 0) as total_savings,
-                COALESCE(SUM(CASE WHEN account_type IN ('checking', 'credit') THEN balance ELSE 0 END), 0) as total_spending,
+                COALESCE(SUM(CASE WHEN account_type IN ('savings', 'investment') THEN balance ELSE 0 END), 0) as total_savings,
+                COALESCE(SUM(CASE WHEN account_type IN ('checking', 'credit', 'loan') THEN balance ELSE 0 END), 0) as total_spending,
                 COUNT(*) as accounts_count
             FROM accounts
             WHERE owner_id = $1
@@ -131,11 +167,36 @@ impl AccountService {
             "#,
         )
         .bind(owner_id)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        .fetch_all(pool);
 
-        let summaries: Vec<CurrencySummary> = currency_rows
+        // The default currency, its precision and today's date for the rates
+        let target = sqlx::query_as::<_, (String, i16, NaiveDate)>(
+            r#"
+            SELECT u.default_currency, c.decimal_places,
+                   (NOW() AT TIME ZONE u.timezone)::DATE
+            FROM users u
+            JOIN currencies c ON c.code = u.default_currency
+            WHERE u.id = $1
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_one(pool);
+
+        let (accounts, currency_rows, (currency, decimal_places, today)) = tokio::try_join!(
+            accounts,
+            async {
+                currency_rows
+                    .await
+                    .map_err(|e| AppError::InternalError(e.to_string()))
+            },
+            async {
+                target
+                    .await
+                    .map_err(|e| AppError::InternalError(e.to_string()))
+            },
+        )?;
+
+        let mut summaries: Vec<CurrencySummary> = currency_rows
             .into_iter()
             .map(|row| {
                 let savings = row.total_savings.unwrap_or(Decimal::ZERO);
@@ -146,10 +207,30 @@ impl AccountService {
                     total_spending: spending,
                     net_worth: savings + spending,
                     accounts_count: row.accounts_count.unwrap_or(0),
+                    conversion: None,
                 }
             })
             .collect();
 
+        let mut rates = Vec::with_capacity(summaries.len());
+        for summary in &summaries {
+            rates.push(
+                CurrencyService::conversion_rate(pool, summary.currency.trim(), &currency, today)
+                    .await?,
+            );
+        }
+        let totals = consolidate(&mut summaries, &rates, decimal_places.max(0) as u32);
+
+        let summary = AccountsSummary {
+            currency,
+            total_savings: totals.total_savings,
+            total_spending: totals.total_spending,
+            net_worth: totals.total_savings + totals.total_spending,
+            accounts_count: summaries.iter().map(|s| s.accounts_count).sum(),
+            rates_as_of: today,
+            unconverted_currencies: totals.unconverted_currencies,
+        };
+
         Ok((accounts, summary, summaries))
     }
 
@@ -175,10 +256,10 @@ impl AccountService {
                 // Validate that the currency exists and is active
                 let is_valid = CurrencyService::validate_currency(pool, code).await?;
                 if !is_valid {
-                    return Err(AppError::ValidationError(format!(
-                        "Currency '{}' is not valid or not active",
-                        code
-                    )));
+                    return Err(AppError::Coded(
+                        ErrorCode::CurrencyInactive,
+                        format!("Currency '{}' is not valid or not active", code),
+                    ));
                 }
                 code.to_uppercase()
             }
@@ -194,22 +275,47 @@ impl AccountService {
             }
         };
 
-        sqlx::query_as::<_, Account>(
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let account = sqlx::query_as::<_, Account>(
             r#"
-            INSERT INTO accounts (owner_id, name, account_type, balance, color_hex, currency)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, owner_id, name, account_type, balance, color_hex, currency, created_at, updated_at
+            INSERT INTO accounts (owner_id, name, account_type, balance, opening_balance, color_hex, currency, allow_negative_balance, display_order)
+            VALUES ($1, $2, $3, $4, $4, $5, $6, $7,
+                    (SELECT COALESCE(MAX(display_order) + 1, 0) FROM accounts WHERE owner_id = $1))
+            RETURNING id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
             "#,
         )
         .bind(owner_id)
-        .bind(&name)
+        .bind(encrypt_field(&name)?)
         .bind(account_type)
         .bind(balance)
         .bind(&dto.color_hex)
         .bind(&currency)
-        .fetch_one(pool)
+        .bind(dto.allow_negative_balance.unwrap_or(true))
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            owner_id,
+            actions::ACCOUNT_CREATE,
+            entities::ACCOUNT,
+            account.id,
+            None,
+            Some(&account),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        account.decrypted()
     }
 
     /// Update an account (partial update - PATCH semantics).
@@ -218,9 +324,17 @@ impl AccountService {
         account_id: Uuid,
         owner_id: Uuid,
         dto: &UpdateAccountDto,
+        if_match: &IfMatch,
     ) -> Result<Account, AppError> {
-        // Verify ownership and get current account
-        let current = Self::get_account_by_id(pool, account_id, owner_id).await?;
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // Verify ownership and lock the current row
+        let before = Self::lock_account(&mut tx, account_id, owner_id).await?;
+        if_match.check(&before.updated_at)?;
+        let current = before.clone().decrypted()?;
 
         // Determine new values
         let new_name = match &dto.name {
@@ -243,50 +357,1115 @@ impl AccountService {
             .unwrap_or(&current.account_type);
 
         let new_color = dto.color_hex.as_ref().unwrap_or(&current.color_hex);
+        let household_visible = dto.household_visible.unwrap_or(current.household_visible);
+        let allow_negative_balance = dto
+            .allow_negative_balance
+            .unwrap_or(current.allow_negative_balance);
+        let is_pinned = dto.is_pinned.unwrap_or(current.is_pinned);
 
-        sqlx::query_as::<_, Account>(
+        let account = sqlx::query_as::<_, Account>(
             r#"
             UPDATE accounts SET
                 name = $3,
                 account_type = $4,
                 color_hex = $5,
+                household_visible = $6,
+                allow_negative_balance = $7,
+                is_pinned = $8,
                 updated_at = NOW()
             WHERE id = $1 AND owner_id = $2
-            RETURNING id, owner_id, name, account_type, balance, color_hex, currency, created_at, updated_at
+            RETURNING id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
             "#,
         )
         .bind(account_id)
         .bind(owner_id)
-        .bind(&new_name)
+        .bind(encrypt_field(&new_name)?)
         .bind(new_type)
         .bind(new_color)
-        .fetch_one(pool)
+        .bind(household_visible)
+        .bind(allow_negative_balance)
+        .bind(is_pinned)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            owner_id,
+            actions::ACCOUNT_UPDATE,
+            entities::ACCOUNT,
+            account_id,
+            Some(&before),
+            Some(&account),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        account.decrypted()
     }
 
-    /// Update only the balance field.
+    /// Persist the user's ordering of their accounts. The list must name
+    /// every account exactly once; pinned accounts still come first.
+    pub async fn reorder_accounts(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &ReorderAccountsDto,
+    ) -> Result<Vec<Account>, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let owned =
+            sqlx::query_scalar::<_, Uuid>("SELECT id FROM accounts WHERE owner_id = $1 FOR UPDATE")
+                .bind(owner_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if owned.len() != dto.account_ids.len()
+            || !dto.account_ids.iter().all(|id| owned.contains(id))
+        {
+            return Err(AppError::ValidationError(
+                "accountIds must list each of your accounts exactly once".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE accounts a
+            SET display_order = o.position - 1
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS o(id, position)
+            WHERE a.id = o.id AND a.owner_id = $1
+            "#,
+        )
+        .bind(owner_id)
+        .bind(&dto.account_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        Self::list_accounts(pool, owner_id).await
+    }
+
+    /// Announce the current balance of each given account to webhooks and
+    /// live streams. Called once the change is committed, so failures are
+    /// logged rather than returned.
+    pub async fn publish_balances(pool: &PgPool, owner_id: Uuid, account_ids: &[Uuid]) {
+        if account_ids.is_empty() {
+            return;
+        }
+
+        let balances = match sqlx::query_as::<_, BalanceChanged>(
+            r#"
+            SELECT id AS account_id, balance, currency
+            FROM accounts
+            WHERE owner_id = $1 AND id = ANY($2)
+            "#,
+        )
+        .bind(owner_id)
+        .bind(account_ids)
+        .fetch_all(pool)
+        .await
+        {
+            Ok(balances) => balances,
+            Err(e) => {
+                warn!(%owner_id, "Failed to load account balances to publish: {}", e);
+                return;
+            }
+        };
+
+        for balance in &balances {
+            WebhookService::publish(pool, owner_id, events::ACCOUNT_BALANCE_CHANGED, balance).await;
+        }
+    }
+
+    /// Update only the balance field. A manual balance change has no
+    /// transaction behind it, so it is carried into the opening balance.
     pub async fn update_balance(
         pool: &PgPool,
         account_id: Uuid,
         owner_id: Uuid,
         dto: &UpdateBalanceDto,
+        if_match: &IfMatch,
     ) -> Result<Account, AppError> {
-        sqlx::query_as::<_, Account>(
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let before = Self::lock_account(&mut tx, account_id, owner_id).await?;
+        if_match.check(&before.updated_at)?;
+
+        let account = sqlx::query_as::<_, Account>(
             r#"
             UPDATE accounts
-            SET balance = $3, updated_at = NOW()
+            SET opening_balance = opening_balance + ($3 - balance), balance = $3, updated_at = NOW()
             WHERE id = $1 AND owner_id = $2
-            RETURNING id, owner_id, name, account_type, balance, color_hex, currency, created_at, updated_at
+            RETURNING id, owner_id, name, account_type, balance, color_hex, currency, household_visible, allow_negative_balance, display_order, is_pinned, created_at, updated_at
             "#,
         )
         .bind(account_id)
         .bind(owner_id)
         .bind(dto.balance)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            owner_id,
+            actions::ACCOUNT_UPDATE,
+            entities::ACCOUNT,
+            account_id,
+            Some(&before),
+            Some(&account),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        account.decrypted()
+    }
+
+    /// Move an account to another currency. The balance is converted at the
+    /// latest stored rate; with `convert_transactions`, expenses and income are
+    /// re-denominated at the rate of their own date. Whatever the converted
+    /// history doesn't explain goes into the opening balance, so the balance
+    /// invariant still holds. Without `confirm` nothing is written.
+    pub async fn change_currency(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        dto: &ChangeCurrencyDto,
+        if_match: &IfMatch,
+    ) -> Result<CurrencyChange, AppError> {
+        let account = Self::get_account_by_id(pool, account_id, owner_id).await?;
+        if_match.check(&account.updated_at)?;
+        let from = account.currency.trim().to_string();
+        let to = dto.currency.trim().to_uppercase();
+        if to == from {
+            return Err(AppError::ValidationError(format!(
+                "Account is already in {}",
+                to
+            )));
+        }
+
+        let target = match CurrencyService::get_currency(pool, &to).await {
+            Ok(currency) if currency.is_active => currency,
+            Ok(_) | Err(AppError::NotFound(_)) => {
+                return Err(AppError::Coded(
+                    ErrorCode::CurrencyInactive,
+                    format!("Currency '{}' is not valid or not active", to),
+                ))
+            }
+            Err(e) => return Err(e),
+        };
+        let decimal_places = target.decimal_places.max(0) as u32;
+        // The smallest positive amount in the new currency
+        let min_amount = Decimal::new(1, decimal_places);
+
+        let today = Self::owner_today(pool, owner_id).await?;
+        let (rate, rate_date) = CurrencyService::conversion_rate(pool, &from, &to, today)
+            .await?
+            .ok_or_else(|| {
+                AppError::ValidationError(format!("No exchange rate stored for {}/{}", from, to))
+            })?;
+
+        let (convertible, transfers) = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                COUNT(*) FILTER (WHERE account_id = $1 AND transaction_type <> 'transfer'),
+                COUNT(*) FILTER (WHERE transaction_type = 'transfer')
+            FROM transactions
+            WHERE account_id = $1 OR destination_account_id = $1
+            "#,
+        )
+        .bind(account_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut change = CurrencyChange {
+            account_id,
+            from_currency: from.clone(),
+            to_currency: to.clone(),
+            rate,
+            rate_date,
+            balance_before: account.balance,
+            balance_after: convert(account.balance, rate, decimal_places),
+            transactions_converted: if dto.convert_transactions {
+                convertible
+            } else {
+                0
+            },
+            transfers_kept: transfers,
+            applied: false,
+            updated_at: account.updated_at,
+        };
+        if !dto.confirm {
+            return Ok(change);
+        }
+
+        // Rates per transaction date, looked up before taking any locks
+        let mut dates: Vec<NaiveDate> = Vec::new();
+        let mut rates: Vec<Decimal> = Vec::new();
+        if dto.convert_transactions {
+            dates = sqlx::query_scalar::<_, NaiveDate>(
+                r#"
+                SELECT DISTINCT transaction_date::DATE
+                FROM transactions
+                WHERE account_id = $1 AND transaction_type <> 'transfer'
+                "#,
+            )
+            .bind(account_id)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            for &date in &dates {
+                let (rate, _) = CurrencyService::conversion_rate(pool, &from, &to, date)
+                    .await?
+                    .unwrap_or((rate, rate_date));
+                rates.push(rate);
+            }
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // The account may have changed since the preview above
+        let locked = Self::lock_account(&mut tx, account_id, owner_id).await?;
+        if_match.check(&locked.updated_at)?;
+        change.balance_before = locked.balance;
+        change.balance_after = convert(locked.balance, rate, decimal_places);
+
+        if dto.convert_transactions {
+            // What was entered stays on record: a transaction entered in the
+            // old currency now counts as foreign, one entered in the new
+            // currency no longer is, and any other keeps its original amount
+            // with the rate that now reproduces the converted one
+            let converted = sqlx::query(
+                r#"
+                UPDATE transactions t
+                SET amount = c.amount,
+                    original_amount = CASE WHEN t.original_currency = $6 THEN NULL
+                                           ELSE COALESCE(t.original_amount, t.amount) END,
+                    original_currency = CASE WHEN t.original_currency = $6 THEN NULL
+                                             ELSE COALESCE(t.original_currency, $7) END,
+                    exchange_rate = CASE WHEN t.original_currency = $6 THEN NULL
+                                         ELSE ROUND(c.amount / COALESCE(t.original_amount, t.amount), 10) END,
+                    updated_at = NOW()
+                FROM (
+                    SELECT s.id, GREATEST(ROUND(s.amount * r.rate, $4), $5) AS amount
+                    FROM transactions s
+                    JOIN UNNEST($2::date[], $3::numeric[]) AS r(day, rate)
+                      ON s.transaction_date::DATE = r.day
+                    WHERE s.account_id = $1 AND s.transaction_type <> 'transfer'
+                ) c
+                WHERE t.id = c.id
+                "#,
+            )
+            .bind(account_id)
+            .bind(&dates)
+            .bind(&rates)
+            .bind(decimal_places as i32)
+            .bind(min_amount)
+            .bind(&to)
+            .bind(&from)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            change.transactions_converted = converted.rows_affected() as i64;
+        }
+
+        change.updated_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            r#"
+            UPDATE accounts
+            SET currency = $2, balance = $3, opening_balance = $3 - account_transaction_net(id),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING updated_at
+            "#,
+        )
+        .bind(account_id)
+        .bind(&to)
+        .bind(change.balance_after)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record(
+            &mut tx,
+            owner_id,
+            actions::ACCOUNT_CHANGE_CURRENCY,
+            entities::ACCOUNT,
+            account_id,
+            json!({
+                "fromCurrency": change.from_currency,
+                "toCurrency": change.to_currency,
+                "rate": change.rate,
+                "rateDate": change.rate_date,
+                "balanceBefore": change.balance_before,
+                "balanceAfter": change.balance_after,
+                "transactionsConverted": change.transactions_converted,
+            }),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        change.applied = true;
+        Ok(change)
+    }
+
+    /// The owner's current date, in their timezone
+    async fn owner_today(pool: &PgPool, owner_id: Uuid) -> Result<NaiveDate, AppError> {
+        sqlx::query_scalar::<_, NaiveDate>(
+            "SELECT (NOW() AT TIME ZONE timezone)::DATE FROM users WHERE id = $1",
+        )
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Fail with 404 unless the user owns the account, and 400 unless it is
+    /// a credit account
+    async fn get_credit_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Account, AppError> {
+        let account = Self::get_account_by_id(pool, account_id, owner_id).await?;
+        if account.account_type != AccountType::Credit.as_str() {
+            return Err(AppError::ValidationError(
+                "Rewards are only available for credit accounts".to_string(),
+            ));
+        }
+        Ok(account)
+    }
+
+    /// Cashback settings of a credit account; no settings means no rewards
+    pub async fn get_rewards_config(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<RewardsConfig, AppError> {
+        Self::get_credit_account(pool, account_id, owner_id).await?;
+
+        let (flat_rate, statement_day) = sqlx::query_as::<_, (Decimal, i16)>(
+            "SELECT flat_rate, statement_day FROM account_rewards WHERE account_id = $1",
+        )
+        .bind(account_id)
         .fetch_optional(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
-        .ok_or_else(|| AppError::NotFound("Account not found".to_string()))
+        .unwrap_or((Decimal::ZERO, 31));
+
+        let category_rates = sqlx::query_as::<_, CategoryRewardRate>(
+            "SELECT category_name, rate FROM account_reward_categories WHERE account_id = $1 ORDER BY category_name",
+        )
+        .bind(account_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(RewardsConfig {
+            account_id,
+            flat_rate,
+            statement_day,
+            category_rates,
+        })
+    }
+
+    /// Replace a credit account's cashback settings
+    pub async fn update_rewards_config(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        dto: &UpdateRewardsDto,
+    ) -> Result<RewardsConfig, AppError> {
+        Self::get_credit_account(pool, account_id, owner_id).await?;
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO account_rewards (account_id, flat_rate, statement_day)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (account_id) DO UPDATE SET
+                flat_rate = EXCLUDED.flat_rate,
+                statement_day = EXCLUDED.statement_day
+            "#,
+        )
+        .bind(account_id)
+        .bind(dto.flat_rate)
+        .bind(dto.statement_day)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query("DELETE FROM account_reward_categories WHERE account_id = $1")
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        for rate in &dto.category_rates {
+            sqlx::query(
+                "INSERT INTO account_reward_categories (account_id, category_name, rate) VALUES ($1, $2, $3)",
+            )
+            .bind(account_id)
+            .bind(rate.category_name.trim())
+            .bind(rate.rate)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        Self::get_rewards_config(pool, account_id, owner_id).await
+    }
+
+    /// Estimate cashback per statement period from the account's expenses.
+    /// Refunds net against spending and take back what they earned; transfers
+    /// (such as card payments) don't count.
+    pub async fn rewards_report(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        period_count: u32,
+    ) -> Result<RewardsReport, AppError> {
+        let config = Self::get_rewards_config(pool, account_id, owner_id).await?;
+
+        let today = Self::owner_today(pool, owner_id).await?;
+
+        let periods = statement_periods(today, config.statement_day, period_count);
+        let (Some(&(_, last_end)), Some(&(first_start, _))) = (periods.first(), periods.last())
+        else {
+            return Ok(RewardsReport::build(config, &periods, today, &[]));
+        };
+
+        let rows = sqlx::query_as::<_, RewardSpendRow>(
+            r#"
+            SELECT
+                (t.transaction_date AT TIME ZONE u.timezone)::DATE AS local_date,
+                t.reporting_amount AS amount,
+                COALESCE(r.rate, $2) AS rate
+            FROM transactions t
+            INNER JOIN accounts a ON a.id = t.account_id
+            INNER JOIN users u ON u.id = a.owner_id
+            INNER JOIN categories c ON c.id = t.category_id
+            LEFT JOIN account_reward_categories r
+                ON r.account_id = t.account_id AND LOWER(r.category_name) = LOWER(c.name)
+            WHERE t.account_id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.reporting_type = 'expense'
+              AND t.transaction_date >= $3::DATE::TIMESTAMP AT TIME ZONE u.timezone
+              AND t.transaction_date < ($4::DATE + 1)::TIMESTAMP AT TIME ZONE u.timezone
+            "#,
+        )
+        .bind(account_id)
+        .bind(config.flat_rate)
+        .bind(first_start)
+        .bind(last_end)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(RewardsReport::build(config, &periods, today, &rows))
+    }
+
+    /// Fail with 404 unless the user owns the account, and 400 unless it is
+    /// a loan account
+    async fn get_loan_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Account, AppError> {
+        let account = Self::get_account_by_id(pool, account_id, owner_id).await?;
+        if account.account_type != AccountType::Loan.as_str() {
+            return Err(AppError::ValidationError(
+                "Amortization is only available for loan accounts".to_string(),
+            ));
+        }
+        Ok(account)
+    }
+
+    /// Terms of a loan account; 404 until they are set
+    pub async fn get_loan_terms(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<LoanTerms, AppError> {
+        Self::get_loan_account(pool, account_id, owner_id).await?;
+
+        sqlx::query_as::<_, LoanTerms>(&format!(
+            "SELECT {LOAN_COLUMNS} FROM account_loans WHERE account_id = $1"
+        ))
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Loan terms have not been set".to_string()))
+    }
+
+    /// Set a loan account's terms. Turning autoposting on marks payments that
+    /// are already due as posted, so only upcoming payments create transactions.
+    pub async fn set_loan_terms(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        dto: &UpdateLoanDto,
+    ) -> Result<LoanTerms, AppError> {
+        Self::get_loan_account(pool, account_id, owner_id).await?;
+        if let Some(payment_account_id) = dto.payment_account_id {
+            if payment_account_id == account_id {
+                return Err(AppError::ValidationError(
+                    "A loan cannot be paid from itself".to_string(),
+                ));
+            }
+            Self::get_account_by_id(pool, payment_account_id, owner_id).await?;
+        }
+
+        let mut terms = LoanTerms {
+            account_id,
+            principal: dto.principal,
+            apr: dto.apr,
+            term_months: dto.term_months,
+            first_payment_date: dto.first_payment_date,
+            auto_post: dto.auto_post,
+            payment_account_id: dto.payment_account_id,
+            category_name: dto
+                .category_name
+                .as_deref()
+                .map(|name| name.trim().to_string()),
+            payments_posted: 0,
+        };
+        let today = Self::owner_today(pool, owner_id).await?;
+        let due_before_today = amortization_schedule(&terms)
+            .1
+            .iter()
+            .filter(|row| row.due_date < today)
+            .count() as i16;
+        terms.payments_posted = due_before_today;
+
+        sqlx::query_as::<_, LoanTerms>(&format!(
+            r#"
+            INSERT INTO account_loans AS l
+                (account_id, principal, apr, term_months, first_payment_date, auto_post,
+                 payment_account_id, category_name, payments_posted)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (account_id) DO UPDATE SET
+                principal = EXCLUDED.principal,
+                apr = EXCLUDED.apr,
+                term_months = EXCLUDED.term_months,
+                first_payment_date = EXCLUDED.first_payment_date,
+                auto_post = EXCLUDED.auto_post,
+                payment_account_id = EXCLUDED.payment_account_id,
+                category_name = EXCLUDED.category_name,
+                payments_posted = CASE WHEN l.auto_post AND EXCLUDED.auto_post
+                    THEN LEAST(l.payments_posted, EXCLUDED.term_months)
+                    ELSE EXCLUDED.payments_posted END
+            RETURNING {LOAN_COLUMNS}
+            "#
+        ))
+        .bind(terms.account_id)
+        .bind(terms.principal)
+        .bind(terms.apr)
+        .bind(terms.term_months)
+        .bind(terms.first_payment_date)
+        .bind(terms.auto_post)
+        .bind(terms.payment_account_id)
+        .bind(&terms.category_name)
+        .bind(terms.payments_posted)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Full amortization schedule of a loan account with its payoff position
+    pub async fn amortization(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<AmortizationResponse, AppError> {
+        let terms = Self::get_loan_terms(pool, account_id, owner_id).await?;
+        let today = Self::owner_today(pool, owner_id).await?;
+
+        Ok(AmortizationResponse::build(terms, today))
+    }
+
+    /// Autoposting loans with payments left to post
+    pub async fn autopost_loans(pool: &PgPool) -> Result<Vec<DueLoan>, AppError> {
+        sqlx::query_as::<_, DueLoan>(
+            r#"
+            SELECT l.account_id, l.principal, l.apr, l.term_months, l.first_payment_date,
+                   l.auto_post, l.payment_account_id, l.category_name, l.payments_posted,
+                   a.owner_id, (NOW() AT TIME ZONE u.timezone)::DATE AS today
+            FROM account_loans l
+            INNER JOIN accounts a ON a.id = l.account_id
+            INNER JOIN users u ON u.id = a.owner_id
+            WHERE l.auto_post
+              AND l.payment_account_id IS NOT NULL
+              AND l.category_name IS NOT NULL
+              AND l.payments_posted < l.term_months
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Post the next unposted payment of an autoposting loan: the interest as
+    /// an expense and the principal as a transfer from the payment account to
+    /// the loan. The payment is claimed first so concurrent runs can't post
+    /// it twice; if a transaction can't be created the claim is released.
+    /// Returns the created transactions, or none when nothing is due.
+    pub async fn post_next_loan_payment(
+        pool: &PgPool,
+        loan: &DueLoan,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let (terms, owner_id) = (&loan.terms, loan.owner_id);
+        let (_, schedule) = amortization_schedule(terms);
+        let Some(row) = schedule
+            .into_iter()
+            .find(|row| row.number == terms.payments_posted + 1)
+            .filter(|row| row.due_date <= loan.today)
+        else {
+            return Ok(Vec::new());
+        };
+        let (Some(payment_account_id), Some(category_name)) =
+            (terms.payment_account_id, terms.category_name.as_deref())
+        else {
+            return Ok(Vec::new());
+        };
+
+        let claimed = sqlx::query(
+            "UPDATE account_loans SET payments_posted = $2 WHERE account_id = $1 AND payments_posted = $2 - 1",
+        )
+        .bind(terms.account_id)
+        .bind(row.number)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if claimed.rows_affected() == 0 {
+            return Ok(Vec::new());
+        }
+
+        // Midday UTC keeps the posting on its due date in most timezones
+        let transaction_date = row
+            .due_date
+            .and_time(NaiveTime::from_hms_opt(12, 0, 0).expect("valid time"))
+            .and_utc();
+        let description = format!("Loan payment {} of {}", row.number, terms.term_months);
+        let mut postings = Vec::new();
+        if row.interest > Decimal::ZERO {
+            postings.push((TransactionType::Expense, None, row.interest, "interest"));
+        }
+        postings.push((
+            TransactionType::Transfer,
+            Some(terms.account_id),
+            row.principal,
+            "principal",
+        ));
+
+        let mut created = Vec::new();
+        let result = async {
+            let category_id =
+                CategoryService::current_id_by_name(pool, owner_id, category_name).await?;
+            for (transaction_type, destination_account_id, amount, part) in postings {
+                let transaction = TransactionService::create_transaction(
+                    pool,
+                    owner_id,
+                    CreateTransactionDto {
+                        reimbursable: false,
+                        reimbursement_payer: None,
+                        tax_category: None,
+                        currency: None,
+                        exchange_rate: None,
+                        pending: false,
+                        payee_id: None,
+                        linked_transaction_id: None,
+                        fee_amount: None,
+                        destination_amount: None,
+                        category_id,
+                        account_id: Some(payment_account_id),
+                        destination_account_id,
+                        amount,
+                        transaction_date,
+                        description: Some(format!("{} ({})", description, part)),
+                        transaction_type,
+                    },
+                )
+                .await?;
+                created.push(transaction);
+            }
+            Ok::<_, AppError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            for transaction in &created {
+                if let Err(undo) =
+                    TransactionService::delete_transaction(pool, owner_id, transaction.id).await
+                {
+                    warn!(
+                        transaction_id = %transaction.id,
+                        "Failed to remove transaction of unposted loan payment: {}", undo
+                    );
+                }
+            }
+            sqlx::query(
+                "UPDATE account_loans SET payments_posted = $2 - 1 WHERE account_id = $1 AND payments_posted = $2",
+            )
+            .bind(terms.account_id)
+            .bind(row.number)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            return Err(e);
+        }
+
+        Ok(created)
+    }
+
+    /// Statement of an account for one month (`month` 0-11) in the owner's
+    /// timezone. The opening balance is the account's opening balance plus
+    /// everything before the month, so manual balance corrections count from
+    /// the start of the history.
+    pub async fn statement(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        year: i16,
+        month: i16,
+        display_month: i16,
+    ) -> Result<Statement, AppError> {
+        let account = Self::get_account_by_id(pool, account_id, owner_id).await?;
+        let period_start = NaiveDate::from_ymd_opt(year as i32, month as u32 + 1, 1)
+            .ok_or_else(|| AppError::ValidationError("Invalid statement month".to_string()))?;
+        let period_end = (period_start + Months::new(1))
+            .pred_opt()
+            .expect("valid date");
+
+        let opening_balance = sqlx::query_scalar::<_, Decimal>(
+            r#"
+            SELECT a.opening_balance + COALESCE(SUM(
+                CASE
+                    WHEN t.account_id = a.id AND t.transaction_type = 'income' THEN t.amount
+                    WHEN t.account_id = a.id THEN -(t.amount + COALESCE(t.fee_amount, 0))
+                    ELSE COALESCE(t.destination_amount, t.amount)
+                END
+            ), 0)
+            FROM accounts a
+            INNER JOIN users u ON u.id = a.owner_id
+            LEFT JOIN transactions t
+                ON (t.account_id = a.id OR (t.destination_account_id = a.id AND t.transaction_type = 'transfer'))
+               AND t.transaction_date < $2::DATE::TIMESTAMP AT TIME ZONE u.timezone
+               AND t.deleted_at IS NULL AND t.status = 'posted'
+            WHERE a.id = $1
+            GROUP BY a.id, a.opening_balance
+            "#,
+        )
+        .bind(account_id)
+        .bind(period_start)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let rows = sqlx::query_as::<_, StatementRow>(
+            r#"
+            SELECT
+                t.id AS transaction_id,
+                (t.transaction_date AT TIME ZONE u.timezone)::DATE AS transaction_date,
+                t.description,
+                c.name AS category_name,
+                t.transaction_type,
+                CASE
+                    WHEN t.account_id = a.id AND t.transaction_type = 'income' THEN t.amount
+                    WHEN t.account_id = a.id THEN -(t.amount + COALESCE(t.fee_amount, 0))
+                    ELSE COALESCE(t.destination_amount, t.amount)
+                END AS amount
+            FROM accounts a
+            INNER JOIN users u ON u.id = a.owner_id
+            INNER JOIN transactions t
+                ON t.account_id = a.id OR (t.destination_account_id = a.id AND t.transaction_type = 'transfer')
+            INNER JOIN categories c ON c.id = t.category_id
+            WHERE a.id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.transaction_date >= $2::DATE::TIMESTAMP AT TIME ZONE u.timezone
+              AND t.transaction_date < ($2::DATE + INTERVAL '1 month') AT TIME ZONE u.timezone
+            ORDER BY t.transaction_date ASC, t.created_at ASC
+            "#,
+        )
+        .bind(account_id)
+        .bind(period_start)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(StatementRow::decrypted)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        let period = StatementPeriod {
+            account_id,
+            account_name: account.name,
+            currency: account.currency.trim().to_string(),
+            year,
+            month: display_month,
+            period_start,
+            period_end,
+        };
+        Ok(Statement::build(period, opening_balance, rows))
+    }
+
+    /// Fail with 404 unless the user owns the account, and 400 unless it is
+    /// a credit or savings account
+    async fn get_charge_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Account, AppError> {
+        let account = Self::get_account_by_id(pool, account_id, owner_id).await?;
+        if account.account_type != AccountType::Credit.as_str()
+            && account.account_type != AccountType::Savings.as_str()
+        {
+            return Err(AppError::ValidationError(
+                "Interest and fees are only available for credit and savings accounts".to_string(),
+            ));
+        }
+        Ok(account)
+    }
+
+    /// Interest and fee settings of an account; 404 until they are set
+    pub async fn get_charges(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<ChargesConfig, AppError> {
+        Self::get_charge_account(pool, account_id, owner_id).await?;
+
+        sqlx::query_as::<_, ChargesConfig>(&format!(
+            "SELECT {CHARGES_COLUMNS} FROM account_charges WHERE account_id = $1"
+        ))
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Interest and fees have not been set".to_string()))
+    }
+
+    /// Set an account's interest rate and monthly fee. Turning autoposting on
+    /// marks cycles that already ended as posted, so only upcoming cycle ends
+    /// create transactions.
+    pub async fn set_charges(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        dto: &UpdateChargesDto,
+    ) -> Result<ChargesConfig, AppError> {
+        Self::get_charge_account(pool, account_id, owner_id).await?;
+        let today = Self::owner_today(pool, owner_id).await?;
+
+        sqlx::query_as::<_, ChargesConfig>(&format!(
+            r#"
+            INSERT INTO account_charges AS c
+                (account_id, apr, monthly_fee, cycle_day, auto_post, category_name, last_posted_cycle)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (account_id) DO UPDATE SET
+                apr = EXCLUDED.apr,
+                monthly_fee = EXCLUDED.monthly_fee,
+                cycle_day = EXCLUDED.cycle_day,
+                auto_post = EXCLUDED.auto_post,
+                category_name = EXCLUDED.category_name,
+                last_posted_cycle = CASE WHEN c.auto_post AND EXCLUDED.auto_post
+                    THEN c.last_posted_cycle
+                    ELSE EXCLUDED.last_posted_cycle END
+            RETURNING {CHARGES_COLUMNS}
+            "#
+        ))
+        .bind(account_id)
+        .bind(dto.apr)
+        .bind(dto.monthly_fee)
+        .bind(dto.cycle_day)
+        .bind(dto.auto_post)
+        .bind(
+            dto.category_name
+                .as_deref()
+                .map(|name| name.trim().to_string()),
+        )
+        .bind(last_cycle_end_before(today, dto.cycle_day))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Autoposting accounts with interest or a fee to post
+    pub async fn autopost_charges(pool: &PgPool) -> Result<Vec<DueCharges>, AppError> {
+        sqlx::query_as::<_, DueCharges>(
+            r#"
+            SELECT c.account_id, c.apr, c.monthly_fee, c.cycle_day, c.auto_post,
+                   c.category_name, c.last_posted_cycle,
+                   a.owner_id, a.account_type, (NOW() AT TIME ZONE u.timezone)::DATE AS today
+            FROM account_charges c
+            INNER JOIN accounts a ON a.id = c.account_id
+            INNER JOIN users u ON u.id = a.owner_id
+            WHERE c.auto_post
+              AND c.category_name IS NOT NULL
+              AND c.last_posted_cycle IS NOT NULL
+              AND (c.apr > 0 OR c.monthly_fee > 0)
+              AND a.account_type IN ('credit', 'savings')
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Post the next ended cycle of an autoposting account: interest on the
+    /// balance at posting time, then the monthly fee as an expense. The cycle
+    /// is claimed first so concurrent runs can't post it twice; if a
+    /// transaction can't be created the claim is released. Returns the
+    /// created transactions and the cycle's end, or `None` when no cycle has
+    /// ended since the last one posted.
+    pub async fn post_next_charge_cycle(
+        pool: &PgPool,
+        due: &DueCharges,
+    ) -> Result<Option<(NaiveDate, Vec<Transaction>)>, AppError> {
+        let (config, owner_id) = (&due.config, due.owner_id);
+        let (Some(last_end), Some(category_name), Some(account_type)) = (
+            config.last_posted_cycle,
+            config.category_name.as_deref(),
+            AccountType::parse(&due.account_type),
+        ) else {
+            return Ok(None);
+        };
+        let cycle_end = next_cycle_end(last_end, config.cycle_day);
+        if cycle_end > due.today {
+            return Ok(None);
+        }
+
+        let claimed = sqlx::query(
+            "UPDATE account_charges SET last_posted_cycle = $2 WHERE account_id = $1 AND last_posted_cycle = $3",
+        )
+        .bind(config.account_id)
+        .bind(cycle_end)
+        .bind(last_end)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if claimed.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        let balance =
+            sqlx::query_scalar::<_, Decimal>("SELECT balance FROM accounts WHERE id = $1")
+                .bind(config.account_id)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+        let days = (cycle_end - last_end).num_days();
+        let mut postings = Vec::new();
+        if let Some((transaction_type, interest)) =
+            cycle_interest(account_type, balance, config.apr, days)
+        {
+            postings.push((transaction_type, interest, "Interest"));
+        }
+        if config.monthly_fee > Decimal::ZERO {
+            postings.push((TransactionType::Expense, config.monthly_fee, "Monthly fee"));
+        }
+
+        // Midday UTC keeps the posting on its cycle end in most timezones
+        let transaction_date = cycle_end
+            .and_time(NaiveTime::from_hms_opt(12, 0, 0).expect("valid time"))
+            .and_utc();
+        let mut created = Vec::new();
+        let result = async {
+            if postings.is_empty() {
+                return Ok(());
+            }
+            let category_id =
+                CategoryService::current_id_by_name(pool, owner_id, category_name).await?;
+            for (transaction_type, amount, label) in postings {
+                let transaction = TransactionService::create_transaction(
+                    pool,
+                    owner_id,
+                    CreateTransactionDto {
+                        reimbursable: false,
+                        reimbursement_payer: None,
+                        tax_category: None,
+                        currency: None,
+                        exchange_rate: None,
+                        pending: false,
+                        payee_id: None,
+                        linked_transaction_id: None,
+                        fee_amount: None,
+                        destination_amount: None,
+                        category_id,
+                        account_id: Some(config.account_id),
+                        destination_account_id: None,
+                        amount,
+                        transaction_date,
+                        description: Some(format!("{} (cycle ending {})", label, cycle_end)),
+                        transaction_type,
+                    },
+                )
+                .await?;
+                created.push(transaction);
+            }
+            Ok::<_, AppError>(())
+        }
+        .await;
+
+        if let Err(e) = result {
+            for transaction in &created {
+                if let Err(undo) =
+                    TransactionService::delete_transaction(pool, owner_id, transaction.id).await
+                {
+                    warn!(
+                        transaction_id = %transaction.id,
+                        "Failed to remove transaction of unposted charges: {}", undo
+                    );
+                }
+            }
+            sqlx::query(
+                "UPDATE account_charges SET last_posted_cycle = $3 WHERE account_id = $1 AND last_posted_cycle = $2",
+            )
+            .bind(config.account_id)
+            .bind(cycle_end)
+            .bind(last_end)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            return Err(e);
+        }
+
+        Ok(Some((cycle_end, created)))
     }
 
     /// Delete an account.
@@ -295,17 +1474,149 @@ impl AccountService {
         account_id: Uuid,
         owner_id: Uuid,
     ) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM accounts WHERE id = $1 AND owner_id = $2")
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let before = Self::lock_account(&mut tx, account_id, owner_id).await?;
+
+        sqlx::query("DELETE FROM accounts WHERE id = $1")
             .bind(account_id)
-            .bind(owner_id)
-            .execute(pool)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            owner_id,
+            actions::ACCOUNT_DELETE,
+            entities::ACCOUNT,
+            account_id,
+            Some(&before),
+            None,
+        )
+        .await?;
+
+        tx.commit()
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        Ok(())
+    }
 
-        if result.rows_affected() == 0 {
+    /// Recompute an account's balance from its opening balance and transaction
+    /// history, optionally overwriting the stored balance when they differ.
+    ///
+    /// The account row is locked first, so transaction writes (which update the
+    /// same row) can't change the balance between the check and the fix.
+    pub async fn recompute_balance(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Option<Uuid>,
+        fix: bool,
+    ) -> Result<BalanceCheck, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let locked = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM accounts WHERE id = $1 AND ($2::uuid IS NULL OR owner_id = $2) FOR UPDATE",
+        )
+        .bind(account_id)
+        .bind(owner_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if locked.is_none() {
             return Err(AppError::NotFound("Account not found".to_string()));
         }
 
-        Ok(())
+        let mut check = sqlx::query_as::<_, BalanceCheck>(
+            r#"
+            SELECT
+                id AS account_id, owner_id, stored_balance, opening_balance, transaction_net,
+                opening_balance + transaction_net AS computed_balance,
+                stored_balance - (opening_balance + transaction_net) AS drift
+            FROM (
+                SELECT id, owner_id, balance AS stored_balance, opening_balance,
+                       account_transaction_net(id) AS transaction_net
+                FROM accounts
+                WHERE id = $1
+            ) a
+            "#,
+        )
+        .bind(account_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if fix && !check.drift.is_zero() {
+            sqlx::query("UPDATE accounts SET balance = $2, updated_at = NOW() WHERE id = $1")
+                .bind(account_id)
+                .bind(check.computed_balance)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            AuditService::record(
+                &mut tx,
+                check.owner_id,
+                actions::ACCOUNT_RECOMPUTE_BALANCE,
+                entities::ACCOUNT,
+                account_id,
+                json!({
+                    "storedBalance": check.stored_balance,
+                    "computedBalance": check.computed_balance,
+                    "drift": check.drift,
+                }),
+            )
+            .await?;
+            check.fixed = true;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if check.fixed {
+            ResponseCache::invalidate_user(check.owner_id);
+        }
+
+        Ok(check)
+    }
+
+    /// Recompute every account's balance (or every account of one owner),
+    /// returning the ones that drifted. Each account is checked in its own
+    /// transaction.
+    pub async fn recompute_all_balances(
+        pool: &PgPool,
+        owner_id: Option<Uuid>,
+        fix: bool,
+    ) -> Result<BalanceRecomputeSummary, AppError> {
+        let account_ids = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM accounts WHERE ($1::uuid IS NULL OR owner_id = $1) ORDER BY id",
+        )
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut drifted = Vec::new();
+        for &account_id in &account_ids {
+            match Self::recompute_balance(pool, account_id, owner_id, fix).await {
+                Ok(check) if !check.drift.is_zero() => drifted.push(check),
+                Ok(_) => {}
+                // Deleted since the list was read
+                Err(AppError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(BalanceRecomputeSummary {
+            checked: account_ids.len(),
+            drifted,
+        })
     }
 }
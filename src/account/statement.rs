@@ -0,0 +1,424 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+use crate::report::models::csv_field;
+
+/// Output format for account statements
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatementFormat {
+    #[default]
+    Json,
+    /// Comma-separated lines, sent as an attachment
+    Csv,
+    /// Printable document, sent as an attachment
+    Pdf,
+}
+
+/// Path parameters for an account statement
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct StatementPath {
+    /// Account UUID
+    pub id: Uuid,
+    /// Calendar year, in the user's timezone
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[param(example = 2026)]
+    pub year: i16,
+    /// Month, numbered as set by monthBase
+    #[param(example = 0)]
+    pub month: i16,
+}
+
+/// Query parameters for an account statement
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct StatementQuery {
+    /// json (default), csv or pdf
+    #[serde(default)]
+    pub format: StatementFormat,
+}
+
+/// A transaction on the account within the statement month
+#[derive(Debug, Clone, FromRow)]
+pub struct StatementRow {
+    pub transaction_id: Uuid,
+    /// Local date in the user's timezone
+    pub transaction_date: NaiveDate,
+    pub description: Option<String>,
+    pub category_name: String,
+    pub transaction_type: String,
+    /// Effect on this account: negative for money out
+    pub amount: Decimal,
+}
+
+impl StatementRow {
+    /// Decrypt the description after loading from the database
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        Ok(self)
+    }
+}
+
+/// One line of a statement
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementLine {
+    pub transaction_id: Uuid,
+    pub transaction_date: NaiveDate,
+    #[schema(example = "Weekly groceries")]
+    pub description: Option<String>,
+    #[schema(example = "Groceries")]
+    pub category_name: String,
+    #[schema(example = "expense")]
+    pub transaction_type: String,
+    /// Effect on this account: negative for money out
+    #[schema(example = -54.20)]
+    pub amount: Decimal,
+    /// Balance after this transaction
+    #[schema(example = 1445.80)]
+    pub running_balance: Decimal,
+}
+
+/// An account's transactions for one month, with balances
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Statement {
+    pub account_id: Uuid,
+    #[schema(example = "My Checking")]
+    pub account_name: String,
+    #[schema(example = "USD")]
+    pub currency: String,
+    #[schema(example = 2026)]
+    pub year: i16,
+    /// Month, numbered as requested
+    #[schema(example = 0)]
+    pub month: i16,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Balance at the start of the month
+    #[schema(example = 1500.00)]
+    pub opening_balance: Decimal,
+    /// Money into the account during the month
+    #[schema(example = 2500.00)]
+    pub total_in: Decimal,
+    /// Money out of the account during the month, as a positive amount
+    #[schema(example = 1830.45)]
+    pub total_out: Decimal,
+    /// Balance at the end of the month
+    #[schema(example = 2169.55)]
+    pub closing_balance: Decimal,
+    /// Transactions in date order
+    pub lines: Vec<StatementLine>,
+}
+
+/// Statement header fields, everything but the balances and lines
+#[derive(Debug, Clone)]
+pub struct StatementPeriod {
+    pub account_id: Uuid,
+    pub account_name: String,
+    pub currency: String,
+    pub year: i16,
+    pub month: i16,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+}
+
+impl Statement {
+    /// Run the balance forward from the opening balance through the rows
+    pub fn build(
+        period: StatementPeriod,
+        opening_balance: Decimal,
+        rows: Vec<StatementRow>,
+    ) -> Self {
+        let mut balance = opening_balance;
+        let mut total_in = Decimal::ZERO;
+        let mut total_out = Decimal::ZERO;
+        let lines = rows
+            .into_iter()
+            .map(|row| {
+                balance += row.amount;
+                if row.amount >= Decimal::ZERO {
+                    total_in += row.amount;
+                } else {
+                    total_out -= row.amount;
+                }
+                StatementLine {
+                    transaction_id: row.transaction_id,
+                    transaction_date: row.transaction_date,
+                    description: row.description,
+                    category_name: row.category_name,
+                    transaction_type: row.transaction_type,
+                    amount: row.amount,
+                    running_balance: balance,
+                }
+            })
+            .collect();
+
+        Self {
+            account_id: period.account_id,
+            account_name: period.account_name,
+            currency: period.currency,
+            year: period.year,
+            month: period.month,
+            period_start: period.period_start,
+            period_end: period.period_end,
+            opening_balance,
+            total_in,
+            total_out,
+            closing_balance: balance,
+            lines,
+        }
+    }
+
+    /// File name for downloads, without extension
+    pub fn file_stem(&self) -> String {
+        format!(
+            "statement-{}-{}",
+            self.period_start.format("%Y-%m"),
+            &self.account_id.simple().to_string()[..8]
+        )
+    }
+
+    /// Opening balance row, one row per transaction, then the closing balance
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("Date,Description,Category,Type,Amount,Balance\r\n");
+        out.push_str(&format!(
+            "{},Opening balance,,,,{}\r\n",
+            self.period_start,
+            money(self.opening_balance)
+        ));
+        for line in &self.lines {
+            out.push_str(&format!(
+                "{},{},{},{},{},{}\r\n",
+                line.transaction_date,
+                csv_field(line.description.as_deref().unwrap_or_default(), true),
+                csv_field(&line.category_name, true),
+                line.transaction_type,
+                money(line.amount),
+                money(line.running_balance),
+            ));
+        }
+        out.push_str(&format!(
+            "{},Closing balance,,,,{}\r\n",
+            self.period_end,
+            money(self.closing_balance)
+        ));
+        out
+    }
+
+    /// The statement as a plain-text PDF, paginated
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut text = vec![
+            format!("Account statement: {}", self.account_name),
+            format!(
+                "Period: {} to {}    Currency: {}",
+                self.period_start, self.period_end, self.currency
+            ),
+            String::new(),
+            format!("Opening balance: {:>14}", money(self.opening_balance)),
+            format!("Money in:        {:>14}", money(self.total_in)),
+            format!("Money out:       {:>14}", money(self.total_out)),
+            format!("Closing balance: {:>14}", money(self.closing_balance)),
+            String::new(),
+            format!(
+                "{:<10}  {:<30}  {:<14}  {:>11}  {:>11}",
+                "Date", "Description", "Category", "Amount", "Balance"
+            ),
+            "-".repeat(82),
+        ];
+        for line in &self.lines {
+            text.push(format!(
+                "{:<10}  {:<30}  {:<14}  {:>11}  {:>11}",
+                line.transaction_date,
+                truncate(line.description.as_deref().unwrap_or_default(), 30),
+                truncate(&line.category_name, 14),
+                money(line.amount),
+                money(line.running_balance),
+            ));
+        }
+        if self.lines.is_empty() {
+            text.push("No transactions this month".to_string());
+        }
+
+        text_pdf(&text)
+    }
+}
+
+/// Money with two decimal places, padding whole amounts
+fn money(amount: Decimal) -> String {
+    format!("{:.2}", amount)
+}
+
+fn truncate(value: &str, width: usize) -> String {
+    if value.chars().count() <= width {
+        value.to_string()
+    } else {
+        let mut cut: String = value.chars().take(width - 1).collect();
+        cut.push('~');
+        cut
+    }
+}
+
+/// Lines per page at 10pt on US Letter
+const PDF_LINES_PER_PAGE: usize = 60;
+
+/// Escape a line for a PDF string literal. Only printable ASCII is written
+/// as is; anything else is replaced.
+fn pdf_string(line: &str) -> String {
+    line.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{c}"),
+            ' '..='~' => c.to_string(),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+/// A minimal PDF of monospaced text lines, split into pages
+fn text_pdf(lines: &[String]) -> Vec<u8> {
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(PDF_LINES_PER_PAGE).collect()
+    };
+
+    // Objects 1-3 are the catalog, the page tree and the font; each page
+    // then takes two: the page and its content stream
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|i| format!("{} 0 R", 4 + 2 * i))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (i, page) in pages.iter().enumerate() {
+        let mut content = String::from("BT /F1 10 Tf 12 TL 50 750 Td\n");
+        for line in page.iter() {
+            content.push_str(&format!("({}) Tj T*\n", pdf_string(line)));
+        }
+        content.push_str("ET");
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            5 + 2 * i
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+    let xref = out.len();
+    out.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        out.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    out.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(day: u32, amount: i64, description: &str) -> StatementRow {
+        StatementRow {
+            transaction_id: Uuid::new_v4(),
+            transaction_date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            description: Some(description.to_string()),
+            category_name: "Food".to_string(),
+            transaction_type: if amount < 0 { "expense" } else { "income" }.to_string(),
+            amount: Decimal::new(amount, 2),
+        }
+    }
+
+    fn statement(rows: Vec<StatementRow>) -> Statement {
+        let period = StatementPeriod {
+            account_id: Uuid::new_v4(),
+            account_name: "Checking".to_string(),
+            currency: "USD".to_string(),
+            year: 2026,
+            month: 2,
+            period_start: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+            period_end: NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+        };
+        Statement::build(period, Decimal::new(10000, 2), rows)
+    }
+
+    #[test]
+    fn test_statement_runs_balance_forward() {
+        let statement = statement(vec![
+            row(2, -2550, "Lunch, with \"team\""),
+            row(15, 50000, "Salary"),
+            row(20, -1000, "=SUM(A1)"),
+        ]);
+
+        let balances: Vec<Decimal> = statement.lines.iter().map(|l| l.running_balance).collect();
+        assert_eq!(
+            balances,
+            vec![
+                Decimal::new(7450, 2),
+                Decimal::new(57450, 2),
+                Decimal::new(56450, 2)
+            ]
+        );
+        assert_eq!(statement.total_in, Decimal::new(50000, 2));
+        assert_eq!(statement.total_out, Decimal::new(3550, 2));
+        assert_eq!(statement.closing_balance, Decimal::new(56450, 2));
+
+        let csv = statement.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[1], "2026-03-01,Opening balance,,,,100.00");
+        assert_eq!(
+            lines[2],
+            "2026-03-02,\"Lunch, with \"\"team\"\"\",Food,expense,-25.50,74.50"
+        );
+        assert_eq!(lines[4], "2026-03-20,'=SUM(A1),Food,expense,-10.00,564.50");
+        assert_eq!(lines[5], "2026-03-31,Closing balance,,,,564.50");
+    }
+
+    #[test]
+    fn test_statement_pdf_paginates() {
+        let rows = (0..100)
+            .map(|i| row(1 + i % 28, -100, "Coffee (large)"))
+            .collect();
+        let pdf = String::from_utf8(statement(rows).to_pdf()).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("Coffee \\(large\\)"));
+
+        // The cross-reference offsets point at their objects
+        let xref_at: usize = pdf.lines().rev().nth(1).unwrap().parse().unwrap();
+        let first_offset: usize = pdf[xref_at..].lines().nth(3).unwrap()[..10]
+            .parse()
+            .unwrap();
+        assert!(pdf[first_offset..].starts_with("1 0 obj"));
+    }
+}
@@ -0,0 +1,168 @@
+use actix_web::{get, post, web, HttpResponse};
+use serde_json::json;
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::service::AccountService;
+use crate::auth::scopes::Scope;
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+use super::log::{AuditLogQuery, AuditLogResponse};
+use super::models::{entities, ActivityFeedResponse, ActivityQuery, UndoResponse};
+use super::service::AuditService;
+
+/// GET /activity - Feed of recent changes, newest first
+#[utoipa::path(
+    get,
+    path = "/activity",
+    tag = "History",
+    params(ActivityQuery),
+    responses(
+        (status = 200, description = "Paginated activity feed", body = ActivityFeedResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/activity")]
+pub async fn list_activity(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<ActivityQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    query.validate()?;
+
+    let (data, total) = AuditService::list_activity(pool.get_ref(), auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(ActivityFeedResponse {
+        data,
+        total,
+        limit: query.limit,
+        offset: query.offset,
+    }))
+}
+
+/// GET /audit-log - The user's own changes with before/after snapshots
+#[utoipa::path(
+    get,
+    path = "/audit-log",
+    tag = "History",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Paginated audit log, newest first", body = AuditLogResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/audit-log")]
+pub async fn list_audit_log(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<AuditLogQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    query.validate()?;
+    query
+        .validate_range()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let (data, total) = AuditService::list_log(pool.get_ref(), auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(AuditLogResponse {
+        data,
+        total,
+        limit: query.limit,
+        offset: query.offset,
+    }))
+}
+
+/// POST /undo - Reverse the user's most recent change
+#[utoipa::path(
+    post,
+    path = "/undo",
+    tag = "History",
+    responses(
+        (status = 200, description = "Most recent change reversed", body = UndoResponse),
+        (status = 404, description = "Nothing to undo within the undo window", body = ErrorResponse),
+        (status = 409, description = "Change can no longer be undone", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/undo")]
+pub async fn undo(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    let entry =
+        AuditService::latest_undoable(pool.get_ref(), auth.user_id, config.undo_window_minutes)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Nothing to undo".to_string()))?;
+
+    // Undoing needs write access to whatever the change touched
+    match entry.entity_type.as_str() {
+        entities::TRANSACTION => auth.require_scope(Scope::TransactionsWrite)?,
+        _ => auth.require_scope(Scope::CategoriesWrite)?,
+    }
+
+    let (response, restored) = AuditService::undo(pool.get_ref(), auth.user_id, entry.id).await?;
+
+    let mut accounts = Vec::new();
+    for restored in restored {
+        for id in &restored.removed_transfers {
+            WebhookService::publish(
+                pool.get_ref(),
+                auth.user_id,
+                events::TRANSACTION_DELETED,
+                &json!({ "id": id }),
+            )
+            .await;
+        }
+        match restored.transaction {
+            Some(transaction) => {
+                let event = if restored.recreated {
+                    events::TRANSACTION_CREATED
+                } else {
+                    events::TRANSACTION_UPDATED
+                };
+                let transaction = TransactionResponse::from(transaction);
+                WebhookService::publish(pool.get_ref(), auth.user_id, event, &transaction).await;
+            }
+            None => {
+                WebhookService::publish(
+                    pool.get_ref(),
+                    auth.user_id,
+                    events::TRANSACTION_DELETED,
+                    &json!({ "id": restored.transaction_id }),
+                )
+                .await;
+            }
+        }
+        accounts.extend(restored.accounts);
+    }
+    accounts.sort_unstable();
+    accounts.dedup();
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts).await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Register the activity, audit log and undo routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_activity)
+        .service(list_audit_log)
+        .service(undo);
+}
@@ -0,0 +1,229 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::models::{entities, AuditEntry};
+use crate::crypto::decrypt_field;
+use crate::errors::AppError;
+
+fn default_limit() -> i64 {
+    50
+}
+
+/// Query parameters for the audit log
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogQuery {
+    /// Only include changes to this entity type (budget, account, category, transaction)
+    #[param(example = "budget")]
+    pub entity_type: Option<String>,
+
+    /// Only include changes to this entity
+    pub entity_id: Option<Uuid>,
+
+    /// Only include this action
+    #[param(example = "budget.update")]
+    pub action: Option<String>,
+
+    /// Only include changes at or after this time
+    #[param(example = "2026-01-01T00:00:00Z")]
+    pub from: Option<DateTime<Utc>>,
+
+    /// Only include changes before this time
+    #[param(example = "2026-02-01T00:00:00Z")]
+    pub to: Option<DateTime<Utc>>,
+
+    /// Maximum results (1-100)
+    #[validate(range(min = 1, max = 100))]
+    #[serde(default = "default_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+
+    /// Number of results to skip
+    #[validate(range(min = 0))]
+    #[serde(default)]
+    #[param(example = 0)]
+    pub offset: i64,
+}
+
+impl AuditLogQuery {
+    /// The time range must not be reversed
+    pub fn validate_range(&self) -> Result<(), ValidationError> {
+        if let (Some(from), Some(to)) = (self.from, self.to) {
+            if from > to {
+                return Err(ValidationError::new("from_after_to"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// One audit log entry with the entity's state before and after the change
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogEntry {
+    /// Audit entry UUID
+    pub id: Uuid,
+    /// User who made the change
+    pub actor_id: Uuid,
+    /// Action name
+    #[schema(example = "budget.update")]
+    pub action: String,
+    /// Type of the affected entity
+    #[schema(example = "budget")]
+    pub entity_type: String,
+    /// ID of the affected entity
+    pub entity_id: Uuid,
+    /// Entity state before the change (absent for creates)
+    #[schema(value_type = Option<Object>)]
+    pub before: Option<Value>,
+    /// Entity state after the change (absent for deletes)
+    #[schema(value_type = Option<Object>)]
+    pub after: Option<Value>,
+    /// Other details, for actions not recorded as before/after snapshots
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<Value>,
+    /// Whether the change was later undone
+    pub undone: bool,
+    /// When the change happened
+    pub created_at: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+    /// Split the stored details into before/after snapshots, decrypting the
+    /// sensitive columns snapshots keep in their stored form
+    pub fn from_entry(entry: AuditEntry) -> Result<Self, AppError> {
+        let (before, after, details) = match entry.details {
+            Value::Object(mut fields)
+                if fields.contains_key("before") || fields.contains_key("after") =>
+            {
+                let mut snapshot = |side: &str| -> Result<Option<Value>, AppError> {
+                    match fields.remove(side) {
+                        Some(Value::Null) | None => Ok(None),
+                        Some(mut value) => {
+                            decrypt_snapshot(&entry.entity_type, &mut value)?;
+                            Ok(Some(value))
+                        }
+                    }
+                };
+                (snapshot("before")?, snapshot("after")?, None)
+            }
+            details => (None, None, Some(details)),
+        };
+
+        Ok(Self {
+            id: entry.id,
+            actor_id: entry.user_id,
+            action: entry.action,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            before,
+            after,
+            details,
+            undone: entry.undone_at.is_some(),
+            created_at: entry.created_at,
+        })
+    }
+}
+
+/// Decrypt the encrypted columns of an entity snapshot in place
+fn decrypt_snapshot(entity_type: &str, snapshot: &mut Value) -> Result<(), AppError> {
+    const TRANSACTION_FIELDS: &[&str] = &["description", "reimbursement_payer"];
+
+    match entity_type {
+        entities::ACCOUNT => decrypt_fields(snapshot, &["name"]),
+        entities::TRANSACTION => decrypt_fields(snapshot, TRANSACTION_FIELDS),
+        entities::CATEGORY => match snapshot.get_mut("transactions") {
+            Some(Value::Array(transactions)) => transactions
+                .iter_mut()
+                .try_for_each(|transaction| decrypt_fields(transaction, TRANSACTION_FIELDS)),
+            _ => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn decrypt_fields(object: &mut Value, keys: &[&str]) -> Result<(), AppError> {
+    for key in keys {
+        if let Some(Value::String(value)) = object.get_mut(*key) {
+            *value = decrypt_field(value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Paginated audit log
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogResponse {
+    /// Entries, newest first
+    pub data: Vec<AuditLogEntry>,
+    /// Total count matching filters
+    #[schema(example = 100)]
+    pub total: i64,
+    /// Limit used
+    #[schema(example = 50)]
+    pub limit: i64,
+    /// Offset used
+    #[schema(example = 0)]
+    pub offset: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(entity_type: &str, details: Value) -> AuditEntry {
+        AuditEntry {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            action: format!("{entity_type}.update"),
+            entity_type: entity_type.to_string(),
+            entity_id: Uuid::nil(),
+            details,
+            created_at: Utc::now(),
+            undone_at: None,
+        }
+    }
+
+    #[test]
+    fn test_audit_log_entry_splits_snapshots() {
+        let updated = AuditLogEntry::from_entry(entry(
+            entities::BUDGET,
+            json!({ "before": { "total_income": "100" }, "after": { "total_income": "200" } }),
+        ))
+        .unwrap();
+        assert_eq!(updated.before, Some(json!({ "total_income": "100" })));
+        assert_eq!(updated.after, Some(json!({ "total_income": "200" })));
+        assert!(updated.details.is_none());
+
+        let created = AuditLogEntry::from_entry(entry(
+            entities::ACCOUNT,
+            json!({ "before": null, "after": { "name": "Checking" } }),
+        ))
+        .unwrap();
+        assert!(created.before.is_none());
+        assert_eq!(created.after, Some(json!({ "name": "Checking" })));
+
+        let other = AuditLogEntry::from_entry(entry(entities::ACCOUNT, json!({ "drift": "1.00" })))
+            .unwrap();
+        assert!(other.before.is_none() && other.after.is_none());
+        assert_eq!(other.details, Some(json!({ "drift": "1.00" })));
+    }
+
+    #[test]
+    fn test_audit_log_query_range() {
+        let query: AuditLogQuery = serde_json::from_value(json!({
+            "from": "2026-02-01T00:00:00Z",
+            "to": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(query.limit, 50);
+        assert!(query.validate_range().is_err());
+    }
+}
@@ -0,0 +1,276 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Audited action names
+pub mod actions {
+    pub const ACCOUNT_CREATE: &str = "account.create";
+    pub const ACCOUNT_UPDATE: &str = "account.update";
+    pub const ACCOUNT_DELETE: &str = "account.delete";
+    pub const ACCOUNT_RECOMPUTE_BALANCE: &str = "account.recompute_balance";
+    pub const ACCOUNT_CHANGE_CURRENCY: &str = "account.change_currency";
+    pub const BUDGET_CREATE: &str = "budget.create";
+    pub const BUDGET_UPDATE: &str = "budget.update";
+    pub const BUDGET_DELETE: &str = "budget.delete";
+    pub const BUDGET_MOVE_ALLOCATION: &str = "budget.move_allocation";
+    pub const TRANSACTION_CREATE: &str = "transaction.create";
+    pub const TRANSACTION_UPDATE: &str = "transaction.update";
+    pub const TRANSACTION_DELETE: &str = "transaction.delete";
+    pub const TRANSACTION_REFUND: &str = "transaction.refund";
+    pub const TRANSACTION_DISTRIBUTE: &str = "transaction.distribute";
+    pub const TRANSACTION_RESTORE: &str = "transaction.restore";
+    /// Groups the per-transaction entries of a bulk operation
+    pub const TRANSACTION_BULK_DELETE: &str = "transaction.bulk_delete";
+    pub const TRANSACTION_BULK_UPDATE: &str = "transaction.bulk_update";
+    pub const CATEGORY_CREATE: &str = "category.create";
+    pub const CATEGORY_UPDATE: &str = "category.update";
+    pub const CATEGORY_DELETE: &str = "category.delete";
+    pub const UNDO: &str = "undo";
+
+    /// Actions that POST /undo can reverse
+    pub const UNDOABLE: &[&str] = &[
+        TRANSACTION_CREATE,
+        TRANSACTION_UPDATE,
+        TRANSACTION_DELETE,
+        TRANSACTION_REFUND,
+        TRANSACTION_BULK_DELETE,
+        TRANSACTION_BULK_UPDATE,
+        CATEGORY_CREATE,
+        CATEGORY_UPDATE,
+        CATEGORY_DELETE,
+    ];
+
+    /// Actions whose entry stands for a group of entries undone together
+    pub const GROUPS: &[&str] = &[TRANSACTION_BULK_DELETE, TRANSACTION_BULK_UPDATE];
+}
+
+/// Audited entity types
+pub mod entities {
+    pub const ACCOUNT: &str = "account";
+    pub const BUDGET: &str = "budget";
+    pub const TRANSACTION: &str = "transaction";
+    pub const CATEGORY: &str = "category";
+    pub const AUDIT_ENTRY: &str = "audit_entry";
+}
+
+/// Database entity for audit log entries
+#[derive(Debug, Clone, FromRow)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub action: String,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub details: Value,
+    pub created_at: DateTime<Utc>,
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+/// Row state before and after a change, stored in `details` for undo.
+/// Sensitive columns are kept in their encrypted form.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChangeDetails<T> {
+    pub before: Option<T>,
+    pub after: Option<T>,
+}
+
+/// Result of undoing an operation
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UndoResponse {
+    /// Action that was reversed
+    #[schema(example = "transaction.delete")]
+    pub undone_action: String,
+    /// Type of the affected entity
+    #[schema(example = "transaction")]
+    pub entity_type: String,
+    /// ID of the affected entity
+    pub entity_id: Uuid,
+    /// When the reversed action was originally performed
+    pub performed_at: DateTime<Utc>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+/// Query parameters for the activity feed
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityQuery {
+    /// Only include changes to this entity type (transaction, category, budget)
+    #[param(example = "transaction")]
+    pub entity_type: Option<String>,
+
+    /// Maximum results (1-100)
+    #[validate(range(min = 1, max = 100))]
+    #[serde(default = "default_limit")]
+    #[param(example = 20)]
+    pub limit: i64,
+
+    /// Number of results to skip
+    #[validate(range(min = 0))]
+    #[serde(default)]
+    #[param(example = 0)]
+    pub offset: i64,
+}
+
+/// One change in the activity feed
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityItem {
+    /// Audit entry UUID
+    pub id: Uuid,
+    /// User who made the change
+    pub actor_id: Uuid,
+    /// Action name
+    #[schema(example = "transaction.create")]
+    pub action: String,
+    /// Type of the affected entity
+    #[schema(example = "transaction")]
+    pub entity_type: String,
+    /// ID of the affected entity
+    pub entity_id: Uuid,
+    /// Short description of the change
+    #[schema(example = "Added expense of 42.50")]
+    pub summary: String,
+    /// Whether the change was later undone
+    pub undone: bool,
+    /// When the change happened
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated activity feed
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityFeedResponse {
+    /// Changes, newest first
+    pub data: Vec<ActivityItem>,
+    /// Total count matching filters
+    #[schema(example = 100)]
+    pub total: i64,
+    /// Limit used
+    #[schema(example = 20)]
+    pub limit: i64,
+    /// Offset used
+    #[schema(example = 0)]
+    pub offset: i64,
+}
+
+/// Read a string field from the after (or, failing that, before) snapshot
+fn snapshot_field<'a>(details: &'a Value, path: &[&str]) -> Option<&'a str> {
+    ["after", "before"].iter().find_map(|side| {
+        path.iter()
+            .try_fold(details.get(side)?, |value, key| value.get(key))
+            .and_then(Value::as_str)
+    })
+}
+
+impl ActivityItem {
+    pub fn from_entry(entry: AuditEntry) -> Self {
+        let details = &entry.details;
+        let transaction = || {
+            format!(
+                "{} of {}",
+                snapshot_field(details, &["transaction_type"]).unwrap_or("transaction"),
+                snapshot_field(details, &["amount"]).unwrap_or("?")
+            )
+        };
+        let category = || snapshot_field(details, &["category", "name"]).unwrap_or("?");
+
+        let summary = match entry.action.as_str() {
+            actions::TRANSACTION_CREATE => format!("Added {}", transaction()),
+            actions::TRANSACTION_UPDATE => format!("Edited {}", transaction()),
+            actions::TRANSACTION_DELETE => format!("Deleted {}", transaction()),
+            actions::TRANSACTION_REFUND => format!(
+                "Refunded {}",
+                snapshot_field(details, &["amount"]).unwrap_or("?")
+            ),
+            actions::TRANSACTION_RESTORE => format!("Restored {} from the trash", transaction()),
+            actions::TRANSACTION_DISTRIBUTE => format!(
+                "Split {} of income by funding rules",
+                details.get("amount").and_then(Value::as_str).unwrap_or("?")
+            ),
+            actions::CATEGORY_CREATE => format!("Created category '{}'", category()),
+            actions::CATEGORY_UPDATE => format!("Updated category '{}'", category()),
+            actions::CATEGORY_DELETE => format!("Deleted category '{}'", category()),
+            actions::ACCOUNT_CREATE => "Created an account".to_string(),
+            actions::ACCOUNT_UPDATE => "Updated an account".to_string(),
+            actions::ACCOUNT_DELETE => "Deleted an account".to_string(),
+            actions::BUDGET_CREATE => "Created a budget".to_string(),
+            actions::BUDGET_UPDATE => "Updated a budget".to_string(),
+            actions::BUDGET_DELETE => "Deleted a budget".to_string(),
+            actions::ACCOUNT_RECOMPUTE_BALANCE => format!(
+                "Corrected account balance by {}",
+                details.get("drift").and_then(Value::as_str).unwrap_or("?")
+            ),
+            actions::BUDGET_MOVE_ALLOCATION => format!(
+                "Moved {} between categories",
+                details.get("amount").and_then(Value::as_str).unwrap_or("?")
+            ),
+            actions::UNDO => format!(
+                "Undid {}",
+                details
+                    .get("undoneAction")
+                    .and_then(Value::as_str)
+                    .unwrap_or("a change")
+            ),
+            other => other.to_string(),
+        };
+
+        Self {
+            id: entry.id,
+            actor_id: entry.user_id,
+            action: entry.action,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            summary,
+            undone: entry.undone_at.is_some(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn entry(action: &str, details: Value) -> AuditEntry {
+        AuditEntry {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            action: action.to_string(),
+            entity_type: "transaction".to_string(),
+            entity_id: Uuid::nil(),
+            details,
+            created_at: Utc::now(),
+            undone_at: None,
+        }
+    }
+
+    #[test]
+    fn test_activity_summaries_read_snapshots() {
+        let created = entry(
+            actions::TRANSACTION_CREATE,
+            json!({ "before": null, "after": { "transaction_type": "expense", "amount": "42.50" } }),
+        );
+        assert_eq!(
+            ActivityItem::from_entry(created).summary,
+            "Added expense of 42.50"
+        );
+
+        let deleted = entry(
+            actions::CATEGORY_DELETE,
+            json!({ "before": { "category": { "name": "Food" } }, "after": null }),
+        );
+        assert_eq!(
+            ActivityItem::from_entry(deleted).summary,
+            "Deleted category 'Food'"
+        );
+    }
+}
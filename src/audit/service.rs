@@ -0,0 +1,357 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::log::{AuditLogEntry, AuditLogQuery};
+use super::models::{
+    actions, entities, ActivityItem, ActivityQuery, AuditEntry, ChangeDetails, UndoResponse,
+};
+use crate::cache::ResponseCache;
+use crate::category::models::CategorySnapshot;
+use crate::category::service::CategoryService;
+use crate::errors::{AppError, ErrorCode};
+use crate::transaction::models::Transaction;
+use crate::transaction::service::{RestoredTransaction, TransactionService};
+
+const AUDIT_ENTRY_COLUMNS: &str =
+    "id, user_id, action, entity_type, entity_id, details, created_at, undone_at";
+
+/// Service layer for the audit log.
+pub struct AuditService;
+
+impl AuditService {
+    /// Append an entry to the audit log.
+    ///
+    /// Takes a connection so callers can record the entry inside the same
+    /// transaction as the change it describes.
+    pub async fn record(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        action: &str,
+        entity_type: &str,
+        entity_id: Uuid,
+        details: Value,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (user_id, action, entity_type, entity_id, details)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(user_id)
+        .bind(action)
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(details)
+        .execute(conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Record a change with before/after snapshots so it can be undone.
+    pub async fn record_change<T: Serialize>(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        action: &str,
+        entity_type: &str,
+        entity_id: Uuid,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<(), AppError> {
+        let details = serde_json::to_value(ChangeDetails { before, after })
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Self::record(conn, user_id, action, entity_type, entity_id, details).await
+    }
+
+    /// Group every entry this database transaction has recorded so far under
+    /// one new entry, so that undo reverses a bulk operation as a whole. The
+    /// entries share the transaction's `NOW()`, which is what identifies them.
+    /// `entity_ids` keeps the order they were changed in, for undoing in reverse.
+    pub async fn record_group(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        action: &str,
+        entity_type: &str,
+        entity_ids: &[Uuid],
+    ) -> Result<(), AppError> {
+        let group_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO audit_log (user_id, action, entity_type, entity_id, details)
+            VALUES ($1, $2, $3, gen_random_uuid(), $4)
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(action)
+        .bind(entity_type)
+        .bind(json!({ "entityIds": entity_ids }))
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            UPDATE audit_log
+            SET group_id = $1
+            WHERE user_id = $2 AND created_at = NOW() AND id <> $1 AND group_id IS NULL
+            "#,
+        )
+        .bind(group_id)
+        .bind(user_id)
+        .execute(conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// The user's changes, newest first, with the total count for pagination.
+    pub async fn list_activity(
+        pool: &PgPool,
+        user_id: Uuid,
+        query: &ActivityQuery,
+    ) -> Result<(Vec<ActivityItem>, i64), AppError> {
+        let entries = sqlx::query_as::<_, AuditEntry>(&format!(
+            r#"
+            SELECT {AUDIT_ENTRY_COLUMNS}
+            FROM audit_log
+            WHERE user_id = $1 AND ($2::TEXT IS NULL OR entity_type = $2)
+            ORDER BY created_at DESC
+            LIMIT $3 OFFSET $4
+            "#
+        ))
+        .bind(user_id)
+        .bind(&query.entity_type)
+        .bind(query.limit)
+        .bind(query.offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM audit_log WHERE user_id = $1 AND ($2::TEXT IS NULL OR entity_type = $2)",
+        )
+        .bind(user_id)
+        .bind(&query.entity_type)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((
+            entries.into_iter().map(ActivityItem::from_entry).collect(),
+            total,
+        ))
+    }
+
+    /// The user's own audit log, newest first, with the total count for
+    /// pagination.
+    pub async fn list_log(
+        pool: &PgPool,
+        user_id: Uuid,
+        query: &AuditLogQuery,
+    ) -> Result<(Vec<AuditLogEntry>, i64), AppError> {
+        const FILTERS: &str = r#"
+            WHERE user_id = $1
+              AND ($2::TEXT IS NULL OR entity_type = $2)
+              AND ($3::UUID IS NULL OR entity_id = $3)
+              AND ($4::TEXT IS NULL OR action = $4)
+              AND ($5::TIMESTAMPTZ IS NULL OR created_at >= $5)
+              AND ($6::TIMESTAMPTZ IS NULL OR created_at < $6)
+        "#;
+
+        let entries = sqlx::query_as::<_, AuditEntry>(&format!(
+            "SELECT {AUDIT_ENTRY_COLUMNS} FROM audit_log {FILTERS} ORDER BY created_at DESC LIMIT $7 OFFSET $8"
+        ))
+        .bind(user_id)
+        .bind(&query.entity_type)
+        .bind(query.entity_id)
+        .bind(&query.action)
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.limit)
+        .bind(query.offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let total =
+            sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM audit_log {FILTERS}"))
+                .bind(user_id)
+                .bind(&query.entity_type)
+                .bind(query.entity_id)
+                .bind(&query.action)
+                .bind(query.from)
+                .bind(query.to)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let entries = entries
+            .into_iter()
+            .map(AuditLogEntry::from_entry)
+            .collect::<Result<_, _>>()?;
+
+        Ok((entries, total))
+    }
+
+    /// The user's most recent undoable change within the undo window, if any.
+    pub async fn latest_undoable(
+        pool: &PgPool,
+        user_id: Uuid,
+        window_minutes: i32,
+    ) -> Result<Option<AuditEntry>, AppError> {
+        sqlx::query_as::<_, AuditEntry>(&format!(
+            r#"
+            SELECT {AUDIT_ENTRY_COLUMNS}
+            FROM audit_log
+            WHERE user_id = $1
+              AND action = ANY($2)
+              AND undone_at IS NULL
+              AND group_id IS NULL
+              AND created_at > NOW() - make_interval(mins => $3)
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#
+        ))
+        .bind(user_id)
+        .bind(actions::UNDOABLE)
+        .bind(window_minutes)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Reverse an audited change, restoring its "before" snapshot (including
+    /// account balance effects) and marking the entry as undone. A bulk
+    /// operation's entries are all reversed, newest change first. Also returns
+    /// the transactions restored, so they can be announced.
+    pub async fn undo(
+        pool: &PgPool,
+        user_id: Uuid,
+        entry_id: Uuid,
+    ) -> Result<(UndoResponse, Vec<RestoredTransaction>), AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // Lock the entry so concurrent undo requests can't reverse it twice
+        let entry = sqlx::query_as::<_, AuditEntry>(&format!(
+            "SELECT {AUDIT_ENTRY_COLUMNS} FROM audit_log WHERE id = $1 AND user_id = $2 FOR UPDATE"
+        ))
+        .bind(entry_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Nothing to undo".to_string()))?;
+
+        if entry.undone_at.is_some() {
+            return Err(AppError::Coded(
+                ErrorCode::AlreadyUndone,
+                "This change has already been undone".to_string(),
+            ));
+        }
+
+        let mut restored = Vec::new();
+        if actions::GROUPS.contains(&entry.action.as_str()) {
+            let mut members = sqlx::query_as::<_, AuditEntry>(&format!(
+                "SELECT {AUDIT_ENTRY_COLUMNS} FROM audit_log WHERE group_id = $1 AND undone_at IS NULL FOR UPDATE"
+            ))
+            .bind(entry.id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            let order: Vec<Uuid> = serde_json::from_value(entry.details["entityIds"].clone())
+                .map_err(|e| AppError::InternalError(format!("Corrupt audit entry: {}", e)))?;
+            let position =
+                |member: &AuditEntry| order.iter().position(|&id| id == member.entity_id);
+            members.sort_by_key(|member| std::cmp::Reverse(position(member)));
+
+            for member in &members {
+                restored.extend(Self::restore_entry(&mut tx, user_id, member).await?);
+            }
+        } else {
+            restored.extend(Self::restore_entry(&mut tx, user_id, &entry).await?);
+        }
+
+        sqlx::query("UPDATE audit_log SET undone_at = NOW() WHERE id = $1 OR group_id = $1")
+            .bind(entry.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Self::record(
+            &mut tx,
+            user_id,
+            actions::UNDO,
+            entities::AUDIT_ENTRY,
+            entry.id,
+            json!({ "undoneAction": entry.action, "entityId": entry.entity_id }),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        let response = UndoResponse {
+            undone_action: entry.action,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            performed_at: entry.created_at,
+        };
+        Ok((response, restored))
+    }
+
+    /// Put the entity of a single entry back to its "before" snapshot. For a
+    /// transaction, returns what was restored.
+    async fn restore_entry(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        entry: &AuditEntry,
+    ) -> Result<Option<RestoredTransaction>, AppError> {
+        match entry.entity_type.as_str() {
+            entities::TRANSACTION => {
+                let details: ChangeDetails<Transaction> = Self::parse_details(entry)?;
+                let restored = TransactionService::restore_snapshot(
+                    tx,
+                    user_id,
+                    entry.entity_id,
+                    details.before.as_ref(),
+                )
+                .await?;
+                Ok(Some(restored))
+            }
+            entities::CATEGORY => {
+                let details: ChangeDetails<CategorySnapshot> = Self::parse_details(entry)?;
+                CategoryService::restore_snapshot(
+                    tx,
+                    user_id,
+                    entry.entity_id,
+                    details.before.as_ref(),
+                )
+                .await?;
+                Ok(None)
+            }
+            _ => Err(AppError::Coded(
+                ErrorCode::NotUndoable,
+                format!("'{}' cannot be undone", entry.action),
+            )),
+        }
+    }
+
+    fn parse_details<T: DeserializeOwned>(
+        entry: &AuditEntry,
+    ) -> Result<ChangeDetails<T>, AppError> {
+        serde_json::from_value(entry.details.clone())
+            .map_err(|e| AppError::InternalError(format!("Corrupt audit entry: {}", e)))
+    }
+}
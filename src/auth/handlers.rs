@@ -1,18 +1,26 @@
-use actix_web::{get, post, web, HttpRequest, HttpResponse};
+use actix_web::{delete, get, post, put, web, HttpRequest, HttpResponse};
+use chrono::Utc;
 use secrecy::Secret;
 use sqlx::PgPool;
 use validator::Validate;
 
-use crate::errors::{AppError, ErrorResponse};
+use crate::errors::{AppError, ErrorCode, ErrorResponse};
+use crate::mailer::Mailer;
+use crate::storage::ObjectStorage;
+use crate::uploads::scanner::Scanner;
 
 use super::jwt::{
-    create_access_token, decode_token, extract_token, revoke_all_user_tokens, revoke_refresh_token,
-    rotate_refresh_token, validate_refresh_token,
+    create_access_token, create_scoped_access_token, decode_first_party_token, decode_token,
+    extract_token, list_user_sessions, revoke_all_user_tokens, revoke_refresh_token,
+    revoke_user_session, rotate_refresh_token, validate_refresh_token,
 };
 use super::models::{
-    AuthTokenResponse, CreateUserDto, GoogleLoginDto, LoginDto, RefreshTokenDto, UserResponseDto,
+    AuthTokenResponse, CreateScopedTokenDto, CreateUserDto, ForgotPasswordDto, GoogleLoginDto,
+    LoginDto, PasswordResetConfig, RefreshTokenDto, ResetPasswordDto, ScopedTokenResponse,
+    UpdateTimezoneDto, UserResponseDto,
 };
 use super::service::AuthService;
+use super::sessions::{SessionIdPath, SessionMeta, SessionResponse};
 
 /// POST /auth/register - Register a new user
 #[utoipa::path(
@@ -28,14 +36,16 @@ use super::service::AuthService;
 )]
 #[post("/auth/register")]
 pub async fn register(
+    req: HttpRequest,
     pool: web::Data<PgPool>,
     jwt_secret: web::Data<Secret<String>>,
     body: web::Json<CreateUserDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    body.validate()?;
 
-    let response = AuthService::register(pool.get_ref(), jwt_secret.get_ref(), &body).await?;
+    let meta = SessionMeta::from_request(&req, body.device_name.as_deref());
+    let response =
+        AuthService::register(pool.get_ref(), jwt_secret.get_ref(), &body, &meta).await?;
 
     Ok(HttpResponse::Created().json(response))
 }
@@ -53,15 +63,18 @@ pub async fn register(
 )]
 #[post("/auth/login")]
 pub async fn login(
+    req: HttpRequest,
     pool: web::Data<PgPool>,
     jwt_secret: web::Data<Secret<String>>,
     body: web::Json<LoginDto>,
 ) -> Result<HttpResponse, AppError> {
+    let meta = SessionMeta::from_request(&req, body.device_name.as_deref());
     let response = AuthService::login(
         pool.get_ref(),
         jwt_secret.get_ref(),
         &body.email,
         &body.password,
+        &meta,
     )
     .await?;
 
@@ -81,12 +94,14 @@ pub async fn login(
 )]
 #[post("/auth/google")]
 pub async fn google_login(
+    req: HttpRequest,
     pool: web::Data<PgPool>,
     jwt_secret: web::Data<Secret<String>>,
     body: web::Json<GoogleLoginDto>,
 ) -> Result<HttpResponse, AppError> {
+    let meta = SessionMeta::from_request(&req, body.device_name.as_deref());
     let response =
-        AuthService::login_with_google(pool.get_ref(), jwt_secret.get_ref(), &body.id_token)
+        AuthService::login_with_google(pool.get_ref(), jwt_secret.get_ref(), &body.id_token, &meta)
             .await?;
 
     Ok(HttpResponse::Ok().json(response))
@@ -105,6 +120,7 @@ pub async fn google_login(
 )]
 #[post("/auth/refresh")]
 pub async fn refresh(
+    req: HttpRequest,
     pool: web::Data<PgPool>,
     jwt_secret: web::Data<Secret<String>>,
     body: web::Json<RefreshTokenDto>,
@@ -116,7 +132,9 @@ pub async fn refresh(
     let user = AuthService::get_user_by_id(pool.get_ref(), token_record.user_id).await?;
 
     // Rotate refresh token atomically (revoke old, create new)
-    let new_refresh_token = rotate_refresh_token(pool.get_ref(), token_record.id, user.id).await?;
+    let meta = SessionMeta::from_request(&req, None);
+    let new_refresh_token =
+        rotate_refresh_token(pool.get_ref(), token_record.id, user.id, &meta).await?;
 
     // Create new access token
     let access_token = create_access_token(&user, jwt_secret.get_ref())?;
@@ -128,6 +146,65 @@ pub async fn refresh(
     )))
 }
 
+/// POST /auth/forgot-password - Email a password reset link
+#[utoipa::path(
+    post,
+    path = "/auth/forgot-password",
+    tag = "Auth",
+    request_body = ForgotPasswordDto,
+    responses(
+        (status = 200, description = "Reset link sent if the account exists"),
+        (status = 400, description = "Validation error", body = ErrorResponse)
+    )
+)]
+#[post("/auth/forgot-password")]
+pub async fn forgot_password(
+    pool: web::Data<PgPool>,
+    mailer: web::Data<dyn Mailer>,
+    config: web::Data<PasswordResetConfig>,
+    body: web::Json<ForgotPasswordDto>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    AuthService::request_password_reset(
+        pool.get_ref(),
+        mailer.into_inner(),
+        config.get_ref(),
+        &body.email,
+    )
+    .await?;
+
+    // Same answer whether or not the account exists
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "If an account exists for this email, a reset link has been sent"
+    })))
+}
+
+/// POST /auth/reset-password - Set a new password with an emailed reset token
+#[utoipa::path(
+    post,
+    path = "/auth/reset-password",
+    tag = "Auth",
+    request_body = ResetPasswordDto,
+    responses(
+        (status = 200, description = "Password changed; all sessions are signed out"),
+        (status = 400, description = "Validation error or invalid reset token", body = ErrorResponse)
+    )
+)]
+#[post("/auth/reset-password")]
+pub async fn reset_password(
+    pool: web::Data<PgPool>,
+    body: web::Json<ResetPasswordDto>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    AuthService::reset_password(pool.get_ref(), &body.token, &body.new_password).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Password has been reset"
+    })))
+}
+
 /// POST /auth/logout - Revoke refresh tokens
 #[utoipa::path(
     post,
@@ -136,7 +213,8 @@ pub async fn refresh(
     request_body(content = Option<RefreshTokenDto>, description = "Optional refresh token to revoke. If not provided, all sessions are revoked."),
     responses(
         (status = 200, description = "Logged out successfully"),
-        (status = 401, description = "Invalid access token", body = ErrorResponse)
+        (status = 401, description = "Invalid access token", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
     ),
     security(
         ("bearer_auth" = [])
@@ -149,9 +227,7 @@ pub async fn logout(
     jwt_secret: web::Data<Secret<String>>,
     body: Option<web::Json<RefreshTokenDto>>,
 ) -> Result<HttpResponse, AppError> {
-    // Get user ID from access token
-    let token = extract_token(&req)?;
-    let claims = decode_token(&token, jwt_secret.get_ref())?;
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
 
     // If refresh token provided, revoke only that token
     // Otherwise, revoke all tokens for the user
@@ -177,6 +253,70 @@ pub async fn logout(
     }
 }
 
+/// GET /auth/sessions - List active sessions
+#[utoipa::path(
+    get,
+    path = "/auth/sessions",
+    tag = "Auth",
+    responses(
+        (status = 200, description = "Active sessions, most recently active first", body = Vec<SessionResponse>),
+        (status = 401, description = "Invalid access token", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[get("/auth/sessions")]
+pub async fn list_sessions(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let sessions = list_user_sessions(pool.get_ref(), claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(
+        sessions
+            .into_iter()
+            .map(SessionResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// DELETE /auth/sessions/{id} - Revoke a single session
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions/{id}",
+    tag = "Auth",
+    params(SessionIdPath),
+    responses(
+        (status = 200, description = "Session revoked"),
+        (status = 401, description = "Invalid access token", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse),
+        (status = 404, description = "Session not found", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[delete("/auth/sessions/{id}")]
+pub async fn revoke_session(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+    path: web::Path<SessionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    revoke_user_session(pool.get_ref(), claims.sub, path.id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Session revoked"
+    })))
+}
+
 /// GET /auth/me - Get current user info
 #[utoipa::path(
     get,
@@ -203,3 +343,163 @@ pub async fn me(
 
     Ok(HttpResponse::Ok().json(UserResponseDto::from_user(&user)))
 }
+
+/// PUT /auth/me/avatar - Upload a new avatar image (raw JPEG, PNG or WebP body, max 5 MB)
+#[utoipa::path(
+    put,
+    path = "/auth/me/avatar",
+    tag = "Auth",
+    request_body(content = Vec<u8>, content_type = "image/*", description = "Avatar image bytes"),
+    responses(
+        (status = 200, description = "Avatar updated", body = UserResponseDto),
+        (status = 400, description = "Unsupported, oversized or rejected image", body = ErrorResponse),
+        (status = 401, description = "Invalid access token", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[put("/auth/me/avatar")]
+pub async fn upload_avatar(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+    storage: web::Data<ObjectStorage>,
+    scanner: web::Data<Scanner>,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let user = AuthService::update_avatar(
+        pool.get_ref(),
+        storage.get_ref(),
+        scanner.get_ref(),
+        claims.sub,
+        body.to_vec(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(UserResponseDto::from_user(&user)))
+}
+
+/// PUT /auth/me/timezone - Set the timezone used for monthly summaries
+#[utoipa::path(
+    put,
+    path = "/auth/me/timezone",
+    tag = "Auth",
+    request_body = UpdateTimezoneDto,
+    responses(
+        (status = 200, description = "Timezone updated", body = UserResponseDto),
+        (status = 400, description = "Unknown timezone", body = ErrorResponse),
+        (status = 401, description = "Invalid access token", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[put("/auth/me/timezone")]
+pub async fn update_timezone(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+    body: web::Json<UpdateTimezoneDto>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let user =
+        AuthService::update_timezone(pool.get_ref(), claims.sub, body.timezone.trim()).await?;
+
+    Ok(HttpResponse::Ok().json(UserResponseDto::from_user(&user)))
+}
+
+/// POST /auth/tokens - Mint a scoped access token for a third-party integration
+#[utoipa::path(
+    post,
+    path = "/auth/tokens",
+    tag = "Auth",
+    request_body = CreateScopedTokenDto,
+    responses(
+        (status = 201, description = "Scoped token created", body = ScopedTokenResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Invalid access token", body = ErrorResponse),
+        (status = 403, description = "Caller is not a first-party session", body = ErrorResponse)
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[post("/auth/tokens")]
+pub async fn create_scoped_token(
+    req: HttpRequest,
+    jwt_secret: web::Data<Secret<String>>,
+    body: web::Json<CreateScopedTokenDto>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    // A token can only delegate scopes it holds itself
+    if let Some(missing) = body.scopes.iter().find(|s| !claims.scopes.contains(s)) {
+        return Err(AppError::Coded(
+            ErrorCode::MissingScope,
+            format!(
+                "Cannot grant scope '{}' not held by the current token",
+                missing.as_str()
+            ),
+        ));
+    }
+
+    // A minted token never outlives the session that minted it
+    let expires_in_minutes = body
+        .expires_in_minutes
+        .min(claims.remaining_seconds(Utc::now()) / 60);
+    if expires_in_minutes < 1 {
+        return Err(AppError::Coded(
+            ErrorCode::InvalidToken,
+            "The current token expires too soon to mint a new one".to_string(),
+        ));
+    }
+
+    let mut scopes = body.scopes.clone();
+    scopes.sort_by_key(|s| s.as_str());
+    scopes.dedup();
+
+    let access_token = create_scoped_access_token(
+        &claims,
+        scopes.clone(),
+        expires_in_minutes,
+        jwt_secret.get_ref(),
+    )?;
+
+    Ok(HttpResponse::Created().json(ScopedTokenResponse {
+        access_token,
+        token_type: "Bearer",
+        expires_in: (expires_in_minutes * 60) as u64,
+        scopes,
+    }))
+}
+
+/// Register the auth routes for signed-in users (no rate limit)
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(logout)
+        .service(me)
+        .service(list_sessions)
+        .service(revoke_session)
+        .service(create_scoped_token)
+        .service(upload_avatar)
+        .service(update_timezone);
+}
+
+/// Auth routes that take credentials; mounted behind the auth rate limit
+pub fn configure_credential_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(register)
+        .service(login)
+        .service(google_login)
+        .service(refresh)
+        .service(forgot_password)
+        .service(reset_password);
+}
@@ -4,22 +4,25 @@ use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation}
 use rand::Rng;
 use secrecy::{ExposeSecret, Secret};
 use sha2::{Digest, Sha256};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorCode};
 
 use super::models::{RefreshToken, TokenClaims, User};
+use super::scopes::Scope;
+use super::sessions::{Session, SessionMeta};
 
 // Token expiration constants
 pub const ACCESS_TOKEN_EXPIRY_MINUTES: i64 = 15;
 pub const REFRESH_TOKEN_EXPIRY_DAYS: i64 = 7;
+pub const PASSWORD_RESET_EXPIRY_MINUTES: i64 = 60;
 
 // ============================================================================
 // JWT Access Token Utilities
 // ============================================================================
 
-/// Create a new JWT access token for a user
+/// Create a new JWT access token for a user (first-party session, all scopes)
 pub fn create_access_token(user: &User, jwt_secret: &Secret<String>) -> Result<String, AppError> {
     let now = Utc::now();
     let expires_at = now + Duration::minutes(ACCESS_TOKEN_EXPIRY_MINUTES);
@@ -30,11 +33,38 @@ pub fn create_access_token(user: &User, jwt_secret: &Secret<String>) -> Result<S
         name: user.full_name.clone(),
         iat: now.timestamp() as usize,
         exp: expires_at.timestamp() as usize,
+        scopes: Scope::all(),
     };
 
+    encode_claims(&claims, jwt_secret)
+}
+
+/// Create a JWT access token restricted to the given scopes (third-party integrations)
+pub fn create_scoped_access_token(
+    parent: &TokenClaims,
+    scopes: Vec<Scope>,
+    expires_in_minutes: i64,
+    jwt_secret: &Secret<String>,
+) -> Result<String, AppError> {
+    let now = Utc::now();
+    let expires_at = now + Duration::minutes(expires_in_minutes);
+
+    let claims = TokenClaims {
+        sub: parent.sub,
+        email: parent.email.clone(),
+        name: parent.name.clone(),
+        iat: now.timestamp() as usize,
+        exp: expires_at.timestamp() as usize,
+        scopes,
+    };
+
+    encode_claims(&claims, jwt_secret)
+}
+
+fn encode_claims(claims: &TokenClaims, jwt_secret: &Secret<String>) -> Result<String, AppError> {
     encode(
         &Header::default(),
-        &claims,
+        claims,
         &EncodingKey::from_secret(jwt_secret.expose_secret().as_bytes()),
     )
     .map_err(|e| AppError::InternalError(format!("Failed to create access token: {e}")))
@@ -48,7 +78,26 @@ pub fn decode_token(token: &str, jwt_secret: &Secret<String>) -> Result<TokenCla
         &Validation::default(),
     )
     .map(|data| data.claims)
-    .map_err(|e| AppError::Unauthorized(format!("Invalid token: {e}")))
+    .map_err(|e| AppError::Coded(ErrorCode::InvalidToken, format!("Invalid token: {e}")))
+}
+
+/// Decode the request's access token, rejecting scoped integration tokens.
+///
+/// Session management, profile changes and token minting are reserved for
+/// first-party sessions; a delegated token must not be able to manage the
+/// account that issued it.
+pub fn decode_first_party_token(
+    req: &HttpRequest,
+    jwt_secret: &Secret<String>,
+) -> Result<TokenClaims, AppError> {
+    let claims = decode_token(&extract_token(req)?, jwt_secret)?;
+    if !claims.is_first_party() {
+        return Err(AppError::Coded(
+            ErrorCode::MissingScope,
+            "This endpoint requires a first-party session token".to_string(),
+        ));
+    }
+    Ok(claims)
 }
 
 /// Extract Bearer token from Authorization header
@@ -81,21 +130,28 @@ pub fn hash_refresh_token(token: &str) -> String {
     hex::encode(hasher.finalize())
 }
 
-/// Create and store a new refresh token in the database
-pub async fn create_refresh_token(pool: &PgPool, user_id: Uuid) -> Result<String, AppError> {
+/// Create and store a new refresh token in the database, recording where it was issued
+pub async fn create_refresh_token(
+    pool: &PgPool,
+    user_id: Uuid,
+    meta: &SessionMeta,
+) -> Result<String, AppError> {
     let raw_token = generate_refresh_token();
     let token_hash = hash_refresh_token(&raw_token);
     let expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_EXPIRY_DAYS);
 
     sqlx::query(
         r#"
-        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
+        INSERT INTO refresh_tokens (user_id, token_hash, expires_at, device_name, user_agent, ip_address)
+        VALUES ($1, $2, $3, $4, $5, $6)
         "#,
     )
     .bind(user_id)
     .bind(&token_hash)
     .bind(expires_at)
+    .bind(&meta.device_name)
+    .bind(&meta.user_agent)
+    .bind(&meta.ip_address)
     .execute(pool)
     .await
     .map_err(|e| AppError::InternalError(format!("Failed to store refresh token: {e}")))?;
@@ -123,7 +179,12 @@ pub async fn validate_refresh_token(
     .fetch_optional(pool)
     .await
     .map_err(|e| AppError::InternalError(e.to_string()))?
-    .ok_or_else(|| AppError::Unauthorized("Invalid or expired refresh token".to_string()))
+    .ok_or_else(|| {
+        AppError::Coded(
+            ErrorCode::InvalidToken,
+            "Invalid or expired refresh token".to_string(),
+        )
+    })
 }
 
 /// Revoke a specific refresh token
@@ -160,11 +221,13 @@ pub async fn revoke_all_user_tokens(pool: &PgPool, user_id: Uuid) -> Result<u64,
     Ok(result.rows_affected())
 }
 
-/// Rotate refresh token atomically (revoke old, create new) within a transaction
+/// Rotate refresh token atomically (revoke old, create new) within a transaction.
+/// The new token keeps the session's device name unless the client sends a new one.
 pub async fn rotate_refresh_token(
     pool: &PgPool,
     old_token_id: Uuid,
     user_id: Uuid,
+    meta: &SessionMeta,
 ) -> Result<String, AppError> {
     let mut tx = pool
         .begin()
@@ -191,13 +254,19 @@ pub async fn rotate_refresh_token(
 
     sqlx::query(
         r#"
-        INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
-        VALUES ($1, $2, $3)
+        INSERT INTO refresh_tokens (user_id, token_hash, expires_at, device_name, user_agent, ip_address)
+        SELECT $1, $2, $3,
+               COALESCE($5, (SELECT device_name FROM refresh_tokens WHERE id = $4)),
+               $6, $7
         "#,
     )
     .bind(user_id)
     .bind(&token_hash)
     .bind(expires_at)
+    .bind(old_token_id)
+    .bind(&meta.device_name)
+    .bind(&meta.user_agent)
+    .bind(&meta.ip_address)
     .execute(&mut *tx)
     .await
     .map_err(|e| AppError::InternalError(format!("Failed to store refresh token: {e}")))?;
@@ -209,6 +278,129 @@ pub async fn rotate_refresh_token(
     Ok(raw_token)
 }
 
+/// List a user's active sessions (unrevoked, unexpired refresh tokens), most recent first
+pub async fn list_user_sessions(pool: &PgPool, user_id: Uuid) -> Result<Vec<Session>, AppError> {
+    sqlx::query_as::<_, Session>(
+        r#"
+        SELECT id, device_name, user_agent, ip_address, created_at, expires_at
+        FROM refresh_tokens
+        WHERE user_id = $1
+          AND expires_at > NOW()
+          AND revoked_at IS NULL
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| AppError::InternalError(e.to_string()))
+}
+
+/// Revoke one of a user's active sessions
+pub async fn revoke_user_session(
+    pool: &PgPool,
+    user_id: Uuid,
+    session_id: Uuid,
+) -> Result<(), AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE refresh_tokens
+        SET revoked_at = NOW()
+        WHERE id = $1 AND user_id = $2
+          AND expires_at > NOW()
+          AND revoked_at IS NULL
+        "#,
+    )
+    .bind(session_id)
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .map_err(|e| AppError::InternalError(format!("Failed to revoke token: {e}")))?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Session not found".to_string()));
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Password Reset Token Utilities
+// ============================================================================
+
+/// Create and store a password reset token, invalidating any the user
+/// still has outstanding. Tokens are generated and hashed like refresh tokens.
+pub async fn create_password_reset_token(pool: &PgPool, user_id: Uuid) -> Result<String, AppError> {
+    let raw_token = generate_refresh_token();
+    let token_hash = hash_refresh_token(&raw_token);
+    let expires_at = Utc::now() + Duration::minutes(PASSWORD_RESET_EXPIRY_MINUTES);
+
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to begin transaction: {e}")))?;
+
+    sqlx::query(
+        r#"
+        UPDATE password_reset_tokens
+        SET used_at = NOW()
+        WHERE user_id = $1 AND used_at IS NULL
+        "#,
+    )
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::InternalError(format!("Failed to invalidate reset tokens: {e}")))?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO password_reset_tokens (user_id, token_hash, expires_at)
+        VALUES ($1, $2, $3)
+        "#,
+    )
+    .bind(user_id)
+    .bind(&token_hash)
+    .bind(expires_at)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| AppError::InternalError(format!("Failed to store reset token: {e}")))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to commit transaction: {e}")))?;
+
+    Ok(raw_token)
+}
+
+/// Mark a password reset token used and return its user. Fails unless the
+/// token exists, is unexpired and hasn't been used.
+pub async fn consume_password_reset_token(
+    conn: &mut PgConnection,
+    raw_token: &str,
+) -> Result<Uuid, AppError> {
+    let token_hash = hash_refresh_token(raw_token);
+
+    sqlx::query_scalar::<_, Uuid>(
+        r#"
+        UPDATE password_reset_tokens
+        SET used_at = NOW()
+        WHERE token_hash = $1
+          AND expires_at > NOW()
+          AND used_at IS NULL
+        RETURNING user_id
+        "#,
+    )
+    .bind(&token_hash)
+    .fetch_optional(conn)
+    .await
+    .map_err(|e| AppError::InternalError(e.to_string()))?
+    .ok_or_else(|| {
+        AppError::Coded(
+            ErrorCode::InvalidResetToken,
+            "Invalid or expired password reset token".to_string(),
+        )
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
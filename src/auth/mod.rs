@@ -2,10 +2,12 @@ pub mod handlers;
 mod jwt;
 pub mod models;
 mod password;
+pub mod scopes;
 mod service;
+pub mod sessions;
 
-// Re-export handlers for use in main.rs
-pub use handlers::{google_login, login, logout, me, refresh, register};
+// Re-export route registration for main.rs and the integration tests
+pub use handlers::{configure_credential_routes, configure_routes};
 
-// Re-export for use in extractors
-pub use jwt::decode_token;
+// Re-export for use in extractors and the account-level handlers of other modules
+pub use jwt::{decode_first_party_token, decode_token};
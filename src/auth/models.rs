@@ -5,6 +5,8 @@ use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
+use super::scopes::Scope;
+
 // ============================================================================
 // User Models
 // ============================================================================
@@ -17,6 +19,10 @@ pub struct User {
     pub password_hash: String,
     pub full_name: Option<String>,
     pub default_currency: String,
+    pub timezone: String,
+    #[serde(skip_serializing)]
+    pub avatar_key: Option<String>,
+    pub avatar_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -53,6 +59,9 @@ pub struct CreateUserDto {
     #[validate(length(max = 100, message = "Full name must be at most 100 characters"))]
     #[schema(example = "John Doe")]
     pub full_name: Option<String>,
+    /// Optional name for this device, shown in the session list
+    #[schema(example = "Pixel 8")]
+    pub device_name: Option<String>,
 }
 
 /// User information returned in responses
@@ -69,6 +78,12 @@ pub struct UserResponseDto {
     /// User's default currency code
     #[schema(example = "USD")]
     pub default_currency: String,
+    /// IANA timezone used to group transactions into months
+    #[schema(example = "America/New_York")]
+    pub timezone: String,
+    /// URL of the user's avatar image
+    #[schema(example = "https://storage.googleapis.com/bucket/avatars/user/avatar-256.png")]
+    pub avatar_url: Option<String>,
     /// Account creation timestamp
     pub created_at: DateTime<Utc>,
 }
@@ -80,11 +95,22 @@ impl UserResponseDto {
             email: user.email.clone(),
             full_name: user.full_name.clone(),
             default_currency: user.default_currency.clone(),
+            timezone: user.timezone.clone(),
+            avatar_url: user.avatar_url.clone(),
             created_at: user.created_at,
         }
     }
 }
 
+/// Request body for changing the user's timezone
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateTimezoneDto {
+    /// IANA timezone name
+    #[validate(length(min = 1, max = 64, message = "Timezone must be 1-64 characters"))]
+    #[schema(example = "Europe/Berlin")]
+    pub timezone: String,
+}
+
 /// Request body for user login
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct LoginDto {
@@ -94,6 +120,59 @@ pub struct LoginDto {
     /// User's password
     #[schema(example = "Password123")]
     pub password: String,
+    /// Optional name for this device, shown in the session list
+    #[schema(example = "Pixel 8")]
+    pub device_name: Option<String>,
+}
+
+/// Request body for requesting a password reset email
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ForgotPasswordDto {
+    /// Email address of the account
+    #[validate(email)]
+    #[schema(example = "user@example.com")]
+    pub email: String,
+}
+
+/// Request body for setting a new password with an emailed reset token
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ResetPasswordDto {
+    /// Token from the reset link
+    #[validate(length(min = 1, message = "Token is required"))]
+    #[schema(example = "a1b2c3d4e5f6...")]
+    pub token: String,
+    /// New password (min 8 chars, must include uppercase, lowercase, and digit)
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    #[validate(custom(
+        function = "validate_password_complexity",
+        message = "Password must contain at least one uppercase letter, one lowercase letter, and one number"
+    ))]
+    #[schema(example = "NewPassword123")]
+    pub new_password: String,
+}
+
+/// Where password reset links point
+#[derive(Debug, Clone)]
+pub struct PasswordResetConfig {
+    /// Frontend page that takes the token
+    pub url: String,
+}
+
+impl Default for PasswordResetConfig {
+    fn default() -> Self {
+        Self {
+            url: "http://localhost:3000/reset-password".to_string(),
+        }
+    }
+}
+
+impl PasswordResetConfig {
+    /// Reset link carrying a token
+    pub fn link(&self, token: &str) -> String {
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        format!("{}{}token={}", self.url, separator, token)
+    }
 }
 
 /// Request body for Google OAuth login
@@ -102,12 +181,16 @@ pub struct GoogleLoginDto {
     /// Google ID token from Google Sign-In
     #[schema(example = "eyJhbGciOiJSUzI1NiIsInR5cCI6...")]
     pub id_token: String,
+    /// Optional name for this device, shown in the session list
+    #[schema(example = "Pixel 8")]
+    pub device_name: Option<String>,
 }
 
 /// Google token verification response structure
 #[derive(Debug, Deserialize)]
 pub struct GoogleTokenInfo {
     /// Google user ID (subject)
+    #[allow(dead_code)]
     pub sub: String,
     /// User's email address
     pub email: String,
@@ -116,6 +199,7 @@ pub struct GoogleTokenInfo {
     /// User's full name
     pub name: Option<String>,
     /// URL to user's profile picture
+    #[allow(dead_code)]
     pub picture: Option<String>,
 }
 
@@ -131,6 +215,20 @@ pub struct TokenClaims {
     pub name: Option<String>, // User display name
     pub iat: usize,           // Issued at
     pub exp: usize,           // Expiration
+    #[serde(default = "Scope::all")]
+    pub scopes: Vec<Scope>, // Granted resource scopes (missing = legacy full access)
+}
+
+impl TokenClaims {
+    /// Whether this is a first-party session token holding every scope
+    pub fn is_first_party(&self) -> bool {
+        Scope::all().iter().all(|scope| self.scopes.contains(scope))
+    }
+
+    /// Seconds until the token expires (zero once it has)
+    pub fn remaining_seconds(&self, now: DateTime<Utc>) -> i64 {
+        (self.exp as i64 - now.timestamp()).max(0)
+    }
 }
 
 /// Refresh token stored in database
@@ -152,6 +250,46 @@ pub struct RefreshTokenDto {
     pub refresh_token: String,
 }
 
+/// Request body for minting a scoped access token for a third-party integration
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateScopedTokenDto {
+    /// Scopes to grant (must be a subset of the caller's own scopes)
+    #[validate(length(min = 1, message = "At least one scope is required"))]
+    #[schema(example = json!(["transactions:read", "accounts:read"]))]
+    pub scopes: Vec<Scope>,
+    /// Token lifetime in minutes (defaults to 60, max 30 days; capped at the caller's remaining lifetime)
+    #[validate(range(
+        min = 1,
+        max = 43200,
+        message = "Expiry must be between 1 minute and 30 days"
+    ))]
+    #[serde(default = "default_scoped_token_expiry")]
+    #[schema(example = 1440)]
+    pub expires_in_minutes: i64,
+}
+
+fn default_scoped_token_expiry() -> i64 {
+    60
+}
+
+/// Response containing a scoped access token
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ScopedTokenResponse {
+    /// JWT access token restricted to the granted scopes
+    #[schema(example = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9...")]
+    pub access_token: String,
+    /// Token type (always "Bearer")
+    #[schema(example = "Bearer")]
+    pub token_type: &'static str,
+    /// Token expiry time in seconds
+    #[schema(example = 86400)]
+    pub expires_in: u64,
+    /// Scopes granted to the token
+    pub scopes: Vec<Scope>,
+}
+
 /// Response containing both access and refresh tokens
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AuthTokenResponse {
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Resource-level permission carried in access token claims.
+///
+/// First-party logins receive every scope; integration tokens are minted with
+/// an explicit subset so third parties only get the access they need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum Scope {
+    #[serde(rename = "budgets:read")]
+    BudgetsRead,
+    #[serde(rename = "budgets:write")]
+    BudgetsWrite,
+    #[serde(rename = "accounts:read")]
+    AccountsRead,
+    #[serde(rename = "accounts:write")]
+    AccountsWrite,
+    #[serde(rename = "categories:read")]
+    CategoriesRead,
+    #[serde(rename = "categories:write")]
+    CategoriesWrite,
+    #[serde(rename = "transactions:read")]
+    TransactionsRead,
+    #[serde(rename = "transactions:write")]
+    TransactionsWrite,
+    #[serde(rename = "currencies:write")]
+    CurrenciesWrite,
+    #[serde(rename = "webhooks:read")]
+    WebhooksRead,
+    #[serde(rename = "webhooks:write")]
+    WebhooksWrite,
+}
+
+impl Scope {
+    /// Every scope, granted to first-party sessions
+    pub fn all() -> Vec<Scope> {
+        vec![
+            Scope::BudgetsRead,
+            Scope::BudgetsWrite,
+            Scope::AccountsRead,
+            Scope::AccountsWrite,
+            Scope::CategoriesRead,
+            Scope::CategoriesWrite,
+            Scope::TransactionsRead,
+            Scope::TransactionsWrite,
+            Scope::CurrenciesWrite,
+            Scope::WebhooksRead,
+            Scope::WebhooksWrite,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::BudgetsRead => "budgets:read",
+            Scope::BudgetsWrite => "budgets:write",
+            Scope::AccountsRead => "accounts:read",
+            Scope::AccountsWrite => "accounts:write",
+            Scope::CategoriesRead => "categories:read",
+            Scope::CategoriesWrite => "categories:write",
+            Scope::TransactionsRead => "transactions:read",
+            Scope::TransactionsWrite => "transactions:write",
+            Scope::CurrenciesWrite => "currencies:write",
+            Scope::WebhooksRead => "webhooks:read",
+            Scope::WebhooksWrite => "webhooks:write",
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn parse(s: &str) -> Option<Self> {
+        Scope::all().into_iter().find(|scope| scope.as_str() == s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_parse_roundtrip() {
+        for scope in Scope::all() {
+            assert_eq!(Scope::parse(scope.as_str()), Some(scope));
+        }
+    }
+
+    #[test]
+    fn test_scope_parse_unknown() {
+        assert_eq!(Scope::parse("budgets:admin"), None);
+    }
+
+    #[test]
+    fn test_scope_serializes_as_resource_action() {
+        let json = serde_json::to_string(&Scope::TransactionsWrite).unwrap();
+        assert_eq!(json, "\"transactions:write\"");
+    }
+}
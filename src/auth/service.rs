@@ -1,17 +1,34 @@
+use std::sync::Arc;
+
 use rand::Rng;
 use secrecy::Secret;
 use sqlx::PgPool;
+use tracing::warn;
 use uuid::Uuid;
 
-use crate::errors::AppError;
+use crate::cache::ResponseCache;
+use crate::errors::{AppError, ErrorCode};
+use crate::mailer::{EmailMessage, Mailer};
+use crate::shutdown;
+use crate::storage::ObjectStorage;
+use crate::uploads::images::square_thumbnails;
+use crate::uploads::scanner::{ScanVerdict, Scanner};
+use crate::uploads::validation::{validate_upload, AVATAR_POLICY};
 
-use super::jwt::{create_access_token, create_refresh_token};
-use super::models::{AuthTokenResponse, CreateUserDto, GoogleTokenInfo, User};
+use super::jwt::{
+    consume_password_reset_token, create_access_token, create_password_reset_token,
+    create_refresh_token, PASSWORD_RESET_EXPIRY_MINUTES,
+};
+use super::models::{AuthTokenResponse, CreateUserDto, GoogleTokenInfo, PasswordResetConfig, User};
 use super::password::{hash_password, verify_password};
+use super::sessions::SessionMeta;
 
 /// Google token verification endpoint
 const GOOGLE_TOKEN_INFO_URL: &str = "https://oauth2.googleapis.com/tokeninfo";
 
+/// Square avatar renditions in pixels; the largest is returned as `avatar_url`
+const AVATAR_SIZES: [u32; 2] = [64, 256];
+
 /// Authentication service handling user registration and login logic
 pub struct AuthService;
 
@@ -21,6 +38,7 @@ impl AuthService {
         pool: &PgPool,
         jwt_secret: &Secret<String>,
         dto: &CreateUserDto,
+        meta: &SessionMeta,
     ) -> Result<AuthTokenResponse, AppError> {
         // Check if email already exists
         let existing_user =
@@ -31,7 +49,10 @@ impl AuthService {
                 .map_err(|e| AppError::InternalError(e.to_string()))?;
 
         if existing_user > 0 {
-            return Err(AppError::Conflict("Email already exists".to_string()));
+            return Err(AppError::Coded(
+                ErrorCode::EmailTaken,
+                "Email already exists".to_string(),
+            ));
         }
 
         // Hash password
@@ -42,7 +63,7 @@ impl AuthService {
             r#"
             INSERT INTO users (email, password_hash, full_name)
             VALUES ($1, $2, $3)
-            RETURNING id, email, password_hash, full_name, default_currency, created_at, updated_at
+            RETURNING id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at
             "#,
         )
         .bind(&dto.email)
@@ -54,7 +75,7 @@ impl AuthService {
 
         // Create tokens
         let access_token = create_access_token(&user, jwt_secret)?;
-        let refresh_token = create_refresh_token(pool, user.id).await?;
+        let refresh_token = create_refresh_token(pool, user.id, meta).await?;
 
         Ok(AuthTokenResponse::new(access_token, refresh_token, &user))
     }
@@ -65,28 +86,35 @@ impl AuthService {
         jwt_secret: &Secret<String>,
         email: &str,
         password: &str,
+        meta: &SessionMeta,
     ) -> Result<AuthTokenResponse, AppError> {
         // Find user by email
         let user = sqlx::query_as::<_, User>(
-            "SELECT id, email, password_hash, full_name, default_currency, created_at, updated_at FROM users WHERE email = $1",
+            "SELECT id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at FROM users WHERE email = $1",
         )
         .bind(email)
         .fetch_optional(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
-        .ok_or_else(|| AppError::Unauthorized("Invalid email or password".to_string()))?;
+        .ok_or_else(|| {
+            AppError::Coded(
+                ErrorCode::InvalidCredentials,
+                "Invalid email or password".to_string(),
+            )
+        })?;
 
         // Verify password
         let is_valid = verify_password(password, &user.password_hash)?;
         if !is_valid {
-            return Err(AppError::Unauthorized(
+            return Err(AppError::Coded(
+                ErrorCode::InvalidCredentials,
                 "Invalid email or password".to_string(),
             ));
         }
 
         // Create tokens
         let access_token = create_access_token(&user, jwt_secret)?;
-        let refresh_token = create_refresh_token(pool, user.id).await?;
+        let refresh_token = create_refresh_token(pool, user.id, meta).await?;
 
         Ok(AuthTokenResponse::new(access_token, refresh_token, &user))
     }
@@ -94,7 +122,7 @@ impl AuthService {
     /// Get user by ID
     pub async fn get_user_by_id(pool: &PgPool, user_id: Uuid) -> Result<User, AppError> {
         sqlx::query_as::<_, User>(
-            "SELECT id, email, password_hash, full_name, default_currency, created_at, updated_at FROM users WHERE id = $1",
+            "SELECT id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at FROM users WHERE id = $1",
         )
         .bind(user_id)
         .fetch_optional(pool)
@@ -103,11 +131,87 @@ impl AuthService {
         .ok_or_else(|| AppError::Unauthorized("User not found".to_string()))
     }
 
+    /// Email a password reset link if an account exists for the address.
+    /// The email is sent in the background, so the response doesn't reveal
+    /// (even by timing) whether the account exists.
+    pub async fn request_password_reset(
+        pool: &PgPool,
+        mailer: Arc<dyn Mailer>,
+        config: &PasswordResetConfig,
+        email: &str,
+    ) -> Result<(), AppError> {
+        let user_id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE email = $1")
+            .bind(email)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let Some(user_id) = user_id else {
+            return Ok(());
+        };
+
+        let token = create_password_reset_token(pool, user_id).await?;
+        let message = EmailMessage {
+            to: email.to_string(),
+            subject: "Reset your password".to_string(),
+            body: format!(
+                "Someone asked to reset the password for your account.\n\n\
+                 Open this link within {} minutes to choose a new password:\n{}\n\n\
+                 If you didn't ask for this, you can ignore this email.",
+                PASSWORD_RESET_EXPIRY_MINUTES,
+                config.link(&token)
+            ),
+        };
+        shutdown::spawn(async move {
+            if let Err(e) = mailer.send(&message).await {
+                warn!(user_id = %user_id, "Failed to send password reset email: {}", e);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Set a new password with a reset token, then sign out every session
+    pub async fn reset_password(
+        pool: &PgPool,
+        token: &str,
+        new_password: &str,
+    ) -> Result<(), AppError> {
+        let password_hash = hash_password(new_password)?;
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let user_id = consume_password_reset_token(&mut tx, token).await?;
+
+        sqlx::query("UPDATE users SET password_hash = $2 WHERE id = $1")
+            .bind(user_id)
+            .bind(&password_hash)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
     /// Authenticate with Google OAuth ID token
     pub async fn login_with_google(
         pool: &PgPool,
         jwt_secret: &Secret<String>,
         id_token: &str,
+        meta: &SessionMeta,
     ) -> Result<AuthTokenResponse, AppError> {
         // Verify the Google ID token
         let google_user = Self::verify_google_token(id_token).await?;
@@ -124,7 +228,7 @@ impl AuthService {
 
         // Create tokens
         let access_token = create_access_token(&user, jwt_secret)?;
-        let refresh_token = create_refresh_token(pool, user.id).await?;
+        let refresh_token = create_refresh_token(pool, user.id, meta).await?;
 
         Ok(AuthTokenResponse::new(access_token, refresh_token, &user))
     }
@@ -157,7 +261,7 @@ impl AuthService {
     ) -> Result<User, AppError> {
         // Try to find existing user by email
         let existing_user = sqlx::query_as::<_, User>(
-            "SELECT id, email, password_hash, full_name, default_currency, created_at, updated_at FROM users WHERE email = $1",
+            "SELECT id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at FROM users WHERE email = $1",
         )
         .bind(&google_user.email)
         .fetch_optional(pool)
@@ -180,7 +284,7 @@ impl AuthService {
             r#"
             INSERT INTO users (email, password_hash, full_name)
             VALUES ($1, $2, $3)
-            RETURNING id, email, password_hash, full_name, default_currency, created_at, updated_at
+            RETURNING id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at
             "#,
         )
         .bind(&google_user.email)
@@ -192,4 +296,104 @@ impl AuthService {
 
         Ok(user)
     }
+
+    /// Validate, scan and resize an avatar image, store its renditions and
+    /// point the user at them. The previous avatar files are removed afterwards.
+    pub async fn update_avatar(
+        pool: &PgPool,
+        storage: &ObjectStorage,
+        scanner: &Scanner,
+        user_id: Uuid,
+        bytes: Vec<u8>,
+    ) -> Result<User, AppError> {
+        let current = Self::get_user_by_id(pool, user_id).await?;
+
+        validate_upload(&bytes, &AVATAR_POLICY)?;
+
+        let verdict = scanner.scan(&bytes).await?;
+        if verdict != ScanVerdict::Clean {
+            tracing::warn!(%user_id, ?verdict, "Rejected avatar upload flagged by malware scan");
+            return Err(AppError::ValidationError(
+                "File failed malware scan".to_string(),
+            ));
+        }
+
+        let renditions =
+            tokio::task::spawn_blocking(move || square_thumbnails(&bytes, &AVATAR_SIZES))
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))??;
+
+        // Fresh key per upload so CDN/browser caches never serve a stale avatar
+        let avatar_key = format!("avatars/{}/{}", user_id, Uuid::new_v4());
+        let mut avatar_url = String::new();
+        for (size, data) in renditions {
+            avatar_url = storage
+                .put(&format!("{}-{}.png", avatar_key, size), data, "image/png")
+                .await?;
+        }
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users SET avatar_key = $2, avatar_url = $3, updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(&avatar_key)
+        .bind(&avatar_url)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if let Some(old_key) = current.avatar_key {
+            for size in AVATAR_SIZES {
+                if let Err(e) = storage.delete(&format!("{}-{}.png", old_key, size)).await {
+                    tracing::warn!("Failed to delete old avatar {}: {}", old_key, e);
+                }
+            }
+        }
+
+        Ok(user)
+    }
+
+    /// Set the user's IANA timezone (e.g. "Europe/Berlin"), used for monthly bucketing
+    pub async fn update_timezone(
+        pool: &PgPool,
+        user_id: Uuid,
+        timezone: &str,
+    ) -> Result<User, AppError> {
+        let known = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM pg_timezone_names WHERE name = $1)",
+        )
+        .bind(timezone)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !known {
+            return Err(AppError::ValidationError(format!(
+                "Unknown timezone '{}'",
+                timezone
+            )));
+        }
+
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE users SET timezone = $2, updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, email, password_hash, full_name, default_currency, timezone, avatar_key, avatar_url, created_at, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(timezone)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::Unauthorized("User not found".to_string()))?;
+
+        // The summary converts at the rates of the user's local date
+        ResponseCache::invalidate_user(user_id);
+        Ok(user)
+    }
 }
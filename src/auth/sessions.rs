@@ -0,0 +1,115 @@
+use actix_web::{http::header, HttpRequest};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Longest device name kept for a session
+const MAX_DEVICE_NAME_CHARS: usize = 100;
+
+/// Longest user agent kept for a session
+const MAX_USER_AGENT_CHARS: usize = 500;
+
+/// Where a refresh token was issued, recorded so users can tell sessions apart
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SessionMeta {
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl SessionMeta {
+    /// Read the user agent and peer IP (the same address the rate limiter
+    /// keys on) from the request, with the client-supplied device name
+    pub fn from_request(req: &HttpRequest, device_name: Option<&str>) -> Self {
+        Self {
+            device_name: device_name.and_then(|name| clean(name, MAX_DEVICE_NAME_CHARS)),
+            user_agent: req
+                .headers()
+                .get(header::USER_AGENT)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|agent| clean(agent, MAX_USER_AGENT_CHARS)),
+            ip_address: req.peer_addr().map(|addr| addr.ip().to_string()),
+        }
+    }
+}
+
+/// Trim and cap a client-supplied value, dropping it when blank
+fn clean(value: &str, max_chars: usize) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.chars().take(max_chars).collect())
+}
+
+/// An active refresh token as a signed-in session
+#[derive(Debug, FromRow)]
+pub struct Session {
+    pub id: Uuid,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Session information returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionResponse {
+    /// Session identifier (changes each time the session's token is refreshed)
+    pub id: Uuid,
+    /// Device name given at sign-in
+    #[schema(example = "Pixel 8")]
+    pub device_name: Option<String>,
+    /// User agent of the client that last refreshed the session
+    #[schema(example = "NextBudget/2.3 (Android 15)")]
+    pub user_agent: Option<String>,
+    /// IP address of the client that last refreshed the session
+    #[schema(example = "203.0.113.7")]
+    pub ip_address: Option<String>,
+    /// When the session was signed in or last refreshed
+    pub last_active_at: DateTime<Utc>,
+    /// When the session expires unless refreshed
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<Session> for SessionResponse {
+    fn from(session: Session) -> Self {
+        Self {
+            id: session.id,
+            device_name: session.device_name,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            last_active_at: session.created_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+/// Path parameters for session ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SessionIdPath {
+    /// Session UUID
+    pub id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_session_meta_from_request() {
+        let req = TestRequest::default()
+            .insert_header((header::USER_AGENT, "  Mozilla/5.0  "))
+            .peer_addr("203.0.113.7:51234".parse().unwrap())
+            .to_http_request();
+
+        let meta = SessionMeta::from_request(&req, Some(&"x".repeat(150)));
+        assert_eq!(meta.user_agent.as_deref(), Some("Mozilla/5.0"));
+        assert_eq!(meta.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(meta.device_name.map(|name| name.len()), Some(100));
+
+        let meta = SessionMeta::from_request(&TestRequest::default().to_http_request(), Some(" "));
+        assert_eq!(meta, SessionMeta::default());
+    }
+}
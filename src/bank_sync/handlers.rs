@@ -0,0 +1,297 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::account::service::AccountService;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+use super::models::{
+    ApproveBankImportDto, ApproveBankImportResponse, BankAccountLink, BankAccountLinkIdPath,
+    BankConnectionIdPath, BankConnectionResponse, BankImport, BankImportIdPath, BankImportStatus,
+    BankImportsQuery, BankSyncResult, LinkBankDto, UpdateBankAccountLinkDto,
+};
+use super::provider::BankSyncProvider;
+use super::service::BankSyncService;
+
+/// POST /bank-sync/connections - Link an institution from the provider's public token
+#[utoipa::path(
+    post,
+    path = "/bank-sync/connections",
+    tag = "Bank Sync",
+    request_body = LinkBankDto,
+    responses(
+        (status = 201, description = "Institution linked with its accounts", body = BankConnectionResponse),
+        (status = 400, description = "Validation error or bank sync not enabled", body = ErrorResponse),
+        (status = 409, description = "The bank login is linked to another user", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/bank-sync/connections")]
+pub async fn link_bank(
+    pool: web::Data<PgPool>,
+    provider: web::Data<BankSyncProvider>,
+    auth: AuthenticatedUser,
+    body: web::Json<LinkBankDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+
+    let connection = BankSyncService::link(
+        pool.get_ref(),
+        provider.get_ref(),
+        auth.user_id,
+        body.public_token.trim(),
+    )
+    .await?;
+
+    Ok(HttpResponse::Created().json(connection))
+}
+
+/// GET /bank-sync/connections - List linked institutions and their accounts
+#[utoipa::path(
+    get,
+    path = "/bank-sync/connections",
+    tag = "Bank Sync",
+    responses(
+        (status = 200, description = "Linked institutions", body = Vec<BankConnectionResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/bank-sync/connections")]
+pub async fn list_bank_connections(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
+    let connections = BankSyncService::list_connections(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(connections))
+}
+
+/// DELETE /bank-sync/connections/{id} - Unlink an institution
+#[utoipa::path(
+    delete,
+    path = "/bank-sync/connections/{id}",
+    tag = "Bank Sync",
+    params(BankConnectionIdPath),
+    responses(
+        (status = 200, description = "Institution unlinked; imported transactions are kept", body = DeleteResponse),
+        (status = 404, description = "Connection not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/bank-sync/connections/{id}")]
+pub async fn delete_bank_connection(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BankConnectionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    BankSyncService::delete_connection(pool.get_ref(), auth.user_id, path.id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Bank connection deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// POST /bank-sync/connections/{id}/sync - Pull new transactions into the review queue now
+#[utoipa::path(
+    post,
+    path = "/bank-sync/connections/{id}/sync",
+    tag = "Bank Sync",
+    params(BankConnectionIdPath),
+    responses(
+        (status = 200, description = "Changes pulled", body = BankSyncResult),
+        (status = 400, description = "Bank sync not enabled", body = ErrorResponse),
+        (status = 404, description = "Connection not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/bank-sync/connections/{id}/sync")]
+pub async fn sync_bank_connection(
+    pool: web::Data<PgPool>,
+    provider: web::Data<BankSyncProvider>,
+    auth: AuthenticatedUser,
+    path: web::Path<BankConnectionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let result = BankSyncService::sync_owner_connection(
+        pool.get_ref(),
+        provider.get_ref(),
+        auth.user_id,
+        path.id,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// PATCH /bank-sync/accounts/{id} - Map an institution account onto a local account
+#[utoipa::path(
+    patch,
+    path = "/bank-sync/accounts/{id}",
+    tag = "Bank Sync",
+    params(BankAccountLinkIdPath),
+    request_body = UpdateBankAccountLinkDto,
+    responses(
+        (status = 200, description = "Mapping saved", body = BankAccountLink),
+        (status = 404, description = "Linked or local account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/bank-sync/accounts/{id}")]
+pub async fn update_bank_account_link(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BankAccountLinkIdPath>,
+    body: web::Json<UpdateBankAccountLinkDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    let link = BankSyncService::update_link(pool.get_ref(), auth.user_id, path.id, body.account_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(link))
+}
+
+/// GET /bank-sync/imports - List imported bank transactions (pending review by default)
+#[utoipa::path(
+    get,
+    path = "/bank-sync/imports",
+    tag = "Bank Sync",
+    params(BankImportsQuery),
+    responses(
+        (status = 200, description = "Imports, newest first", body = Vec<BankImport>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/bank-sync/imports")]
+pub async fn list_bank_imports(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<BankImportsQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let imports = BankSyncService::list_imports(
+        pool.get_ref(),
+        auth.user_id,
+        query.status.unwrap_or(BankImportStatus::Pending),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(imports))
+}
+
+/// POST /bank-sync/imports/{id}/approve - Create the transaction for a pending import
+#[utoipa::path(
+    post,
+    path = "/bank-sync/imports/{id}/approve",
+    tag = "Bank Sync",
+    params(BankImportIdPath),
+    request_body = ApproveBankImportDto,
+    responses(
+        (status = 200, description = "Transaction created and the import approved", body = ApproveBankImportResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Import, category or account not found", body = ErrorResponse),
+        (status = 409, description = "Import already reviewed, or a possible duplicate", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/bank-sync/imports/{id}/approve")]
+pub async fn approve_bank_import(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BankImportIdPath>,
+    body: web::Json<ApproveBankImportDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let (import, transaction) =
+        BankSyncService::approve_import(pool.get_ref(), auth.user_id, path.id, body.into_inner())
+            .await?;
+
+    let response = ApproveBankImportResponse {
+        import,
+        transaction: TransactionResponse::from(transaction),
+    };
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &response.transaction,
+    )
+    .await;
+    if let Some(account_id) = response.transaction.account_id {
+        AccountService::publish_balances(pool.get_ref(), auth.user_id, &[account_id]).await;
+    }
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /bank-sync/imports/{id}/dismiss - Leave a pending import out
+#[utoipa::path(
+    post,
+    path = "/bank-sync/imports/{id}/dismiss",
+    tag = "Bank Sync",
+    params(BankImportIdPath),
+    responses(
+        (status = 200, description = "Import dismissed; later syncs don't offer it again", body = BankImport),
+        (status = 404, description = "Import not found", body = ErrorResponse),
+        (status = 409, description = "Import already reviewed", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/bank-sync/imports/{id}/dismiss")]
+pub async fn dismiss_bank_import(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BankImportIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let import = BankSyncService::dismiss_import(pool.get_ref(), auth.user_id, path.id).await?;
+
+    Ok(HttpResponse::Ok().json(import))
+}
+
+/// Register the bank connection and import review routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(link_bank)
+        .service(list_bank_connections)
+        .service(delete_bank_connection)
+        .service(sync_bank_connection)
+        .service(update_bank_account_link)
+        .service(list_bank_imports)
+        .service(approve_bank_import)
+        .service(dismiss_bank_import);
+}
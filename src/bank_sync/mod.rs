@@ -0,0 +1,6 @@
+pub mod handlers;
+pub mod models;
+pub mod provider;
+pub mod service;
+
+pub use handlers::*;
@@ -0,0 +1,237 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::crypto::{decrypt_field, decrypt_optional};
+use crate::errors::AppError;
+use crate::transaction::models::{TransactionResponse, TransactionType};
+
+/// Days either side of an import's date searched for a matching transaction
+pub const DUPLICATE_WINDOW_DAYS: i32 = 3;
+
+/// Database entity for bank connections
+#[derive(Debug, Clone, FromRow)]
+pub struct BankConnection {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub provider: String,
+    pub access_token: String,
+    pub institution_name: Option<String>,
+    pub sync_cursor: Option<String>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BankConnection {
+    /// Decrypt the access token after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.access_token = decrypt_field(&self.access_token)?;
+        Ok(self)
+    }
+}
+
+/// An institution account and the local account it's booked to
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BankAccountLink {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    #[schema(example = "Plaid Checking")]
+    pub name: String,
+    /// Last digits of the account number
+    #[schema(example = "0000")]
+    pub mask: Option<String>,
+    #[schema(example = "USD")]
+    pub currency: Option<String>,
+    /// Local account imports are booked to; unset until the user maps one
+    pub account_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A linked institution with its accounts
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BankConnectionResponse {
+    pub id: Uuid,
+    #[schema(example = "plaid")]
+    pub provider: String,
+    #[schema(example = "First Platypus Bank")]
+    pub institution_name: Option<String>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Why the latest sync failed; cleared by the next successful one
+    pub last_error: Option<String>,
+    pub accounts: Vec<BankAccountLink>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl BankConnectionResponse {
+    pub fn new(connection: BankConnection, accounts: Vec<BankAccountLink>) -> Self {
+        Self {
+            id: connection.id,
+            provider: connection.provider,
+            institution_name: connection.institution_name,
+            last_synced_at: connection.last_synced_at,
+            last_error: connection.last_error,
+            accounts,
+            created_at: connection.created_at,
+        }
+    }
+}
+
+/// Request body for linking an institution
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LinkBankDto {
+    /// Public token returned to the client by the provider's link flow
+    #[validate(length(min = 1, max = 500, message = "Public token must be 1-500 characters"))]
+    #[schema(example = "public-sandbox-b0e2c4ee-a763-4df5-bfe9-46a46bce993d")]
+    pub public_token: String,
+}
+
+/// Request body for mapping an institution account onto a local account
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBankAccountLinkDto {
+    /// Local account to book imports to; null unmaps it
+    pub account_id: Option<Uuid>,
+}
+
+/// Changes pulled by one sync
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BankSyncResult {
+    /// New transactions waiting for review
+    pub imports_added: u64,
+    /// Pending imports withdrawn by the institution
+    pub imports_removed: u64,
+}
+
+/// Where an import stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BankImportStatus {
+    /// Waiting for the user to approve or dismiss it
+    Pending,
+    /// Turned into a transaction
+    Imported,
+    /// Left out; later syncs don't offer it again
+    Dismissed,
+}
+
+impl BankImportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BankImportStatus::Pending => "pending",
+            BankImportStatus::Imported => "imported",
+            BankImportStatus::Dismissed => "dismissed",
+        }
+    }
+}
+
+/// A transaction pulled from the institution, with the existing transaction
+/// it may duplicate
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BankImport {
+    pub id: Uuid,
+    pub link_id: Uuid,
+    /// Institution account it was pulled from
+    #[schema(example = "Plaid Checking")]
+    pub account_name: String,
+    /// Local account the institution account is mapped to
+    pub account_id: Option<Uuid>,
+    #[schema(example = 12.50)]
+    pub amount: Decimal,
+    #[schema(value_type = TransactionType)]
+    pub transaction_type: String,
+    pub transaction_date: NaiveDate,
+    #[schema(example = "Corner Cafe")]
+    pub description: Option<String>,
+    #[schema(value_type = BankImportStatus)]
+    pub status: String,
+    /// Transaction created on approval
+    pub transaction_id: Option<Uuid>,
+    /// For pending imports, an existing transaction with the same amount and
+    /// type within a few days on the same account
+    pub duplicate_of_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl BankImport {
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        Ok(self)
+    }
+
+    /// When the transaction is booked: midday UTC keeps it on the bank's
+    /// date, without landing in the future
+    pub fn booked_at(&self) -> DateTime<Utc> {
+        self.transaction_date
+            .and_time(NaiveTime::from_hms_opt(12, 0, 0).expect("valid time"))
+            .and_utc()
+            .min(Utc::now())
+    }
+}
+
+/// Query parameters for listing imports
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BankImportsQuery {
+    /// Imports with this status (default pending)
+    pub status: Option<BankImportStatus>,
+}
+
+/// Request body for approving an import; omitted fields use the bank's values
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproveBankImportDto {
+    /// Category for the transaction
+    pub category_id: Uuid,
+
+    /// Account to book to (defaults to the mapped account)
+    pub account_id: Option<Uuid>,
+
+    /// Description (defaults to the bank's, max 200 chars)
+    #[validate(length(max = 200, message = "Description cannot exceed 200 characters"))]
+    #[schema(example = "Coffee with Sam")]
+    pub description: Option<String>,
+
+    /// Import even though it looks like an existing transaction
+    #[serde(default)]
+    pub allow_duplicate: bool,
+}
+
+/// Response for approving an import
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproveBankImportResponse {
+    pub import: BankImport,
+    pub transaction: TransactionResponse,
+}
+
+/// Path parameters for a bank connection
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BankConnectionIdPath {
+    /// Connection UUID
+    pub id: Uuid,
+}
+
+/// Path parameters for a linked institution account
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BankAccountLinkIdPath {
+    /// Linked account UUID
+    pub id: Uuid,
+}
+
+/// Path parameters for a bank import
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BankImportIdPath {
+    /// Import UUID
+    pub id: Uuid,
+}
@@ -0,0 +1,284 @@
+use std::time::Duration;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use secrecy::{ExposeSecret, Secret};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::errors::AppError;
+
+/// Upper bound on a single provider request
+const PROVIDER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Transactions requested per sync page
+const SYNC_PAGE_SIZE: u32 = 250;
+
+/// An institution login just linked by the user
+#[derive(Debug)]
+pub struct LinkedItem {
+    pub item_id: String,
+    pub access_token: String,
+    pub institution_name: Option<String>,
+    pub accounts: Vec<ExternalAccount>,
+}
+
+/// An account at the institution
+#[derive(Debug, Clone)]
+pub struct ExternalAccount {
+    pub account_id: String,
+    pub name: String,
+    pub mask: Option<String>,
+    pub currency: Option<String>,
+}
+
+/// A posted transaction reported by the provider
+#[derive(Debug, Clone)]
+pub struct ExternalTransaction {
+    pub transaction_id: String,
+    pub account_id: String,
+    /// Positive; the direction is in `is_income`
+    pub amount: Decimal,
+    pub is_income: bool,
+    pub date: NaiveDate,
+    pub description: Option<String>,
+}
+
+/// One page of changes since a sync cursor
+#[derive(Debug, Default)]
+pub struct SyncPage {
+    /// New and changed transactions
+    pub upserted: Vec<ExternalTransaction>,
+    /// Ids of transactions the institution withdrew
+    pub removed: Vec<String>,
+    pub next_cursor: String,
+    pub has_more: bool,
+}
+
+/// Credentials for the Plaid API
+#[derive(Debug, Clone)]
+pub struct PlaidClient {
+    base_url: String,
+    client_id: String,
+    secret: Secret<String>,
+}
+
+/// Pluggable bank data aggregator
+#[derive(Debug, Clone)]
+pub enum BankSyncProvider {
+    /// Bank sync is off; linking institutions is rejected
+    Disabled,
+    Plaid(PlaidClient),
+}
+
+impl BankSyncProvider {
+    /// Name stored with each connection
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            BankSyncProvider::Disabled => None,
+            BankSyncProvider::Plaid(_) => Some("plaid"),
+        }
+    }
+
+    fn unavailable() -> AppError {
+        AppError::ValidationError("Bank sync is not enabled on this server".to_string())
+    }
+
+    /// Trade the public token from the client's link flow for a lasting
+    /// access token, and list the institution's accounts
+    pub async fn link(&self, public_token: &str) -> Result<LinkedItem, AppError> {
+        match self {
+            BankSyncProvider::Disabled => Err(Self::unavailable()),
+            BankSyncProvider::Plaid(plaid) => plaid.link(public_token).await,
+        }
+    }
+
+    /// Changes since `cursor`, or the full history when there is none
+    pub async fn sync(
+        &self,
+        access_token: &str,
+        cursor: Option<&str>,
+    ) -> Result<SyncPage, AppError> {
+        match self {
+            BankSyncProvider::Disabled => Err(Self::unavailable()),
+            BankSyncProvider::Plaid(plaid) => plaid.sync(access_token, cursor).await,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PlaidExchange {
+    access_token: String,
+    item_id: String,
+}
+
+#[derive(Deserialize)]
+struct PlaidAccounts {
+    accounts: Vec<PlaidAccount>,
+    item: PlaidItem,
+}
+
+#[derive(Deserialize)]
+struct PlaidItem {
+    institution_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PlaidAccount {
+    account_id: String,
+    name: String,
+    mask: Option<String>,
+    balances: PlaidBalances,
+}
+
+#[derive(Deserialize)]
+struct PlaidBalances {
+    iso_currency_code: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PlaidSync {
+    added: Vec<PlaidTransaction>,
+    modified: Vec<PlaidTransaction>,
+    removed: Vec<PlaidRemoved>,
+    next_cursor: String,
+    has_more: bool,
+}
+
+#[derive(Deserialize)]
+struct PlaidTransaction {
+    transaction_id: String,
+    account_id: String,
+    /// Positive for money leaving the account
+    amount: Decimal,
+    date: NaiveDate,
+    name: Option<String>,
+    merchant_name: Option<String>,
+    #[serde(default)]
+    pending: bool,
+}
+
+#[derive(Deserialize)]
+struct PlaidRemoved {
+    transaction_id: String,
+}
+
+#[derive(Deserialize)]
+struct PlaidErrorBody {
+    error_code: Option<String>,
+    error_message: Option<String>,
+}
+
+impl PlaidClient {
+    pub fn new(base_url: String, client_id: String, secret: String) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client_id,
+            secret: Secret::new(secret),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        mut body: serde_json::Value,
+    ) -> Result<T, AppError> {
+        body["client_id"] = json!(self.client_id);
+        body["secret"] = json!(self.secret.expose_secret());
+
+        let response = reqwest::Client::new()
+            .post(format!("{}{}", self.base_url, endpoint))
+            .json(&body)
+            .timeout(PROVIDER_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Plaid request failed: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response
+                .json::<PlaidErrorBody>()
+                .await
+                .ok()
+                .map(|e| {
+                    format!(
+                        "{}: {}",
+                        e.error_code.unwrap_or_default(),
+                        e.error_message.unwrap_or_default()
+                    )
+                })
+                .unwrap_or_else(|| status.to_string());
+            return Err(AppError::InternalError(format!(
+                "Plaid {endpoint} failed ({detail})"
+            )));
+        }
+
+        response
+            .json::<T>()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Unexpected Plaid response: {e}")))
+    }
+
+    async fn link(&self, public_token: &str) -> Result<LinkedItem, AppError> {
+        let exchange: PlaidExchange = self
+            .call(
+                "/item/public_token/exchange",
+                json!({ "public_token": public_token }),
+            )
+            .await?;
+        let accounts: PlaidAccounts = self
+            .call(
+                "/accounts/get",
+                json!({ "access_token": exchange.access_token }),
+            )
+            .await?;
+
+        Ok(LinkedItem {
+            item_id: exchange.item_id,
+            access_token: exchange.access_token,
+            institution_name: accounts.item.institution_name,
+            accounts: accounts
+                .accounts
+                .into_iter()
+                .map(|a| ExternalAccount {
+                    account_id: a.account_id,
+                    name: a.name,
+                    mask: a.mask,
+                    currency: a.balances.iso_currency_code,
+                })
+                .collect(),
+        })
+    }
+
+    async fn sync(&self, access_token: &str, cursor: Option<&str>) -> Result<SyncPage, AppError> {
+        let mut body = json!({ "access_token": access_token, "count": SYNC_PAGE_SIZE });
+        if let Some(cursor) = cursor {
+            body["cursor"] = json!(cursor);
+        }
+        let page: PlaidSync = self.call("/transactions/sync", body).await?;
+
+        // Pending transactions come back under a new id once they post
+        let upserted = page
+            .added
+            .into_iter()
+            .chain(page.modified)
+            .filter(|t| !t.pending && !t.amount.is_zero())
+            .map(|t| ExternalTransaction {
+                transaction_id: t.transaction_id,
+                account_id: t.account_id,
+                amount: t.amount.abs(),
+                is_income: t.amount < Decimal::ZERO,
+                date: t.date,
+                description: t.merchant_name.or(t.name),
+            })
+            .collect();
+
+        Ok(SyncPage {
+            upserted,
+            removed: page.removed.into_iter().map(|r| r.transaction_id).collect(),
+            next_cursor: page.next_cursor,
+            has_more: page.has_more,
+        })
+    }
+}
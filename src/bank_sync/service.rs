@@ -0,0 +1,577 @@
+use serde_json::json;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::models::{
+    ApproveBankImportDto, BankAccountLink, BankConnection, BankConnectionResponse, BankImport,
+    BankImportStatus, BankSyncResult, DUPLICATE_WINDOW_DAYS,
+};
+use super::provider::{BankSyncProvider, SyncPage};
+use crate::crypto::{encrypt_field, encrypt_optional};
+use crate::errors::{AppError, ErrorCode};
+use crate::transaction::models::{CreateTransactionDto, Transaction, TransactionType};
+use crate::transaction::service::TransactionService;
+
+const CONNECTION_COLUMNS: &str = "id, owner_id, provider, access_token, institution_name, sync_cursor, last_synced_at, last_error, created_at";
+
+const LINK_COLUMNS: &str = "id, connection_id, name, mask, currency, account_id, created_at";
+
+/// Service layer for bank connections and the transactions they pull in.
+pub struct BankSyncService;
+
+impl BankSyncService {
+    /// Imports joined with their account link and, while pending, the
+    /// existing transaction they most likely duplicate
+    fn import_select() -> String {
+        format!(
+            r#"
+            SELECT i.id, i.link_id, l.name AS account_name, l.account_id, i.amount,
+                   i.transaction_type, i.transaction_date, i.description, i.status,
+                   i.transaction_id, d.id AS duplicate_of_id, i.created_at, i.updated_at
+            FROM bank_imports i
+            JOIN bank_account_links l ON l.id = i.link_id
+            LEFT JOIN LATERAL (
+                SELECT t.id
+                FROM transactions t
+                JOIN categories c ON c.id = t.category_id
+                JOIN budgets b ON b.id = c.budget_id
+                WHERE i.status = 'pending'
+                  AND b.owner_id = i.owner_id
+                  AND t.deleted_at IS NULL
+                  AND t.amount = i.amount
+                  AND t.transaction_type = i.transaction_type
+                  AND t.transaction_date::DATE
+                      BETWEEN i.transaction_date - {DUPLICATE_WINDOW_DAYS}
+                          AND i.transaction_date + {DUPLICATE_WINDOW_DAYS}
+                  AND (l.account_id IS NULL OR t.account_id IS NULL OR t.account_id = l.account_id)
+                  AND NOT EXISTS (SELECT 1 FROM bank_imports other WHERE other.transaction_id = t.id)
+                ORDER BY ABS(t.transaction_date::DATE - i.transaction_date), t.created_at
+                LIMIT 1
+            ) d ON TRUE
+            "#
+        )
+    }
+
+    /// Link an institution from the public token of the provider's link
+    /// flow. Linking the same login again refreshes its token and accounts.
+    pub async fn link(
+        pool: &PgPool,
+        provider: &BankSyncProvider,
+        owner_id: Uuid,
+        public_token: &str,
+    ) -> Result<BankConnectionResponse, AppError> {
+        let linked = provider.link(public_token).await?;
+        let provider_name = provider.name().unwrap_or_default();
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let connection = sqlx::query_as::<_, BankConnection>(&format!(
+            r#"
+            INSERT INTO bank_connections (owner_id, provider, item_id, access_token, institution_name)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (provider, item_id) DO UPDATE
+            SET access_token = EXCLUDED.access_token,
+                institution_name = EXCLUDED.institution_name,
+                updated_at = NOW()
+            WHERE bank_connections.owner_id = EXCLUDED.owner_id
+            RETURNING {CONNECTION_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(provider_name)
+        .bind(&linked.item_id)
+        .bind(encrypt_field(&linked.access_token)?)
+        .bind(&linked.institution_name)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| {
+            AppError::Conflict("This bank login is linked to another user".to_string())
+        })?
+        .decrypted()?;
+
+        for account in &linked.accounts {
+            sqlx::query(
+                r#"
+                INSERT INTO bank_account_links
+                    (connection_id, external_account_id, name, mask, currency)
+                VALUES ($1, $2, LEFT($3, 100), LEFT($4, 10), $5)
+                ON CONFLICT (connection_id, external_account_id) DO UPDATE
+                SET name = EXCLUDED.name, mask = EXCLUDED.mask, currency = EXCLUDED.currency
+                "#,
+            )
+            .bind(connection.id)
+            .bind(&account.account_id)
+            .bind(&account.name)
+            .bind(&account.mask)
+            .bind(account.currency.as_deref().filter(|c| c.len() == 3))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let accounts = Self::links(pool, &[connection.id]).await?;
+        Ok(BankConnectionResponse::new(connection, accounts))
+    }
+
+    async fn links(
+        pool: &PgPool,
+        connection_ids: &[Uuid],
+    ) -> Result<Vec<BankAccountLink>, AppError> {
+        sqlx::query_as::<_, BankAccountLink>(&format!(
+            "SELECT {LINK_COLUMNS} FROM bank_account_links WHERE connection_id = ANY($1) ORDER BY name, id"
+        ))
+        .bind(connection_ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// The user's linked institutions with their accounts
+    pub async fn list_connections(
+        pool: &PgPool,
+        owner_id: Uuid,
+    ) -> Result<Vec<BankConnectionResponse>, AppError> {
+        let connections = sqlx::query_as::<_, BankConnection>(&format!(
+            "SELECT {CONNECTION_COLUMNS} FROM bank_connections WHERE owner_id = $1 ORDER BY created_at"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let ids: Vec<Uuid> = connections.iter().map(|c| c.id).collect();
+        let links = Self::links(pool, &ids).await?;
+
+        Ok(connections
+            .into_iter()
+            .map(|connection| {
+                let accounts = links
+                    .iter()
+                    .filter(|l| l.connection_id == connection.id)
+                    .cloned()
+                    .collect();
+                BankConnectionResponse::new(connection, accounts)
+            })
+            .collect())
+    }
+
+    async fn get_connection(
+        pool: &PgPool,
+        owner_id: Uuid,
+        connection_id: Uuid,
+    ) -> Result<BankConnection, AppError> {
+        sqlx::query_as::<_, BankConnection>(&format!(
+            "SELECT {CONNECTION_COLUMNS} FROM bank_connections WHERE id = $1 AND owner_id = $2"
+        ))
+        .bind(connection_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Bank connection not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Unlink an institution. Pending imports go with it; transactions
+    /// already imported are kept.
+    pub async fn delete_connection(
+        pool: &PgPool,
+        owner_id: Uuid,
+        connection_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM bank_connections WHERE id = $1 AND owner_id = $2")
+            .bind(connection_id)
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Bank connection not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Map an institution account onto one of the user's accounts, or unmap it
+    pub async fn update_link(
+        pool: &PgPool,
+        owner_id: Uuid,
+        link_id: Uuid,
+        account_id: Option<Uuid>,
+    ) -> Result<BankAccountLink, AppError> {
+        if let Some(account_id) = account_id {
+            let owns_account = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+            )
+            .bind(account_id)
+            .bind(owner_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            if !owns_account {
+                return Err(AppError::NotFound("Account not found".to_string()));
+            }
+        }
+
+        sqlx::query_as::<_, BankAccountLink>(
+            r#"
+            UPDATE bank_account_links l
+            SET account_id = $3
+            FROM bank_connections c
+            WHERE l.id = $1 AND c.id = l.connection_id AND c.owner_id = $2
+            RETURNING l.id, l.connection_id, l.name, l.mask, l.currency, l.account_id, l.created_at
+            "#,
+        )
+        .bind(link_id)
+        .bind(owner_id)
+        .bind(account_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Linked bank account not found".to_string()))
+    }
+
+    /// Sync one of the user's connections now
+    pub async fn sync_owner_connection(
+        pool: &PgPool,
+        provider: &BankSyncProvider,
+        owner_id: Uuid,
+        connection_id: Uuid,
+    ) -> Result<BankSyncResult, AppError> {
+        let connection = Self::get_connection(pool, owner_id, connection_id).await?;
+        Self::sync_connection(pool, provider, &connection).await
+    }
+
+    /// Connections the configured provider can sync, for the sync job
+    pub async fn syncable_connections(
+        pool: &PgPool,
+        provider: &BankSyncProvider,
+    ) -> Result<Vec<BankConnection>, AppError> {
+        let Some(provider_name) = provider.name() else {
+            return Ok(Vec::new());
+        };
+
+        sqlx::query_as::<_, BankConnection>(&format!(
+            "SELECT {CONNECTION_COLUMNS} FROM bank_connections WHERE provider = $1 ORDER BY last_synced_at NULLS FIRST"
+        ))
+        .bind(provider_name)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(BankConnection::decrypted)
+        .collect()
+    }
+
+    /// Pull every change since the connection's cursor into pending imports.
+    /// Each page is stored with its cursor, so a failure resumes from the last
+    /// stored page; the failure is recorded on the connection.
+    pub async fn sync_connection(
+        pool: &PgPool,
+        provider: &BankSyncProvider,
+        connection: &BankConnection,
+    ) -> Result<BankSyncResult, AppError> {
+        let mut result = BankSyncResult::default();
+        let mut cursor = connection.sync_cursor.clone();
+
+        loop {
+            let page = match provider
+                .sync(&connection.access_token, cursor.as_deref())
+                .await
+            {
+                Ok(page) => page,
+                Err(e) => {
+                    Self::record_sync_error(pool, connection.id, &e).await;
+                    return Err(e);
+                }
+            };
+
+            let (added, removed) = Self::store_page(pool, connection, &page).await?;
+            result.imports_added += added;
+            result.imports_removed += removed;
+
+            if !page.has_more {
+                break;
+            }
+            cursor = Some(page.next_cursor);
+        }
+
+        Ok(result)
+    }
+
+    /// Store one page of changes and advance the cursor, all or nothing.
+    /// Imports already reviewed are left as they are.
+    async fn store_page(
+        pool: &PgPool,
+        connection: &BankConnection,
+        page: &SyncPage,
+    ) -> Result<(u64, u64), AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut added = 0;
+        for transaction in &page.upserted {
+            let transaction_type = if transaction.is_income {
+                TransactionType::Income
+            } else {
+                TransactionType::Expense
+            };
+            let inserted = sqlx::query_scalar::<_, bool>(
+                r#"
+                INSERT INTO bank_imports
+                    (owner_id, link_id, external_id, amount, transaction_type, transaction_date, description)
+                SELECT $1, l.id, $4, $5, $6, $7, $8
+                FROM bank_account_links l
+                WHERE l.connection_id = $2 AND l.external_account_id = $3
+                ON CONFLICT (link_id, external_id) DO UPDATE
+                SET amount = EXCLUDED.amount,
+                    transaction_type = EXCLUDED.transaction_type,
+                    transaction_date = EXCLUDED.transaction_date,
+                    description = EXCLUDED.description,
+                    updated_at = NOW()
+                WHERE bank_imports.status = 'pending'
+                -- xmax is only zero for a freshly inserted row
+                RETURNING xmax = 0
+                "#,
+            )
+            .bind(connection.owner_id)
+            .bind(connection.id)
+            .bind(&transaction.account_id)
+            .bind(&transaction.transaction_id)
+            .bind(transaction.amount)
+            .bind(transaction_type.as_str())
+            .bind(transaction.date)
+            .bind(encrypt_optional(transaction.description.as_deref())?)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            if inserted == Some(true) {
+                added += 1;
+            }
+        }
+
+        let removed = sqlx::query(
+            r#"
+            DELETE FROM bank_imports i
+            USING bank_account_links l
+            WHERE l.id = i.link_id AND l.connection_id = $1
+              AND i.external_id = ANY($2) AND i.status = 'pending'
+            "#,
+        )
+        .bind(connection.id)
+        .bind(&page.removed)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .rows_affected();
+
+        sqlx::query(
+            r#"
+            UPDATE bank_connections
+            SET sync_cursor = $2, last_synced_at = NOW(), last_error = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(connection.id)
+        .bind(&page.next_cursor)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((added, removed))
+    }
+
+    async fn record_sync_error(pool: &PgPool, connection_id: Uuid, error: &AppError) {
+        let message: String = error.to_string().chars().take(500).collect();
+        if let Err(e) = sqlx::query(
+            "UPDATE bank_connections SET last_error = $2, updated_at = NOW() WHERE id = $1",
+        )
+        .bind(connection_id)
+        .bind(message)
+        .execute(pool)
+        .await
+        {
+            warn!(connection_id = %connection_id, "Failed to record bank sync error: {}", e);
+        }
+    }
+
+    /// The user's imports with the given status, newest first
+    pub async fn list_imports(
+        pool: &PgPool,
+        owner_id: Uuid,
+        status: BankImportStatus,
+    ) -> Result<Vec<BankImport>, AppError> {
+        sqlx::query_as::<_, BankImport>(&format!(
+            "{} WHERE i.owner_id = $1 AND i.status = $2 ORDER BY i.transaction_date DESC, i.created_at DESC",
+            Self::import_select()
+        ))
+        .bind(owner_id)
+        .bind(status.as_str())
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(BankImport::decrypted)
+        .collect()
+    }
+
+    async fn get_import(
+        pool: &PgPool,
+        owner_id: Uuid,
+        import_id: Uuid,
+    ) -> Result<BankImport, AppError> {
+        sqlx::query_as::<_, BankImport>(&format!(
+            "{} WHERE i.id = $1 AND i.owner_id = $2",
+            Self::import_select()
+        ))
+        .bind(import_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Bank import not found".to_string()))?
+        .decrypted()
+    }
+
+    fn already_reviewed() -> AppError {
+        AppError::Coded(
+            ErrorCode::ImportAlreadyReviewed,
+            "Bank import was already approved or dismissed".to_string(),
+        )
+    }
+
+    /// Commit a pending import as a transaction. Fails with 409 if it looks
+    /// like an existing transaction, unless the user allows the duplicate, or
+    /// if it was already reviewed, including by a concurrent request.
+    pub async fn approve_import(
+        pool: &PgPool,
+        owner_id: Uuid,
+        import_id: Uuid,
+        dto: ApproveBankImportDto,
+    ) -> Result<(BankImport, Transaction), AppError> {
+        let import = Self::get_import(pool, owner_id, import_id).await?;
+        if import.status != BankImportStatus::Pending.as_str() {
+            return Err(Self::already_reviewed());
+        }
+        if let (Some(duplicate_of_id), false) = (import.duplicate_of_id, dto.allow_duplicate) {
+            return Err(AppError::Detailed(
+                ErrorCode::PossibleDuplicate,
+                "Bank import looks like an existing transaction".to_string(),
+                json!({ "duplicateOfId": duplicate_of_id }),
+            ));
+        }
+
+        let transaction_type = if import.transaction_type == TransactionType::Income.as_str() {
+            TransactionType::Income
+        } else {
+            TransactionType::Expense
+        };
+        let description = dto
+            .description
+            .or_else(|| import.description.clone())
+            .map(|d| d.chars().take(200).collect::<String>())
+            .filter(|d| !d.trim().is_empty());
+
+        let transaction = TransactionService::create_transaction(
+            pool,
+            owner_id,
+            CreateTransactionDto {
+                reimbursable: false,
+                reimbursement_payer: None,
+                tax_category: None,
+                currency: None,
+                exchange_rate: None,
+                pending: false,
+                payee_id: None,
+                linked_transaction_id: None,
+                fee_amount: None,
+                destination_amount: None,
+                category_id: dto.category_id,
+                account_id: dto.account_id.or(import.account_id),
+                destination_account_id: None,
+                amount: import.amount,
+                transaction_date: import.booked_at(),
+                description,
+                transaction_type,
+            },
+        )
+        .await?;
+
+        let marked = sqlx::query(
+            r#"
+            UPDATE bank_imports SET status = 'imported', transaction_id = $3, updated_at = NOW()
+            WHERE id = $1 AND owner_id = $2 AND status = 'pending'
+            "#,
+        )
+        .bind(import_id)
+        .bind(owner_id)
+        .bind(transaction.id)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()));
+
+        match marked {
+            Ok(result) if result.rows_affected() == 1 => Ok((
+                Self::get_import(pool, owner_id, import_id).await?,
+                transaction,
+            )),
+            other => {
+                // Don't leave behind a transaction for an import that wasn't approved
+                if let Err(undo) =
+                    TransactionService::delete_transaction(pool, owner_id, transaction.id).await
+                {
+                    warn!(
+                        transaction_id = %transaction.id,
+                        "Failed to remove transaction of unapproved bank import: {}", undo
+                    );
+                }
+                Err(other.err().unwrap_or_else(Self::already_reviewed))
+            }
+        }
+    }
+
+    /// Leave a pending import out; later syncs don't offer it again
+    pub async fn dismiss_import(
+        pool: &PgPool,
+        owner_id: Uuid,
+        import_id: Uuid,
+    ) -> Result<BankImport, AppError> {
+        let import = Self::get_import(pool, owner_id, import_id).await?;
+        if import.status != BankImportStatus::Pending.as_str() {
+            return Err(Self::already_reviewed());
+        }
+
+        let result = sqlx::query(
+            r#"
+            UPDATE bank_imports SET status = 'dismissed', updated_at = NOW()
+            WHERE id = $1 AND owner_id = $2 AND status = 'pending'
+            "#,
+        )
+        .bind(import_id)
+        .bind(owner_id)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(Self::already_reviewed());
+        }
+        Self::get_import(pool, owner_id, import_id).await
+    }
+}
@@ -0,0 +1,290 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+use super::models::{
+    BillIdPath, BillPayment, BillResponse, CreateBillDto, PayBillDto, PayBillResponse,
+    UpcomingBillsQuery, UpdateBillDto,
+};
+use super::service::BillService;
+
+/// GET /bills - List bills, soonest due first
+#[utoipa::path(
+    get,
+    path = "/bills",
+    tag = "Bills",
+    responses(
+        (status = 200, description = "List of bills", body = Vec<BillResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/bills")]
+pub async fn list_bills(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let bills = BillService::list(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        bills
+            .into_iter()
+            .map(BillResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// GET /bills/upcoming - Bills overdue or due soon
+#[utoipa::path(
+    get,
+    path = "/bills/upcoming",
+    tag = "Bills",
+    params(UpcomingBillsQuery),
+    responses(
+        (status = 200, description = "Overdue bills and bills due within the window", body = Vec<BillResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/bills/upcoming")]
+pub async fn upcoming_bills(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<UpcomingBillsQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let bills = BillService::upcoming(pool.get_ref(), auth.user_id, query.days).await?;
+
+    Ok(HttpResponse::Ok().json(
+        bills
+            .into_iter()
+            .map(BillResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// GET /bills/{id} - Get a bill
+#[utoipa::path(
+    get,
+    path = "/bills/{id}",
+    tag = "Bills",
+    params(BillIdPath),
+    responses(
+        (status = 200, description = "Bill details", body = BillResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/bills/{id}")]
+pub async fn get_bill(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BillIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let bill = BillService::get(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(BillResponse::from(bill)))
+}
+
+/// POST /bills - Create a bill
+#[utoipa::path(
+    post,
+    path = "/bills",
+    tag = "Bills",
+    request_body = CreateBillDto,
+    responses(
+        (status = 201, description = "Bill created", body = BillResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/bills")]
+pub async fn create_bill(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateBillDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let bill = BillService::create(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(BillResponse::from(bill)))
+}
+
+/// PATCH /bills/{id} - Update a bill
+#[utoipa::path(
+    patch,
+    path = "/bills/{id}",
+    tag = "Bills",
+    params(BillIdPath),
+    request_body = UpdateBillDto,
+    responses(
+        (status = 200, description = "Bill updated", body = BillResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Bill, category or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/bills/{id}")]
+pub async fn update_bill(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BillIdPath>,
+    body: web::Json<UpdateBillDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let bill =
+        BillService::update(pool.get_ref(), path.id, auth.user_id, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(BillResponse::from(bill)))
+}
+
+/// DELETE /bills/{id} - Delete a bill
+#[utoipa::path(
+    delete,
+    path = "/bills/{id}",
+    tag = "Bills",
+    params(BillIdPath),
+    responses(
+        (status = 200, description = "Bill deleted; payment transactions are kept", body = DeleteResponse),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/bills/{id}")]
+pub async fn delete_bill(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BillIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    BillService::delete(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Bill deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// GET /bills/{id}/payments - Payment history of a bill
+#[utoipa::path(
+    get,
+    path = "/bills/{id}/payments",
+    tag = "Bills",
+    params(BillIdPath),
+    responses(
+        (status = 200, description = "Payments, newest first", body = Vec<BillPayment>),
+        (status = 404, description = "Bill not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/bills/{id}/payments")]
+pub async fn list_bill_payments(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BillIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let payments = BillService::payments(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(payments))
+}
+
+/// POST /bills/{id}/pay - Mark the bill's next occurrence paid
+#[utoipa::path(
+    post,
+    path = "/bills/{id}/pay",
+    tag = "Bills",
+    params(BillIdPath),
+    request_body(content = Option<PayBillDto>, description = "Optional; omit to record a payment of the bill's amount now"),
+    responses(
+        (status = 200, description = "Payment recorded and the bill advanced to its next due date", body = PayBillResponse),
+        (status = 400, description = "Validation error or the linked transaction is not an expense", body = ErrorResponse),
+        (status = 404, description = "Bill, transaction, current budget or matching category not found", body = ErrorResponse),
+        (status = 409, description = "Transaction already pays a bill, or the bill was paid concurrently", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/bills/{id}/pay")]
+pub async fn pay_bill(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BillIdPath>,
+    body: Option<web::Json<PayBillDto>>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let dto = body.map(web::Json::into_inner).unwrap_or_default();
+    dto.validate()?;
+
+    let (bill, payment, transaction) =
+        BillService::pay(pool.get_ref(), path.id, auth.user_id, dto, false).await?;
+
+    let response = PayBillResponse {
+        bill: BillResponse::from(bill),
+        payment,
+        transaction: transaction.map(TransactionResponse::from),
+    };
+    if let Some(transaction) = &response.transaction {
+        WebhookService::publish(
+            pool.get_ref(),
+            auth.user_id,
+            events::TRANSACTION_CREATED,
+            transaction,
+        )
+        .await;
+    }
+    WebhookService::publish(pool.get_ref(), auth.user_id, events::BILL_PAID, &response).await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Register the bill routes, `/bills/upcoming` ahead of `/bills/{id}`
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_bills)
+        .service(upcoming_bills)
+        .service(get_bill)
+        .service(create_bill)
+        .service(update_bill)
+        .service(delete_bill)
+        .service(list_bill_payments)
+        .service(pay_bill);
+}
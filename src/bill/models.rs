@@ -0,0 +1,325 @@
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::template::models::double_option;
+use crate::transaction::models::TransactionResponse;
+
+fn validate_positive_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount <= Decimal::ZERO {
+        return Err(ValidationError::new("amount_must_be_positive"));
+    }
+    Ok(())
+}
+
+/// Due date of a bill in the given month; short months use their last day
+pub fn due_date_in(year: i32, month: u32, due_day: i16) -> NaiveDate {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).expect("valid month");
+    let last_day = (first + Months::new(1))
+        .pred_opt()
+        .expect("valid date")
+        .day();
+    first
+        .with_day((due_day as u32).clamp(1, last_day))
+        .expect("day within month")
+}
+
+/// First due date on or after `date`
+pub fn next_due_on_or_after(date: NaiveDate, due_day: i16) -> NaiveDate {
+    let this_month = due_date_in(date.year(), date.month(), due_day);
+    if this_month >= date {
+        this_month
+    } else {
+        following_due_date(this_month, due_day)
+    }
+}
+
+/// Due date of the occurrence after the one due on `due`
+pub fn following_due_date(due: NaiveDate, due_day: i16) -> NaiveDate {
+    let next_month = due.with_day(1).expect("valid date") + Months::new(1);
+    due_date_in(next_month.year(), next_month.month(), due_day)
+}
+
+/// Database entity for bills
+#[derive(Debug, Clone, FromRow)]
+pub struct Bill {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub amount: Decimal,
+    pub due_day: i16,
+    pub category_name: String,
+    pub account_id: Option<Uuid>,
+    pub autopay: bool,
+    pub reminder_days: i16,
+    pub next_due_date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// The owner's current date, in their timezone
+    pub today: NaiveDate,
+}
+
+impl Bill {
+    pub fn days_until_due(&self) -> i64 {
+        (self.next_due_date - self.today).num_days()
+    }
+
+    pub fn status(&self) -> BillStatus {
+        match self.days_until_due() {
+            days if days < 0 => BillStatus::Overdue,
+            days if days <= self.reminder_days as i64 => BillStatus::Due,
+            _ => BillStatus::Scheduled,
+        }
+    }
+}
+
+/// Where a bill's next occurrence stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BillStatus {
+    /// Not due within the reminder window
+    Scheduled,
+    /// Due today or within the reminder window
+    Due,
+    /// The due date has passed without a payment
+    Overdue,
+}
+
+/// Bill returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillResponse {
+    pub id: Uuid,
+    #[schema(example = "Rent")]
+    pub name: String,
+    #[schema(example = 1200.00)]
+    pub amount: Decimal,
+    /// Day of the month the bill is due (1-31)
+    #[schema(example = 1)]
+    pub due_day: i16,
+    /// Category name, matched against the current month's budget when paid
+    #[schema(example = "Housing")]
+    pub category_name: String,
+    pub account_id: Option<Uuid>,
+    /// Whether payments are recorded automatically on the due date
+    pub autopay: bool,
+    /// Days before the due date the reminder is sent
+    #[schema(example = 3)]
+    pub reminder_days: i16,
+    /// Due date of the oldest unpaid occurrence
+    pub next_due_date: NaiveDate,
+    /// Negative when overdue
+    #[schema(example = 5)]
+    pub days_until_due: i64,
+    pub status: BillStatus,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Bill> for BillResponse {
+    fn from(b: Bill) -> Self {
+        Self {
+            days_until_due: b.days_until_due(),
+            status: b.status(),
+            id: b.id,
+            name: b.name,
+            amount: b.amount,
+            due_day: b.due_day,
+            category_name: b.category_name,
+            account_id: b.account_id,
+            autopay: b.autopay,
+            reminder_days: b.reminder_days,
+            next_due_date: b.next_due_date,
+            created_at: b.created_at,
+            updated_at: b.updated_at,
+        }
+    }
+}
+
+fn default_reminder_days() -> i16 {
+    3
+}
+
+/// Request body for creating a bill
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateBillDto {
+    /// Display name (1-50 characters)
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    #[schema(example = "Rent")]
+    pub name: String,
+
+    /// Amount due each month
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 1200.00)]
+    pub amount: Decimal,
+
+    /// Day of the month the bill is due (1-31)
+    #[validate(range(min = 1, max = 31, message = "Due day must be between 1 and 31"))]
+    #[schema(example = 1)]
+    pub due_day: i16,
+
+    /// Category whose name payments are filed under
+    pub category_id: Uuid,
+
+    /// Account the bill is paid from (optional)
+    pub account_id: Option<Uuid>,
+
+    /// Record payments automatically on the due date (defaults to false)
+    #[serde(default)]
+    pub autopay: bool,
+
+    /// Days before the due date to send the reminder (0-30, defaults to 3)
+    #[validate(range(min = 0, max = 30, message = "Reminder days must be between 0 and 30"))]
+    #[serde(default = "default_reminder_days")]
+    #[schema(example = 3)]
+    pub reminder_days: i16,
+
+    /// First unpaid due date (defaults to the next due day from today)
+    pub first_due_date: Option<NaiveDate>,
+}
+
+/// Request body for updating a bill (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateBillDto {
+    /// Display name
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: Option<String>,
+
+    /// Amount due each month
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    pub amount: Option<Decimal>,
+
+    /// Day of the month the bill is due; moves the next due date within its month
+    #[validate(range(min = 1, max = 31, message = "Due day must be between 1 and 31"))]
+    pub due_day: Option<i16>,
+
+    /// Category whose name payments are filed under
+    pub category_id: Option<Uuid>,
+
+    /// Account ID (use null to remove account association)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub account_id: Option<Option<Uuid>>,
+
+    /// Record payments automatically on the due date
+    pub autopay: Option<bool>,
+
+    /// Days before the due date to send the reminder (0-30)
+    #[validate(range(min = 0, max = 30, message = "Reminder days must be between 0 and 30"))]
+    pub reminder_days: Option<i16>,
+}
+
+/// Request body for marking a bill paid; send `{}` to record a payment of the
+/// bill's amount now
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayBillDto {
+    /// Existing expense that paid the bill; when absent a transaction is created
+    pub transaction_id: Option<Uuid>,
+
+    /// Amount paid (defaults to the bill's amount; ignored with transactionId)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 1200.00)]
+    pub amount: Option<Decimal>,
+
+    /// Payment date (defaults to now; ignored with transactionId)
+    pub transaction_date: Option<DateTime<Utc>>,
+}
+
+/// Database entity for a paid bill occurrence
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillPayment {
+    pub id: Uuid,
+    pub bill_id: Uuid,
+    /// Transaction recording the payment (null once that transaction is deleted)
+    pub transaction_id: Option<Uuid>,
+    /// Due date of the occurrence this pays
+    pub due_date: NaiveDate,
+    #[schema(example = 1200.00)]
+    pub amount: Decimal,
+    /// Whether the payment was recorded by autopay
+    pub autopay: bool,
+    pub paid_at: DateTime<Utc>,
+}
+
+/// Response for POST /bills/{id}/pay
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayBillResponse {
+    /// The bill, advanced to its next occurrence
+    pub bill: BillResponse,
+    pub payment: BillPayment,
+    /// Transaction created for the payment (absent when an existing one was linked)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction: Option<TransactionResponse>,
+}
+
+fn default_upcoming_days() -> i64 {
+    14
+}
+
+/// Query parameters for upcoming bills
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct UpcomingBillsQuery {
+    /// Include bills due within this many days (1-90, default 14); overdue bills are always included
+    #[validate(range(min = 1, max = 90, message = "days must be between 1 and 90"))]
+    #[serde(default = "default_upcoming_days")]
+    #[param(example = 14)]
+    pub days: i64,
+}
+
+/// Path parameters for bill ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BillIdPath {
+    /// Bill UUID
+    pub id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_due_dates_clamp_to_month_end() {
+        assert_eq!(due_date_in(2026, 2, 31), date(2026, 2, 28));
+        assert_eq!(due_date_in(2028, 2, 30), date(2028, 2, 29));
+
+        assert_eq!(
+            next_due_on_or_after(date(2026, 3, 15), 15),
+            date(2026, 3, 15)
+        );
+        assert_eq!(
+            next_due_on_or_after(date(2026, 3, 16), 15),
+            date(2026, 4, 15)
+        );
+        assert_eq!(
+            next_due_on_or_after(date(2026, 12, 20), 5),
+            date(2027, 1, 5)
+        );
+
+        // Clamped in February, back to the 31st afterwards
+        let feb = following_due_date(date(2026, 1, 31), 31);
+        assert_eq!(feb, date(2026, 2, 28));
+        assert_eq!(following_due_date(feb, 31), date(2026, 3, 31));
+    }
+}
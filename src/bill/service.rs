@@ -0,0 +1,431 @@
+use chrono::{Datelike, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::models::{
+    due_date_in, following_due_date, next_due_on_or_after, Bill, BillPayment, CreateBillDto,
+    PayBillDto, UpdateBillDto,
+};
+use crate::account::service::AccountService;
+use crate::category::service::CategoryService;
+use crate::errors::{AppError, ErrorCode};
+use crate::transaction::models::{CreateTransactionDto, Transaction, TransactionType};
+use crate::transaction::service::TransactionService;
+
+const BILL_COLUMNS: &str = "b.id, b.owner_id, b.name, b.amount, b.due_day, b.category_name, b.account_id, b.autopay, b.reminder_days, b.next_due_date, b.created_at, b.updated_at, \
+     (SELECT (NOW() AT TIME ZONE u.timezone)::DATE FROM users u WHERE u.id = b.owner_id) AS today";
+
+const PAYMENT_COLUMNS: &str = "id, bill_id, transaction_id, due_date, amount, autopay, paid_at";
+
+/// Service layer for bills.
+pub struct BillService;
+
+impl BillService {
+    /// List bills, soonest due first
+    pub async fn list(pool: &PgPool, owner_id: Uuid) -> Result<Vec<Bill>, AppError> {
+        sqlx::query_as::<_, Bill>(&format!(
+            "SELECT {BILL_COLUMNS} FROM bills b WHERE b.owner_id = $1 ORDER BY b.next_due_date, b.name"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Bills overdue or due within `days` days, soonest first
+    pub async fn upcoming(pool: &PgPool, owner_id: Uuid, days: i64) -> Result<Vec<Bill>, AppError> {
+        sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            SELECT {BILL_COLUMNS}
+            FROM bills b
+            INNER JOIN users owner ON owner.id = b.owner_id
+            WHERE b.owner_id = $1
+              AND b.next_due_date <= (NOW() AT TIME ZONE owner.timezone)::DATE + $2::INTEGER
+            ORDER BY b.next_due_date, b.name
+            "#
+        ))
+        .bind(owner_id)
+        .bind(days as i32)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Get a bill by ID
+    pub async fn get(pool: &PgPool, bill_id: Uuid, owner_id: Uuid) -> Result<Bill, AppError> {
+        sqlx::query_as::<_, Bill>(&format!(
+            "SELECT {BILL_COLUMNS} FROM bills b WHERE b.id = $1 AND b.owner_id = $2"
+        ))
+        .bind(bill_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Bill not found".to_string()))
+    }
+
+    /// The owner's current date, in their timezone
+    async fn today(pool: &PgPool, owner_id: Uuid) -> Result<NaiveDate, AppError> {
+        sqlx::query_scalar::<_, NaiveDate>(
+            "SELECT (NOW() AT TIME ZONE timezone)::DATE FROM users WHERE id = $1",
+        )
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Create a bill
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateBillDto,
+    ) -> Result<Bill, AppError> {
+        let name = dto.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Name cannot be empty".to_string(),
+            ));
+        }
+
+        let category = CategoryService::get_by_id(pool, dto.category_id, owner_id).await?;
+        if let Some(account_id) = dto.account_id {
+            AccountService::get_account_by_id(pool, account_id, owner_id).await?;
+        }
+        let next_due_date = match dto.first_due_date {
+            Some(date) => date,
+            None => next_due_on_or_after(Self::today(pool, owner_id).await?, dto.due_day),
+        };
+
+        sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            INSERT INTO bills AS b
+                (owner_id, name, amount, due_day, category_name, account_id, autopay,
+                 reminder_days, next_due_date)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING {BILL_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(&name)
+        .bind(dto.amount)
+        .bind(dto.due_day)
+        .bind(&category.name)
+        .bind(dto.account_id)
+        .bind(dto.autopay)
+        .bind(dto.reminder_days)
+        .bind(next_due_date)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Update a bill (partial update - PATCH semantics)
+    pub async fn update(
+        pool: &PgPool,
+        bill_id: Uuid,
+        owner_id: Uuid,
+        dto: UpdateBillDto,
+    ) -> Result<Bill, AppError> {
+        let current = Self::get(pool, bill_id, owner_id).await?;
+
+        let name = match dto.name {
+            Some(n) => {
+                let trimmed = n.trim().to_string();
+                if trimmed.is_empty() {
+                    return Err(AppError::ValidationError(
+                        "Name cannot be empty".to_string(),
+                    ));
+                }
+                trimmed
+            }
+            None => current.name,
+        };
+        let category_name = match dto.category_id {
+            Some(category_id) => {
+                CategoryService::get_by_id(pool, category_id, owner_id)
+                    .await?
+                    .name
+            }
+            None => current.category_name,
+        };
+        let account_id = dto.account_id.unwrap_or(current.account_id);
+        if let Some(account_id) = account_id {
+            if Some(account_id) != current.account_id {
+                AccountService::get_account_by_id(pool, account_id, owner_id).await?;
+            }
+        }
+        // A new due day moves the next occurrence within its month
+        let due_day = dto.due_day.unwrap_or(current.due_day);
+        let next_due_date = if due_day != current.due_day {
+            let due = current.next_due_date;
+            due_date_in(due.year(), due.month(), due_day)
+        } else {
+            current.next_due_date
+        };
+
+        sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            UPDATE bills b SET
+                name = $3,
+                amount = $4,
+                due_day = $5,
+                category_name = $6,
+                account_id = $7,
+                autopay = $8,
+                reminder_days = $9,
+                next_due_date = $10
+            WHERE b.id = $1 AND b.owner_id = $2
+            RETURNING {BILL_COLUMNS}
+            "#
+        ))
+        .bind(bill_id)
+        .bind(owner_id)
+        .bind(&name)
+        .bind(dto.amount.unwrap_or(current.amount))
+        .bind(due_day)
+        .bind(&category_name)
+        .bind(account_id)
+        .bind(dto.autopay.unwrap_or(current.autopay))
+        .bind(dto.reminder_days.unwrap_or(current.reminder_days))
+        .bind(next_due_date)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Delete a bill and its payment history (the transactions stay)
+    pub async fn delete(pool: &PgPool, bill_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM bills WHERE id = $1 AND owner_id = $2")
+            .bind(bill_id)
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Bill not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Payments recorded for a bill, newest first
+    pub async fn payments(
+        pool: &PgPool,
+        bill_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Vec<BillPayment>, AppError> {
+        Self::get(pool, bill_id, owner_id).await?;
+
+        sqlx::query_as::<_, BillPayment>(&format!(
+            "SELECT {PAYMENT_COLUMNS} FROM bill_payments WHERE bill_id = $1 ORDER BY due_date DESC"
+        ))
+        .bind(bill_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Mark the bill's oldest unpaid occurrence paid and advance it to the
+    /// next month. Links `dto.transaction_id` when given, otherwise creates an
+    /// expense in the same-named category of the current month's budget.
+    /// Returns the created transaction, if any.
+    pub async fn pay(
+        pool: &PgPool,
+        bill_id: Uuid,
+        owner_id: Uuid,
+        dto: PayBillDto,
+        autopay: bool,
+    ) -> Result<(Bill, BillPayment, Option<Transaction>), AppError> {
+        let bill = Self::get(pool, bill_id, owner_id).await?;
+
+        let (transaction_id, amount, created) = match dto.transaction_id {
+            Some(transaction_id) => {
+                let transaction =
+                    TransactionService::get_transaction(pool, owner_id, transaction_id).await?;
+                if transaction.get_type() != TransactionType::Expense
+                    || transaction.reversal_of_id.is_some()
+                {
+                    return Err(AppError::ValidationError(
+                        "Only an expense can pay a bill".to_string(),
+                    ));
+                }
+                let linked = sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS(SELECT 1 FROM bill_payments WHERE transaction_id = $1)",
+                )
+                .bind(transaction_id)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+                if linked {
+                    return Err(AppError::Coded(
+                        ErrorCode::TransactionAlreadyPaysBill,
+                        "Transaction already pays a bill".to_string(),
+                    ));
+                }
+                (transaction_id, transaction.amount, None)
+            }
+            None => {
+                let category_id =
+                    CategoryService::current_id_by_name(pool, owner_id, &bill.category_name)
+                        .await?;
+                let transaction = TransactionService::create_transaction(
+                    pool,
+                    owner_id,
+                    CreateTransactionDto {
+                        reimbursable: false,
+                        reimbursement_payer: None,
+                        tax_category: None,
+                        currency: None,
+                        exchange_rate: None,
+                        pending: false,
+                        payee_id: None,
+                        linked_transaction_id: None,
+                        fee_amount: None,
+                        destination_amount: None,
+                        category_id,
+                        account_id: bill.account_id,
+                        destination_account_id: None,
+                        amount: dto.amount.unwrap_or(bill.amount),
+                        transaction_date: dto.transaction_date.unwrap_or_else(Utc::now),
+                        description: Some(bill.name.clone()),
+                        transaction_type: TransactionType::Expense,
+                    },
+                )
+                .await?;
+                (transaction.id, transaction.amount, Some(transaction))
+            }
+        };
+
+        match Self::record_payment(pool, &bill, transaction_id, amount, autopay).await {
+            Ok((bill, payment)) => Ok((bill, payment, created)),
+            Err(e) => {
+                // Don't leave behind a transaction for a payment that wasn't recorded
+                if let Some(transaction) = &created {
+                    if let Err(undo) =
+                        TransactionService::delete_transaction(pool, owner_id, transaction.id).await
+                    {
+                        warn!(
+                            transaction_id = %transaction.id,
+                            "Failed to remove transaction of unrecorded bill payment: {}", undo
+                        );
+                    }
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Advance the bill past `bill.next_due_date` and record the payment.
+    /// Fails with 409 if another payment advanced it first.
+    async fn record_payment(
+        pool: &PgPool,
+        bill: &Bill,
+        transaction_id: Uuid,
+        amount: Decimal,
+        autopay: bool,
+    ) -> Result<(Bill, BillPayment), AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let advanced = sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            UPDATE bills b SET next_due_date = $3
+            WHERE b.id = $1 AND b.next_due_date = $2
+            RETURNING {BILL_COLUMNS}
+            "#
+        ))
+        .bind(bill.id)
+        .bind(bill.next_due_date)
+        .bind(following_due_date(bill.next_due_date, bill.due_day))
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| {
+            AppError::Coded(
+                ErrorCode::BillAlreadyPaid,
+                "Bill was paid by another request".to_string(),
+            )
+        })?;
+
+        let payment = sqlx::query_as::<_, BillPayment>(&format!(
+            r#"
+            INSERT INTO bill_payments (bill_id, transaction_id, due_date, amount, autopay)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {PAYMENT_COLUMNS}
+            "#
+        ))
+        .bind(bill.id)
+        .bind(transaction_id)
+        .bind(bill.next_due_date)
+        .bind(amount)
+        .bind(autopay)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((advanced, payment))
+    }
+
+    /// Autopay bills whose due date has arrived in the owner's timezone
+    pub async fn autopay_due(pool: &PgPool) -> Result<Vec<Bill>, AppError> {
+        sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            SELECT {BILL_COLUMNS}
+            FROM bills b
+            INNER JOIN users owner ON owner.id = b.owner_id
+            WHERE b.autopay
+              AND b.next_due_date <= (NOW() AT TIME ZONE owner.timezone)::DATE
+            ORDER BY b.next_due_date
+            "#
+        ))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Claim the bills whose reminder window has opened and whose reminder
+    /// hasn't been sent for the current due date
+    pub async fn claim_reminders(pool: &PgPool) -> Result<Vec<Bill>, AppError> {
+        sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            UPDATE bills b SET reminded_for = b.next_due_date
+            FROM users owner
+            WHERE owner.id = b.owner_id
+              AND b.reminded_for IS DISTINCT FROM b.next_due_date
+              AND b.next_due_date >= (NOW() AT TIME ZONE owner.timezone)::DATE
+              AND b.next_due_date - b.reminder_days <= (NOW() AT TIME ZONE owner.timezone)::DATE
+            RETURNING {BILL_COLUMNS}
+            "#
+        ))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Claim the bills that became overdue and haven't been flagged for the
+    /// current due date
+    pub async fn claim_overdue(pool: &PgPool) -> Result<Vec<Bill>, AppError> {
+        sqlx::query_as::<_, Bill>(&format!(
+            r#"
+            UPDATE bills b SET overdue_notified_for = b.next_due_date
+            FROM users owner
+            WHERE owner.id = b.owner_id
+              AND b.overdue_notified_for IS DISTINCT FROM b.next_due_date
+              AND b.next_due_date < (NOW() AT TIME ZONE owner.timezone)::DATE
+            RETURNING {BILL_COLUMNS}
+            "#
+        ))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+}
@@ -0,0 +1,69 @@
+//! Runs the test suite against a disposable Postgres container, for machines
+//! and CI runners without a provisioned `budget_db`. Needs a Docker daemon.
+//!
+//! ```bash
+//! cargo run --features testcontainers --bin test-postgres             # cargo test
+//! cargo run --features testcontainers --bin test-postgres -- api_tests # extra cargo test args
+//! ```
+//!
+//! The container is removed when the run finishes.
+
+use std::env;
+use std::process::ExitCode;
+
+use sqlx::PgPool;
+use testcontainers_modules::postgres::Postgres;
+use testcontainers_modules::testcontainers::runners::AsyncRunner;
+use testcontainers_modules::testcontainers::ImageExt;
+
+/// Migrations rely on the built-in gen_random_uuid() from Postgres 13
+const POSTGRES_TAG: &str = "16-alpine";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let container = Postgres::default()
+        .with_db_name("budget_db")
+        .with_tag(POSTGRES_TAG)
+        .start()
+        .await
+        .expect("Failed to start Postgres container (is Docker running?)");
+    let host = container
+        .get_host()
+        .await
+        .expect("Failed to resolve container host");
+    let port = container
+        .get_host_port_ipv4(5432)
+        .await
+        .expect("Failed to resolve container port");
+    let database_url = format!("postgres://postgres:postgres@{host}:{port}/budget_db");
+    println!("Postgres container ready at {database_url}");
+
+    // Migrate the main database too, for anything that doesn't use #[sqlx::test]
+    let pool = PgPool::connect(&database_url)
+        .await
+        .expect("Failed to connect to Postgres container");
+    sqlx::migrate!()
+        .run(&pool)
+        .await
+        .expect("Failed to run migrations");
+    pool.close().await;
+
+    // `cargo run` sets CARGO to the cargo binary that invoked us
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = tokio::process::Command::new(cargo)
+        .arg("test")
+        .args(env::args().skip(1))
+        .env("DATABASE_URL", &database_url)
+        .status()
+        .await
+        .expect("Failed to run cargo test");
+
+    // Stop and remove the container before reporting the result
+    drop(container);
+
+    match status.code() {
+        Some(0) => ExitCode::SUCCESS,
+        Some(code) => ExitCode::from(code.clamp(1, 255) as u8),
+        None => ExitCode::FAILURE,
+    }
+}
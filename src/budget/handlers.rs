@@ -1,25 +1,37 @@
+use actix_web::http::header;
 use actix_web::{delete, get, patch, post, web, HttpResponse};
 use sqlx::PgPool;
 use validator::Validate;
 
+use crate::auth::scopes::Scope;
+use crate::category::models::CategoryResponse;
+use crate::category::service::CategoryService;
+use crate::category::templates::ApplyCategoryTemplateDto;
 use crate::errors::{AppError, ErrorResponse};
-use crate::extractors::AuthenticatedUser;
+use crate::extractors::{etag, AuthenticatedUser, IfMatch};
 
 use super::models::{
-    BudgetIdPath, BudgetResponse, CreateBudgetDto, ListBudgetsQuery, MonthYearPath,
-    UpdateBudgetDto, UpdateIncomeDto, UpdateSavingsRateDto,
+    AutoAllocateQuery, AutoAllocateResponse, BudgetHealthQuery, BudgetHealthResponse, BudgetIdPath,
+    BudgetProjectionResponse, BudgetResponse, BudgetWithCategoriesResponse, CopyBudgetDto,
+    CreateBudgetDto, ListBudgetsQuery, MonthBaseQuery, MonthSummary, MonthYearPath,
+    MoveAllocationDto, MoveAllocationResponse, UnallocatedResponse, UpdateBudgetDto,
+    UpdateIncomeDto, UpdateSavingsRateDto, YearPath,
 };
 use super::service::BudgetService;
+use super::sharing::{
+    BudgetMemberPath, InvitationIdPath, InvitationResponse, InviteMemberDto, MemberResponse,
+};
 
 /// GET /budgets - List all budgets for the authenticated user
 #[utoipa::path(
     get,
     path = "/budgets",
     tag = "Budgets",
-    params(ListBudgetsQuery),
+    params(ListBudgetsQuery, MonthBaseQuery),
     responses(
         (status = 200, description = "List of budgets", body = Vec<BudgetResponse>),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -27,17 +39,18 @@ use super::service::BudgetService;
 pub async fn list_budgets(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
     query: web::Query<ListBudgetsQuery>,
 ) -> Result<HttpResponse, AppError> {
-    query
-        .validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    query.validate()?;
 
     let budgets = BudgetService::list_budgets(pool.get_ref(), auth.user_id, &query).await?;
 
     let response: Vec<BudgetResponse> = budgets
         .into_iter()
-        .map(BudgetResponse::from_budget)
+        .map(|b| BudgetResponse::from_budget(b).with_month_base(&month_base))
         .collect();
 
     Ok(HttpResponse::Ok().json(response))
@@ -48,23 +61,156 @@ pub async fn list_budgets(
     get,
     path = "/budgets/{id}",
     tag = "Budgets",
-    params(BudgetIdPath),
+    params(BudgetIdPath, MonthBaseQuery),
     responses(
         (status = 200, description = "Budget details", body = BudgetResponse),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
 #[get("/budgets/{id}")]
 pub async fn get_budget(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    path: web::Path<BudgetIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    let budget = BudgetService::get_budget_by_id(pool.get_ref(), path.id, auth.user_id).await?;
+
+    let response = BudgetResponse::from_budget(budget).with_month_base(&month_base);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
+}
+
+/// GET /budgets/{id}/full - Get a budget with its categories and spending totals
+#[utoipa::path(
+    get,
+    path = "/budgets/{id}/full",
+    tag = "Budgets",
+    params(BudgetIdPath, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Budget with categories and totals", body = BudgetWithCategoriesResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/{id}/full")]
+pub async fn get_budget_full(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    path: web::Path<BudgetIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+    auth.require_scope(Scope::CategoriesRead)?;
+
+    let budget = BudgetService::get_budget_by_id(pool.get_ref(), path.id, auth.user_id).await?;
+    let categories =
+        CategoryService::get_by_budget_id(pool.get_ref(), path.id, auth.user_id).await?;
+
+    let response = BudgetWithCategoriesResponse::new(
+        BudgetResponse::from_budget(budget).with_month_base(&month_base),
+        categories
+            .into_iter()
+            .map(CategoryResponse::from_category_with_spent)
+            .collect(),
+    );
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.budget.updated_at)))
+        .json(response))
+}
+
+/// GET /budgets/{id}/unallocated - Get the "to be budgeted" amount
+#[utoipa::path(
+    get,
+    path = "/budgets/{id}/unallocated",
+    tag = "Budgets",
+    params(BudgetIdPath),
+    responses(
+        (status = 200, description = "Unallocated income for the budget", body = UnallocatedResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/{id}/unallocated")]
+pub async fn get_unallocated(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
     path: web::Path<BudgetIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
     let budget = BudgetService::get_budget_by_id(pool.get_ref(), path.id, auth.user_id).await?;
 
-    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget)))
+    Ok(HttpResponse::Ok().json(UnallocatedResponse::from_budget(&budget)))
+}
+
+/// GET /budgets/{id}/health - Get the budget health score
+#[utoipa::path(
+    get,
+    path = "/budgets/{id}/health",
+    tag = "Budgets",
+    params(BudgetIdPath, BudgetHealthQuery),
+    responses(
+        (status = 200, description = "Budget health score and component metrics", body = BudgetHealthResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/{id}/health")]
+pub async fn get_budget_health(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+    query: web::Query<BudgetHealthQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    query.validate()?;
+
+    let health =
+        BudgetService::get_health(pool.get_ref(), path.id, auth.user_id, query.threshold).await?;
+
+    Ok(HttpResponse::Ok().json(health))
+}
+
+/// GET /budgets/{id}/projection - Project end-of-month spending
+#[utoipa::path(
+    get,
+    path = "/budgets/{id}/projection",
+    tag = "Budgets",
+    params(BudgetIdPath),
+    responses(
+        (status = 200, description = "Projected spending per category and overall", body = BudgetProjectionResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/{id}/projection")]
+pub async fn get_budget_projection(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    let projection = BudgetService::get_projection(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(projection))
 }
 
 /// GET /budgets/month/{month}/year/{year} - Get budget for specific month/year
@@ -72,11 +218,12 @@ pub async fn get_budget(
     get,
     path = "/budgets/month/{month}/year/{year}",
     tag = "Budgets",
-    params(MonthYearPath),
+    params(MonthYearPath, MonthBaseQuery),
     responses(
         (status = 200, description = "Budget details", body = BudgetResponse),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -84,20 +231,55 @@ pub async fn get_budget(
 pub async fn get_budget_by_month_year(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
     path: web::Path<MonthYearPath>,
 ) -> Result<HttpResponse, AppError> {
-    path.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::BudgetsRead)?;
 
-    let budget = BudgetService::get_budget_by_month_year(
-        pool.get_ref(),
-        auth.user_id,
-        path.month,
-        path.year,
-    )
-    .await?;
+    path.validate()?;
 
-    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget)))
+    let month = month_base.to_internal(path.month)?;
+
+    let budget =
+        BudgetService::get_budget_by_month_year(pool.get_ref(), auth.user_id, month, path.year)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget).with_month_base(&month_base)))
+}
+
+/// GET /budgets/year/{year}/summary - Budgeted and actual figures for each month of a year
+#[utoipa::path(
+    get,
+    path = "/budgets/year/{year}/summary",
+    tag = "Budgets",
+    params(YearPath, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Twelve months, January first, with zeroes where a month has no budget or transactions", body = Vec<MonthSummary>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/year/{year}/summary")]
+pub async fn get_year_summary(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    path: web::Path<YearPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    path.validate()?;
+
+    let months: Vec<MonthSummary> =
+        BudgetService::get_year_summary(pool.get_ref(), auth.user_id, path.year)
+            .await?
+            .into_iter()
+            .map(|month| month.with_month_base(&month_base))
+            .collect();
+
+    Ok(HttpResponse::Ok().json(months))
 }
 
 /// POST /budgets - Create a new budget
@@ -105,12 +287,15 @@ pub async fn get_budget_by_month_year(
     post,
     path = "/budgets",
     tag = "Budgets",
+    params(MonthBaseQuery),
     request_body = CreateBudgetDto,
     responses(
         (status = 201, description = "Budget created", body = BudgetResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category template not found", body = ErrorResponse),
         (status = 409, description = "Budget already exists for this month/year", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -118,16 +303,77 @@ pub async fn get_budget_by_month_year(
 pub async fn create_budget(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
     body: web::Json<CreateBudgetDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::BudgetsWrite)?;
+    if body.template_id.is_some() {
+        auth.require_scope(Scope::CategoriesWrite)?;
+    }
+
+    body.validate()?;
     body.validate_decimals()
         .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
-    let budget = BudgetService::create_budget(pool.get_ref(), auth.user_id, &body).await?;
+    let mut dto = body.into_inner();
+    dto.month = month_base.to_internal(dto.month)?;
 
-    Ok(HttpResponse::Created().json(BudgetResponse::from_budget(budget)))
+    let budget = BudgetService::create_budget(pool.get_ref(), auth.user_id, &dto).await?;
+
+    let response = BudgetResponse::from_budget(budget).with_month_base(&month_base);
+    Ok(HttpResponse::Created()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
+}
+
+/// POST /budgets/copy - Copy a month's budget and its categories into another month
+#[utoipa::path(
+    post,
+    path = "/budgets/copy",
+    tag = "Budgets",
+    params(MonthBaseQuery),
+    request_body = CopyBudgetDto,
+    responses(
+        (status = 201, description = "Budget created with the copied categories", body = BudgetWithCategoriesResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Source budget not found", body = ErrorResponse),
+        (status = 409, description = "Budget already exists for the target month/year", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/budgets/copy")]
+pub async fn copy_budget(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    body: web::Json<CopyBudgetDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    body.validate()?;
+    body.validate_periods()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let mut dto = body.into_inner();
+    dto.source_month = month_base.to_internal(dto.source_month)?;
+    dto.target_month = month_base.to_internal(dto.target_month)?;
+
+    let (budget, categories) =
+        BudgetService::copy_budget(pool.get_ref(), auth.user_id, &dto).await?;
+
+    let response = BudgetWithCategoriesResponse::new(
+        BudgetResponse::from_budget(budget).with_month_base(&month_base),
+        categories
+            .into_iter()
+            .map(CategoryResponse::from_category)
+            .collect(),
+    );
+    Ok(HttpResponse::Created()
+        .insert_header((header::ETAG, etag(&response.budget.updated_at)))
+        .json(response))
 }
 
 /// PATCH /budgets/{id} - Update a budget (partial update)
@@ -135,13 +381,15 @@ pub async fn create_budget(
     patch,
     path = "/budgets/{id}",
     tag = "Budgets",
-    params(BudgetIdPath),
+    params(BudgetIdPath, MonthBaseQuery),
     request_body = UpdateBudgetDto,
     responses(
         (status = 200, description = "Budget updated", body = BudgetResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -149,17 +397,28 @@ pub async fn create_budget(
 pub async fn update_budget(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
+    month_base: MonthBaseQuery,
     path: web::Path<BudgetIdPath>,
     body: web::Json<UpdateBudgetDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    body.validate()?;
     body.validate_decimals()
         .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
-    let budget = BudgetService::update_budget(pool.get_ref(), path.id, auth.user_id, &body).await?;
+    let mut dto = body.into_inner();
+    dto.month = dto.month.map(|m| month_base.to_internal(m)).transpose()?;
 
-    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget)))
+    let budget =
+        BudgetService::update_budget(pool.get_ref(), path.id, auth.user_id, &dto, &if_match)
+            .await?;
+
+    let response = BudgetResponse::from_budget(budget).with_month_base(&month_base);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// PATCH /budgets/{id}/income - Update income only
@@ -167,13 +426,15 @@ pub async fn update_budget(
     patch,
     path = "/budgets/{id}/income",
     tag = "Budgets",
-    params(BudgetIdPath),
+    params(BudgetIdPath, MonthBaseQuery),
     request_body = UpdateIncomeDto,
     responses(
         (status = 200, description = "Income updated", body = BudgetResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -181,15 +442,23 @@ pub async fn update_budget(
 pub async fn update_income(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
+    month_base: MonthBaseQuery,
     path: web::Path<BudgetIdPath>,
     body: web::Json<UpdateIncomeDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::BudgetsWrite)?;
 
-    let budget = BudgetService::update_income(pool.get_ref(), path.id, auth.user_id, &body).await?;
+    body.validate()?;
+
+    let budget =
+        BudgetService::update_income(pool.get_ref(), path.id, auth.user_id, &body, &if_match)
+            .await?;
 
-    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget)))
+    let response = BudgetResponse::from_budget(budget).with_month_base(&month_base);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// PATCH /budgets/{id}/savings-rate - Update savings rate only
@@ -197,13 +466,15 @@ pub async fn update_income(
     patch,
     path = "/budgets/{id}/savings-rate",
     tag = "Budgets",
-    params(BudgetIdPath),
+    params(BudgetIdPath, MonthBaseQuery),
     request_body = UpdateSavingsRateDto,
     responses(
         (status = 200, description = "Savings rate updated", body = BudgetResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -211,16 +482,89 @@ pub async fn update_income(
 pub async fn update_savings_rate(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
+    month_base: MonthBaseQuery,
     path: web::Path<BudgetIdPath>,
     body: web::Json<UpdateSavingsRateDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    body.validate()?;
 
     let budget =
-        BudgetService::update_savings_rate(pool.get_ref(), path.id, auth.user_id, &body).await?;
+        BudgetService::update_savings_rate(pool.get_ref(), path.id, auth.user_id, &body, &if_match)
+            .await?;
+
+    let response = BudgetResponse::from_budget(budget).with_month_base(&month_base);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
+}
+
+/// POST /budgets/{id}/auto-allocate - Set allocations from average past spending
+#[utoipa::path(
+    post,
+    path = "/budgets/{id}/auto-allocate",
+    tag = "Budgets",
+    params(BudgetIdPath, AutoAllocateQuery),
+    responses(
+        (status = 200, description = "Suggested allocations (applied unless preview=true)", body = AutoAllocateResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/budgets/{id}/auto-allocate")]
+pub async fn auto_allocate(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+    query: web::Query<AutoAllocateQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    query.validate()?;
+
+    let response =
+        BudgetService::auto_allocate(pool.get_ref(), path.id, auth.user_id, &query).await?;
 
-    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget)))
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /budgets/{id}/move-allocation - Move allocation between two categories
+#[utoipa::path(
+    post,
+    path = "/budgets/{id}/move-allocation",
+    tag = "Budgets",
+    params(BudgetIdPath),
+    request_body = MoveAllocationDto,
+    responses(
+        (status = 200, description = "Allocation moved", body = MoveAllocationResponse),
+        (status = 400, description = "Validation error or insufficient allocation", body = ErrorResponse),
+        (status = 404, description = "Budget or category not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/budgets/{id}/move-allocation")]
+pub async fn move_allocation(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+    body: web::Json<MoveAllocationDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    body.validate_move()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let response =
+        BudgetService::move_allocation(pool.get_ref(), path.id, auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(response))
 }
 
 /// DELETE /budgets/{id} - Delete a budget
@@ -232,7 +576,8 @@ pub async fn update_savings_rate(
     responses(
         (status = 204, description = "Budget deleted"),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -242,7 +587,219 @@ pub async fn delete_budget(
     auth: AuthenticatedUser,
     path: web::Path<BudgetIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
     BudgetService::delete_budget(pool.get_ref(), path.id, auth.user_id).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// POST /budgets/{id}/invite - Invite a user to a budget by email
+#[utoipa::path(
+    post,
+    path = "/budgets/{id}/invite",
+    tag = "Budgets",
+    params(BudgetIdPath),
+    request_body = InviteMemberDto,
+    responses(
+        (status = 201, description = "Invitation created", body = InvitationResponse),
+        (status = 400, description = "Validation error or user already has access", body = ErrorResponse),
+        (status = 403, description = "Missing required scope or not the budget owner", body = ErrorResponse),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 409, description = "Email already has a pending invitation", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/budgets/{id}/invite")]
+pub async fn invite_member(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+    body: web::Json<InviteMemberDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    body.validate()?;
+    body.validate_role()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let invitation =
+        BudgetService::invite_member(pool.get_ref(), path.id, auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(InvitationResponse::from(invitation)))
+}
+
+/// GET /budgets/invitations - List pending invitations for the authenticated user
+#[utoipa::path(
+    get,
+    path = "/budgets/invitations",
+    tag = "Budgets",
+    responses(
+        (status = 200, description = "Pending invitations addressed to the user's email", body = Vec<InvitationResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/invitations")]
+pub async fn list_invitations(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    let invitations = BudgetService::list_invitations(pool.get_ref(), auth.user_id).await?;
+    let response: Vec<InvitationResponse> = invitations
+        .into_iter()
+        .map(InvitationResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /budgets/invitations/{id}/accept - Accept an invitation and join the budget
+#[utoipa::path(
+    post,
+    path = "/budgets/invitations/{id}/accept",
+    tag = "Budgets",
+    params(InvitationIdPath, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Invitation accepted; the shared budget", body = BudgetResponse),
+        (status = 404, description = "Invitation not found, expired or already accepted", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/budgets/invitations/{id}/accept")]
+pub async fn accept_invitation(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    path: web::Path<InvitationIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    let budget = BudgetService::accept_invitation(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(BudgetResponse::from_budget(budget).with_month_base(&month_base)))
+}
+
+/// GET /budgets/{id}/members - List the owner and members of a budget
+#[utoipa::path(
+    get,
+    path = "/budgets/{id}/members",
+    tag = "Budgets",
+    params(BudgetIdPath),
+    responses(
+        (status = 200, description = "Budget owner followed by members", body = Vec<MemberResponse>),
+        (status = 404, description = "Budget not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/budgets/{id}/members")]
+pub async fn list_members(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    let members = BudgetService::list_members(pool.get_ref(), path.id, auth.user_id).await?;
+    let response: Vec<MemberResponse> = members.into_iter().map(MemberResponse::from).collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// DELETE /budgets/{id}/members/{user_id} - Remove a member (or leave a budget)
+#[utoipa::path(
+    delete,
+    path = "/budgets/{id}/members/{user_id}",
+    tag = "Budgets",
+    params(BudgetMemberPath),
+    responses(
+        (status = 204, description = "Member removed"),
+        (status = 404, description = "Member not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/budgets/{id}/members/{user_id}")]
+pub async fn remove_member(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetMemberPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    BudgetService::remove_member(pool.get_ref(), path.id, path.user_id, auth.user_id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /budgets/{id}/apply-template - Create a category template's categories in a budget
+/// Categories whose name the budget already has are skipped
+#[utoipa::path(
+    post,
+    path = "/budgets/{id}/apply-template",
+    tag = "Budgets",
+    params(BudgetIdPath),
+    request_body = ApplyCategoryTemplateDto,
+    responses(
+        (status = 201, description = "Categories created from the template", body = Vec<CategoryResponse>),
+        (status = 404, description = "Budget or category template not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/budgets/{id}/apply-template")]
+pub async fn apply_category_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<BudgetIdPath>,
+    body: web::Json<ApplyCategoryTemplateDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    let categories =
+        CategoryService::apply_template(pool.get_ref(), path.id, body.template_id, auth.user_id)
+            .await?;
+    let response: Vec<CategoryResponse> = categories
+        .into_iter()
+        .map(CategoryResponse::from_category)
+        .collect();
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Register the budget routes. `main.rs` and the integration tests both
+/// mount them through here, so the two can't drift apart. Specific paths
+/// come before the generic `/budgets/{id}` ones.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_budgets)
+        .service(create_budget)
+        .service(copy_budget)
+        .service(get_budget_by_month_year)
+        .service(get_year_summary)
+        .service(list_invitations)
+        .service(accept_invitation)
+        .service(get_unallocated)
+        .service(get_budget_health)
+        .service(get_budget_projection)
+        .service(get_budget_full)
+        .service(get_budget)
+        .service(update_income)
+        .service(update_savings_rate)
+        .service(update_budget)
+        .service(auto_allocate)
+        .service(move_allocation)
+        .service(delete_budget)
+        .service(invite_member)
+        .service(list_members)
+        .service(remove_member)
+        .service(apply_category_template);
+}
@@ -1,5 +1,6 @@
 pub mod handlers;
 pub mod models;
 pub mod service;
+pub mod sharing;
 
 pub use handlers::*;
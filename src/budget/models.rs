@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
@@ -6,6 +7,9 @@ use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
+use crate::category::models::CategoryResponse;
+use crate::errors::AppError;
+
 /// Validate that a Decimal is non-negative
 fn validate_non_negative(value: &Decimal) -> Result<(), ValidationError> {
     if *value < Decimal::ZERO {
@@ -23,8 +27,8 @@ fn validate_percentage(value: &Decimal) -> Result<(), ValidationError> {
     Ok(())
 }
 
-/// Database entity for budgets
-#[derive(Debug, Clone, FromRow)]
+/// Database entity for budgets (also the audit snapshot of a budget)
+#[derive(Debug, Clone, Serialize, FromRow)]
 pub struct Budget {
     pub id: Uuid,
     #[allow(dead_code)] // Used in SQL queries for ownership check
@@ -34,18 +38,74 @@ pub struct Budget {
     pub total_income: Decimal,
     pub savings_rate: Decimal,
     pub currency: String,
+    /// Sum of category allocations (computed in query)
+    pub total_allocated: Decimal,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Allocation totals for a budget
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetTotals {
+    /// Sum of all category allocations
+    #[schema(example = 4200.00)]
+    pub total_allocated: Decimal,
+    /// Income available for spending (income - savings target)
+    #[schema(example = 4000.00)]
+    pub spending_budget: Decimal,
+    /// Whether allocations exceed the spending budget
+    pub is_over_allocated: bool,
+    /// Amount allocations exceed the spending budget by (0 when within budget)
+    #[schema(example = 200.00)]
+    pub over_allocated_by: Decimal,
+    /// "To be budgeted": income - savings target - allocations (negative when over-allocated)
+    #[schema(example = -200.00)]
+    pub unallocated: Decimal,
+}
+
+/// Zero-based budgeting figures for a budget
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UnallocatedResponse {
+    /// Budget UUID
+    pub budget_id: Uuid,
+    /// Total monthly income
+    #[schema(example = 5000.00)]
+    pub total_income: Decimal,
+    /// Computed: income * savings_rate / 100
+    #[schema(example = 1000.00)]
+    pub savings_target: Decimal,
+    /// Sum of all category allocations
+    #[schema(example = 3600.00)]
+    pub total_allocated: Decimal,
+    /// Income left to assign to categories (0 means every unit is budgeted)
+    #[schema(example = 400.00)]
+    pub unallocated: Decimal,
+}
+
+impl UnallocatedResponse {
+    pub fn from_budget(budget: &Budget) -> Self {
+        let savings_target = budget.total_income * budget.savings_rate / Decimal::from(100);
+
+        Self {
+            budget_id: budget.id,
+            total_income: budget.total_income,
+            savings_target,
+            total_allocated: budget.total_allocated,
+            unallocated: budget.total_income - savings_target - budget.total_allocated,
+        }
+    }
+}
+
 /// Budget response with computed fields
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BudgetResponse {
     /// Unique budget identifier
     pub id: Uuid,
-    /// Month (0-11, where 0 = January)
-    #[schema(example = 0, minimum = 0, maximum = 11)]
+    /// Month (0-11 where 0 = January, or 1-12 with monthBase=1)
+    #[schema(example = 0, minimum = 0, maximum = 12)]
     pub month: i16,
     /// Year
     #[schema(example = 2024)]
@@ -65,6 +125,8 @@ pub struct BudgetResponse {
     /// ISO 4217 currency code
     #[schema(example = "USD")]
     pub currency: String,
+    /// Computed: allocation totals against the spending budget
+    pub totals: BudgetTotals,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -86,19 +148,31 @@ impl BudgetResponse {
             savings_target,
             spending_budget,
             currency: budget.currency,
+            totals: BudgetTotals {
+                total_allocated: budget.total_allocated,
+                spending_budget,
+                is_over_allocated: budget.total_allocated > spending_budget,
+                over_allocated_by: (budget.total_allocated - spending_budget).max(Decimal::ZERO),
+                unallocated: spending_budget - budget.total_allocated,
+            },
             created_at: budget.created_at,
             updated_at: budget.updated_at,
         }
     }
+
+    /// Report the month in the caller's numbering convention
+    pub fn with_month_base(mut self, month_base: &MonthBaseQuery) -> Self {
+        self.month = month_base.to_external(self.month);
+        self
+    }
 }
 
 /// Request body for creating a new budget
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateBudgetDto {
-    /// Month (0-11, where 0 = January)
-    #[validate(range(min = 0, max = 11, message = "Month must be between 0 and 11"))]
-    #[schema(example = 0, minimum = 0, maximum = 11)]
+    /// Month (0-11 where 0 = January, or 1-12 with monthBase=1)
+    #[schema(example = 0, minimum = 0, maximum = 12)]
     pub month: i16,
 
     /// Year
@@ -119,6 +193,9 @@ pub struct CreateBudgetDto {
     /// Currency code (optional, defaults to user's default_currency)
     #[schema(example = "USD")]
     pub currency: Option<String>,
+
+    /// Category template whose categories the budget starts with (optional)
+    pub template_id: Option<Uuid>,
 }
 
 impl CreateBudgetDto {
@@ -134,12 +211,72 @@ impl CreateBudgetDto {
     }
 }
 
+/// Request body for copying a month's budget and its categories into another month
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyBudgetDto {
+    /// Month to copy from (0-11, or 1-12 with monthBase=1)
+    #[schema(example = 0, minimum = 0, maximum = 12)]
+    pub source_month: i16,
+
+    /// Year to copy from
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[schema(example = 2024)]
+    pub source_year: i16,
+
+    /// Month of the new budget (0-11, or 1-12 with monthBase=1)
+    #[schema(example = 1, minimum = 0, maximum = 12)]
+    pub target_month: i16,
+
+    /// Year of the new budget
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[schema(example = 2024)]
+    pub target_year: i16,
+}
+
+impl CopyBudgetDto {
+    /// A budget can't be copied onto its own month
+    pub fn validate_periods(&self) -> Result<(), ValidationError> {
+        if (self.source_month, self.source_year) == (self.target_month, self.target_year) {
+            return Err(ValidationError::new("source and target month must differ"));
+        }
+        Ok(())
+    }
+}
+
+/// Budget returned together with its categories
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetWithCategoriesResponse {
+    #[serde(flatten)]
+    pub budget: BudgetResponse,
+    /// The budget's categories, by name
+    pub categories: Vec<CategoryResponse>,
+    /// Computed: total expenses across the categories
+    #[schema(example = 2650.00)]
+    pub total_spent: Decimal,
+    /// Computed: total allocated - total spent
+    #[schema(example = 1550.00)]
+    pub total_remaining: Decimal,
+}
+
+impl BudgetWithCategoriesResponse {
+    pub fn new(budget: BudgetResponse, categories: Vec<CategoryResponse>) -> Self {
+        let total_spent = categories.iter().map(|c| c.spent_amount).sum();
+        Self {
+            total_remaining: budget.totals.total_allocated - total_spent,
+            budget,
+            categories,
+            total_spent,
+        }
+    }
+}
+
 /// Request body for updating a budget (PATCH - all fields optional)
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateBudgetDto {
-    /// Month (0-11)
-    #[validate(range(min = 0, max = 11, message = "Month must be between 0 and 11"))]
+    /// Month (0-11, or 1-12 with monthBase=1)
     #[schema(example = 0)]
     pub month: Option<i16>,
 
@@ -190,6 +327,366 @@ pub struct UpdateSavingsRateDto {
     pub savings_rate: Decimal,
 }
 
+/// Request body for moving allocation between two categories of a budget
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveAllocationDto {
+    /// Category to take allocation from
+    pub from_category_id: Uuid,
+
+    /// Category to give allocation to
+    pub to_category_id: Uuid,
+
+    /// Amount to move (must be positive)
+    #[schema(example = 50.00)]
+    pub amount: Decimal,
+}
+
+impl MoveAllocationDto {
+    /// Validate fields that can't use derive macro
+    pub fn validate_move(&self) -> Result<(), ValidationError> {
+        if self.amount <= Decimal::ZERO {
+            return Err(ValidationError::new("amount must be positive"));
+        }
+        if self.from_category_id == self.to_category_id {
+            return Err(ValidationError::new(
+                "source and destination categories must differ",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A category's allocation after a move
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryAllocation {
+    /// Category UUID
+    #[sqlx(rename = "id")]
+    pub category_id: Uuid,
+    /// Category name
+    #[schema(example = "Dining Out")]
+    pub name: String,
+    /// New allocated amount
+    #[schema(example = 150.00)]
+    pub allocated_amount: Decimal,
+}
+
+/// Result of moving allocation between categories
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MoveAllocationResponse {
+    /// Budget UUID
+    pub budget_id: Uuid,
+    /// Amount moved
+    #[schema(example = 50.00)]
+    pub amount: Decimal,
+    /// Source category after the move
+    pub from: CategoryAllocation,
+    /// Destination category after the move
+    pub to: CategoryAllocation,
+}
+
+fn default_health_threshold() -> i32 {
+    90
+}
+
+/// Query parameters for the budget health score
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetHealthQuery {
+    /// Percent of its allocation a category may spend before it counts against the score
+    #[validate(range(min = 1, max = 1000, message = "threshold must be between 1 and 1000"))]
+    #[serde(default = "default_health_threshold")]
+    #[param(example = 90)]
+    pub threshold: i32,
+}
+
+/// Elapsed share of the budget month in the owner's timezone
+#[derive(Debug, Clone, FromRow)]
+pub struct BudgetPeriodRow {
+    pub total_income: Decimal,
+    pub savings_rate: Decimal,
+    /// 0 before the month starts, 1 once it has ended
+    pub elapsed_fraction: Decimal,
+}
+
+/// Spending pace compared to time elapsed in the month
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PaceMetric {
+    /// Percent of the month elapsed
+    #[schema(example = 50.0)]
+    pub elapsed_percent: Decimal,
+    /// Percent of the spending budget spent
+    #[schema(example = 62.5)]
+    pub spent_percent: Decimal,
+    /// 100 when on or under pace, minus 2 points per percentage point ahead
+    #[schema(example = 75)]
+    pub score: i32,
+}
+
+/// Categories spending beyond the threshold share of their allocation
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryThresholdMetric {
+    /// Threshold as a percent of allocation
+    #[schema(example = 90)]
+    pub threshold_percent: i32,
+    /// Categories at or above the threshold
+    pub over_threshold: Vec<String>,
+    /// Total categories in the budget
+    #[schema(example = 8)]
+    pub total_categories: usize,
+    /// Share of categories under the threshold, 0-100
+    #[schema(example = 75)]
+    pub score: i32,
+}
+
+/// Progress toward the savings target (income minus spending so far)
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SavingsMetric {
+    /// Computed: income * savings_rate / 100
+    #[schema(example = 1000.00)]
+    pub savings_target: Decimal,
+    /// Income minus spending so far
+    #[schema(example = 2500.00)]
+    pub current_savings: Decimal,
+    /// current_savings as a share of the target, capped at 100
+    #[schema(example = 100)]
+    pub score: i32,
+}
+
+/// Composite budget health score with component metrics
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetHealthResponse {
+    /// Budget UUID
+    pub budget_id: Uuid,
+    /// Weighted score 0-100: 40% pace, 30% categories, 30% savings
+    #[schema(example = 82)]
+    pub score: i32,
+    pub pace: PaceMetric,
+    pub categories: CategoryThresholdMetric,
+    pub savings: SavingsMetric,
+}
+
+/// Spending for one category, input to the health score
+pub struct CategorySpending {
+    pub name: String,
+    pub allocated_amount: Decimal,
+    pub spent_amount: Decimal,
+}
+
+/// Clamp a 0-100 Decimal score to an integer
+fn to_score(value: Decimal) -> i32 {
+    value
+        .round()
+        .clamp(Decimal::ZERO, Decimal::from(100))
+        .to_i32()
+        .unwrap_or(0)
+}
+
+impl BudgetHealthResponse {
+    pub fn compute(
+        budget_id: Uuid,
+        period: &BudgetPeriodRow,
+        categories: &[CategorySpending],
+        threshold: i32,
+    ) -> Self {
+        let hundred = Decimal::from(100);
+        let savings_target = period.total_income * period.savings_rate / hundred;
+        let spending_budget = period.total_income - savings_target;
+        let total_spent: Decimal = categories.iter().map(|c| c.spent_amount).sum();
+
+        let elapsed_percent = (period.elapsed_fraction * hundred).round_dp(1);
+        let spent_percent = if spending_budget > Decimal::ZERO {
+            (total_spent / spending_budget * hundred).round_dp(1)
+        } else if total_spent > Decimal::ZERO {
+            hundred
+        } else {
+            Decimal::ZERO
+        };
+        let pace_score = to_score(
+            hundred - (spent_percent - elapsed_percent).max(Decimal::ZERO) * Decimal::from(2),
+        );
+
+        let threshold_ratio = Decimal::from(threshold) / hundred;
+        let over_threshold: Vec<String> = categories
+            .iter()
+            .filter(|c| {
+                c.spent_amount > Decimal::ZERO
+                    && c.spent_amount >= c.allocated_amount * threshold_ratio
+            })
+            .map(|c| c.name.clone())
+            .collect();
+        let category_score = if categories.is_empty() {
+            100
+        } else {
+            to_score(
+                Decimal::from(categories.len() - over_threshold.len()) * hundred
+                    / Decimal::from(categories.len()),
+            )
+        };
+
+        let current_savings = period.total_income - total_spent;
+        let savings_score = if savings_target > Decimal::ZERO {
+            to_score(current_savings / savings_target * hundred)
+        } else {
+            100
+        };
+
+        let score = to_score(
+            (Decimal::from(pace_score) * Decimal::from(4)
+                + Decimal::from(category_score) * Decimal::from(3)
+                + Decimal::from(savings_score) * Decimal::from(3))
+                / Decimal::from(10),
+        );
+
+        Self {
+            budget_id,
+            score,
+            pace: PaceMetric {
+                elapsed_percent,
+                spent_percent,
+                score: pace_score,
+            },
+            categories: CategoryThresholdMetric {
+                threshold_percent: threshold,
+                over_threshold,
+                total_categories: categories.len(),
+                score: category_score,
+            },
+            savings: SavingsMetric {
+                savings_target,
+                current_savings,
+                score: savings_score,
+            },
+        }
+    }
+}
+
+/// Current-month spending for one category, split into recurring and other items
+#[derive(Debug, Clone, FromRow)]
+pub struct CategoryProjectionRow {
+    pub category_id: Uuid,
+    pub name: String,
+    pub allocated_amount: Decimal,
+    pub spent_amount: Decimal,
+    /// Part of spent_amount matching a recurring item
+    pub recurring_spent: Decimal,
+    /// Recurring items not yet seen this month
+    pub recurring_pending: Decimal,
+}
+
+/// Projected end-of-month spending for one category
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryProjection {
+    pub category_id: Uuid,
+    #[schema(example = "Groceries")]
+    pub name: String,
+    #[schema(example = 400.00)]
+    pub allocated_amount: Decimal,
+    /// Spent so far this month
+    #[schema(example = 180.00)]
+    pub spent_amount: Decimal,
+    /// Recurring items expected before the month ends
+    #[schema(example = 0)]
+    pub recurring_pending: Decimal,
+    /// Estimated spending by the end of the month
+    #[schema(example = 372.00)]
+    pub projected_amount: Decimal,
+    /// Projected spending minus the allocation (negative means under)
+    #[schema(example = -28.00)]
+    pub projected_variance: Decimal,
+}
+
+/// Response for GET /budgets/{id}/projection
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetProjectionResponse {
+    pub budget_id: Uuid,
+    /// Percent of the month elapsed
+    #[schema(example = 48.4)]
+    pub elapsed_percent: Decimal,
+    /// Income minus the savings target
+    #[schema(example = 4000.00)]
+    pub spending_budget: Decimal,
+    /// Spent so far this month
+    #[schema(example = 1850.00)]
+    pub spent_amount: Decimal,
+    /// Estimated spending by the end of the month
+    #[schema(example = 3720.00)]
+    pub projected_amount: Decimal,
+    /// Projected spending minus the spending budget (negative means under)
+    #[schema(example = -280.00)]
+    pub projected_variance: Decimal,
+    /// Whether the month is on course to end over the spending budget
+    pub projected_over_budget: bool,
+    pub categories: Vec<CategoryProjection>,
+}
+
+/// Extrapolate one category's spending to the end of the month. Recurring items
+/// are counted once (already paid or still pending); everything else is scaled
+/// by the share of the month that has elapsed.
+fn project_spending(row: &CategoryProjectionRow, elapsed_fraction: Decimal) -> Decimal {
+    if elapsed_fraction >= Decimal::ONE {
+        return row.spent_amount;
+    }
+    let discretionary = row.spent_amount - row.recurring_spent;
+    let extrapolated = if elapsed_fraction > Decimal::ZERO {
+        discretionary / elapsed_fraction
+    } else {
+        discretionary
+    };
+    (row.recurring_spent + row.recurring_pending + extrapolated).round_dp(2)
+}
+
+impl BudgetProjectionResponse {
+    pub fn compute(
+        budget_id: Uuid,
+        period: &BudgetPeriodRow,
+        rows: &[CategoryProjectionRow],
+    ) -> Self {
+        let hundred = Decimal::from(100);
+        let spending_budget =
+            period.total_income - period.total_income * period.savings_rate / hundred;
+
+        let categories: Vec<CategoryProjection> = rows
+            .iter()
+            .map(|row| {
+                let projected_amount = project_spending(row, period.elapsed_fraction);
+                CategoryProjection {
+                    category_id: row.category_id,
+                    name: row.name.clone(),
+                    allocated_amount: row.allocated_amount,
+                    spent_amount: row.spent_amount,
+                    recurring_pending: row.recurring_pending,
+                    projected_amount,
+                    projected_variance: projected_amount - row.allocated_amount,
+                }
+            })
+            .collect();
+
+        let spent_amount: Decimal = categories.iter().map(|c| c.spent_amount).sum();
+        let projected_amount: Decimal = categories.iter().map(|c| c.projected_amount).sum();
+        let projected_variance = projected_amount - spending_budget;
+
+        Self {
+            budget_id,
+            elapsed_percent: (period.elapsed_fraction * hundred).round_dp(1),
+            spending_budget,
+            spent_amount,
+            projected_amount,
+            projected_variance,
+            projected_over_budget: projected_variance > Decimal::ZERO,
+            categories,
+        }
+    }
+}
+
 /// Path parameters for budget ID
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct BudgetIdPath {
@@ -200,8 +697,7 @@ pub struct BudgetIdPath {
 /// Path parameters for month/year lookup
 #[derive(Debug, Deserialize, Validate, IntoParams)]
 pub struct MonthYearPath {
-    /// Month (0-11)
-    #[validate(range(min = 0, max = 11, message = "Month must be between 0 and 11"))]
+    /// Month (0-11, or 1-12 with monthBase=1)
     #[param(example = 0)]
     pub month: i16,
 
@@ -211,6 +707,97 @@ pub struct MonthYearPath {
     pub year: i16,
 }
 
+/// Path parameters for the yearly summary
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct YearPath {
+    /// Year
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[param(example = 2026)]
+    pub year: i16,
+}
+
+/// Income, savings rate and category allocations of a budget in the summarized year
+#[derive(Debug, Clone, FromRow)]
+pub struct YearBudgetRow {
+    pub id: Uuid,
+    pub month: i16,
+    pub total_income: Decimal,
+    pub savings_rate: Decimal,
+    pub total_allocated: Decimal,
+}
+
+/// Posted income and expenses of a month in the summarized year
+#[derive(Debug, Clone, FromRow)]
+pub struct MonthTotalsRow {
+    pub month: i16,
+    pub income: Decimal,
+    pub expenses: Decimal,
+}
+
+/// One month of the yearly overview
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthSummary {
+    /// Month (0-11 where 0 = January, or 1-12 with monthBase=1)
+    #[schema(example = 0)]
+    pub month: i16,
+    /// The month's budget (null when the month has none)
+    pub budget_id: Option<Uuid>,
+    /// Income planned in the budget (0 without one)
+    #[schema(example = 5000.00)]
+    pub budgeted_income: Decimal,
+    /// Computed: budgeted income * savings rate / 100 (0 without a budget)
+    #[schema(example = 1000.00)]
+    pub savings_target: Decimal,
+    /// Spending planned across the budget's categories (0 without a budget)
+    #[schema(example = 4000.00)]
+    pub budgeted_spend: Decimal,
+    /// Income recorded by posted transactions
+    #[schema(example = 5200.00)]
+    pub income: Decimal,
+    /// Expenses recorded by posted transactions, net of refunds
+    #[schema(example = 3900.00)]
+    pub actual_spend: Decimal,
+    /// Computed: income - actual spend (negative when spending exceeded income)
+    #[schema(example = 1300.00)]
+    pub savings_achieved: Decimal,
+}
+
+impl MonthSummary {
+    /// Twelve entries, January first, with zeroes for months that have no
+    /// budget or no transactions
+    pub fn for_year(budgets: &[YearBudgetRow], totals: &[MonthTotalsRow]) -> Vec<Self> {
+        (0..12)
+            .map(|month| {
+                let budget = budgets.iter().find(|b| b.month == month);
+                let (income, expenses) = totals
+                    .iter()
+                    .find(|t| t.month == month)
+                    .map_or((Decimal::ZERO, Decimal::ZERO), |t| (t.income, t.expenses));
+
+                Self {
+                    month,
+                    budget_id: budget.map(|b| b.id),
+                    budgeted_income: budget.map_or(Decimal::ZERO, |b| b.total_income),
+                    savings_target: budget.map_or(Decimal::ZERO, |b| {
+                        b.total_income * b.savings_rate / Decimal::from(100)
+                    }),
+                    budgeted_spend: budget.map_or(Decimal::ZERO, |b| b.total_allocated),
+                    income,
+                    actual_spend: expenses,
+                    savings_achieved: income - expenses,
+                }
+            })
+            .collect()
+    }
+
+    /// Report the month in the caller's numbering convention
+    pub fn with_month_base(mut self, month_base: &MonthBaseQuery) -> Self {
+        self.month = month_base.to_external(self.month);
+        self
+    }
+}
+
 /// Query parameters for listing budgets
 #[derive(Debug, Deserialize, Validate, IntoParams)]
 pub struct ListBudgetsQuery {
@@ -235,3 +822,303 @@ pub struct ListBudgetsQuery {
 fn default_limit() -> i64 {
     20
 }
+
+fn default_auto_allocate_months() -> i32 {
+    3
+}
+
+/// Query parameters for auto-allocating a budget from past spending
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAllocateQuery {
+    /// Number of preceding months to average (1-12)
+    #[validate(range(min = 1, max = 12, message = "months must be between 1 and 12"))]
+    #[serde(default = "default_auto_allocate_months")]
+    #[param(example = 3)]
+    pub months: i32,
+
+    /// When true, return suggestions without changing any allocations
+    #[serde(default)]
+    #[param(example = true)]
+    pub preview: bool,
+}
+
+/// Historical spending for one category, matched by name across past budgets
+#[derive(Debug, Clone, FromRow)]
+pub struct CategorySpendingHistory {
+    pub category_id: Uuid,
+    pub name: String,
+    pub allocated_amount: Decimal,
+    pub average_spent: Decimal,
+    pub months_with_data: i64,
+}
+
+/// Suggested allocation for a single category
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationSuggestion {
+    /// Category UUID
+    pub category_id: Uuid,
+    /// Category name
+    #[schema(example = "Groceries")]
+    pub name: String,
+    /// Allocation before auto-allocation
+    #[schema(example = 400.00)]
+    pub current_allocated: Decimal,
+    /// Average monthly spending over the months with data (current allocation if none)
+    #[schema(example = 452.30)]
+    pub suggested_allocated: Decimal,
+    /// Number of past months with a category of the same name
+    #[schema(example = 3)]
+    pub months_with_data: i64,
+}
+
+impl AllocationSuggestion {
+    pub fn from_history(history: CategorySpendingHistory) -> Self {
+        let suggested_allocated = if history.months_with_data > 0 {
+            history.average_spent.round_dp(2)
+        } else {
+            history.allocated_amount
+        };
+
+        Self {
+            category_id: history.category_id,
+            name: history.name,
+            current_allocated: history.allocated_amount,
+            suggested_allocated,
+            months_with_data: history.months_with_data,
+        }
+    }
+}
+
+/// Result of an auto-allocation run
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoAllocateResponse {
+    /// Budget UUID
+    pub budget_id: Uuid,
+    /// Number of preceding months averaged
+    #[schema(example = 3)]
+    pub months: i32,
+    /// Whether the suggestions were written to the categories
+    pub applied: bool,
+    /// Sum of suggested allocations
+    #[schema(example = 3800.00)]
+    pub total_suggested: Decimal,
+    /// Per-category suggestions
+    pub suggestions: Vec<AllocationSuggestion>,
+}
+
+/// Query parameter selecting how months are numbered in a request and its response.
+///
+/// Months are stored 0-11 (matching JavaScript `Date`); `monthBase=1` lets
+/// clients send and receive 1-12 instead. Extracted from the query string,
+/// falling back to the server default configured with `BUDGET_MONTH_BASE`.
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthBaseQuery {
+    /// Month numbering: 0 for 0-11 (default), 1 for 1-12
+    #[param(example = 1, minimum = 0, maximum = 1)]
+    pub month_base: i16,
+}
+
+impl MonthBaseQuery {
+    fn base(&self) -> Result<i16, AppError> {
+        match self.month_base {
+            0 | 1 => Ok(self.month_base),
+            _ => Err(AppError::ValidationError(
+                "monthBase must be 0 or 1".to_string(),
+            )),
+        }
+    }
+
+    /// Convert a client-supplied month to the stored 0-11 value
+    pub fn to_internal(&self, month: i16) -> Result<i16, AppError> {
+        let base = self.base()?;
+        if !(base..=base + 11).contains(&month) {
+            return Err(AppError::ValidationError(format!(
+                "Month must be between {} and {}",
+                base,
+                base + 11
+            )));
+        }
+        Ok(month - base)
+    }
+
+    /// Convert a stored 0-11 month to the client's numbering
+    pub fn to_external(&self, month: i16) -> i16 {
+        month + self.base().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_month_base_one_round_trip() {
+        let base = MonthBaseQuery { month_base: 1 };
+        assert_eq!(base.to_internal(1).unwrap(), 0);
+        assert_eq!(base.to_internal(12).unwrap(), 11);
+        assert_eq!(base.to_external(11), 12);
+        assert!(base.to_internal(0).is_err());
+        assert!(base.to_internal(13).is_err());
+    }
+
+    #[test]
+    fn test_suggestion_keeps_allocation_without_history() {
+        let history = CategorySpendingHistory {
+            category_id: Uuid::nil(),
+            name: "Travel".to_string(),
+            allocated_amount: Decimal::from(250),
+            average_spent: Decimal::ZERO,
+            months_with_data: 0,
+        };
+        let suggestion = AllocationSuggestion::from_history(history);
+        assert_eq!(suggestion.suggested_allocated, Decimal::from(250));
+
+        let history = CategorySpendingHistory {
+            category_id: Uuid::nil(),
+            name: "Groceries".to_string(),
+            allocated_amount: Decimal::from(400),
+            average_spent: Decimal::new(4523333, 4),
+            months_with_data: 3,
+        };
+        let suggestion = AllocationSuggestion::from_history(history);
+        assert_eq!(suggestion.suggested_allocated, Decimal::new(45233, 2));
+    }
+
+    #[test]
+    fn test_health_score_components() {
+        // Half way through the month with 60% of the spending budget spent
+        let period = BudgetPeriodRow {
+            total_income: Decimal::from(1000),
+            savings_rate: Decimal::from(20),
+            elapsed_fraction: Decimal::new(5, 1),
+        };
+        let categories = vec![
+            CategorySpending {
+                name: "Rent".to_string(),
+                allocated_amount: Decimal::from(400),
+                spent_amount: Decimal::from(400),
+            },
+            CategorySpending {
+                name: "Food".to_string(),
+                allocated_amount: Decimal::from(400),
+                spent_amount: Decimal::from(80),
+            },
+        ];
+
+        let health = BudgetHealthResponse::compute(Uuid::nil(), &period, &categories, 90);
+        assert_eq!(health.pace.score, 80);
+        assert_eq!(health.categories.over_threshold, vec!["Rent".to_string()]);
+        assert_eq!(health.categories.score, 50);
+        assert_eq!(health.savings.score, 100);
+        assert_eq!(health.score, 77);
+    }
+
+    #[test]
+    fn test_copy_budget_needs_a_different_month() {
+        let dto = CopyBudgetDto {
+            source_month: 0,
+            source_year: 2026,
+            target_month: 1,
+            target_year: 2026,
+        };
+        assert!(dto.validate_periods().is_ok());
+
+        let same = CopyBudgetDto {
+            target_month: 0,
+            ..dto
+        };
+        assert!(same.validate_periods().is_err());
+    }
+
+    #[test]
+    fn test_month_base_zero_is_identity() {
+        let base = MonthBaseQuery { month_base: 0 };
+        assert_eq!(base.to_internal(0).unwrap(), 0);
+        assert_eq!(base.to_external(11), 11);
+        assert!(base.to_internal(12).is_err());
+        assert!(MonthBaseQuery { month_base: 2 }.to_internal(3).is_err());
+    }
+
+    #[test]
+    fn test_projection_scales_discretionary_and_adds_pending_recurring() {
+        let period = BudgetPeriodRow {
+            total_income: Decimal::from(1000),
+            savings_rate: Decimal::from(20),
+            elapsed_fraction: Decimal::new(25, 2),
+        };
+        let rows = vec![
+            CategoryProjectionRow {
+                category_id: Uuid::nil(),
+                name: "Groceries".to_string(),
+                allocated_amount: Decimal::from(300),
+                spent_amount: Decimal::from(100),
+                recurring_spent: Decimal::ZERO,
+                recurring_pending: Decimal::ZERO,
+            },
+            CategoryProjectionRow {
+                category_id: Uuid::nil(),
+                name: "Subscriptions".to_string(),
+                allocated_amount: Decimal::from(50),
+                spent_amount: Decimal::from(15),
+                recurring_spent: Decimal::from(15),
+                recurring_pending: Decimal::from(20),
+            },
+        ];
+
+        let projection = BudgetProjectionResponse::compute(Uuid::nil(), &period, &rows);
+
+        assert_eq!(projection.spending_budget, Decimal::from(800));
+        assert_eq!(
+            projection.categories[0].projected_amount,
+            Decimal::from(400)
+        );
+        assert_eq!(
+            projection.categories[0].projected_variance,
+            Decimal::from(100)
+        );
+        assert_eq!(projection.categories[1].projected_amount, Decimal::from(35));
+        assert_eq!(projection.projected_amount, Decimal::from(435));
+        assert!(!projection.projected_over_budget);
+    }
+
+    #[test]
+    fn test_year_summary_fills_months_without_budgets_or_transactions() {
+        let budgets = vec![YearBudgetRow {
+            id: Uuid::nil(),
+            month: 2,
+            total_income: Decimal::from(2000),
+            savings_rate: Decimal::from(25),
+            total_allocated: Decimal::from(1500),
+        }];
+        let totals = vec![
+            MonthTotalsRow {
+                month: 2,
+                income: Decimal::from(2100),
+                expenses: Decimal::from(1600),
+            },
+            MonthTotalsRow {
+                month: 5,
+                income: Decimal::ZERO,
+                expenses: Decimal::from(40),
+            },
+        ];
+
+        let months = MonthSummary::for_year(&budgets, &totals);
+
+        assert_eq!(months.len(), 12);
+        assert!(months.iter().enumerate().all(|(i, m)| m.month == i as i16));
+        assert_eq!(months[2].budget_id, Some(Uuid::nil()));
+        assert_eq!(months[2].savings_target, Decimal::from(500));
+        assert_eq!(months[2].budgeted_spend, Decimal::from(1500));
+        assert_eq!(months[2].savings_achieved, Decimal::from(500));
+        assert_eq!(months[5].budget_id, None);
+        assert_eq!(months[5].budgeted_income, Decimal::ZERO);
+        assert_eq!(months[5].budgeted_spend, Decimal::ZERO);
+        assert_eq!(months[5].savings_achieved, Decimal::from(-40));
+        assert_eq!(months[0].actual_spend, Decimal::ZERO);
+    }
+}
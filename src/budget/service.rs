@@ -1,35 +1,65 @@
 use rust_decimal::Decimal;
+use serde_json::json;
 use sqlx::PgPool;
 use uuid::Uuid;
 
 use super::models::{
-    Budget, CreateBudgetDto, ListBudgetsQuery, UpdateBudgetDto, UpdateIncomeDto,
-    UpdateSavingsRateDto,
+    AllocationSuggestion, AutoAllocateQuery, AutoAllocateResponse, Budget, BudgetHealthResponse,
+    BudgetPeriodRow, BudgetProjectionResponse, CategoryAllocation, CategoryProjectionRow,
+    CategorySpending, CategorySpendingHistory, CopyBudgetDto, CreateBudgetDto, ListBudgetsQuery,
+    MonthSummary, MonthTotalsRow, MoveAllocationDto, MoveAllocationResponse, UpdateBudgetDto,
+    UpdateIncomeDto, UpdateSavingsRateDto, YearBudgetRow,
 };
+use super::sharing::{BudgetInvitation, BudgetMember, InviteMemberDto};
+use crate::audit::models::{actions, entities};
+use crate::audit::service::AuditService;
+use crate::cache::ResponseCache;
+use crate::category::models::{Category, CategorySnapshot};
+use crate::category::service::CategoryService;
 use crate::currency::service::CurrencyService;
-use crate::errors::AppError;
+use crate::errors::{AppError, ErrorCode};
+use crate::extractors::IfMatch;
+use crate::summary::service::SummaryService;
+
+const INVITATION_COLUMNS: &str = "id, budget_id, email, role, invited_by, created_at, expires_at";
+
+/// Map an invitation write error, reporting a second pending invitation for
+/// the same email as a conflict
+fn invitation_write_error(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Coded(
+            ErrorCode::DuplicateInvitation,
+            "This email already has a pending invitation to the budget".to_string(),
+        ),
+        _ => AppError::InternalError(e.to_string()),
+    }
+}
 
 /// Service layer for budget business logic.
 pub struct BudgetService;
 
 impl BudgetService {
-    /// List all budgets for a user with optional year filtering and pagination.
+    /// List the user's budgets and those shared with them, with optional year
+    /// filtering and pagination.
     pub async fn list_budgets(
         pool: &PgPool,
-        owner_id: Uuid,
+        user_id: Uuid,
         query: &ListBudgetsQuery,
     ) -> Result<Vec<Budget>, AppError> {
         let budgets = if let Some(year) = query.year {
             sqlx::query_as::<_, Budget>(
                 r#"
-                SELECT id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+                SELECT id, owner_id, month, year, total_income, savings_rate, currency,
+                    (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                    created_at, updated_at
                 FROM budgets
-                WHERE owner_id = $1 AND year = $2
+                WHERE (owner_id = $1 OR id IN (SELECT budget_id FROM budget_members WHERE user_id = $1))
+                  AND year = $2
                 ORDER BY year DESC, month DESC
                 LIMIT $3 OFFSET $4
                 "#,
             )
-            .bind(owner_id)
+            .bind(user_id)
             .bind(year)
             .bind(query.limit)
             .bind(query.offset)
@@ -38,14 +68,16 @@ impl BudgetService {
         } else {
             sqlx::query_as::<_, Budget>(
                 r#"
-                SELECT id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+                SELECT id, owner_id, month, year, total_income, savings_rate, currency,
+                    (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                    created_at, updated_at
                 FROM budgets
-                WHERE owner_id = $1
+                WHERE owner_id = $1 OR id IN (SELECT budget_id FROM budget_members WHERE user_id = $1)
                 ORDER BY year DESC, month DESC
                 LIMIT $2 OFFSET $3
                 "#,
             )
-            .bind(owner_id)
+            .bind(user_id)
             .bind(query.limit)
             .bind(query.offset)
             .fetch_all(pool)
@@ -55,21 +87,23 @@ impl BudgetService {
         budgets.map_err(|e| AppError::InternalError(e.to_string()))
     }
 
-    /// Get a budget by ID, ensuring the requesting user owns it.
+    /// Get a budget by ID, ensuring the requesting user owns it or is a member.
     pub async fn get_budget_by_id(
         pool: &PgPool,
         budget_id: Uuid,
-        owner_id: Uuid,
+        user_id: Uuid,
     ) -> Result<Budget, AppError> {
         sqlx::query_as::<_, Budget>(
             r#"
-            SELECT id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+            SELECT id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
             FROM budgets
-            WHERE id = $1 AND owner_id = $2
+            WHERE id = $1 AND budget_access(id, $2, FALSE)
             "#,
         )
         .bind(budget_id)
-        .bind(owner_id)
+        .bind(user_id)
         .fetch_optional(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
@@ -85,7 +119,9 @@ impl BudgetService {
     ) -> Result<Budget, AppError> {
         sqlx::query_as::<_, Budget>(
             r#"
-            SELECT id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+            SELECT id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
             FROM budgets
             WHERE owner_id = $1 AND month = $2 AND year = $3
             "#,
@@ -99,30 +135,76 @@ impl BudgetService {
         .ok_or_else(|| AppError::NotFound(format!("Budget not found for {}/{}", month + 1, year)))
     }
 
-    /// Create a new budget.
-    pub async fn create_budget(
+    /// Budgeted and actual figures for each month of a year, for the owner's
+    /// budgets and transactions. Months without either report zeroes.
+    pub async fn get_year_summary(
         pool: &PgPool,
         owner_id: Uuid,
-        dto: &CreateBudgetDto,
-    ) -> Result<Budget, AppError> {
-        // Check for existing budget with same month/year
-        let exists = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM budgets WHERE owner_id = $1 AND month = $2 AND year = $3",
+        year: i16,
+    ) -> Result<Vec<MonthSummary>, AppError> {
+        SummaryService::refresh_dirty(pool, Some(owner_id)).await?;
+
+        let budgets = sqlx::query_as::<_, YearBudgetRow>(
+            r#"
+            SELECT b.id, b.month, b.total_income, b.savings_rate,
+                   COALESCE(SUM(c.allocated_amount), 0) AS total_allocated
+            FROM budgets b
+            LEFT JOIN categories c ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND b.year = $2
+            GROUP BY b.id
+            "#,
         )
         .bind(owner_id)
-        .bind(dto.month)
-        .bind(dto.year)
-        .fetch_one(pool)
+        .bind(year)
+        .fetch_all(pool);
+
+        let totals = sqlx::query_as::<_, MonthTotalsRow>(
+            r#"
+            SELECT
+                month,
+                COALESCE(SUM(total) FILTER (WHERE transaction_type = 'income'), 0) AS income,
+                COALESCE(SUM(total) FILTER (WHERE transaction_type = 'expense'), 0) AS expenses
+            FROM monthly_category_totals
+            WHERE owner_id = $1 AND year = $2
+            GROUP BY month
+            "#,
+        )
+        .bind(owner_id)
+        .bind(year)
+        .fetch_all(pool);
+
+        let (budgets, totals) = tokio::try_join!(budgets, totals)
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(MonthSummary::for_year(&budgets, &totals))
+    }
+
+    /// ID of the budget for the current month in the owner's timezone
+    pub async fn current_budget_id(pool: &PgPool, owner_id: Uuid) -> Result<Uuid, AppError> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT b.id
+            FROM budgets b
+            INNER JOIN users u ON u.id = b.owner_id
+            WHERE b.owner_id = $1
+              AND b.year = EXTRACT(YEAR FROM NOW() AT TIME ZONE u.timezone)
+              AND b.month = EXTRACT(MONTH FROM NOW() AT TIME ZONE u.timezone) - 1
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_optional(pool)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("No budget exists for the current month".to_string()))
+    }
 
-        if exists > 0 {
-            return Err(AppError::Conflict(format!(
-                "Budget already exists for {}/{}",
-                dto.month + 1,
-                dto.year
-            )));
-        }
+    /// Create a new budget.
+    pub async fn create_budget(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateBudgetDto,
+    ) -> Result<Budget, AppError> {
+        Self::ensure_month_free(pool, owner_id, dto.month, dto.year).await?;
 
         let total_income = dto.total_income.unwrap_or(Decimal::ZERO);
         let savings_rate = dto.savings_rate.unwrap_or(Decimal::ZERO);
@@ -152,33 +234,232 @@ impl BudgetService {
             }
         };
 
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut budget = Self::insert_budget(
+            &mut tx,
+            owner_id,
+            dto.month,
+            dto.year,
+            total_income,
+            savings_rate,
+            &currency,
+        )
+        .await?;
+
+        if let Some(template_id) = dto.template_id {
+            let categories =
+                CategoryService::apply_template_in_tx(&mut tx, owner_id, budget.id, template_id)
+                    .await?;
+            budget.total_allocated = categories.iter().map(|c| c.allocated_amount).sum();
+        }
+
+        AuditService::record_change(
+            &mut tx,
+            owner_id,
+            actions::BUDGET_CREATE,
+            entities::BUDGET,
+            budget.id,
+            None,
+            Some(&budget),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        Ok(budget)
+    }
+
+    /// Fail with a conflict if the user already has a budget for the month
+    async fn ensure_month_free(
+        pool: &PgPool,
+        owner_id: Uuid,
+        month: i16,
+        year: i16,
+    ) -> Result<(), AppError> {
+        let exists = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM budgets WHERE owner_id = $1 AND month = $2 AND year = $3",
+        )
+        .bind(owner_id)
+        .bind(month)
+        .bind(year)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if exists > 0 {
+            return Err(Self::month_conflict(month, year));
+        }
+        Ok(())
+    }
+
+    fn month_conflict(month: i16, year: i16) -> AppError {
+        AppError::Coded(
+            ErrorCode::BudgetMonthConflict,
+            format!("Budget already exists for {}/{}", month + 1, year),
+        )
+    }
+
+    /// Insert a budget row. A concurrent insert for the same month is reported
+    /// as the same conflict the up-front check gives.
+    async fn insert_budget(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        owner_id: Uuid,
+        month: i16,
+        year: i16,
+        total_income: Decimal,
+        savings_rate: Decimal,
+        currency: &str,
+    ) -> Result<Budget, AppError> {
         sqlx::query_as::<_, Budget>(
             r#"
             INSERT INTO budgets (owner_id, month, year, total_income, savings_rate, currency)
             VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+            RETURNING id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
             "#,
         )
         .bind(owner_id)
-        .bind(dto.month)
-        .bind(dto.year)
+        .bind(month)
+        .bind(year)
         .bind(total_income)
         .bind(savings_rate)
-        .bind(&currency)
-        .fetch_one(pool)
+        .bind(currency)
+        .fetch_one(&mut **tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                Self::month_conflict(month, year)
+            }
+            _ => AppError::InternalError(e.to_string()),
+        })
+    }
+
+    /// Copy one of the user's budgets and its categories (allocations, colors
+    /// and tax categories) into a month that has no budget yet. Transactions
+    /// are not copied.
+    pub async fn copy_budget(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CopyBudgetDto,
+    ) -> Result<(Budget, Vec<Category>), AppError> {
+        let source =
+            Self::get_budget_by_month_year(pool, owner_id, dto.source_month, dto.source_year)
+                .await?;
+        Self::ensure_month_free(pool, owner_id, dto.target_month, dto.target_year).await?;
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut budget = Self::insert_budget(
+            &mut tx,
+            owner_id,
+            dto.target_month,
+            dto.target_year,
+            source.total_income,
+            source.savings_rate,
+            &source.currency,
+        )
+        .await?;
+
+        let mut categories = sqlx::query_as::<_, Category>(
+            r#"
+            INSERT INTO categories (budget_id, name, allocated_amount, color_hex, tax_category)
+            SELECT $1, name, allocated_amount, color_hex, tax_category
+            FROM categories
+            WHERE budget_id = $2
+            RETURNING id, budget_id, name, allocated_amount, color_hex, tax_category,
+                      created_at, updated_at
+            "#,
+        )
+        .bind(budget.id)
+        .bind(source.id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        categories.sort_by(|a, b| a.name.cmp(&b.name));
+        budget.total_allocated = categories.iter().map(|c| c.allocated_amount).sum();
+
+        AuditService::record_change(
+            &mut tx,
+            owner_id,
+            actions::BUDGET_CREATE,
+            entities::BUDGET,
+            budget.id,
+            None,
+            Some(&budget),
+        )
+        .await?;
+        for category in &categories {
+            AuditService::record_change(
+                &mut tx,
+                owner_id,
+                actions::CATEGORY_CREATE,
+                entities::CATEGORY,
+                category.id,
+                None,
+                Some(&CategorySnapshot::of(category)),
+            )
+            .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        Ok((budget, categories))
+    }
+
+    /// Lock a budget the user can change, for an audited update.
+    async fn lock_for_write(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        budget_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Budget, AppError> {
+        sqlx::query_as::<_, Budget>(
+            r#"
+            SELECT id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
+            FROM budgets
+            WHERE id = $1 AND budget_access(id, $2, TRUE)
+            FOR UPDATE
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))
     }
 
     /// Update a budget (partial update - PATCH semantics).
     pub async fn update_budget(
         pool: &PgPool,
         budget_id: Uuid,
-        owner_id: Uuid,
+        user_id: Uuid,
         dto: &UpdateBudgetDto,
+        if_match: &IfMatch,
     ) -> Result<Budget, AppError> {
-        // First verify ownership and get current budget
-        let current = Self::get_budget_by_id(pool, budget_id, owner_id).await?;
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // First verify access and lock the current budget
+        let current = Self::lock_for_write(&mut tx, budget_id, user_id).await?;
+        if_match.check(&current.updated_at)?;
 
         // Determine new values (use existing if not provided)
         let new_month = dto.month.unwrap_or(current.month);
@@ -191,29 +472,30 @@ impl BudgetService {
             let exists = sqlx::query_scalar::<_, i64>(
                 "SELECT COUNT(*) FROM budgets WHERE owner_id = $1 AND month = $2 AND year = $3 AND id != $4",
             )
-            .bind(owner_id)
+            .bind(current.owner_id)
             .bind(new_month)
             .bind(new_year)
             .bind(budget_id)
-            .fetch_one(pool)
+            .fetch_one(&mut *tx)
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
             if exists > 0 {
-                return Err(AppError::Conflict(format!(
-                    "Budget already exists for {}/{}",
-                    new_month + 1,
-                    new_year
-                )));
+                return Err(AppError::Coded(
+                    ErrorCode::BudgetMonthConflict,
+                    format!("Budget already exists for {}/{}", new_month + 1, new_year),
+                ));
             }
         }
 
-        sqlx::query_as::<_, Budget>(
+        let budget = sqlx::query_as::<_, Budget>(
             r#"
             UPDATE budgets
             SET month = $1, year = $2, total_income = $3, savings_rate = $4, updated_at = NOW()
-            WHERE id = $5 AND owner_id = $6
-            RETURNING id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+            WHERE id = $5
+            RETURNING id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
             "#,
         )
         .bind(new_month)
@@ -221,77 +503,722 @@ impl BudgetService {
         .bind(new_income)
         .bind(new_savings_rate)
         .bind(budget_id)
-        .bind(owner_id)
-        .fetch_one(pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::BUDGET_UPDATE,
+            entities::BUDGET,
+            budget_id,
+            Some(&current),
+            Some(&budget),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(budget)
     }
 
-    /// Update only the income field (optimized single query with ownership check).
+    /// Update only the income field.
     pub async fn update_income(
         pool: &PgPool,
         budget_id: Uuid,
-        owner_id: Uuid,
+        user_id: Uuid,
         dto: &UpdateIncomeDto,
+        if_match: &IfMatch,
     ) -> Result<Budget, AppError> {
-        sqlx::query_as::<_, Budget>(
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let before = Self::lock_for_write(&mut tx, budget_id, user_id).await?;
+        if_match.check(&before.updated_at)?;
+
+        let budget = sqlx::query_as::<_, Budget>(
             r#"
             UPDATE budgets
             SET total_income = $1, updated_at = NOW()
-            WHERE id = $2 AND owner_id = $3
-            RETURNING id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+            WHERE id = $2
+            RETURNING id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
             "#,
         )
         .bind(dto.total_income)
         .bind(budget_id)
-        .bind(owner_id)
-        .fetch_optional(pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?
-        .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::BUDGET_UPDATE,
+            entities::BUDGET,
+            budget_id,
+            Some(&before),
+            Some(&budget),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(budget)
     }
 
-    /// Update only the savings rate field (optimized single query with ownership check).
+    /// Update only the savings rate field.
     pub async fn update_savings_rate(
         pool: &PgPool,
         budget_id: Uuid,
-        owner_id: Uuid,
+        user_id: Uuid,
         dto: &UpdateSavingsRateDto,
+        if_match: &IfMatch,
     ) -> Result<Budget, AppError> {
-        sqlx::query_as::<_, Budget>(
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let before = Self::lock_for_write(&mut tx, budget_id, user_id).await?;
+        if_match.check(&before.updated_at)?;
+
+        let budget = sqlx::query_as::<_, Budget>(
             r#"
             UPDATE budgets
             SET savings_rate = $1, updated_at = NOW()
-            WHERE id = $2 AND owner_id = $3
-            RETURNING id, owner_id, month, year, total_income, savings_rate, currency, created_at, updated_at
+            WHERE id = $2
+            RETURNING id, owner_id, month, year, total_income, savings_rate, currency,
+                (SELECT COALESCE(SUM(c.allocated_amount), 0) FROM categories c WHERE c.budget_id = budgets.id) AS total_allocated,
+                created_at, updated_at
             "#,
         )
         .bind(dto.savings_rate)
         .bind(budget_id)
-        .bind(owner_id)
-        .fetch_optional(pool)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?
-        .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::BUDGET_UPDATE,
+            entities::BUDGET,
+            budget_id,
+            Some(&before),
+            Some(&budget),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(budget)
     }
 
-    /// Delete a budget.
+    /// Delete a budget. Only the owner can delete it.
     pub async fn delete_budget(
         pool: &PgPool,
         budget_id: Uuid,
-        owner_id: Uuid,
+        user_id: Uuid,
     ) -> Result<(), AppError> {
-        let result = sqlx::query("DELETE FROM budgets WHERE id = $1 AND owner_id = $2")
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let before = Self::lock_for_write(&mut tx, budget_id, user_id).await?;
+        if before.owner_id != user_id {
+            return Err(AppError::NotFound("Budget not found".to_string()));
+        }
+
+        sqlx::query("DELETE FROM budgets WHERE id = $1")
             .bind(budget_id)
-            .bind(owner_id)
-            .execute(pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        if result.rows_affected() == 0 {
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::BUDGET_DELETE,
+            entities::BUDGET,
+            budget_id,
+            Some(&before),
+            None,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(())
+    }
+
+    /// Suggest (and optionally apply) category allocations from the average
+    /// spending of same-named categories in the preceding `months` budgets.
+    pub async fn auto_allocate(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+        query: &AutoAllocateQuery,
+    ) -> Result<AutoAllocateResponse, AppError> {
+        // Verify access before looking at history; applying needs edit access
+        Self::get_budget_by_id(pool, budget_id, user_id).await?;
+        if !query.preview
+            && !CategoryService::verify_budget_access(pool, budget_id, user_id, true).await?
+        {
             return Err(AppError::NotFound("Budget not found".to_string()));
         }
 
+        let history = sqlx::query_as::<_, CategorySpendingHistory>(
+            r#"
+            WITH target AS (
+                SELECT id, owner_id, year * 12 + month AS period
+                FROM budgets
+                WHERE id = $1 AND budget_access(id, $2, FALSE)
+            ),
+            past AS (
+                SELECT
+                    LOWER(c.name) AS name_key,
+                    c.id AS category_id,
+                    COALESCE(SUM(CASE WHEN t.reporting_type = 'expense' THEN t.reporting_amount ELSE 0 END), 0) AS spent
+                FROM target
+                INNER JOIN budgets b ON b.owner_id = target.owner_id
+                    AND b.year * 12 + b.month >= target.period - $3
+                    AND b.year * 12 + b.month < target.period
+                INNER JOIN users u ON u.id = b.owner_id
+                INNER JOIN categories c ON c.budget_id = b.id
+                LEFT JOIN transactions t ON t.category_id = c.id
+                    AND t.deleted_at IS NULL AND t.status = 'posted'
+                    AND t.transaction_date >= make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+                    AND t.transaction_date < (make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+                GROUP BY c.id, c.name
+            )
+            SELECT
+                c.id AS category_id,
+                c.name,
+                c.allocated_amount,
+                COALESCE(AVG(past.spent), 0) AS average_spent,
+                COUNT(past.category_id) AS months_with_data
+            FROM target
+            INNER JOIN categories c ON c.budget_id = target.id
+            LEFT JOIN past ON past.name_key = LOWER(c.name)
+            GROUP BY c.id, c.name, c.allocated_amount
+            ORDER BY c.name
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .bind(query.months)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let suggestions: Vec<AllocationSuggestion> = history
+            .into_iter()
+            .map(AllocationSuggestion::from_history)
+            .collect();
+
+        if !query.preview {
+            let mut tx = pool
+                .begin()
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            for suggestion in suggestions
+                .iter()
+                .filter(|s| s.suggested_allocated != s.current_allocated)
+            {
+                sqlx::query(
+                    "UPDATE categories SET allocated_amount = $1, updated_at = NOW() WHERE id = $2",
+                )
+                .bind(suggestion.suggested_allocated)
+                .bind(suggestion.category_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+            }
+
+            tx.commit()
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+            ResponseCache::invalidate_user(user_id);
+        }
+
+        Ok(AutoAllocateResponse {
+            budget_id,
+            months: query.months,
+            applied: !query.preview,
+            total_suggested: suggestions.iter().map(|s| s.suggested_allocated).sum(),
+            suggestions,
+        })
+    }
+
+    /// Move allocation from one category to another in a single transaction,
+    /// recording the move in the audit log.
+    pub async fn move_allocation(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+        dto: &MoveAllocationDto,
+    ) -> Result<MoveAllocationResponse, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // Lock both categories (in a stable order) and verify they belong to the user's budget
+        let categories = sqlx::query_as::<_, CategoryAllocation>(
+            r#"
+            SELECT c.id, c.name, c.allocated_amount
+            FROM categories c
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE c.budget_id = $1 AND budget_access(b.id, $2, TRUE) AND c.id IN ($3, $4)
+            ORDER BY c.id
+            FOR UPDATE OF c
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .bind(dto.from_category_id)
+        .bind(dto.to_category_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let from = categories
+            .iter()
+            .find(|c| c.category_id == dto.from_category_id)
+            .ok_or_else(|| AppError::NotFound("Source category not found".to_string()))?;
+        if !categories
+            .iter()
+            .any(|c| c.category_id == dto.to_category_id)
+        {
+            return Err(AppError::NotFound(
+                "Destination category not found".to_string(),
+            ));
+        }
+
+        if from.allocated_amount < dto.amount {
+            return Err(AppError::Coded(
+                ErrorCode::InsufficientAllocation,
+                format!(
+                    "Cannot move {} from '{}': only {} allocated",
+                    dto.amount, from.name, from.allocated_amount
+                ),
+            ));
+        }
+
+        let from = Self::adjust_allocation(&mut tx, dto.from_category_id, -dto.amount).await?;
+        let to = Self::adjust_allocation(&mut tx, dto.to_category_id, dto.amount).await?;
+
+        AuditService::record(
+            &mut tx,
+            user_id,
+            actions::BUDGET_MOVE_ALLOCATION,
+            entities::BUDGET,
+            budget_id,
+            json!({
+                "fromCategoryId": dto.from_category_id,
+                "toCategoryId": dto.to_category_id,
+                "amount": dto.amount,
+            }),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(MoveAllocationResponse {
+            budget_id,
+            amount: dto.amount,
+            from,
+            to,
+        })
+    }
+
+    /// Add `delta` to a category's allocation
+    pub async fn adjust_allocation(
+        tx: &mut sqlx::PgConnection,
+        category_id: Uuid,
+        delta: Decimal,
+    ) -> Result<CategoryAllocation, AppError> {
+        sqlx::query_as::<_, CategoryAllocation>(
+            r#"
+            UPDATE categories
+            SET allocated_amount = allocated_amount + $1, updated_at = NOW()
+            WHERE id = $2
+            RETURNING id, name, allocated_amount
+            "#,
+        )
+        .bind(delta)
+        .bind(category_id)
+        .fetch_one(tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Score the budget's health from spending pace, category thresholds and
+    /// savings progress, using the budget month in the owner's timezone.
+    pub async fn get_health(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+        threshold: i32,
+    ) -> Result<BudgetHealthResponse, AppError> {
+        let period = Self::get_period(pool, budget_id, user_id).await?;
+
+        let categories: Vec<CategorySpending> =
+            CategoryService::get_by_budget_id(pool, budget_id, user_id)
+                .await?
+                .into_iter()
+                .map(|c| CategorySpending {
+                    name: c.name,
+                    allocated_amount: c.allocated_amount,
+                    spent_amount: c.spent_amount,
+                })
+                .collect();
+
+        Ok(BudgetHealthResponse::compute(
+            budget_id,
+            &period,
+            &categories,
+            threshold,
+        ))
+    }
+
+    /// Budget income, savings rate and elapsed share of its month in the owner's timezone
+    async fn get_period(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BudgetPeriodRow, AppError> {
+        sqlx::query_as::<_, BudgetPeriodRow>(
+            r#"
+            SELECT
+                b.total_income,
+                b.savings_rate,
+                (EXTRACT(EPOCH FROM LEAST(GREATEST(NOW() - p.start_at, INTERVAL '0'), p.end_at - p.start_at))
+                    / EXTRACT(EPOCH FROM p.end_at - p.start_at))::NUMERIC(7, 6) AS elapsed_fraction
+            FROM budgets b
+            INNER JOIN users u ON u.id = b.owner_id
+            CROSS JOIN LATERAL (
+                SELECT
+                    make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone AS start_at,
+                    (make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone AS end_at
+            ) p
+            WHERE b.id = $1 AND budget_access(b.id, $2, FALSE)
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Budget not found".to_string()))
+    }
+
+    /// Project end-of-month spending per category and overall. Recurring items are
+    /// expenses whose amount appeared in the same-named category in each of the
+    /// two previous months; the rest of this month's spending is extrapolated
+    /// from the days elapsed.
+    pub async fn get_projection(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<BudgetProjectionResponse, AppError> {
+        let period = Self::get_period(pool, budget_id, user_id).await?;
+
+        let rows = sqlx::query_as::<_, CategoryProjectionRow>(
+            r#"
+            WITH target AS (
+                SELECT
+                    b.id,
+                    b.owner_id,
+                    make_timestamp(b.year, b.month + 1, 1, 0, 0, 0) AS start_at,
+                    u.timezone
+                FROM budgets b
+                INNER JOIN users u ON u.id = b.owner_id
+                WHERE b.id = $1 AND budget_access(b.id, $2, FALSE)
+            ),
+            history AS (
+                SELECT
+                    LOWER(c.name) AS name_key,
+                    t.amount,
+                    date_trunc('month', t.transaction_date AT TIME ZONE target.timezone) AS month_start
+                FROM target
+                INNER JOIN budgets b ON b.owner_id = target.owner_id
+                INNER JOIN categories c ON c.budget_id = b.id
+                INNER JOIN transactions t ON t.category_id = c.id
+                WHERE t.transaction_type = 'expense'
+                  AND t.deleted_at IS NULL AND t.status = 'posted'
+                  AND t.transaction_date >= (target.start_at - INTERVAL '2 months') AT TIME ZONE target.timezone
+                  AND t.transaction_date < target.start_at AT TIME ZONE target.timezone
+            ),
+            recurring AS (
+                SELECT name_key, amount
+                FROM history
+                GROUP BY name_key, amount
+                HAVING COUNT(DISTINCT month_start) = 2
+            ),
+            current_month AS (
+                SELECT t.category_id, t.reporting_amount AS amount
+                FROM target
+                INNER JOIN categories c ON c.budget_id = target.id
+                INNER JOIN transactions t ON t.category_id = c.id
+                WHERE t.reporting_type = 'expense'
+                  AND t.deleted_at IS NULL AND t.status = 'posted'
+                  AND t.transaction_date >= target.start_at AT TIME ZONE target.timezone
+                  AND t.transaction_date < (target.start_at + INTERVAL '1 month') AT TIME ZONE target.timezone
+            )
+            SELECT
+                c.id AS category_id,
+                c.name,
+                c.allocated_amount,
+                COALESCE((
+                    SELECT SUM(cm.amount) FROM current_month cm WHERE cm.category_id = c.id
+                ), 0) AS spent_amount,
+                COALESCE((
+                    SELECT SUM(cm.amount)
+                    FROM current_month cm
+                    WHERE cm.category_id = c.id
+                      AND EXISTS (
+                          SELECT 1 FROM recurring r
+                          WHERE r.name_key = LOWER(c.name) AND r.amount = cm.amount
+                      )
+                ), 0) AS recurring_spent,
+                COALESCE((
+                    SELECT SUM(r.amount)
+                    FROM recurring r
+                    WHERE r.name_key = LOWER(c.name)
+                      AND NOT EXISTS (
+                          SELECT 1 FROM current_month cm
+                          WHERE cm.category_id = c.id AND cm.amount = r.amount
+                      )
+                ), 0) AS recurring_pending
+            FROM target
+            INNER JOIN categories c ON c.budget_id = target.id
+            ORDER BY c.name
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(BudgetProjectionResponse::compute(budget_id, &period, &rows))
+    }
+
+    /// Invite a user by email to a budget. Only the owner can share a budget.
+    pub async fn invite_member(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+        dto: &InviteMemberDto,
+    ) -> Result<BudgetInvitation, AppError> {
+        let budget = Self::get_budget_by_id(pool, budget_id, user_id).await?;
+        if budget.owner_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the budget owner can invite members".to_string(),
+            ));
+        }
+
+        let already_shared: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM users u
+                WHERE LOWER(u.email) = LOWER($2)
+                  AND (u.id = $3 OR EXISTS (
+                      SELECT 1 FROM budget_members m
+                      WHERE m.budget_id = $1 AND m.user_id = u.id
+                  ))
+            )
+            "#,
+        )
+        .bind(budget_id)
+        .bind(&dto.email)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if already_shared {
+            return Err(AppError::ValidationError(
+                "This user already has access to the budget".to_string(),
+            ));
+        }
+
+        // An expired invitation no longer blocks inviting the email again
+        sqlx::query(
+            r#"
+            DELETE FROM budget_invitations
+            WHERE budget_id = $1 AND LOWER(email) = LOWER($2)
+              AND accepted_at IS NULL AND expires_at <= NOW()
+            "#,
+        )
+        .bind(budget_id)
+        .bind(&dto.email)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query_as::<_, BudgetInvitation>(&format!(
+            r#"
+            INSERT INTO budget_invitations (budget_id, email, role, invited_by)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {INVITATION_COLUMNS}
+            "#
+        ))
+        .bind(budget_id)
+        .bind(&dto.email)
+        .bind(dto.role.as_str())
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(invitation_write_error)
+    }
+
+    /// Pending, unexpired invitations addressed to the user's email
+    pub async fn list_invitations(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<BudgetInvitation>, AppError> {
+        sqlx::query_as::<_, BudgetInvitation>(
+            r#"
+            SELECT i.id, i.budget_id, i.email, i.role, i.invited_by, i.created_at, i.expires_at
+            FROM budget_invitations i
+            INNER JOIN users u ON LOWER(u.email) = LOWER(i.email)
+            WHERE u.id = $1 AND i.accepted_at IS NULL AND i.expires_at > NOW()
+            ORDER BY i.created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Accept an invitation addressed to the user's email, joining the budget
+    /// with the invited role.
+    pub async fn accept_invitation(
+        pool: &PgPool,
+        invitation_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Budget, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let (budget_id, role): (Uuid, String) = sqlx::query_as(
+            r#"
+            UPDATE budget_invitations i
+            SET accepted_at = NOW()
+            FROM users u
+            WHERE i.id = $1 AND u.id = $2
+              AND LOWER(u.email) = LOWER(i.email)
+              AND i.accepted_at IS NULL AND i.expires_at > NOW()
+            RETURNING i.budget_id, i.role
+            "#,
+        )
+        .bind(invitation_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Invitation not found".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO budget_members (budget_id, user_id, role)
+            SELECT $1, $2, $3
+            WHERE NOT EXISTS (SELECT 1 FROM budgets WHERE id = $1 AND owner_id = $2)
+            ON CONFLICT (budget_id, user_id) DO UPDATE SET role = EXCLUDED.role
+            "#,
+        )
+        .bind(budget_id)
+        .bind(user_id)
+        .bind(&role)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Self::get_budget_by_id(pool, budget_id, user_id).await
+    }
+
+    /// The owner and members of a budget the user can see, owner first
+    pub async fn list_members(
+        pool: &PgPool,
+        budget_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<BudgetMember>, AppError> {
+        Self::get_budget_by_id(pool, budget_id, user_id).await?;
+
+        sqlx::query_as::<_, BudgetMember>(
+            r#"
+            SELECT u.id AS user_id, u.email, u.full_name, 'owner' AS role, b.created_at, 0 AS rank
+            FROM budgets b
+            INNER JOIN users u ON u.id = b.owner_id
+            WHERE b.id = $1
+            UNION ALL
+            SELECT u.id, u.email, u.full_name, m.role, m.created_at, 1
+            FROM budget_members m
+            INNER JOIN users u ON u.id = m.user_id
+            WHERE m.budget_id = $1
+            ORDER BY rank, created_at
+            "#,
+        )
+        .bind(budget_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Remove a member from a budget. The owner can remove anyone; members
+    /// can only remove themselves (leave the budget).
+    pub async fn remove_member(
+        pool: &PgPool,
+        budget_id: Uuid,
+        member_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM budget_members
+            WHERE budget_id = $1 AND user_id = $2
+              AND ($2 = $3 OR EXISTS (SELECT 1 FROM budgets WHERE id = $1 AND owner_id = $3))
+            "#,
+        )
+        .bind(budget_id)
+        .bind(member_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Member not found".to_string()));
+        }
+
         Ok(())
     }
 }
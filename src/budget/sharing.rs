@@ -0,0 +1,186 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+/// Role of a member on a shared budget. The owner is not a member; it is
+/// reported with the `owner` role in member listings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetRole {
+    /// Full control, including sharing and deleting the budget
+    Owner,
+    /// Can change the budget, its categories and their transactions
+    Editor,
+    /// Can only read the budget, its categories and their transactions
+    Viewer,
+}
+
+impl BudgetRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetRole::Owner => "owner",
+            BudgetRole::Editor => "editor",
+            BudgetRole::Viewer => "viewer",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "owner" => Some(BudgetRole::Owner),
+            "editor" => Some(BudgetRole::Editor),
+            "viewer" => Some(BudgetRole::Viewer),
+            _ => None,
+        }
+    }
+}
+
+/// Request body for inviting a user to a budget
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InviteMemberDto {
+    /// Email of the user to invite (they may sign up later)
+    #[validate(email)]
+    #[schema(example = "partner@example.com")]
+    pub email: String,
+    /// Role granted on acceptance (editor or viewer)
+    pub role: BudgetRole,
+}
+
+impl InviteMemberDto {
+    /// Only members can be invited; there is a single owner
+    pub fn validate_role(&self) -> Result<(), &'static str> {
+        if self.role == BudgetRole::Owner {
+            return Err("role must be editor or viewer");
+        }
+        Ok(())
+    }
+}
+
+/// Budget invitation from the database
+#[derive(Debug, FromRow)]
+pub struct BudgetInvitation {
+    pub id: Uuid,
+    pub budget_id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Budget invitation returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InvitationResponse {
+    /// Invitation identifier, used to accept it
+    pub id: Uuid,
+    /// Budget being shared
+    pub budget_id: Uuid,
+    /// Email the invitation is addressed to
+    #[schema(example = "partner@example.com")]
+    pub email: String,
+    /// Role granted on acceptance
+    pub role: BudgetRole,
+    /// User who sent the invitation
+    pub invited_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// The invitation can't be accepted after this
+    pub expires_at: DateTime<Utc>,
+}
+
+impl From<BudgetInvitation> for InvitationResponse {
+    fn from(invitation: BudgetInvitation) -> Self {
+        Self {
+            id: invitation.id,
+            budget_id: invitation.budget_id,
+            email: invitation.email,
+            role: BudgetRole::parse(&invitation.role).unwrap_or(BudgetRole::Viewer),
+            invited_by: invitation.invited_by,
+            created_at: invitation.created_at,
+            expires_at: invitation.expires_at,
+        }
+    }
+}
+
+/// Budget member from the database (including the owner)
+#[derive(Debug, FromRow)]
+pub struct BudgetMember {
+    pub user_id: Uuid,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub role: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Budget member returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberResponse {
+    pub user_id: Uuid,
+    #[schema(example = "partner@example.com")]
+    pub email: String,
+    pub full_name: Option<String>,
+    pub role: BudgetRole,
+    /// When the user joined (for the owner, when the budget was created)
+    pub joined_at: DateTime<Utc>,
+}
+
+impl From<BudgetMember> for MemberResponse {
+    fn from(member: BudgetMember) -> Self {
+        Self {
+            user_id: member.user_id,
+            email: member.email,
+            full_name: member.full_name,
+            role: BudgetRole::parse(&member.role).unwrap_or(BudgetRole::Viewer),
+            joined_at: member.created_at,
+        }
+    }
+}
+
+/// Path parameters for invitation ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct InvitationIdPath {
+    /// Invitation UUID
+    pub id: Uuid,
+}
+
+/// Path parameters for a budget member
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BudgetMemberPath {
+    /// Budget UUID
+    pub id: Uuid,
+    /// Member's user UUID
+    pub user_id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invite_member_dto_roles() {
+        let dto: InviteMemberDto =
+            serde_json::from_str(r#"{"email":"partner@example.com","role":"editor"}"#).unwrap();
+        assert!(dto.validate().is_ok());
+        assert!(dto.validate_role().is_ok());
+
+        let owner = InviteMemberDto {
+            email: "partner@example.com".to_string(),
+            role: BudgetRole::Owner,
+        };
+        assert!(owner.validate_role().is_err());
+
+        let bad_email = InviteMemberDto {
+            email: "not-an-email".to_string(),
+            role: BudgetRole::Viewer,
+        };
+        assert!(bad_email.validate().is_err());
+
+        for role in [BudgetRole::Owner, BudgetRole::Editor, BudgetRole::Viewer] {
+            assert_eq!(BudgetRole::parse(role.as_str()), Some(role));
+        }
+    }
+}
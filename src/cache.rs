@@ -0,0 +1,221 @@
+use actix_web::http::header::ContentType;
+use actix_web::web::Bytes;
+use actix_web::HttpResponse;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Lifetime of an entry when the cache isn't configured (tests, tools)
+const DEFAULT_TTL: Duration = Duration::from_secs(30);
+
+/// Above this many slots, expired ones are dropped before adding another
+const PRUNE_ABOVE: usize = 10_000;
+
+/// Process-wide cache, created on first use
+static CACHE: OnceLock<ResponseCache> = OnceLock::new();
+
+/// A cached read endpoint, per user where the response is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheKey {
+    /// `GET /currencies`, the same for everyone. The list only changes
+    /// through migrations, so the entry just expires.
+    Currencies,
+    /// `GET /accounts/summary`
+    AccountsSummary(Uuid),
+    /// `GET /categories`
+    Categories(Uuid),
+}
+
+struct Slot {
+    /// Changed by every invalidation; a load started before one doesn't store
+    generation: u64,
+    body: Option<(Instant, Bytes)>,
+}
+
+#[derive(Default)]
+struct Slots {
+    slots: HashMap<CacheKey, Slot>,
+    next_generation: u64,
+}
+
+impl Slots {
+    fn next_generation(&mut self) -> u64 {
+        self.next_generation += 1;
+        self.next_generation
+    }
+}
+
+/// In-process cache of serialized JSON bodies for hot read endpoints.
+///
+/// Services invalidate entries after committing changes that affect them. An
+/// entry also expires after the TTL, which bounds how stale other instances
+/// (and members of a shared budget) can see it, since invalidation only
+/// reaches this process and the acting user.
+pub struct ResponseCache {
+    /// Zero disables caching
+    ttl: Duration,
+    slots: Mutex<Slots>,
+}
+
+impl ResponseCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            slots: Mutex::new(Slots::default()),
+        }
+    }
+
+    fn global() -> &'static Self {
+        CACHE.get_or_init(|| Self::new(DEFAULT_TTL))
+    }
+
+    /// Set the entry lifetime (zero disables caching); only the first call,
+    /// before anything is cached, takes effect
+    pub fn init(ttl: Duration) {
+        let _ = CACHE.set(Self::new(ttl));
+    }
+
+    /// Respond with the cached body for `key`, or load, serialize and cache it
+    pub async fn json<T, F, Fut>(key: CacheKey, load: F) -> Result<HttpResponse, AppError>
+    where
+        T: Serialize,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, AppError>>,
+    {
+        let cache = Self::global();
+        let generation = match cache.lookup(key) {
+            Ok(body) => return Ok(Self::respond(body)),
+            Err(generation) => generation,
+        };
+
+        let body = Bytes::from(
+            serde_json::to_vec(&load().await?)
+                .map_err(|e| AppError::InternalError(e.to_string()))?,
+        );
+        if let Some(generation) = generation {
+            cache.store(key, generation, body.clone());
+        }
+        Ok(Self::respond(body))
+    }
+
+    fn respond(body: Bytes) -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type(ContentType::json())
+            .body(body)
+    }
+
+    /// The fresh cached body, or else the generation a load must still match
+    /// to be stored (None while caching is disabled)
+    fn lookup(&self, key: CacheKey) -> Result<Bytes, Option<u64>> {
+        if self.ttl.is_zero() {
+            return Err(None);
+        }
+        let mut slots = self.slots.lock().unwrap();
+        if let Some(Slot {
+            body: Some((stored_at, body)),
+            ..
+        }) = slots.slots.get(&key)
+        {
+            if stored_at.elapsed() < self.ttl {
+                return Ok(body.clone());
+            }
+        }
+
+        if !slots.slots.contains_key(&key) {
+            if slots.slots.len() >= PRUNE_ABOVE {
+                let ttl = self.ttl;
+                slots.slots.retain(|_, slot| {
+                    slot.body
+                        .as_ref()
+                        .is_some_and(|(stored_at, _)| stored_at.elapsed() < ttl)
+                });
+            }
+            let generation = slots.next_generation();
+            slots.slots.insert(
+                key,
+                Slot {
+                    generation,
+                    body: None,
+                },
+            );
+        }
+        Err(Some(slots.slots[&key].generation))
+    }
+
+    fn store(&self, key: CacheKey, generation: u64, body: Bytes) {
+        let mut slots = self.slots.lock().unwrap();
+        if let Some(slot) = slots.slots.get_mut(&key) {
+            if slot.generation == generation {
+                slot.body = Some((Instant::now(), body));
+            }
+        }
+    }
+
+    fn invalidate_where(&self, matches: impl Fn(&CacheKey) -> bool) {
+        let mut slots = self.slots.lock().unwrap();
+        let keys: Vec<CacheKey> = slots.slots.keys().copied().filter(|k| matches(k)).collect();
+        for key in keys {
+            let generation = slots.next_generation();
+            if let Some(slot) = slots.slots.get_mut(&key) {
+                slot.generation = generation;
+                slot.body = None;
+            }
+        }
+    }
+
+    /// Drop the user's cached account summary and categories, after a change
+    /// to their accounts, transactions, categories or budgets
+    pub fn invalidate_user(user_id: Uuid) {
+        Self::global().invalidate_where(|key| {
+            matches!(key, CacheKey::AccountsSummary(id) | CacheKey::Categories(id) if *id == user_id)
+        });
+    }
+
+    /// Drop every cached account summary, after exchange rates or balances
+    /// change for everyone at once
+    pub fn invalidate_account_summaries() {
+        Self::global().invalidate_where(|key| matches!(key, CacheKey::AccountsSummary(_)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalidation_drops_entries_and_discards_loads_started_before_it() {
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        let user = Uuid::new_v4();
+        let key = CacheKey::Categories(user);
+
+        let generation = cache.lookup(key).unwrap_err().unwrap();
+        cache.store(key, generation, Bytes::from_static(b"[1]"));
+        assert_eq!(cache.lookup(key), Ok(Bytes::from_static(b"[1]")));
+
+        cache.invalidate_where(|k| *k == key);
+        let generation = cache.lookup(key).unwrap_err().unwrap();
+
+        // A load racing an invalidation must not store what it read
+        cache.invalidate_where(|k| *k == key);
+        cache.store(key, generation, Bytes::from_static(b"[stale]"));
+        assert!(cache.lookup(key).is_err());
+
+        // Other users' entries are untouched
+        let other = CacheKey::Categories(Uuid::new_v4());
+        let generation = cache.lookup(other).unwrap_err().unwrap();
+        cache.store(other, generation, Bytes::from_static(b"[2]"));
+        cache.invalidate_where(|k| *k == key);
+        assert!(cache.lookup(other).is_ok());
+    }
+
+    #[test]
+    fn test_zero_ttl_disables_caching() {
+        let cache = ResponseCache::new(Duration::ZERO);
+        assert_eq!(cache.lookup(CacheKey::Currencies), Err(None));
+    }
+}
@@ -1,14 +1,22 @@
+use actix_web::http::header;
 use actix_web::{delete, get, patch, post, web, HttpResponse};
 use sqlx::PgPool;
 use validator::Validate;
 
+use crate::auth::scopes::Scope;
+use crate::budget::models::MonthBaseQuery;
+use crate::cache::{CacheKey, ResponseCache};
 use crate::errors::{AppError, ErrorResponse};
-use crate::extractors::AuthenticatedUser;
+use crate::extractors::{etag, AuthenticatedUser, IfMatch};
 
 use super::models::{
-    BudgetIdPath, CategoryIdPath, CategoryResponse, CreateCategoryDto, UpdateCategoryDto,
+    AllocationGuardQuery, BudgetIdPath, CategoryIdPath, CategoryResponse, CategoryStatsQuery,
+    CategoryStatsResponse, CreateCategoryDto, UpdateCategoryDto,
 };
 use super::service::CategoryService;
+use super::templates::{
+    CategoryTemplateIdPath, CategoryTemplateResponse, CreateCategoryTemplateDto,
+};
 
 /// GET /categories - List all categories for the authenticated user
 #[utoipa::path(
@@ -17,7 +25,8 @@ use super::service::CategoryService;
     tag = "Categories",
     responses(
         (status = 200, description = "List of categories", body = Vec<CategoryResponse>),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -26,14 +35,17 @@ pub async fn list_categories(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
 ) -> Result<HttpResponse, AppError> {
-    let categories = CategoryService::get_all_for_user(pool.get_ref(), auth.user_id).await?;
+    auth.require_scope(Scope::CategoriesRead)?;
 
-    let response: Vec<CategoryResponse> = categories
-        .into_iter()
-        .map(CategoryResponse::from_category_with_spent)
-        .collect();
+    ResponseCache::json(CacheKey::Categories(auth.user_id), || async {
+        let categories = CategoryService::get_all_for_user(pool.get_ref(), auth.user_id).await?;
 
-    Ok(HttpResponse::Ok().json(response))
+        Ok(categories
+            .into_iter()
+            .map(CategoryResponse::from_category_with_spent)
+            .collect::<Vec<_>>())
+    })
+    .await
 }
 
 /// GET /categories/budget/{budget_id} - Get all categories for a budget
@@ -45,7 +57,8 @@ pub async fn list_categories(
     responses(
         (status = 200, description = "List of categories for budget", body = Vec<CategoryResponse>),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -55,6 +68,8 @@ pub async fn get_categories_by_budget(
     auth: AuthenticatedUser,
     path: web::Path<BudgetIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesRead)?;
+
     let categories =
         CategoryService::get_by_budget_id(pool.get_ref(), path.budget_id, auth.user_id).await?;
 
@@ -66,6 +81,39 @@ pub async fn get_categories_by_budget(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// GET /categories/{id}/stats - Spending statistics across recent months
+#[utoipa::path(
+    get,
+    path = "/categories/{id}/stats",
+    tag = "Categories",
+    params(CategoryIdPath, CategoryStatsQuery, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Category statistics", body = CategoryStatsResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/categories/{id}/stats")]
+pub async fn get_category_stats(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<CategoryIdPath>,
+    query: web::Query<CategoryStatsQuery>,
+    month_base: MonthBaseQuery,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesRead)?;
+
+    query.validate()?;
+
+    let stats =
+        CategoryService::get_stats(pool.get_ref(), path.id, auth.user_id, query.months).await?;
+
+    Ok(HttpResponse::Ok().json(stats.with_month_base(&month_base)))
+}
+
 /// GET /categories/{id} - Get a specific category
 #[utoipa::path(
     get,
@@ -75,7 +123,8 @@ pub async fn get_categories_by_budget(
     responses(
         (status = 200, description = "Category details", body = CategoryResponse),
         (status = 404, description = "Category not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -85,9 +134,14 @@ pub async fn get_category(
     auth: AuthenticatedUser,
     path: web::Path<CategoryIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesRead)?;
+
     let category = CategoryService::get_by_id(pool.get_ref(), path.id, auth.user_id).await?;
 
-    Ok(HttpResponse::Ok().json(CategoryResponse::from_category_with_spent(category)))
+    let response = CategoryResponse::from_category_with_spent(category);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// POST /categories - Create a new category
@@ -95,12 +149,15 @@ pub async fn get_category(
     post,
     path = "/categories",
     tag = "Categories",
+    params(AllocationGuardQuery),
     request_body = CreateCategoryDto,
     responses(
-        (status = 201, description = "Category created", body = CategoryResponse),
+        (status = 201, description = "Category created (may include over-allocation warnings)", body = CategoryResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Budget not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 409, description = "Strict mode: allocations would exceed the spending budget", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -108,16 +165,23 @@ pub async fn get_category(
 pub async fn create_category(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    guard: web::Query<AllocationGuardQuery>,
     body: web::Json<CreateCategoryDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    body.validate()?;
     body.validate_decimals()
         .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
-    let category = CategoryService::create(pool.get_ref(), &body, auth.user_id).await?;
+    let category =
+        CategoryService::create(pool.get_ref(), &body, auth.user_id, guard.strict).await?;
+    let warnings = CategoryService::allocation_warnings(pool.get_ref(), category.budget_id).await?;
 
-    Ok(HttpResponse::Created().json(CategoryResponse::from_category(category)))
+    let response = CategoryResponse::from_category(category).with_warnings(warnings);
+    Ok(HttpResponse::Created()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// PATCH /categories/{id} - Update a category
@@ -125,13 +189,16 @@ pub async fn create_category(
     patch,
     path = "/categories/{id}",
     tag = "Categories",
-    params(CategoryIdPath),
+    params(CategoryIdPath, AllocationGuardQuery),
     request_body = UpdateCategoryDto,
     responses(
-        (status = 200, description = "Category updated", body = CategoryResponse),
+        (status = 200, description = "Category updated (may include over-allocation warnings)", body = CategoryResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Category not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 409, description = "Strict mode: allocations would exceed the spending budget", body = ErrorResponse),
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -139,17 +206,32 @@ pub async fn create_category(
 pub async fn update_category(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
     path: web::Path<CategoryIdPath>,
+    guard: web::Query<AllocationGuardQuery>,
     body: web::Json<UpdateCategoryDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    body.validate()?;
     body.validate_fields()
         .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
-    let category = CategoryService::update(pool.get_ref(), path.id, &body, auth.user_id).await?;
+    let category = CategoryService::update(
+        pool.get_ref(),
+        path.id,
+        &body,
+        auth.user_id,
+        &if_match,
+        guard.strict,
+    )
+    .await?;
+    let warnings = CategoryService::allocation_warnings(pool.get_ref(), category.budget_id).await?;
 
-    Ok(HttpResponse::Ok().json(CategoryResponse::from_category(category)))
+    let response = CategoryResponse::from_category(category).with_warnings(warnings);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// DELETE /categories/{id} - Delete a category
@@ -161,7 +243,8 @@ pub async fn update_category(
     responses(
         (status = 204, description = "Category deleted"),
         (status = 404, description = "Category not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -171,7 +254,133 @@ pub async fn delete_category(
     auth: AuthenticatedUser,
     path: web::Path<CategoryIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesWrite)?;
+
     CategoryService::delete(pool.get_ref(), path.id, auth.user_id).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// GET /category-templates - List saved category templates
+#[utoipa::path(
+    get,
+    path = "/category-templates",
+    tag = "Categories",
+    responses(
+        (status = 200, description = "List of category templates", body = Vec<CategoryTemplateResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/category-templates")]
+pub async fn list_category_templates(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesRead)?;
+
+    let templates = CategoryService::list_templates(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(templates))
+}
+
+/// GET /category-templates/{id} - Get a category template
+#[utoipa::path(
+    get,
+    path = "/category-templates/{id}",
+    tag = "Categories",
+    params(CategoryTemplateIdPath),
+    responses(
+        (status = 200, description = "Category template details", body = CategoryTemplateResponse),
+        (status = 404, description = "Category template not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/category-templates/{id}")]
+pub async fn get_category_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<CategoryTemplateIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesRead)?;
+
+    let template = CategoryService::get_template(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(template))
+}
+
+/// POST /category-templates - Save a named set of categories
+#[utoipa::path(
+    post,
+    path = "/category-templates",
+    tag = "Categories",
+    request_body = CreateCategoryTemplateDto,
+    responses(
+        (status = 201, description = "Category template created", body = CategoryTemplateResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/category-templates")]
+pub async fn create_category_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateCategoryTemplateDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    body.validate()?;
+    body.validate_categories()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let template = CategoryService::create_template(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(template))
+}
+
+/// DELETE /category-templates/{id} - Delete a category template
+#[utoipa::path(
+    delete,
+    path = "/category-templates/{id}",
+    tag = "Categories",
+    params(CategoryTemplateIdPath),
+    responses(
+        (status = 204, description = "Category template deleted"),
+        (status = 404, description = "Category template not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/category-templates/{id}")]
+pub async fn delete_category_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<CategoryTemplateIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    CategoryService::delete_template(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Register the category and category template routes, fixed paths first
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_categories)
+        .service(get_categories_by_budget)
+        .service(get_category_stats)
+        .service(get_category)
+        .service(create_category)
+        .service(update_category)
+        .service(delete_category)
+        .service(list_category_templates)
+        .service(get_category_template)
+        .service(create_category_template)
+        .service(delete_category_template);
+}
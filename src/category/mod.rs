@@ -1,5 +1,6 @@
 pub mod handlers;
 pub mod models;
 pub mod service;
+pub mod templates;
 
 pub use handlers::*;
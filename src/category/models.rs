@@ -6,8 +6,11 @@ use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
+use crate::budget::models::MonthBaseQuery;
+use crate::transaction::models::Transaction;
+
 /// Validate hex color format (#RRGGBB)
-fn validate_color_hex(color: &str) -> Result<(), ValidationError> {
+pub fn validate_color_hex(color: &str) -> Result<(), ValidationError> {
     if color.len() != 7 {
         return Err(ValidationError::new("invalid_length"));
     }
@@ -21,25 +24,53 @@ fn validate_color_hex(color: &str) -> Result<(), ValidationError> {
 }
 
 /// Validate that a Decimal is non-negative
-fn validate_non_negative(value: &Decimal) -> Result<(), ValidationError> {
+pub fn validate_non_negative(value: &Decimal) -> Result<(), ValidationError> {
     if *value < Decimal::ZERO {
         return Err(ValidationError::new("must be non-negative"));
     }
     Ok(())
 }
 
+/// Trim a tax category label; blank means not deductible
+pub fn normalize_tax_category(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
 /// Database entity for categories
-#[derive(Debug, Clone, FromRow)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Category {
     pub id: Uuid,
     pub budget_id: Uuid,
     pub name: String,
     pub allocated_amount: Decimal,
     pub color_hex: String,
+    pub tax_category: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Category state stored in the audit log for undo. Deleting a category
+/// cascades to its transactions, so delete snapshots include them.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategorySnapshot {
+    pub category: Category,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transactions: Vec<Transaction>,
+}
+
+impl CategorySnapshot {
+    /// Snapshot of the category row alone (create/update)
+    pub fn of(category: &Category) -> Self {
+        Self {
+            category: category.clone(),
+            transactions: Vec::new(),
+        }
+    }
+}
+
 /// Extended model with computed spent_amount from transactions
 #[derive(Debug, Clone, FromRow)]
 pub struct CategoryWithSpent {
@@ -48,6 +79,7 @@ pub struct CategoryWithSpent {
     pub name: String,
     pub allocated_amount: Decimal,
     pub color_hex: String,
+    pub tax_category: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub spent_amount: Decimal,
@@ -76,10 +108,71 @@ pub struct CategoryResponse {
     /// Display color in hex format
     #[schema(example = "#4CAF50")]
     pub color_hex: String,
+    /// Tax category its expenses count toward (omitted when not deductible)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Charitable donations")]
+    pub tax_category: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     pub updated_at: DateTime<Utc>,
+    /// Non-blocking warnings about the parent budget (omitted when empty)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<AllocationWarning>,
+}
+
+/// Non-blocking warning returned when category allocations exceed the budget
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AllocationWarning {
+    /// Warning type code
+    #[schema(example = "OVER_ALLOCATED")]
+    pub code: String,
+    /// Human-readable message
+    #[schema(example = "Category allocations exceed the spending budget by 200.00")]
+    pub message: String,
+    /// Sum of all category allocations in the budget
+    #[schema(example = 4200.00)]
+    pub total_allocated: Decimal,
+    /// Budget income minus savings target
+    #[schema(example = 4000.00)]
+    pub spending_budget: Decimal,
+}
+
+/// Allocation figures for a budget, used to derive warnings
+#[derive(Debug, Clone, FromRow)]
+pub struct AllocationTotalsRow {
+    pub total_allocated: Decimal,
+    pub total_income: Decimal,
+    pub savings_rate: Decimal,
+}
+
+impl AllocationTotalsRow {
+    /// Income minus savings target
+    pub fn spending_budget(&self) -> Decimal {
+        self.total_income - self.total_income * self.savings_rate / Decimal::from(100)
+    }
+
+    /// How far allocations exceed the spending budget, if they do
+    pub fn over_allocated_by(&self) -> Option<Decimal> {
+        let over = self.total_allocated - self.spending_budget();
+        (over > Decimal::ZERO).then_some(over)
+    }
+
+    pub fn warnings(&self) -> Vec<AllocationWarning> {
+        let Some(over) = self.over_allocated_by() else {
+            return Vec::new();
+        };
+        vec![AllocationWarning {
+            code: "OVER_ALLOCATED".to_string(),
+            message: format!(
+                "Category allocations exceed the spending budget by {}",
+                over.round_dp(2)
+            ),
+            total_allocated: self.total_allocated,
+            spending_budget: self.spending_budget(),
+        }]
+    }
 }
 
 impl CategoryResponse {
@@ -94,11 +187,19 @@ impl CategoryResponse {
             spent_amount: cat.spent_amount,
             remaining_amount,
             color_hex: cat.color_hex,
+            tax_category: cat.tax_category,
             created_at: cat.created_at,
             updated_at: cat.updated_at,
+            warnings: Vec::new(),
         }
     }
 
+    /// Attach budget-level warnings to the response
+    pub fn with_warnings(mut self, warnings: Vec<AllocationWarning>) -> Self {
+        self.warnings = warnings;
+        self
+    }
+
     pub fn from_category(cat: Category) -> Self {
         Self {
             id: cat.id,
@@ -108,13 +209,15 @@ impl CategoryResponse {
             spent_amount: Decimal::ZERO,
             remaining_amount: cat.allocated_amount,
             color_hex: cat.color_hex,
+            tax_category: cat.tax_category,
             created_at: cat.created_at,
             updated_at: cat.updated_at,
+            warnings: Vec::new(),
         }
     }
 }
 
-fn default_color() -> String {
+pub fn default_color() -> String {
     "#64748b".to_string()
 }
 
@@ -143,6 +246,11 @@ pub struct CreateCategoryDto {
     #[serde(default = "default_color")]
     #[schema(example = "#4CAF50")]
     pub color_hex: String,
+
+    /// Tax category its expenses count toward, for the yearly tax report
+    #[validate(length(max = 50, message = "Tax category cannot exceed 50 characters"))]
+    #[schema(example = "Charitable donations")]
+    pub tax_category: Option<String>,
 }
 
 impl CreateCategoryDto {
@@ -171,6 +279,11 @@ pub struct UpdateCategoryDto {
     /// Display color in hex format
     #[schema(example = "#2196F3")]
     pub color_hex: Option<String>,
+
+    /// Tax category its expenses count toward; an empty string clears it
+    #[validate(length(max = 50, message = "Tax category cannot exceed 50 characters"))]
+    #[schema(example = "Charitable donations")]
+    pub tax_category: Option<String>,
 }
 
 impl UpdateCategoryDto {
@@ -193,9 +306,233 @@ pub struct CategoryIdPath {
     pub id: Uuid,
 }
 
+/// Query parameters for category writes that set an allocation
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AllocationGuardQuery {
+    /// Reject the write with 409 when it would allocate more than the budget's
+    /// spending budget, instead of only warning
+    #[serde(default)]
+    #[param(example = true)]
+    pub strict: bool,
+}
+
 /// Path parameters for budget ID
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct BudgetIdPath {
     /// Budget UUID
     pub budget_id: Uuid,
 }
+
+fn default_stats_months() -> i32 {
+    6
+}
+
+/// Query parameters for category statistics
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryStatsQuery {
+    /// Number of months ending with the category's budget month (1-24)
+    #[validate(range(min = 1, max = 24, message = "months must be between 1 and 24"))]
+    #[serde(default = "default_stats_months")]
+    #[param(example = 6)]
+    pub months: i32,
+}
+
+/// Spending for a same-named category in one budget month
+#[derive(Debug, Clone, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyCategorySpend {
+    /// Month (0-11, or 1-12 with monthBase=1)
+    #[schema(example = 2)]
+    pub month: i16,
+    /// Year
+    #[schema(example = 2026)]
+    pub year: i16,
+    /// Expenses in the month
+    #[schema(example = 412.50)]
+    pub spent: Decimal,
+    /// Number of expense transactions in the month
+    #[schema(example = 9)]
+    pub transaction_count: i64,
+}
+
+/// Direction of spending over the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SpendingTrend {
+    Up,
+    Down,
+    Flat,
+}
+
+/// Spending statistics for a category across monthly budgets
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryStatsResponse {
+    /// Category UUID
+    pub category_id: Uuid,
+    /// Category name (matched case-insensitively across budgets)
+    #[schema(example = "Groceries")]
+    pub name: String,
+    /// Window length in months
+    #[schema(example = 6)]
+    pub months: i32,
+    /// Months in the window with a budget containing this category
+    #[schema(example = 5)]
+    pub months_with_data: usize,
+    /// Average spend over months with data
+    #[schema(example = 398.20)]
+    pub average_monthly_spend: Decimal,
+    /// Lowest-spend month
+    pub min_month: Option<MonthlyCategorySpend>,
+    /// Highest-spend month
+    pub max_month: Option<MonthlyCategorySpend>,
+    /// Least-squares trend of monthly spend (flat when within 5% of the average per month)
+    pub trend: SpendingTrend,
+    /// Average number of transactions per month with data
+    #[schema(example = 8.4)]
+    pub transactions_per_month: Decimal,
+    /// Monthly series, oldest first
+    pub monthly: Vec<MonthlyCategorySpend>,
+}
+
+impl CategoryStatsResponse {
+    pub fn compute(
+        category_id: Uuid,
+        name: String,
+        months: i32,
+        monthly: Vec<MonthlyCategorySpend>,
+    ) -> Self {
+        let count = monthly.len();
+        let n = Decimal::from(count.max(1));
+        let total_spent: Decimal = monthly.iter().map(|m| m.spent).sum();
+        let total_transactions: i64 = monthly.iter().map(|m| m.transaction_count).sum();
+        let average = total_spent / n;
+
+        Self {
+            category_id,
+            name,
+            months,
+            months_with_data: count,
+            average_monthly_spend: average.round_dp(2),
+            min_month: monthly.iter().min_by_key(|m| m.spent).cloned(),
+            max_month: monthly.iter().max_by_key(|m| m.spent).cloned(),
+            trend: Self::trend(&monthly, average),
+            transactions_per_month: (Decimal::from(total_transactions) / n).round_dp(1),
+            monthly,
+        }
+    }
+
+    /// Slope of a least-squares fit over month index, compared to 5% of the average
+    fn trend(monthly: &[MonthlyCategorySpend], average: Decimal) -> SpendingTrend {
+        if monthly.len() < 2 {
+            return SpendingTrend::Flat;
+        }
+
+        let points: Vec<(Decimal, Decimal)> = monthly
+            .iter()
+            .map(|m| {
+                (
+                    Decimal::from(i32::from(m.year) * 12 + i32::from(m.month)),
+                    m.spent,
+                )
+            })
+            .collect();
+        let n = Decimal::from(points.len());
+        let mean_x = points.iter().map(|(x, _)| *x).sum::<Decimal>() / n;
+        let numerator: Decimal = points
+            .iter()
+            .map(|(x, y)| (*x - mean_x) * (*y - average))
+            .sum();
+        let denominator: Decimal = points
+            .iter()
+            .map(|(x, _)| (*x - mean_x) * (*x - mean_x))
+            .sum();
+        if denominator.is_zero() {
+            return SpendingTrend::Flat;
+        }
+
+        let slope = numerator / denominator;
+        let tolerance = average.abs() * Decimal::new(5, 2);
+        if slope > tolerance {
+            SpendingTrend::Up
+        } else if slope < -tolerance {
+            SpendingTrend::Down
+        } else {
+            SpendingTrend::Flat
+        }
+    }
+
+    /// Report months in the caller's numbering convention
+    pub fn with_month_base(mut self, month_base: &MonthBaseQuery) -> Self {
+        for m in self
+            .monthly
+            .iter_mut()
+            .chain(self.min_month.iter_mut())
+            .chain(self.max_month.iter_mut())
+        {
+            m.month = month_base.to_external(m.month);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn month(month: i16, spent: i64, transaction_count: i64) -> MonthlyCategorySpend {
+        MonthlyCategorySpend {
+            month,
+            year: 2026,
+            spent: Decimal::from(spent),
+            transaction_count,
+        }
+    }
+
+    #[test]
+    fn test_stats_detect_rising_spend() {
+        let stats = CategoryStatsResponse::compute(
+            Uuid::nil(),
+            "Groceries".to_string(),
+            6,
+            vec![month(0, 100, 4), month(1, 200, 6), month(2, 300, 8)],
+        );
+
+        assert_eq!(stats.average_monthly_spend, Decimal::from(200));
+        assert_eq!(stats.min_month.unwrap().month, 0);
+        assert_eq!(stats.max_month.unwrap().month, 2);
+        assert_eq!(stats.trend, SpendingTrend::Up);
+        assert_eq!(stats.transactions_per_month, Decimal::from(6));
+    }
+
+    #[test]
+    fn test_stats_flat_with_small_changes() {
+        let stats = CategoryStatsResponse::compute(
+            Uuid::nil(),
+            "Rent".to_string(),
+            3,
+            vec![month(0, 1000, 1), month(1, 1010, 1), month(2, 1000, 1)],
+        );
+        assert_eq!(stats.trend, SpendingTrend::Flat);
+    }
+
+    #[test]
+    fn test_over_allocation_is_measured_against_spending_budget() {
+        let totals = AllocationTotalsRow {
+            total_allocated: Decimal::from(800),
+            total_income: Decimal::from(1000),
+            savings_rate: Decimal::from(20),
+        };
+        assert_eq!(totals.spending_budget(), Decimal::from(800));
+        assert_eq!(totals.over_allocated_by(), None);
+        assert!(totals.warnings().is_empty());
+
+        let totals = AllocationTotalsRow {
+            total_allocated: Decimal::from(950),
+            ..totals
+        };
+        assert_eq!(totals.over_allocated_by(), Some(Decimal::from(150)));
+        assert_eq!(totals.warnings().len(), 1);
+    }
+}
@@ -1,51 +1,161 @@
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde_json::json;
+use sqlx::{PgConnection, PgPool, Postgres};
 use uuid::Uuid;
 
-use super::models::{Category, CategoryWithSpent, CreateCategoryDto, UpdateCategoryDto};
-use crate::errors::AppError;
+use super::models::{
+    normalize_tax_category, AllocationTotalsRow, AllocationWarning, Category, CategorySnapshot,
+    CategoryStatsResponse, CategoryWithSpent, CreateCategoryDto, MonthlyCategorySpend,
+    UpdateCategoryDto,
+};
+use super::templates::{
+    CategoryTemplate, CategoryTemplateItem, CategoryTemplateItemRow, CategoryTemplateResponse,
+    CreateCategoryTemplateDto,
+};
+use crate::audit::models::{actions, entities};
+use crate::audit::service::AuditService;
+use crate::budget::service::BudgetService;
+use crate::cache::ResponseCache;
+use crate::errors::{AppError, ErrorCode};
+use crate::extractors::IfMatch;
+use crate::summary::service::SummaryService;
+use crate::transaction::models::Transaction;
 
 /// Service layer for category business logic.
 pub struct CategoryService;
 
 impl CategoryService {
-    /// Verify user owns the budget - CRITICAL for authorization
-    pub async fn verify_budget_ownership(
+    /// Verify the user owns the budget or is a member allowed to read it (or,
+    /// with `write`, to change it) - CRITICAL for authorization
+    pub async fn verify_budget_access(
         pool: &PgPool,
         budget_id: Uuid,
         user_id: Uuid,
+        write: bool,
     ) -> Result<bool, AppError> {
-        let result = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM budgets WHERE id = $1 AND owner_id = $2",
+        sqlx::query_scalar::<_, bool>("SELECT budget_access($1, $2, $3)")
+            .bind(budget_id)
+            .bind(user_id)
+            .bind(write)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Compare the budget's total allocations to its spending budget
+    /// (income minus savings target) and return any non-blocking warnings.
+    pub async fn allocation_warnings(
+        pool: &PgPool,
+        budget_id: Uuid,
+    ) -> Result<Vec<AllocationWarning>, AppError> {
+        let totals = Self::allocation_totals(pool, budget_id).await?;
+        Ok(totals.map(|t| t.warnings()).unwrap_or_default())
+    }
+
+    async fn allocation_totals<'e, E>(
+        executor: E,
+        budget_id: Uuid,
+    ) -> Result<Option<AllocationTotalsRow>, AppError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as::<_, AllocationTotalsRow>(
+            r#"
+            SELECT
+                COALESCE((SELECT SUM(c.allocated_amount) FROM categories c WHERE c.budget_id = b.id), 0) as total_allocated,
+                b.total_income,
+                b.savings_rate
+            FROM budgets b
+            WHERE b.id = $1
+            "#,
         )
         .bind(budget_id)
-        .bind(user_id)
-        .fetch_one(pool)
+        .fetch_optional(executor)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Lock the budget so strict allocation checks of concurrent category
+    /// writes run one after another, each seeing the previous one's change
+    async fn lock_budget(conn: &mut PgConnection, budget_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("SELECT 1 FROM budgets WHERE id = $1 FOR UPDATE")
+            .bind(budget_id)
+            .execute(conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        Ok(())
+    }
 
-        Ok(result > 0)
+    /// Strict mode: fail with 409 when a write that raised allocations left
+    /// them above the spending budget. Writes that don't raise them (renames,
+    /// cuts) pass even in a budget that is already over-allocated.
+    async fn check_allocation_limit(
+        conn: &mut PgConnection,
+        budget_id: Uuid,
+        increase: Decimal,
+    ) -> Result<(), AppError> {
+        if increase <= Decimal::ZERO {
+            return Ok(());
+        }
+        let Some(totals) = Self::allocation_totals(conn, budget_id).await? else {
+            return Ok(());
+        };
+        match totals.over_allocated_by() {
+            Some(over) => Err(AppError::Detailed(
+                ErrorCode::AllocationExceedsBudget,
+                format!(
+                    "Category allocations would exceed the spending budget by {}",
+                    over.round_dp(2)
+                ),
+                json!({
+                    "overAllocatedBy": over.round_dp(2),
+                    "totalAllocated": totals.total_allocated,
+                    "spendingBudget": totals.spending_budget(),
+                }),
+            )),
+            None => Ok(()),
+        }
     }
 
-    /// Get category by ID with ownership check through budget
+    /// ID of the same-named category in the current month's budget, for
+    /// features that remember a category by name across months
+    pub async fn current_id_by_name(
+        pool: &PgPool,
+        user_id: Uuid,
+        name: &str,
+    ) -> Result<Uuid, AppError> {
+        let budget_id = BudgetService::current_budget_id(pool, user_id).await?;
+        sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM categories WHERE budget_id = $1 AND LOWER(name) = LOWER($2) LIMIT 1",
+        )
+        .bind(budget_id)
+        .bind(name)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| {
+            AppError::NotFound(format!(
+                "No category named '{}' in the current month's budget",
+                name
+            ))
+        })
+    }
+
+    /// Get category by ID with an access check through its budget
     pub async fn get_by_id(
         pool: &PgPool,
         category_id: Uuid,
         user_id: Uuid,
     ) -> Result<CategoryWithSpent, AppError> {
-        // Query with LEFT JOIN to transactions - returns 0 if no transactions exist
         sqlx::query_as::<_, CategoryWithSpent>(
             r#"
             SELECT
                 c.id, c.budget_id, c.name, c.allocated_amount,
-                c.color_hex, c.created_at, c.updated_at,
-                COALESCE(SUM(CASE WHEN t.transaction_type = 'expense' THEN t.amount ELSE 0 END), 0) as spent_amount
+                c.color_hex, c.tax_category, c.created_at, c.updated_at,
+                c.spent_amount
             FROM categories c
-            INNER JOIN budgets b ON c.budget_id = b.id AND b.owner_id = $2
-            LEFT JOIN transactions t ON c.id = t.category_id
+            INNER JOIN budgets b ON c.budget_id = b.id AND budget_access(b.id, $2, FALSE)
             WHERE c.id = $1
-            GROUP BY c.id, c.budget_id, c.name, c.allocated_amount,
-                     c.color_hex, c.created_at, c.updated_at
             "#,
         )
         .bind(category_id)
@@ -62,8 +172,8 @@ impl CategoryService {
         budget_id: Uuid,
         user_id: Uuid,
     ) -> Result<Vec<CategoryWithSpent>, AppError> {
-        // First verify ownership
-        if !Self::verify_budget_ownership(pool, budget_id, user_id).await? {
+        // First verify access
+        if !Self::verify_budget_access(pool, budget_id, user_id, false).await? {
             return Err(AppError::NotFound("Budget not found".to_string()));
         }
 
@@ -71,13 +181,10 @@ impl CategoryService {
             r#"
             SELECT
                 c.id, c.budget_id, c.name, c.allocated_amount,
-                c.color_hex, c.created_at, c.updated_at,
-                COALESCE(SUM(CASE WHEN t.transaction_type = 'expense' THEN t.amount ELSE 0 END), 0) as spent_amount
+                c.color_hex, c.tax_category, c.created_at, c.updated_at,
+                c.spent_amount
             FROM categories c
-            LEFT JOIN transactions t ON c.id = t.category_id
             WHERE c.budget_id = $1
-            GROUP BY c.id, c.budget_id, c.name, c.allocated_amount,
-                     c.color_hex, c.created_at, c.updated_at
             ORDER BY c.name ASC
             "#,
         )
@@ -87,7 +194,7 @@ impl CategoryService {
         .map_err(|e| AppError::InternalError(e.to_string()))
     }
 
-    /// Get all categories for user (across all budgets)
+    /// Get all categories for user (across all budgets they can read, shared ones included)
     pub async fn get_all_for_user(
         pool: &PgPool,
         user_id: Uuid,
@@ -96,13 +203,10 @@ impl CategoryService {
             r#"
             SELECT
                 c.id, c.budget_id, c.name, c.allocated_amount,
-                c.color_hex, c.created_at, c.updated_at,
-                COALESCE(SUM(CASE WHEN t.transaction_type = 'expense' THEN t.amount ELSE 0 END), 0) as spent_amount
+                c.color_hex, c.tax_category, c.created_at, c.updated_at,
+                c.spent_amount
             FROM categories c
-            INNER JOIN budgets b ON c.budget_id = b.id AND b.owner_id = $1
-            LEFT JOIN transactions t ON c.id = t.category_id
-            GROUP BY c.id, c.budget_id, c.name, c.allocated_amount,
-                     c.color_hex, c.created_at, c.updated_at
+            INNER JOIN budgets b ON c.budget_id = b.id AND budget_access(b.id, $1, FALSE)
             ORDER BY c.name ASC
             "#,
         )
@@ -112,14 +216,74 @@ impl CategoryService {
         .map_err(|e| AppError::InternalError(e.to_string()))
     }
 
+    /// Spending statistics for a category over the `months` budget months
+    /// ending with its own, matching same-named categories in earlier budgets.
+    /// Monthly amounts come from the summary tables.
+    pub async fn get_stats(
+        pool: &PgPool,
+        category_id: Uuid,
+        user_id: Uuid,
+        months: i32,
+    ) -> Result<CategoryStatsResponse, AppError> {
+        let category = Self::get_by_id(pool, category_id, user_id).await?;
+        let owner_id = sqlx::query_scalar::<_, Uuid>("SELECT owner_id FROM budgets WHERE id = $1")
+            .bind(category.budget_id)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        SummaryService::refresh_dirty(pool, Some(owner_id)).await?;
+
+        let monthly = sqlx::query_as::<_, MonthlyCategorySpend>(
+            r#"
+            WITH target AS (
+                SELECT c.name, b.owner_id, b.year * 12 + b.month AS period
+                FROM categories c
+                INNER JOIN budgets b ON c.budget_id = b.id
+                WHERE c.id = $1 AND budget_access(b.id, $2, FALSE)
+            )
+            SELECT
+                b.month,
+                b.year,
+                COALESCE(SUM(s.total), 0) AS spent,
+                COALESCE(SUM(s.transaction_count), 0)::BIGINT AS transaction_count
+            FROM target
+            INNER JOIN budgets b ON b.owner_id = target.owner_id
+                AND b.year * 12 + b.month > target.period - $3
+                AND b.year * 12 + b.month <= target.period
+            INNER JOIN categories c ON c.budget_id = b.id AND LOWER(c.name) = LOWER(target.name)
+            LEFT JOIN monthly_category_totals s ON s.category_id = c.id
+                AND s.owner_id = b.owner_id
+                AND s.year = b.year
+                AND s.month = b.month
+                AND s.transaction_type = 'expense'
+            GROUP BY b.year, b.month
+            ORDER BY b.year, b.month
+            "#,
+        )
+        .bind(category_id)
+        .bind(user_id)
+        .bind(months)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(CategoryStatsResponse::compute(
+            category_id,
+            category.name,
+            months,
+            monthly,
+        ))
+    }
+
     /// Create a new category
     pub async fn create(
         pool: &PgPool,
         dto: &CreateCategoryDto,
         user_id: Uuid,
+        strict: bool,
     ) -> Result<Category, AppError> {
-        // Verify budget ownership first
-        if !Self::verify_budget_ownership(pool, dto.budget_id, user_id).await? {
+        // Verify budget access first
+        if !Self::verify_budget_access(pool, dto.budget_id, user_id, true).await? {
             return Err(AppError::NotFound("Budget not found".to_string()));
         }
 
@@ -133,21 +297,52 @@ impl CategoryService {
 
         let allocated_amount = dto.allocated_amount.unwrap_or(Decimal::ZERO);
 
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if strict {
+            Self::lock_budget(&mut tx, dto.budget_id).await?;
+        }
+
         let category = sqlx::query_as::<_, Category>(
             r#"
-            INSERT INTO categories (budget_id, name, allocated_amount, color_hex)
-            VALUES ($1, $2, $3, $4)
-            RETURNING id, budget_id, name, allocated_amount, color_hex, created_at, updated_at
+            INSERT INTO categories (budget_id, name, allocated_amount, color_hex, tax_category)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, budget_id, name, allocated_amount, color_hex, tax_category,
+                      created_at, updated_at
             "#,
         )
         .bind(dto.budget_id)
         .bind(&name)
         .bind(allocated_amount)
         .bind(&dto.color_hex)
-        .fetch_one(pool)
+        .bind(normalize_tax_category(dto.tax_category.as_deref()))
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?;
 
+        if strict {
+            Self::check_allocation_limit(&mut tx, dto.budget_id, allocated_amount).await?;
+        }
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::CATEGORY_CREATE,
+            entities::CATEGORY,
+            category.id,
+            None,
+            Some(&CategorySnapshot::of(&category)),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
         Ok(category)
     }
 
@@ -157,9 +352,20 @@ impl CategoryService {
         category_id: Uuid,
         dto: &UpdateCategoryDto,
         user_id: Uuid,
+        if_match: &IfMatch,
+        strict: bool,
     ) -> Result<Category, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
         // First verify the category exists and user has access
-        let existing = Self::get_by_id(pool, category_id, user_id).await?;
+        let existing = Self::lock_owned(&mut tx, category_id, user_id).await?;
+        if_match.check(&existing.updated_at)?;
+        if strict {
+            Self::lock_budget(&mut tx, existing.budget_id).await?;
+        }
 
         // Build update values
         let new_name = match &dto.name {
@@ -172,40 +378,530 @@ impl CategoryService {
                 }
                 trimmed
             }
-            None => existing.name,
+            None => existing.name.clone(),
         };
 
         let new_allocated_amount = dto.allocated_amount.unwrap_or(existing.allocated_amount);
         let new_color_hex = dto.color_hex.as_ref().unwrap_or(&existing.color_hex);
+        let new_tax_category = match &dto.tax_category {
+            Some(t) => normalize_tax_category(Some(t)),
+            None => existing.tax_category.clone(),
+        };
 
-        sqlx::query_as::<_, Category>(
+        let updated = sqlx::query_as::<_, Category>(
             r#"
             UPDATE categories
-            SET name = $2, allocated_amount = $3, color_hex = $4, updated_at = NOW()
+            SET name = $2, allocated_amount = $3, color_hex = $4, tax_category = $5,
+                updated_at = NOW()
             WHERE id = $1
-            RETURNING id, budget_id, name, allocated_amount, color_hex, created_at, updated_at
+            RETURNING id, budget_id, name, allocated_amount, color_hex, tax_category,
+                      created_at, updated_at
             "#,
         )
         .bind(category_id)
         .bind(&new_name)
         .bind(new_allocated_amount)
         .bind(new_color_hex)
-        .fetch_one(pool)
+        .bind(&new_tax_category)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if strict {
+            Self::check_allocation_limit(
+                &mut tx,
+                existing.budget_id,
+                new_allocated_amount - existing.allocated_amount,
+            )
+            .await?;
+        }
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::CATEGORY_UPDATE,
+            entities::CATEGORY,
+            category_id,
+            Some(&CategorySnapshot::of(&existing)),
+            Some(&CategorySnapshot::of(&updated)),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(updated)
     }
 
     /// Delete a category (cascades to transactions)
     pub async fn delete(pool: &PgPool, category_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
         // Verify ownership first
-        let _ = Self::get_by_id(pool, category_id, user_id).await?;
+        let category = Self::lock_owned(&mut tx, category_id, user_id).await?;
+
+        // Snapshot the transactions the cascade will remove so undo can restore them
+        let transactions = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT id, category_id, account_id, destination_account_id, amount, transaction_date,
+                   description, transaction_type, reversal_of_id,
+                   reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                   tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            FROM transactions
+            WHERE category_id = $1 AND deleted_at IS NULL AND status = 'posted'
+            "#,
+        )
+        .bind(category_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
 
         sqlx::query("DELETE FROM categories WHERE id = $1")
             .bind(category_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::CATEGORY_DELETE,
+            entities::CATEGORY,
+            category_id,
+            Some(&CategorySnapshot {
+                category,
+                transactions,
+            }),
+            None,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(())
+    }
+
+    /// Lock a category row the user may change
+    async fn lock_owned(
+        conn: &mut PgConnection,
+        category_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Category, AppError> {
+        sqlx::query_as::<_, Category>(
+            r#"
+            SELECT c.id, c.budget_id, c.name, c.allocated_amount, c.color_hex, c.tax_category,
+                   c.created_at, c.updated_at
+            FROM categories c
+            INNER JOIN budgets b ON c.budget_id = b.id AND budget_access(b.id, $2, TRUE)
+            WHERE c.id = $1
+            FOR UPDATE OF c
+            "#,
+        )
+        .bind(category_id)
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Category not found".to_string()))
+    }
+
+    /// Put a category back to an audited snapshot (used by undo). `None` means
+    /// the category didn't exist, so it is removed. Transactions in a delete
+    /// snapshot are re-inserted as-is: the cascade never touched balances.
+    pub async fn restore_snapshot(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        category_id: Uuid,
+        snapshot: Option<&CategorySnapshot>,
+    ) -> Result<(), AppError> {
+        let Some(snapshot) = snapshot else {
+            let _ = Self::lock_owned(conn, category_id, user_id).await?;
+
+            let has_transactions = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS(SELECT 1 FROM transactions WHERE category_id = $1 AND deleted_at IS NULL)",
+            )
+            .bind(category_id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            if has_transactions {
+                return Err(AppError::Coded(
+                    ErrorCode::UndoConflict,
+                    "Cannot undo: the category now has transactions".to_string(),
+                ));
+            }
+
+            sqlx::query("DELETE FROM categories WHERE id = $1")
+                .bind(category_id)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            return Ok(());
+        };
+
+        let category = &snapshot.category;
+        let budget_exists = sqlx::query_scalar::<_, bool>("SELECT budget_access($1, $2, TRUE)")
+            .bind(category.budget_id)
+            .bind(user_id)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !budget_exists {
+            return Err(AppError::Coded(
+                ErrorCode::UndoConflict,
+                "Cannot undo: the budget no longer exists".to_string(),
+            ));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO categories
+                (id, budget_id, name, allocated_amount, color_hex, tax_category, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                name = EXCLUDED.name,
+                allocated_amount = EXCLUDED.allocated_amount,
+                color_hex = EXCLUDED.color_hex,
+                tax_category = EXCLUDED.tax_category,
+                updated_at = NOW()
+            "#,
+        )
+        .bind(category.id)
+        .bind(category.budget_id)
+        .bind(&category.name)
+        .bind(category.allocated_amount)
+        .bind(&category.color_hex)
+        .bind(&category.tax_category)
+        .bind(category.created_at)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // Originals go in before their refunds so the links can be restored
+        let (originals, refunds): (Vec<_>, Vec<_>) = snapshot
+            .transactions
+            .iter()
+            .partition(|t| t.reversal_of_id.is_none());
+
+        for t in originals.into_iter().chain(refunds) {
+            // Accounts deleted since fall back to NULL, as ON DELETE SET NULL would have done
+            sqlx::query(
+                r#"
+                INSERT INTO transactions
+                    (id, category_id, account_id, destination_account_id, amount, transaction_date,
+                     description, transaction_type, created_at, reversal_of_id,
+                     reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                     tax_category)
+                VALUES (
+                    $1, $2,
+                    (SELECT id FROM accounts WHERE id = $3),
+                    (SELECT id FROM accounts WHERE id = $4),
+                    $5, $6, $7, $8, $9,
+                    (SELECT id FROM transactions WHERE id = $10),
+                    $11, $12, $13,
+                    (SELECT id FROM transactions WHERE id = $14),
+                    $15
+                )
+                ON CONFLICT (id) DO NOTHING
+                "#,
+            )
+            .bind(t.id)
+            .bind(t.category_id)
+            .bind(t.account_id)
+            .bind(t.destination_account_id)
+            .bind(t.amount)
+            .bind(t.transaction_date)
+            .bind(&t.description)
+            .bind(&t.transaction_type)
+            .bind(t.created_at)
+            .bind(t.reversal_of_id)
+            .bind(t.reimbursable)
+            .bind(&t.reimbursement_status)
+            .bind(&t.reimbursement_payer)
+            .bind(t.reimbursed_by_id)
+            .bind(&t.tax_category)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+    /// List the user's category templates with their categories
+    pub async fn list_templates(
+        pool: &PgPool,
+        owner_id: Uuid,
+    ) -> Result<Vec<CategoryTemplateResponse>, AppError> {
+        let templates = sqlx::query_as::<_, CategoryTemplate>(
+            "SELECT id, name, created_at, updated_at FROM category_templates WHERE owner_id = $1 ORDER BY name",
+        )
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let ids: Vec<Uuid> = templates.iter().map(|t| t.id).collect();
+        let rows = sqlx::query_as::<_, CategoryTemplateItemRow>(
+            r#"
+            SELECT template_id, name, allocated_amount, color_hex, tax_category
+            FROM category_template_items
+            WHERE template_id = ANY($1)
+            ORDER BY template_id, position
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut responses: Vec<CategoryTemplateResponse> = templates
+            .into_iter()
+            .map(|template| CategoryTemplateResponse::new(template, Vec::new()))
+            .collect();
+        for row in rows {
+            if let Some(response) = responses.iter_mut().find(|r| r.id == row.template_id) {
+                response.categories.push(row.item);
+            }
+        }
+        Ok(responses)
+    }
+
+    /// Get a category template with its categories
+    pub async fn get_template(
+        pool: &PgPool,
+        template_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<CategoryTemplateResponse, AppError> {
+        let template = sqlx::query_as::<_, CategoryTemplate>(
+            "SELECT id, name, created_at, updated_at FROM category_templates WHERE id = $1 AND owner_id = $2",
+        )
+        .bind(template_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Category template not found".to_string()))?;
+
+        let mut conn = pool
+            .acquire()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        let items = Self::template_items(&mut conn, template_id, owner_id).await?;
+
+        Ok(CategoryTemplateResponse::new(template, items))
+    }
+
+    /// Save a named set of categories
+    pub async fn create_template(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateCategoryTemplateDto,
+    ) -> Result<CategoryTemplateResponse, AppError> {
+        let name = dto.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Name cannot be empty".to_string(),
+            ));
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let template = sqlx::query_as::<_, CategoryTemplate>(
+            r#"
+            INSERT INTO category_templates (owner_id, name)
+            VALUES ($1, $2)
+            RETURNING id, name, created_at, updated_at
+            "#,
+        )
+        .bind(owner_id)
+        .bind(&name)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut items = Vec::with_capacity(dto.categories.len());
+        for (position, item) in dto.categories.iter().enumerate() {
+            let item = CategoryTemplateItem {
+                name: item.name.trim().to_string(),
+                tax_category: normalize_tax_category(item.tax_category.as_deref()),
+                ..item.clone()
+            };
+            sqlx::query(
+                r#"
+                INSERT INTO category_template_items
+                    (template_id, position, name, allocated_amount, color_hex, tax_category)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(template.id)
+            .bind(position as i32)
+            .bind(&item.name)
+            .bind(item.allocated_amount)
+            .bind(&item.color_hex)
+            .bind(&item.tax_category)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            items.push(item);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(CategoryTemplateResponse::new(template, items))
+    }
+
+    /// Delete a category template; categories created from it are kept
+    pub async fn delete_template(
+        pool: &PgPool,
+        template_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM category_templates WHERE id = $1 AND owner_id = $2")
+            .bind(template_id)
+            .bind(owner_id)
             .execute(pool)
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(
+                "Category template not found".to_string(),
+            ));
+        }
         Ok(())
     }
+
+    /// Categories of one of the user's templates, in display order
+    async fn template_items(
+        conn: &mut PgConnection,
+        template_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Vec<CategoryTemplateItem>, AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM category_templates WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(template_id)
+        .bind(owner_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if !exists {
+            return Err(AppError::NotFound(
+                "Category template not found".to_string(),
+            ));
+        }
+
+        sqlx::query_as::<_, CategoryTemplateItem>(
+            r#"
+            SELECT name, allocated_amount, color_hex, tax_category
+            FROM category_template_items
+            WHERE template_id = $1
+            ORDER BY position
+            "#,
+        )
+        .bind(template_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Create a template's categories in a budget the user may change, inside
+    /// the caller's transaction. Categories whose name the budget already has
+    /// are skipped, so applying a template twice adds nothing.
+    pub async fn apply_template_in_tx(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        budget_id: Uuid,
+        template_id: Uuid,
+    ) -> Result<Vec<Category>, AppError> {
+        let items = Self::template_items(conn, template_id, user_id).await?;
+
+        let existing: Vec<String> = sqlx::query_scalar::<_, String>(
+            "SELECT LOWER(name) FROM categories WHERE budget_id = $1",
+        )
+        .bind(budget_id)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut created = Vec::new();
+        for item in items
+            .into_iter()
+            .filter(|item| !existing.contains(&item.name.to_lowercase()))
+        {
+            let category = sqlx::query_as::<_, Category>(
+                r#"
+                INSERT INTO categories (budget_id, name, allocated_amount, color_hex, tax_category)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING id, budget_id, name, allocated_amount, color_hex, tax_category,
+                          created_at, updated_at
+                "#,
+            )
+            .bind(budget_id)
+            .bind(&item.name)
+            .bind(item.allocated_amount)
+            .bind(&item.color_hex)
+            .bind(&item.tax_category)
+            .fetch_one(&mut *conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            AuditService::record_change(
+                conn,
+                user_id,
+                actions::CATEGORY_CREATE,
+                entities::CATEGORY,
+                category.id,
+                None,
+                Some(&CategorySnapshot::of(&category)),
+            )
+            .await?;
+
+            created.push(category);
+        }
+
+        Ok(created)
+    }
+
+    /// Apply a category template to an existing budget
+    pub async fn apply_template(
+        pool: &PgPool,
+        budget_id: Uuid,
+        template_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<Category>, AppError> {
+        if !Self::verify_budget_access(pool, budget_id, user_id, true).await? {
+            return Err(AppError::NotFound("Budget not found".to_string()));
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let created = Self::apply_template_in_tx(&mut tx, user_id, budget_id, template_id).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(created)
+    }
 }
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::models::{default_color, validate_color_hex, validate_non_negative};
+
+/// One category a template creates
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTemplateItem {
+    /// Category name (1-50 characters)
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    #[schema(example = "Groceries")]
+    pub name: String,
+
+    /// Amount allocated (defaults to 0)
+    #[validate(custom(
+        function = "validate_non_negative",
+        message = "Allocated amount must be non-negative"
+    ))]
+    #[serde(default)]
+    #[schema(example = 500.00)]
+    pub allocated_amount: Decimal,
+
+    /// Display color in hex format (defaults to #64748b)
+    #[validate(custom(
+        function = "validate_color_hex",
+        message = "Color must be in #RRGGBB format"
+    ))]
+    #[serde(default = "default_color")]
+    #[schema(example = "#4CAF50")]
+    pub color_hex: String,
+
+    /// Tax category its expenses count toward
+    #[validate(length(max = 50, message = "Tax category cannot exceed 50 characters"))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Charitable donations")]
+    pub tax_category: Option<String>,
+}
+
+/// Request body for saving a category template
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateCategoryTemplateDto {
+    /// Display name (1-50 characters)
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    #[schema(example = "Standard month")]
+    pub name: String,
+
+    /// Categories to create, in display order (1-100)
+    #[validate(length(min = 1, max = 100, message = "A template needs 1-100 categories"))]
+    #[validate(nested)]
+    pub categories: Vec<CategoryTemplateItem>,
+}
+
+impl CreateCategoryTemplateDto {
+    /// Category names must be unique, ignoring case
+    pub fn validate_categories(&self) -> Result<(), ValidationError> {
+        let mut seen: Vec<String> = Vec::new();
+        for item in &self.categories {
+            let key = item.name.trim().to_lowercase();
+            if key.is_empty() || seen.contains(&key) {
+                return Err(ValidationError::new("duplicate_category_name"));
+            }
+            seen.push(key);
+        }
+        Ok(())
+    }
+}
+
+/// Category template from the database
+#[derive(Debug, FromRow)]
+pub struct CategoryTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Template category row, with the template it belongs to
+#[derive(Debug, FromRow)]
+pub struct CategoryTemplateItemRow {
+    pub template_id: Uuid,
+    #[sqlx(flatten)]
+    pub item: CategoryTemplateItem,
+}
+
+/// Category template returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryTemplateResponse {
+    pub id: Uuid,
+    /// Display name
+    #[schema(example = "Standard month")]
+    pub name: String,
+    /// Categories the template creates, in display order
+    pub categories: Vec<CategoryTemplateItem>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CategoryTemplateResponse {
+    pub fn new(template: CategoryTemplate, categories: Vec<CategoryTemplateItem>) -> Self {
+        Self {
+            id: template.id,
+            name: template.name,
+            categories,
+            created_at: template.created_at,
+            updated_at: template.updated_at,
+        }
+    }
+}
+
+/// Request body for applying a category template to a budget
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyCategoryTemplateDto {
+    /// Template whose categories to create
+    pub template_id: Uuid,
+}
+
+/// Path parameters for category template ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct CategoryTemplateIdPath {
+    /// Category template UUID
+    pub id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_template_dto_defaults_and_duplicates() {
+        let dto: CreateCategoryTemplateDto = serde_json::from_value(serde_json::json!({
+            "name": "Standard month",
+            "categories": [{ "name": "Rent", "allocatedAmount": 1200 }, { "name": "Groceries" }]
+        }))
+        .unwrap();
+        assert!(dto.validate().is_ok());
+        assert!(dto.validate_categories().is_ok());
+        assert_eq!(dto.categories[1].allocated_amount, Decimal::ZERO);
+        assert_eq!(dto.categories[1].color_hex, "#64748b");
+
+        let duplicate: CreateCategoryTemplateDto = serde_json::from_value(serde_json::json!({
+            "name": "Doubled",
+            "categories": [{ "name": "Rent" }, { "name": " rent " }]
+        }))
+        .unwrap();
+        assert!(duplicate.validate_categories().is_err());
+
+        let bad_color: CreateCategoryTemplateDto = serde_json::from_value(serde_json::json!({
+            "name": "Bad",
+            "categories": [{ "name": "Rent", "colorHex": "red" }]
+        }))
+        .unwrap();
+        assert!(bad_color.validate().is_err());
+    }
+}
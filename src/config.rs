@@ -0,0 +1,757 @@
+use secrecy::{ExposeSecret, Secret};
+use std::env;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::auth::models::PasswordResetConfig;
+use crate::bank_sync::provider::{BankSyncProvider, PlaidClient};
+use crate::crypto::EncryptionKeys;
+use crate::db::models::PoolConfig;
+use crate::debug::models::DebugConfig;
+use crate::errors::AppError;
+use crate::health::models::HealthConfig;
+use crate::holding::price::PriceProvider;
+use crate::jobs::models::{
+    BankSyncConfig, BillReminderConfig, ChargePostingConfig, DigestConfig, IntegrityAuditConfig,
+    JobsConfig, LeaderElectionConfig, LoanPostingConfig, RefreshTokenCleanupConfig,
+    RetentionJobConfig, SummaryRefreshConfig, ValuationConfig, RETENTION_POLICIES,
+};
+use crate::mailer::smtp::{SmtpMailer, SmtpSecurity};
+use crate::ratelimit::RateLimitQuota;
+use crate::receipt::models::InboundEmailConfig;
+use crate::storage::ObjectStorage;
+use crate::uploads::scanner::Scanner;
+
+/// Shortest JWT secret accepted: 256 bits, the HS256 key size
+const MIN_JWT_SECRET_LEN: usize = 32;
+
+/// Where the HTTP server listens
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub host: String,
+    pub port: u16,
+    /// How long shutdown waits for in-flight requests, then for background tasks
+    pub shutdown_timeout: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            host: "0.0.0.0".to_string(),
+            port: 8080,
+            shutdown_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Rate limits and where their counters are kept
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    /// Limit per client IP on the credential endpoints (login, register, ...)
+    pub auth: RateLimitQuota,
+    /// Limit per authenticated user on writes (POST, PUT, PATCH, DELETE)
+    pub writes: RateLimitQuota,
+    /// Redis shared by all instances; without it each process counts on its own
+    pub redis_url: Option<Secret<String>>,
+    /// Prefix of the Redis keys holding the counters
+    pub key_prefix: String,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            auth: RateLimitQuota {
+                period: Duration::from_secs(1),
+                burst: 5,
+            },
+            writes: RateLimitQuota {
+                period: Duration::from_secs(1),
+                burst: 60,
+            },
+            redis_url: None,
+            key_prefix: "ratelimit".to_string(),
+        }
+    }
+}
+
+/// Settings read once at startup and shared as app data. Nothing else reads
+/// the environment; features get their settings from here.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database_url: Secret<String>,
+    pub jwt_secret: Secret<String>,
+    pub server: ServerConfig,
+    /// Origins allowed to call the API from a browser
+    pub cors_allowed_origins: Vec<String>,
+    pub pool: PoolConfig,
+    /// Apply pending migrations before the server starts taking requests
+    pub migrate_on_startup: bool,
+    pub rate_limit: RateLimitConfig,
+    /// Open Exchange Rates key; rates can't be synchronized without it
+    pub open_exchange_rates_api_key: Option<Secret<String>>,
+    /// Latest fetched rates older than this degrade readiness
+    pub exchange_rate_max_age: chrono::Duration,
+    /// Lifetime of cached hot read responses; zero disables the cache
+    pub response_cache_ttl: Duration,
+    /// Scheduled job intervals and retention windows
+    pub jobs: JobsConfig,
+    /// How long after a change it can still be undone
+    pub undo_window_minutes: i32,
+    /// Month numbering when a request doesn't specify `monthBase` (0 or 1)
+    pub budget_month_base: i16,
+    pub password_reset: PasswordResetConfig,
+    pub debug: DebugConfig,
+    pub inbound_email: InboundEmailConfig,
+    /// Outgoing mail server; without one mail is only logged
+    pub smtp: Option<SmtpMailer>,
+    /// Malware scanning run on uploads before they are accepted
+    pub upload_scanner: Scanner,
+    pub storage: ObjectStorage,
+    pub bank_sync: BankSyncProvider,
+    pub price_provider: PriceProvider,
+    /// Column encryption keys; without them sensitive fields stay plaintext
+    pub encryption_keys: Option<EncryptionKeys>,
+}
+
+impl Config {
+    /// Defaults for everything but the two settings without one
+    pub fn new(database_url: impl Into<String>, jwt_secret: impl Into<String>) -> Self {
+        Self {
+            database_url: Secret::new(database_url.into()),
+            jwt_secret: Secret::new(jwt_secret.into()),
+            server: ServerConfig::default(),
+            cors_allowed_origins: vec!["http://localhost:3000".to_string()],
+            pool: PoolConfig::default(),
+            migrate_on_startup: true,
+            rate_limit: RateLimitConfig::default(),
+            open_exchange_rates_api_key: None,
+            exchange_rate_max_age: chrono::Duration::hours(48),
+            response_cache_ttl: Duration::from_secs(30),
+            jobs: JobsConfig::default(),
+            undo_window_minutes: 15,
+            budget_month_base: 0,
+            password_reset: PasswordResetConfig::default(),
+            debug: DebugConfig::default(),
+            inbound_email: InboundEmailConfig::default(),
+            smtp: None,
+            upload_scanner: Scanner::Disabled,
+            storage: ObjectStorage::Local {
+                root: PathBuf::from("./media"),
+                public_url: "http://localhost:8080/media".to_string(),
+            },
+            bank_sync: BankSyncProvider::Disabled,
+            price_provider: PriceProvider::Manual,
+            encryption_keys: None,
+        }
+    }
+
+    /// Read the configuration from the environment (see `.env.example` for
+    /// every variable and its default). Unset or blank variables take their
+    /// default; set but unparsable ones are errors, so a typo stops startup
+    /// instead of silently falling back.
+    pub fn from_env() -> Result<Self, AppError> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, AppError> {
+        let vars = Vars(lookup);
+        let defaults = PoolConfig::default();
+
+        let database_url = vars.required("DATABASE_URL")?;
+        let mut config = Self::new(database_url, vars.required("JWT_SECRET")?);
+        config.server = ServerConfig {
+            host: vars.get("HOST").unwrap_or(config.server.host),
+            port: vars.parse("PORT", config.server.port)?,
+            shutdown_timeout: vars.secs("SHUTDOWN_TIMEOUT_SECS", config.server.shutdown_timeout)?,
+        };
+        if let Some(origins) = vars.get("CORS_ALLOWED_ORIGINS") {
+            config.cors_allowed_origins = origins
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect();
+        }
+        config.pool = PoolConfig {
+            max_connections: vars.parse("DB_MAX_CONNECTIONS", defaults.max_connections)?,
+            min_connections: vars.parse("DB_MIN_CONNECTIONS", defaults.min_connections)?,
+            acquire_timeout: vars.secs("DB_ACQUIRE_TIMEOUT_SECS", defaults.acquire_timeout)?,
+            idle_timeout: vars.secs("DB_IDLE_TIMEOUT_SECS", defaults.idle_timeout)?,
+            max_lifetime: vars.secs("DB_MAX_LIFETIME_SECS", defaults.max_lifetime)?,
+            acquire_slow_threshold: Duration::from_millis(vars.parse(
+                "DB_ACQUIRE_SLOW_MS",
+                defaults.acquire_slow_threshold.as_millis() as u64,
+            )?),
+            saturation_percent: vars
+                .parse("DB_POOL_SATURATION_PERCENT", defaults.saturation_percent)?,
+            metrics_interval: vars
+                .secs("DB_POOL_METRICS_INTERVAL_SECS", defaults.metrics_interval)?,
+        };
+        config.migrate_on_startup = vars.flag("DB_MIGRATE_ON_STARTUP", true)?;
+        config.rate_limit = RateLimitConfig {
+            auth: RateLimitQuota {
+                period: Duration::from_millis(vars.parse(
+                    "RATE_LIMIT_AUTH_PERIOD_MS",
+                    config.rate_limit.auth.period.as_millis() as u64,
+                )?),
+                burst: vars.parse("RATE_LIMIT_AUTH_BURST", config.rate_limit.auth.burst)?,
+            },
+            writes: RateLimitQuota {
+                period: Duration::from_millis(vars.parse(
+                    "RATE_LIMIT_WRITE_PERIOD_MS",
+                    config.rate_limit.writes.period.as_millis() as u64,
+                )?),
+                burst: vars.parse("RATE_LIMIT_WRITE_BURST", config.rate_limit.writes.burst)?,
+            },
+            redis_url: vars.get("REDIS_URL").map(Secret::new),
+            key_prefix: vars
+                .get("RATE_LIMIT_KEY_PREFIX")
+                .unwrap_or(config.rate_limit.key_prefix),
+        };
+        config.open_exchange_rates_api_key = vars.get("OPENEXCHANGERATES_API_KEY").map(Secret::new);
+        config.exchange_rate_max_age =
+            chrono::Duration::hours(vars.parse("EXCHANGE_RATE_MAX_AGE_HOURS", 48)?);
+        config.response_cache_ttl =
+            vars.secs("RESPONSE_CACHE_TTL_SECS", config.response_cache_ttl)?;
+        config.jobs = Self::jobs_from(&vars)?;
+        config.undo_window_minutes =
+            vars.parse("UNDO_WINDOW_MINUTES", config.undo_window_minutes)?;
+        config.budget_month_base = vars.parse("BUDGET_MONTH_BASE", config.budget_month_base)?;
+        if let Some(url) = vars.get("PASSWORD_RESET_URL") {
+            config.password_reset.url = url;
+        }
+        config.debug.enabled = vars.flag("DEBUG_ENDPOINTS_ENABLED", false)?;
+        if let Some(domain) = vars.get("INBOUND_EMAIL_DOMAIN") {
+            config.inbound_email.domain = domain.to_lowercase();
+        }
+        config.inbound_email.secret = vars.get("INBOUND_EMAIL_SECRET");
+        config.smtp = vars
+            .get("SMTP_HOST")
+            .map(|host| Self::smtp_from(&vars, host))
+            .transpose()?;
+        config.upload_scanner = match vars
+            .choice("UPLOAD_SCAN_MODE", "none", &["none", "command", "clamd"])?
+            .as_str()
+        {
+            "command" => {
+                let command =
+                    vars.required_for("UPLOAD_SCAN_COMMAND", "UPLOAD_SCAN_MODE=command")?;
+                let mut parts = command.split_whitespace().map(str::to_string);
+                Scanner::Command {
+                    program: parts.next().unwrap_or_default(),
+                    args: parts.collect(),
+                }
+            }
+            "clamd" => Scanner::Clamd {
+                addr: vars
+                    .get("CLAMD_ADDR")
+                    .unwrap_or_else(|| "127.0.0.1:3310".to_string()),
+            },
+            _ => Scanner::Disabled,
+        };
+        if vars.choice("STORAGE_BACKEND", "local", &["local", "gcs"])? == "gcs" {
+            config.storage = ObjectStorage::Gcs {
+                bucket: vars.required_for("STORAGE_GCS_BUCKET", "STORAGE_BACKEND=gcs")?,
+            };
+        } else if let ObjectStorage::Local { root, public_url } = &mut config.storage {
+            if let Some(dir) = vars.get("STORAGE_LOCAL_DIR") {
+                *root = PathBuf::from(dir);
+            }
+            if let Some(url) = vars.get("STORAGE_PUBLIC_URL") {
+                *public_url = url.trim_end_matches('/').to_string();
+            }
+        }
+        if vars.choice("BANK_SYNC_PROVIDER", "none", &["none", "plaid"])? == "plaid" {
+            let base_url = match vars.get("PLAID_BASE_URL") {
+                Some(url) => url,
+                None => format!(
+                    "https://{}.plaid.com",
+                    vars.choice(
+                        "PLAID_ENV",
+                        "sandbox",
+                        &["sandbox", "development", "production"]
+                    )?
+                ),
+            };
+            config.bank_sync = BankSyncProvider::Plaid(PlaidClient::new(
+                base_url,
+                vars.required_for("PLAID_CLIENT_ID", "BANK_SYNC_PROVIDER=plaid")?,
+                vars.required_for("PLAID_SECRET", "BANK_SYNC_PROVIDER=plaid")?,
+            ));
+        }
+        if vars.choice("PRICE_PROVIDER", "manual", &["manual", "http"])? == "http" {
+            config.price_provider = PriceProvider::Http {
+                url: vars.required_for("PRICE_API_URL", "PRICE_PROVIDER=http")?,
+            };
+        }
+        config.encryption_keys = vars
+            .get("DATA_ENCRYPTION_KEY")
+            .map(|key| {
+                EncryptionKeys::decode(
+                    &key,
+                    &vars
+                        .get("DATA_ENCRYPTION_PREVIOUS_KEYS")
+                        .unwrap_or_default(),
+                )
+            })
+            .transpose()?;
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn jobs_from<F: Fn(&str) -> Option<String>>(vars: &Vars<F>) -> Result<JobsConfig, AppError> {
+        let defaults = JobsConfig::default();
+
+        Ok(JobsConfig {
+            leader: LeaderElectionConfig {
+                lock_key: vars.parse("JOB_LEADER_LOCK_KEY", defaults.leader.lock_key)?,
+                interval: vars.secs("JOB_LEADER_CHECK_INTERVAL_SECS", defaults.leader.interval)?,
+            },
+            refresh_token_cleanup: RefreshTokenCleanupConfig {
+                retention_days: vars.parse(
+                    "REFRESH_TOKEN_RETENTION_DAYS",
+                    defaults.refresh_token_cleanup.retention_days,
+                )?,
+                interval: vars.hours(
+                    "REFRESH_TOKEN_CLEANUP_INTERVAL_HOURS",
+                    defaults.refresh_token_cleanup.interval,
+                )?,
+            },
+            retention: RetentionJobConfig {
+                interval: vars
+                    .hours("RETENTION_JOB_INTERVAL_HOURS", defaults.retention.interval)?,
+                dry_run: vars.flag("RETENTION_DRY_RUN", defaults.retention.dry_run)?,
+                windows: RETENTION_POLICIES
+                    .iter()
+                    .map(|policy| {
+                        let days = vars.days_or_off(policy.env_var, policy.default_days)?;
+                        Ok((policy.name, days))
+                    })
+                    .collect::<Result<_, AppError>>()?,
+            },
+            summary_refresh: SummaryRefreshConfig {
+                interval: vars.secs(
+                    "SUMMARY_REFRESH_INTERVAL_SECS",
+                    defaults.summary_refresh.interval,
+                )?,
+            },
+            integrity_audit: IntegrityAuditConfig {
+                interval: vars.hours(
+                    "INTEGRITY_AUDIT_INTERVAL_HOURS",
+                    defaults.integrity_audit.interval,
+                )?,
+            },
+            bill_reminders: BillReminderConfig {
+                interval: vars.minutes(
+                    "BILL_REMINDER_INTERVAL_MINUTES",
+                    defaults.bill_reminders.interval,
+                )?,
+            },
+            loan_postings: LoanPostingConfig {
+                interval: vars.minutes(
+                    "LOAN_POSTING_INTERVAL_MINUTES",
+                    defaults.loan_postings.interval,
+                )?,
+            },
+            charge_postings: ChargePostingConfig {
+                interval: vars.minutes(
+                    "CHARGE_POSTING_INTERVAL_MINUTES",
+                    defaults.charge_postings.interval,
+                )?,
+            },
+            valuation: ValuationConfig {
+                interval: vars
+                    .minutes("VALUATION_INTERVAL_MINUTES", defaults.valuation.interval)?,
+            },
+            digests: DigestConfig {
+                interval: vars.minutes("DIGEST_INTERVAL_MINUTES", defaults.digests.interval)?,
+            },
+            bank_sync: BankSyncConfig {
+                interval: vars
+                    .minutes("BANK_SYNC_INTERVAL_MINUTES", defaults.bank_sync.interval)?,
+            },
+        })
+    }
+
+    fn smtp_from<F: Fn(&str) -> Option<String>>(
+        vars: &Vars<F>,
+        host: String,
+    ) -> Result<SmtpMailer, AppError> {
+        let security = match vars
+            .choice("SMTP_SECURITY", "starttls", &["starttls", "tls", "none"])?
+            .as_str()
+        {
+            "tls" => SmtpSecurity::Tls,
+            "none" => SmtpSecurity::None,
+            _ => SmtpSecurity::StartTls,
+        };
+
+        Ok(SmtpMailer::new(
+            host,
+            vars.parse("SMTP_PORT", SmtpMailer::default_port(security))?,
+            security,
+            vars.get("SMTP_USERNAME"),
+            vars.get("SMTP_PASSWORD").map(Secret::new),
+            vars.required_for("SMTP_FROM", "SMTP_HOST is set")?,
+        ))
+    }
+
+    /// Check settings that parse but can't work
+    pub fn validate(&self) -> Result<(), AppError> {
+        let invalid = |message: String| Err(AppError::InternalError(message));
+
+        if self.database_url.expose_secret().trim().is_empty() {
+            return invalid("DATABASE_URL must not be empty".to_string());
+        }
+        if self.jwt_secret.expose_secret().len() < MIN_JWT_SECRET_LEN {
+            return invalid(format!(
+                "JWT_SECRET must be at least {MIN_JWT_SECRET_LEN} characters"
+            ));
+        }
+        if self.cors_allowed_origins.is_empty() {
+            return invalid("CORS_ALLOWED_ORIGINS must list at least one origin".to_string());
+        }
+        if let Some(origin) = self
+            .cors_allowed_origins
+            .iter()
+            .find(|origin| !origin.starts_with("http://") && !origin.starts_with("https://"))
+        {
+            return invalid(format!(
+                "CORS_ALLOWED_ORIGINS entry '{origin}' must start with http:// or https://"
+            ));
+        }
+        if self.pool.max_connections == 0 {
+            return invalid("DB_MAX_CONNECTIONS must be at least 1".to_string());
+        }
+        if self.pool.min_connections > self.pool.max_connections {
+            return invalid(format!(
+                "DB_MIN_CONNECTIONS ({}) must not exceed DB_MAX_CONNECTIONS ({})",
+                self.pool.min_connections, self.pool.max_connections
+            ));
+        }
+        if !(1..=100).contains(&self.pool.saturation_percent) {
+            return invalid("DB_POOL_SATURATION_PERCENT must be between 1 and 100".to_string());
+        }
+        if self.rate_limit.auth.period.is_zero() || self.rate_limit.auth.burst == 0 {
+            return invalid(
+                "RATE_LIMIT_AUTH_PERIOD_MS and RATE_LIMIT_AUTH_BURST must be at least 1"
+                    .to_string(),
+            );
+        }
+        if self.rate_limit.writes.period.is_zero() || self.rate_limit.writes.burst == 0 {
+            return invalid(
+                "RATE_LIMIT_WRITE_PERIOD_MS and RATE_LIMIT_WRITE_BURST must be at least 1"
+                    .to_string(),
+            );
+        }
+        if self.exchange_rate_max_age <= chrono::Duration::zero() {
+            return invalid("EXCHANGE_RATE_MAX_AGE_HOURS must be at least 1".to_string());
+        }
+        let jobs = &self.jobs;
+        for (name, interval) in [
+            ("JOB_LEADER_CHECK_INTERVAL_SECS", jobs.leader.interval),
+            (
+                "REFRESH_TOKEN_CLEANUP_INTERVAL_HOURS",
+                jobs.refresh_token_cleanup.interval,
+            ),
+            ("RETENTION_JOB_INTERVAL_HOURS", jobs.retention.interval),
+            (
+                "SUMMARY_REFRESH_INTERVAL_SECS",
+                jobs.summary_refresh.interval,
+            ),
+            (
+                "INTEGRITY_AUDIT_INTERVAL_HOURS",
+                jobs.integrity_audit.interval,
+            ),
+            (
+                "BILL_REMINDER_INTERVAL_MINUTES",
+                jobs.bill_reminders.interval,
+            ),
+            ("LOAN_POSTING_INTERVAL_MINUTES", jobs.loan_postings.interval),
+            (
+                "CHARGE_POSTING_INTERVAL_MINUTES",
+                jobs.charge_postings.interval,
+            ),
+            ("VALUATION_INTERVAL_MINUTES", jobs.valuation.interval),
+            ("DIGEST_INTERVAL_MINUTES", jobs.digests.interval),
+            ("BANK_SYNC_INTERVAL_MINUTES", jobs.bank_sync.interval),
+        ] {
+            if interval.is_zero() {
+                return invalid(format!("{name} must be at least 1"));
+            }
+        }
+        if jobs.refresh_token_cleanup.retention_days < 0 {
+            return invalid("REFRESH_TOKEN_RETENTION_DAYS must not be negative".to_string());
+        }
+        if let Some(policy) = RETENTION_POLICIES.iter().find(|policy| {
+            jobs.retention
+                .retention_days(policy)
+                .is_some_and(|days| days < 0)
+        }) {
+            return invalid(format!("{} must not be negative", policy.env_var));
+        }
+        if self.undo_window_minutes < 1 {
+            return invalid("UNDO_WINDOW_MINUTES must be at least 1".to_string());
+        }
+        if !matches!(self.budget_month_base, 0 | 1) {
+            return invalid("BUDGET_MONTH_BASE must be 0 or 1".to_string());
+        }
+        if let PriceProvider::Http { url } = &self.price_provider {
+            if !url.contains("{ticker}") {
+                return invalid("PRICE_API_URL must contain a {ticker} placeholder".to_string());
+            }
+        }
+        if let Some(keys) = &self.encryption_keys {
+            keys.cipher()?;
+        }
+        Ok(())
+    }
+
+    /// Thresholds for the readiness checks
+    pub fn health(&self) -> HealthConfig {
+        HealthConfig {
+            exchange_rates_enabled: self.open_exchange_rates_api_key.is_some(),
+            exchange_rate_max_age: self.exchange_rate_max_age,
+        }
+    }
+}
+
+/// Environment lookups with the parse errors naming the variable
+struct Vars<F>(F);
+
+impl<F: Fn(&str) -> Option<String>> Vars<F> {
+    /// Trimmed value; None when unset or blank
+    fn get(&self, name: &str) -> Option<String> {
+        (self.0)(name)
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    fn required(&self, name: &str) -> Result<String, AppError> {
+        self.get(name)
+            .ok_or_else(|| AppError::InternalError(format!("{name} must be set")))
+    }
+
+    fn parse<T: FromStr>(&self, name: &str, default: T) -> Result<T, AppError> {
+        match self.get(name) {
+            None => Ok(default),
+            Some(value) => value.parse().map_err(|_| {
+                AppError::InternalError(format!("{name} has an invalid value '{value}'"))
+            }),
+        }
+    }
+
+    fn secs(&self, name: &str, default: Duration) -> Result<Duration, AppError> {
+        self.parse(name, default.as_secs()).map(Duration::from_secs)
+    }
+
+    fn minutes(&self, name: &str, default: Duration) -> Result<Duration, AppError> {
+        self.parse(name, default.as_secs() / 60)
+            .map(|minutes: u64| Duration::from_secs(minutes.saturating_mul(60)))
+    }
+
+    fn hours(&self, name: &str, default: Duration) -> Result<Duration, AppError> {
+        self.parse(name, default.as_secs() / 3600)
+            .map(|hours: u64| Duration::from_secs(hours.saturating_mul(3600)))
+    }
+
+    /// A number of days, or `off` (None)
+    fn days_or_off(&self, name: &str, default: Option<i32>) -> Result<Option<i32>, AppError> {
+        match self.get(name) {
+            Some(value) if value.eq_ignore_ascii_case("off") => Ok(None),
+            Some(value) => value.parse().map(Some).map_err(|_| {
+                AppError::InternalError(format!(
+                    "{name} has an invalid value '{value}', expected a number of days or off"
+                ))
+            }),
+            None => Ok(default),
+        }
+    }
+
+    /// One of a fixed set of lowercase options
+    fn choice(&self, name: &str, default: &str, options: &[&str]) -> Result<String, AppError> {
+        let value = self
+            .get(name)
+            .map(|v| v.to_lowercase())
+            .unwrap_or_else(|| default.to_string());
+        if options.contains(&value.as_str()) {
+            Ok(value)
+        } else {
+            Err(AppError::InternalError(format!(
+                "{name} has an invalid value '{value}', expected one of: {}",
+                options.join(", ")
+            )))
+        }
+    }
+
+    /// A setting another one depends on
+    fn required_for(&self, name: &str, condition: &str) -> Result<String, AppError> {
+        self.get(name)
+            .ok_or_else(|| AppError::InternalError(format!("{name} must be set when {condition}")))
+    }
+
+    fn flag(&self, name: &str, default: bool) -> Result<bool, AppError> {
+        match self.get(name).map(|v| v.to_lowercase()).as_deref() {
+            None => Ok(default),
+            Some("true" | "1" | "yes" | "on") => Ok(true),
+            Some("false" | "0" | "no" | "off") => Ok(false),
+            Some(value) => Err(AppError::InternalError(format!(
+                "{name} has an invalid value '{value}', expected true or false"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn load(vars: &[(&str, &str)]) -> Result<Config, AppError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    const REQUIRED: [(&str, &str); 2] = [
+        ("DATABASE_URL", "postgres://localhost/budget_db"),
+        ("JWT_SECRET", "0123456789abcdef0123456789abcdef"),
+    ];
+
+    #[test]
+    fn test_defaults_apply_to_unset_and_blank_variables() {
+        let config = load(&[REQUIRED[0], REQUIRED[1], ("PORT", " ")]).unwrap();
+
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.cors_allowed_origins, vec!["http://localhost:3000"]);
+        assert_eq!(config.pool.max_connections, 10);
+        assert!(config.migrate_on_startup);
+        assert_eq!(config.rate_limit.auth.burst, 5);
+        assert!(config.rate_limit.redis_url.is_none());
+        assert!(!config.health().exchange_rates_enabled);
+    }
+
+    #[test]
+    fn test_set_variables_are_parsed() {
+        let config = load(&[
+            REQUIRED[0],
+            REQUIRED[1],
+            ("PORT", "9000"),
+            (
+                "CORS_ALLOWED_ORIGINS",
+                "https://a.example, https://b.example,",
+            ),
+            ("DB_MAX_CONNECTIONS", "20"),
+            ("DB_MIGRATE_ON_STARTUP", "off"),
+            ("RATE_LIMIT_AUTH_PERIOD_MS", "2000"),
+            ("RATE_LIMIT_WRITE_BURST", "10"),
+            ("OPENEXCHANGERATES_API_KEY", "key"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.server.port, 9000);
+        assert_eq!(
+            config.cors_allowed_origins,
+            vec!["https://a.example", "https://b.example"]
+        );
+        assert_eq!(config.pool.max_connections, 20);
+        assert!(!config.migrate_on_startup);
+        assert_eq!(config.rate_limit.auth.period, Duration::from_millis(2000));
+        assert_eq!(config.rate_limit.writes.burst, 10);
+        assert!(config.health().exchange_rates_enabled);
+    }
+
+    #[test]
+    fn test_missing_or_invalid_settings_are_rejected() {
+        let message = |result: Result<Config, AppError>| result.unwrap_err().to_string();
+
+        assert!(message(load(&[REQUIRED[1]])).contains("DATABASE_URL must be set"));
+        assert!(message(load(&[REQUIRED[0], ("JWT_SECRET", "short")])).contains("JWT_SECRET"));
+        assert!(
+            message(load(&[REQUIRED[0], REQUIRED[1], ("PORT", "eighty")]))
+                .contains("PORT has an invalid value 'eighty'")
+        );
+        assert!(message(load(&[
+            REQUIRED[0],
+            REQUIRED[1],
+            ("DB_MIN_CONNECTIONS", "5"),
+            ("DB_MAX_CONNECTIONS", "2"),
+        ]))
+        .contains("DB_MIN_CONNECTIONS"));
+        assert!(message(load(&[
+            REQUIRED[0],
+            REQUIRED[1],
+            ("CORS_ALLOWED_ORIGINS", "localhost:3000"),
+        ]))
+        .contains("must start with http"));
+    }
+
+    #[test]
+    fn test_feature_settings_are_parsed_and_validated() {
+        use crate::jobs::models::TRANSACTION_TRASH_RETENTION;
+
+        let config = load(&[REQUIRED[0], REQUIRED[1]]).unwrap();
+        assert_eq!(
+            config
+                .jobs
+                .retention
+                .retention_days(&TRANSACTION_TRASH_RETENTION),
+            Some(30)
+        );
+        assert_eq!(config.undo_window_minutes, 15);
+        assert!(config.smtp.is_none());
+        assert!(matches!(config.bank_sync, BankSyncProvider::Disabled));
+
+        let config = load(&[
+            REQUIRED[0],
+            REQUIRED[1],
+            ("RETENTION_TRANSACTION_TRASH_DAYS", "off"),
+            ("BUDGET_MONTH_BASE", "1"),
+            ("DIGEST_INTERVAL_MINUTES", "5"),
+            ("UPLOAD_SCAN_MODE", "Command"),
+            ("UPLOAD_SCAN_COMMAND", "clamdscan --stdin -"),
+        ])
+        .unwrap();
+        assert_eq!(
+            config
+                .jobs
+                .retention
+                .retention_days(&TRANSACTION_TRASH_RETENTION),
+            None
+        );
+        assert_eq!(config.budget_month_base, 1);
+        assert_eq!(config.jobs.digests.interval, Duration::from_secs(300));
+        assert!(
+            matches!(config.upload_scanner, Scanner::Command { ref program, .. } if program == "clamdscan")
+        );
+
+        let message = |vars: &[(&str, &str)]| {
+            let vars: Vec<_> = REQUIRED.iter().chain(vars).copied().collect();
+            load(&vars).unwrap_err().to_string()
+        };
+        assert!(message(&[("RETENTION_TRANSACTION_TRASH_DAYS", "3O")])
+            .contains("RETENTION_TRANSACTION_TRASH_DAYS has an invalid value '3O'"));
+        assert!(message(&[("RETENTION_AUDIT_LOG_DAYS", "-1")])
+            .contains("RETENTION_AUDIT_LOG_DAYS must not be negative"));
+        assert!(message(&[("BUDGET_MONTH_BASE", "2")]).contains("BUDGET_MONTH_BASE"));
+        assert!(message(&[("UNDO_WINDOW_MINUTES", "0")]).contains("UNDO_WINDOW_MINUTES"));
+        assert!(message(&[("BANK_SYNC_INTERVAL_MINUTES", "0")])
+            .contains("BANK_SYNC_INTERVAL_MINUTES must be at least 1"));
+        assert!(message(&[("SMTP_HOST", "smtp.example.com")])
+            .contains("SMTP_FROM must be set when SMTP_HOST is set"));
+        assert!(
+            message(&[("BANK_SYNC_PROVIDER", "plaid"), ("PLAID_ENV", "prod")])
+                .contains("PLAID_ENV has an invalid value 'prod'")
+        );
+        assert!(message(&[
+            ("PRICE_PROVIDER", "http"),
+            ("PRICE_API_URL", "https://p.example")
+        ])
+        .contains("{ticker}"));
+        assert!(message(&[("DATA_ENCRYPTION_KEY", "c2hvcnQ=")]).contains("32 bytes"));
+    }
+}
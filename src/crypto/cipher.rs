@@ -0,0 +1,261 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use std::sync::OnceLock;
+
+use crate::errors::AppError;
+
+/// Marker prefix for encrypted column values: `enc:v1:<key_id>:<base64(nonce || ciphertext)>`
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+
+/// Process-wide cipher, configured once at startup
+static CIPHER: OnceLock<Option<FieldCipher>> = OnceLock::new();
+
+struct KeyEntry {
+    id: String,
+    cipher: Aes256Gcm,
+}
+
+impl KeyEntry {
+    fn new(key: &[u8]) -> Result<Self, AppError> {
+        if key.len() != 32 {
+            return Err(AppError::InternalError(
+                "Encryption keys must be 32 bytes (base64-encoded)".to_string(),
+            ));
+        }
+        let cipher = Aes256Gcm::new_from_slice(key)
+            .map_err(|e| AppError::InternalError(format!("Invalid encryption key: {e}")))?;
+        // Short, non-reversible fingerprint so values record which key sealed them
+        let id = hex::encode(&Sha256::digest(key)[..4]);
+        Ok(Self { id, cipher })
+    }
+}
+
+/// Column encryption keys, decoded at startup by [`crate::config::Config`]
+#[derive(Clone)]
+pub struct EncryptionKeys {
+    pub current: Vec<u8>,
+    /// Keys still needed to read values sealed before a rotation
+    pub previous: Vec<Vec<u8>>,
+}
+
+impl EncryptionKeys {
+    /// Decode the base64 current key and comma-separated previous keys
+    pub fn decode(current: &str, previous: &str) -> Result<Self, AppError> {
+        Ok(Self {
+            current: decode_key(current)?,
+            previous: previous
+                .split(',')
+                .filter(|k| !k.trim().is_empty())
+                .map(decode_key)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Build the cipher, failing on keys of the wrong size
+    pub fn cipher(&self) -> Result<FieldCipher, AppError> {
+        FieldCipher::new(&self.current, &self.previous)
+    }
+}
+
+impl std::fmt::Debug for EncryptionKeys {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptionKeys")
+            .field("current", &"[REDACTED]")
+            .field("previous", &self.previous.len())
+            .finish()
+    }
+}
+
+/// AES-256-GCM cipher for sensitive text columns.
+/// Holds the current key plus any previous keys still needed to read old values.
+pub struct FieldCipher {
+    current: KeyEntry,
+    previous: Vec<KeyEntry>,
+}
+
+impl FieldCipher {
+    pub fn new(current: &[u8], previous: &[Vec<u8>]) -> Result<Self, AppError> {
+        Ok(Self {
+            current: KeyEntry::new(current)?,
+            previous: previous
+                .iter()
+                .map(|k| KeyEntry::new(k))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Encrypt a plaintext value with the current key
+    pub fn encrypt(&self, plaintext: &str) -> Result<String, AppError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .current
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| AppError::InternalError(format!("Failed to encrypt field: {e}")))?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend_from_slice(&ciphertext);
+
+        Ok(format!(
+            "{ENCRYPTED_PREFIX}{}:{}",
+            self.current.id,
+            BASE64.encode(payload)
+        ))
+    }
+
+    /// Decrypt a stored value. Values without the marker prefix are legacy plaintext.
+    pub fn decrypt(&self, value: &str) -> Result<String, AppError> {
+        let Some((key_id, payload)) = parse_encrypted(value) else {
+            return Ok(value.to_string());
+        };
+
+        let key = std::iter::once(&self.current)
+            .chain(self.previous.iter())
+            .find(|k| k.id == key_id)
+            .ok_or_else(|| {
+                AppError::InternalError(format!("No encryption key available for id {key_id}"))
+            })?;
+
+        let bytes = BASE64
+            .decode(payload)
+            .map_err(|e| AppError::InternalError(format!("Malformed encrypted field: {e}")))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(AppError::InternalError(
+                "Malformed encrypted field: payload too short".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+
+        let plaintext = key
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|e| AppError::InternalError(format!("Failed to decrypt field: {e}")))?;
+
+        String::from_utf8(plaintext)
+            .map_err(|e| AppError::InternalError(format!("Decrypted field is not UTF-8: {e}")))
+    }
+
+    /// Whether a stored value is already sealed with the current key
+    pub fn is_current(&self, value: &str) -> bool {
+        matches!(parse_encrypted(value), Some((key_id, _)) if key_id == self.current.id)
+    }
+}
+
+fn decode_key(encoded: &str) -> Result<Vec<u8>, AppError> {
+    BASE64
+        .decode(encoded.trim())
+        .map_err(|e| AppError::InternalError(format!("Encryption key is not valid base64: {e}")))
+}
+
+fn parse_encrypted(value: &str) -> Option<(&str, &str)> {
+    value.strip_prefix(ENCRYPTED_PREFIX)?.split_once(':')
+}
+
+/// Configure the process-wide cipher; without keys sensitive fields stay
+/// plaintext. Call at startup, before the first value is read or written.
+pub fn init(keys: Option<&EncryptionKeys>) -> Result<bool, AppError> {
+    if let Some(configured) = CIPHER.get() {
+        return Ok(configured.is_some());
+    }
+    let cipher = keys.map(EncryptionKeys::cipher).transpose()?;
+    Ok(CIPHER.get_or_init(|| cipher).is_some())
+}
+
+pub(super) fn cipher() -> Option<&'static FieldCipher> {
+    CIPHER.get_or_init(|| None).as_ref()
+}
+
+/// Whether sensitive columns are being encrypted on write
+pub fn is_enabled() -> bool {
+    cipher().is_some()
+}
+
+/// Encrypt a value for storage (passthrough when encryption is not configured)
+pub fn encrypt_field(value: &str) -> Result<String, AppError> {
+    match cipher() {
+        Some(c) => c.encrypt(value),
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Decrypt a stored value (passthrough for plaintext)
+pub fn decrypt_field(value: &str) -> Result<String, AppError> {
+    match cipher() {
+        Some(c) => c.decrypt(value),
+        None if parse_encrypted(value).is_some() => Err(AppError::InternalError(
+            "Encrypted field found but DATA_ENCRYPTION_KEY is not configured".to_string(),
+        )),
+        None => Ok(value.to_string()),
+    }
+}
+
+pub fn encrypt_optional(value: Option<&str>) -> Result<Option<String>, AppError> {
+    value.map(encrypt_field).transpose()
+}
+
+pub fn decrypt_optional(value: Option<String>) -> Result<Option<String>, AppError> {
+    value.as_deref().map(decrypt_field).transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> Vec<u8> {
+        vec![byte; 32]
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let cipher = FieldCipher::new(&key(1), &[]).unwrap();
+        let sealed = cipher.encrypt("Weekly groceries").unwrap();
+        assert!(sealed.starts_with(ENCRYPTED_PREFIX));
+        assert_ne!(sealed, "Weekly groceries");
+        assert_eq!(cipher.decrypt(&sealed).unwrap(), "Weekly groceries");
+    }
+
+    #[test]
+    fn test_encrypt_uses_random_nonce() {
+        let cipher = FieldCipher::new(&key(1), &[]).unwrap();
+        assert_ne!(
+            cipher.encrypt("same").unwrap(),
+            cipher.encrypt("same").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decrypt_plaintext_passthrough() {
+        let cipher = FieldCipher::new(&key(1), &[]).unwrap();
+        assert_eq!(cipher.decrypt("legacy value").unwrap(), "legacy value");
+    }
+
+    #[test]
+    fn test_decrypt_with_previous_key() {
+        let old = FieldCipher::new(&key(1), &[]).unwrap();
+        let sealed = old.encrypt("rotated").unwrap();
+
+        let rotated = FieldCipher::new(&key(2), &[key(1)]).unwrap();
+        assert!(!rotated.is_current(&sealed));
+        assert_eq!(rotated.decrypt(&sealed).unwrap(), "rotated");
+        assert!(rotated.is_current(&rotated.encrypt("rotated").unwrap()));
+    }
+
+    #[test]
+    fn test_decrypt_unknown_key_fails() {
+        let sealed = FieldCipher::new(&key(1), &[])
+            .unwrap()
+            .encrypt("secret")
+            .unwrap();
+        let other = FieldCipher::new(&key(2), &[]).unwrap();
+        assert!(other.decrypt(&sealed).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_length_rejected() {
+        assert!(FieldCipher::new(&[0u8; 16], &[]).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+mod cipher;
+mod reencrypt;
+
+pub use cipher::{
+    decrypt_field, decrypt_optional, encrypt_field, encrypt_optional, init, is_enabled,
+    EncryptionKeys,
+};
+pub use reencrypt::reencrypt_all;
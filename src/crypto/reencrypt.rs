@@ -0,0 +1,125 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::cipher::cipher;
+use crate::errors::AppError;
+
+const BATCH_SIZE: i64 = 500;
+
+/// Number of rows rewritten per encrypted column
+#[derive(Debug, Default)]
+pub struct ReencryptReport {
+    pub transaction_descriptions: u64,
+    pub reimbursement_payers: u64,
+    pub account_names: u64,
+}
+
+/// Rewrite every sensitive column with the current key.
+/// Encrypts legacy plaintext and re-seals values written under a previous key;
+/// rows already sealed with the current key are left untouched.
+pub async fn reencrypt_all(pool: &PgPool) -> Result<ReencryptReport, AppError> {
+    let cipher = cipher().ok_or_else(|| {
+        AppError::InternalError("DATA_ENCRYPTION_KEY must be set to re-encrypt data".to_string())
+    })?;
+
+    let mut report = ReencryptReport::default();
+
+    let mut last_id = Uuid::nil();
+    loop {
+        let rows = sqlx::query_as::<_, (Uuid, String)>(
+            r#"
+            SELECT id, description FROM transactions
+            WHERE description IS NOT NULL AND id > $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(last_id)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let Some((id, _)) = rows.last() else { break };
+        last_id = *id;
+
+        for (id, value) in rows {
+            if cipher.is_current(&value) {
+                continue;
+            }
+            let sealed = cipher.encrypt(&cipher.decrypt(&value)?)?;
+            sqlx::query("UPDATE transactions SET description = $2 WHERE id = $1")
+                .bind(id)
+                .bind(&sealed)
+                .execute(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+            report.transaction_descriptions += 1;
+        }
+    }
+
+    let mut last_id = Uuid::nil();
+    loop {
+        let rows = sqlx::query_as::<_, (Uuid, String)>(
+            r#"
+            SELECT id, reimbursement_payer FROM transactions
+            WHERE reimbursement_payer IS NOT NULL AND id > $1
+            ORDER BY id
+            LIMIT $2
+            "#,
+        )
+        .bind(last_id)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let Some((id, _)) = rows.last() else { break };
+        last_id = *id;
+
+        for (id, value) in rows {
+            if cipher.is_current(&value) {
+                continue;
+            }
+            let sealed = cipher.encrypt(&cipher.decrypt(&value)?)?;
+            sqlx::query("UPDATE transactions SET reimbursement_payer = $2 WHERE id = $1")
+                .bind(id)
+                .bind(&sealed)
+                .execute(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+            report.reimbursement_payers += 1;
+        }
+    }
+
+    let mut last_id = Uuid::nil();
+    loop {
+        let rows = sqlx::query_as::<_, (Uuid, String)>(
+            "SELECT id, name FROM accounts WHERE id > $1 ORDER BY id LIMIT $2",
+        )
+        .bind(last_id)
+        .bind(BATCH_SIZE)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let Some((id, _)) = rows.last() else { break };
+        last_id = *id;
+
+        for (id, value) in rows {
+            if cipher.is_current(&value) {
+                continue;
+            }
+            let sealed = cipher.encrypt(&cipher.decrypt(&value)?)?;
+            sqlx::query("UPDATE accounts SET name = $2 WHERE id = $1")
+                .bind(id)
+                .bind(&sealed)
+                .execute(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+            report.account_names += 1;
+        }
+    }
+
+    Ok(report)
+}
@@ -1,11 +1,18 @@
 use actix_web::{get, post, web, HttpResponse};
+use secrecy::ExposeSecret;
 use sqlx::PgPool;
-use std::env;
+use validator::Validate;
 
+use crate::auth::scopes::Scope;
+use crate::cache::{CacheKey, ResponseCache};
+use crate::config::Config;
 use crate::errors::{AppError, ErrorResponse};
 use crate::extractors::AuthenticatedUser;
 
-use super::models::{CurrenciesListResponse, CurrencyResponse, SyncRatesResponse};
+use super::models::{
+    CurrenciesListResponse, CurrencyResponse, ExchangeRateResponse, PaginatedRatesResponse,
+    RateHistoryQuery, SyncRatesResponse,
+};
 use super::service::CurrencyService;
 
 /// GET /currencies - List all active currencies
@@ -20,17 +27,53 @@ use super::service::CurrencyService;
 )]
 #[get("/currencies")]
 pub async fn list_currencies(pool: web::Data<PgPool>) -> Result<HttpResponse, AppError> {
-    let currencies = CurrencyService::list_currencies(pool.get_ref()).await?;
+    ResponseCache::json(CacheKey::Currencies, || async {
+        let currencies = CurrencyService::list_currencies(pool.get_ref()).await?;
 
-    let response = CurrenciesListResponse {
-        count: currencies.len(),
-        currencies: currencies
+        Ok(CurrenciesListResponse {
+            count: currencies.len(),
+            currencies: currencies
+                .into_iter()
+                .map(CurrencyResponse::from_currency)
+                .collect(),
+        })
+    })
+    .await
+}
+
+/// GET /currencies/rates - Stored exchange rate history for a currency pair
+#[utoipa::path(
+    get,
+    path = "/currencies/rates",
+    tag = "Currencies",
+    params(RateHistoryQuery),
+    responses(
+        (status = 200, description = "Stored rates for the pair, oldest first", body = PaginatedRatesResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    )
+)]
+#[get("/currencies/rates")]
+pub async fn list_exchange_rates(
+    pool: web::Data<PgPool>,
+    query: web::Query<RateHistoryQuery>,
+) -> Result<HttpResponse, AppError> {
+    query.validate()?;
+    query
+        .validate_range()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let (rates, total) = CurrencyService::rate_history(pool.get_ref(), &query).await?;
+
+    Ok(HttpResponse::Ok().json(PaginatedRatesResponse {
+        data: rates
             .into_iter()
-            .map(CurrencyResponse::from_currency)
+            .map(ExchangeRateResponse::from_exchange_rate)
             .collect(),
-    };
-
-    Ok(HttpResponse::Ok().json(response))
+        total,
+        limit: query.limit,
+        offset: query.offset,
+    }))
 }
 
 /// POST /currencies/sync-rates - Trigger exchange rate synchronization
@@ -41,6 +84,7 @@ pub async fn list_currencies(pool: web::Data<PgPool>) -> Result<HttpResponse, Ap
     responses(
         (status = 200, description = "Exchange rates synchronized", body = SyncRatesResponse),
         (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
@@ -48,16 +92,27 @@ pub async fn list_currencies(pool: web::Data<PgPool>) -> Result<HttpResponse, Ap
 #[post("/currencies/sync-rates")]
 pub async fn sync_exchange_rates(
     pool: web::Data<PgPool>,
-    _auth: AuthenticatedUser,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
 ) -> Result<HttpResponse, AppError> {
-    let api_key = env::var("OPENEXCHANGERATES_API_KEY").map_err(|_| {
+    auth.require_scope(Scope::CurrenciesWrite)?;
+
+    let api_key = config.open_exchange_rates_api_key.as_ref().ok_or_else(|| {
         AppError::InternalError("Open Exchange Rates API key not configured".to_string())
     })?;
 
-    let rates_updated = CurrencyService::fetch_and_store_rates(pool.get_ref(), &api_key).await?;
+    let rates_updated =
+        CurrencyService::fetch_and_store_rates(pool.get_ref(), api_key.expose_secret()).await?;
 
     Ok(HttpResponse::Ok().json(SyncRatesResponse {
         message: "Exchange rates synchronized successfully".to_string(),
         rates_updated,
     }))
 }
+
+/// Register the currency and exchange rate routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_currencies)
+        .service(list_exchange_rates)
+        .service(sync_exchange_rates);
+}
@@ -5,7 +5,9 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
 /// Database entity for currencies
 #[derive(Debug, Clone, FromRow)]
@@ -68,7 +70,7 @@ pub struct CurrenciesListResponse {
 /// Database entity for exchange rates
 #[derive(Debug, Clone, FromRow)]
 pub struct ExchangeRate {
-    pub id: i64,
+    pub id: Uuid,
     pub base_currency: String,
     pub target_currency: String,
     pub rate: Decimal,
@@ -104,6 +106,77 @@ impl ExchangeRateResponse {
     }
 }
 
+fn default_limit() -> i64 {
+    100
+}
+
+/// Query parameters for the stored rate history of a currency pair
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct RateHistoryQuery {
+    /// Base currency code
+    #[validate(length(equal = 3, message = "Currency code must be 3 characters"))]
+    #[param(example = "USD")]
+    pub base: String,
+
+    /// Target currency code
+    #[validate(length(equal = 3, message = "Currency code must be 3 characters"))]
+    #[param(example = "EUR")]
+    pub target: String,
+
+    /// First rate date to include
+    #[param(example = "2026-01-01")]
+    pub start: Option<NaiveDate>,
+
+    /// Last rate date to include
+    #[param(example = "2026-03-31")]
+    pub end: Option<NaiveDate>,
+
+    /// Maximum results (1-366)
+    #[validate(range(min = 1, max = 366))]
+    #[serde(default = "default_limit")]
+    #[param(example = 100)]
+    pub limit: i64,
+
+    /// Number of results to skip
+    #[validate(range(min = 0))]
+    #[serde(default)]
+    #[param(example = 0)]
+    pub offset: i64,
+}
+
+impl RateHistoryQuery {
+    /// The pair must be two different currencies and the range must not be reversed
+    pub fn validate_range(&self) -> Result<(), ValidationError> {
+        if self.base.eq_ignore_ascii_case(&self.target) {
+            return Err(ValidationError::new("same_currency"));
+        }
+        if let (Some(start), Some(end)) = (self.start, self.end) {
+            if start > end {
+                return Err(ValidationError::new("start_after_end"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Paginated rate history, oldest rate first
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedRatesResponse {
+    /// Stored rates for the pair
+    pub data: Vec<ExchangeRateResponse>,
+    /// Total stored rates in the range
+    #[schema(example = 90)]
+    pub total: i64,
+    /// Limit used
+    #[schema(example = 100)]
+    pub limit: i64,
+    /// Offset used
+    #[schema(example = 0)]
+    pub offset: i64,
+}
+
 /// Response for exchange rate sync operation
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -130,3 +203,28 @@ pub struct OxrApiResponse {
     /// Exchange rates as currency code -> rate mapping
     pub rates: HashMap<String, Decimal>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_history_query_rejects_same_pair_and_reversed_range() {
+        let mut query = RateHistoryQuery {
+            base: "USD".to_string(),
+            target: "eur".to_string(),
+            start: NaiveDate::from_ymd_opt(2026, 1, 1),
+            end: NaiveDate::from_ymd_opt(2026, 3, 31),
+            limit: default_limit(),
+            offset: 0,
+        };
+        assert!(query.validate_range().is_ok());
+
+        query.target = "usd".to_string();
+        assert!(query.validate_range().is_err());
+
+        query.target = "EUR".to_string();
+        query.end = NaiveDate::from_ymd_opt(2025, 12, 31);
+        assert!(query.validate_range().is_err());
+    }
+}
@@ -1,9 +1,11 @@
 #![allow(dead_code)]
 
 use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
 use sqlx::PgPool;
 
-use super::models::{Currency, ExchangeRate, OxrApiResponse};
+use super::models::{Currency, ExchangeRate, OxrApiResponse, RateHistoryQuery};
+use crate::cache::ResponseCache;
 use crate::errors::AppError;
 
 /// Service layer for currency business logic.
@@ -76,10 +78,10 @@ impl CurrencyService {
         // Try to find the exact date first, then fall back to the most recent rate
         sqlx::query_as::<_, ExchangeRate>(
             r#"
-            SELECT id, base_currency, target_currency, rate, rate_date, created_at
+            SELECT id, base_currency, target_currency, rate, effective_date AS rate_date, fetched_at AS created_at
             FROM exchange_rates
-            WHERE base_currency = $1 AND target_currency = $2 AND rate_date <= $3
-            ORDER BY rate_date DESC
+            WHERE base_currency = $1 AND target_currency = $2 AND effective_date <= $3
+            ORDER BY effective_date DESC
             LIMIT 1
             "#,
         )
@@ -97,6 +99,104 @@ impl CurrencyService {
         })
     }
 
+    /// Stored rates for one currency pair within an optional date range,
+    /// oldest first, with the total count in the range.
+    pub async fn rate_history(
+        pool: &PgPool,
+        query: &RateHistoryQuery,
+    ) -> Result<(Vec<ExchangeRate>, i64), AppError> {
+        let base_upper = query.base.to_uppercase();
+        let target_upper = query.target.to_uppercase();
+
+        let total = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM exchange_rates
+            WHERE base_currency = $1 AND target_currency = $2
+              AND ($3::DATE IS NULL OR effective_date >= $3)
+              AND ($4::DATE IS NULL OR effective_date <= $4)
+            "#,
+        )
+        .bind(&base_upper)
+        .bind(&target_upper)
+        .bind(query.start)
+        .bind(query.end)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let rates = sqlx::query_as::<_, ExchangeRate>(
+            r#"
+            SELECT id, base_currency, target_currency, rate, effective_date AS rate_date, fetched_at AS created_at
+            FROM exchange_rates
+            WHERE base_currency = $1 AND target_currency = $2
+              AND ($3::DATE IS NULL OR effective_date >= $3)
+              AND ($4::DATE IS NULL OR effective_date <= $4)
+            ORDER BY effective_date ASC
+            LIMIT $5 OFFSET $6
+            "#,
+        )
+        .bind(&base_upper)
+        .bind(&target_upper)
+        .bind(query.start)
+        .bind(query.end)
+        .bind(query.limit)
+        .bind(query.offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((rates, total))
+    }
+
+    /// Rate converting `from` into `to` on a date, and the date of the stored
+    /// rate used. Pairs are looked up directly, inverted, or crossed through a
+    /// shared base currency (the sync stores everything against USD). The
+    /// latest rate on or before `date` wins; dates before the stored history
+    /// use its earliest rate. `None` when no rate links the two currencies.
+    pub async fn conversion_rate(
+        pool: &PgPool,
+        from: &str,
+        to: &str,
+        date: NaiveDate,
+    ) -> Result<Option<(Decimal, NaiveDate)>, AppError> {
+        let from_upper = from.to_uppercase();
+        let to_upper = to.to_uppercase();
+        if from_upper == to_upper {
+            return Ok(Some((Decimal::ONE, date)));
+        }
+
+        sqlx::query_as::<_, (Decimal, NaiveDate)>(
+            r#"
+            WITH candidates AS (
+                SELECT rate, effective_date
+                FROM exchange_rates
+                WHERE base_currency = $1 AND target_currency = $2
+                UNION ALL
+                SELECT 1 / rate, effective_date
+                FROM exchange_rates
+                WHERE base_currency = $2 AND target_currency = $1
+                UNION ALL
+                SELECT t.rate / f.rate, f.effective_date
+                FROM exchange_rates f
+                JOIN exchange_rates t
+                    ON t.base_currency = f.base_currency AND t.effective_date = f.effective_date
+                WHERE f.target_currency = $1 AND t.target_currency = $2
+            )
+            SELECT rate, effective_date
+            FROM candidates
+            ORDER BY effective_date <= $3 DESC, ABS(effective_date - $3)
+            LIMIT 1
+            "#,
+        )
+        .bind(&from_upper)
+        .bind(&to_upper)
+        .bind(date)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
     /// Fetch exchange rates from Open Exchange Rates API and store them.
     pub async fn fetch_and_store_rates(pool: &PgPool, api_key: &str) -> Result<usize, AppError> {
         let url = format!(
@@ -129,10 +229,10 @@ impl CurrencyService {
         for (target_currency, rate) in oxr_response.rates {
             let result = sqlx::query(
                 r#"
-                INSERT INTO exchange_rates (base_currency, target_currency, rate, rate_date)
+                INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
                 VALUES ($1, $2, $3, $4)
-                ON CONFLICT (base_currency, target_currency, rate_date)
-                DO UPDATE SET rate = EXCLUDED.rate, created_at = NOW()
+                ON CONFLICT (base_currency, target_currency, effective_date)
+                DO UPDATE SET rate = EXCLUDED.rate, fetched_at = NOW()
                 "#,
             )
             .bind(&base_currency)
@@ -147,6 +247,8 @@ impl CurrencyService {
             }
         }
 
+        // Summaries convert balances at these rates
+        ResponseCache::invalidate_account_summaries();
         Ok(rates_updated)
     }
 }
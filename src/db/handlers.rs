@@ -0,0 +1,25 @@
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+
+use super::metrics::PoolMetrics;
+use crate::jobs::models::IntegrityAuditMetrics;
+
+/// GET /metrics - Connection pool and integrity audit metrics in the Prometheus text format
+#[get("/metrics")]
+pub async fn pool_metrics(
+    pool: web::Data<PgPool>,
+    metrics: web::Data<PoolMetrics>,
+    integrity: web::Data<IntegrityAuditMetrics>,
+) -> HttpResponse {
+    let mut body = metrics.snapshot(pool.get_ref()).to_prometheus();
+    body.push_str(&integrity.to_prometheus());
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(body)
+}
+
+/// Register the metrics route
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(pool_metrics);
+}
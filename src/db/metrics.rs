@@ -0,0 +1,97 @@
+use sqlx::PgPool;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
+
+use super::models::{PoolConfig, PoolStats};
+use crate::shutdown;
+
+/// Connection pool metrics, fed by a background probe.
+///
+/// Queries acquire connections inside sqlx where the wait can't be observed,
+/// so the sampler times its own acquisition on every tick. Under contention
+/// the probe waits in the same queue as request queries.
+#[derive(Debug)]
+pub struct PoolMetrics {
+    config: PoolConfig,
+    last_wait_micros: AtomicU64,
+    wait_micros_sum: AtomicU64,
+    probes: AtomicU64,
+    failures: AtomicU64,
+    last_probe_failed: AtomicBool,
+}
+
+impl PoolMetrics {
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            config,
+            last_wait_micros: AtomicU64::new(0),
+            wait_micros_sum: AtomicU64::new(0),
+            probes: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            last_probe_failed: AtomicBool::new(false),
+        }
+    }
+
+    /// Time one acquisition and record it
+    async fn probe(&self, pool: &PgPool) {
+        let started = Instant::now();
+        let result = pool.acquire().await;
+        let waited = started.elapsed().as_micros() as u64;
+
+        self.probes.fetch_add(1, Ordering::Relaxed);
+        self.last_wait_micros.store(waited, Ordering::Relaxed);
+        self.wait_micros_sum.fetch_add(waited, Ordering::Relaxed);
+
+        match result {
+            Ok(_conn) => self.last_probe_failed.store(false, Ordering::Relaxed),
+            Err(e) => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+                self.last_probe_failed.store(true, Ordering::Relaxed);
+                warn!("Connection pool probe failed to acquire: {}", e);
+            }
+        }
+    }
+
+    /// Current pool state combined with the latest probe results
+    pub fn snapshot(&self, pool: &PgPool) -> PoolStats {
+        let size = pool.size();
+        let idle = (pool.num_idle() as u32).min(size);
+        let in_use = size - idle;
+        let probes = self.probes.load(Ordering::Relaxed);
+
+        PoolStats {
+            max_connections: self.config.max_connections,
+            size,
+            idle,
+            in_use,
+            last_acquire_wait_ms: (probes > 0)
+                .then(|| self.last_wait_micros.load(Ordering::Relaxed) as f64 / 1000.0),
+            acquire_wait_seconds_sum: self.wait_micros_sum.load(Ordering::Relaxed) as f64
+                / 1_000_000.0,
+            acquire_probes: probes,
+            acquire_failures: self.failures.load(Ordering::Relaxed),
+            saturated: in_use >= self.config.saturation_limit()
+                || self.last_probe_failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Spawn the background task that probes the pool every `metrics_interval`
+pub fn spawn_pool_sampler(pool: PgPool, metrics: Arc<PoolMetrics>) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(metrics.config.metrics_interval);
+        while shutdown::tick(&mut ticker).await {
+            metrics.probe(&pool).await;
+            let stats = metrics.snapshot(&pool);
+            if stats.saturated {
+                warn!(
+                    in_use = stats.in_use,
+                    max_connections = stats.max_connections,
+                    "Connection pool saturated"
+                );
+            }
+        }
+    });
+}
@@ -0,0 +1,21 @@
+pub mod handlers;
+pub mod metrics;
+pub mod models;
+
+pub use handlers::*;
+
+use sqlx::migrate::{MigrateError, Migrator};
+use sqlx::PgPool;
+use tracing::info;
+
+/// Migrations shipped with this build, embedded from `migrations/`
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Apply any pending embedded migrations. The migrator holds an advisory
+/// lock while it runs, so instances starting together apply each one once.
+pub async fn run_migrations(pool: &PgPool) -> Result<(), MigrateError> {
+    MIGRATOR.run(pool).await?;
+    let latest = MIGRATOR.iter().map(|m| m.version).max().unwrap_or_default();
+    info!("Database schema is at migration {}", latest);
+    Ok(())
+}
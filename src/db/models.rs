@@ -0,0 +1,200 @@
+use serde::Serialize;
+use sqlx::postgres::PgPoolOptions;
+use std::fmt::Write;
+use std::time::Duration;
+
+/// Connection pool sizing, timeouts and saturation thresholds
+#[derive(Debug, Clone, Copy)]
+pub struct PoolConfig {
+    /// Upper bound on open connections
+    pub max_connections: u32,
+    /// Connections kept open even when idle
+    pub min_connections: u32,
+    /// How long a query waits for a free connection before failing
+    pub acquire_timeout: Duration,
+    /// Idle connections are closed after this long
+    pub idle_timeout: Duration,
+    /// Connections are recycled after this long regardless of use
+    pub max_lifetime: Duration,
+    /// Acquisitions slower than this are logged as warnings
+    pub acquire_slow_threshold: Duration,
+    /// Share of `max_connections` in use at which the pool counts as saturated
+    pub saturation_percent: u32,
+    /// How often the pool is sampled for metrics
+    pub metrics_interval: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(5),
+            idle_timeout: Duration::from_secs(300),
+            max_lifetime: Duration::from_secs(900),
+            acquire_slow_threshold: Duration::from_millis(500),
+            saturation_percent: 90,
+            metrics_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Pool options built from this configuration
+    pub fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.max_connections)
+            .min_connections(self.min_connections)
+            .acquire_timeout(self.acquire_timeout)
+            .acquire_slow_threshold(self.acquire_slow_threshold)
+            .idle_timeout(self.idle_timeout)
+            .max_lifetime(self.max_lifetime)
+    }
+
+    /// Connections in use at which the pool counts as saturated (at least 1)
+    pub fn saturation_limit(&self) -> u32 {
+        (self.max_connections * self.saturation_percent)
+            .div_ceil(100)
+            .max(1)
+    }
+}
+
+/// Point-in-time view of the connection pool
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolStats {
+    pub max_connections: u32,
+    /// Open connections, idle or in use
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    /// Wait for a connection on the most recent probe
+    pub last_acquire_wait_ms: Option<f64>,
+    /// Total wait across all probes, in seconds
+    #[serde(skip)]
+    pub acquire_wait_seconds_sum: f64,
+    /// Number of probes taken
+    #[serde(skip)]
+    pub acquire_probes: u64,
+    /// Probes that failed to get a connection within the acquire timeout
+    pub acquire_failures: u64,
+    /// In-use connections reached the saturation limit, or the last probe failed
+    pub saturated: bool,
+}
+
+impl PoolStats {
+    /// Render the stats in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            let _ = writeln!(out, "{name} {value}");
+        };
+
+        metric(
+            "db_pool_max_connections",
+            "gauge",
+            "Configured maximum pool size",
+            self.max_connections.to_string(),
+        );
+        metric(
+            "db_pool_connections",
+            "gauge",
+            "Open connections, idle or in use",
+            self.size.to_string(),
+        );
+        metric(
+            "db_pool_connections_idle",
+            "gauge",
+            "Idle connections",
+            self.idle.to_string(),
+        );
+        metric(
+            "db_pool_connections_in_use",
+            "gauge",
+            "Connections checked out by queries",
+            self.in_use.to_string(),
+        );
+        metric(
+            "db_pool_acquire_wait_seconds_last",
+            "gauge",
+            "Time the most recent probe waited for a connection",
+            self.last_acquire_wait_ms
+                .map(|ms| (ms / 1000.0).to_string())
+                .unwrap_or_else(|| "NaN".to_string()),
+        );
+        metric(
+            "db_pool_acquire_wait_seconds_sum",
+            "counter",
+            "Total time probes waited for a connection",
+            self.acquire_wait_seconds_sum.to_string(),
+        );
+        metric(
+            "db_pool_acquire_wait_seconds_count",
+            "counter",
+            "Number of acquisition probes",
+            self.acquire_probes.to_string(),
+        );
+        metric(
+            "db_pool_acquire_failures_total",
+            "counter",
+            "Probes that timed out or failed to get a connection",
+            self.acquire_failures.to_string(),
+        );
+        metric(
+            "db_pool_saturated",
+            "gauge",
+            "1 when the pool is saturated",
+            u8::from(self.saturated).to_string(),
+        );
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturation_limit_and_prometheus_output() {
+        let config = PoolConfig {
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(5),
+            idle_timeout: Duration::from_secs(300),
+            max_lifetime: Duration::from_secs(900),
+            acquire_slow_threshold: Duration::from_millis(500),
+            saturation_percent: 85,
+            metrics_interval: Duration::from_secs(5),
+        };
+        assert_eq!(config.saturation_limit(), 9);
+        assert_eq!(
+            PoolConfig {
+                max_connections: 1,
+                saturation_percent: 0,
+                ..config
+            }
+            .saturation_limit(),
+            1
+        );
+
+        let stats = PoolStats {
+            max_connections: 10,
+            size: 4,
+            idle: 1,
+            in_use: 3,
+            last_acquire_wait_ms: Some(2.5),
+            acquire_wait_seconds_sum: 0.5,
+            acquire_probes: 20,
+            acquire_failures: 1,
+            saturated: false,
+        };
+        let text = stats.to_prometheus();
+        assert!(text
+            .contains("# TYPE db_pool_connections_in_use gauge\ndb_pool_connections_in_use 3\n"));
+        assert!(text.contains("db_pool_acquire_wait_seconds_count 20\n"));
+        assert!(text.contains("db_pool_saturated 0\n"));
+    }
+}
@@ -0,0 +1,420 @@
+//! Seeded generator of realistic-looking budget data for load tests.
+//!
+//! Spending is spread over everyday categories with log-normal amounts, a few
+//! accounts see most of the activity, salaries land in checking accounts and
+//! savings receive occasional transfers. The same profile and seed always
+//! produce the same data, so benchmarks can be repeated.
+
+use chrono::{DateTime, Datelike, Months, NaiveDate, NaiveTime, TimeZone, Utc};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::Decimal;
+
+use super::models::LoadProfile;
+use crate::account::models::AccountType;
+use crate::transaction::models::TransactionType;
+
+/// A spending category: name, color, relative frequency, median amount in
+/// cents, log-normal spread and sample merchants
+struct CategorySpec {
+    name: &'static str,
+    color_hex: &'static str,
+    weight: u32,
+    median_cents: f64,
+    sigma: f64,
+    merchants: &'static [&'static str],
+}
+
+const CATEGORIES: &[CategorySpec] = &[
+    CategorySpec {
+        name: "Groceries",
+        color_hex: "#22C55E",
+        weight: 30,
+        median_cents: 4_500.0,
+        sigma: 0.7,
+        merchants: &[
+            "FreshMart",
+            "Green Grocer",
+            "Corner Market",
+            "Whole Harvest",
+        ],
+    },
+    CategorySpec {
+        name: "Dining",
+        color_hex: "#F97316",
+        weight: 20,
+        median_cents: 2_200.0,
+        sigma: 0.6,
+        merchants: &["Corner Cafe", "Noodle House", "Taco Stand", "Pizza Place"],
+    },
+    CategorySpec {
+        name: "Transport",
+        color_hex: "#3B82F6",
+        weight: 15,
+        median_cents: 1_800.0,
+        sigma: 0.8,
+        merchants: &["City Transit", "RideShare", "Fuel Stop"],
+    },
+    CategorySpec {
+        name: "Shopping",
+        color_hex: "#A855F7",
+        weight: 10,
+        median_cents: 5_000.0,
+        sigma: 1.0,
+        merchants: &["MegaStore", "Online Bazaar", "Shoe Hub"],
+    },
+    CategorySpec {
+        name: "Entertainment",
+        color_hex: "#EC4899",
+        weight: 8,
+        median_cents: 2_500.0,
+        sigma: 0.7,
+        merchants: &["Cinema City", "StreamCo", "Game Vault"],
+    },
+    CategorySpec {
+        name: "Utilities",
+        color_hex: "#EAB308",
+        weight: 5,
+        median_cents: 9_000.0,
+        sigma: 0.4,
+        merchants: &["Power & Light", "Water Works", "FiberNet"],
+    },
+    CategorySpec {
+        name: "Health",
+        color_hex: "#14B8A6",
+        weight: 5,
+        median_cents: 4_000.0,
+        sigma: 0.9,
+        merchants: &["Pharmacy Plus", "City Clinic"],
+    },
+    CategorySpec {
+        name: "Housing",
+        color_hex: "#64748B",
+        weight: 2,
+        median_cents: 140_000.0,
+        sigma: 0.2,
+        merchants: &["Rent", "Property Management"],
+    },
+    CategorySpec {
+        name: "Travel",
+        color_hex: "#0EA5E9",
+        weight: 1,
+        median_cents: 35_000.0,
+        sigma: 0.9,
+        merchants: &["SkyAir", "Harbor Hotel", "Rail Europe"],
+    },
+];
+
+/// Category for salaries and transfers
+const INCOME_CATEGORY: &str = "Income";
+const INCOME_COLOR: &str = "#16A34A";
+
+/// Share of transactions that are salaries and transfers; the rest are expenses
+const INCOME_SHARE: f64 = 0.03;
+const TRANSFER_SHARE: f64 = 0.04;
+
+/// Largest generated amount, kept well inside NUMERIC(12,2)
+const MAX_CENTS: i64 = 5_000_000;
+
+/// An account to create
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedAccount {
+    pub name: String,
+    pub account_type: AccountType,
+    pub color_hex: &'static str,
+    pub opening_balance: Decimal,
+}
+
+/// A budget category, created in every generated month
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedCategory {
+    pub name: &'static str,
+    pub color_hex: &'static str,
+    /// Monthly allocation, roughly the expected monthly spending
+    pub allocated: Decimal,
+}
+
+/// A transaction, referring to accounts, months and categories by index
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedTransaction {
+    /// Index into `LoadPlan::months`
+    pub month: usize,
+    /// Index into `LoadPlan::categories`
+    pub category: usize,
+    pub account: usize,
+    pub destination: Option<usize>,
+    pub transaction_type: TransactionType,
+    pub amount: Decimal,
+    pub transaction_date: DateTime<Utc>,
+    pub description: String,
+}
+
+/// Everything except the transactions, which are streamed by `transactions()`
+#[derive(Debug, Clone)]
+pub struct LoadPlan {
+    pub accounts: Vec<GeneratedAccount>,
+    pub categories: Vec<GeneratedCategory>,
+    /// First day of each month, oldest first, ending with the current month
+    pub months: Vec<NaiveDate>,
+    /// Monthly income recorded on each budget
+    pub monthly_income: Decimal,
+    profile: LoadProfile,
+    today: NaiveDate,
+}
+
+fn cents(value: i64) -> Decimal {
+    Decimal::new(value, 2)
+}
+
+/// Standard normal sample (Box-Muller)
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+fn log_normal_cents(rng: &mut StdRng, median_cents: f64, sigma: f64) -> i64 {
+    let value = median_cents * (sigma * standard_normal(rng)).exp();
+    (value.round() as i64).clamp(1, MAX_CENTS)
+}
+
+impl LoadPlan {
+    pub fn new(profile: &LoadProfile, today: NaiveDate) -> Self {
+        let mut rng = StdRng::seed_from_u64(profile.seed);
+
+        // Mostly checking and credit cards, with some savings
+        let accounts = (0..profile.accounts as usize)
+            .map(|i| {
+                let (account_type, color_hex, opening) = match i % 10 {
+                    0..=4 => (
+                        AccountType::Checking,
+                        "#2563EB",
+                        rng.gen_range(50_000..500_000),
+                    ),
+                    5..=7 => (AccountType::Credit, "#DC2626", 0),
+                    _ => (
+                        AccountType::Savings,
+                        "#059669",
+                        rng.gen_range(500_000..5_000_000),
+                    ),
+                };
+                GeneratedAccount {
+                    name: format!("Load test {} {}", account_type.as_str(), i + 1),
+                    account_type,
+                    color_hex,
+                    opening_balance: cents(opening),
+                }
+            })
+            .collect();
+
+        let this_month = today.with_day(1).expect("valid date");
+        let months: Vec<NaiveDate> = (0..profile.months)
+            .rev()
+            .map(|back| this_month - Months::new(back))
+            .collect();
+
+        // Allocate each category its expected monthly spending
+        let per_month = f64::from(profile.transactions) / f64::from(profile.months);
+        let expense_share = 1.0 - INCOME_SHARE - TRANSFER_SHARE;
+        let total_weight: u32 = CATEGORIES.iter().map(|c| c.weight).sum();
+        let mut categories: Vec<GeneratedCategory> = CATEGORIES
+            .iter()
+            .map(|spec| {
+                let count =
+                    per_month * expense_share * f64::from(spec.weight) / f64::from(total_weight);
+                let mean = spec.median_cents * (spec.sigma * spec.sigma / 2.0).exp();
+                GeneratedCategory {
+                    name: spec.name,
+                    color_hex: spec.color_hex,
+                    allocated: cents((count * mean / 100.0).round() as i64 * 100),
+                }
+            })
+            .collect();
+        categories.push(GeneratedCategory {
+            name: INCOME_CATEGORY,
+            color_hex: INCOME_COLOR,
+            allocated: Decimal::ZERO,
+        });
+
+        // Income roughly covers spending, as in a real household budget
+        let monthly_income = categories
+            .iter()
+            .map(|c| c.allocated)
+            .sum::<Decimal>()
+            .round_dp(0);
+
+        Self {
+            accounts,
+            categories,
+            months,
+            monthly_income,
+            profile: profile.clone(),
+            today,
+        }
+    }
+
+    /// The transactions, generated lazily so millions don't sit in memory
+    pub fn transactions(&self) -> impl Iterator<Item = GeneratedTransaction> + '_ {
+        let mut rng = StdRng::seed_from_u64(self.profile.seed.wrapping_add(1));
+
+        let by_type = |wanted: AccountType| -> Vec<usize> {
+            self.accounts
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| a.account_type == wanted)
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let checking = by_type(AccountType::Checking);
+        let savings = by_type(AccountType::Savings);
+        let spending: Vec<usize> = checking
+            .iter()
+            .chain(by_type(AccountType::Credit).iter())
+            .copied()
+            .collect();
+
+        // A few accounts see most of the activity (Zipf-like weights)
+        let zipf = |n: usize| WeightedIndex::new((1..=n).map(|rank| 1.0 / rank as f64)).ok();
+        let spending_pick = zipf(spending.len());
+        let checking_pick = zipf(checking.len());
+        let savings_pick = zipf(savings.len());
+        let category_pick =
+            WeightedIndex::new(CATEGORIES.iter().map(|c| c.weight)).expect("positive weights");
+        let income_category = self.categories.len() - 1;
+
+        (0..self.profile.transactions).map(move |_| {
+            let month = rng.gen_range(0..self.months.len());
+            let first = self.months[month];
+            let last = if month + 1 == self.months.len() {
+                self.today
+            } else {
+                (first + Months::new(1)).pred_opt().expect("valid date")
+            };
+            let day =
+                first + chrono::Days::new(rng.gen_range(0..=(last - first).num_days()) as u64);
+            // Mostly during the day
+            let time = NaiveTime::from_hms_opt(rng.gen_range(7..23), rng.gen_range(0..60), 0)
+                .expect("valid time");
+            let transaction_date = Utc.from_utc_datetime(&day.and_time(time));
+
+            let roll: f64 = rng.gen();
+            if roll < INCOME_SHARE {
+                if let Some(pick) = &checking_pick {
+                    return GeneratedTransaction {
+                        month,
+                        category: income_category,
+                        account: checking[pick.sample(&mut rng)],
+                        destination: None,
+                        transaction_type: TransactionType::Income,
+                        amount: cents(log_normal_cents(&mut rng, 300_000.0, 0.3)),
+                        transaction_date,
+                        description: "Salary".to_string(),
+                    };
+                }
+            } else if roll < INCOME_SHARE + TRANSFER_SHARE {
+                if let (Some(from), Some(to)) = (&checking_pick, &savings_pick) {
+                    return GeneratedTransaction {
+                        month,
+                        category: income_category,
+                        account: checking[from.sample(&mut rng)],
+                        destination: Some(savings[to.sample(&mut rng)]),
+                        transaction_type: TransactionType::Transfer,
+                        amount: cents(log_normal_cents(&mut rng, 30_000.0, 0.8)),
+                        transaction_date,
+                        description: "Transfer to savings".to_string(),
+                    };
+                }
+            }
+
+            let category = category_pick.sample(&mut rng);
+            let spec = &CATEGORIES[category];
+            let account = spending_pick
+                .as_ref()
+                .map_or(0, |pick| spending[pick.sample(&mut rng)]);
+            GeneratedTransaction {
+                month,
+                category,
+                account,
+                destination: None,
+                transaction_type: TransactionType::Expense,
+                amount: cents(log_normal_cents(&mut rng, spec.median_cents, spec.sigma)),
+                transaction_date,
+                description: spec.merchants[rng.gen_range(0..spec.merchants.len())].to_string(),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(accounts: u32, transactions: u32) -> LoadProfile {
+        LoadProfile {
+            accounts,
+            transactions,
+            months: 12,
+            seed: 7,
+        }
+    }
+
+    #[test]
+    fn test_load_plan() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let plan = LoadPlan::new(&profile(20, 5_000), today);
+
+        assert_eq!(plan.accounts.len(), 20);
+        assert_eq!(plan.months.len(), 12);
+        assert_eq!(
+            plan.months[0],
+            NaiveDate::from_ymd_opt(2025, 11, 1).unwrap()
+        );
+        assert_eq!(
+            plan.months[11],
+            NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()
+        );
+
+        let transactions: Vec<_> = plan.transactions().collect();
+        assert_eq!(transactions.len(), 5_000);
+        for t in &transactions {
+            assert!(t.amount > Decimal::ZERO);
+            let date = t.transaction_date.date_naive();
+            assert!(date >= plan.months[t.month] && date <= today);
+            assert_eq!(date.month(), plan.months[t.month].month());
+            match t.transaction_type {
+                TransactionType::Expense => {
+                    assert_ne!(plan.accounts[t.account].account_type, AccountType::Savings)
+                }
+                TransactionType::Income => {
+                    assert_eq!(plan.accounts[t.account].account_type, AccountType::Checking)
+                }
+                TransactionType::Transfer => assert_eq!(
+                    t.destination.map(|d| plan.accounts[d].account_type),
+                    Some(AccountType::Savings)
+                ),
+            }
+        }
+        // All kinds show up, with expenses dominating
+        let count = |kind: TransactionType| {
+            transactions
+                .iter()
+                .filter(|t| t.transaction_type == kind)
+                .count()
+        };
+        assert!(count(TransactionType::Expense) > 4_000);
+        assert!(count(TransactionType::Income) > 0);
+        assert!(count(TransactionType::Transfer) > 0);
+
+        // The same seed generates the same data
+        let again = LoadPlan::new(&profile(20, 5_000), today);
+        assert_eq!(again.accounts, plan.accounts);
+        assert!(again.transactions().eq(plan.transactions()));
+
+        // Without a savings account there is nowhere to transfer to
+        let solo = LoadPlan::new(&profile(1, 200), today);
+        assert!(solo
+            .transactions()
+            .all(|t| t.account == 0 && t.transaction_type != TransactionType::Transfer));
+    }
+}
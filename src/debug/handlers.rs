@@ -0,0 +1,86 @@
+use actix_web::{get, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::service::AccountService;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{BalanceInvariantReport, DebugConfig, LoadProfile, LoadSummary};
+use super::service::DebugService;
+
+/// GET /debug/invariants/balances - Check the user's balances against history
+///
+/// Read-only: drifted balances are reported, not fixed. Only available when
+/// `DEBUG_ENDPOINTS_ENABLED` is set.
+#[utoipa::path(
+    get,
+    path = "/debug/invariants/balances",
+    tag = "Debug",
+    responses(
+        (status = 200, description = "Whether the balance invariant holds, with any drifted accounts", body = BalanceInvariantReport),
+        (status = 404, description = "Debug endpoints are disabled", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/debug/invariants/balances")]
+pub async fn check_balance_invariant(
+    pool: web::Data<PgPool>,
+    config: web::Data<DebugConfig>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    config.require_enabled()?;
+    auth.require_scope(Scope::AccountsRead)?;
+
+    let summary =
+        AccountService::recompute_all_balances(pool.get_ref(), Some(auth.user_id), false).await?;
+
+    Ok(HttpResponse::Ok().json(BalanceInvariantReport::from(summary)))
+}
+
+/// POST /debug/load-data - Generate load-test data for the user
+///
+/// Creates accounts, a budget with categories per month and the requested
+/// volume of transactions. Large volumes take minutes; prefer the
+/// `generate-load-data` command for millions. Only available when
+/// `DEBUG_ENDPOINTS_ENABLED` is set.
+#[utoipa::path(
+    post,
+    path = "/debug/load-data",
+    tag = "Debug",
+    request_body = LoadProfile,
+    responses(
+        (status = 201, description = "Data generated", body = LoadSummary),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Debug endpoints are disabled", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/debug/load-data")]
+pub async fn generate_load_data(
+    pool: web::Data<PgPool>,
+    config: web::Data<DebugConfig>,
+    auth: AuthenticatedUser,
+    body: web::Json<LoadProfile>,
+) -> Result<HttpResponse, AppError> {
+    config.require_enabled()?;
+    auth.require_scope(Scope::AccountsWrite)?;
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let summary = DebugService::generate_load_data(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(summary))
+}
+
+/// Register the debug routes; each answers 404 unless debug endpoints are enabled
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(check_balance_invariant)
+        .service(generate_load_data);
+}
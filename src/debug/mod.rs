@@ -0,0 +1,6 @@
+pub mod generator;
+pub mod handlers;
+pub mod models;
+pub mod service;
+
+pub use handlers::*;
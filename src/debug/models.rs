@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::account::models::BalanceRecomputeSummary;
+use crate::errors::AppError;
+
+/// Settings for the debug endpoints, meant for staging and local environments.
+/// Never enable them in production.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DebugConfig {
+    pub enabled: bool,
+}
+
+impl DebugConfig {
+    /// Debug endpoints answer 404 unless enabled, as if they didn't exist
+    pub fn require_enabled(&self) -> Result<(), AppError> {
+        if !self.enabled {
+            return Err(AppError::NotFound("Not found".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Whether every account balance equals its opening balance plus its
+/// transaction history
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceInvariantReport {
+    /// True when no account drifted
+    pub holds: bool,
+    #[serde(flatten)]
+    pub summary: BalanceRecomputeSummary,
+}
+
+impl From<BalanceRecomputeSummary> for BalanceInvariantReport {
+    fn from(summary: BalanceRecomputeSummary) -> Self {
+        Self {
+            holds: summary.drifted.is_empty(),
+            summary,
+        }
+    }
+}
+
+fn default_load_accounts() -> u32 {
+    50
+}
+
+fn default_load_transactions() -> u32 {
+    100_000
+}
+
+fn default_load_months() -> u32 {
+    24
+}
+
+fn default_load_seed() -> u64 {
+    1
+}
+
+/// Volumes of load-test data to generate
+#[derive(Debug, Clone, PartialEq, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadProfile {
+    /// Accounts to create (1-500, default 50)
+    #[serde(default = "default_load_accounts")]
+    #[validate(range(min = 1, max = 500, message = "Accounts must be between 1 and 500"))]
+    #[schema(example = 50)]
+    pub accounts: u32,
+
+    /// Transactions to spread across them (1-5,000,000, default 100,000)
+    #[serde(default = "default_load_transactions")]
+    #[validate(range(
+        min = 1,
+        max = 5_000_000,
+        message = "Transactions must be between 1 and 5,000,000"
+    ))]
+    #[schema(example = 1000000)]
+    pub transactions: u32,
+
+    /// Months of history, ending with the current month (1-120, default 24)
+    #[serde(default = "default_load_months")]
+    #[validate(range(min = 1, max = 120, message = "Months must be between 1 and 120"))]
+    #[schema(example = 24)]
+    pub months: u32,
+
+    /// Random seed; the same profile and seed generate the same data
+    #[serde(default = "default_load_seed")]
+    #[schema(example = 1)]
+    pub seed: u64,
+}
+
+impl Default for LoadProfile {
+    fn default() -> Self {
+        Self {
+            accounts: default_load_accounts(),
+            transactions: default_load_transactions(),
+            months: default_load_months(),
+            seed: default_load_seed(),
+        }
+    }
+}
+
+impl LoadProfile {
+    /// Parse `--accounts N --transactions N --months N --seed N`; omitted flags
+    /// keep their defaults
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let mut profile = Self::default();
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("Missing value for {flag}"))?;
+            let invalid = |_| format!("Invalid value for {flag}: {value}");
+            match flag.as_str() {
+                "--accounts" => profile.accounts = value.parse().map_err(invalid)?,
+                "--transactions" => profile.transactions = value.parse().map_err(invalid)?,
+                "--months" => profile.months = value.parse().map_err(invalid)?,
+                "--seed" => profile.seed = value.parse().map_err(invalid)?,
+                _ => return Err(format!("Unknown option {flag}")),
+            }
+        }
+        profile.validate().map_err(|e| e.to_string())?;
+        Ok(profile)
+    }
+}
+
+/// What the load-test generator created
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadSummary {
+    /// User the data belongs to
+    pub user_id: Uuid,
+    #[schema(example = 50)]
+    pub accounts: usize,
+    #[schema(example = 24)]
+    pub budgets: usize,
+    #[schema(example = 240)]
+    pub categories: usize,
+    #[schema(example = 1000000)]
+    pub transactions: u64,
+    /// Wall-clock time taken
+    #[schema(example = 95000)]
+    pub elapsed_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_load_profile_from_args() {
+        assert_eq!(LoadProfile::from_args(&[]), Ok(LoadProfile::default()));
+
+        let profile =
+            LoadProfile::from_args(&args("--transactions 1000000 --accounts 50 --seed 9")).unwrap();
+        assert_eq!(profile.transactions, 1_000_000);
+        assert_eq!(profile.accounts, 50);
+        assert_eq!(profile.months, 24);
+        assert_eq!(profile.seed, 9);
+
+        assert!(LoadProfile::from_args(&args("--accounts")).is_err());
+        assert!(LoadProfile::from_args(&args("--accounts many")).is_err());
+        assert!(LoadProfile::from_args(&args("--accounts 0")).is_err());
+        assert!(LoadProfile::from_args(&args("--users 3")).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+use std::time::Instant;
+
+use chrono::{Datelike, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tracing::info;
+use uuid::Uuid;
+
+use super::generator::{GeneratedTransaction, LoadPlan};
+use super::models::{LoadProfile, LoadSummary};
+use crate::cache::ResponseCache;
+use crate::crypto::encrypt_field;
+use crate::errors::AppError;
+
+/// Transactions inserted per statement
+const BATCH_SIZE: usize = 5_000;
+
+/// Service layer for the debug endpoints and commands.
+pub struct DebugService;
+
+impl DebugService {
+    /// Id of the user with this email, for the command-line generator
+    pub async fn user_id_by_email(pool: &PgPool, email: &str) -> Result<Uuid, AppError> {
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM users WHERE LOWER(email) = LOWER($1)")
+            .bind(email.trim())
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+    }
+
+    /// Generate load-test accounts, budgets, categories and transactions for a
+    /// user. Existing budgets for the generated months are reused. Balances
+    /// are set from the generated history, so the balance invariant holds.
+    pub async fn generate_load_data(
+        pool: &PgPool,
+        user_id: Uuid,
+        profile: &LoadProfile,
+    ) -> Result<LoadSummary, AppError> {
+        let started = Instant::now();
+        let plan = LoadPlan::new(profile, Utc::now().date_naive());
+
+        let mut account_ids = Vec::with_capacity(plan.accounts.len());
+        for account in &plan.accounts {
+            let id = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO accounts (owner_id, name, account_type, balance, opening_balance, color_hex, currency)
+                SELECT $1, $2, $3, $4, $4, $5, default_currency FROM users WHERE id = $1
+                RETURNING id
+                "#,
+            )
+            .bind(user_id)
+            .bind(encrypt_field(&account.name)?)
+            .bind(account.account_type.as_str())
+            .bind(account.opening_balance)
+            .bind(account.color_hex)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            account_ids.push(id);
+        }
+
+        // Category ids per month, in the plan's category order
+        let mut category_ids: Vec<Vec<Uuid>> = Vec::with_capacity(plan.months.len());
+        for month in &plan.months {
+            let budget_id = sqlx::query_scalar::<_, Uuid>(
+                r#"
+                INSERT INTO budgets (owner_id, month, year, total_income, currency)
+                SELECT $1, $2, $3, $4, default_currency FROM users WHERE id = $1
+                ON CONFLICT (owner_id, month, year) DO UPDATE SET updated_at = budgets.updated_at
+                RETURNING id
+                "#,
+            )
+            .bind(user_id)
+            .bind(month.month0() as i16)
+            .bind(month.year() as i16)
+            .bind(plan.monthly_income)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            let names: Vec<&str> = plan.categories.iter().map(|c| c.name).collect();
+            let colors: Vec<&str> = plan.categories.iter().map(|c| c.color_hex).collect();
+            let allocated: Vec<Decimal> = plan.categories.iter().map(|c| c.allocated).collect();
+            let created = sqlx::query_as::<_, (Uuid, String)>(
+                r#"
+                INSERT INTO categories (budget_id, name, color_hex, allocated_amount)
+                SELECT $1, name, color_hex, allocated
+                FROM UNNEST($2::text[], $3::text[], $4::numeric[]) AS c(name, color_hex, allocated)
+                RETURNING id, name
+                "#,
+            )
+            .bind(budget_id)
+            .bind(&names)
+            .bind(&colors)
+            .bind(&allocated)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+            // Category names are unique within the plan
+            let ids = names
+                .iter()
+                .map(|name| {
+                    created
+                        .iter()
+                        .find(|(_, created_name)| created_name == name)
+                        .map(|(id, _)| *id)
+                        .ok_or_else(|| AppError::InternalError("Category not created".to_string()))
+                })
+                .collect::<Result<Vec<Uuid>, AppError>>()?;
+            category_ids.push(ids);
+        }
+
+        let mut inserted: u64 = 0;
+        let mut batch: Vec<GeneratedTransaction> = Vec::with_capacity(BATCH_SIZE);
+        for transaction in plan.transactions() {
+            batch.push(transaction);
+            if batch.len() == BATCH_SIZE {
+                inserted += Self::insert_batch(pool, &batch, &account_ids, &category_ids).await?;
+                batch.clear();
+                if inserted.is_multiple_of(100_000) {
+                    info!(user_id = %user_id, "Generated {} load-test transactions", inserted);
+                }
+            }
+        }
+        if !batch.is_empty() {
+            inserted += Self::insert_batch(pool, &batch, &account_ids, &category_ids).await?;
+        }
+
+        sqlx::query(
+            "UPDATE accounts SET balance = opening_balance + account_transaction_net(id) WHERE id = ANY($1)",
+        )
+        .bind(&account_ids)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        ResponseCache::invalidate_user(user_id);
+        Ok(LoadSummary {
+            user_id,
+            accounts: account_ids.len(),
+            budgets: category_ids.len(),
+            categories: category_ids.iter().map(Vec::len).sum(),
+            transactions: inserted,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        })
+    }
+
+    async fn insert_batch(
+        pool: &PgPool,
+        batch: &[GeneratedTransaction],
+        account_ids: &[Uuid],
+        category_ids: &[Vec<Uuid>],
+    ) -> Result<u64, AppError> {
+        let mut categories = Vec::with_capacity(batch.len());
+        let mut accounts = Vec::with_capacity(batch.len());
+        let mut destinations = Vec::with_capacity(batch.len());
+        let mut amounts = Vec::with_capacity(batch.len());
+        let mut types = Vec::with_capacity(batch.len());
+        let mut dates = Vec::with_capacity(batch.len());
+        let mut descriptions = Vec::with_capacity(batch.len());
+        for t in batch {
+            categories.push(category_ids[t.month][t.category]);
+            accounts.push(account_ids[t.account]);
+            destinations.push(t.destination.map(|d| account_ids[d]));
+            amounts.push(t.amount);
+            types.push(t.transaction_type.as_str());
+            dates.push(t.transaction_date);
+            descriptions.push(encrypt_field(&t.description)?);
+        }
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO transactions
+                (category_id, account_id, destination_account_id, amount, transaction_type, transaction_date, description)
+            SELECT * FROM UNNEST(
+                $1::uuid[], $2::uuid[], $3::uuid[], $4::numeric[], $5::text[], $6::timestamptz[], $7::text[]
+            )
+            "#,
+        )
+        .bind(&categories)
+        .bind(&accounts)
+        .bind(&destinations)
+        .bind(&amounts)
+        .bind(&types)
+        .bind(&dates)
+        .bind(&descriptions)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
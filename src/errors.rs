@@ -1,82 +1,633 @@
-use actix_web::{HttpResponse, ResponseError};
+use actix_web::error::{JsonPayloadError, PathError, QueryPayloadError};
+use actix_web::http::StatusCode;
+use actix_web::{get, web, HttpRequest, HttpResponse, Responder, ResponseError};
 use serde::Serialize;
 use std::fmt;
 use tracing::error;
 use utoipa::ToSchema;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+use crate::request_id::RequestId;
 
 #[derive(Debug)]
 pub enum AppError {
     ValidationError(String),
+    /// Request fields that failed validation, reported one by one
+    ValidationFailed(ValidationErrors),
+    /// A path, query or body value that couldn't be deserialized, with the
+    /// field it belongs to when that can be told
+    Malformed(String, Option<FieldError>),
     Unauthorized(String),
+    #[allow(dead_code)]
+    Forbidden(String),
     NotFound(String),
+    #[allow(dead_code)]
     Conflict(String),
     InternalError(String),
+    /// A failure with its own stable code; the code decides the status
+    Coded(ErrorCode, String),
+    /// A coded failure with structured details clients can act on
+    Detailed(ErrorCode, String, serde_json::Value),
+}
+
+/// Stable, machine-readable error codes. Clients branch on these (and
+/// localize messages by them) rather than parsing messages, so a published
+/// code never changes meaning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // General classes, used when nothing more specific applies
+    ValidationError,
+    MalformedRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    PreconditionFailed,
+    RateLimited,
+    InternalError,
+    // Auth
+    InvalidCredentials,
+    InvalidToken,
+    MissingScope,
+    AdminRequired,
+    EmailTaken,
+    InvalidResetToken,
+    // Budgets and accounts
+    BudgetMonthConflict,
+    InsufficientAllocation,
+    AllocationExceedsBudget,
+    CurrencyInactive,
+    DuplicateHolding,
+    DuplicateInvitation,
+    // Transactions
+    AlreadyRefunded,
+    RefundExceedsRemaining,
+    TransactionHasRefunds,
+    TransactionAlreadyPaysBill,
+    TransactionNotPending,
+    BillAlreadyPaid,
+    ReceiptAlreadyConfirmed,
+    DuplicatePayee,
+    DuplicateTag,
+    InsufficientFunds,
+    // Bank sync
+    ImportAlreadyReviewed,
+    PossibleDuplicate,
+    // Undo
+    AlreadyUndone,
+    NotUndoable,
+    UndoConflict,
+}
+
+impl ErrorCode {
+    /// Every code, in catalog order
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::ValidationError,
+        ErrorCode::MalformedRequest,
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::NotFound,
+        ErrorCode::Conflict,
+        ErrorCode::PreconditionFailed,
+        ErrorCode::RateLimited,
+        ErrorCode::InternalError,
+        ErrorCode::InvalidCredentials,
+        ErrorCode::InvalidToken,
+        ErrorCode::MissingScope,
+        ErrorCode::AdminRequired,
+        ErrorCode::EmailTaken,
+        ErrorCode::InvalidResetToken,
+        ErrorCode::BudgetMonthConflict,
+        ErrorCode::InsufficientAllocation,
+        ErrorCode::AllocationExceedsBudget,
+        ErrorCode::CurrencyInactive,
+        ErrorCode::DuplicateHolding,
+        ErrorCode::DuplicateInvitation,
+        ErrorCode::AlreadyRefunded,
+        ErrorCode::RefundExceedsRemaining,
+        ErrorCode::TransactionHasRefunds,
+        ErrorCode::TransactionAlreadyPaysBill,
+        ErrorCode::TransactionNotPending,
+        ErrorCode::BillAlreadyPaid,
+        ErrorCode::ReceiptAlreadyConfirmed,
+        ErrorCode::DuplicatePayee,
+        ErrorCode::DuplicateTag,
+        ErrorCode::InsufficientFunds,
+        ErrorCode::ImportAlreadyReviewed,
+        ErrorCode::PossibleDuplicate,
+        ErrorCode::AlreadyUndone,
+        ErrorCode::NotUndoable,
+        ErrorCode::UndoConflict,
+    ];
+
+    /// The code as sent in responses
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::ValidationError => "VALIDATION_ERROR",
+            ErrorCode::MalformedRequest => "MALFORMED_REQUEST",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::Forbidden => "FORBIDDEN",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Conflict => "CONFLICT",
+            ErrorCode::PreconditionFailed => "PRECONDITION_FAILED",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+            ErrorCode::InvalidCredentials => "INVALID_CREDENTIALS",
+            ErrorCode::InvalidToken => "INVALID_TOKEN",
+            ErrorCode::MissingScope => "MISSING_SCOPE",
+            ErrorCode::AdminRequired => "ADMIN_REQUIRED",
+            ErrorCode::EmailTaken => "EMAIL_TAKEN",
+            ErrorCode::InvalidResetToken => "INVALID_RESET_TOKEN",
+            ErrorCode::BudgetMonthConflict => "BUDGET_MONTH_CONFLICT",
+            ErrorCode::InsufficientAllocation => "INSUFFICIENT_ALLOCATION",
+            ErrorCode::AllocationExceedsBudget => "ALLOCATION_EXCEEDS_BUDGET",
+            ErrorCode::CurrencyInactive => "CURRENCY_INACTIVE",
+            ErrorCode::DuplicateHolding => "DUPLICATE_HOLDING",
+            ErrorCode::DuplicateInvitation => "DUPLICATE_INVITATION",
+            ErrorCode::AlreadyRefunded => "ALREADY_REFUNDED",
+            ErrorCode::RefundExceedsRemaining => "REFUND_EXCEEDS_REMAINING",
+            ErrorCode::TransactionHasRefunds => "TRANSACTION_HAS_REFUNDS",
+            ErrorCode::TransactionAlreadyPaysBill => "TRANSACTION_ALREADY_PAYS_BILL",
+            ErrorCode::TransactionNotPending => "TRANSACTION_NOT_PENDING",
+            ErrorCode::BillAlreadyPaid => "BILL_ALREADY_PAID",
+            ErrorCode::ReceiptAlreadyConfirmed => "RECEIPT_ALREADY_CONFIRMED",
+            ErrorCode::DuplicatePayee => "DUPLICATE_PAYEE",
+            ErrorCode::DuplicateTag => "DUPLICATE_TAG",
+            ErrorCode::InsufficientFunds => "INSUFFICIENT_FUNDS",
+            ErrorCode::ImportAlreadyReviewed => "IMPORT_ALREADY_REVIEWED",
+            ErrorCode::PossibleDuplicate => "POSSIBLE_DUPLICATE",
+            ErrorCode::AlreadyUndone => "ALREADY_UNDONE",
+            ErrorCode::NotUndoable => "NOT_UNDOABLE",
+            ErrorCode::UndoConflict => "UNDO_CONFLICT",
+        }
+    }
+
+    /// The general class a code belongs to, sent as `error` for clients that
+    /// only distinguish classes
+    pub fn class(self) -> ErrorCode {
+        match self {
+            ErrorCode::InvalidCredentials | ErrorCode::InvalidToken => ErrorCode::Unauthorized,
+            ErrorCode::MissingScope | ErrorCode::AdminRequired => ErrorCode::Forbidden,
+            ErrorCode::InvalidResetToken
+            | ErrorCode::InsufficientAllocation
+            | ErrorCode::CurrencyInactive
+            | ErrorCode::RefundExceedsRemaining => ErrorCode::ValidationError,
+            ErrorCode::EmailTaken
+            | ErrorCode::BudgetMonthConflict
+            | ErrorCode::AllocationExceedsBudget
+            | ErrorCode::DuplicateHolding
+            | ErrorCode::DuplicateInvitation
+            | ErrorCode::AlreadyRefunded
+            | ErrorCode::TransactionHasRefunds
+            | ErrorCode::TransactionAlreadyPaysBill
+            | ErrorCode::TransactionNotPending
+            | ErrorCode::BillAlreadyPaid
+            | ErrorCode::ReceiptAlreadyConfirmed
+            | ErrorCode::DuplicatePayee
+            | ErrorCode::DuplicateTag
+            | ErrorCode::InsufficientFunds
+            | ErrorCode::ImportAlreadyReviewed
+            | ErrorCode::PossibleDuplicate
+            | ErrorCode::AlreadyUndone
+            | ErrorCode::NotUndoable
+            | ErrorCode::UndoConflict => ErrorCode::Conflict,
+            general => general,
+        }
+    }
+
+    /// HTTP status sent with the code
+    pub fn status(self) -> StatusCode {
+        match self.class() {
+            ErrorCode::ValidationError => StatusCode::BAD_REQUEST,
+            ErrorCode::MalformedRequest => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict => StatusCode::CONFLICT,
+            ErrorCode::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// What the code means, for the catalog
+    pub fn description(self) -> &'static str {
+        match self {
+            ErrorCode::ValidationError => "The request is malformed or a field is invalid",
+            ErrorCode::MalformedRequest => {
+                "A path, query or body value couldn't be read as the expected type"
+            }
+            ErrorCode::Unauthorized => "Authentication is missing or was rejected",
+            ErrorCode::Forbidden => "The caller may not perform this action",
+            ErrorCode::NotFound => "The resource doesn't exist or isn't visible to the caller",
+            ErrorCode::Conflict => "The request conflicts with the resource's current state",
+            ErrorCode::PreconditionFailed => {
+                "The resource changed since the version named in If-Match"
+            }
+            ErrorCode::RateLimited => "Too many requests; retry after the Retry-After delay",
+            ErrorCode::InternalError => "An unexpected server error",
+            ErrorCode::InvalidCredentials => "Email and password don't match an account",
+            ErrorCode::InvalidToken => "The access or refresh token is invalid or expired",
+            ErrorCode::MissingScope => "The token lacks a scope the endpoint requires",
+            ErrorCode::AdminRequired => "The endpoint is limited to administrators",
+            ErrorCode::EmailTaken => "An account with this email already exists",
+            ErrorCode::InvalidResetToken => {
+                "The password reset token is invalid, expired or already used"
+            }
+            ErrorCode::BudgetMonthConflict => "A budget already exists for this month and year",
+            ErrorCode::InsufficientAllocation => {
+                "The source category has less allocated than the amount to move"
+            }
+            ErrorCode::AllocationExceedsBudget => {
+                "In strict mode, category allocations may not exceed the budget's spending budget"
+            }
+            ErrorCode::CurrencyInactive => "The currency is unknown or not active",
+            ErrorCode::DuplicateHolding => "The account already holds this ticker",
+            ErrorCode::DuplicateInvitation => {
+                "The user already has a pending invitation to this budget"
+            }
+            ErrorCode::AlreadyRefunded => "The transaction has already been fully refunded",
+            ErrorCode::RefundExceedsRemaining => {
+                "The refund is larger than what remains refundable"
+            }
+            ErrorCode::TransactionHasRefunds => {
+                "The transaction has refunds, which must be deleted first"
+            }
+            ErrorCode::TransactionAlreadyPaysBill => "The transaction already pays a bill",
+            ErrorCode::TransactionNotPending => {
+                "The transaction was already approved or rejected"
+            }
+            ErrorCode::BillAlreadyPaid => "The bill occurrence was paid by another request",
+            ErrorCode::ReceiptAlreadyConfirmed => "The receipt draft was already confirmed",
+            ErrorCode::DuplicatePayee => "A payee with this name already exists",
+            ErrorCode::DuplicateTag => "A tag with this name already exists",
+            ErrorCode::InsufficientFunds => {
+                "The transaction would take an account that doesn't allow it below zero"
+            }
+            ErrorCode::ImportAlreadyReviewed => {
+                "The bank import was already approved or dismissed"
+            }
+            ErrorCode::PossibleDuplicate => {
+                "The bank import looks like an existing transaction; approve with allowDuplicate to import it anyway"
+            }
+            ErrorCode::AlreadyUndone => "The change has already been undone",
+            ErrorCode::NotUndoable => "The change can't be undone",
+            ErrorCode::UndoConflict => "Later changes prevent undoing this one",
+        }
+    }
+}
+
+impl AppError {
+    /// The stable code sent with this error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            AppError::ValidationError(_) | AppError::ValidationFailed(_) => {
+                ErrorCode::ValidationError
+            }
+            AppError::Malformed(_, _) => ErrorCode::MalformedRequest,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::Forbidden(_) => ErrorCode::Forbidden,
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::Conflict(_) => ErrorCode::Conflict,
+            AppError::InternalError(_) => ErrorCode::InternalError,
+            AppError::Coded(code, _) | AppError::Detailed(code, _, _) => *code,
+        }
+    }
 }
 
 /// Standard error response format
 #[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct ErrorResponse {
-    /// Error type code (e.g., "VALIDATION_ERROR", "NOT_FOUND")
-    #[schema(example = "VALIDATION_ERROR")]
+    /// General error class (e.g., "VALIDATION_ERROR", "NOT_FOUND")
+    #[schema(example = "CONFLICT")]
     pub error: String,
+    /// Stable code clients can branch on; equals `error` when nothing more
+    /// specific applies. See GET /errors/catalog.
+    #[schema(example = "BUDGET_MONTH_CONFLICT")]
+    pub code: String,
     /// Human-readable error message
-    #[schema(example = "Invalid input provided")]
+    #[schema(example = "Budget already exists for 1/2026")]
     pub message: String,
+    /// Structured details for codes that carry them (omitted otherwise)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<serde_json::Value>,
+    /// Each invalid request field (only for field validation failures)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<FieldError>>,
+    /// ID of the failed request, also sent as `X-Request-Id`; quote it when
+    /// reporting a problem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "3f2c6c1e-8a4b-4d0e-9b7a-2f1d5c9e8a10")]
+    pub request_id: Option<String>,
+}
+
+impl ErrorResponse {
+    pub fn new(code: ErrorCode, message: String) -> Self {
+        Self {
+            error: code.class().as_str().to_string(),
+            code: code.as_str().to_string(),
+            message,
+            details: None,
+            fields: None,
+            request_id: RequestId::current(),
+        }
+    }
+}
+
+/// One failed validation rule on a request field
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    /// Path of the field as sent, with nested fields and list items spelled out
+    #[schema(example = "splits[0].amount")]
+    pub field: String,
+    /// Rule that failed (e.g. "length", "range", "email", or a custom rule)
+    #[schema(example = "range")]
+    pub code: String,
+    /// Human-readable description of the problem
+    #[schema(example = "Amount must be positive")]
+    pub message: String,
+}
+
+impl FieldError {
+    /// Flatten validator errors into one entry per failed rule, sorted by field
+    pub fn collect(errors: &ValidationErrors) -> Vec<Self> {
+        let mut fields = Vec::new();
+        Self::collect_into(errors, "", &mut fields);
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        fields
+    }
+
+    fn collect_into(errors: &ValidationErrors, prefix: &str, out: &mut Vec<Self>) {
+        for (name, kind) in errors.errors() {
+            let path = if prefix.is_empty() {
+                camel_case(name)
+            } else {
+                format!("{prefix}.{}", camel_case(name))
+            };
+            match kind {
+                ValidationErrorsKind::Field(errors) => out.extend(errors.iter().map(|e| {
+                    FieldError {
+                        field: path.clone(),
+                        code: e.code.to_string(),
+                        message: e
+                            .message
+                            .as_ref()
+                            .map(|m| m.to_string())
+                            .unwrap_or_else(|| format!("{path} is invalid ({})", e.code)),
+                    }
+                })),
+                ValidationErrorsKind::Struct(errors) => Self::collect_into(errors, &path, out),
+                ValidationErrorsKind::List(items) => {
+                    for (index, errors) in items {
+                        Self::collect_into(errors, &format!("{path}[{index}]"), out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Request bodies use camelCase field names; validator reports Rust's
+fn camel_case(name: &str) -> String {
+    let mut parts = name.split('_');
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    out
+}
+
+/// An entry in the error code catalog
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorCatalogEntry {
+    #[schema(example = "BUDGET_MONTH_CONFLICT")]
+    pub code: ErrorCode,
+    /// General class, sent as `error`
+    #[schema(example = "CONFLICT")]
+    pub class: ErrorCode,
+    /// HTTP status the code is sent with
+    #[schema(example = 409)]
+    pub status: u16,
+    #[schema(example = "A budget already exists for this month and year")]
+    pub description: &'static str,
+}
+
+/// GET /errors/catalog - Every error code the API can return
+#[utoipa::path(
+    get,
+    path = "/errors/catalog",
+    tag = "Errors",
+    responses(
+        (status = 200, description = "Error codes with their status and meaning", body = Vec<ErrorCatalogEntry>)
+    )
+)]
+#[get("/errors/catalog")]
+pub async fn error_catalog() -> impl Responder {
+    HttpResponse::Ok().json(
+        ErrorCode::ALL
+            .iter()
+            .map(|&code| ErrorCatalogEntry {
+                code,
+                class: code.class(),
+                status: code.status().as_u16(),
+                description: code.description(),
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Register the error catalog route
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(error_catalog);
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::ValidationError(msg) => write!(f, "Validation error: {msg}"),
+            AppError::ValidationFailed(errors) => write!(f, "Validation error: {errors}"),
+            AppError::Malformed(msg, _) => write!(f, "Malformed request: {msg}"),
             AppError::Unauthorized(msg) => write!(f, "Unauthorized: {msg}"),
+            AppError::Forbidden(msg) => write!(f, "Forbidden: {msg}"),
             AppError::NotFound(msg) => write!(f, "Not found: {msg}"),
             AppError::Conflict(msg) => write!(f, "Conflict: {msg}"),
             AppError::InternalError(msg) => write!(f, "Internal error: {msg}"),
+            AppError::Coded(code, msg) | AppError::Detailed(code, msg, _) => {
+                write!(f, "{}: {msg}", code.as_str())
+            }
         }
     }
 }
 
 impl ResponseError for AppError {
+    fn status_code(&self) -> StatusCode {
+        self.code().status()
+    }
+
     fn error_response(&self) -> HttpResponse {
-        let (status, error_type, message) = match self {
-            AppError::ValidationError(msg) => (
-                actix_web::http::StatusCode::BAD_REQUEST,
-                "VALIDATION_ERROR",
-                msg.clone(),
-            ),
-            AppError::Unauthorized(msg) => (
-                actix_web::http::StatusCode::UNAUTHORIZED,
-                "UNAUTHORIZED",
-                msg.clone(),
-            ),
-            AppError::NotFound(msg) => (
-                actix_web::http::StatusCode::NOT_FOUND,
-                "NOT_FOUND",
-                msg.clone(),
-            ),
-            AppError::Conflict(msg) => (
-                actix_web::http::StatusCode::CONFLICT,
-                "CONFLICT",
-                msg.clone(),
-            ),
+        let message = match self {
             AppError::InternalError(msg) => {
                 // Log the actual error for debugging, but don't expose to client
                 error!("Internal error: {msg}");
-                (
-                    actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
-                    "INTERNAL_ERROR",
-                    "An internal error occurred".to_string(),
-                )
+                "An internal error occurred".to_string()
+            }
+            AppError::ValidationFailed(errors) => {
+                let fields = FieldError::collect(errors);
+                let mut names: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+                names.dedup();
+                match names.as_slice() {
+                    [_] => fields
+                        .iter()
+                        .map(|f| f.message.as_str())
+                        .collect::<Vec<_>>()
+                        .join("; "),
+                    _ => format!("Invalid fields: {}", names.join(", ")),
+                }
             }
+            AppError::ValidationError(msg)
+            | AppError::Malformed(msg, _)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg)
+            | AppError::Coded(_, msg)
+            | AppError::Detailed(_, msg, _) => msg.clone(),
         };
 
-        HttpResponse::build(status).json(ErrorResponse {
-            error: error_type.to_string(),
-            message,
-        })
+        let code = self.code();
+        let mut response = ErrorResponse::new(code, message);
+        match self {
+            AppError::Detailed(_, _, details) => response.details = Some(details.clone()),
+            AppError::ValidationFailed(errors) => {
+                response.fields = Some(FieldError::collect(errors))
+            }
+            AppError::Malformed(_, Some(field)) => response.fields = Some(vec![field.clone()]),
+            _ => {}
+        }
+        HttpResponse::build(code.status()).json(response)
+    }
+}
+
+impl From<ValidationErrors> for AppError {
+    fn from(errors: ValidationErrors) -> Self {
+        AppError::ValidationFailed(errors)
+    }
+}
+
+impl AppError {
+    /// Turn a serde failure into a 422, naming the field when the message
+    /// does (missing or unknown fields) or when exactly one sent value fits
+    /// `params` are the raw `(name, value)` pairs sent in `part` of the
+    /// request, whose values are each called a `param`
+    fn malformed(part: &str, param: &str, detail: &str, params: &[(String, String)]) -> Self {
+        let field = if let Some(name) = quoted_after(detail, "missing field ") {
+            Some(FieldError {
+                field: name.to_string(),
+                code: "required".to_string(),
+                message: format!("Missing {param} '{name}'"),
+            })
+        } else if let Some(name) = quoted_after(detail, "unknown field ") {
+            Some(FieldError {
+                field: name.to_string(),
+                code: "unknown".to_string(),
+                message: format!("Unknown {param} '{name}'"),
+            })
+        } else {
+            offending_param(detail, params).map(|name| FieldError {
+                field: name.to_string(),
+                code: "invalid".to_string(),
+                message: format!("Invalid {param} '{name}': {detail}"),
+            })
+        };
+
+        let message = match &field {
+            Some(field) => field.message.clone(),
+            None => format!("Invalid {part}: {detail}"),
+        };
+        AppError::Malformed(message, field)
     }
 }
 
+/// The text between backticks right after `prefix`, as serde quotes names
+fn quoted_after<'a>(detail: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = &detail[detail.find(prefix)? + prefix.len()..];
+    let rest = rest.strip_prefix('`')?;
+    rest.get(..rest.find('`')?)
+}
+
+/// The sent parameter a value error is about: the only one sent, the one whose
+/// value the message quotes (unknown enum variants), or the only one that isn't
+/// a UUID when UUID parsing failed
+fn offending_param<'a>(detail: &str, params: &'a [(String, String)]) -> Option<&'a str> {
+    let single = |mut matches: Vec<&'a (String, String)>| match matches.len() {
+        1 => matches.pop().map(|(name, _)| name.as_str()),
+        _ => None,
+    };
+
+    if params.len() == 1 {
+        return Some(params[0].0.as_str());
+    }
+    if let Some(value) = quoted_after(detail, "unknown variant ") {
+        return single(params.iter().filter(|(_, v)| v == value).collect());
+    }
+    if detail.contains("UUID") {
+        return single(
+            params
+                .iter()
+                .filter(|(_, v)| uuid::Uuid::parse_str(v).is_err())
+                .collect(),
+        );
+    }
+    None
+}
+
+/// `PathConfig` error handler: unparsable path segments (bad UUIDs, ...)
+pub fn path_error(err: PathError, req: &HttpRequest) -> actix_web::Error {
+    let PathError::Deserialize(inner) = &err else {
+        return err.into();
+    };
+    let params: Vec<(String, String)> = req
+        .match_info()
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    AppError::malformed("path", "path parameter", &inner.to_string(), &params).into()
+}
+
+/// `QueryConfig` error handler: unparsable or missing query parameters
+pub fn query_error(err: QueryPayloadError, req: &HttpRequest) -> actix_web::Error {
+    let QueryPayloadError::Deserialize(inner) = &err else {
+        return err.into();
+    };
+    let params = web::Query::<Vec<(String, String)>>::from_query(req.query_string())
+        .map(web::Query::into_inner)
+        .unwrap_or_default();
+    AppError::malformed("query", "query parameter", &inner.to_string(), &params).into()
+}
+
+/// `JsonConfig` error handler: bodies that aren't JSON or don't fit the
+/// expected shape. Size and content type errors keep their own status.
+pub fn json_error(err: JsonPayloadError, _: &HttpRequest) -> actix_web::Error {
+    let JsonPayloadError::Deserialize(inner) = &err else {
+        return err.into();
+    };
+    // serde_json appends the position, which means little to clients
+    let detail = inner.to_string();
+    let detail = match detail.rfind(" at line ") {
+        Some(at) => &detail[..at],
+        None => &detail,
+    };
+    AppError::malformed("request body", "field", detail, &[]).into()
+}
+
 // Convenience conversion from sqlx::Error
 impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
@@ -86,3 +637,111 @@ impl From<sqlx::Error> for AppError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_are_cataloged_with_their_class() {
+        for code in ErrorCode::ALL {
+            let serialized = serde_json::to_value(code).unwrap();
+            assert_eq!(serialized, code.as_str());
+            assert_eq!(code.class().class(), code.class());
+        }
+        assert_eq!(
+            ErrorCode::BudgetMonthConflict.status(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(ErrorCode::MissingScope.class(), ErrorCode::Forbidden);
+        assert_eq!(
+            AppError::NotFound("x".to_string()).code().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_field_errors_are_flattened_with_request_field_names() {
+        let mut nested = ValidationErrors::new();
+        nested.add("amount", validator::ValidationError::new("range"));
+        let mut errors = ValidationErrors::new();
+        errors.add(
+            "transaction_date",
+            validator::ValidationError::new("required")
+                .with_message("Transaction date is required".into()),
+        );
+        errors.errors_mut().insert(
+            "splits".into(),
+            ValidationErrorsKind::List([(1, Box::new(nested))].into()),
+        );
+
+        assert_eq!(
+            FieldError::collect(&errors),
+            vec![
+                FieldError {
+                    field: "splits[1].amount".to_string(),
+                    code: "range".to_string(),
+                    message: "splits[1].amount is invalid (range)".to_string(),
+                },
+                FieldError {
+                    field: "transactionDate".to_string(),
+                    code: "required".to_string(),
+                    message: "Transaction date is required".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_values_name_the_field_when_it_can_be_told() {
+        let params = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let field = |error: AppError| match error {
+            AppError::Malformed(_, field) => field.map(|f| (f.field, f.code)),
+            other => panic!("unexpected {other:?}"),
+        };
+        let named = |name: &str, code: &str| Some((name.to_string(), code.to_string()));
+
+        assert_eq!(
+            field(AppError::malformed(
+                "query",
+                "query parameter",
+                "missing field `month`",
+                &[]
+            )),
+            named("month", "required")
+        );
+        assert_eq!(
+            field(AppError::malformed(
+                "query",
+                "query parameter",
+                "unknown variant `foo`, expected one of `income`, `expense`",
+                &params(&[("month", "1"), ("type", "foo")]),
+            )),
+            named("type", "invalid")
+        );
+        let id = "3fa85f64-5717-4562-b3fc-2c963f66afa6";
+        assert_eq!(
+            field(AppError::malformed(
+                "path",
+                "path parameter",
+                "UUID parsing failed: invalid length",
+                &params(&[("budget_id", id), ("id", "abc")]),
+            )),
+            named("id", "invalid")
+        );
+        assert_eq!(
+            field(AppError::malformed(
+                "query",
+                "query parameter",
+                "invalid digit found in string",
+                &params(&[("month", "x"), ("year", "y")]),
+            )),
+            None
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::models::EventResponse;
+
+/// Events buffered per live stream before a slow one falls behind and has
+/// to catch up from the event log
+const BUS_CAPACITY: usize = 1024;
+
+/// Process-wide bus, created on first use
+static BUS: OnceLock<broadcast::Sender<BusEvent>> = OnceLock::new();
+
+/// A logged event and the user it belongs to
+#[derive(Debug, Clone)]
+pub struct BusEvent {
+    pub owner_id: Uuid,
+    pub event: EventResponse,
+}
+
+/// In-process broadcast of logged events to open live streams.
+///
+/// Only reaches clients connected to this instance; the event log stays the
+/// source of truth and streams read it to fill any gap.
+pub struct EventBus;
+
+impl EventBus {
+    fn sender() -> &'static broadcast::Sender<BusEvent> {
+        BUS.get_or_init(|| broadcast::channel(BUS_CAPACITY).0)
+    }
+
+    /// Hand an event to every open stream. Nobody listening is not an error.
+    pub fn publish(owner_id: Uuid, event: &EventResponse) {
+        let _ = Self::sender().send(BusEvent {
+            owner_id,
+            event: event.clone(),
+        });
+    }
+
+    /// Receive every event published from now on, for all users
+    pub fn subscribe() -> broadcast::Receiver<BusEvent> {
+        Self::sender().subscribe()
+    }
+}
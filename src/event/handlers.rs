@@ -0,0 +1,105 @@
+use actix_web::http::header::{self, CacheDirective};
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{EventListResponse, EventsQuery};
+use super::service::EventService;
+use super::stream::live_events;
+
+/// Header an `EventSource` sends on reconnect with the last frame ID it saw
+const LAST_EVENT_ID: &str = "Last-Event-ID";
+
+/// GET /events - Read the event log, oldest first
+///
+/// Consumers page with `cursor` and keep the last `nextCursor` so they can
+/// resume after an outage without missing events.
+#[utoipa::path(
+    get,
+    path = "/events",
+    tag = "Events",
+    params(EventsQuery),
+    responses(
+        (status = 200, description = "Page of events", body = EventListResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/events")]
+pub async fn list_events(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<EventsQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksRead)?;
+
+    query.validate()?;
+
+    let events = EventService::list(pool.get_ref(), auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(EventListResponse::from_page(events, query.limit)))
+}
+
+/// GET /events/stream - Live events as server-sent events
+///
+/// Each frame's `id` is the event's sequence. A client reconnecting with
+/// `Last-Event-ID` first receives what it missed from the event log.
+#[utoipa::path(
+    get,
+    path = "/events/stream",
+    tag = "Events",
+    params(
+        ("Last-Event-ID" = Option<i64>, Header, description = "Resume after this sequence")
+    ),
+    responses(
+        (status = 200, description = "Stream of `text/event-stream` frames, one per event", content_type = "text/event-stream", body = String),
+        (status = 400, description = "Last-Event-ID is not a sequence number", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/events/stream")]
+pub async fn stream_events(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksRead)?;
+
+    let resume_after = req
+        .headers()
+        .get(LAST_EVENT_ID)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .ok_or_else(|| {
+                    AppError::ValidationError("Last-Event-ID must be a sequence number".to_string())
+                })
+        })
+        .transpose()?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(header::CacheControl(vec![CacheDirective::NoCache]))
+        // Keep reverse proxies from buffering frames
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(live_events(
+            pool.get_ref().clone(),
+            auth.user_id,
+            resume_after,
+        )))
+}
+
+/// Register the event log routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_events).service(stream_events);
+}
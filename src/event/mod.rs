@@ -0,0 +1,7 @@
+pub mod bus;
+pub mod handlers;
+pub mod models;
+pub mod service;
+pub mod stream;
+
+pub use handlers::*;
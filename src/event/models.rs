@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::crypto::decrypt_field;
+use crate::errors::AppError;
+
+fn default_event_limit() -> i64 {
+    100
+}
+
+/// Database entity for the event log
+#[derive(Debug, Clone, FromRow)]
+pub struct DomainEvent {
+    pub id: Uuid,
+    pub sequence: i64,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub event_type: String,
+    /// JSON text, possibly encrypted
+    pub payload: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DomainEvent {
+    /// Decrypt and parse the payload for responses and deliveries
+    pub fn into_response(self) -> Result<EventResponse, AppError> {
+        let data = serde_json::from_str(&decrypt_field(&self.payload)?)
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        Ok(EventResponse {
+            id: self.id,
+            sequence: self.sequence,
+            event_type: self.event_type,
+            created_at: self.created_at,
+            data,
+        })
+    }
+}
+
+/// A logged domain event
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventResponse {
+    pub id: Uuid,
+    /// Position in the user's event log
+    #[schema(example = 1042)]
+    pub sequence: i64,
+    #[serde(rename = "type")]
+    #[schema(example = "transaction.created")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    /// Event-specific payload
+    pub data: Value,
+}
+
+impl EventResponse {
+    /// Server-sent events frame. The sequence is the frame ID, so a
+    /// reconnecting client's `Last-Event-ID` says where to resume.
+    pub fn to_sse_frame(&self) -> String {
+        format!(
+            "id: {}\nevent: {}\ndata: {}\n\n",
+            self.sequence, self.event_type, self.data
+        )
+    }
+}
+
+/// Query parameters for reading the event log
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct EventsQuery {
+    /// Only events recorded at or after this time
+    #[param(example = "2026-10-01T00:00:00Z")]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only events after this sequence number (use `nextCursor` to page)
+    #[validate(range(min = 0))]
+    #[param(example = 1042)]
+    pub cursor: Option<i64>,
+
+    /// Maximum results (1-500)
+    #[validate(range(min = 1, max = 500))]
+    #[serde(default = "default_event_limit")]
+    #[param(example = 100)]
+    pub limit: i64,
+}
+
+/// A page of the event log, oldest first
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EventListResponse {
+    pub data: Vec<EventResponse>,
+    /// Whether more events follow this page
+    pub has_more: bool,
+    /// Cursor for the next page; keep it to resume after an outage
+    #[schema(example = 1142)]
+    pub next_cursor: Option<i64>,
+}
+
+impl EventListResponse {
+    /// Build a page from up to `limit + 1` rows, the extra one signalling more
+    pub fn from_page(mut data: Vec<EventResponse>, limit: i64) -> Self {
+        let has_more = data.len() as i64 > limit;
+        data.truncate(limit as usize);
+        let next_cursor = data.last().map(|e| e.sequence);
+        Self {
+            data,
+            has_more,
+            next_cursor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(sequence: i64) -> EventResponse {
+        EventResponse {
+            id: Uuid::new_v4(),
+            sequence,
+            event_type: "transaction.created".to_string(),
+            created_at: Utc::now(),
+            data: Value::Null,
+        }
+    }
+
+    #[test]
+    fn test_event_page_reports_more_and_cursor() {
+        let page = EventListResponse::from_page(vec![event(4), event(5), event(6)], 2);
+        assert!(page.has_more);
+        assert_eq!(page.data.len(), 2);
+        assert_eq!(page.next_cursor, Some(5));
+
+        let last = EventListResponse::from_page(vec![event(7)], 2);
+        assert!(!last.has_more);
+        assert_eq!(last.next_cursor, Some(7));
+    }
+
+    #[test]
+    fn test_sse_frame_carries_sequence_type_and_single_line_data() {
+        let mut event = event(42);
+        event.data = serde_json::json!({ "description": "two\nlines" });
+
+        assert_eq!(
+            event.to_sse_frame(),
+            "id: 42\nevent: transaction.created\ndata: {\"description\":\"two\\nlines\"}\n\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::bus::EventBus;
+use super::models::{DomainEvent, EventResponse, EventsQuery};
+use crate::crypto::encrypt_field;
+use crate::errors::AppError;
+
+const EVENT_COLUMNS: &str = "id, sequence, owner_id, event_type, payload, created_at";
+
+/// Service layer for the append-only event log.
+pub struct EventService;
+
+impl EventService {
+    /// Append an event to the user's log and pass it on to live streams
+    pub async fn append(
+        pool: &PgPool,
+        owner_id: Uuid,
+        event_type: &str,
+        data: &Value,
+    ) -> Result<EventResponse, AppError> {
+        let event = sqlx::query_as::<_, DomainEvent>(&format!(
+            r#"
+            INSERT INTO events (owner_id, event_type, payload)
+            VALUES ($1, $2, $3)
+            RETURNING {EVENT_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(event_type)
+        .bind(encrypt_field(&data.to_string())?)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_response()?;
+
+        EventBus::publish(owner_id, &event);
+        Ok(event)
+    }
+
+    /// Events oldest first, fetching one extra row so callers can tell whether more follow
+    pub async fn list(
+        pool: &PgPool,
+        owner_id: Uuid,
+        query: &EventsQuery,
+    ) -> Result<Vec<EventResponse>, AppError> {
+        sqlx::query_as::<_, DomainEvent>(&format!(
+            r#"
+            SELECT {EVENT_COLUMNS}
+            FROM events
+            WHERE owner_id = $1
+              AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+              AND ($3::BIGINT IS NULL OR sequence > $3)
+            ORDER BY sequence
+            LIMIT $4
+            "#
+        ))
+        .bind(owner_id)
+        .bind(query.since)
+        .bind(query.cursor)
+        .bind(query.limit + 1)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(DomainEvent::into_response)
+        .collect()
+    }
+
+    /// Events of the given types (all when empty) recorded at or after `from`, oldest first
+    pub async fn since(
+        pool: &PgPool,
+        owner_id: Uuid,
+        from: DateTime<Utc>,
+        event_types: &[String],
+        limit: i64,
+    ) -> Result<Vec<EventResponse>, AppError> {
+        sqlx::query_as::<_, DomainEvent>(&format!(
+            r#"
+            SELECT {EVENT_COLUMNS}
+            FROM events
+            WHERE owner_id = $1
+              AND created_at >= $2
+              AND (cardinality($3::TEXT[]) = 0 OR event_type = ANY($3))
+            ORDER BY sequence
+            LIMIT $4
+            "#
+        ))
+        .bind(owner_id)
+        .bind(from)
+        .bind(event_types)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(DomainEvent::into_response)
+        .collect()
+    }
+}
@@ -0,0 +1,130 @@
+use actix_web::web::Bytes;
+use futures::stream::{self, Stream};
+use sqlx::PgPool;
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{interval, Interval, MissedTickBehavior};
+use tracing::warn;
+use uuid::Uuid;
+
+use super::bus::{BusEvent, EventBus};
+use super::models::{EventResponse, EventsQuery};
+use super::service::EventService;
+use crate::errors::AppError;
+use crate::shutdown;
+
+/// Comment frame sent while idle so proxies don't drop the connection
+const KEEP_ALIVE: &[u8] = b": keep-alive\n\n";
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Events read from the log per query while catching up
+const CATCH_UP_PAGE: i64 = 200;
+
+/// One user's live event stream.
+///
+/// Subscribes to the bus before reading the log, so nothing published while
+/// catching up is lost; the sequence of the last event sent drops anything
+/// delivered both ways.
+struct LiveStream {
+    pool: PgPool,
+    owner_id: Uuid,
+    rx: broadcast::Receiver<BusEvent>,
+    pending: VecDeque<EventResponse>,
+    /// Read the log after this sequence before taking events from the bus
+    catch_up_from: Option<i64>,
+    last_sequence: Option<i64>,
+    keep_alive: Interval,
+}
+
+impl LiveStream {
+    /// Next SSE frame; None ends the stream and the client reconnects
+    async fn next_frame(&mut self) -> Option<Bytes> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                if self
+                    .last_sequence
+                    .is_some_and(|last| event.sequence <= last)
+                {
+                    continue;
+                }
+                self.last_sequence = Some(event.sequence);
+                return Some(Bytes::from(event.to_sse_frame()));
+            }
+
+            if let Some(cursor) = self.catch_up_from {
+                if let Err(e) = self.read_log(cursor).await {
+                    warn!(owner_id = %self.owner_id, "Failed to read events for live stream: {}", e);
+                    return None;
+                }
+                continue;
+            }
+
+            tokio::select! {
+                received = self.rx.recv() => match received {
+                    Ok(bus_event) if bus_event.owner_id == self.owner_id => {
+                        self.pending.push_back(bus_event.event);
+                    }
+                    Ok(_) => {}
+                    // Fell behind the bus; whatever was dropped is in the log
+                    Err(RecvError::Lagged(_)) => match self.last_sequence {
+                        Some(last) => self.catch_up_from = Some(last),
+                        None => return None,
+                    },
+                    Err(RecvError::Closed) => return None,
+                },
+                _ = self.keep_alive.tick() => return Some(Bytes::from_static(KEEP_ALIVE)),
+                // End the stream so shutdown isn't held up; the client reconnects
+                // to another instance
+                _ = shutdown::cancelled() => return None,
+            }
+        }
+    }
+
+    /// Queue one page of logged events after `cursor`, finishing the catch-up
+    /// once the log has nothing more
+    async fn read_log(&mut self, cursor: i64) -> Result<(), AppError> {
+        let query = EventsQuery {
+            since: None,
+            cursor: Some(cursor),
+            limit: CATCH_UP_PAGE,
+        };
+        let mut events = EventService::list(&self.pool, self.owner_id, &query).await?;
+
+        if events.len() as i64 > CATCH_UP_PAGE {
+            events.truncate(CATCH_UP_PAGE as usize);
+            self.catch_up_from = events.last().map(|e| e.sequence);
+        } else {
+            self.catch_up_from = None;
+        }
+        self.pending.extend(events);
+        Ok(())
+    }
+}
+
+/// Frames for the user's events from now on, preceded by any logged after
+/// `resume_after` (the client's `Last-Event-ID`)
+pub fn live_events(
+    pool: PgPool,
+    owner_id: Uuid,
+    resume_after: Option<i64>,
+) -> impl Stream<Item = Result<Bytes, Infallible>> {
+    let mut keep_alive = interval(KEEP_ALIVE_INTERVAL);
+    keep_alive.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let live = LiveStream {
+        pool,
+        owner_id,
+        rx: EventBus::subscribe(),
+        pending: VecDeque::new(),
+        catch_up_from: resume_after,
+        last_sequence: resume_after,
+        keep_alive,
+    };
+
+    stream::unfold(live, |mut live| async move {
+        let frame = live.next_frame().await?;
+        Some((Ok(frame), live))
+    })
+}
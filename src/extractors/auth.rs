@@ -1,14 +1,63 @@
 use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
 use futures::future::{err, ok, Ready};
 use secrecy::Secret;
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::auth::decode_token;
-use crate::errors::AppError;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorCode};
 
-/// Extractor that validates JWT and provides the authenticated user's ID.
+/// Extractor that validates JWT and provides the authenticated user's ID and scopes.
 pub struct AuthenticatedUser {
     pub user_id: Uuid,
+    pub scopes: Vec<Scope>,
+}
+
+impl AuthenticatedUser {
+    /// Fail with 403 unless the token was granted the given scope
+    pub fn require_scope(&self, scope: Scope) -> Result<(), AppError> {
+        if self.scopes.contains(&scope) {
+            Ok(())
+        } else {
+            Err(AppError::Coded(
+                ErrorCode::MissingScope,
+                format!("Token is missing required scope '{}'", scope.as_str()),
+            ))
+        }
+    }
+
+    /// Whether this is a first-party session token holding every scope
+    pub fn is_first_party(&self) -> bool {
+        Scope::all().iter().all(|scope| self.scopes.contains(scope))
+    }
+
+    /// Fail with 403 unless the user is flagged as an administrator and
+    /// signed in with a first-party token; scoped tokens never act as admin
+    pub async fn require_admin(&self, pool: &PgPool) -> Result<(), AppError> {
+        if !self.is_first_party() {
+            return Err(AppError::Coded(
+                ErrorCode::AdminRequired,
+                "Admin access requires a first-party session token".to_string(),
+            ));
+        }
+
+        let is_admin = sqlx::query_scalar::<_, bool>("SELECT is_admin FROM users WHERE id = $1")
+            .bind(self.user_id)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?
+            .unwrap_or(false);
+
+        if is_admin {
+            Ok(())
+        } else {
+            Err(AppError::Coded(
+                ErrorCode::AdminRequired,
+                "Admin access required".to_string(),
+            ))
+        }
+    }
 }
 
 impl FromRequest for AuthenticatedUser {
@@ -45,6 +94,7 @@ impl FromRequest for AuthenticatedUser {
         match decode_token(&token, &jwt_secret) {
             Ok(claims) => ok(AuthenticatedUser {
                 user_id: claims.sub,
+                scopes: claims.scopes,
             }),
             Err(e) => err(e),
         }
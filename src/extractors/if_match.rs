@@ -0,0 +1,84 @@
+use actix_web::{dev::Payload, http::header, FromRequest, HttpRequest};
+use chrono::{DateTime, Utc};
+use futures::future::{ok, Ready};
+
+use crate::errors::{AppError, ErrorCode};
+
+/// Entity tag of a row version, derived from its `updated_at`
+pub fn etag(updated_at: &DateTime<Utc>) -> String {
+    format!("\"{}\"", updated_at.timestamp_micros())
+}
+
+/// Extractor for the `If-Match` header, used to reject updates to a row that
+/// changed since the client fetched it. Without the header every version
+/// matches, so clients that don't send it keep last-write-wins behavior.
+#[derive(Debug, Clone, Default)]
+pub struct IfMatch(Option<Vec<String>>);
+
+impl IfMatch {
+    /// Parse the header value: `*` or a comma-separated list of entity tags
+    pub fn parse(value: &str) -> Self {
+        Self(Some(
+            value
+                .split(',')
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
+        ))
+    }
+
+    /// Fail with 412 unless the row's current version is one the client
+    /// named. Weak tags never match, as If-Match uses strong comparison.
+    pub fn check(&self, updated_at: &DateTime<Utc>) -> Result<(), AppError> {
+        let Some(tags) = &self.0 else {
+            return Ok(());
+        };
+        let current = etag(updated_at);
+        if tags.iter().any(|tag| tag == "*" || *tag == current) {
+            return Ok(());
+        }
+        Err(AppError::Coded(
+            ErrorCode::PreconditionFailed,
+            "The resource was changed since it was fetched; reload it and retry".to_string(),
+        ))
+    }
+}
+
+impl FromRequest for IfMatch {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let if_match = req
+            .headers()
+            .get(header::IF_MATCH)
+            .map(|value| IfMatch::parse(value.to_str().unwrap_or_default()))
+            .unwrap_or_default();
+        ok(if_match)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_if_match_compares_strong_tags() {
+        let updated_at = Utc.timestamp_micros(1_767_225_600_123_456).unwrap();
+        let current = etag(&updated_at);
+        assert_eq!(current, "\"1767225600123456\"");
+
+        assert!(IfMatch::default().check(&updated_at).is_ok());
+        assert!(IfMatch::parse("*").check(&updated_at).is_ok());
+        assert!(IfMatch::parse(&format!("\"1\", {current}"))
+            .check(&updated_at)
+            .is_ok());
+        assert!(IfMatch::parse(&format!("W/{current}"))
+            .check(&updated_at)
+            .is_err());
+        assert!(IfMatch::parse("\"1767225600123455\"")
+            .check(&updated_at)
+            .is_err());
+    }
+}
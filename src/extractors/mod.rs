@@ -1,3 +1,6 @@
 mod auth;
+mod if_match;
+mod month_base;
 
 pub use auth::AuthenticatedUser;
+pub use if_match::{etag, IfMatch};
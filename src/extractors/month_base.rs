@@ -0,0 +1,31 @@
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
+use futures::future::{ready, Ready};
+use serde::Deserialize;
+
+use crate::budget::models::MonthBaseQuery;
+use crate::config::Config;
+use crate::errors;
+
+/// `monthBase` as sent by the client, before the server default applies
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RequestedMonthBase {
+    month_base: Option<i16>,
+}
+
+impl FromRequest for MonthBaseQuery {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let default = req
+            .app_data::<web::Data<Config>>()
+            .map_or(0, |config| config.budget_month_base);
+        let month_base = web::Query::<RequestedMonthBase>::from_query(req.query_string())
+            .map(|query| MonthBaseQuery {
+                month_base: query.month_base.unwrap_or(default),
+            })
+            .map_err(|e| errors::query_error(e, req));
+        ready(month_base)
+    }
+}
@@ -0,0 +1,147 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{
+    CreateFundingRuleDto, FundingRuleIdPath, FundingRuleResponse, UpdateFundingRuleDto,
+};
+use super::service::FundingService;
+
+/// GET /funding-rules - List funding rules in the order they apply
+#[utoipa::path(
+    get,
+    path = "/funding-rules",
+    tag = "Funding rules",
+    responses(
+        (status = 200, description = "List of funding rules", body = Vec<FundingRuleResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/funding-rules")]
+pub async fn list_funding_rules(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let rules = FundingService::list(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        rules
+            .into_iter()
+            .map(FundingRuleResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// POST /funding-rules - Create a rule splitting new income into a category or account
+#[utoipa::path(
+    post,
+    path = "/funding-rules",
+    tag = "Funding rules",
+    request_body = CreateFundingRuleDto,
+    responses(
+        (status = 201, description = "Funding rule created", body = FundingRuleResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/funding-rules")]
+pub async fn create_funding_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateFundingRuleDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+    body.validate_shape()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let rule = FundingService::create(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(FundingRuleResponse::from(rule)))
+}
+
+/// PATCH /funding-rules/{id} - Update a funding rule
+#[utoipa::path(
+    patch,
+    path = "/funding-rules/{id}",
+    tag = "Funding rules",
+    params(FundingRuleIdPath),
+    request_body = UpdateFundingRuleDto,
+    responses(
+        (status = 200, description = "Funding rule updated", body = FundingRuleResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Funding rule or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/funding-rules/{id}")]
+pub async fn update_funding_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<FundingRuleIdPath>,
+    body: web::Json<UpdateFundingRuleDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+    body.validate_shape()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let rule =
+        FundingService::update(pool.get_ref(), path.id, auth.user_id, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(FundingRuleResponse::from(rule)))
+}
+
+/// DELETE /funding-rules/{id} - Delete a funding rule
+#[utoipa::path(
+    delete,
+    path = "/funding-rules/{id}",
+    tag = "Funding rules",
+    params(FundingRuleIdPath),
+    responses(
+        (status = 200, description = "Funding rule deleted", body = DeleteResponse),
+        (status = 404, description = "Funding rule not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/funding-rules/{id}")]
+pub async fn delete_funding_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<FundingRuleIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    FundingService::delete(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Funding rule deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// Register the funding rule routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_funding_rules)
+        .service(create_funding_rule)
+        .service(update_funding_rule)
+        .service(delete_funding_rule);
+}
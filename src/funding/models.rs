@@ -0,0 +1,315 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::template::models::double_option;
+
+fn validate_percentage(percentage: &Decimal) -> Result<(), ValidationError> {
+    if *percentage <= Decimal::ZERO || *percentage > Decimal::from(100) {
+        return Err(ValidationError::new("percentage_out_of_range"));
+    }
+    Ok(())
+}
+
+fn validate_positive_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount <= Decimal::ZERO {
+        return Err(ValidationError::new("amount_must_be_positive"));
+    }
+    Ok(())
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Database entity for funding rules
+#[derive(Debug, Clone, FromRow)]
+pub struct FundingRule {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub name: String,
+    pub category_name: Option<String>,
+    pub target_account_id: Option<Uuid>,
+    pub percentage: Option<Decimal>,
+    pub fixed_amount: Option<Decimal>,
+    pub source_account_id: Option<Uuid>,
+    pub position: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl FundingRule {
+    /// The rule's share of an income: its percentage (rounded to cents) or its
+    /// fixed amount
+    pub fn share_of(&self, income: Decimal) -> Decimal {
+        match (self.percentage, self.fixed_amount) {
+            (Some(percentage), _) => (income * percentage / Decimal::from(100)).round_dp(2),
+            (None, Some(fixed_amount)) => fixed_amount,
+            (None, None) => Decimal::ZERO,
+        }
+    }
+}
+
+/// Split an income across rules in order. Each rule takes its share of the
+/// income, capped at what earlier rules left, so the splits never exceed it.
+pub fn plan_distribution(income: Decimal, rules: &[FundingRule]) -> Vec<Decimal> {
+    let mut remaining = income;
+    rules
+        .iter()
+        .map(|rule| {
+            let amount = rule.share_of(income).min(remaining);
+            remaining -= amount;
+            amount
+        })
+        .collect()
+}
+
+/// Funding rule returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRuleResponse {
+    pub id: Uuid,
+    /// Display name
+    #[schema(example = "Rent")]
+    pub name: String,
+    /// Category whose allocation is funded, matched by name in the income's budget
+    #[schema(example = "Rent")]
+    pub category_name: Option<String>,
+    /// Account the share is transferred to
+    pub target_account_id: Option<Uuid>,
+    /// Percentage of each income
+    #[schema(example = 20.00)]
+    pub percentage: Option<Decimal>,
+    /// Fixed amount taken from each income
+    #[schema(example = 500.00)]
+    pub fixed_amount: Option<Decimal>,
+    /// Only income into this account is split
+    pub source_account_id: Option<Uuid>,
+    /// Order the rule applies in; lower first
+    #[schema(example = 0)]
+    pub position: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<FundingRule> for FundingRuleResponse {
+    fn from(r: FundingRule) -> Self {
+        Self {
+            id: r.id,
+            name: r.name,
+            category_name: r.category_name,
+            target_account_id: r.target_account_id,
+            percentage: r.percentage,
+            fixed_amount: r.fixed_amount,
+            source_account_id: r.source_account_id,
+            position: r.position,
+            is_active: r.is_active,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+/// Request body for creating a funding rule. Give exactly one target
+/// (`categoryId` or `targetAccountId`) and exactly one share (`percentage` or
+/// `fixedAmount`).
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFundingRuleDto {
+    /// Display name (1-50 characters)
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    #[schema(example = "Rent")]
+    pub name: String,
+
+    /// Category whose name the rule funds in each income's budget
+    pub category_id: Option<Uuid>,
+
+    /// Account (such as a savings account) the share is transferred to
+    pub target_account_id: Option<Uuid>,
+
+    /// Percentage of each income (0-100)
+    #[validate(custom(
+        function = "validate_percentage",
+        message = "Percentage must be greater than 0 and at most 100"
+    ))]
+    #[schema(example = 20.00)]
+    pub percentage: Option<Decimal>,
+
+    /// Fixed amount taken from each income
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Fixed amount must be positive"
+    ))]
+    #[schema(example = 500.00)]
+    pub fixed_amount: Option<Decimal>,
+
+    /// Only split income into this account (optional)
+    pub source_account_id: Option<Uuid>,
+
+    /// Order the rule applies in; lower first (defaults to 0)
+    #[serde(default)]
+    #[schema(example = 0)]
+    pub position: i32,
+
+    /// Whether the rule applies (defaults to true)
+    #[serde(default = "default_active")]
+    pub is_active: bool,
+}
+
+impl CreateFundingRuleDto {
+    /// Exactly one target and exactly one share
+    pub fn validate_shape(&self) -> Result<(), ValidationError> {
+        if self.category_id.is_some() == self.target_account_id.is_some() {
+            return Err(ValidationError::new("exactly_one_target"));
+        }
+        if self.percentage.is_some() == self.fixed_amount.is_some() {
+            return Err(ValidationError::new("exactly_one_share"));
+        }
+        Ok(())
+    }
+}
+
+/// Request body for updating a funding rule (PATCH - all fields optional).
+/// Setting `percentage` clears `fixedAmount` and vice versa; the target can't
+/// be changed.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateFundingRuleDto {
+    /// Display name
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: Option<String>,
+
+    /// Percentage of each income (0-100)
+    #[validate(custom(
+        function = "validate_percentage",
+        message = "Percentage must be greater than 0 and at most 100"
+    ))]
+    pub percentage: Option<Decimal>,
+
+    /// Fixed amount taken from each income
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Fixed amount must be positive"
+    ))]
+    pub fixed_amount: Option<Decimal>,
+
+    /// Source account (use null to split income into any account)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub source_account_id: Option<Option<Uuid>>,
+
+    /// Order the rule applies in
+    pub position: Option<i32>,
+
+    /// Whether the rule applies
+    pub is_active: Option<bool>,
+}
+
+impl UpdateFundingRuleDto {
+    /// At most one share
+    pub fn validate_shape(&self) -> Result<(), ValidationError> {
+        if self.percentage.is_some() && self.fixed_amount.is_some() {
+            return Err(ValidationError::new("exactly_one_share"));
+        }
+        Ok(())
+    }
+}
+
+/// Path parameters for funding rule ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FundingRuleIdPath {
+    /// Funding rule UUID
+    pub id: Uuid,
+}
+
+/// One rule's part of an income, recorded in the audit log
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedFunding {
+    pub rule_id: Uuid,
+    pub amount: Decimal,
+    /// Category whose allocation was raised
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<Uuid>,
+    /// Transfer made to the target account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_id: Option<Uuid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(percentage: Option<Decimal>, fixed_amount: Option<Decimal>) -> FundingRule {
+        FundingRule {
+            id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Rule".to_string(),
+            category_name: Some("Rent".to_string()),
+            target_account_id: None,
+            percentage,
+            fixed_amount,
+            source_account_id: None,
+            position: 0,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_plan_distribution_caps_at_income() {
+        let rules = [
+            rule(None, Some(Decimal::from(500))),
+            rule(Some(Decimal::new(3333, 2)), None),
+            rule(None, Some(Decimal::from(400))),
+            rule(Some(Decimal::from(10)), None),
+        ];
+
+        assert_eq!(
+            plan_distribution(Decimal::from(1000), &rules),
+            vec![
+                Decimal::from(500),
+                Decimal::new(33330, 2),
+                Decimal::new(16670, 2),
+                Decimal::ZERO,
+            ]
+        );
+        assert_eq!(
+            plan_distribution(Decimal::new(9999, 2), &rules[1..2]),
+            vec![Decimal::new(3333, 2)]
+        );
+    }
+
+    #[test]
+    fn test_create_dto_requires_one_target_and_one_share() {
+        let dto = |value| serde_json::from_value::<CreateFundingRuleDto>(value).unwrap();
+        let id = Uuid::new_v4();
+
+        assert!(
+            dto(serde_json::json!({ "name": "Rent", "categoryId": id, "fixedAmount": 500 }))
+                .validate_shape()
+                .is_ok()
+        );
+        assert!(
+            dto(serde_json::json!({ "name": "Rent", "fixedAmount": 500 }))
+                .validate_shape()
+                .is_err()
+        );
+        assert!(dto(serde_json::json!({
+            "name": "Save",
+            "targetAccountId": id,
+            "percentage": 20,
+            "fixedAmount": 100
+        }))
+        .validate_shape()
+        .is_err());
+    }
+}
@@ -0,0 +1,222 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::models::{CreateFundingRuleDto, FundingRule, UpdateFundingRuleDto};
+use crate::category::service::CategoryService;
+use crate::errors::AppError;
+
+const FUNDING_RULE_COLUMNS: &str = "id, owner_id, name, category_name, target_account_id, percentage, fixed_amount, source_account_id, position, is_active, created_at, updated_at";
+
+/// Service layer for income funding rules.
+pub struct FundingService;
+
+impl FundingService {
+    /// List rules in the order they apply
+    pub async fn list(pool: &PgPool, owner_id: Uuid) -> Result<Vec<FundingRule>, AppError> {
+        sqlx::query_as::<_, FundingRule>(&format!(
+            "SELECT {FUNDING_RULE_COLUMNS} FROM funding_rules WHERE owner_id = $1 \
+             ORDER BY position, created_at"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Active rules that split income into the given account, in the order they apply
+    pub async fn active_rules(
+        conn: &mut PgConnection,
+        owner_id: Uuid,
+        source_account_id: Option<Uuid>,
+    ) -> Result<Vec<FundingRule>, AppError> {
+        sqlx::query_as::<_, FundingRule>(&format!(
+            "SELECT {FUNDING_RULE_COLUMNS} FROM funding_rules \
+             WHERE owner_id = $1 AND is_active \
+               AND (source_account_id IS NULL OR source_account_id = $2) \
+             ORDER BY position, created_at"
+        ))
+        .bind(owner_id)
+        .bind(source_account_id)
+        .fetch_all(conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Get a rule by ID
+    pub async fn get(
+        pool: &PgPool,
+        rule_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<FundingRule, AppError> {
+        sqlx::query_as::<_, FundingRule>(&format!(
+            "SELECT {FUNDING_RULE_COLUMNS} FROM funding_rules WHERE id = $1 AND owner_id = $2"
+        ))
+        .bind(rule_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Funding rule not found".to_string()))
+    }
+
+    /// Fail with 404 unless the account belongs to the user
+    async fn verify_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<(), AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(account_id)
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !exists {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// A rule can't transfer income back into the account it arrived in
+    fn check_accounts(
+        target_account_id: Option<Uuid>,
+        source_account_id: Option<Uuid>,
+    ) -> Result<(), AppError> {
+        if target_account_id.is_some() && target_account_id == source_account_id {
+            return Err(AppError::ValidationError(
+                "Target account must differ from the source account".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Create a rule
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateFundingRuleDto,
+    ) -> Result<FundingRule, AppError> {
+        let name = dto.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Name cannot be empty".to_string(),
+            ));
+        }
+        Self::check_accounts(dto.target_account_id, dto.source_account_id)?;
+
+        let category_name = match dto.category_id {
+            Some(category_id) => Some(
+                CategoryService::get_by_id(pool, category_id, owner_id)
+                    .await?
+                    .name,
+            ),
+            None => None,
+        };
+        for account_id in [dto.target_account_id, dto.source_account_id]
+            .into_iter()
+            .flatten()
+        {
+            Self::verify_account(pool, account_id, owner_id).await?;
+        }
+
+        sqlx::query_as::<_, FundingRule>(&format!(
+            r#"
+            INSERT INTO funding_rules
+                (owner_id, name, category_name, target_account_id, percentage, fixed_amount,
+                 source_account_id, position, is_active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING {FUNDING_RULE_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(&name)
+        .bind(&category_name)
+        .bind(dto.target_account_id)
+        .bind(dto.percentage)
+        .bind(dto.fixed_amount)
+        .bind(dto.source_account_id)
+        .bind(dto.position)
+        .bind(dto.is_active)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Update a rule (partial update - PATCH semantics)
+    pub async fn update(
+        pool: &PgPool,
+        rule_id: Uuid,
+        owner_id: Uuid,
+        dto: UpdateFundingRuleDto,
+    ) -> Result<FundingRule, AppError> {
+        let current = Self::get(pool, rule_id, owner_id).await?;
+
+        let name = match dto.name {
+            Some(n) => {
+                let trimmed = n.trim().to_string();
+                if trimmed.is_empty() {
+                    return Err(AppError::ValidationError(
+                        "Name cannot be empty".to_string(),
+                    ));
+                }
+                trimmed
+            }
+            None => current.name,
+        };
+        let (percentage, fixed_amount) = match (dto.percentage, dto.fixed_amount) {
+            (Some(percentage), _) => (Some(percentage), None),
+            (None, Some(fixed_amount)) => (None, Some(fixed_amount)),
+            (None, None) => (current.percentage, current.fixed_amount),
+        };
+        let source_account_id = dto.source_account_id.unwrap_or(current.source_account_id);
+        Self::check_accounts(current.target_account_id, source_account_id)?;
+        if let Some(account_id) = source_account_id {
+            if Some(account_id) != current.source_account_id {
+                Self::verify_account(pool, account_id, owner_id).await?;
+            }
+        }
+
+        sqlx::query_as::<_, FundingRule>(&format!(
+            r#"
+            UPDATE funding_rules SET
+                name = $3,
+                percentage = $4,
+                fixed_amount = $5,
+                source_account_id = $6,
+                position = $7,
+                is_active = $8
+            WHERE id = $1 AND owner_id = $2
+            RETURNING {FUNDING_RULE_COLUMNS}
+            "#
+        ))
+        .bind(rule_id)
+        .bind(owner_id)
+        .bind(&name)
+        .bind(percentage)
+        .bind(fixed_amount)
+        .bind(source_account_id)
+        .bind(dto.position.unwrap_or(current.position))
+        .bind(dto.is_active.unwrap_or(current.is_active))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Delete a rule
+    pub async fn delete(pool: &PgPool, rule_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM funding_rules WHERE id = $1 AND owner_id = $2")
+            .bind(rule_id)
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Funding rule not found".to_string()));
+        }
+        Ok(())
+    }
+}
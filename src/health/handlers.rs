@@ -0,0 +1,96 @@
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::db::metrics::PoolMetrics;
+
+use super::models::{
+    ComponentHealth, HealthConfig, LivenessResponse, ReadinessComponents, ReadinessResponse,
+};
+use super::service::HealthService;
+
+/// GET /health/live - Liveness probe
+///
+/// Touches no dependencies, so a database outage doesn't get the process restarted.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Process is up", body = LivenessResponse)
+    )
+)]
+#[get("/health/live")]
+pub async fn liveness() -> HttpResponse {
+    HttpResponse::Ok().json(LivenessResponse::new())
+}
+
+/// GET /health - Liveness probe, kept for probes configured before `/health/live`
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Process is up", body = LivenessResponse)
+    )
+)]
+#[get("/health")]
+pub async fn health_check() -> HttpResponse {
+    HttpResponse::Ok().json(LivenessResponse::new())
+}
+
+/// GET /health/ready - Readiness probe
+///
+/// Checks the database, applied migrations, connection pool and exchange rate
+/// age. Responds 503 only when a component is down; stale rates degrade the
+/// status but keep the instance in rotation.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Ready, possibly degraded", body = ReadinessResponse),
+        (status = 503, description = "A required component is down", body = ReadinessResponse)
+    )
+)]
+#[get("/health/ready")]
+pub async fn readiness(
+    pool: web::Data<PgPool>,
+    metrics: web::Data<PoolMetrics>,
+    config: web::Data<HealthConfig>,
+) -> HttpResponse {
+    let pool = pool.get_ref();
+    let stats = metrics.snapshot(pool);
+
+    let (database, migrations, exchange_rates) = tokio::join!(
+        HealthService::check_database(pool),
+        HealthService::check_migrations(pool),
+        HealthService::check_exchange_rates(pool, &config),
+    );
+    let pool_health = if stats.saturated {
+        ComponentHealth::down("Connection pool is saturated")
+    } else {
+        ComponentHealth::up()
+    };
+
+    let response = ReadinessResponse::new(
+        ReadinessComponents {
+            database,
+            migrations,
+            pool: pool_health,
+            exchange_rates,
+        },
+        stats,
+    );
+    if response.is_ready() {
+        HttpResponse::Ok().json(response)
+    } else {
+        HttpResponse::ServiceUnavailable().json(response)
+    }
+}
+
+/// Register the health probe routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(health_check)
+        .service(liveness)
+        .service(readiness);
+}
@@ -0,0 +1,242 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::HashSet;
+use utoipa::ToSchema;
+
+use crate::db::models::PoolStats;
+
+/// Thresholds for the readiness checks
+#[derive(Debug, Clone, Copy)]
+pub struct HealthConfig {
+    /// Rates are fetched only with an API key; without one their age isn't checked
+    pub exchange_rates_enabled: bool,
+    /// Latest fetched rates older than this report the component as degraded
+    pub exchange_rate_max_age: Duration,
+}
+
+impl Default for HealthConfig {
+    fn default() -> Self {
+        Self {
+            exchange_rates_enabled: false,
+            exchange_rate_max_age: Duration::hours(48),
+        }
+    }
+}
+
+/// State of one dependency checked for readiness
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentStatus {
+    /// Working normally
+    Up,
+    /// Working, but with stale or partial data; the instance still takes traffic
+    Degraded,
+    /// Not usable; the instance should not take traffic
+    Down,
+}
+
+/// Result of checking one dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ComponentHealth {
+    pub status: ComponentStatus,
+    /// Why the component isn't up, or what was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl ComponentHealth {
+    pub fn up() -> Self {
+        Self {
+            status: ComponentStatus::Up,
+            detail: None,
+        }
+    }
+
+    pub fn degraded(detail: impl Into<String>) -> Self {
+        Self {
+            status: ComponentStatus::Degraded,
+            detail: Some(detail.into()),
+        }
+    }
+
+    pub fn down(detail: impl Into<String>) -> Self {
+        Self {
+            status: ComponentStatus::Down,
+            detail: Some(detail.into()),
+        }
+    }
+
+    /// Compare the migrations this build ships with those recorded as applied
+    /// (`version`, `success`). Pending or failed migrations mean the schema
+    /// doesn't match the queries, so the instance isn't ready.
+    pub fn migrations(expected: &[i64], applied: &[(i64, bool)]) -> Self {
+        if let Some((version, _)) = applied.iter().find(|(_, success)| !success) {
+            return Self::down(format!("Migration {version} failed"));
+        }
+
+        let applied: HashSet<i64> = applied.iter().map(|(version, _)| *version).collect();
+        let pending: Vec<i64> = expected
+            .iter()
+            .copied()
+            .filter(|version| !applied.contains(version))
+            .collect();
+        match pending.as_slice() {
+            [] => Self::up(),
+            [version] => Self::down(format!("Migration {version} is pending")),
+            [.., latest] => Self::down(format!(
+                "{} migrations are pending, up to {latest}",
+                pending.len()
+            )),
+        }
+    }
+
+    /// Age of the latest fetched exchange rates against the allowed maximum.
+    /// Stale rates still convert, so this only degrades readiness.
+    pub fn exchange_rates(
+        latest_fetch: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+        max_age: Duration,
+    ) -> Self {
+        match latest_fetch {
+            None => Self::degraded("No exchange rates have been fetched"),
+            Some(fetched_at) if now - fetched_at > max_age => Self::degraded(format!(
+                "Latest exchange rates are {} hours old",
+                (now - fetched_at).num_hours()
+            )),
+            Some(_) => Self::up(),
+        }
+    }
+}
+
+/// Response for the liveness probe
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LivenessResponse {
+    /// Always `up`: answering at all means the process is alive
+    pub status: ComponentStatus,
+    /// Version of the running build
+    #[schema(example = "0.1.0")]
+    pub version: &'static str,
+}
+
+impl LivenessResponse {
+    pub fn new() -> Self {
+        Self {
+            status: ComponentStatus::Up,
+            version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
+
+impl Default for LivenessResponse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Overall readiness, from the worst component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReadinessStatus {
+    Ready,
+    Degraded,
+    Unavailable,
+}
+
+/// Dependencies checked for readiness
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadinessComponents {
+    /// Database answers queries
+    pub database: ComponentHealth,
+    /// Every migration this build ships has been applied
+    pub migrations: ComponentHealth,
+    /// Connection pool isn't saturated
+    pub pool: ComponentHealth,
+    /// Exchange rates were fetched recently
+    pub exchange_rates: ComponentHealth,
+}
+
+/// Response for the readiness probe
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub status: ReadinessStatus,
+    pub components: ReadinessComponents,
+    /// Connection pool state behind the `pool` component
+    #[schema(value_type = Object)]
+    pub pool: PoolStats,
+}
+
+impl ReadinessResponse {
+    pub fn new(components: ReadinessComponents, pool: PoolStats) -> Self {
+        let statuses = [
+            components.database.status,
+            components.migrations.status,
+            components.pool.status,
+            components.exchange_rates.status,
+        ];
+        let status = if statuses.contains(&ComponentStatus::Down) {
+            ReadinessStatus::Unavailable
+        } else if statuses.contains(&ComponentStatus::Degraded) {
+            ReadinessStatus::Degraded
+        } else {
+            ReadinessStatus::Ready
+        };
+
+        Self {
+            status,
+            components,
+            pool,
+        }
+    }
+
+    /// Only an unavailable instance is taken out of rotation
+    pub fn is_ready(&self) -> bool {
+        self.status != ReadinessStatus::Unavailable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrations_report_pending_and_failed_versions() {
+        let expected = [1, 2, 3];
+
+        assert_eq!(
+            ComponentHealth::migrations(&expected, &[(1, true), (2, true), (3, true)]),
+            ComponentHealth::up()
+        );
+        assert_eq!(
+            ComponentHealth::migrations(&expected, &[(1, true), (2, true)]),
+            ComponentHealth::down("Migration 3 is pending")
+        );
+        assert_eq!(
+            ComponentHealth::migrations(&expected, &[(1, true)]),
+            ComponentHealth::down("2 migrations are pending, up to 3")
+        );
+        assert_eq!(
+            ComponentHealth::migrations(&expected, &[(1, true), (2, false)]),
+            ComponentHealth::down("Migration 2 failed")
+        );
+    }
+
+    #[test]
+    fn test_exchange_rates_degrade_once_stale() {
+        let now = Utc::now();
+        let max_age = Duration::hours(48);
+
+        assert_eq!(
+            ComponentHealth::exchange_rates(Some(now - Duration::hours(47)), now, max_age),
+            ComponentHealth::up()
+        );
+        assert_eq!(
+            ComponentHealth::exchange_rates(Some(now - Duration::hours(72)), now, max_age),
+            ComponentHealth::degraded("Latest exchange rates are 72 hours old")
+        );
+        assert_eq!(
+            ComponentHealth::exchange_rates(None, now, max_age).status,
+            ComponentStatus::Degraded
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::db::MIGRATOR;
+
+use super::models::{ComponentHealth, HealthConfig};
+
+/// Service layer for the readiness checks. Each check reports its failure as
+/// a component status instead of an error, so one broken dependency doesn't
+/// hide the state of the others.
+pub struct HealthService;
+
+impl HealthService {
+    /// Whether the database answers a trivial query
+    pub async fn check_database(pool: &PgPool) -> ComponentHealth {
+        match sqlx::query("SELECT 1").execute(pool).await {
+            Ok(_) => ComponentHealth::up(),
+            Err(e) => ComponentHealth::down(format!("Database unreachable: {e}")),
+        }
+    }
+
+    /// Whether every migration embedded in this build has been applied
+    pub async fn check_migrations(pool: &PgPool) -> ComponentHealth {
+        let applied = sqlx::query_as::<_, (i64, bool)>(
+            "SELECT version, success FROM _sqlx_migrations ORDER BY version",
+        )
+        .fetch_all(pool)
+        .await;
+
+        match applied {
+            Ok(applied) => {
+                let expected: Vec<i64> = MIGRATOR
+                    .iter()
+                    .filter(|m| !m.migration_type.is_down_migration())
+                    .map(|m| m.version)
+                    .collect();
+                ComponentHealth::migrations(&expected, &applied)
+            }
+            Err(e) => ComponentHealth::down(format!("Migration state unavailable: {e}")),
+        }
+    }
+
+    /// Whether exchange rates were fetched within the configured maximum age
+    pub async fn check_exchange_rates(pool: &PgPool, config: &HealthConfig) -> ComponentHealth {
+        if !config.exchange_rates_enabled {
+            return ComponentHealth {
+                detail: Some("Rate fetching is not configured".to_string()),
+                ..ComponentHealth::up()
+            };
+        }
+
+        let latest = sqlx::query_scalar::<_, Option<DateTime<Utc>>>(
+            "SELECT MAX(fetched_at) FROM exchange_rates",
+        )
+        .fetch_one(pool)
+        .await;
+
+        match latest {
+            Ok(latest) => {
+                ComponentHealth::exchange_rates(latest, Utc::now(), config.exchange_rate_max_age)
+            }
+            Err(e) => ComponentHealth::degraded(format!("Exchange rate age unavailable: {e}")),
+        }
+    }
+}
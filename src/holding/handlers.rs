@@ -0,0 +1,187 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::{AccountIdPath, DeleteResponse};
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{
+    CreateHoldingDto, HoldingPath, HoldingResponse, HoldingsResponse, UpdateHoldingDto,
+    ValuationHistoryQuery, ValuationSnapshot,
+};
+use super::service::HoldingService;
+
+/// GET /accounts/{id}/holdings - Holdings of an investment account with gain/loss
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/holdings",
+    tag = "Holdings",
+    params(AccountIdPath),
+    responses(
+        (status = 200, description = "Holdings valued at their latest prices", body = HoldingsResponse),
+        (status = 400, description = "Not an investment account", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/holdings")]
+pub async fn list_holdings(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
+    let (account, holdings) = HoldingService::list(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(HoldingsResponse::build(account, holdings)))
+}
+
+/// POST /accounts/{id}/holdings - Add a holding
+#[utoipa::path(
+    post,
+    path = "/accounts/{id}/holdings",
+    tag = "Holdings",
+    params(AccountIdPath),
+    request_body = CreateHoldingDto,
+    responses(
+        (status = 201, description = "Holding added", body = HoldingResponse),
+        (status = 400, description = "Validation error or not an investment account", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 409, description = "The account already holds this ticker", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/accounts/{id}/holdings")]
+pub async fn create_holding(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    body: web::Json<CreateHoldingDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+
+    let holding = HoldingService::create(pool.get_ref(), path.id, auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(HoldingResponse::from(holding)))
+}
+
+/// PATCH /accounts/{id}/holdings/{holding_id} - Update a holding
+#[utoipa::path(
+    patch,
+    path = "/accounts/{id}/holdings/{holding_id}",
+    tag = "Holdings",
+    params(HoldingPath),
+    request_body = UpdateHoldingDto,
+    responses(
+        (status = 200, description = "Holding updated", body = HoldingResponse),
+        (status = 400, description = "Validation error or not an investment account", body = ErrorResponse),
+        (status = 404, description = "Account or holding not found", body = ErrorResponse),
+        (status = 409, description = "The account already holds this ticker", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/accounts/{id}/holdings/{holding_id}")]
+pub async fn update_holding(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<HoldingPath>,
+    body: web::Json<UpdateHoldingDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    body.validate()?;
+
+    let holding = HoldingService::update(
+        pool.get_ref(),
+        path.id,
+        path.holding_id,
+        auth.user_id,
+        &body,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(HoldingResponse::from(holding)))
+}
+
+/// DELETE /accounts/{id}/holdings/{holding_id} - Remove a holding
+#[utoipa::path(
+    delete,
+    path = "/accounts/{id}/holdings/{holding_id}",
+    tag = "Holdings",
+    params(HoldingPath),
+    responses(
+        (status = 200, description = "Holding removed", body = DeleteResponse),
+        (status = 400, description = "Not an investment account", body = ErrorResponse),
+        (status = 404, description = "Account or holding not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/accounts/{id}/holdings/{holding_id}")]
+pub async fn delete_holding(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<HoldingPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsWrite)?;
+
+    HoldingService::delete(pool.get_ref(), path.id, path.holding_id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Holding deleted successfully".to_string(),
+        id: path.holding_id,
+    }))
+}
+
+/// GET /accounts/{id}/valuations - Daily valuation history of an investment account
+#[utoipa::path(
+    get,
+    path = "/accounts/{id}/valuations",
+    tag = "Holdings",
+    params(AccountIdPath, ValuationHistoryQuery),
+    responses(
+        (status = 200, description = "Valuation snapshots, newest first", body = Vec<ValuationSnapshot>),
+        (status = 400, description = "Validation error or not an investment account", body = ErrorResponse),
+        (status = 404, description = "Account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/accounts/{id}/valuations")]
+pub async fn list_valuations(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<AccountIdPath>,
+    query: web::Query<ValuationHistoryQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+
+    query.validate()?;
+
+    let snapshots =
+        HoldingService::valuations(pool.get_ref(), path.id, auth.user_id, query.days).await?;
+
+    Ok(HttpResponse::Ok().json(snapshots))
+}
+
+/// Register the investment holding and valuation routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_holdings)
+        .service(create_holding)
+        .service(update_holding)
+        .service(delete_holding)
+        .service(list_valuations);
+}
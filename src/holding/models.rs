@@ -0,0 +1,355 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::account::models::Account;
+
+/// Tickers are 1-20 letters, digits or `.-^=:` (e.g. `BRK.B`, `^GSPC`, `EURUSD=X`)
+fn validate_ticker(ticker: &str) -> Result<(), ValidationError> {
+    let ticker = ticker.trim();
+    if ticker.is_empty()
+        || ticker.len() > 20
+        || !ticker
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ".-^=:".contains(c))
+    {
+        return Err(ValidationError::new("invalid_ticker"));
+    }
+    Ok(())
+}
+
+fn validate_positive_quantity(quantity: &Decimal) -> Result<(), ValidationError> {
+    if *quantity <= Decimal::ZERO {
+        return Err(ValidationError::new("quantity_must_be_positive"));
+    }
+    Ok(())
+}
+
+fn validate_non_negative(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount < Decimal::ZERO {
+        return Err(ValidationError::new("amount_must_not_be_negative"));
+    }
+    Ok(())
+}
+
+/// Canonical form of a ticker as stored
+pub fn normalize_ticker(ticker: &str) -> String {
+    ticker.trim().to_uppercase()
+}
+
+/// Gain as a percentage of cost, rounded to two places; undefined for zero cost
+fn gain_loss_percent(gain_loss: Decimal, cost_basis: Decimal) -> Option<Decimal> {
+    (!cost_basis.is_zero()).then(|| (gain_loss / cost_basis * Decimal::from(100)).round_dp(2))
+}
+
+/// Database entity for holdings
+#[derive(Debug, Clone, FromRow)]
+pub struct Holding {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub ticker: String,
+    pub quantity: Decimal,
+    pub cost_basis: Decimal,
+    pub price: Option<Decimal>,
+    pub priced_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Holding {
+    /// Quantity at the latest price; unknown until the holding is priced
+    pub fn market_value(&self) -> Option<Decimal> {
+        self.price.map(|price| (self.quantity * price).round_dp(2))
+    }
+}
+
+/// A holding with its valuation
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingResponse {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    #[schema(example = "VTI")]
+    pub ticker: String,
+    #[schema(example = 12.5)]
+    pub quantity: Decimal,
+    /// Total paid for the position
+    #[schema(example = 2500.00)]
+    pub cost_basis: Decimal,
+    /// Latest unit price, if known
+    #[schema(example = 245.10)]
+    pub price: Option<Decimal>,
+    /// When the price was last set
+    pub priced_at: Option<DateTime<Utc>>,
+    /// Quantity times price
+    #[schema(example = 3063.75)]
+    pub market_value: Option<Decimal>,
+    /// Market value minus cost basis
+    #[schema(example = 563.75)]
+    pub gain_loss: Option<Decimal>,
+    /// Gain or loss as a percentage of cost basis
+    #[schema(example = 22.55)]
+    pub gain_loss_percent: Option<Decimal>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Holding> for HoldingResponse {
+    fn from(holding: Holding) -> Self {
+        let market_value = holding.market_value();
+        let gain_loss = market_value.map(|value| value - holding.cost_basis);
+        Self {
+            id: holding.id,
+            account_id: holding.account_id,
+            ticker: holding.ticker,
+            quantity: holding.quantity,
+            cost_basis: holding.cost_basis,
+            price: holding.price,
+            priced_at: holding.priced_at,
+            market_value,
+            gain_loss,
+            gain_loss_percent: gain_loss
+                .and_then(|gain| gain_loss_percent(gain, holding.cost_basis)),
+            created_at: holding.created_at,
+            updated_at: holding.updated_at,
+        }
+    }
+}
+
+/// Response for GET /accounts/{id}/holdings
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldingsResponse {
+    pub account_id: Uuid,
+    /// Currency of cost bases, prices and totals
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// Uninvested cash: the account's balance
+    #[schema(example = 150.00)]
+    pub cash_balance: Decimal,
+    pub holdings: Vec<HoldingResponse>,
+    /// Market value of the priced holdings
+    #[schema(example = 3063.75)]
+    pub holdings_value: Decimal,
+    /// Cost basis of the priced holdings
+    #[schema(example = 2500.00)]
+    pub cost_basis: Decimal,
+    /// Gain or loss over the priced holdings
+    #[schema(example = 563.75)]
+    pub gain_loss: Decimal,
+    #[schema(example = 22.55)]
+    pub gain_loss_percent: Option<Decimal>,
+    /// Cash plus holdings value
+    #[schema(example = 3213.75)]
+    pub total_value: Decimal,
+    /// Holdings without a price, left out of the totals
+    #[schema(example = 0)]
+    pub unpriced_count: usize,
+}
+
+impl HoldingsResponse {
+    pub fn build(account: Account, holdings: Vec<Holding>) -> Self {
+        let priced = holdings.iter().filter(|h| h.price.is_some());
+        let holdings_value: Decimal = priced.clone().filter_map(Holding::market_value).sum();
+        let cost_basis: Decimal = priced.map(|h| h.cost_basis).sum();
+        let gain_loss = holdings_value - cost_basis;
+        let unpriced_count = holdings.iter().filter(|h| h.price.is_none()).count();
+
+        Self {
+            account_id: account.id,
+            currency: account.currency,
+            cash_balance: account.balance,
+            holdings: holdings.into_iter().map(HoldingResponse::from).collect(),
+            holdings_value,
+            cost_basis,
+            gain_loss,
+            gain_loss_percent: gain_loss_percent(gain_loss, cost_basis),
+            total_value: account.balance + holdings_value,
+            unpriced_count,
+        }
+    }
+}
+
+/// Request body for adding a holding
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateHoldingDto {
+    /// Ticker symbol, unique within the account (case-insensitive)
+    #[validate(custom(
+        function = "validate_ticker",
+        message = "Ticker must be 1-20 letters, digits or .-^=:"
+    ))]
+    #[schema(example = "VTI")]
+    pub ticker: String,
+
+    /// Units held (must be positive)
+    #[validate(custom(
+        function = "validate_positive_quantity",
+        message = "Quantity must be positive"
+    ))]
+    #[schema(example = 12.5)]
+    pub quantity: Decimal,
+
+    /// Total paid for the position, in the account currency
+    #[validate(custom(
+        function = "validate_non_negative",
+        message = "Cost basis cannot be negative"
+    ))]
+    #[schema(example = 2500.00)]
+    pub cost_basis: Decimal,
+
+    /// Unit price to use until the price provider sets one
+    #[validate(custom(
+        function = "validate_non_negative",
+        message = "Price cannot be negative"
+    ))]
+    #[schema(example = 245.10)]
+    pub price: Option<Decimal>,
+}
+
+/// Request body for updating a holding (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateHoldingDto {
+    #[validate(custom(
+        function = "validate_ticker",
+        message = "Ticker must be 1-20 letters, digits or .-^=:"
+    ))]
+    #[schema(example = "VTI")]
+    pub ticker: Option<String>,
+
+    #[validate(custom(
+        function = "validate_positive_quantity",
+        message = "Quantity must be positive"
+    ))]
+    #[schema(example = 15)]
+    pub quantity: Option<Decimal>,
+
+    #[validate(custom(
+        function = "validate_non_negative",
+        message = "Cost basis cannot be negative"
+    ))]
+    #[schema(example = 3100.00)]
+    pub cost_basis: Option<Decimal>,
+
+    /// Manual unit price; replaced on the next valuation when a price provider is configured
+    #[validate(custom(
+        function = "validate_non_negative",
+        message = "Price cannot be negative"
+    ))]
+    #[schema(example = 250.00)]
+    pub price: Option<Decimal>,
+}
+
+/// An investment account's value on one day, recorded by the valuation job
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValuationSnapshot {
+    /// Local date in the owner's timezone
+    pub valued_on: NaiveDate,
+    #[schema(example = 150.00)]
+    pub cash_balance: Decimal,
+    /// Market value of the holdings priced at the time
+    #[schema(example = 3063.75)]
+    pub holdings_value: Decimal,
+    /// Cost basis of those holdings
+    #[schema(example = 2500.00)]
+    pub cost_basis: Decimal,
+    /// Cash plus holdings value
+    #[schema(example = 3213.75)]
+    pub total_value: Decimal,
+}
+
+fn default_valuation_days() -> i32 {
+    30
+}
+
+/// Query parameters for valuation history
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct ValuationHistoryQuery {
+    /// Days of history, newest first (1-366, default 30)
+    #[validate(range(min = 1, max = 366, message = "days must be between 1 and 366"))]
+    #[serde(default = "default_valuation_days")]
+    #[param(example = 30)]
+    pub days: i32,
+}
+
+/// Path parameters for a holding
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HoldingPath {
+    /// Account UUID
+    pub id: Uuid,
+    /// Holding UUID
+    pub holding_id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(quantity: i64, cost_basis: i64, price: Option<i64>) -> Holding {
+        Holding {
+            id: Uuid::new_v4(),
+            account_id: Uuid::new_v4(),
+            ticker: "VTI".to_string(),
+            quantity: Decimal::from(quantity),
+            cost_basis: Decimal::from(cost_basis),
+            price: price.map(Decimal::from),
+            priced_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_holdings_valuation() {
+        let account = Account {
+            id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            name: "Brokerage".to_string(),
+            account_type: "investment".to_string(),
+            balance: Decimal::from(100),
+            color_hex: "#112233".to_string(),
+            currency: "USD".to_string(),
+            household_visible: true,
+            allow_negative_balance: true,
+            display_order: 0,
+            is_pinned: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let response = HoldingsResponse::build(
+            account,
+            vec![
+                holding(10, 800, Some(100)),
+                holding(4, 500, Some(100)),
+                holding(3, 90, None),
+            ],
+        );
+
+        assert_eq!(response.holdings[0].gain_loss, Some(Decimal::from(200)));
+        assert_eq!(
+            response.holdings[0].gain_loss_percent,
+            Some(Decimal::from(25))
+        );
+        assert_eq!(
+            response.holdings[1].gain_loss_percent,
+            Some(Decimal::from(-20))
+        );
+        assert_eq!(response.holdings[2].market_value, None);
+        // The unpriced holding is left out of the totals
+        assert_eq!(response.holdings_value, Decimal::from(1400));
+        assert_eq!(response.cost_basis, Decimal::from(1300));
+        assert_eq!(response.gain_loss, Decimal::from(100));
+        assert_eq!(response.total_value, Decimal::from(1500));
+        assert_eq!(response.unpriced_count, 1);
+
+        assert_eq!(normalize_ticker(" brk.b "), "BRK.B");
+        assert!(validate_ticker("^GSPC").is_ok());
+        assert!(validate_ticker("BAD TICKER").is_err());
+    }
+}
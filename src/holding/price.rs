@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use rust_decimal::Decimal;
+
+use crate::errors::AppError;
+
+/// Upper bound on a single price lookup
+const QUOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(serde::Deserialize)]
+struct Quote {
+    price: Decimal,
+}
+
+/// Pluggable source of security prices for the valuation job
+#[derive(Debug, Clone)]
+pub enum PriceProvider {
+    /// No automatic prices; holdings keep the price users enter
+    Manual,
+    /// HTTP endpoint answering `{"price": ...}` in the account currency.
+    /// `{ticker}` in the URL is replaced by the holding's ticker.
+    Http { url: String },
+}
+
+impl PriceProvider {
+    /// Latest unit price of a ticker; `None` when the provider doesn't supply prices
+    pub async fn quote(&self, ticker: &str) -> Result<Option<Decimal>, AppError> {
+        match self {
+            PriceProvider::Manual => Ok(None),
+            PriceProvider::Http { url } => {
+                let response = reqwest::Client::new()
+                    .get(url.replace("{ticker}", ticker))
+                    .timeout(QUOTE_TIMEOUT)
+                    .send()
+                    .await
+                    .map_err(|e| AppError::InternalError(format!("Price lookup failed: {e}")))?;
+
+                if !response.status().is_success() {
+                    return Err(AppError::InternalError(format!(
+                        "Price lookup for {} failed with status {}",
+                        ticker,
+                        response.status()
+                    )));
+                }
+
+                let quote = response
+                    .json::<Quote>()
+                    .await
+                    .map_err(|e| AppError::InternalError(format!("Price lookup failed: {e}")))?;
+                if quote.price < Decimal::ZERO {
+                    return Err(AppError::InternalError(format!(
+                        "Price lookup for {} returned a negative price",
+                        ticker
+                    )));
+                }
+
+                Ok(Some(quote.price))
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{
+    normalize_ticker, CreateHoldingDto, Holding, UpdateHoldingDto, ValuationSnapshot,
+};
+use crate::account::models::{Account, AccountType};
+use crate::account::service::AccountService;
+use crate::errors::{AppError, ErrorCode};
+
+const HOLDING_COLUMNS: &str =
+    "id, account_id, ticker, quantity, cost_basis, price, priced_at, created_at, updated_at";
+
+/// Map a write error, reporting a duplicate ticker as a conflict
+fn write_error(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Coded(
+            ErrorCode::DuplicateHolding,
+            "The account already holds this ticker".to_string(),
+        ),
+        _ => AppError::InternalError(e.to_string()),
+    }
+}
+
+/// Service layer for investment holdings.
+pub struct HoldingService;
+
+impl HoldingService {
+    /// Fail with 404 unless the user owns the account, and 400 unless it is
+    /// an investment account
+    async fn get_investment_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<Account, AppError> {
+        let account = AccountService::get_account_by_id(pool, account_id, owner_id).await?;
+        if account.account_type != AccountType::Investment.as_str() {
+            return Err(AppError::ValidationError(
+                "Holdings are only available for investment accounts".to_string(),
+            ));
+        }
+        Ok(account)
+    }
+
+    /// The account and its holdings, by ticker
+    pub async fn list(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<(Account, Vec<Holding>), AppError> {
+        let account = Self::get_investment_account(pool, account_id, owner_id).await?;
+
+        let holdings = sqlx::query_as::<_, Holding>(&format!(
+            "SELECT {HOLDING_COLUMNS} FROM account_holdings WHERE account_id = $1 ORDER BY ticker"
+        ))
+        .bind(account_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((account, holdings))
+    }
+
+    /// Add a holding to an investment account
+    pub async fn create(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        dto: &CreateHoldingDto,
+    ) -> Result<Holding, AppError> {
+        Self::get_investment_account(pool, account_id, owner_id).await?;
+
+        sqlx::query_as::<_, Holding>(&format!(
+            r#"
+            INSERT INTO account_holdings (account_id, ticker, quantity, cost_basis, price, priced_at)
+            VALUES ($1, $2, $3, $4, $5, CASE WHEN $5::NUMERIC IS NULL THEN NULL ELSE NOW() END)
+            RETURNING {HOLDING_COLUMNS}
+            "#
+        ))
+        .bind(account_id)
+        .bind(normalize_ticker(&dto.ticker))
+        .bind(dto.quantity)
+        .bind(dto.cost_basis)
+        .bind(dto.price)
+        .fetch_one(pool)
+        .await
+        .map_err(write_error)
+    }
+
+    /// Update a holding; a new price is stamped with the current time
+    pub async fn update(
+        pool: &PgPool,
+        account_id: Uuid,
+        holding_id: Uuid,
+        owner_id: Uuid,
+        dto: &UpdateHoldingDto,
+    ) -> Result<Holding, AppError> {
+        Self::get_investment_account(pool, account_id, owner_id).await?;
+
+        sqlx::query_as::<_, Holding>(&format!(
+            r#"
+            UPDATE account_holdings SET
+                ticker = COALESCE($3, ticker),
+                quantity = COALESCE($4, quantity),
+                cost_basis = COALESCE($5, cost_basis),
+                price = COALESCE($6, price),
+                priced_at = CASE WHEN $6::NUMERIC IS NULL THEN priced_at ELSE NOW() END
+            WHERE id = $1 AND account_id = $2
+            RETURNING {HOLDING_COLUMNS}
+            "#
+        ))
+        .bind(holding_id)
+        .bind(account_id)
+        .bind(dto.ticker.as_deref().map(normalize_ticker))
+        .bind(dto.quantity)
+        .bind(dto.cost_basis)
+        .bind(dto.price)
+        .fetch_optional(pool)
+        .await
+        .map_err(write_error)?
+        .ok_or_else(|| AppError::NotFound("Holding not found".to_string()))
+    }
+
+    /// Remove a holding
+    pub async fn delete(
+        pool: &PgPool,
+        account_id: Uuid,
+        holding_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<(), AppError> {
+        Self::get_investment_account(pool, account_id, owner_id).await?;
+
+        let result = sqlx::query("DELETE FROM account_holdings WHERE id = $1 AND account_id = $2")
+            .bind(holding_id)
+            .bind(account_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Holding not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Daily valuation snapshots from the last `days` days, newest first
+    pub async fn valuations(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+        days: i32,
+    ) -> Result<Vec<ValuationSnapshot>, AppError> {
+        Self::get_investment_account(pool, account_id, owner_id).await?;
+
+        sqlx::query_as::<_, ValuationSnapshot>(
+            r#"
+            SELECT v.valued_on, v.cash_balance, v.holdings_value, v.cost_basis,
+                   v.cash_balance + v.holdings_value AS total_value
+            FROM account_valuations v
+            INNER JOIN users u ON u.id = $2
+            WHERE v.account_id = $1
+              AND v.valued_on > (NOW() AT TIME ZONE u.timezone)::DATE - $3
+            ORDER BY v.valued_on DESC
+            "#,
+        )
+        .bind(account_id)
+        .bind(owner_id)
+        .bind(days)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Every ticker held in any account
+    pub async fn held_tickers(pool: &PgPool) -> Result<Vec<String>, AppError> {
+        sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT ticker FROM account_holdings ORDER BY ticker",
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Set the price of every holding of a ticker
+    pub async fn set_price(pool: &PgPool, ticker: &str, price: Decimal) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "UPDATE account_holdings SET price = $2, priced_at = NOW() WHERE ticker = $1",
+        )
+        .bind(ticker)
+        .bind(price)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Record today's valuation of every investment account, replacing an
+    /// earlier snapshot from the same local day. Unpriced holdings count at
+    /// zero. Returns the number of accounts valued.
+    pub async fn snapshot_valuations(pool: &PgPool) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO account_valuations (account_id, valued_on, cash_balance, holdings_value, cost_basis)
+            SELECT
+                a.id,
+                (NOW() AT TIME ZONE u.timezone)::DATE,
+                a.balance,
+                COALESCE(SUM(ROUND(h.quantity * h.price, 2)), 0),
+                COALESCE(SUM(h.cost_basis) FILTER (WHERE h.price IS NOT NULL), 0)
+            FROM accounts a
+            INNER JOIN users u ON u.id = a.owner_id
+            LEFT JOIN account_holdings h ON h.account_id = a.id
+            WHERE a.account_type = 'investment'
+            GROUP BY a.id, u.timezone
+            ON CONFLICT (account_id, valued_on) DO UPDATE SET
+                cash_balance = EXCLUDED.cash_balance,
+                holdings_value = EXCLUDED.holdings_value,
+                cost_basis = EXCLUDED.cost_basis,
+                created_at = NOW()
+            "#,
+        )
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+}
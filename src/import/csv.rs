@@ -0,0 +1,212 @@
+use super::models::ImportFormat;
+use super::parser::{parse_amount, parse_date, StatementEntry, StatementParser};
+use crate::errors::AppError;
+
+/// Header names recognized for each column, compared case-insensitively
+const DATE_COLUMNS: &[&str] = &[
+    "date",
+    "transaction date",
+    "posted date",
+    "posting date",
+    "booking date",
+];
+const AMOUNT_COLUMNS: &[&str] = &["amount", "transaction amount"];
+const DEBIT_COLUMNS: &[&str] = &["debit", "withdrawal", "withdrawals", "money out"];
+const CREDIT_COLUMNS: &[&str] = &["credit", "deposit", "deposits", "money in"];
+const DESCRIPTION_COLUMNS: &[&str] = &[
+    "description",
+    "payee",
+    "name",
+    "details",
+    "memo",
+    "narrative",
+];
+const ID_COLUMNS: &[&str] = &["id", "transaction id", "reference", "fitid"];
+
+/// Comma, semicolon or tab separated export with a header row naming a date
+/// column and either an amount column or debit and credit columns
+pub struct CsvParser;
+
+/// Where each field lives in a row
+struct Columns {
+    date: usize,
+    amount: AmountColumns,
+    description: Option<usize>,
+    id: Option<usize>,
+}
+
+enum AmountColumns {
+    /// One signed column
+    Signed(usize),
+    /// Money out and money in, both written as positive numbers
+    Split { debit: usize, credit: usize },
+}
+
+impl Columns {
+    fn find(header: &[String]) -> Option<Self> {
+        let position = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.contains(&h.trim().to_lowercase().as_str()))
+        };
+
+        let amount = match position(AMOUNT_COLUMNS) {
+            Some(column) => AmountColumns::Signed(column),
+            None => AmountColumns::Split {
+                debit: position(DEBIT_COLUMNS)?,
+                credit: position(CREDIT_COLUMNS)?,
+            },
+        };
+        Some(Self {
+            date: position(DATE_COLUMNS)?,
+            amount,
+            description: position(DESCRIPTION_COLUMNS),
+            id: position(ID_COLUMNS),
+        })
+    }
+}
+
+/// The separator used most in the header line
+fn delimiter(header: &str) -> char {
+    [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|d| header.matches(*d).count())
+        .unwrap_or(',')
+}
+
+/// Split one line into fields, honoring double quotes and `""` escapes
+fn split_fields(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+impl StatementParser for CsvParser {
+    fn format(&self) -> ImportFormat {
+        ImportFormat::Csv
+    }
+
+    fn detect(&self, contents: &str) -> bool {
+        contents
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .is_some_and(|header| Columns::find(&split_fields(header, delimiter(header))).is_some())
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<StatementEntry>, AppError> {
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        let Some((_, header)) = lines.next() else {
+            return Ok(Vec::new());
+        };
+        let delimiter = delimiter(header);
+        let columns = Columns::find(&split_fields(header, delimiter)).ok_or_else(|| {
+            AppError::ValidationError(
+                "CSV header needs a date column and an amount column (or debit and credit columns)"
+                    .to_string(),
+            )
+        })?;
+
+        lines
+            .map(|(index, line)| {
+                let fields = split_fields(line, delimiter);
+                let field = |column: usize| fields.get(column).map(|f| f.trim()).unwrap_or("");
+                let invalid = |what: &str| {
+                    AppError::ValidationError(format!("Line {}: invalid {what}", index + 1))
+                };
+
+                let date = parse_date(field(columns.date)).ok_or_else(|| invalid("date"))?;
+                let amount = match columns.amount {
+                    AmountColumns::Signed(column) => parse_amount(field(column)),
+                    AmountColumns::Split { debit, credit } => {
+                        match (parse_amount(field(debit)), parse_amount(field(credit))) {
+                            (None, None) => None,
+                            (debit, credit) => Some(
+                                credit.unwrap_or_default().abs() - debit.unwrap_or_default().abs(),
+                            ),
+                        }
+                    }
+                }
+                .ok_or_else(|| invalid("amount"))?;
+                let text = |column: Option<usize>| {
+                    column
+                        .map(field)
+                        .filter(|v| !v.is_empty())
+                        .map(str::to_string)
+                };
+
+                Ok(StatementEntry {
+                    external_id: text(columns.id),
+                    date,
+                    amount,
+                    description: text(columns.description),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_parses_signed_amount_column() {
+        let csv = "Date,Description,Amount,Reference\n\
+                   2026-01-15,\"Cafe, Corner\",-4.75,A1\n\
+                   \n\
+                   2026-01-16,Payroll,\"1,000.00\",A2\n";
+
+        let entries = CsvParser.parse(csv).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].description.as_deref(), Some("Cafe, Corner"));
+        assert_eq!(entries[0].amount, Decimal::new(-475, 2));
+        assert_eq!(entries[0].external_id.as_deref(), Some("A1"));
+        assert_eq!(entries[1].amount, Decimal::from(1000));
+        assert_eq!(
+            entries[1].date,
+            NaiveDate::from_ymd_opt(2026, 1, 16).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parses_debit_and_credit_columns() {
+        let csv = "Posted Date;Payee;Debit;Credit\n01/15/2026;Cafe;4.75;\n01/16/2026;Refund;;10\n";
+
+        let entries = CsvParser.parse(csv).unwrap();
+
+        assert_eq!(entries[0].amount, Decimal::new(-475, 2));
+        assert_eq!(entries[1].amount, Decimal::from(10));
+        assert_eq!(entries[0].external_id, None);
+    }
+
+    #[test]
+    fn test_rejects_bad_rows_with_line_number() {
+        assert!(!CsvParser.detect("Name,Value\nx,1\n"));
+
+        let error = CsvParser
+            .parse("Date,Amount\n2026-01-15,abc\n")
+            .unwrap_err();
+        assert!(error.to_string().contains("Line 2"));
+    }
+}
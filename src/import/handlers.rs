@@ -0,0 +1,75 @@
+use actix_web::{post, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+use super::models::{ImportStatementQuery, ImportStatementResponse};
+use super::service::ImportService;
+
+/// POST /transactions/import - Import a bank statement file (CSV, OFX or QIF) as pending transactions
+///
+/// The raw file is the request body. Entries imported before into the same
+/// account are skipped, so overlapping statements can be imported safely.
+#[utoipa::path(
+    post,
+    path = "/transactions/import",
+    tag = "Transactions",
+    params(ImportStatementQuery),
+    request_body(content = String, content_type = "text/plain", description = "Statement file contents"),
+    responses(
+        (status = 200, description = "Statement imported; new entries await approval", body = ImportStatementResponse),
+        (status = 400, description = "Unreadable file or no transactions in it", body = ErrorResponse),
+        (status = 404, description = "Category or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/import")]
+pub async fn import_statement(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<ImportStatementQuery>,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let contents = String::from_utf8_lossy(&body);
+    let contents = contents.trim_start_matches('\u{feff}');
+    let import =
+        ImportService::import_statement(pool.get_ref(), auth.user_id, contents, &query).await?;
+
+    let transactions: Vec<TransactionResponse> = import
+        .imported
+        .into_iter()
+        .map(TransactionResponse::from)
+        .collect();
+    for response in &transactions {
+        WebhookService::publish(
+            pool.get_ref(),
+            auth.user_id,
+            events::TRANSACTION_CREATED,
+            response,
+        )
+        .await;
+    }
+
+    Ok(HttpResponse::Ok().json(ImportStatementResponse {
+        format: import.format,
+        parsed: import.parsed,
+        imported: transactions.len(),
+        duplicates: import.duplicates,
+        skipped: import.skipped,
+        transactions,
+    }))
+}
+
+/// Register the statement import route
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(import_statement);
+}
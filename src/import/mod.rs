@@ -0,0 +1,9 @@
+pub mod csv;
+pub mod handlers;
+pub mod models;
+pub mod ofx;
+pub mod parser;
+pub mod qif;
+pub mod service;
+
+pub use handlers::*;
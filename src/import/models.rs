@@ -0,0 +1,115 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::parser::StatementEntry;
+use crate::transaction::models::TransactionResponse;
+
+/// Bank statement file formats that can be imported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// Comma, semicolon or tab separated values with a header row
+    Csv,
+    /// Open Financial Exchange (.ofx, .qfx)
+    Ofx,
+    /// Quicken Interchange Format
+    Qif,
+}
+
+/// Query parameters for importing a statement
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+#[into_params(rename_all = "camelCase")]
+pub struct ImportStatementQuery {
+    /// Account the statement belongs to
+    pub account_id: Uuid,
+    /// Category the imported transactions start in
+    pub category_id: Uuid,
+    /// File format (detected from the contents when omitted)
+    pub format: Option<ImportFormat>,
+}
+
+/// Outcome of a statement import
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportStatementResponse {
+    pub format: ImportFormat,
+    /// Entries read from the file
+    #[schema(example = 42)]
+    pub parsed: usize,
+    /// Entries added as pending transactions
+    #[schema(example = 40)]
+    pub imported: usize,
+    /// Entries already imported earlier, or repeated within the file
+    #[schema(example = 1)]
+    pub duplicates: usize,
+    /// Zero-amount entries left out
+    #[schema(example = 1)]
+    pub skipped: usize,
+    /// The new pending transactions, awaiting approval
+    pub transactions: Vec<TransactionResponse>,
+}
+
+impl StatementEntry {
+    /// Stable id for deduplicating re-imports: the bank's id when there is
+    /// one, otherwise a digest of the entry plus how many identical entries
+    /// came before it in the file
+    pub fn import_id(&self, occurrence: usize) -> String {
+        if let Some(id) = &self.external_id {
+            return format!("id:{id}");
+        }
+        let digest = Sha256::digest(format!(
+            "{}|{}|{}",
+            self.date,
+            self.amount.normalize(),
+            self.description.as_deref().unwrap_or_default().trim()
+        ));
+        format!("sha256:{}:{occurrence}", hex::encode(digest))
+    }
+
+    /// When the transaction is booked: midday UTC keeps it on the
+    /// statement's date, without landing in the future
+    pub fn booked_at(&self) -> DateTime<Utc> {
+        self.date
+            .and_time(NaiveTime::from_hms_opt(12, 0, 0).expect("valid time"))
+            .and_utc()
+            .min(Utc::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    fn entry(external_id: Option<&str>, amount: i64) -> StatementEntry {
+        StatementEntry {
+            external_id: external_id.map(str::to_string),
+            date: NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            amount: Decimal::from(amount),
+            description: Some("Cafe".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_import_id_prefers_bank_id() {
+        assert_eq!(entry(Some("F1"), -5).import_id(0), "id:F1");
+        assert_eq!(entry(Some("F1"), -5).import_id(3), "id:F1");
+    }
+
+    #[test]
+    fn test_import_id_digest_tells_repeats_apart() {
+        let first = entry(None, -5).import_id(0);
+        assert_eq!(first, entry(None, -5).import_id(0));
+        assert_ne!(first, entry(None, -5).import_id(1));
+        assert_ne!(first, entry(None, -6).import_id(0));
+
+        let mut scaled = entry(None, -5);
+        scaled.amount = Decimal::new(-500, 2);
+        assert_eq!(first, scaled.import_id(0));
+    }
+}
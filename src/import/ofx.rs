@@ -0,0 +1,134 @@
+use chrono::NaiveDate;
+
+use super::models::ImportFormat;
+use super::parser::{parse_amount, StatementEntry, StatementParser};
+use crate::errors::AppError;
+
+/// Open Financial Exchange statements, both the SGML flavor (1.x, unclosed
+/// leaf tags) and XML (2.x)
+pub struct OfxParser;
+
+/// Value of a leaf `<TAG>` within an upper-cased block: up to the next tag
+/// or line break, with the common entities decoded
+fn tag_value(block: &str, upper: &str, tag: &str) -> Option<String> {
+    let open = format!("<{tag}>");
+    let start = upper.find(&open)? + open.len();
+    let rest = &block[start..];
+    let end = rest.find(['<', '\n', '\r']).unwrap_or(rest.len());
+    let value = rest[..end]
+        .trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&");
+    (!value.is_empty()).then_some(value)
+}
+
+/// OFX dates start with `YYYYMMDD`, optionally followed by a time and zone
+fn parse_ofx_date(raw: &str) -> Option<NaiveDate> {
+    let digits = raw.get(..8)?;
+    if !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    NaiveDate::from_ymd_opt(
+        digits[..4].parse().ok()?,
+        digits[4..6].parse().ok()?,
+        digits[6..].parse().ok()?,
+    )
+}
+
+impl StatementParser for OfxParser {
+    fn format(&self) -> ImportFormat {
+        ImportFormat::Ofx
+    }
+
+    fn detect(&self, contents: &str) -> bool {
+        let head = contents.trim_start().to_ascii_uppercase();
+        head.starts_with("OFXHEADER") || head.contains("<OFX>")
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<StatementEntry>, AppError> {
+        // ASCII upper-casing keeps byte offsets, so tags are found in `upper`
+        // and values read from `contents`
+        let upper = contents.to_ascii_uppercase();
+        let starts: Vec<usize> = upper.match_indices("<STMTTRN>").map(|(i, _)| i).collect();
+
+        starts
+            .iter()
+            .enumerate()
+            .map(|(n, &start)| {
+                let end = starts.get(n + 1).copied().unwrap_or(contents.len());
+                let end = upper[start..end]
+                    .find("</STMTTRN>")
+                    .map_or(end, |close| start + close);
+                let (block, block_upper) = (&contents[start..end], &upper[start..end]);
+                let value = |tag: &str| tag_value(block, block_upper, tag);
+                let invalid = |what: &str| {
+                    AppError::ValidationError(format!("OFX transaction {}: invalid {what}", n + 1))
+                };
+
+                Ok(StatementEntry {
+                    external_id: value("FITID"),
+                    date: value("DTPOSTED")
+                        .as_deref()
+                        .and_then(parse_ofx_date)
+                        .ok_or_else(|| invalid("DTPOSTED"))?,
+                    amount: value("TRNAMT")
+                        .as_deref()
+                        .and_then(parse_amount)
+                        .ok_or_else(|| invalid("TRNAMT"))?,
+                    description: value("NAME").or_else(|| value("MEMO")),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_parses_sgml_statement() {
+        let ofx = "OFXHEADER:100\nDATA:OFXSGML\n\n<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><BANKTRANLIST>\n\
+                   <STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>20260115120000[-5:EST]\n<TRNAMT>-4.75\n<FITID>F1\n<NAME>Corner Cafe &amp; Bakery\n\
+                   <STMTTRN>\n<TRNTYPE>CREDIT\n<DTPOSTED>20260116\n<TRNAMT>1000.00\n<FITID>F2\n<MEMO>Payroll\n\
+                   </BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+
+        let entries = OfxParser.parse(ofx).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].external_id.as_deref(), Some("F1"));
+        assert_eq!(
+            entries[0].date,
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()
+        );
+        assert_eq!(entries[0].amount, Decimal::new(-475, 2));
+        assert_eq!(
+            entries[0].description.as_deref(),
+            Some("Corner Cafe & Bakery")
+        );
+        assert_eq!(entries[1].description.as_deref(), Some("Payroll"));
+    }
+
+    #[test]
+    fn test_parses_xml_statement() {
+        let ofx = r#"<?xml version="1.0"?><?OFX OFXHEADER="200"?><OFX><BANKTRANLIST><STMTTRN><DTPOSTED>20260201</DTPOSTED><TRNAMT>-12.50</TRNAMT><FITID>X9</FITID><NAME>Green Grocer</NAME></STMTTRN></BANKTRANLIST></OFX>"#;
+
+        assert!(OfxParser.detect(ofx));
+        let entries = OfxParser.parse(ofx).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].description.as_deref(), Some("Green Grocer"));
+        assert_eq!(entries[0].amount, Decimal::new(-1250, 2));
+    }
+
+    #[test]
+    fn test_reports_transactions_without_amount() {
+        let ofx = "<OFX><STMTTRN><DTPOSTED>20260201<FITID>1</STMTTRN></OFX>";
+        assert!(OfxParser
+            .parse(ofx)
+            .unwrap_err()
+            .to_string()
+            .contains("TRNAMT"));
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::csv::CsvParser;
+use super::models::ImportFormat;
+use super::ofx::OfxParser;
+use super::qif::QifParser;
+use crate::errors::AppError;
+
+/// One transaction read from a statement file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementEntry {
+    /// The bank's id for the entry (OFX FITID, a CSV id column, a QIF check number)
+    pub external_id: Option<String>,
+    pub date: NaiveDate,
+    /// Signed: negative for money leaving the account
+    pub amount: Decimal,
+    pub description: Option<String>,
+}
+
+/// A bank export format
+pub trait StatementParser {
+    fn format(&self) -> ImportFormat;
+
+    /// Whether the file looks like this format
+    fn detect(&self, contents: &str) -> bool;
+
+    /// Every entry in the file, in file order
+    fn parse(&self, contents: &str) -> Result<Vec<StatementEntry>, AppError>;
+}
+
+/// Parsers tried in order when detecting a file's format; CSV accepts
+/// anything, so it goes last
+const PARSERS: &[&dyn StatementParser] = &[&OfxParser, &QifParser, &CsvParser];
+
+/// The parser for `format`, or the first one that recognizes the file
+pub fn parser_for(contents: &str, format: Option<ImportFormat>) -> &'static dyn StatementParser {
+    match format {
+        Some(format) => *PARSERS
+            .iter()
+            .find(|p| p.format() == format)
+            .expect("a parser for every format"),
+        None => *PARSERS
+            .iter()
+            .find(|p| p.detect(contents))
+            .unwrap_or(&PARSERS[PARSERS.len() - 1]),
+    }
+}
+
+/// Parse an amount as written in statements: optional currency symbol,
+/// thousands separators, and a leading minus or parentheses for debits
+pub fn parse_amount(raw: &str) -> Option<Decimal> {
+    let trimmed = raw.trim();
+    let (negative, trimmed) = match trimmed.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(inner) => (true, inner),
+        None => (false, trimmed),
+    };
+    let cleaned: String = trimmed
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+        .collect();
+    if cleaned.is_empty() {
+        return None;
+    }
+    let amount: Decimal = cleaned.parse().ok()?;
+    Some(if negative { -amount.abs() } else { amount })
+}
+
+/// Parse a date in one of the layouts banks export: ISO, US (month first)
+/// or European with dots. Two-digit years are tried first, since `%Y` would
+/// read `26` as the year 26.
+pub fn parse_date(raw: &str) -> Option<NaiveDate> {
+    const FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%m/%d/%y", "%m/%d/%Y", "%d.%m.%Y"];
+
+    let raw = raw.trim();
+    FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount_formats() {
+        assert_eq!(parse_amount("-12.50"), Some(Decimal::new(-1250, 2)));
+        assert_eq!(parse_amount("$1,234.56"), Some(Decimal::new(123456, 2)));
+        assert_eq!(parse_amount("(45.00)"), Some(Decimal::new(-4500, 2)));
+        assert_eq!(parse_amount("+3"), Some(Decimal::from(3)));
+        assert_eq!(parse_amount(""), None);
+        assert_eq!(parse_amount("n/a"), None);
+    }
+
+    #[test]
+    fn test_parse_date_formats() {
+        let expected = NaiveDate::from_ymd_opt(2026, 1, 15);
+        for raw in [
+            "2026-01-15",
+            "2026/01/15",
+            "01/15/2026",
+            "1/15/26",
+            "15.01.2026",
+        ] {
+            assert_eq!(parse_date(raw), expected, "{raw}");
+        }
+        assert_eq!(parse_date("yesterday"), None);
+    }
+
+    #[test]
+    fn test_detects_format_from_contents() {
+        let ofx = "OFXHEADER:100\n<OFX><BANKMSGSRSV1></BANKMSGSRSV1></OFX>";
+        let qif = "!Type:Bank\nD01/15/2026\nT-4.75\n^\n";
+        let csv = "Date,Description,Amount\n2026-01-15,Cafe,-4.75\n";
+
+        assert_eq!(parser_for(ofx, None).format(), ImportFormat::Ofx);
+        assert_eq!(parser_for(qif, None).format(), ImportFormat::Qif);
+        assert_eq!(parser_for(csv, None).format(), ImportFormat::Csv);
+        assert_eq!(
+            parser_for(csv, Some(ImportFormat::Qif)).format(),
+            ImportFormat::Qif
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use super::models::ImportFormat;
+use super::parser::{parse_amount, parse_date, StatementEntry, StatementParser};
+use crate::errors::AppError;
+
+/// Quicken Interchange Format: one field per line, keyed by its first
+/// character, with `^` closing each record
+pub struct QifParser;
+
+/// Fields of the record being read
+#[derive(Default)]
+struct Record {
+    date: Option<String>,
+    amount: Option<String>,
+    payee: Option<String>,
+    memo: Option<String>,
+    number: Option<String>,
+}
+
+impl Record {
+    fn is_empty(&self) -> bool {
+        self.date.is_none() && self.amount.is_none() && self.payee.is_none()
+    }
+
+    fn into_entry(self, n: usize) -> Result<StatementEntry, AppError> {
+        let invalid =
+            |what: &str| AppError::ValidationError(format!("QIF record {n}: invalid {what}"));
+
+        Ok(StatementEntry {
+            external_id: self.number,
+            date: self
+                .date
+                .as_deref()
+                .and_then(parse_qif_date)
+                .ok_or_else(|| invalid("date"))?,
+            amount: self
+                .amount
+                .as_deref()
+                .and_then(parse_amount)
+                .ok_or_else(|| invalid("amount"))?,
+            description: self.payee.or(self.memo),
+        })
+    }
+}
+
+/// Quicken writes years after 2000 with an apostrophe (`1/15'26`) and may
+/// pad days with spaces
+fn parse_qif_date(raw: &str) -> Option<chrono::NaiveDate> {
+    let normalized: String = raw
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| if c == '\'' { '/' } else { c })
+        .collect();
+    parse_date(&normalized)
+}
+
+impl StatementParser for QifParser {
+    fn format(&self) -> ImportFormat {
+        ImportFormat::Qif
+    }
+
+    fn detect(&self, contents: &str) -> bool {
+        contents
+            .lines()
+            .find(|l| !l.trim().is_empty())
+            .is_some_and(|first| {
+                let first = first.trim_start().to_ascii_lowercase();
+                first.starts_with("!type:") || first.starts_with("!account")
+            })
+    }
+
+    fn parse(&self, contents: &str) -> Result<Vec<StatementEntry>, AppError> {
+        let mut entries = Vec::new();
+        let mut record = Record::default();
+        // Records after `!Account` describe the account, not transactions
+        let mut account_block = false;
+
+        for line in contents.lines() {
+            let line = line.trim_end();
+            let mut chars = line.chars();
+            let Some(code) = chars.next() else {
+                continue;
+            };
+            let value = || Some(chars.as_str().trim().to_string()).filter(|v| !v.is_empty());
+
+            match code {
+                '!' => {
+                    account_block = line.to_ascii_lowercase().starts_with("!account");
+                    record = Record::default();
+                }
+                '^' => {
+                    let finished = std::mem::take(&mut record);
+                    if account_block {
+                        account_block = false;
+                    } else if !finished.is_empty() {
+                        entries.push(finished.into_entry(entries.len() + 1)?);
+                    }
+                }
+                _ if account_block => {}
+                'D' => record.date = value(),
+                'T' => record.amount = value(),
+                'U' if record.amount.is_none() => record.amount = value(),
+                'P' => record.payee = value(),
+                'M' => record.memo = value(),
+                'N' => record.number = value(),
+                _ => {}
+            }
+        }
+        if !record.is_empty() && !account_block {
+            entries.push(record.into_entry(entries.len() + 1)?);
+        }
+
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_parses_bank_records() {
+        let qif = "!Account\nNChecking\nTBank\n^\n!Type:Bank\n\
+                   D1/15'26\nT-4.75\nPCorner Cafe\nLDining\n^\n\
+                   D01/16/2026\nU1,000.00\nT1,000.00\nMPayroll\nN1042\n^\n";
+
+        let entries = QifParser.parse(qif).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].date,
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap()
+        );
+        assert_eq!(entries[0].amount, Decimal::new(-475, 2));
+        assert_eq!(entries[0].description.as_deref(), Some("Corner Cafe"));
+        assert_eq!(entries[1].amount, Decimal::from(1000));
+        assert_eq!(entries[1].description.as_deref(), Some("Payroll"));
+        assert_eq!(entries[1].external_id.as_deref(), Some("1042"));
+    }
+
+    #[test]
+    fn test_keeps_last_record_without_terminator() {
+        let entries = QifParser.parse("!Type:Bank\nD2026-02-01\nT-3\n").unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let error = QifParser.parse("!Type:Bank\nDsoon\nT-3\n^\n").unwrap_err();
+        assert!(error.to_string().contains("QIF record 1"));
+    }
+}
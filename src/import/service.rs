@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{ImportFormat, ImportStatementQuery};
+use super::parser::parser_for;
+use crate::cache::ResponseCache;
+use crate::crypto::encrypt_optional;
+use crate::errors::AppError;
+use crate::payee::service::PayeeService;
+use crate::transaction::models::{Transaction, TransactionType};
+
+/// What importing one statement file did
+pub struct StatementImport {
+    pub format: ImportFormat,
+    pub parsed: usize,
+    pub imported: Vec<Transaction>,
+    pub duplicates: usize,
+    pub skipped: usize,
+}
+
+/// Service layer for importing bank statement files
+pub struct ImportService;
+
+impl ImportService {
+    /// Parse a statement file and add its entries to the account as pending
+    /// transactions, skipping entries imported before. Pending transactions
+    /// have no balance effects until approved, so the whole file goes in one
+    /// database transaction without touching balances.
+    pub async fn import_statement(
+        pool: &PgPool,
+        user_id: Uuid,
+        contents: &str,
+        query: &ImportStatementQuery,
+    ) -> Result<StatementImport, AppError> {
+        // 1. Parse in the given or detected format
+        let parser = parser_for(contents, query.format);
+        let entries = parser.parse(contents)?;
+        if entries.is_empty() {
+            return Err(AppError::ValidationError(
+                "No transactions found in the file".to_string(),
+            ));
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 2. Verify the user may book to the category and owns the account
+        let category_valid = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM categories c
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE c.id = $1 AND budget_access(b.id, $2, TRUE)
+            )
+            "#,
+        )
+        .bind(query.category_id)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !category_valid {
+            return Err(AppError::NotFound(
+                "Category not found or access denied".to_string(),
+            ));
+        }
+
+        let account_valid = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(query.account_id)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !account_valid {
+            return Err(AppError::NotFound(
+                "Account not found or access denied".to_string(),
+            ));
+        }
+
+        // 3. Insert each entry as pending, filed by the user's rules; the
+        // import id makes re-imports no-ops
+        let rules = PayeeService::rule_set(&mut tx, user_id).await?;
+        let parsed = entries.len();
+        let mut imported = Vec::new();
+        let mut duplicates = 0;
+        let mut skipped = 0;
+        // Identical entries in one file (two coffees on the same day) are told
+        // apart by how many came before
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+
+        for entry in entries {
+            if entry.amount.is_zero() {
+                skipped += 1;
+                continue;
+            }
+            let occurrence = occurrences.entry(entry.import_id(0)).or_default();
+            let import_id = entry.import_id(*occurrence);
+            *occurrence += 1;
+
+            let transaction_type = if entry.amount.is_sign_negative() {
+                TransactionType::Expense
+            } else {
+                TransactionType::Income
+            };
+
+            let (category_id, payee_id) = PayeeService::categorize(
+                &mut tx,
+                &rules,
+                query.category_id,
+                None,
+                entry.description.as_deref(),
+            )
+            .await?;
+
+            let transaction = sqlx::query_as::<_, Transaction>(
+                r#"
+                INSERT INTO transactions
+                    (category_id, account_id, amount, transaction_date, description,
+                     transaction_type, status, import_id, payee_id)
+                VALUES ($1, $2, $3, $4, $5, $6, 'pending', $7, $8)
+                ON CONFLICT (account_id, import_id) WHERE import_id IS NOT NULL DO NOTHING
+                RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                          transaction_type, reversal_of_id,
+                          reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                          tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+                "#,
+            )
+            .bind(category_id)
+            .bind(query.account_id)
+            .bind(entry.amount.abs())
+            .bind(entry.booked_at())
+            .bind(encrypt_optional(entry.description.as_deref())?)
+            .bind(transaction_type.as_str())
+            .bind(&import_id)
+            .bind(payee_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            match transaction {
+                Some(transaction) => imported.push(transaction.decrypted()?),
+                None => duplicates += 1,
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(StatementImport {
+            format: parser.format(),
+            parsed,
+            imported,
+            duplicates,
+            skipped,
+        })
+    }
+}
@@ -0,0 +1,274 @@
+use actix_web::{post, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{
+    BankSyncSummary, BillReminderSummary, ChargePostingSummary, DigestSummary,
+    IntegrityAuditMetrics, LoanPostingSummary, RefreshTokenCleanupResponse, RetentionRunQuery,
+    RetentionRunResponse, ValuationSummary,
+};
+use super::service::JobService;
+use crate::account::models::BalanceRecomputeSummary;
+use crate::bank_sync::provider::BankSyncProvider;
+use crate::holding::price::PriceProvider;
+use crate::mailer::Mailer;
+
+/// POST /admin/jobs/refresh-token-cleanup - Purge expired and revoked refresh tokens now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/refresh-token-cleanup",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Cleanup completed", body = RefreshTokenCleanupResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/refresh-token-cleanup")]
+pub async fn run_refresh_token_cleanup(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let config = config.jobs.refresh_token_cleanup;
+    let deleted_count =
+        JobService::purge_refresh_tokens(pool.get_ref(), config.retention_days).await?;
+
+    Ok(HttpResponse::Ok().json(RefreshTokenCleanupResponse {
+        message: "Refresh token cleanup completed".to_string(),
+        deleted_count,
+        retention_days: config.retention_days,
+    }))
+}
+
+/// POST /admin/jobs/retention - Apply data retention policies (use ?dryRun=true to preview)
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/retention",
+    tag = "Admin",
+    params(RetentionRunQuery),
+    responses(
+        (status = 200, description = "Retention policies applied", body = RetentionRunResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/retention")]
+pub async fn run_retention(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    query: web::Query<RetentionRunQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let policies =
+        JobService::run_retention(pool.get_ref(), &config.jobs.retention, query.dry_run).await?;
+
+    Ok(HttpResponse::Ok().json(RetentionRunResponse {
+        dry_run: query.dry_run,
+        policies,
+    }))
+}
+
+/// POST /admin/jobs/integrity-audit - Check every account balance against history now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/integrity-audit",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Accounts checked and those that drifted", body = BalanceRecomputeSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/integrity-audit")]
+pub async fn run_integrity_audit(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    metrics: web::Data<IntegrityAuditMetrics>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_integrity_audit(pool.get_ref()).await?;
+    metrics.record(&summary);
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// POST /admin/jobs/bill-reminders - Record due autopay payments and send bill notices now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/bill-reminders",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Autopay payments recorded and notices sent", body = BillReminderSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/bill-reminders")]
+pub async fn run_bill_reminders(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_bill_reminders(pool.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// POST /admin/jobs/loan-postings - Post loan payments that have come due now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/loan-postings",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Due loan payments posted", body = LoanPostingSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/loan-postings")]
+pub async fn run_loan_postings(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_loan_postings(pool.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// POST /admin/jobs/charge-postings - Post interest and fees for cycles that have ended now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/charge-postings",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Ended cycles posted", body = ChargePostingSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/charge-postings")]
+pub async fn run_charge_postings(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_charge_postings(pool.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// POST /admin/jobs/valuation - Reprice holdings and record investment account values now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/valuation",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Holdings repriced and daily valuations recorded", body = ValuationSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/valuation")]
+pub async fn run_valuation(
+    pool: web::Data<PgPool>,
+    provider: web::Data<PriceProvider>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_valuation(pool.get_ref(), provider.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// POST /admin/jobs/digests - Email spending digests whose period has ended now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/digests",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Due spending digests sent", body = DigestSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/digests")]
+pub async fn run_digests(
+    pool: web::Data<PgPool>,
+    mailer: web::Data<dyn Mailer>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_digests(pool.get_ref(), mailer.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// POST /admin/jobs/bank-sync - Pull new transactions from every linked institution now
+#[utoipa::path(
+    post,
+    path = "/admin/jobs/bank-sync",
+    tag = "Admin",
+    responses(
+        (status = 200, description = "Linked institutions synced", body = BankSyncSummary),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Admin access required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/admin/jobs/bank-sync")]
+pub async fn run_bank_sync(
+    pool: web::Data<PgPool>,
+    provider: web::Data<BankSyncProvider>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_admin(pool.get_ref()).await?;
+
+    let summary = JobService::run_bank_sync(pool.get_ref(), provider.get_ref()).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// Register the admin routes that run background jobs on demand
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(run_refresh_token_cleanup)
+        .service(run_retention)
+        .service(run_integrity_audit)
+        .service(run_bill_reminders)
+        .service(run_loan_postings)
+        .service(run_charge_postings)
+        .service(run_valuation)
+        .service(run_digests)
+        .service(run_bank_sync);
+}
@@ -0,0 +1,96 @@
+use sqlx::{Connection, PgConnection, PgPool};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::models::LeaderElectionConfig;
+use crate::shutdown;
+
+/// Whether this instance currently runs the scheduled jobs.
+///
+/// Leadership is a session-level Postgres advisory lock held on a connection
+/// detached from the pool. If the connection drops, Postgres releases the
+/// lock and another instance takes over on its next attempt.
+#[derive(Debug, Clone, Default)]
+pub struct Leadership {
+    is_leader: Arc<AtomicBool>,
+}
+
+impl Leadership {
+    pub fn is_leader(&self) -> bool {
+        self.is_leader.load(Ordering::Relaxed)
+    }
+
+    fn set(&self, leader: bool) {
+        if self.is_leader.swap(leader, Ordering::Relaxed) != leader {
+            if leader {
+                info!("Acquired scheduled job leadership");
+            } else {
+                warn!("Lost scheduled job leadership");
+            }
+        }
+    }
+}
+
+/// Try for leadership once, then keep contending in the background until
+/// shutdown, when leadership is given up. Jobs spawned after this returns see
+/// the outcome of the first attempt.
+pub async fn start_leader_election(pool: PgPool, config: LeaderElectionConfig) -> Leadership {
+    let leadership = Leadership::default();
+    let mut session = try_acquire(&pool, config.lock_key).await;
+    leadership.set(session.is_some());
+
+    let handle = leadership.clone();
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        ticker.tick().await;
+        while shutdown::tick(&mut ticker).await {
+            session = match session.take() {
+                Some(mut conn) => match sqlx::query("SELECT 1").execute(&mut conn).await {
+                    Ok(_) => Some(conn),
+                    Err(e) => {
+                        warn!("Leader session check failed: {}", e);
+                        None
+                    }
+                },
+                None => try_acquire(&pool, config.lock_key).await,
+            };
+            handle.set(session.is_some());
+        }
+
+        // Closing the session releases the lock, so another instance can take
+        // over on its next attempt instead of waiting for the connection to drop
+        if let Some(conn) = session {
+            let _ = conn.close().await;
+        }
+    });
+
+    leadership
+}
+
+/// Take the advisory lock on a dedicated connection, returning it when held
+async fn try_acquire(pool: &PgPool, lock_key: i64) -> Option<PgConnection> {
+    let mut conn = match pool.acquire().await {
+        Ok(conn) => conn.detach(),
+        Err(e) => {
+            warn!("Leader election could not get a connection: {}", e);
+            return None;
+        }
+    };
+
+    match sqlx::query_scalar::<_, bool>("SELECT pg_try_advisory_lock($1)")
+        .bind(lock_key)
+        .fetch_one(&mut conn)
+        .await
+    {
+        Ok(true) => Some(conn),
+        Ok(false) => {
+            let _ = conn.close().await;
+            None
+        }
+        Err(e) => {
+            warn!("Leader election lock attempt failed: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod handlers;
+pub mod leader;
+pub mod models;
+pub mod scheduler;
+pub mod service;
+
+pub use handlers::*;
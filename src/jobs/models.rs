@@ -0,0 +1,456 @@
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::account::models::BalanceRecomputeSummary;
+
+/// Default number of days expired/revoked refresh tokens are kept before purging
+const DEFAULT_REFRESH_TOKEN_RETENTION_DAYS: i32 = 30;
+/// Default interval between scheduled cleanup runs
+const DEFAULT_REFRESH_TOKEN_CLEANUP_INTERVAL_HOURS: u64 = 24;
+
+/// Settings for every scheduled job, read once at startup by
+/// [`crate::config::Config`]
+#[derive(Debug, Clone, Default)]
+pub struct JobsConfig {
+    pub leader: LeaderElectionConfig,
+    pub refresh_token_cleanup: RefreshTokenCleanupConfig,
+    pub retention: RetentionJobConfig,
+    pub summary_refresh: SummaryRefreshConfig,
+    pub integrity_audit: IntegrityAuditConfig,
+    pub bill_reminders: BillReminderConfig,
+    pub loan_postings: LoanPostingConfig,
+    pub charge_postings: ChargePostingConfig,
+    pub valuation: ValuationConfig,
+    pub digests: DigestConfig,
+    pub bank_sync: BankSyncConfig,
+}
+
+/// Settings for the refresh token cleanup job
+#[derive(Debug, Clone, Copy)]
+pub struct RefreshTokenCleanupConfig {
+    /// Tokens expired or revoked longer than this many days are deleted
+    pub retention_days: i32,
+    /// How often the scheduled job runs
+    pub interval: Duration,
+}
+
+impl Default for RefreshTokenCleanupConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_REFRESH_TOKEN_RETENTION_DAYS,
+            interval: Duration::from_secs(DEFAULT_REFRESH_TOKEN_CLEANUP_INTERVAL_HOURS * 3600),
+        }
+    }
+}
+
+/// Response for a manually triggered refresh token cleanup
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshTokenCleanupResponse {
+    /// Success message
+    #[schema(example = "Refresh token cleanup completed")]
+    pub message: String,
+    /// Number of refresh tokens deleted
+    #[schema(example = 42)]
+    pub deleted_count: u64,
+    /// Retention window applied, in days
+    #[schema(example = 30)]
+    pub retention_days: i32,
+}
+
+/// A table whose old rows are purged by the retention job.
+///
+/// `age_condition` is a SQL predicate on the table where `$1` is the retention
+/// window in days. New policies are registered in [`RETENTION_POLICIES`].
+#[derive(Debug)]
+pub struct RetentionPolicy {
+    /// Stable policy name used in reports and logs
+    pub name: &'static str,
+    /// Table the policy purges from
+    pub table: &'static str,
+    /// Predicate selecting rows older than `$1` days
+    pub age_condition: &'static str,
+    /// Environment variable overriding the retention window ("off" disables),
+    /// read by [`crate::config::Config`]
+    pub env_var: &'static str,
+    /// Retention window when the env var is unset; None leaves the policy off
+    pub default_days: Option<i32>,
+}
+
+/// Deleted transactions wait in the trash this long before they are purged
+pub const TRANSACTION_TRASH_RETENTION: RetentionPolicy = RetentionPolicy {
+    name: "transaction_trash",
+    table: "transactions",
+    age_condition: "deleted_at < NOW() - make_interval(days => $1)",
+    env_var: "RETENTION_TRANSACTION_TRASH_DAYS",
+    default_days: Some(30),
+};
+
+/// All retention policies known to the retention job
+pub const RETENTION_POLICIES: &[RetentionPolicy] = &[
+    RetentionPolicy {
+        name: "exchange_rates",
+        table: "exchange_rates",
+        age_condition: "effective_date < CURRENT_DATE - $1::int",
+        env_var: "RETENTION_EXCHANGE_RATES_DAYS",
+        // Historical rates back old conversions, so keep them unless configured
+        default_days: None,
+    },
+    TRANSACTION_TRASH_RETENTION,
+    RetentionPolicy {
+        name: "audit_log",
+        table: "audit_log",
+        age_condition: "created_at < NOW() - make_interval(days => $1)",
+        env_var: "RETENTION_AUDIT_LOG_DAYS",
+        default_days: Some(730),
+    },
+    RetentionPolicy {
+        name: "account_valuations",
+        table: "account_valuations",
+        age_condition: "valued_on < CURRENT_DATE - $1::int",
+        env_var: "RETENTION_ACCOUNT_VALUATIONS_DAYS",
+        // Daily snapshots only feed recent value charts
+        default_days: Some(730),
+    },
+];
+
+/// Settings for the scheduled retention job
+#[derive(Debug, Clone)]
+pub struct RetentionJobConfig {
+    /// How often the scheduled job runs
+    pub interval: Duration,
+    /// When true, scheduled runs only report what would be purged
+    pub dry_run: bool,
+    /// Configured windows by policy name; None disables the policy.
+    /// Policies missing here use their default.
+    pub windows: HashMap<&'static str, Option<i32>>,
+}
+
+impl Default for RetentionJobConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(24 * 3600),
+            dry_run: false,
+            windows: HashMap::new(),
+        }
+    }
+}
+
+impl RetentionJobConfig {
+    /// Retention window of a policy in days; None when the policy is disabled
+    pub fn retention_days(&self, policy: &RetentionPolicy) -> Option<i32> {
+        self.windows
+            .get(policy.name)
+            .copied()
+            .unwrap_or(policy.default_days)
+    }
+}
+
+/// Settings for the monthly summary refresh job
+#[derive(Debug, Clone, Copy)]
+pub struct SummaryRefreshConfig {
+    /// How often queued months are recomputed
+    pub interval: Duration,
+}
+
+impl Default for SummaryRefreshConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Settings for the balance integrity audit job
+#[derive(Debug, Clone, Copy)]
+pub struct IntegrityAuditConfig {
+    /// How often every account is checked
+    pub interval: Duration,
+}
+
+impl Default for IntegrityAuditConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(24 * 3600),
+        }
+    }
+}
+
+/// Settings for the bill reminder and autopay job
+#[derive(Debug, Clone, Copy)]
+pub struct BillReminderConfig {
+    /// How often due and overdue bills are checked; short enough to catch
+    /// each owner's day boundary
+    pub interval: Duration,
+}
+
+impl Default for BillReminderConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Outcome of one bill reminder run
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BillReminderSummary {
+    /// Autopay bills whose payment was recorded
+    pub autopaid: u64,
+    /// Autopay bills whose payment could not be recorded (e.g. no matching category)
+    pub autopay_failed: u64,
+    /// Reminders sent for bills coming due
+    pub reminded: u64,
+    /// Notices sent for bills that became overdue
+    pub overdue: u64,
+}
+
+/// Settings for the holdings valuation job
+#[derive(Debug, Clone, Copy)]
+pub struct ValuationConfig {
+    /// How often holdings are repriced and account values recorded
+    pub interval: Duration,
+}
+
+impl Default for ValuationConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Outcome of one valuation run
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ValuationSummary {
+    /// Tickers given a new price by the price provider
+    pub tickers_priced: u64,
+    /// Tickers whose price lookup failed; their holdings keep the last price
+    pub price_failures: u64,
+    /// Investment accounts whose daily snapshot was recorded
+    pub accounts_valued: u64,
+}
+
+/// Settings for the loan payment autoposting job
+#[derive(Debug, Clone, Copy)]
+pub struct LoanPostingConfig {
+    /// How often due loan payments are posted; short enough to catch each
+    /// owner's day boundary
+    pub interval: Duration,
+}
+
+impl Default for LoanPostingConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Outcome of one loan posting run
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoanPostingSummary {
+    /// Loan payments posted as transactions
+    pub posted: u64,
+    /// Loans whose due payment could not be posted (e.g. no matching category)
+    pub failed: u64,
+}
+
+/// Settings for the interest and fee autoposting job
+#[derive(Debug, Clone, Copy)]
+pub struct ChargePostingConfig {
+    /// How often ended cycles are posted; short enough to catch each owner's
+    /// day boundary
+    pub interval: Duration,
+}
+
+impl Default for ChargePostingConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Outcome of one interest and fee posting run
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChargePostingSummary {
+    /// Ended cycles whose interest and fee were posted
+    pub cycles_posted: u64,
+    /// Accounts whose ended cycle could not be posted (e.g. no matching category)
+    pub failed: u64,
+}
+
+/// Settings for the spending digest email job
+#[derive(Debug, Clone, Copy)]
+pub struct DigestConfig {
+    /// How often ended digest periods are checked; short enough to catch
+    /// each owner's week and month boundaries
+    pub interval: Duration,
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60 * 60),
+        }
+    }
+}
+
+/// Outcome of one spending digest run
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DigestSummary {
+    /// Digests emailed
+    pub sent: u64,
+    /// Digests that could not be compiled or sent; they are retried next run
+    pub failed: u64,
+}
+
+/// Settings for the bank sync job
+#[derive(Debug, Clone, Copy)]
+pub struct BankSyncConfig {
+    /// How often linked institutions are asked for new transactions
+    pub interval: Duration,
+}
+
+impl Default for BankSyncConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(240 * 60),
+        }
+    }
+}
+
+/// Outcome of one bank sync run
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BankSyncSummary {
+    /// Connections synced
+    pub connections_synced: u64,
+    /// Connections whose sync failed; the error is kept on the connection
+    pub failed: u64,
+    /// New transactions queued for review
+    pub imports_added: u64,
+}
+
+/// Outcome of the latest integrity audit, published on `GET /metrics`
+#[derive(Debug, Default)]
+pub struct IntegrityAuditMetrics {
+    runs: AtomicU64,
+    last_run_unix: AtomicI64,
+    last_checked: AtomicU64,
+    last_drifted: AtomicU64,
+}
+
+impl IntegrityAuditMetrics {
+    pub fn record(&self, summary: &BalanceRecomputeSummary) {
+        self.runs.fetch_add(1, Ordering::Relaxed);
+        self.last_run_unix
+            .store(Utc::now().timestamp(), Ordering::Relaxed);
+        self.last_checked
+            .store(summary.checked as u64, Ordering::Relaxed);
+        self.last_drifted
+            .store(summary.drifted.len() as u64, Ordering::Relaxed);
+    }
+
+    /// Render in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let metrics = [
+            (
+                "integrity_audit_runs_total",
+                "counter",
+                "Completed integrity audit runs",
+                self.runs.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "integrity_audit_last_run_timestamp_seconds",
+                "gauge",
+                "Unix time the last audit finished",
+                self.last_run_unix.load(Ordering::Relaxed),
+            ),
+            (
+                "integrity_audit_accounts_checked",
+                "gauge",
+                "Accounts checked by the last audit",
+                self.last_checked.load(Ordering::Relaxed) as i64,
+            ),
+            (
+                "integrity_audit_balance_discrepancies",
+                "gauge",
+                "Accounts whose balance drifted from history in the last audit",
+                self.last_drifted.load(Ordering::Relaxed) as i64,
+            ),
+        ];
+
+        metrics
+            .iter()
+            .map(|(name, kind, help, value)| {
+                format!("# HELP {name} {help}\n# TYPE {name} {kind}\n{name} {value}\n")
+            })
+            .collect()
+    }
+}
+
+/// Advisory lock key shared by every instance competing to run scheduled jobs
+const DEFAULT_JOB_LEADER_LOCK_KEY: i64 = 0x6e62_6a6f_6273;
+
+/// Settings for electing the instance that runs scheduled jobs
+#[derive(Debug, Clone, Copy)]
+pub struct LeaderElectionConfig {
+    /// Postgres advisory lock key held by the leader
+    pub lock_key: i64,
+    /// How often followers retry the lock and the leader checks its session
+    pub interval: Duration,
+}
+
+impl Default for LeaderElectionConfig {
+    fn default() -> Self {
+        Self {
+            lock_key: DEFAULT_JOB_LEADER_LOCK_KEY,
+            interval: Duration::from_secs(15),
+        }
+    }
+}
+
+/// Query parameters for triggering the retention job
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionRunQuery {
+    /// Only count matching rows without deleting them
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Outcome of a single retention policy
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicyReport {
+    /// Policy name
+    #[schema(example = "exchange_rates")]
+    pub policy: String,
+    /// Retention window in days (null when the policy is disabled)
+    #[schema(example = 730)]
+    pub retention_days: Option<i32>,
+    /// Rows deleted, or rows that would be deleted in a dry run
+    #[schema(example = 1200)]
+    pub affected_rows: u64,
+}
+
+/// Response for a retention job run
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionRunResponse {
+    /// Whether rows were only counted
+    pub dry_run: bool,
+    /// Per-policy results
+    pub policies: Vec<RetentionPolicyReport>,
+}
@@ -0,0 +1,296 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use super::leader::Leadership;
+use super::models::{
+    BankSyncConfig, BillReminderConfig, ChargePostingConfig, DigestConfig, IntegrityAuditConfig,
+    IntegrityAuditMetrics, LoanPostingConfig, RefreshTokenCleanupConfig, RetentionJobConfig,
+    SummaryRefreshConfig, ValuationConfig,
+};
+use super::service::JobService;
+use crate::bank_sync::provider::BankSyncProvider;
+use crate::holding::price::PriceProvider;
+use crate::mailer::Mailer;
+use crate::shutdown;
+use crate::summary::service::SummaryService;
+
+/// Spawn the periodic refresh token cleanup on the Tokio runtime.
+/// The first run happens immediately, then once per configured interval.
+/// Only the instance holding job leadership runs it. Like every job here, it
+/// stops at shutdown once its current run is done.
+pub fn spawn_refresh_token_cleanup(
+    pool: PgPool,
+    config: RefreshTokenCleanupConfig,
+    leadership: Leadership,
+) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::purge_refresh_tokens(&pool, config.retention_days).await {
+                Ok(deleted) => info!(
+                    deleted,
+                    retention_days = config.retention_days,
+                    "Refresh token cleanup completed"
+                ),
+                Err(e) => error!("Refresh token cleanup failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the periodic data retention job on the Tokio runtime.
+pub fn spawn_retention(pool: PgPool, config: RetentionJobConfig, leadership: Leadership) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_retention(&pool, &config, config.dry_run).await {
+                Ok(reports) => {
+                    for report in reports.iter().filter(|r| r.retention_days.is_some()) {
+                        info!(
+                            policy = %report.policy,
+                            affected_rows = report.affected_rows,
+                            dry_run = config.dry_run,
+                            "Retention policy applied"
+                        );
+                    }
+                }
+                Err(e) => error!("Retention job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the monthly summary refresh on the Tokio runtime.
+/// Each run recomputes only the months queued since the previous one.
+pub fn spawn_summary_refresh(pool: PgPool, config: SummaryRefreshConfig, leadership: Leadership) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match SummaryService::refresh_dirty(&pool, None).await {
+                Ok(0) => {}
+                Ok(months) => info!(months, "Monthly summaries refreshed"),
+                Err(e) => error!("Monthly summary refresh failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the periodic balance integrity audit on the Tokio runtime.
+pub fn spawn_integrity_audit(
+    pool: PgPool,
+    config: IntegrityAuditConfig,
+    leadership: Leadership,
+    metrics: Arc<IntegrityAuditMetrics>,
+) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_integrity_audit(&pool).await {
+                Ok(summary) => {
+                    metrics.record(&summary);
+                    if summary.drifted.is_empty() {
+                        info!(checked = summary.checked, "Integrity audit found no drift");
+                    } else {
+                        warn!(
+                            checked = summary.checked,
+                            drifted = summary.drifted.len(),
+                            "Integrity audit found balance drift"
+                        );
+                    }
+                }
+                Err(e) => error!("Integrity audit failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the bill reminder and autopay job on the Tokio runtime.
+pub fn spawn_bill_reminders(pool: PgPool, config: BillReminderConfig, leadership: Leadership) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_bill_reminders(&pool).await {
+                Ok(summary) => {
+                    if summary.autopaid
+                        + summary.autopay_failed
+                        + summary.reminded
+                        + summary.overdue
+                        > 0
+                    {
+                        info!(
+                            autopaid = summary.autopaid,
+                            autopay_failed = summary.autopay_failed,
+                            reminded = summary.reminded,
+                            overdue = summary.overdue,
+                            "Bill reminders processed"
+                        );
+                    }
+                }
+                Err(e) => error!("Bill reminder job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the loan payment autoposting job on the Tokio runtime.
+pub fn spawn_loan_postings(pool: PgPool, config: LoanPostingConfig, leadership: Leadership) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_loan_postings(&pool).await {
+                Ok(summary) => {
+                    if summary.posted + summary.failed > 0 {
+                        info!(
+                            posted = summary.posted,
+                            failed = summary.failed,
+                            "Loan payments posted"
+                        );
+                    }
+                }
+                Err(e) => error!("Loan posting job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the interest and fee autoposting job on the Tokio runtime.
+pub fn spawn_charge_postings(pool: PgPool, config: ChargePostingConfig, leadership: Leadership) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_charge_postings(&pool).await {
+                Ok(summary) => {
+                    if summary.cycles_posted + summary.failed > 0 {
+                        info!(
+                            cycles_posted = summary.cycles_posted,
+                            failed = summary.failed,
+                            "Interest and fees posted"
+                        );
+                    }
+                }
+                Err(e) => error!("Charge posting job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the holdings valuation job on the Tokio runtime.
+pub fn spawn_valuation(
+    pool: PgPool,
+    provider: PriceProvider,
+    config: ValuationConfig,
+    leadership: Leadership,
+) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_valuation(&pool, &provider).await {
+                Ok(summary) => {
+                    if summary.price_failures > 0 {
+                        warn!(
+                            tickers_priced = summary.tickers_priced,
+                            price_failures = summary.price_failures,
+                            "Some holdings could not be priced"
+                        );
+                    }
+                    if summary.tickers_priced + summary.accounts_valued > 0 {
+                        info!(
+                            tickers_priced = summary.tickers_priced,
+                            accounts_valued = summary.accounts_valued,
+                            "Holdings valued"
+                        );
+                    }
+                }
+                Err(e) => error!("Valuation job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the spending digest email job on the Tokio runtime.
+pub fn spawn_digests(
+    pool: PgPool,
+    mailer: Arc<dyn Mailer>,
+    config: DigestConfig,
+    leadership: Leadership,
+) {
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_digests(&pool, mailer.as_ref()).await {
+                Ok(summary) => {
+                    if summary.sent + summary.failed > 0 {
+                        info!(
+                            sent = summary.sent,
+                            failed = summary.failed,
+                            "Spending digests processed"
+                        );
+                    }
+                }
+                Err(e) => error!("Spending digest job failed: {}", e),
+            }
+        }
+    });
+}
+
+/// Spawn the bank sync job on the Tokio runtime. Does nothing while bank
+/// sync is disabled.
+pub fn spawn_bank_sync(
+    pool: PgPool,
+    provider: Arc<BankSyncProvider>,
+    config: BankSyncConfig,
+    leadership: Leadership,
+) {
+    if provider.name().is_none() {
+        return;
+    }
+    shutdown::spawn(async move {
+        let mut ticker = tokio::time::interval(config.interval);
+        while shutdown::tick(&mut ticker).await {
+            if !leadership.is_leader() {
+                continue;
+            }
+            match JobService::run_bank_sync(&pool, &provider).await {
+                Ok(summary) => {
+                    if summary.connections_synced + summary.failed > 0 {
+                        info!(
+                            connections_synced = summary.connections_synced,
+                            failed = summary.failed,
+                            imports_added = summary.imports_added,
+                            "Bank sync completed"
+                        );
+                    }
+                }
+                Err(e) => error!("Bank sync job failed: {}", e),
+            }
+        }
+    });
+}
@@ -0,0 +1,366 @@
+use sqlx::PgPool;
+use tracing::warn;
+
+use super::models::{
+    BankSyncSummary, BillReminderSummary, ChargePostingSummary, DigestSummary, LoanPostingSummary,
+    RetentionJobConfig, RetentionPolicy, RetentionPolicyReport, ValuationSummary,
+    RETENTION_POLICIES,
+};
+use crate::account::models::BalanceRecomputeSummary;
+use crate::account::service::AccountService;
+use crate::bank_sync::provider::BankSyncProvider;
+use crate::bank_sync::service::BankSyncService;
+use crate::bill::models::{BillResponse, PayBillDto, PayBillResponse};
+use crate::bill::service::BillService;
+use crate::errors::AppError;
+use crate::holding::price::PriceProvider;
+use crate::holding::service::HoldingService;
+use crate::mailer::Mailer;
+use crate::notification::service::NotificationService;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+/// Rows deleted per statement so large purges don't hold long locks
+const RETENTION_BATCH_SIZE: i64 = 5000;
+
+/// Service layer for background maintenance jobs.
+pub struct JobService;
+
+impl JobService {
+    /// Delete refresh tokens that expired or were revoked more than `retention_days` ago.
+    /// Returns the number of rows removed.
+    pub async fn purge_refresh_tokens(pool: &PgPool, retention_days: i32) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM refresh_tokens
+            WHERE expires_at < NOW() - make_interval(days => $1)
+               OR revoked_at < NOW() - make_interval(days => $1)
+            "#,
+        )
+        .bind(retention_days)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Apply every enabled retention policy. In dry-run mode rows are only counted.
+    pub async fn run_retention(
+        pool: &PgPool,
+        config: &RetentionJobConfig,
+        dry_run: bool,
+    ) -> Result<Vec<RetentionPolicyReport>, AppError> {
+        let mut reports = Vec::with_capacity(RETENTION_POLICIES.len());
+
+        for policy in RETENTION_POLICIES {
+            let retention_days = config.retention_days(policy);
+            let affected_rows = match retention_days {
+                Some(days) if dry_run => Self::count_expired(pool, policy, days).await?,
+                Some(days) => Self::purge_expired(pool, policy, days).await?,
+                None => 0,
+            };
+
+            reports.push(RetentionPolicyReport {
+                policy: policy.name.to_string(),
+                retention_days,
+                affected_rows,
+            });
+        }
+
+        Ok(reports)
+    }
+
+    /// Check every account's stored balance against opening balance plus
+    /// transaction history. Drift is recorded in `balance_discrepancies` and
+    /// raised to the owner as an `account.balance_drift` event; nothing is fixed.
+    pub async fn run_integrity_audit(pool: &PgPool) -> Result<BalanceRecomputeSummary, AppError> {
+        let summary = AccountService::recompute_all_balances(pool, None, false).await?;
+
+        for check in &summary.drifted {
+            sqlx::query(
+                r#"
+                INSERT INTO balance_discrepancies
+                    (account_id, owner_id, stored_balance, opening_balance,
+                     transaction_net, computed_balance, drift)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(check.account_id)
+            .bind(check.owner_id)
+            .bind(check.stored_balance)
+            .bind(check.opening_balance)
+            .bind(check.transaction_net)
+            .bind(check.computed_balance)
+            .bind(check.drift)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            WebhookService::publish(pool, check.owner_id, events::ACCOUNT_BALANCE_DRIFT, check)
+                .await;
+        }
+
+        Ok(summary)
+    }
+
+    /// Record autopay payments that have come due, then raise `bill.due` for
+    /// bills entering their reminder window and `bill.overdue` for bills past
+    /// due. Each notice goes out once per due date.
+    pub async fn run_bill_reminders(pool: &PgPool) -> Result<BillReminderSummary, AppError> {
+        let mut summary = BillReminderSummary::default();
+
+        for bill in BillService::autopay_due(pool).await? {
+            let (bill_id, owner_id) = (bill.id, bill.owner_id);
+            match BillService::pay(pool, bill_id, owner_id, PayBillDto::default(), true).await {
+                Ok((bill, payment, transaction)) => {
+                    summary.autopaid += 1;
+                    let response = PayBillResponse {
+                        bill: BillResponse::from(bill),
+                        payment,
+                        transaction: transaction.map(TransactionResponse::from),
+                    };
+                    if let Some(transaction) = &response.transaction {
+                        WebhookService::publish(
+                            pool,
+                            owner_id,
+                            events::TRANSACTION_CREATED,
+                            transaction,
+                        )
+                        .await;
+                    }
+                    WebhookService::publish(pool, owner_id, events::BILL_PAID, &response).await;
+                }
+                Err(e) => {
+                    summary.autopay_failed += 1;
+                    warn!(bill_id = %bill_id, "Bill autopay failed: {}", e);
+                }
+            }
+        }
+
+        for bill in BillService::claim_reminders(pool).await? {
+            summary.reminded += 1;
+            let owner_id = bill.owner_id;
+            WebhookService::publish(pool, owner_id, events::BILL_DUE, &BillResponse::from(bill))
+                .await;
+        }
+
+        for bill in BillService::claim_overdue(pool).await? {
+            summary.overdue += 1;
+            let owner_id = bill.owner_id;
+            WebhookService::publish(
+                pool,
+                owner_id,
+                events::BILL_OVERDUE,
+                &BillResponse::from(bill),
+            )
+            .await;
+        }
+
+        Ok(summary)
+    }
+
+    /// Reprice every held ticker from the price provider, then record each
+    /// investment account's value for the day. A failed lookup leaves that
+    /// ticker at its last price.
+    pub async fn run_valuation(
+        pool: &PgPool,
+        provider: &PriceProvider,
+    ) -> Result<ValuationSummary, AppError> {
+        let mut summary = ValuationSummary::default();
+
+        if !matches!(provider, PriceProvider::Manual) {
+            for ticker in HoldingService::held_tickers(pool).await? {
+                match provider.quote(&ticker).await {
+                    Ok(Some(price)) => {
+                        HoldingService::set_price(pool, &ticker, price).await?;
+                        summary.tickers_priced += 1;
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        summary.price_failures += 1;
+                        warn!(ticker = %ticker, "Price lookup failed: {}", e);
+                    }
+                }
+            }
+        }
+
+        summary.accounts_valued = HoldingService::snapshot_valuations(pool).await?;
+
+        Ok(summary)
+    }
+
+    /// Post every loan payment that has come due on autoposting loans,
+    /// catching up on missed runs one payment at a time
+    pub async fn run_loan_postings(pool: &PgPool) -> Result<LoanPostingSummary, AppError> {
+        let mut summary = LoanPostingSummary::default();
+
+        for mut loan in AccountService::autopost_loans(pool).await? {
+            loop {
+                match AccountService::post_next_loan_payment(pool, &loan).await {
+                    Ok(transactions) if transactions.is_empty() => break,
+                    Ok(transactions) => {
+                        summary.posted += 1;
+                        loan.terms.payments_posted += 1;
+                        for transaction in transactions {
+                            WebhookService::publish(
+                                pool,
+                                loan.owner_id,
+                                events::TRANSACTION_CREATED,
+                                &TransactionResponse::from(transaction),
+                            )
+                            .await;
+                        }
+                    }
+                    Err(e) => {
+                        summary.failed += 1;
+                        warn!(account_id = %loan.terms.account_id, "Loan payment posting failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Post interest and fees for every ended cycle of autoposting accounts,
+    /// catching up on missed runs one cycle at a time
+    pub async fn run_charge_postings(pool: &PgPool) -> Result<ChargePostingSummary, AppError> {
+        let mut summary = ChargePostingSummary::default();
+
+        for mut due in AccountService::autopost_charges(pool).await? {
+            loop {
+                match AccountService::post_next_charge_cycle(pool, &due).await {
+                    Ok(None) => break,
+                    Ok(Some((cycle_end, transactions))) => {
+                        summary.cycles_posted += 1;
+                        due.config.last_posted_cycle = Some(cycle_end);
+                        for transaction in transactions {
+                            WebhookService::publish(
+                                pool,
+                                due.owner_id,
+                                events::TRANSACTION_CREATED,
+                                &TransactionResponse::from(transaction),
+                            )
+                            .await;
+                        }
+                    }
+                    Err(e) => {
+                        summary.failed += 1;
+                        warn!(account_id = %due.config.account_id, "Charge posting failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Email each opted-in user the digest for their week or month that just
+    /// ended. A digest that fails is given back and retried on the next run.
+    pub async fn run_digests(
+        pool: &PgPool,
+        mailer: &dyn Mailer,
+    ) -> Result<DigestSummary, AppError> {
+        let mut summary = DigestSummary::default();
+
+        for claim in NotificationService::claim_digests(pool).await? {
+            let owner_id = claim.owner_id;
+            let result = match NotificationService::compile_digest(pool, claim.clone()).await {
+                Ok(digest) => mailer.send(&digest.to_email()).await,
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(()) => {
+                    summary.sent += 1;
+                    NotificationService::mark_digest_sent(pool, owner_id).await?;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    warn!(owner_id = %owner_id, "Spending digest failed: {}", e);
+                    NotificationService::release_digest(pool, &claim).await?;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Pull new transactions from every linked institution into the review
+    /// queue. A failing connection keeps its error and is retried next run.
+    pub async fn run_bank_sync(
+        pool: &PgPool,
+        provider: &BankSyncProvider,
+    ) -> Result<BankSyncSummary, AppError> {
+        let mut summary = BankSyncSummary::default();
+
+        for connection in BankSyncService::syncable_connections(pool, provider).await? {
+            match BankSyncService::sync_connection(pool, provider, &connection).await {
+                Ok(result) => {
+                    summary.connections_synced += 1;
+                    summary.imports_added += result.imports_added;
+                }
+                Err(e) => {
+                    summary.failed += 1;
+                    warn!(connection_id = %connection.id, "Bank sync failed: {}", e);
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn count_expired(
+        pool: &PgPool,
+        policy: &RetentionPolicy,
+        days: i32,
+    ) -> Result<u64, AppError> {
+        // Table and predicate are compile-time constants, never user input
+        let sql = format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            policy.table, policy.age_condition
+        );
+
+        let count = sqlx::query_scalar::<_, i64>(&sql)
+            .bind(days)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(count as u64)
+    }
+
+    async fn purge_expired(
+        pool: &PgPool,
+        policy: &RetentionPolicy,
+        days: i32,
+    ) -> Result<u64, AppError> {
+        let sql = format!(
+            "DELETE FROM {table} WHERE ctid IN (SELECT ctid FROM {table} WHERE {cond} LIMIT $2)",
+            table = policy.table,
+            cond = policy.age_condition
+        );
+
+        let mut total = 0;
+        loop {
+            let deleted = sqlx::query(&sql)
+                .bind(days)
+                .bind(RETENTION_BATCH_SIZE)
+                .execute(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?
+                .rows_affected();
+
+            total += deleted;
+            if deleted < RETENTION_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+
+        Ok(total)
+    }
+}
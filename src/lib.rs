@@ -1,9 +1,43 @@
 pub mod account;
+pub mod audit;
 pub mod auth;
+pub mod bank_sync;
+pub mod bill;
 pub mod budget;
+pub mod cache;
 pub mod category;
+pub mod config;
+pub mod crypto;
 pub mod currency;
+pub mod db;
+pub mod debug;
 pub mod errors;
+pub mod event;
 pub mod extractors;
+pub mod funding;
+pub mod health;
+pub mod holding;
+pub mod import;
+pub mod jobs;
+pub mod mailer;
+pub mod notification;
 pub mod openapi;
+pub mod payee;
+pub mod preferences;
+pub mod query;
+pub mod ratelimit;
+pub mod receipt;
+pub mod report;
+pub mod request_id;
+pub mod routes;
+pub mod search;
+pub mod settings;
+pub mod shutdown;
+pub mod storage;
+pub mod summary;
+pub mod tag;
+pub mod template;
 pub mod transaction;
+pub mod uploads;
+pub mod webhook;
+pub mod widget;
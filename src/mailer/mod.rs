@@ -0,0 +1,48 @@
+pub mod smtp;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::info;
+
+use self::smtp::SmtpMailer;
+use crate::errors::AppError;
+
+/// A plain-text email to one recipient
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+/// Delivers outgoing email
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError>;
+}
+
+/// Writes messages to the log instead of sending them, for development
+/// without an SMTP server
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        info!(
+            to = %message.to,
+            subject = %message.subject,
+            "Email not sent (SMTP_HOST unset):\n{}",
+            message.body
+        );
+        Ok(())
+    }
+}
+
+/// The SMTP mailer when one is configured, otherwise [`LogMailer`]
+pub fn from_config(smtp: Option<SmtpMailer>) -> Arc<dyn Mailer> {
+    match smtp {
+        Some(smtp) => Arc::new(smtp),
+        None => Arc::new(LogMailer),
+    }
+}
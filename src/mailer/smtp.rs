@@ -0,0 +1,271 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chrono::Utc;
+use secrecy::{ExposeSecret, Secret};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufStream};
+use tokio::net::TcpStream;
+use tokio_native_tls::{native_tls, TlsConnector};
+use uuid::Uuid;
+
+use super::{EmailMessage, Mailer};
+use crate::errors::AppError;
+
+/// Time allowed for a whole delivery, from connecting to QUIT
+const SMTP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How the connection to the SMTP server is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    StartTls,
+    /// TLS from the start (usually port 465)
+    Tls,
+    /// No encryption; for local relays only
+    None,
+}
+
+/// Sends email through an SMTP server, authenticating with AUTH LOGIN when
+/// credentials are configured
+#[derive(Debug, Clone)]
+pub struct SmtpMailer {
+    host: String,
+    port: u16,
+    security: SmtpSecurity,
+    username: Option<String>,
+    password: Option<Secret<String>>,
+    from: String,
+}
+
+impl SmtpMailer {
+    pub fn new(
+        host: String,
+        port: u16,
+        security: SmtpSecurity,
+        username: Option<String>,
+        password: Option<Secret<String>>,
+        from: String,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            security,
+            username,
+            password,
+            from,
+        }
+    }
+
+    /// Default port for a connection security mode
+    pub fn default_port(security: SmtpSecurity) -> u16 {
+        match security {
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::StartTls | SmtpSecurity::None => 587,
+        }
+    }
+
+    async fn deliver(&self, message: &EmailMessage) -> Result<(), AppError> {
+        let tcp = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(smtp_error)?;
+
+        match self.security {
+            SmtpSecurity::Tls => {
+                let mut stream = BufStream::new(self.tls(tcp).await?);
+                expect_reply(&mut stream, 220).await?;
+                self.session(&mut stream, message).await
+            }
+            SmtpSecurity::StartTls => {
+                let mut stream = BufStream::new(tcp);
+                expect_reply(&mut stream, 220).await?;
+                command(&mut stream, &format!("EHLO {}", self.helo_name()), 250).await?;
+                command(&mut stream, "STARTTLS", 220).await?;
+                let mut stream = BufStream::new(self.tls(stream.into_inner()).await?);
+                self.session(&mut stream, message).await
+            }
+            SmtpSecurity::None => {
+                let mut stream = BufStream::new(tcp);
+                expect_reply(&mut stream, 220).await?;
+                self.session(&mut stream, message).await
+            }
+        }
+    }
+
+    async fn tls(
+        &self,
+        tcp: TcpStream,
+    ) -> Result<tokio_native_tls::TlsStream<TcpStream>, AppError> {
+        let connector = native_tls::TlsConnector::new().map_err(smtp_error)?;
+        TlsConnector::from(connector)
+            .connect(&self.host, tcp)
+            .await
+            .map_err(smtp_error)
+    }
+
+    /// Everything after the greeting (and STARTTLS): EHLO, AUTH, the envelope and the data
+    async fn session<S>(
+        &self,
+        stream: &mut BufStream<S>,
+        message: &EmailMessage,
+    ) -> Result<(), AppError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        command(stream, &format!("EHLO {}", self.helo_name()), 250).await?;
+
+        if let (Some(username), Some(password)) = (&self.username, &self.password) {
+            command(stream, "AUTH LOGIN", 334).await?;
+            command(stream, &BASE64.encode(username), 334).await?;
+            command(stream, &BASE64.encode(password.expose_secret()), 235).await?;
+        }
+
+        command(stream, &format!("MAIL FROM:<{}>", self.from), 250).await?;
+        command(stream, &format!("RCPT TO:<{}>", message.to), 250).await?;
+        command(stream, "DATA", 354).await?;
+        stream
+            .write_all(format_message(&self.from, &self.helo_name(), message).as_bytes())
+            .await
+            .map_err(smtp_error)?;
+        stream.flush().await.map_err(smtp_error)?;
+        expect_reply(stream, 250).await?;
+
+        // The message is accepted; a failed QUIT doesn't matter
+        let _ = command(stream, "QUIT", 221).await;
+        Ok(())
+    }
+
+    /// Domain of the sender address, used to introduce ourselves
+    fn helo_name(&self) -> String {
+        self.from
+            .rsplit_once('@')
+            .map(|(_, domain)| domain.trim_end_matches('>').to_string())
+            .unwrap_or_else(|| "localhost".to_string())
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+        tokio::time::timeout(SMTP_TIMEOUT, self.deliver(message))
+            .await
+            .map_err(|_| AppError::InternalError("SMTP delivery timed out".to_string()))?
+    }
+}
+
+fn smtp_error(e: impl std::fmt::Display) -> AppError {
+    AppError::InternalError(format!("SMTP error: {e}"))
+}
+
+/// Split a reply line into its code, whether it is the last line of the
+/// reply, and its text
+pub fn parse_reply_line(line: &str) -> Option<(u16, bool, &str)> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let code = line.get(..3)?.parse().ok()?;
+    match line.get(3..4) {
+        None => Some((code, true, "")),
+        Some(" ") => Some((code, true, &line[4..])),
+        Some("-") => Some((code, false, &line[4..])),
+        Some(_) => None,
+    }
+}
+
+/// Read a (possibly multi-line) reply and fail unless it is in the same class
+/// as `expected` (2xx, 3xx)
+async fn expect_reply<S>(stream: &mut BufStream<S>, expected: u16) -> Result<(), AppError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut text = Vec::new();
+    loop {
+        let mut line = String::new();
+        if stream.read_line(&mut line).await.map_err(smtp_error)? == 0 {
+            return Err(smtp_error("connection closed by server"));
+        }
+        let (code, last, line_text) = parse_reply_line(&line)
+            .ok_or_else(|| smtp_error(format!("malformed reply '{}'", line.trim_end())))?;
+        text.push(line_text.to_string());
+        if last {
+            if code / 100 != expected / 100 {
+                return Err(smtp_error(format!(
+                    "server replied {} {}",
+                    code,
+                    text.join(" ")
+                )));
+            }
+            return Ok(());
+        }
+    }
+}
+
+async fn command<S>(stream: &mut BufStream<S>, line: &str, expected: u16) -> Result<(), AppError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream
+        .write_all(format!("{line}\r\n").as_bytes())
+        .await
+        .map_err(smtp_error)?;
+    stream.flush().await.map_err(smtp_error)?;
+    expect_reply(stream, expected).await
+}
+
+/// Headers and body for the DATA command, with CRLF line endings, leading dots
+/// doubled and the terminating `.` line
+pub fn format_message(from: &str, domain: &str, message: &EmailMessage) -> String {
+    let subject = if message.subject.is_ascii() {
+        message.subject.clone()
+    } else {
+        format!("=?UTF-8?B?{}?=", BASE64.encode(&message.subject))
+    };
+
+    let mut data = format!(
+        "From: {from}\r\nTo: {}\r\nSubject: {subject}\r\nDate: {}\r\nMessage-ID: <{}@{domain}>\r\n\
+         MIME-Version: 1.0\r\nContent-Type: text/plain; charset=utf-8\r\n\
+         Content-Transfer-Encoding: 8bit\r\n\r\n",
+        message.to,
+        Utc::now().to_rfc2822(),
+        Uuid::new_v4(),
+    );
+    for line in message.body.lines() {
+        if line.starts_with('.') {
+            data.push('.');
+        }
+        data.push_str(line);
+        data.push_str("\r\n");
+    }
+    data.push_str(".\r\n");
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply_line() {
+        assert_eq!(
+            parse_reply_line("250-smtp.example.com\r\n"),
+            Some((250, false, "smtp.example.com"))
+        );
+        assert_eq!(parse_reply_line("250 OK\r\n"), Some((250, true, "OK")));
+        assert_eq!(parse_reply_line("354\r\n"), Some((354, true, "")));
+        assert_eq!(parse_reply_line("hello\r\n"), None);
+    }
+
+    #[test]
+    fn test_format_message_stuffs_dots_and_encodes_subject() {
+        let data = format_message(
+            "no-reply@example.com",
+            "example.com",
+            &EmailMessage {
+                to: "user@example.com".to_string(),
+                subject: "Réinitialiser".to_string(),
+                body: "Hello\n.hidden\nBye".to_string(),
+            },
+        );
+
+        assert!(data.contains("Subject: =?UTF-8?B?UsOpaW5pdGlhbGlzZXI=?=\r\n"));
+        assert!(data.ends_with("\r\n\r\nHello\r\n..hidden\r\nBye\r\n.\r\n"));
+    }
+}
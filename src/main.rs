@@ -1,42 +1,61 @@
 mod account;
+mod audit;
 mod auth;
+mod bank_sync;
+mod bill;
 mod budget;
+mod cache;
 mod category;
+mod config;
+mod crypto;
 mod currency;
+mod db;
+mod debug;
 mod errors;
+mod event;
 mod extractors;
+mod funding;
+mod health;
+mod holding;
+mod import;
+mod jobs;
+mod mailer;
+mod notification;
 mod openapi;
+mod payee;
+mod preferences;
+mod query;
+mod ratelimit;
+mod receipt;
+mod report;
+mod request_id;
+mod routes;
+mod search;
+mod settings;
+mod shutdown;
+mod storage;
+mod summary;
+mod tag;
+mod template;
 mod transaction;
+mod uploads;
+mod webhook;
+mod widget;
 
 use actix_cors::Cors;
+use actix_files::Files;
 use actix_governor::{Governor, GovernorConfigBuilder};
-use actix_web::{get, http::header, web, App, HttpResponse, HttpServer, Responder};
+use actix_web::middleware::{from_fn, Condition};
+use actix_web::{http::header, web, App, HttpServer};
 use dotenvy::dotenv;
-use secrecy::Secret;
-use sqlx::postgres::PgPoolOptions;
-use sqlx::PgPool;
+use secrecy::ExposeSecret;
 use std::env;
-use std::time::Duration;
+use std::sync::Arc;
 use tracing::info;
 use tracing_actix_web::TracingLogger;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
-/// Health check endpoint that verifies database connectivity
-#[get("/health")]
-async fn health_check(pool: web::Data<PgPool>) -> impl Responder {
-    match sqlx::query("SELECT 1").execute(pool.get_ref()).await {
-        Ok(_) => HttpResponse::Ok().json(serde_json::json!({
-            "status": "healthy",
-            "database": "connected"
-        })),
-        Err(_) => HttpResponse::ServiceUnavailable().json(serde_json::json!({
-            "status": "unhealthy",
-            "database": "disconnected"
-        })),
-    }
-}
-
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
     dotenv().ok();
@@ -49,119 +68,307 @@ async fn main() -> std::io::Result<()> {
         )
         .init();
 
-    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let jwt_secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
-
-    // Wrap JWT secret in Secret for secure handling
-    let jwt_secret = Secret::new(jwt_secret);
-
-    // Get allowed origins from environment (comma-separated), default to localhost
-    let allowed_origins =
-        env::var("CORS_ALLOWED_ORIGINS").unwrap_or_else(|_| "http://localhost:3000".to_string());
+    // Settings the server needs, validated before anything starts
+    let config =
+        config::Config::from_env().unwrap_or_else(|e| panic!("Invalid configuration: {e}"));
+    let jwt_secret = config.jwt_secret.clone();
 
     // Configure connection pool for Cloud Run (handles concurrent requests)
-    let pool = PgPoolOptions::new()
-        .max_connections(10)
-        .min_connections(0)
-        .acquire_timeout(Duration::from_secs(5))
-        .idle_timeout(Duration::from_secs(300))
-        .max_lifetime(Duration::from_secs(900))
-        .connect(&database_url)
+    let pool = config
+        .pool
+        .pool_options()
+        .connect(config.database_url.expose_secret())
         .await
         .expect("Failed to create pool");
+    let pool_metrics = web::Data::new(db::metrics::PoolMetrics::new(config.pool));
+    cache::ResponseCache::init(config.response_cache_ttl);
+
+    // `be-rust migrate` applies pending migrations, then exits; otherwise they are
+    // applied on startup unless DB_MIGRATE_ON_STARTUP=false
+    if matches!(env::args().nth(1).as_deref(), Some("migrate" | "--migrate")) {
+        db::run_migrations(&pool)
+            .await
+            .expect("Failed to run migrations");
+        return Ok(());
+    }
+    if config.migrate_on_startup {
+        db::run_migrations(&pool)
+            .await
+            .expect("Failed to run migrations");
+    }
+
+    // Load column encryption keys; without a key, sensitive fields stay plaintext
+    let encryption_enabled = crypto::init(config.encryption_keys.as_ref())
+        .expect("Invalid data encryption configuration");
+    info!("Column encryption enabled: {}", encryption_enabled);
+
+    // `be-rust reencrypt` rewrites sensitive columns under the current key, then exits
+    if env::args().nth(1).as_deref() == Some("reencrypt") {
+        let report = crypto::reencrypt_all(&pool)
+            .await
+            .expect("Failed to re-encrypt sensitive columns");
+        info!(
+            "Re-encrypted {} transaction descriptions, {} reimbursement payers and {} account names",
+            report.transaction_descriptions, report.reimbursement_payers, report.account_names
+        );
+        return Ok(());
+    }
+
+    // Debug endpoints and commands are for staging and local environments only
+    let debug_config = config.debug;
+    info!("Debug endpoints enabled: {}", debug_config.enabled);
+
+    // `be-rust generate-load-data <email> [--accounts N] [--transactions N] [--months N] [--seed N]`
+    // adds load-test data to an existing user, then exits
+    if env::args().nth(1).as_deref() == Some("generate-load-data") {
+        assert!(
+            debug_config.enabled,
+            "generate-load-data requires DEBUG_ENDPOINTS_ENABLED=true"
+        );
+        let args: Vec<String> = env::args().skip(2).collect();
+        let (email, flags) = args.split_first().expect(
+            "Usage: be-rust generate-load-data <email> [--accounts N] [--transactions N] [--months N] [--seed N]",
+        );
+        let profile =
+            debug::models::LoadProfile::from_args(flags).unwrap_or_else(|e| panic!("{e}"));
+        let user_id = debug::service::DebugService::user_id_by_email(&pool, email)
+            .await
+            .expect("Failed to find the user");
+        let summary = debug::service::DebugService::generate_load_data(&pool, user_id, &profile)
+            .await
+            .expect("Failed to generate load-test data");
+        info!(
+            "Generated {} accounts, {} budgets, {} categories and {} transactions in {} ms",
+            summary.accounts,
+            summary.budgets,
+            summary.categories,
+            summary.transactions,
+            summary.elapsed_ms
+        );
+        return Ok(());
+    }
 
-    info!("Starting server at http://0.0.0.0:8080");
+    // Object storage for uploaded files (avatars)
+    let object_storage = config.storage.clone();
+    if let Some(root) = object_storage.local_root() {
+        std::fs::create_dir_all(root).expect("Failed to create local storage directory");
+    }
+
+    // Receipt ingestion addresses and inbound email verification
+    let inbound_email = config.inbound_email.clone();
+
+    // Outgoing email (password reset links); logged instead of sent without SMTP_HOST
+    let mailer = mailer::from_config(config.smtp.clone());
+    let password_reset = config.password_reset.clone();
+
+    // Bank data aggregator for linked institutions; disabled unless configured
+    let bank_sync = web::Data::new(config.bank_sync.clone());
+
+    // Security prices for investment holdings
+    let price_provider = config.price_provider.clone();
+
+    // Background maintenance jobs, run only by the elected leader instance
+    let jobs_config = &config.jobs;
+    let leadership = jobs::leader::start_leader_election(pool.clone(), jobs_config.leader).await;
+    jobs::scheduler::spawn_refresh_token_cleanup(
+        pool.clone(),
+        jobs_config.refresh_token_cleanup,
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_retention(
+        pool.clone(),
+        jobs_config.retention.clone(),
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_summary_refresh(
+        pool.clone(),
+        jobs_config.summary_refresh,
+        leadership.clone(),
+    );
+    let integrity_metrics = web::Data::new(jobs::models::IntegrityAuditMetrics::default());
+    jobs::scheduler::spawn_integrity_audit(
+        pool.clone(),
+        jobs_config.integrity_audit,
+        leadership.clone(),
+        integrity_metrics.clone().into_inner(),
+    );
+    jobs::scheduler::spawn_bill_reminders(
+        pool.clone(),
+        jobs_config.bill_reminders,
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_loan_postings(
+        pool.clone(),
+        jobs_config.loan_postings,
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_charge_postings(
+        pool.clone(),
+        jobs_config.charge_postings,
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_valuation(
+        pool.clone(),
+        price_provider.clone(),
+        jobs_config.valuation,
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_digests(
+        pool.clone(),
+        mailer.clone(),
+        jobs_config.digests,
+        leadership.clone(),
+    );
+    jobs::scheduler::spawn_bank_sync(
+        pool.clone(),
+        bank_sync.clone().into_inner(),
+        jobs_config.bank_sync,
+        leadership.clone(),
+    );
+    db::metrics::spawn_pool_sampler(pool.clone(), pool_metrics.clone().into_inner());
+
+    info!(
+        "Starting server at http://{}:{}",
+        config.server.host, config.server.port
+    );
 
-    // Configure rate limiting for auth endpoints
-    // ~5 requests per minute with burst of 5
+    // Configure rate limiting for auth endpoints: `burst` requests, replenished
+    // one per `period`
+    let auth_quota = config.rate_limit.auth;
     let auth_governor_config = GovernorConfigBuilder::default()
-        .seconds_per_request(1)
-        .burst_size(5)
+        .period(auth_quota.period)
+        .burst_size(auth_quota.burst)
         .finish()
         .expect("Failed to create rate limiter config");
 
-    HttpServer::new(move || {
+    // With REDIS_URL set, limits are shared across instances instead of per process
+    let redis_limiter = config
+        .rate_limit
+        .redis_url
+        .as_ref()
+        .map(|url| {
+            ratelimit::RedisRateLimiter::new(
+                url.expose_secret(),
+                config.rate_limit.key_prefix.clone(),
+            )
+        })
+        .transpose()
+        .expect("Invalid rate limit configuration")
+        .map(Arc::new);
+    info!(
+        "Distributed rate limiting enabled: {}",
+        redis_limiter.is_some()
+    );
+    // Built once so every worker counts into the same per-user buckets
+    let write_limiter =
+        ratelimit::UserWriteLimiter::new(config.rate_limit.writes, redis_limiter.clone());
+
+    let bind_address = (config.server.host.clone(), config.server.port);
+    let shutdown_timeout = config.server.shutdown_timeout;
+    let config = web::Data::new(config);
+    let server_pool = pool.clone();
+
+    let server = HttpServer::new(move || {
         // Clone allowed_origins for this closure invocation
-        let allowed_origins = allowed_origins.clone();
+        let allowed_origins = config.cors_allowed_origins.clone();
+        let redis_limiter = redis_limiter.clone();
+        let write_limiter = write_limiter.clone();
 
         // Configure CORS
         let cors = Cors::default()
             .allowed_origin_fn(move |origin, _req_head| {
                 let origin_str = origin.to_str().unwrap_or("");
-                allowed_origins
-                    .split(',')
-                    .any(|allowed| allowed.trim() == origin_str)
+                allowed_origins.iter().any(|allowed| allowed == origin_str)
             })
-            .allowed_methods(vec!["GET", "POST", "PATCH", "DELETE", "OPTIONS"])
-            .allowed_headers(vec![header::AUTHORIZATION, header::CONTENT_TYPE])
+            .allowed_methods(vec!["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"])
+            .allowed_headers(vec![
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                header::IF_MATCH,
+                request_id::REQUEST_ID_HEADER,
+            ])
+            .expose_headers(vec![header::ETAG, request_id::REQUEST_ID_HEADER])
             .max_age(3600);
 
         App::new()
             // Middleware (order matters: outer to inner)
-            .wrap(TracingLogger::default())
+            .wrap(from_fn(move |req, next| {
+                ratelimit::enforce_user_writes(write_limiter.clone(), req, next)
+            }))
+            .wrap(TracingLogger::<request_id::RequestIdRootSpan>::new())
+            .wrap(from_fn(request_id::assign))
             .wrap(cors)
             // Shared state
-            .app_data(web::Data::new(pool.clone()))
+            .app_data(config.clone())
+            .app_data(web::Data::new(server_pool.clone()))
             .app_data(web::Data::new(jwt_secret.clone()))
+            .app_data(web::Data::new(object_storage.clone()))
+            .app_data(web::Data::new(config.upload_scanner.clone()))
+            .app_data(web::Data::new(price_provider.clone()))
+            .app_data(bank_sync.clone())
+            .app_data(web::Data::new(inbound_email.clone()))
+            .app_data(web::Data::from(mailer.clone()))
+            .app_data(web::Data::new(password_reset.clone()))
+            .app_data(web::Data::new(debug_config))
+            .app_data(pool_metrics.clone())
+            .app_data(web::Data::new(config.health()))
+            .app_data(integrity_metrics.clone())
+            // Undeserializable path, query and body values get a JSON 422
+            .app_data(web::PathConfig::default().error_handler(errors::path_error))
+            .app_data(web::QueryConfig::default().error_handler(errors::query_error))
+            .app_data(web::JsonConfig::default().error_handler(errors::json_error))
+            // Raw body uploads (avatars) are capped by their upload policy
+            .app_data(web::PayloadConfig::new(
+                uploads::validation::AVATAR_POLICY.max_bytes,
+            ))
             // Swagger UI
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
                     .url("/api-docs/openapi.json", openapi::ApiDoc::openapi()),
             )
-            // Health endpoint (no rate limiting)
-            .service(health_check)
-            // Auth endpoints without rate limiting
-            .service(auth::logout)
-            .service(auth::me)
-            // Budget endpoints (order matters: specific routes before generic {id} routes)
-            .service(budget::list_budgets)
-            .service(budget::create_budget)
-            .service(budget::get_budget_by_month_year)
-            .service(budget::get_budget)
-            .service(budget::update_income)
-            .service(budget::update_savings_rate)
-            .service(budget::update_budget)
-            .service(budget::delete_budget)
-            // Account endpoints (order matters: specific routes before generic {id} routes)
-            .service(account::list_accounts)
-            .service(account::get_accounts_summary)
-            .service(account::get_accounts_by_type)
-            .service(account::get_account)
-            .service(account::create_account)
-            .service(account::update_account_balance)
-            .service(account::update_account)
-            .service(account::delete_account)
-            // Category endpoints (order matters: specific routes before generic {id} routes)
-            .service(category::list_categories)
-            .service(category::get_categories_by_budget)
-            .service(category::get_category)
-            .service(category::create_category)
-            .service(category::update_category)
-            .service(category::delete_category)
-            // Transaction endpoints (order matters: specific routes before generic {id} routes)
-            .service(transaction::list_transactions)
-            .service(transaction::get_by_category)
-            .service(transaction::get_by_categories)
-            .service(transaction::get_by_account)
-            .service(transaction::get_summary)
-            .service(transaction::get_transaction)
-            .service(transaction::create_transaction)
-            .service(transaction::update_transaction)
-            .service(transaction::delete_transaction)
-            // Currency endpoints (order matters: specific routes before generic routes)
-            .service(currency::list_currencies)
-            .service(currency::sync_exchange_rates)
+            // API endpoints, the same list the integration tests run against
+            .configure(routes::configure_api)
+            // Locally stored uploads (object storage serves them directly in production)
+            .configure(|cfg| {
+                if let Some(root) = object_storage.local_root() {
+                    cfg.service(Files::new("/media", root));
+                }
+            })
             // Auth endpoints with rate limiting (must be last to avoid catching all routes)
             .service(
                 web::scope("")
-                    .wrap(Governor::new(&auth_governor_config))
-                    .service(auth::register)
-                    .service(auth::login)
-                    .service(auth::google_login)
-                    .service(auth::refresh),
+                    .wrap(Condition::new(
+                        redis_limiter.is_none(),
+                        Governor::new(&auth_governor_config),
+                    ))
+                    .wrap(from_fn(move |req, next| {
+                        ratelimit::enforce(redis_limiter.clone(), "auth", auth_quota, req, next)
+                    }))
+                    .configure(auth::configure_credential_routes),
             )
     })
-    .bind(("0.0.0.0", 8080))?
-    .run()
-    .await
+    // Signals are handled below so background tasks stop along with the server
+    .disable_signals()
+    .shutdown_timeout(shutdown_timeout.as_secs())
+    .bind(bind_address)?
+    .run();
+
+    // On SIGTERM/SIGINT: stop accepting connections, tell background tasks to
+    // stop, and let in-flight requests finish
+    let server_handle = server.handle();
+    tokio::spawn(async move {
+        shutdown::signal().await;
+        info!("Shutting down: draining in-flight requests");
+        shutdown::begin();
+        server_handle.stop(true).await;
+    });
+
+    server.await?;
+
+    // Wait for jobs and deliveries in progress before closing the pool under them
+    info!("Server stopped; waiting for background tasks");
+    shutdown::wait(shutdown_timeout).await;
+    pool.close().await;
+    info!("Shutdown complete");
+    Ok(())
 }
@@ -0,0 +1,250 @@
+use actix_web::{get, patch, post, put, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::scopes::Scope;
+use crate::category::models::CategoryIdPath;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{
+    CategoryAlertsResponse, MarkAllReadResponse, NotificationIdPath, NotificationListResponse,
+    NotificationPreferencesResponse, NotificationResponse, NotificationsQuery,
+    UpdateCategoryAlertsDto, UpdateNotificationDto, UpdateNotificationPreferencesDto,
+};
+use super::service::NotificationService;
+
+/// GET /categories/{id}/alerts - The percentages of a category's budget you're notified at
+#[utoipa::path(
+    get,
+    path = "/categories/{id}/alerts",
+    tag = "Notifications",
+    params(CategoryIdPath),
+    responses(
+        (status = 200, description = "Alert thresholds", body = CategoryAlertsResponse),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/categories/{id}/alerts")]
+pub async fn get_category_alerts(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<CategoryIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesRead)?;
+
+    let thresholds =
+        NotificationService::category_thresholds(pool.get_ref(), auth.user_id, path.id).await?;
+
+    Ok(HttpResponse::Ok().json(CategoryAlertsResponse {
+        category_id: path.id,
+        thresholds,
+    }))
+}
+
+/// PUT /categories/{id}/alerts - Replace the percentages of a category's budget you're notified at
+#[utoipa::path(
+    put,
+    path = "/categories/{id}/alerts",
+    tag = "Notifications",
+    params(CategoryIdPath),
+    request_body = UpdateCategoryAlertsDto,
+    responses(
+        (status = 200, description = "Alert thresholds saved", body = CategoryAlertsResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/categories/{id}/alerts")]
+pub async fn update_category_alerts(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<CategoryIdPath>,
+    body: web::Json<UpdateCategoryAlertsDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::CategoriesWrite)?;
+
+    body.validate()?;
+
+    let thresholds = NotificationService::set_category_thresholds(
+        pool.get_ref(),
+        auth.user_id,
+        path.id,
+        &body.thresholds,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(CategoryAlertsResponse {
+        category_id: path.id,
+        thresholds,
+    }))
+}
+
+/// GET /notifications - List notifications, newest first
+#[utoipa::path(
+    get,
+    path = "/notifications",
+    tag = "Notifications",
+    params(NotificationsQuery),
+    responses(
+        (status = 200, description = "Notifications and the unread count", body = NotificationListResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/notifications")]
+pub async fn list_notifications(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<NotificationsQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    query.validate()?;
+
+    let notifications = NotificationService::list(pool.get_ref(), auth.user_id, &query).await?;
+    let unread_count = NotificationService::unread_count(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(NotificationListResponse {
+        data: notifications
+            .into_iter()
+            .map(NotificationResponse::from)
+            .collect(),
+        unread_count,
+    }))
+}
+
+/// POST /notifications/read-all - Mark every notification read
+#[utoipa::path(
+    post,
+    path = "/notifications/read-all",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Unread notifications marked read", body = MarkAllReadResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/notifications/read-all")]
+pub async fn mark_all_notifications_read(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    let updated = NotificationService::mark_all_read(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(MarkAllReadResponse { updated }))
+}
+
+/// GET /notifications/preferences - Get notification preferences
+#[utoipa::path(
+    get,
+    path = "/notifications/preferences",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Saved preferences, or the defaults", body = NotificationPreferencesResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/notifications/preferences")]
+pub async fn get_notification_preferences(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsRead)?;
+
+    let preferences = NotificationService::preferences(pool.get_ref(), auth.user_id).await?;
+
+    Ok(
+        HttpResponse::Ok().json(NotificationPreferencesResponse::from_preferences(
+            preferences,
+        )),
+    )
+}
+
+/// PUT /notifications/preferences - Replace notification preferences, e.g. opt into the spending digest
+#[utoipa::path(
+    put,
+    path = "/notifications/preferences",
+    tag = "Notifications",
+    request_body = UpdateNotificationPreferencesDto,
+    responses(
+        (status = 200, description = "Preferences saved", body = NotificationPreferencesResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/notifications/preferences")]
+pub async fn update_notification_preferences(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<UpdateNotificationPreferencesDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    let preferences =
+        NotificationService::set_preferences(pool.get_ref(), auth.user_id, body.digest_frequency)
+            .await?;
+
+    Ok(
+        HttpResponse::Ok().json(NotificationPreferencesResponse::from_preferences(Some(
+            preferences,
+        ))),
+    )
+}
+
+/// PATCH /notifications/{id} - Mark a notification read or unread
+#[utoipa::path(
+    patch,
+    path = "/notifications/{id}",
+    tag = "Notifications",
+    params(NotificationIdPath),
+    request_body = UpdateNotificationDto,
+    responses(
+        (status = 200, description = "Notification updated", body = NotificationResponse),
+        (status = 404, description = "Notification not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/notifications/{id}")]
+pub async fn update_notification(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<NotificationIdPath>,
+    body: web::Json<UpdateNotificationDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::BudgetsWrite)?;
+
+    let notification =
+        NotificationService::set_read(pool.get_ref(), auth.user_id, path.id, body.read).await?;
+
+    Ok(HttpResponse::Ok().json(NotificationResponse::from(notification)))
+}
+
+/// Register the notification and category alert routes,
+/// `/notifications/read-all` and `/notifications/preferences` ahead of `/notifications/{id}`
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_category_alerts)
+        .service(update_category_alerts)
+        .service(list_notifications)
+        .service(mark_all_notifications_read)
+        .service(update_notification)
+        .service(get_notification_preferences)
+        .service(update_notification_preferences);
+}
@@ -0,0 +1,461 @@
+use chrono::{DateTime, Datelike, Days, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::mailer::EmailMessage;
+
+/// Kinds of notification
+pub mod kinds {
+    /// A category's spending reached one of its alert thresholds
+    pub const BUDGET_THRESHOLD: &str = "budget_threshold";
+}
+
+fn default_notification_limit() -> i64 {
+    50
+}
+
+fn validate_thresholds(thresholds: &[i32]) -> Result<(), ValidationError> {
+    if thresholds.iter().all(|t| (1..=1000).contains(t)) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("threshold_out_of_range"))
+    }
+}
+
+/// Request body for replacing a category's alert thresholds
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCategoryAlertsDto {
+    /// Percentages of the allocated amount to be notified at (1-1000);
+    /// an empty list turns alerts off
+    #[validate(
+        length(max = 10, message = "At most 10 thresholds per category"),
+        custom(
+            function = "validate_thresholds",
+            message = "Thresholds must be between 1 and 1000 percent"
+        )
+    )]
+    #[schema(example = json!([80, 100]))]
+    pub thresholds: Vec<i32>,
+}
+
+/// A user's alert thresholds for one category
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryAlertsResponse {
+    pub category_id: Uuid,
+    /// Percentages of the allocated amount, lowest first
+    #[schema(example = json!([80, 100]))]
+    pub thresholds: Vec<i32>,
+}
+
+/// Database entity for notifications, joined with the category name
+#[derive(Debug, Clone, FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub kind: String,
+    pub category_id: Option<Uuid>,
+    pub category_name: Option<String>,
+    pub threshold_percent: Option<i32>,
+    pub spent_amount: Option<Decimal>,
+    pub allocated_amount: Option<Decimal>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Notification returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    #[schema(example = "budget_threshold")]
+    pub kind: String,
+    /// Human-readable summary
+    #[schema(example = "Groceries reached 80% of its budget (400.00 of 500.00)")]
+    pub message: String,
+    pub category_id: Option<Uuid>,
+    #[schema(example = "Groceries")]
+    pub category_name: Option<String>,
+    #[schema(example = 80)]
+    pub threshold_percent: Option<i32>,
+    /// Category spending when the notification was raised
+    #[schema(example = 400.00)]
+    pub spent_amount: Option<Decimal>,
+    /// Category allocation when the notification was raised
+    #[schema(example = 500.00)]
+    pub allocated_amount: Option<Decimal>,
+    pub read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Notification> for NotificationResponse {
+    fn from(n: Notification) -> Self {
+        let category = n.category_name.as_deref().unwrap_or("A category");
+        let message = match (n.threshold_percent, n.spent_amount, n.allocated_amount) {
+            (Some(threshold), Some(spent), Some(allocated)) => format!(
+                "{category} reached {threshold}% of its budget ({spent:.2} of {allocated:.2})"
+            ),
+            _ => format!("{category} reached an alert threshold"),
+        };
+
+        Self {
+            id: n.id,
+            kind: n.kind,
+            message,
+            category_id: n.category_id,
+            category_name: n.category_name,
+            threshold_percent: n.threshold_percent,
+            spent_amount: n.spent_amount,
+            allocated_amount: n.allocated_amount,
+            read: n.read_at.is_some(),
+            read_at: n.read_at,
+            created_at: n.created_at,
+        }
+    }
+}
+
+/// Query parameters for listing notifications
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationsQuery {
+    /// Only notifications not yet read
+    #[serde(default)]
+    pub unread_only: bool,
+
+    /// Maximum results (1-200)
+    #[validate(range(min = 1, max = 200))]
+    #[serde(default = "default_notification_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+}
+
+/// Notifications, newest first
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationListResponse {
+    pub data: Vec<NotificationResponse>,
+    /// Unread notifications in total, not just on this page
+    #[schema(example = 3)]
+    pub unread_count: i64,
+}
+
+/// Request body for marking a notification read or unread
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNotificationDto {
+    pub read: bool,
+}
+
+/// Result of marking every notification read
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkAllReadResponse {
+    /// Notifications that were unread
+    #[schema(example = 3)]
+    pub updated: u64,
+}
+
+/// Path parameters for notification ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct NotificationIdPath {
+    /// Notification UUID
+    pub id: Uuid,
+}
+
+/// How often the spending digest email is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DigestFrequency {
+    /// No digest
+    Off,
+    /// Every Monday, covering the previous Monday to Sunday
+    Weekly,
+    /// On the 1st, covering the previous calendar month
+    Monthly,
+}
+
+impl DigestFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Off => "off",
+            DigestFrequency::Weekly => "weekly",
+            DigestFrequency::Monthly => "monthly",
+        }
+    }
+}
+
+/// Database entity for notification preferences
+#[derive(Debug, FromRow)]
+pub struct NotificationPreferences {
+    pub digest_frequency: String,
+    pub digest_sent_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for replacing notification preferences
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNotificationPreferencesDto {
+    pub digest_frequency: DigestFrequency,
+}
+
+/// Response for GET/PUT /notifications/preferences
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferencesResponse {
+    #[schema(value_type = DigestFrequency)]
+    pub digest_frequency: String,
+    /// When the last digest went out
+    pub last_digest_sent_at: Option<DateTime<Utc>>,
+    /// When the preferences were last saved; absent until the first save
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl NotificationPreferencesResponse {
+    pub fn from_preferences(preferences: Option<NotificationPreferences>) -> Self {
+        match preferences {
+            Some(p) => Self {
+                digest_frequency: p.digest_frequency,
+                last_digest_sent_at: p.digest_sent_at,
+                updated_at: Some(p.updated_at),
+            },
+            None => Self {
+                digest_frequency: DigestFrequency::Off.as_str().to_string(),
+                last_digest_sent_at: None,
+                updated_at: None,
+            },
+        }
+    }
+}
+
+/// A digest claimed for sending: the recipient and the period it covers
+#[derive(Debug, Clone, FromRow)]
+pub struct DigestClaim {
+    pub owner_id: Uuid,
+    pub email: String,
+    pub full_name: Option<String>,
+    pub currency: String,
+    pub frequency: String,
+    /// First day of the period, in the owner's timezone
+    pub period_start: NaiveDate,
+    /// Day after the period's last day
+    pub period_end: NaiveDate,
+    /// Period end of the digest before this one, restored if sending fails
+    pub previous_period_end: Option<NaiveDate>,
+}
+
+/// Spending and income over a digest period
+#[derive(Debug, Default, FromRow)]
+pub struct DigestTotals {
+    pub spent: Decimal,
+    pub income: Decimal,
+    pub transaction_count: i64,
+}
+
+/// One of the categories spent most in over a digest period
+#[derive(Debug, FromRow)]
+pub struct DigestCategory {
+    pub name: String,
+    pub spent: Decimal,
+}
+
+/// The budget for the month a digest period ends in
+#[derive(Debug, FromRow)]
+pub struct DigestBudget {
+    pub allocated: Decimal,
+    pub spent: Decimal,
+    /// Categories whose spending exceeds their allocation
+    pub over_budget: Vec<String>,
+}
+
+/// Everything in one spending digest email
+#[derive(Debug)]
+pub struct SpendingDigest {
+    pub claim: DigestClaim,
+    pub totals: DigestTotals,
+    pub top_categories: Vec<DigestCategory>,
+    pub budget: Option<DigestBudget>,
+}
+
+impl SpendingDigest {
+    /// Render the digest as a plain-text email
+    pub fn to_email(&self) -> EmailMessage {
+        let claim = &self.claim;
+        let currency = &claim.currency;
+        let last_day = claim
+            .period_end
+            .checked_sub_days(Days::new(1))
+            .unwrap_or(claim.period_end);
+        let period = format!(
+            "{} - {}",
+            claim.period_start.format("%b %-d, %Y"),
+            last_day.format("%b %-d, %Y")
+        );
+        let unit = if claim.frequency == DigestFrequency::Monthly.as_str() {
+            "month"
+        } else {
+            "week"
+        };
+        let greeting = claim
+            .full_name
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or("there");
+
+        let mut body = format!(
+            "Hi {greeting},\n\nHere is your spending for {period}.\n\n\
+             Spent: {:.2} {currency}\nIncome: {:.2} {currency}\nTransactions: {}\n\n",
+            self.totals.spent, self.totals.income, self.totals.transaction_count
+        );
+
+        if self.top_categories.is_empty() {
+            body.push_str(&format!("No spending this {unit}.\n\n"));
+        } else {
+            body.push_str("Top categories:\n");
+            for category in &self.top_categories {
+                body.push_str(&format!("  {}: {:.2}\n", category.name, category.spent));
+            }
+            body.push('\n');
+        }
+
+        let budget_month = NaiveDate::from_ymd_opt(last_day.year(), last_day.month(), 1)
+            .unwrap_or(last_day)
+            .format("%B %Y");
+        match &self.budget {
+            Some(budget) => {
+                body.push_str(&format!(
+                    "{budget_month} budget: {:.2} of {:.2} spent",
+                    budget.spent, budget.allocated
+                ));
+                if budget.allocated > Decimal::ZERO {
+                    let percent = (budget.spent * Decimal::from(100) / budget.allocated).round();
+                    body.push_str(&format!(" ({percent}%)"));
+                }
+                body.push('\n');
+                if !budget.over_budget.is_empty() {
+                    body.push_str(&format!("Over budget: {}\n", budget.over_budget.join(", ")));
+                }
+            }
+            None => body.push_str(&format!("No budget for {budget_month}.\n")),
+        }
+
+        body.push_str(&format!(
+            "\nYou're receiving this because {} digests are on in your notification preferences.\n",
+            claim.frequency
+        ));
+
+        EmailMessage {
+            to: claim.email.clone(),
+            subject: format!("Your {} spending digest: {period}", claim.frequency),
+            body,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification() -> Notification {
+        Notification {
+            id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            kind: kinds::BUDGET_THRESHOLD.to_string(),
+            category_id: Some(Uuid::new_v4()),
+            category_name: Some("Groceries".to_string()),
+            threshold_percent: Some(80),
+            spent_amount: Some(Decimal::new(400, 0)),
+            allocated_amount: Some(Decimal::new(500, 0)),
+            read_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_threshold_message_names_category_and_amounts() {
+        let response = NotificationResponse::from(notification());
+        assert_eq!(
+            response.message,
+            "Groceries reached 80% of its budget (400.00 of 500.00)"
+        );
+        assert!(!response.read);
+    }
+
+    #[test]
+    fn test_thresholds_must_be_positive_percentages() {
+        let dto = |thresholds: Vec<i32>| UpdateCategoryAlertsDto { thresholds };
+        assert!(dto(vec![80, 100]).validate().is_ok());
+        assert!(dto(vec![]).validate().is_ok());
+        assert!(dto(vec![0]).validate().is_err());
+        assert!(dto(vec![1001]).validate().is_err());
+        assert!(dto((1..=11).collect()).validate().is_err());
+    }
+
+    fn digest(frequency: DigestFrequency) -> SpendingDigest {
+        SpendingDigest {
+            claim: DigestClaim {
+                owner_id: Uuid::new_v4(),
+                email: "me@example.com".to_string(),
+                full_name: Some("Ada".to_string()),
+                currency: "USD".to_string(),
+                frequency: frequency.as_str().to_string(),
+                period_start: NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+                period_end: NaiveDate::from_ymd_opt(2026, 4, 1).unwrap(),
+                previous_period_end: None,
+            },
+            totals: DigestTotals {
+                spent: Decimal::new(15050, 2),
+                income: Decimal::new(2000, 0),
+                transaction_count: 3,
+            },
+            top_categories: vec![DigestCategory {
+                name: "Groceries".to_string(),
+                spent: Decimal::new(12050, 2),
+            }],
+            budget: Some(DigestBudget {
+                allocated: Decimal::new(200, 0),
+                spent: Decimal::new(15050, 2),
+                over_budget: vec!["Dining".to_string()],
+            }),
+        }
+    }
+
+    #[test]
+    fn test_digest_email_covers_totals_categories_and_budget() {
+        let email = digest(DigestFrequency::Monthly).to_email();
+        assert_eq!(email.to, "me@example.com");
+        assert_eq!(
+            email.subject,
+            "Your monthly spending digest: Mar 1, 2026 - Mar 31, 2026"
+        );
+        assert!(email.body.starts_with("Hi Ada,"));
+        assert!(email.body.contains("Spent: 150.50 USD"));
+        assert!(email.body.contains("Income: 2000.00 USD"));
+        assert!(email.body.contains("  Groceries: 120.50\n"));
+        assert!(email
+            .body
+            .contains("March 2026 budget: 150.50 of 200.00 spent (75%)"));
+        assert!(email.body.contains("Over budget: Dining"));
+    }
+
+    #[test]
+    fn test_digest_email_without_spending_or_budget() {
+        let mut digest = digest(DigestFrequency::Weekly);
+        digest.claim.full_name = None;
+        digest.top_categories.clear();
+        digest.budget = None;
+
+        let email = digest.to_email();
+        assert!(email.body.starts_with("Hi there,"));
+        assert!(email.body.contains("No spending this week."));
+        assert!(email.body.contains("No budget for March 2026."));
+    }
+}
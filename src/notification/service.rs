@@ -0,0 +1,458 @@
+use chrono::{Datelike, Days};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{
+    kinds, DigestBudget, DigestCategory, DigestClaim, DigestFrequency, DigestTotals, Notification,
+    NotificationPreferences, NotificationsQuery, SpendingDigest,
+};
+use crate::errors::AppError;
+
+const NOTIFICATION_COLUMNS: &str =
+    "n.id, n.owner_id, n.kind, n.category_id, c.name AS category_name, \
+     n.threshold_percent, n.spent_amount, n.allocated_amount, n.read_at, n.created_at";
+
+/// Categories listed in a spending digest
+const DIGEST_TOP_CATEGORIES: i64 = 5;
+
+/// Service layer for spending alerts and the notifications they raise.
+pub struct NotificationService;
+
+impl NotificationService {
+    /// The user's alert thresholds for a category they can see, lowest first
+    pub async fn category_thresholds(
+        pool: &PgPool,
+        owner_id: Uuid,
+        category_id: Uuid,
+    ) -> Result<Vec<i32>, AppError> {
+        Self::verify_category(pool, owner_id, category_id).await?;
+
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT threshold_percent
+            FROM category_alert_rules
+            WHERE owner_id = $1 AND category_id = $2
+            ORDER BY threshold_percent
+            "#,
+        )
+        .bind(owner_id)
+        .bind(category_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Replace the user's alert thresholds for a category. Thresholds kept
+    /// from before keep their state, so they don't notify again.
+    pub async fn set_category_thresholds(
+        pool: &PgPool,
+        owner_id: Uuid,
+        category_id: Uuid,
+        thresholds: &[i32],
+    ) -> Result<Vec<i32>, AppError> {
+        Self::verify_category(pool, owner_id, category_id).await?;
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM category_alert_rules
+            WHERE owner_id = $1 AND category_id = $2 AND NOT (threshold_percent = ANY($3))
+            "#,
+        )
+        .bind(owner_id)
+        .bind(category_id)
+        .bind(thresholds)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO category_alert_rules (owner_id, category_id, threshold_percent)
+            SELECT $1, $2, UNNEST($3::INTEGER[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(owner_id)
+        .bind(category_id)
+        .bind(thresholds)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // Spending may already be past a new threshold
+        Self::evaluate_thresholds(&mut tx, &[category_id]).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Self::category_thresholds(pool, owner_id, category_id).await
+    }
+
+    /// Check the alert rules on the given categories against their current
+    /// spending, inside the caller's database transaction. A rule raises one
+    /// notification when its threshold is reached and rearms once spending
+    /// falls back below it.
+    pub async fn evaluate_thresholds(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        category_ids: &[Uuid],
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            WITH state AS (
+                SELECT r.id, r.owner_id, r.category_id, r.threshold_percent, r.notified_at,
+                       c.spent_amount, c.allocated_amount,
+                       c.allocated_amount > 0
+                           AND c.spent_amount * 100 >= c.allocated_amount * r.threshold_percent AS reached
+                FROM category_alert_rules r
+                JOIN categories c ON c.id = r.category_id
+                WHERE r.category_id = ANY($1)
+                FOR UPDATE OF r
+            ),
+            rearmed AS (
+                UPDATE category_alert_rules r
+                SET notified_at = NULL
+                FROM state s
+                WHERE r.id = s.id AND NOT s.reached AND s.notified_at IS NOT NULL
+            ),
+            fired AS (
+                UPDATE category_alert_rules r
+                SET notified_at = NOW()
+                FROM state s
+                WHERE r.id = s.id AND s.reached AND s.notified_at IS NULL
+                RETURNING s.owner_id, s.category_id, s.threshold_percent, s.spent_amount, s.allocated_amount
+            )
+            INSERT INTO notifications
+                (owner_id, kind, category_id, threshold_percent, spent_amount, allocated_amount)
+            SELECT owner_id, $2, category_id, threshold_percent, spent_amount, allocated_amount
+            FROM fired
+            "#,
+        )
+        .bind(category_ids)
+        .bind(kinds::BUDGET_THRESHOLD)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// The user's notifications, newest first
+    pub async fn list(
+        pool: &PgPool,
+        owner_id: Uuid,
+        query: &NotificationsQuery,
+    ) -> Result<Vec<Notification>, AppError> {
+        sqlx::query_as::<_, Notification>(&format!(
+            r#"
+            SELECT {NOTIFICATION_COLUMNS}
+            FROM notifications n
+            LEFT JOIN categories c ON c.id = n.category_id
+            WHERE n.owner_id = $1 AND (NOT $2 OR n.read_at IS NULL)
+            ORDER BY n.created_at DESC, n.id
+            LIMIT $3
+            "#
+        ))
+        .bind(owner_id)
+        .bind(query.unread_only)
+        .bind(query.limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// How many of the user's notifications are unread
+    pub async fn unread_count(pool: &PgPool, owner_id: Uuid) -> Result<i64, AppError> {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM notifications WHERE owner_id = $1 AND read_at IS NULL",
+        )
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Mark a notification read or unread. Reading it again keeps the first read time.
+    pub async fn set_read(
+        pool: &PgPool,
+        owner_id: Uuid,
+        notification_id: Uuid,
+        read: bool,
+    ) -> Result<Notification, AppError> {
+        sqlx::query_as::<_, Notification>(&format!(
+            r#"
+            WITH updated AS (
+                UPDATE notifications
+                SET read_at = CASE WHEN $3 THEN COALESCE(read_at, NOW()) END
+                WHERE id = $1 AND owner_id = $2
+                RETURNING *
+            )
+            SELECT {NOTIFICATION_COLUMNS}
+            FROM updated n
+            LEFT JOIN categories c ON c.id = n.category_id
+            "#
+        ))
+        .bind(notification_id)
+        .bind(owner_id)
+        .bind(read)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Notification not found".to_string()))
+    }
+
+    /// Mark every unread notification read, returning how many there were
+    pub async fn mark_all_read(pool: &PgPool, owner_id: Uuid) -> Result<u64, AppError> {
+        sqlx::query(
+            "UPDATE notifications SET read_at = NOW() WHERE owner_id = $1 AND read_at IS NULL",
+        )
+        .bind(owner_id)
+        .execute(pool)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// The user's notification preferences, if any have been saved
+    pub async fn preferences(
+        pool: &PgPool,
+        owner_id: Uuid,
+    ) -> Result<Option<NotificationPreferences>, AppError> {
+        sqlx::query_as::<_, NotificationPreferences>(
+            r#"
+            SELECT digest_frequency, digest_sent_at, updated_at
+            FROM notification_preferences
+            WHERE owner_id = $1
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Replace the user's notification preferences. Turning the digest on or
+    /// changing its frequency starts with the period in progress, so no
+    /// digest goes out for time before the change.
+    pub async fn set_preferences(
+        pool: &PgPool,
+        owner_id: Uuid,
+        digest_frequency: DigestFrequency,
+    ) -> Result<NotificationPreferences, AppError> {
+        sqlx::query_as::<_, NotificationPreferences>(
+            r#"
+            INSERT INTO notification_preferences (owner_id, digest_frequency, digest_period_end)
+            SELECT u.id, $2,
+                   CASE $2
+                       WHEN 'weekly' THEN date_trunc('week', NOW() AT TIME ZONE u.timezone)::DATE
+                       WHEN 'monthly' THEN date_trunc('month', NOW() AT TIME ZONE u.timezone)::DATE
+                   END
+            FROM users u
+            WHERE u.id = $1
+            ON CONFLICT (owner_id) DO UPDATE
+            SET digest_frequency = EXCLUDED.digest_frequency,
+                digest_period_end = CASE
+                    WHEN notification_preferences.digest_frequency = EXCLUDED.digest_frequency
+                        THEN notification_preferences.digest_period_end
+                    ELSE EXCLUDED.digest_period_end
+                END,
+                updated_at = NOW()
+            RETURNING digest_frequency, digest_sent_at, updated_at
+            "#,
+        )
+        .bind(owner_id)
+        .bind(digest_frequency.as_str())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Claim every digest whose period has ended since the user's last one:
+    /// the previous week (Monday to Sunday) or calendar month in their
+    /// timezone. Each period is claimed once, even across instances.
+    pub async fn claim_digests(pool: &PgPool) -> Result<Vec<DigestClaim>, AppError> {
+        sqlx::query_as::<_, DigestClaim>(
+            r#"
+            WITH due AS (
+                SELECT p.owner_id, p.digest_frequency, p.digest_period_end AS previous_period_end,
+                       CASE p.digest_frequency
+                           WHEN 'weekly' THEN date_trunc('week', NOW() AT TIME ZONE u.timezone)::DATE
+                           ELSE date_trunc('month', NOW() AT TIME ZONE u.timezone)::DATE
+                       END AS period_end
+                FROM notification_preferences p
+                JOIN users u ON u.id = p.owner_id
+                WHERE p.digest_frequency <> 'off'
+                FOR UPDATE OF p SKIP LOCKED
+            ),
+            claimed AS (
+                UPDATE notification_preferences p
+                SET digest_period_end = d.period_end
+                FROM due d
+                WHERE p.owner_id = d.owner_id
+                  AND (d.previous_period_end IS NULL OR d.previous_period_end < d.period_end)
+                RETURNING d.owner_id, d.digest_frequency, d.period_end, d.previous_period_end
+            )
+            SELECT c.owner_id, u.email, u.full_name, u.default_currency AS currency,
+                   c.digest_frequency AS frequency,
+                   CASE c.digest_frequency
+                       WHEN 'weekly' THEN c.period_end - 7
+                       ELSE (c.period_end - INTERVAL '1 month')::DATE
+                   END AS period_start,
+                   c.period_end, c.previous_period_end
+            FROM claimed c
+            JOIN users u ON u.id = c.owner_id
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Record that a claimed digest went out
+    pub async fn mark_digest_sent(pool: &PgPool, owner_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE notification_preferences SET digest_sent_at = NOW() WHERE owner_id = $1",
+        )
+        .bind(owner_id)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Give back a claimed digest that couldn't be sent, so the next run retries it
+    pub async fn release_digest(pool: &PgPool, claim: &DigestClaim) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE notification_preferences
+            SET digest_period_end = $3
+            WHERE owner_id = $1 AND digest_period_end = $2
+            "#,
+        )
+        .bind(claim.owner_id)
+        .bind(claim.period_end)
+        .bind(claim.previous_period_end)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Compile a claimed digest from the user's own budgets: totals and top
+    /// categories over the period, and the budget for the month it ends in
+    pub async fn compile_digest(
+        pool: &PgPool,
+        claim: DigestClaim,
+    ) -> Result<SpendingDigest, AppError> {
+        const PERIOD_TRANSACTIONS: &str = r#"
+            FROM transactions t
+            JOIN categories c ON c.id = t.category_id
+            JOIN budgets b ON b.id = c.budget_id
+            JOIN users u ON u.id = b.owner_id
+            WHERE b.owner_id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.transaction_date >= $2::TIMESTAMP AT TIME ZONE u.timezone
+              AND t.transaction_date < $3::TIMESTAMP AT TIME ZONE u.timezone
+        "#;
+
+        let totals = sqlx::query_as::<_, DigestTotals>(&format!(
+            r#"
+            SELECT
+                COALESCE(SUM(t.reporting_amount) FILTER (WHERE t.reporting_type = 'expense'), 0) AS spent,
+                COALESCE(SUM(t.reporting_amount) FILTER (WHERE t.reporting_type = 'income'), 0) AS income,
+                COUNT(*) AS transaction_count
+            {PERIOD_TRANSACTIONS}
+            "#
+        ))
+        .bind(claim.owner_id)
+        .bind(claim.period_start)
+        .bind(claim.period_end)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let top_categories = sqlx::query_as::<_, DigestCategory>(&format!(
+            r#"
+            SELECT c.name, SUM(t.reporting_amount) AS spent
+            {PERIOD_TRANSACTIONS}
+              AND t.reporting_type = 'expense'
+            GROUP BY c.name
+            HAVING SUM(t.reporting_amount) > 0
+            ORDER BY spent DESC, c.name
+            LIMIT $4
+            "#
+        ))
+        .bind(claim.owner_id)
+        .bind(claim.period_start)
+        .bind(claim.period_end)
+        .bind(DIGEST_TOP_CATEGORIES)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let last_day = claim
+            .period_end
+            .checked_sub_days(Days::new(1))
+            .unwrap_or(claim.period_end);
+        let budget = sqlx::query_as::<_, DigestBudget>(
+            r#"
+            SELECT
+                COALESCE(SUM(c.allocated_amount), 0) AS allocated,
+                COALESCE(SUM(c.spent_amount), 0) AS spent,
+                COALESCE(
+                    ARRAY_AGG(c.name ORDER BY c.name) FILTER (WHERE c.spent_amount > c.allocated_amount),
+                    '{}'
+                ) AS over_budget
+            FROM budgets b
+            LEFT JOIN categories c ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND b.year = $2 AND b.month = $3
+            GROUP BY b.id
+            "#,
+        )
+        .bind(claim.owner_id)
+        .bind(last_day.year() as i16)
+        .bind(last_day.month0() as i16)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(SpendingDigest {
+            claim,
+            totals,
+            top_categories,
+            budget,
+        })
+    }
+
+    /// Fail with 404 unless the user can see the category's budget
+    async fn verify_category(
+        pool: &PgPool,
+        owner_id: Uuid,
+        category_id: Uuid,
+    ) -> Result<(), AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM categories c
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE c.id = $1 AND budget_access(b.id, $2, FALSE)
+            )
+            "#,
+        )
+        .bind(category_id)
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !exists {
+            return Err(AppError::NotFound("Category not found".to_string()));
+        }
+        Ok(())
+    }
+}
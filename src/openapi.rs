@@ -1,26 +1,118 @@
 use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
 use utoipa::{Modify, OpenApi};
 
+use crate::account::charges::{ChargesConfig, UpdateChargesDto};
+use crate::account::consolidation::CurrencyConversion;
+use crate::account::loan::{
+    AmortizationResponse, AmortizationRow, LoanTerms, PayoffSummary, UpdateLoanDto,
+};
 use crate::account::models::{
     AccountResponse, AccountType, AccountsListResponse, AccountsSummary, AccountsSummaryResponse,
-    CreateAccountDto, CurrencySummary, DeleteResponse, UpdateAccountDto, UpdateBalanceDto,
+    BalanceCheck, BalanceRecomputeSummary, CreateAccountDto, CurrencySummary, DeleteResponse,
+    ReorderAccountsDto, UpdateAccountDto, UpdateBalanceDto,
+};
+use crate::account::redenomination::{ChangeCurrencyDto, CurrencyChange};
+use crate::account::rewards::{
+    CategoryRewardRate, RewardPeriod, RewardsConfig, RewardsReport, UpdateRewardsDto,
 };
+use crate::account::statement::{Statement, StatementFormat, StatementLine};
+use crate::audit::log::{AuditLogEntry, AuditLogResponse};
+use crate::audit::models::{ActivityFeedResponse, ActivityItem, UndoResponse};
 use crate::auth::models::{
-    AuthTokenResponse, CreateUserDto, GoogleLoginDto, LoginDto, RefreshTokenDto, UserResponseDto,
+    AuthTokenResponse, CreateScopedTokenDto, CreateUserDto, ForgotPasswordDto, GoogleLoginDto,
+    LoginDto, RefreshTokenDto, ResetPasswordDto, ScopedTokenResponse, UpdateTimezoneDto,
+    UserResponseDto,
+};
+use crate::auth::scopes::Scope;
+use crate::auth::sessions::SessionResponse;
+use crate::bank_sync::models::{
+    ApproveBankImportDto, ApproveBankImportResponse, BankAccountLink, BankConnectionResponse,
+    BankImport, BankImportStatus, BankSyncResult, LinkBankDto, UpdateBankAccountLinkDto,
+};
+use crate::bill::models::{
+    BillPayment, BillResponse, BillStatus, CreateBillDto, PayBillDto, PayBillResponse,
+    UpdateBillDto,
 };
 use crate::budget::models::{
-    BudgetResponse, CreateBudgetDto, UpdateBudgetDto, UpdateIncomeDto, UpdateSavingsRateDto,
+    AllocationSuggestion, AutoAllocateResponse, BudgetHealthResponse, BudgetProjectionResponse,
+    BudgetResponse, BudgetTotals, BudgetWithCategoriesResponse, CategoryAllocation,
+    CategoryProjection, CategoryThresholdMetric, CopyBudgetDto, CreateBudgetDto, MonthSummary,
+    MoveAllocationDto, MoveAllocationResponse, PaceMetric, SavingsMetric, UnallocatedResponse,
+    UpdateBudgetDto, UpdateIncomeDto, UpdateSavingsRateDto,
+};
+use crate::budget::sharing::{BudgetRole, InvitationResponse, InviteMemberDto, MemberResponse};
+use crate::category::models::{
+    AllocationWarning, CategoryResponse, CategoryStatsResponse, CreateCategoryDto,
+    MonthlyCategorySpend, SpendingTrend, UpdateCategoryDto,
+};
+use crate::category::templates::{
+    ApplyCategoryTemplateDto, CategoryTemplateItem, CategoryTemplateResponse,
+    CreateCategoryTemplateDto,
+};
+use crate::currency::models::{
+    CurrenciesListResponse, CurrencyResponse, ExchangeRateResponse, PaginatedRatesResponse,
+    SyncRatesResponse,
+};
+use crate::debug::models::{BalanceInvariantReport, LoadProfile, LoadSummary};
+use crate::errors::{ErrorCatalogEntry, ErrorCode, ErrorResponse, FieldError};
+use crate::event::models::{EventListResponse, EventResponse};
+use crate::funding::models::{CreateFundingRuleDto, FundingRuleResponse, UpdateFundingRuleDto};
+use crate::health::models::{
+    ComponentHealth, ComponentStatus, LivenessResponse, ReadinessComponents, ReadinessResponse,
+    ReadinessStatus,
+};
+use crate::holding::models::{
+    CreateHoldingDto, HoldingResponse, HoldingsResponse, UpdateHoldingDto, ValuationSnapshot,
 };
-use crate::category::models::{CategoryResponse, CreateCategoryDto, UpdateCategoryDto};
-use crate::currency::models::{CurrenciesListResponse, CurrencyResponse, SyncRatesResponse};
-use crate::errors::ErrorResponse;
+use crate::import::models::{ImportFormat, ImportStatementResponse};
+use crate::jobs::models::{
+    BankSyncSummary, BillReminderSummary, ChargePostingSummary, DigestSummary, LoanPostingSummary,
+    RefreshTokenCleanupResponse, RetentionPolicyReport, RetentionRunResponse, ValuationSummary,
+};
+use crate::notification::models::{
+    CategoryAlertsResponse, DigestFrequency, MarkAllReadResponse, NotificationListResponse,
+    NotificationPreferencesResponse, NotificationResponse, UpdateCategoryAlertsDto,
+    UpdateNotificationDto, UpdateNotificationPreferencesDto,
+};
+use crate::payee::models::{CreatePayeeDto, PayeeResponse, PayeeSuggestion};
+use crate::payee::rules::{ApplyRulesResponse, CreateRuleDto, RuleResponse, UpdateRuleDto};
+use crate::preferences::models::{DateFormat, FirstDayOfWeek, Preferences, UpdatePreferencesDto};
+use crate::query::models::{QueryMetric, QueryRequest, QueryResponse, StructuredQuery};
+use crate::receipt::models::{
+    ConfirmReceiptDto, ConfirmReceiptResponse, InboundEmailPayload, InboundEmailResponse,
+    ReceiptAddressResponse, ReceiptDraft, ReceiptDraftStatus,
+};
+use crate::report::household::{HouseholdCurrencyTotals, HouseholdReport, MemberContribution};
+use crate::report::models::{
+    CategoryComparison, MonthComparisonResponse, MonthRef, PayerReimbursements,
+    ReimbursementReport, ReportFormat, SpendComparison, SpendDelta, TaxCategoryTotal, TaxReport,
+};
+use crate::search::models::{SearchGroup, SearchResponse, SearchResult, SearchResultType};
+use crate::settings::models::{DashboardLayout, DashboardSettingsResponse};
+use crate::tag::models::{CreateTagDto, SetTransactionTagsDto, TagResponse, UpdateTagDto};
+use crate::template::models::{
+    CreateTemplateDto, TemplateResponse, UpdateTemplateDto, UseTemplateDto,
+};
+use crate::transaction::account_summary::AccountSpendingSummary;
+use crate::transaction::bulk::{
+    BulkDeleteResponse, BulkDeleteTransactionsDto, BulkUpdateResponse, BulkUpdateTransactionsDto,
+};
+use crate::transaction::duplicates::{DuplicatePair, MergeTransactionDto};
+use crate::transaction::matching::TransactionMatch;
 use crate::transaction::models::{
-    CategoriesQueryDto, CategorySpendingSummary, CreateTransactionDto, EmbeddedAccountInfo,
-    EmbeddedCategoryInfo, PaginatedDetailedTransactionResponse, PaginatedTransactionResponse,
-    TransactionDetailResponse, TransactionResponse, TransactionSummary, TransactionType,
-    UpdateTransactionDto,
+    CategoriesQueryDto, CategorySpendingSummary, CategorySuggestion, CreateTransactionDto,
+    DescriptionSuggestion, EmbeddedAccountInfo, EmbeddedCategoryInfo,
+    PaginatedDetailedTransactionResponse, PaginatedTransactionResponse, QuickTransactionDto,
+    QuickTransactionResponse, RefundTransactionDto, TransactionDetailResponse, TransactionResponse,
+    TransactionStatus, TransactionSummary, TransactionType, UpdateTransactionDto,
 };
-
+use crate::transaction::reimbursement::{ReimbursementStatus, UpdateReimbursementDto};
+use crate::transaction::trash::{PaginatedTrashResponse, TrashedTransactionResponse};
+use crate::webhook::models::{
+    CreateWebhookDto, ReplayResponse, UpdateWebhookDto, WebhookDelivery,
+    WebhookDeliveryListResponse, WebhookEvent, WebhookResponse, WebhookSecretResponse,
+};
+use crate::widget::models::{WidgetSummaryResponse, WidgetTransaction};
 /// Security scheme modifier for Bearer token authentication
 struct SecurityAddon;
 
@@ -61,109 +153,507 @@ impl Modify for SecurityAddon {
     ),
     tags(
         (name = "Health", description = "Health check endpoints"),
+        (name = "Errors", description = "Machine-readable error codes"),
         (name = "Auth", description = "Authentication and user management"),
         (name = "Budgets", description = "Monthly budget management"),
         (name = "Accounts", description = "Financial account management"),
         (name = "Categories", description = "Budget category management"),
         (name = "Transactions", description = "Transaction management with atomic balance updates"),
-        (name = "Currencies", description = "Currency and exchange rate management")
+        (name = "Payees", description = "Payees and payee lookups for transaction entry"),
+        (name = "Rules", description = "Rules filing transactions under a category and payee by description"),
+        (name = "Templates", description = "Saved transactions for one-tap entry"),
+        (name = "Funding rules", description = "Rules splitting new income across categories and savings accounts"),
+        (name = "Tags", description = "Labels on transactions, independent of categories"),
+        (name = "Notifications", description = "Category spending alerts, the notifications they raise, and the spending digest email"),
+        (name = "Bills", description = "Recurring bills with due dates, reminders and payments"),
+        (name = "Receipts", description = "Receipts forwarded by email, confirmed into transactions"),
+        (name = "Bank Sync", description = "Institutions linked through Plaid, with pulled transactions queued for review"),
+        (name = "Holdings", description = "Securities held in investment accounts, with valuation"),
+        (name = "Currencies", description = "Currency and exchange rate management"),
+        (name = "Widgets", description = "Lightweight payloads for home-screen widgets"),
+        (name = "Settings", description = "Per-user client settings"),
+        (name = "Reports", description = "Spending reports and comparisons"),
+        (name = "Search", description = "Unified search for command-palette style lookups"),
+        (name = "Webhooks", description = "Signed event deliveries to external endpoints"),
+        (name = "Events", description = "Append-only log of domain events, also streamed live"),
+        (name = "Query", description = "Natural-language questions about spending"),
+        (name = "History", description = "Activity feed and undo"),
+        (name = "Admin", description = "Administrative maintenance operations"),
+        (name = "Debug", description = "Diagnostics for staging environments, disabled in production")
     ),
     paths(
+        // Health endpoints
+        crate::health::handlers::health_check,
+        crate::health::handlers::liveness,
+        crate::health::handlers::readiness,
+        crate::errors::error_catalog,
         // Auth endpoints
         crate::auth::handlers::register,
         crate::auth::handlers::login,
         crate::auth::handlers::google_login,
         crate::auth::handlers::refresh,
+        crate::auth::handlers::forgot_password,
+        crate::auth::handlers::reset_password,
         crate::auth::handlers::logout,
         crate::auth::handlers::me,
+        crate::auth::handlers::list_sessions,
+        crate::auth::handlers::revoke_session,
+        crate::auth::handlers::create_scoped_token,
+        crate::auth::handlers::upload_avatar,
+        crate::auth::handlers::update_timezone,
         // Budget endpoints
         crate::budget::handlers::list_budgets,
         crate::budget::handlers::get_budget,
+        crate::budget::handlers::get_budget_full,
         crate::budget::handlers::get_budget_by_month_year,
+        crate::budget::handlers::get_year_summary,
+        crate::budget::handlers::get_unallocated,
+        crate::budget::handlers::get_budget_health,
+        crate::budget::handlers::get_budget_projection,
         crate::budget::handlers::create_budget,
+        crate::budget::handlers::copy_budget,
         crate::budget::handlers::update_budget,
         crate::budget::handlers::update_income,
         crate::budget::handlers::update_savings_rate,
+        crate::budget::handlers::auto_allocate,
+        crate::budget::handlers::move_allocation,
         crate::budget::handlers::delete_budget,
+        crate::budget::handlers::invite_member,
+        crate::budget::handlers::list_invitations,
+        crate::budget::handlers::accept_invitation,
+        crate::budget::handlers::list_members,
+        crate::budget::handlers::remove_member,
+        crate::budget::handlers::apply_category_template,
         // Account endpoints
         crate::account::handlers::list_accounts,
         crate::account::handlers::get_accounts_summary,
         crate::account::handlers::get_accounts_by_type,
         crate::account::handlers::get_account,
         crate::account::handlers::create_account,
+        crate::account::handlers::reorder_accounts,
         crate::account::handlers::update_account,
         crate::account::handlers::update_account_balance,
+        crate::account::handlers::change_account_currency,
+        crate::account::handlers::recompute_account_balance,
+        crate::account::handlers::recompute_all_balances,
         crate::account::handlers::delete_account,
+        crate::account::handlers::get_account_rewards,
+        crate::account::handlers::update_account_rewards,
+        crate::account::handlers::get_account_amortization,
+        crate::account::handlers::update_account_loan,
+        crate::account::handlers::get_account_charges,
+        crate::account::handlers::update_account_charges,
+        crate::account::handlers::get_account_statement,
+        // Holding endpoints
+        crate::holding::handlers::list_holdings,
+        crate::holding::handlers::create_holding,
+        crate::holding::handlers::update_holding,
+        crate::holding::handlers::delete_holding,
+        crate::holding::handlers::list_valuations,
         // Category endpoints
         crate::category::handlers::list_categories,
         crate::category::handlers::get_categories_by_budget,
+        crate::category::handlers::get_category_stats,
         crate::category::handlers::get_category,
         crate::category::handlers::create_category,
         crate::category::handlers::update_category,
         crate::category::handlers::delete_category,
+        crate::category::handlers::list_category_templates,
+        crate::category::handlers::get_category_template,
+        crate::category::handlers::create_category_template,
+        crate::category::handlers::delete_category_template,
         // Transaction endpoints
         crate::transaction::handlers::list_transactions,
         crate::transaction::handlers::get_by_category,
         crate::transaction::handlers::get_by_categories,
         crate::transaction::handlers::get_by_account,
         crate::transaction::handlers::get_summary,
+        crate::transaction::handlers::get_summary_by_account,
+        crate::transaction::handlers::suggest_category,
+        crate::transaction::handlers::suggest_descriptions,
+        crate::transaction::handlers::match_transactions,
+        crate::transaction::handlers::find_duplicate_transactions,
+        crate::transaction::handlers::list_trash,
+        crate::transaction::handlers::list_pending_transactions,
         crate::transaction::handlers::get_transaction,
         crate::transaction::handlers::create_transaction,
+        crate::transaction::handlers::quick_create_transaction,
+        crate::transaction::handlers::refund_transaction,
         crate::transaction::handlers::update_transaction,
+        crate::transaction::handlers::update_reimbursement,
+        crate::transaction::handlers::restore_transaction,
+        crate::transaction::handlers::approve_transaction,
+        crate::transaction::handlers::reject_transaction,
+        crate::transaction::handlers::merge_transaction,
         crate::transaction::handlers::delete_transaction,
+        crate::transaction::handlers::bulk_delete_transactions,
+        crate::transaction::handlers::bulk_update_transactions,
+        crate::import::handlers::import_statement,
+        crate::payee::handlers::autocomplete_payees,
+        crate::payee::handlers::list_payees,
+        crate::payee::handlers::create_payee,
+        crate::payee::handlers::delete_payee,
+        // Categorization rule endpoints
+        crate::payee::handlers::list_rules,
+        crate::payee::handlers::get_rule,
+        crate::payee::handlers::create_rule,
+        crate::payee::handlers::update_rule,
+        crate::payee::handlers::delete_rule,
+        crate::payee::handlers::apply_rules,
+        // Template endpoints
+        crate::template::handlers::list_templates,
+        crate::template::handlers::get_template,
+        crate::template::handlers::create_template,
+        crate::template::handlers::update_template,
+        crate::template::handlers::delete_template,
+        crate::template::handlers::create_from_template,
+        // Funding rule endpoints
+        crate::funding::handlers::list_funding_rules,
+        crate::funding::handlers::create_funding_rule,
+        crate::funding::handlers::update_funding_rule,
+        crate::funding::handlers::delete_funding_rule,
+        crate::tag::handlers::list_tags,
+        crate::tag::handlers::create_tag,
+        crate::tag::handlers::update_tag,
+        crate::tag::handlers::delete_tag,
+        crate::tag::handlers::get_transaction_tags,
+        crate::tag::handlers::set_transaction_tags,
+        crate::notification::handlers::get_category_alerts,
+        crate::notification::handlers::update_category_alerts,
+        crate::notification::handlers::list_notifications,
+        crate::notification::handlers::mark_all_notifications_read,
+        crate::notification::handlers::update_notification,
+        crate::notification::handlers::get_notification_preferences,
+        crate::notification::handlers::update_notification_preferences,
+        // Receipt endpoints
+        crate::receipt::handlers::get_receipt_address,
+        crate::receipt::handlers::rotate_receipt_address,
+        crate::receipt::handlers::list_receipt_drafts,
+        crate::receipt::handlers::confirm_receipt_draft,
+        crate::receipt::handlers::delete_receipt_draft,
+        crate::receipt::handlers::receive_inbound_email,
+        crate::bank_sync::handlers::link_bank,
+        crate::bank_sync::handlers::list_bank_connections,
+        crate::bank_sync::handlers::delete_bank_connection,
+        crate::bank_sync::handlers::sync_bank_connection,
+        crate::bank_sync::handlers::update_bank_account_link,
+        crate::bank_sync::handlers::list_bank_imports,
+        crate::bank_sync::handlers::approve_bank_import,
+        crate::bank_sync::handlers::dismiss_bank_import,
+        // Bill endpoints
+        crate::bill::handlers::list_bills,
+        crate::bill::handlers::upcoming_bills,
+        crate::bill::handlers::get_bill,
+        crate::bill::handlers::create_bill,
+        crate::bill::handlers::update_bill,
+        crate::bill::handlers::delete_bill,
+        crate::bill::handlers::list_bill_payments,
+        crate::bill::handlers::pay_bill,
+        crate::widget::handlers::get_widget_summary,
+        crate::settings::handlers::get_dashboard_settings,
+        crate::settings::handlers::update_dashboard_settings,
+        crate::preferences::handlers::get_preferences,
+        crate::preferences::handlers::update_preferences,
+        crate::query::handlers::run_query,
+        crate::report::handlers::compare_months,
+        crate::report::handlers::household_report,
+        crate::report::handlers::reimbursements,
+        crate::report::handlers::tax_report,
+        crate::search::handlers::search,
+        crate::webhook::handlers::list_webhooks,
+        crate::webhook::handlers::get_webhook,
+        crate::webhook::handlers::create_webhook,
+        crate::webhook::handlers::update_webhook,
+        crate::webhook::handlers::delete_webhook,
+        crate::webhook::handlers::list_webhook_deliveries,
+        crate::webhook::handlers::rotate_webhook_secret,
+        crate::webhook::handlers::test_webhook,
+        crate::webhook::handlers::replay_webhook,
+        crate::event::handlers::list_events,
+        crate::event::handlers::stream_events,
+        crate::audit::handlers::list_activity,
+        crate::audit::handlers::list_audit_log,
+        crate::audit::handlers::undo,
         // Currency endpoints
         crate::currency::handlers::list_currencies,
+        crate::currency::handlers::list_exchange_rates,
         crate::currency::handlers::sync_exchange_rates,
+        // Admin endpoints
+        crate::jobs::handlers::run_refresh_token_cleanup,
+        crate::jobs::handlers::run_retention,
+        crate::jobs::handlers::run_integrity_audit,
+        crate::jobs::handlers::run_bill_reminders,
+        crate::jobs::handlers::run_loan_postings,
+        crate::jobs::handlers::run_charge_postings,
+        crate::jobs::handlers::run_valuation,
+        crate::jobs::handlers::run_digests,
+        crate::jobs::handlers::run_bank_sync,
+        // Debug endpoints
+        crate::debug::handlers::check_balance_invariant,
+        crate::debug::handlers::generate_load_data,
     ),
     components(
         schemas(
             // Error response
             ErrorResponse,
+            FieldError,
+            ErrorCode,
+            ErrorCatalogEntry,
+            // Health schemas
+            LivenessResponse,
+            ReadinessResponse,
+            ReadinessStatus,
+            ReadinessComponents,
+            ComponentHealth,
+            ComponentStatus,
             // Auth schemas
             CreateUserDto,
             LoginDto,
             GoogleLoginDto,
+            ForgotPasswordDto,
+            ResetPasswordDto,
             RefreshTokenDto,
             UserResponseDto,
+            SessionResponse,
             AuthTokenResponse,
+            Scope,
+            CreateScopedTokenDto,
+            ScopedTokenResponse,
+            UpdateTimezoneDto,
             // Budget schemas
             BudgetResponse,
+            BudgetTotals,
+            UnallocatedResponse,
+            MonthSummary,
+            BudgetHealthResponse,
+            PaceMetric,
+            CategoryThresholdMetric,
+            SavingsMetric,
+            BudgetProjectionResponse,
+            CategoryProjection,
+            AutoAllocateResponse,
+            AllocationSuggestion,
+            MoveAllocationDto,
+            MoveAllocationResponse,
+            CategoryAllocation,
             CreateBudgetDto,
+            CopyBudgetDto,
+            BudgetWithCategoriesResponse,
             UpdateBudgetDto,
             UpdateIncomeDto,
             UpdateSavingsRateDto,
+            BudgetRole,
+            InviteMemberDto,
+            InvitationResponse,
+            MemberResponse,
             // Account schemas
             AccountType,
             AccountResponse,
             AccountsListResponse,
             AccountsSummary,
             CurrencySummary,
+            CurrencyConversion,
+            RewardsConfig,
+            RewardsReport,
+            RewardPeriod,
+            CategoryRewardRate,
+            UpdateRewardsDto,
+            LoanTerms,
+            UpdateLoanDto,
+            AmortizationResponse,
+            AmortizationRow,
+            PayoffSummary,
+            ChargesConfig,
+            Statement,
+            StatementLine,
+            StatementFormat,
+            UpdateChargesDto,
             AccountsSummaryResponse,
             CreateAccountDto,
             UpdateAccountDto,
+            ReorderAccountsDto,
             UpdateBalanceDto,
+            ChangeCurrencyDto,
+            CurrencyChange,
+            BalanceCheck,
+            BalanceRecomputeSummary,
             DeleteResponse,
             // Category schemas
             CategoryResponse,
+            AllocationWarning,
+            CategoryStatsResponse,
+            MonthlyCategorySpend,
+            SpendingTrend,
             CreateCategoryDto,
             UpdateCategoryDto,
+            CategoryTemplateItem,
+            CategoryTemplateResponse,
+            CreateCategoryTemplateDto,
+            ApplyCategoryTemplateDto,
             // Transaction schemas
             TransactionType,
+            TransactionStatus,
+            ImportFormat,
+            ImportStatementResponse,
             TransactionResponse,
             TransactionDetailResponse,
             EmbeddedAccountInfo,
             EmbeddedCategoryInfo,
             PaginatedTransactionResponse,
             PaginatedDetailedTransactionResponse,
+            TrashedTransactionResponse,
+            PaginatedTrashResponse,
+            BulkDeleteTransactionsDto,
+            BulkUpdateTransactionsDto,
+            BulkDeleteResponse,
+            BulkUpdateResponse,
             TransactionSummary,
+            AccountSpendingSummary,
+            CategorySuggestion,
+            DescriptionSuggestion,
+            TransactionMatch,
+            DuplicatePair,
+            MergeTransactionDto,
+            WidgetSummaryResponse,
+            WidgetTransaction,
+            DashboardLayout,
+            DashboardSettingsResponse,
+            Preferences,
+            UpdatePreferencesDto,
+            FirstDayOfWeek,
+            DateFormat,
+            MonthComparisonResponse,
+            ReimbursementReport,
+            PayerReimbursements,
+            TaxReport,
+            TaxCategoryTotal,
+            ReportFormat,
+            MonthRef,
+            SpendComparison,
+            SpendDelta,
+            CategoryComparison,
+            HouseholdReport,
+            HouseholdCurrencyTotals,
+            MemberContribution,
+            // Search schemas
+            SearchResponse,
+            SearchGroup,
+            SearchResult,
+            SearchResultType,
+            // Webhook schemas
+            CreateWebhookDto,
+            UpdateWebhookDto,
+            WebhookResponse,
+            WebhookDelivery,
+            WebhookDeliveryListResponse,
+            WebhookSecretResponse,
+            WebhookEvent,
+            ReplayResponse,
+            // Event log schemas
+            EventResponse,
+            EventListResponse,
+            QueryRequest,
+            QueryResponse,
+            StructuredQuery,
+            QueryMetric,
+            UndoResponse,
+            ActivityFeedResponse,
+            ActivityItem,
+            AuditLogResponse,
+            AuditLogEntry,
             CategorySpendingSummary,
             CreateTransactionDto,
+            QuickTransactionDto,
+            QuickTransactionResponse,
             UpdateTransactionDto,
+            RefundTransactionDto,
+            UpdateReimbursementDto,
+            ReimbursementStatus,
             CategoriesQueryDto,
+            PayeeSuggestion,
+            PayeeResponse,
+            CreatePayeeDto,
+            // Categorization rule schemas
+            RuleResponse,
+            CreateRuleDto,
+            UpdateRuleDto,
+            ApplyRulesResponse,
+            // Template schemas
+            TemplateResponse,
+            CreateTemplateDto,
+            UpdateTemplateDto,
+            UseTemplateDto,
+            // Funding rule schemas
+            FundingRuleResponse,
+            CreateFundingRuleDto,
+            UpdateFundingRuleDto,
+            // Tag schemas
+            TagResponse,
+            CreateTagDto,
+            UpdateTagDto,
+            SetTransactionTagsDto,
+            // Notification schemas
+            CategoryAlertsResponse,
+            UpdateCategoryAlertsDto,
+            NotificationResponse,
+            NotificationListResponse,
+            UpdateNotificationDto,
+            MarkAllReadResponse,
+            DigestFrequency,
+            NotificationPreferencesResponse,
+            UpdateNotificationPreferencesDto,
+            // Holding schemas
+            HoldingsResponse,
+            HoldingResponse,
+            CreateHoldingDto,
+            UpdateHoldingDto,
+            ValuationSnapshot,
+            // Receipt schemas
+            ReceiptAddressResponse,
+            ReceiptDraft,
+            ReceiptDraftStatus,
+            ConfirmReceiptDto,
+            ConfirmReceiptResponse,
+            // Bank sync schemas
+            LinkBankDto,
+            BankConnectionResponse,
+            BankAccountLink,
+            UpdateBankAccountLinkDto,
+            BankSyncResult,
+            BankImport,
+            BankImportStatus,
+            ApproveBankImportDto,
+            ApproveBankImportResponse,
+            InboundEmailPayload,
+            InboundEmailResponse,
+            // Bill schemas
+            BillResponse,
+            BillStatus,
+            CreateBillDto,
+            UpdateBillDto,
+            PayBillDto,
+            PayBillResponse,
+            BillPayment,
             // Currency schemas
             CurrencyResponse,
             CurrenciesListResponse,
+            ExchangeRateResponse,
+            PaginatedRatesResponse,
             SyncRatesResponse,
+            // Admin schemas
+            RefreshTokenCleanupResponse,
+            RetentionPolicyReport,
+            RetentionRunResponse,
+            BillReminderSummary,
+            LoanPostingSummary,
+            ChargePostingSummary,
+            ValuationSummary,
+            DigestSummary,
+            BankSyncSummary,
+            // Debug schemas
+            BalanceInvariantReport,
+            LoadProfile,
+            LoadSummary,
         )
     ),
     modifiers(&SecurityAddon)
@@ -0,0 +1,344 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+use super::models::{
+    CreatePayeeDto, PayeeAutocompleteQuery, PayeeIdPath, PayeeResponse, PayeeSuggestion,
+};
+use super::rules::{ApplyRulesResponse, CreateRuleDto, RuleIdPath, RuleResponse, UpdateRuleDto};
+use super::service::PayeeService;
+
+/// GET /payees/autocomplete - Rank past payees matching a prefix
+#[utoipa::path(
+    get,
+    path = "/payees/autocomplete",
+    tag = "Payees",
+    params(PayeeAutocompleteQuery),
+    responses(
+        (status = 200, description = "Matching payees with their usual category and last amount", body = Vec<PayeeSuggestion>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/payees/autocomplete")]
+pub async fn autocomplete_payees(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<PayeeAutocompleteQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let suggestions =
+        PayeeService::autocomplete(pool.get_ref(), auth.user_id, &query.q, query.limit).await?;
+
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+/// GET /payees - List payees by name
+#[utoipa::path(
+    get,
+    path = "/payees",
+    tag = "Payees",
+    responses(
+        (status = 200, description = "List of payees", body = Vec<PayeeResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/payees")]
+pub async fn list_payees(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let payees = PayeeService::list(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        payees
+            .into_iter()
+            .map(PayeeResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// POST /payees - Create a payee
+#[utoipa::path(
+    post,
+    path = "/payees",
+    tag = "Payees",
+    request_body = CreatePayeeDto,
+    responses(
+        (status = 201, description = "Payee created", body = PayeeResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 409, description = "A payee with this name already exists", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/payees")]
+pub async fn create_payee(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreatePayeeDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let payee = PayeeService::create(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(PayeeResponse::from(payee)))
+}
+
+/// DELETE /payees/{id} - Delete a payee and the rules assigning it
+#[utoipa::path(
+    delete,
+    path = "/payees/{id}",
+    tag = "Payees",
+    params(PayeeIdPath),
+    responses(
+        (status = 200, description = "Payee deleted", body = DeleteResponse),
+        (status = 404, description = "Payee not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/payees/{id}")]
+pub async fn delete_payee(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<PayeeIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    PayeeService::delete(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Payee deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// GET /rules - List categorization rules in the order they are tried
+#[utoipa::path(
+    get,
+    path = "/rules",
+    tag = "Rules",
+    responses(
+        (status = 200, description = "List of rules", body = Vec<RuleResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/rules")]
+pub async fn list_rules(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let rules = PayeeService::list_rules(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        rules
+            .into_iter()
+            .map(RuleResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// GET /rules/{id} - Get a categorization rule
+#[utoipa::path(
+    get,
+    path = "/rules/{id}",
+    tag = "Rules",
+    params(RuleIdPath),
+    responses(
+        (status = 200, description = "Rule details", body = RuleResponse),
+        (status = 404, description = "Rule not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/rules/{id}")]
+pub async fn get_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<RuleIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let rule = PayeeService::get_rule(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(RuleResponse::from(rule)))
+}
+
+/// POST /rules - Create a categorization rule
+#[utoipa::path(
+    post,
+    path = "/rules",
+    tag = "Rules",
+    request_body = CreateRuleDto,
+    responses(
+        (status = 201, description = "Rule created", body = RuleResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category or payee not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/rules")]
+pub async fn create_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateRuleDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+    body.validate_action()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let rule = PayeeService::create_rule(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(RuleResponse::from(rule)))
+}
+
+/// PATCH /rules/{id} - Update a categorization rule
+#[utoipa::path(
+    patch,
+    path = "/rules/{id}",
+    tag = "Rules",
+    params(RuleIdPath),
+    request_body = UpdateRuleDto,
+    responses(
+        (status = 200, description = "Rule updated", body = RuleResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Rule, category or payee not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/rules/{id}")]
+pub async fn update_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<RuleIdPath>,
+    body: web::Json<UpdateRuleDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let rule =
+        PayeeService::update_rule(pool.get_ref(), path.id, auth.user_id, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(RuleResponse::from(rule)))
+}
+
+/// DELETE /rules/{id} - Delete a categorization rule
+#[utoipa::path(
+    delete,
+    path = "/rules/{id}",
+    tag = "Rules",
+    params(RuleIdPath),
+    responses(
+        (status = 200, description = "Rule deleted", body = DeleteResponse),
+        (status = 404, description = "Rule not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/rules/{id}")]
+pub async fn delete_rule(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<RuleIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    PayeeService::delete_rule(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Rule deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// POST /rules/apply - Run the rules over existing transactions
+#[utoipa::path(
+    post,
+    path = "/rules/apply",
+    tag = "Rules",
+    responses(
+        (status = 200, description = "Transactions whose category or payee changed", body = ApplyRulesResponse),
+        (status = 409, description = "Refiling would take an account that doesn't allow it below zero", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/rules/apply")]
+pub async fn apply_rules(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let (scanned, transactions) = PayeeService::apply_rules(pool.get_ref(), auth.user_id).await?;
+
+    for transaction in &transactions {
+        WebhookService::publish(
+            pool.get_ref(),
+            auth.user_id,
+            events::TRANSACTION_UPDATED,
+            transaction,
+        )
+        .await;
+    }
+
+    Ok(HttpResponse::Ok().json(ApplyRulesResponse {
+        scanned,
+        updated: transactions.len(),
+        data: transactions
+            .into_iter()
+            .map(TransactionResponse::from)
+            .collect(),
+    }))
+}
+
+/// Register the payee and categorization rule routes, `/payees/autocomplete`
+/// and `/rules/apply` ahead of the `{id}` routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(autocomplete_payees)
+        .service(list_payees)
+        .service(create_payee)
+        .service(delete_payee)
+        .service(apply_rules)
+        .service(list_rules)
+        .service(get_rule)
+        .service(create_rule)
+        .service(update_rule)
+        .service(delete_rule);
+}
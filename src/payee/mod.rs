@@ -0,0 +1,6 @@
+pub mod handlers;
+pub mod models;
+pub mod rules;
+pub mod service;
+
+pub use handlers::*;
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::transaction::models::DescriptionUsageRow;
+
+/// Database entity for payees
+#[derive(Debug, Clone, FromRow)]
+pub struct Payee {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Payee returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayeeResponse {
+    pub id: Uuid,
+    #[schema(example = "Uber")]
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Payee> for PayeeResponse {
+    fn from(p: Payee) -> Self {
+        Self {
+            id: p.id,
+            name: p.name,
+            created_at: p.created_at,
+            updated_at: p.updated_at,
+        }
+    }
+}
+
+/// Request body for creating a payee
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePayeeDto {
+    /// Payee name (1-100 characters, unique per user ignoring case)
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    #[schema(example = "Uber")]
+    pub name: String,
+}
+
+/// Path parameters for payee ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PayeeIdPath {
+    /// Payee UUID
+    pub id: Uuid,
+}
+
+fn default_autocomplete_limit() -> i64 {
+    8
+}
+
+/// Query parameters for payee autocomplete
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct PayeeAutocompleteQuery {
+    /// Text typed so far
+    #[validate(length(min = 1, max = 100, message = "q must be 1-100 characters"))]
+    #[param(example = "whole")]
+    pub q: String,
+
+    /// Maximum number of matches (1-20)
+    #[validate(range(min = 1, max = 20))]
+    #[serde(default = "default_autocomplete_limit")]
+    #[param(example = 8)]
+    pub limit: i64,
+}
+
+/// A ranked autocomplete match
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayeeSuggestion {
+    /// Payee name as most recently entered
+    #[schema(example = "Whole Foods Market")]
+    pub name: String,
+    /// Category most often used with this payee
+    #[schema(example = "Groceries")]
+    pub category_name: String,
+    /// Amount of the most recent transaction
+    #[schema(example = 54.20)]
+    pub last_amount: Decimal,
+    pub last_used_at: DateTime<Utc>,
+    /// Number of transactions with this payee
+    #[schema(example = 12)]
+    pub transaction_count: i64,
+    /// Where the match came from; "description" until payees are tracked separately
+    #[schema(example = "description")]
+    pub source: String,
+}
+
+/// Running totals for one payee while ranking
+struct PayeeGroup {
+    name: String,
+    last_amount: Decimal,
+    last_used_at: DateTime<Utc>,
+    count: i64,
+    categories: HashMap<String, i64>,
+    starts_with_query: bool,
+}
+
+impl PayeeSuggestion {
+    /// Group past transactions (newest first) by description and rank those
+    /// matching the query: whole-name prefix matches first, then word prefix
+    /// matches, each by frequency and then recency.
+    pub fn rank(rows: Vec<DescriptionUsageRow>, q: &str, limit: usize) -> Vec<Self> {
+        let query = q.trim().to_lowercase();
+        let mut groups: HashMap<String, PayeeGroup> = HashMap::new();
+
+        for row in rows {
+            let Some(description) = row.description else {
+                continue;
+            };
+            let name = description.trim();
+            let key = name.to_lowercase();
+            let starts_with_query = key.starts_with(&query);
+            if !starts_with_query && !key.split_whitespace().any(|w| w.starts_with(&query)) {
+                continue;
+            }
+
+            let group = groups.entry(key).or_insert_with(|| PayeeGroup {
+                name: name.to_string(),
+                last_amount: row.amount,
+                last_used_at: row.transaction_date,
+                count: 0,
+                categories: HashMap::new(),
+                starts_with_query,
+            });
+            group.count += 1;
+            *group.categories.entry(row.category_name).or_insert(0) += 1;
+        }
+
+        let mut groups: Vec<PayeeGroup> = groups.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.starts_with_query
+                .cmp(&a.starts_with_query)
+                .then_with(|| b.count.cmp(&a.count))
+                .then_with(|| b.last_used_at.cmp(&a.last_used_at))
+        });
+
+        groups
+            .into_iter()
+            .take(limit)
+            .map(|group| {
+                let category_name = group
+                    .categories
+                    .into_iter()
+                    .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))
+                    .map(|(name, _)| name)
+                    .unwrap_or_default();
+                Self {
+                    name: group.name,
+                    category_name,
+                    last_amount: group.last_amount,
+                    last_used_at: group.last_used_at,
+                    transaction_count: group.count,
+                    source: "description".to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn row(description: &str, amount: i64, category: &str, day: u32) -> DescriptionUsageRow {
+        DescriptionUsageRow {
+            description: Some(description.to_string()),
+            amount: Decimal::from(amount),
+            category_name: category.to_string(),
+            transaction_date: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_rank_groups_descriptions_and_prefers_prefix_matches() {
+        let rows = vec![
+            row("Whole Foods", 60, "Groceries", 20),
+            row("Fresh Whole Market", 15, "Groceries", 19),
+            row("whole foods", 40, "Dining", 12),
+            row("Whole Foods", 55, "Groceries", 5),
+            row("Shell", 30, "Fuel", 3),
+        ];
+
+        let suggestions = PayeeSuggestion::rank(rows, "Who", 5);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].name, "Whole Foods");
+        assert_eq!(suggestions[0].transaction_count, 3);
+        assert_eq!(suggestions[0].last_amount, Decimal::from(60));
+        assert_eq!(suggestions[0].category_name, "Groceries");
+        assert_eq!(suggestions[1].name, "Fresh Whole Market");
+    }
+}
@@ -0,0 +1,203 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::template::models::double_option;
+use crate::transaction::models::TransactionResponse;
+
+/// Database entity for categorization rules
+#[derive(Debug, Clone, FromRow)]
+pub struct CategorizationRule {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub pattern: String,
+    pub category_name: Option<String>,
+    pub payee_id: Option<Uuid>,
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Categorization rule returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RuleResponse {
+    pub id: Uuid,
+    /// Text the description must contain, ignoring case
+    #[schema(example = "UBER")]
+    pub pattern: String,
+    /// Category name, matched against the transaction's budget when applied
+    #[schema(example = "Transport")]
+    pub category_name: Option<String>,
+    /// Payee assigned to matching transactions
+    pub payee_id: Option<Uuid>,
+    /// Rules with a higher priority are tried first
+    #[schema(example = 0)]
+    pub priority: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<CategorizationRule> for RuleResponse {
+    fn from(r: CategorizationRule) -> Self {
+        Self {
+            id: r.id,
+            pattern: r.pattern,
+            category_name: r.category_name,
+            payee_id: r.payee_id,
+            priority: r.priority,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+        }
+    }
+}
+
+/// Request body for creating a categorization rule
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRuleDto {
+    /// Text the description must contain, ignoring case (1-100 characters)
+    #[validate(length(min = 1, max = 100, message = "Pattern must be 1-100 characters"))]
+    #[schema(example = "UBER")]
+    pub pattern: String,
+
+    /// Category whose name matching transactions are filed under
+    pub category_id: Option<Uuid>,
+
+    /// Payee assigned to matching transactions
+    pub payee_id: Option<Uuid>,
+
+    /// Rules with a higher priority are tried first (defaults to 0)
+    #[serde(default)]
+    #[schema(example = 0)]
+    pub priority: i32,
+}
+
+impl CreateRuleDto {
+    /// Validate that the rule assigns a category, a payee or both
+    pub fn validate_action(&self) -> Result<(), ValidationError> {
+        if self.category_id.is_none() && self.payee_id.is_none() {
+            return Err(ValidationError::new(
+                "a rule must set a category_id, a payee_id or both",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Request body for updating a categorization rule (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRuleDto {
+    /// Text the description must contain
+    #[validate(length(min = 1, max = 100, message = "Pattern must be 1-100 characters"))]
+    #[schema(example = "UBER TRIP")]
+    pub pattern: Option<String>,
+
+    /// Category whose name matching transactions are filed under (use null to remove)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub category_id: Option<Option<Uuid>>,
+
+    /// Payee assigned to matching transactions (use null to remove)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub payee_id: Option<Option<Uuid>>,
+
+    /// Rules with a higher priority are tried first
+    pub priority: Option<i32>,
+}
+
+/// Path parameters for rule ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct RuleIdPath {
+    /// Rule UUID
+    pub id: Uuid,
+}
+
+/// Response for POST /rules/apply
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyRulesResponse {
+    /// Number of transactions checked against the rules
+    #[schema(example = 240)]
+    pub scanned: usize,
+    /// Number of transactions whose category or payee changed
+    #[schema(example = 12)]
+    pub updated: usize,
+    pub data: Vec<TransactionResponse>,
+}
+
+/// A user's rules in the order they are tried
+#[derive(Debug, Default)]
+pub struct RuleSet {
+    rules: Vec<CategorizationRule>,
+}
+
+impl RuleSet {
+    /// Order rules by priority, then oldest first
+    pub fn new(mut rules: Vec<CategorizationRule>) -> Self {
+        rules.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule whose pattern occurs in the description, ignoring case
+    pub fn first_match(&self, description: Option<&str>) -> Option<&CategorizationRule> {
+        let description = description?.to_lowercase();
+        self.rules
+            .iter()
+            .find(|rule| description.contains(&rule.pattern.trim().to_lowercase()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn rule(pattern: &str, category: &str, priority: i32, day: u32) -> CategorizationRule {
+        let created_at = Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        CategorizationRule {
+            id: Uuid::new_v4(),
+            owner_id: Uuid::nil(),
+            pattern: pattern.to_string(),
+            category_name: Some(category.to_string()),
+            payee_id: None,
+            priority,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn test_first_match_ignores_case_and_prefers_priority_then_age() {
+        let rules = RuleSet::new(vec![
+            rule("uber", "Transport", 0, 2),
+            rule("UBER EATS", "Dining", 5, 3),
+            rule("Uber", "Travel", 0, 1),
+        ]);
+
+        let matched = |description| {
+            rules
+                .first_match(description)
+                .and_then(|r| r.category_name.clone())
+        };
+
+        assert_eq!(matched(Some("Uber Eats order")), Some("Dining".to_string()));
+        assert_eq!(matched(Some("UBER *TRIP")), Some("Travel".to_string()));
+        assert_eq!(matched(Some("Lyft ride")), None);
+        assert_eq!(matched(None), None);
+    }
+}
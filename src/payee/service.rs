@@ -0,0 +1,437 @@
+use sqlx::{PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::cache::ResponseCache;
+use crate::category::service::CategoryService;
+use crate::crypto;
+use crate::errors::{AppError, ErrorCode};
+use crate::extractors::IfMatch;
+use crate::transaction::models::{Transaction, UpdateTransactionDto};
+use crate::transaction::service::{TransactionService, DESCRIPTION_SCAN_WINDOW};
+
+use super::models::{CreatePayeeDto, Payee, PayeeSuggestion};
+use super::rules::{CategorizationRule, CreateRuleDto, RuleSet, UpdateRuleDto};
+
+const PAYEE_COLUMNS: &str = "id, owner_id, name, created_at, updated_at";
+const RULE_COLUMNS: &str =
+    "id, owner_id, pattern, category_name, payee_id, priority, created_at, updated_at";
+
+/// Map a write error, reporting a duplicate name as a conflict
+fn write_error(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Coded(
+            ErrorCode::DuplicatePayee,
+            "A payee with this name already exists".to_string(),
+        ),
+        _ => AppError::InternalError(e.to_string()),
+    }
+}
+
+/// A transaction considered by a retroactive rule run
+#[derive(sqlx::FromRow)]
+struct RuleCandidateRow {
+    id: Uuid,
+    category_id: Uuid,
+    payee_id: Option<Uuid>,
+    description: Option<String>,
+    status: String,
+}
+
+/// Service layer for payees, payee lookups and categorization rules.
+pub struct PayeeService;
+
+impl PayeeService {
+    /// Rank the user's past payees matching what has been typed so far.
+    /// Descriptions are encrypted at rest, so recent transactions are matched
+    /// after decryption rather than in SQL.
+    pub async fn autocomplete(
+        pool: &PgPool,
+        user_id: Uuid,
+        q: &str,
+        limit: i64,
+    ) -> Result<Vec<PayeeSuggestion>, AppError> {
+        let rows =
+            TransactionService::recent_descriptions(pool, user_id, DESCRIPTION_SCAN_WINDOW).await?;
+
+        Ok(PayeeSuggestion::rank(rows, q, limit as usize))
+    }
+
+    /// List the user's payees by name
+    pub async fn list(pool: &PgPool, owner_id: Uuid) -> Result<Vec<Payee>, AppError> {
+        sqlx::query_as::<_, Payee>(&format!(
+            "SELECT {PAYEE_COLUMNS} FROM payees WHERE owner_id = $1 ORDER BY LOWER(name)"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Create a payee
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreatePayeeDto,
+    ) -> Result<Payee, AppError> {
+        let name = dto.name.trim();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Name cannot be empty".to_string(),
+            ));
+        }
+
+        sqlx::query_as::<_, Payee>(&format!(
+            "INSERT INTO payees (owner_id, name) VALUES ($1, $2) RETURNING {PAYEE_COLUMNS}"
+        ))
+        .bind(owner_id)
+        .bind(name)
+        .fetch_one(pool)
+        .await
+        .map_err(write_error)
+    }
+
+    /// Delete a payee. Its transactions keep their description and lose the
+    /// payee; rules assigning it are removed.
+    pub async fn delete(pool: &PgPool, payee_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM payees WHERE id = $1 AND owner_id = $2")
+            .bind(payee_id)
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Payee not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Fail with 404 unless the payee belongs to the user
+    pub async fn verify<'e, E>(executor: E, payee_id: Uuid, owner_id: Uuid) -> Result<(), AppError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM payees WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(payee_id)
+        .bind(owner_id)
+        .fetch_one(executor)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !exists {
+            return Err(AppError::NotFound(
+                "Payee not found or access denied".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// List the user's rules in the order they are tried
+    pub async fn list_rules(
+        pool: &PgPool,
+        owner_id: Uuid,
+    ) -> Result<Vec<CategorizationRule>, AppError> {
+        sqlx::query_as::<_, CategorizationRule>(&format!(
+            "SELECT {RULE_COLUMNS} FROM categorization_rules WHERE owner_id = $1 \
+             ORDER BY priority DESC, created_at"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Get a rule by ID
+    pub async fn get_rule(
+        pool: &PgPool,
+        rule_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<CategorizationRule, AppError> {
+        sqlx::query_as::<_, CategorizationRule>(&format!(
+            "SELECT {RULE_COLUMNS} FROM categorization_rules WHERE id = $1 AND owner_id = $2"
+        ))
+        .bind(rule_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Rule not found".to_string()))
+    }
+
+    /// Create a rule
+    pub async fn create_rule(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateRuleDto,
+    ) -> Result<CategorizationRule, AppError> {
+        let pattern = dto.pattern.trim();
+        if pattern.is_empty() {
+            return Err(AppError::ValidationError(
+                "Pattern cannot be empty".to_string(),
+            ));
+        }
+
+        let category_name = match dto.category_id {
+            Some(category_id) => Some(
+                CategoryService::get_by_id(pool, category_id, owner_id)
+                    .await?
+                    .name,
+            ),
+            None => None,
+        };
+        if let Some(payee_id) = dto.payee_id {
+            Self::verify(pool, payee_id, owner_id).await?;
+        }
+
+        sqlx::query_as::<_, CategorizationRule>(&format!(
+            r#"
+            INSERT INTO categorization_rules (owner_id, pattern, category_name, payee_id, priority)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {RULE_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(pattern)
+        .bind(&category_name)
+        .bind(dto.payee_id)
+        .bind(dto.priority)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Update a rule (partial update - PATCH semantics)
+    pub async fn update_rule(
+        pool: &PgPool,
+        rule_id: Uuid,
+        owner_id: Uuid,
+        dto: UpdateRuleDto,
+    ) -> Result<CategorizationRule, AppError> {
+        let current = Self::get_rule(pool, rule_id, owner_id).await?;
+
+        let pattern = match dto.pattern {
+            Some(p) => {
+                let trimmed = p.trim().to_string();
+                if trimmed.is_empty() {
+                    return Err(AppError::ValidationError(
+                        "Pattern cannot be empty".to_string(),
+                    ));
+                }
+                trimmed
+            }
+            None => current.pattern,
+        };
+        let category_name = match dto.category_id {
+            Some(Some(category_id)) => Some(
+                CategoryService::get_by_id(pool, category_id, owner_id)
+                    .await?
+                    .name,
+            ),
+            Some(None) => None,
+            None => current.category_name,
+        };
+        let payee_id = dto.payee_id.unwrap_or(current.payee_id);
+        if let Some(payee_id) = payee_id {
+            if Some(payee_id) != current.payee_id {
+                Self::verify(pool, payee_id, owner_id).await?;
+            }
+        }
+        if category_name.is_none() && payee_id.is_none() {
+            return Err(AppError::ValidationError(
+                "A rule must set a category, a payee or both".to_string(),
+            ));
+        }
+        let priority = dto.priority.unwrap_or(current.priority);
+
+        sqlx::query_as::<_, CategorizationRule>(&format!(
+            r#"
+            UPDATE categorization_rules SET
+                pattern = $3,
+                category_name = $4,
+                payee_id = $5,
+                priority = $6
+            WHERE id = $1 AND owner_id = $2
+            RETURNING {RULE_COLUMNS}
+            "#
+        ))
+        .bind(rule_id)
+        .bind(owner_id)
+        .bind(&pattern)
+        .bind(&category_name)
+        .bind(payee_id)
+        .bind(priority)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Delete a rule
+    pub async fn delete_rule(pool: &PgPool, rule_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result =
+            sqlx::query("DELETE FROM categorization_rules WHERE id = $1 AND owner_id = $2")
+                .bind(rule_id)
+                .bind(owner_id)
+                .execute(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Rule not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Load the user's rules inside the caller's database transaction
+    pub async fn rule_set(
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        owner_id: Uuid,
+    ) -> Result<RuleSet, AppError> {
+        let rules = sqlx::query_as::<_, CategorizationRule>(&format!(
+            "SELECT {RULE_COLUMNS} FROM categorization_rules WHERE owner_id = $1"
+        ))
+        .bind(owner_id)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(RuleSet::new(rules))
+    }
+
+    /// The category and payee a transaction is filed under once the first
+    /// matching rule is applied. The rule's category is looked up by name in
+    /// the budget of `category_id` and left alone when that budget has none;
+    /// a payee already set is kept.
+    pub async fn categorize(
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        rules: &RuleSet,
+        category_id: Uuid,
+        payee_id: Option<Uuid>,
+        description: Option<&str>,
+    ) -> Result<(Uuid, Option<Uuid>), AppError> {
+        let Some(rule) = rules.first_match(description) else {
+            return Ok((category_id, payee_id));
+        };
+
+        let category_id = match &rule.category_name {
+            Some(name) => sqlx::query_scalar::<_, Uuid>(
+                r#"
+                SELECT c.id FROM categories c
+                JOIN categories current ON current.budget_id = c.budget_id
+                WHERE current.id = $1 AND LOWER(c.name) = LOWER($2)
+                LIMIT 1
+                "#,
+            )
+            .bind(category_id)
+            .bind(name)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?
+            .unwrap_or(category_id),
+            None => category_id,
+        };
+
+        Ok((category_id, payee_id.or(rule.payee_id)))
+    }
+
+    /// Run the user's rules over their existing transactions in budgets they
+    /// own. Posted transactions go through the regular update so balances,
+    /// spending and the audit log follow; pending ones are refiled in place.
+    /// Returns the number of transactions scanned and those that changed.
+    pub async fn apply_rules(
+        pool: &PgPool,
+        owner_id: Uuid,
+    ) -> Result<(usize, Vec<Transaction>), AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let rules = Self::rule_set(&mut tx, owner_id).await?;
+        if rules.is_empty() {
+            return Ok((0, Vec::new()));
+        }
+
+        let candidates = sqlx::query_as::<_, RuleCandidateRow>(
+            r#"
+            SELECT t.id, t.category_id, t.payee_id, t.description, t.status
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND t.deleted_at IS NULL AND t.status <> 'rejected'
+            ORDER BY t.transaction_date, t.id
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let scanned = candidates.len();
+        let mut updated = Vec::new();
+        for row in candidates {
+            let description = crypto::decrypt_optional(row.description)?;
+            let (category_id, payee_id) = Self::categorize(
+                &mut tx,
+                &rules,
+                row.category_id,
+                row.payee_id,
+                description.as_deref(),
+            )
+            .await?;
+            if category_id == row.category_id && payee_id == row.payee_id {
+                continue;
+            }
+
+            let transaction = if row.status == "posted" {
+                TransactionService::update_in_tx(
+                    &mut tx,
+                    owner_id,
+                    row.id,
+                    UpdateTransactionDto {
+                        category_id: Some(category_id),
+                        account_id: None,
+                        destination_account_id: None,
+                        amount: None,
+                        transaction_date: None,
+                        description: None,
+                        transaction_type: None,
+                        tax_category: None,
+                        payee_id: Some(payee_id),
+                        fee_amount: None,
+                        destination_amount: None,
+                    },
+                    &IfMatch::default(),
+                )
+                .await?
+            } else {
+                sqlx::query_as::<_, Transaction>(
+                    r#"
+                    UPDATE transactions SET category_id = $2, payee_id = $3, updated_at = NOW()
+                    WHERE id = $1
+                    RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                              transaction_type, reversal_of_id,
+                              reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                              tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+                    "#,
+                )
+                .bind(row.id)
+                .bind(category_id)
+                .bind(payee_id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?
+            };
+            updated.push(transaction.decrypted()?);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(owner_id);
+
+        Ok((scanned, updated))
+    }
+}
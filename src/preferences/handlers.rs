@@ -0,0 +1,72 @@
+use actix_web::{get, patch, web, HttpRequest, HttpResponse};
+use secrecy::Secret;
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::decode_first_party_token;
+use crate::errors::{AppError, ErrorResponse};
+
+use super::models::{Preferences, UpdatePreferencesDto};
+use super::service::PreferencesService;
+
+/// GET /users/me/preferences - Get the user's preferences
+#[utoipa::path(
+    get,
+    path = "/users/me/preferences",
+    tag = "Settings",
+    responses(
+        (status = 200, description = "Saved preferences, defaults for anything not set", body = Preferences),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/users/me/preferences")]
+pub async fn get_preferences(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let preferences = PreferencesService::get(pool.get_ref(), claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// PATCH /users/me/preferences - Update some of the user's preferences
+#[utoipa::path(
+    patch,
+    path = "/users/me/preferences",
+    tag = "Settings",
+    request_body = UpdatePreferencesDto,
+    responses(
+        (status = 200, description = "Preferences updated", body = Preferences),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse),
+        (status = 404, description = "Default account not found", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/users/me/preferences")]
+pub async fn update_preferences(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+    body: web::Json<UpdatePreferencesDto>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let preferences =
+        PreferencesService::update(pool.get_ref(), claims.sub, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(preferences))
+}
+
+/// Register the user preference routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_preferences).service(update_preferences);
+}
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::template::models::double_option;
+
+/// Locales are a language code with an optional region, e.g. `en` or `en-US`
+fn validate_locale(locale: &str) -> Result<(), ValidationError> {
+    let mut parts = locale.split('-');
+    let language = parts.next().unwrap_or_default();
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_lowercase()) {
+        return Err(ValidationError::new("invalid_language"));
+    }
+    if let Some(region) = parts.next() {
+        let letters = region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase());
+        let digits = region.len() == 3 && region.chars().all(|c| c.is_ascii_digit());
+        if !letters && !digits {
+            return Err(ValidationError::new("invalid_region"));
+        }
+    }
+    if parts.next().is_some() {
+        return Err(ValidationError::new("invalid_locale"));
+    }
+    Ok(())
+}
+
+/// Day calendars and weekly views start on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FirstDayOfWeek {
+    #[default]
+    Monday,
+    Saturday,
+    Sunday,
+}
+
+/// How dates are displayed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum DateFormat {
+    #[default]
+    #[serde(rename = "YYYY-MM-DD")]
+    Iso,
+    #[serde(rename = "DD/MM/YYYY")]
+    DayMonthYear,
+    #[serde(rename = "MM/DD/YYYY")]
+    MonthDayYear,
+    #[serde(rename = "DD.MM.YYYY")]
+    DayMonthYearDotted,
+}
+
+fn default_locale() -> String {
+    "en-US".to_string()
+}
+
+/// UI and behavior preferences, stored as JSON. Keys missing from what was
+/// saved (including ones added later) read as their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Preferences {
+    /// Day calendars and weekly views start on
+    pub first_day_of_week: FirstDayOfWeek,
+    /// How dates are displayed
+    #[schema(example = "YYYY-MM-DD")]
+    pub date_format: DateFormat,
+    /// Account preselected for new transactions
+    pub default_account_id: Option<Uuid>,
+    /// Leave archived accounts out of account lists
+    pub hide_archived_accounts: bool,
+    /// Language and region for text and number formatting
+    #[schema(example = "en-US")]
+    pub locale: String,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            first_day_of_week: FirstDayOfWeek::default(),
+            date_format: DateFormat::default(),
+            default_account_id: None,
+            hide_archived_accounts: false,
+            locale: default_locale(),
+        }
+    }
+}
+
+/// Request body for updating preferences (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePreferencesDto {
+    /// Day calendars and weekly views start on
+    pub first_day_of_week: Option<FirstDayOfWeek>,
+
+    /// How dates are displayed
+    pub date_format: Option<DateFormat>,
+
+    /// Account preselected for new transactions (use null to remove)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub default_account_id: Option<Option<Uuid>>,
+
+    /// Leave archived accounts out of account lists
+    pub hide_archived_accounts: Option<bool>,
+
+    /// Language code with an optional region, e.g. `en` or `en-US`
+    #[validate(custom(
+        function = "validate_locale",
+        message = "Locale must look like 'en' or 'en-US'"
+    ))]
+    #[schema(example = "de-DE")]
+    pub locale: Option<String>,
+}
+
+impl UpdatePreferencesDto {
+    /// The preferences with this update's fields replaced
+    pub fn apply(self, mut preferences: Preferences) -> Preferences {
+        if let Some(day) = self.first_day_of_week {
+            preferences.first_day_of_week = day;
+        }
+        if let Some(format) = self.date_format {
+            preferences.date_format = format;
+        }
+        if let Some(account_id) = self.default_account_id {
+            preferences.default_account_id = account_id;
+        }
+        if let Some(hide) = self.hide_archived_accounts {
+            preferences.hide_archived_accounts = hide;
+        }
+        if let Some(locale) = self.locale {
+            preferences.locale = locale;
+        }
+        preferences
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_saved_preferences_fill_in_defaults() {
+        let preferences: Preferences =
+            serde_json::from_value(json!({ "dateFormat": "DD.MM.YYYY" })).unwrap();
+        assert_eq!(preferences.date_format, DateFormat::DayMonthYearDotted);
+        assert_eq!(preferences.first_day_of_week, FirstDayOfWeek::Monday);
+        assert_eq!(preferences.locale, "en-US");
+    }
+
+    #[test]
+    fn test_update_applies_only_given_fields() {
+        let dto: UpdatePreferencesDto = serde_json::from_value(json!({
+            "firstDayOfWeek": "sunday",
+            "defaultAccountId": null
+        }))
+        .unwrap();
+        let saved = Preferences {
+            default_account_id: Some(Uuid::nil()),
+            locale: "de-DE".to_string(),
+            ..Preferences::default()
+        };
+
+        let updated = dto.apply(saved);
+        assert_eq!(updated.first_day_of_week, FirstDayOfWeek::Sunday);
+        assert_eq!(updated.default_account_id, None);
+        assert_eq!(updated.locale, "de-DE");
+    }
+
+    #[test]
+    fn test_validate_locale() {
+        for locale in ["en", "en-US", "fil-PH", "es-419"] {
+            assert!(validate_locale(locale).is_ok(), "{locale}");
+        }
+        for locale in ["", "EN", "en-us", "en_US", "english", "en-US-x"] {
+            assert!(validate_locale(locale).is_err(), "{locale}");
+        }
+    }
+}
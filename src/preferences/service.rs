@@ -0,0 +1,98 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+use super::models::{Preferences, UpdatePreferencesDto};
+
+/// Service layer for per-user UI and behavior preferences, kept next to the
+/// dashboard layout in `user_settings`.
+pub struct PreferencesService;
+
+impl PreferencesService {
+    /// The user's preferences; defaults until something is saved. A default
+    /// account deleted since is left out.
+    pub async fn get(pool: &PgPool, user_id: Uuid) -> Result<Preferences, AppError> {
+        let saved = sqlx::query_scalar::<_, sqlx::types::Json<Preferences>>(
+            "SELECT preferences FROM user_settings WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut preferences = saved.map(|p| p.0).unwrap_or_default();
+        if let Some(account_id) = preferences.default_account_id {
+            if !Self::owns_account(pool, user_id, account_id).await? {
+                preferences.default_account_id = None;
+            }
+        }
+        Ok(preferences)
+    }
+
+    /// Apply a partial update to the user's preferences
+    pub async fn update(
+        pool: &PgPool,
+        user_id: Uuid,
+        dto: UpdatePreferencesDto,
+    ) -> Result<Preferences, AppError> {
+        if let Some(Some(account_id)) = dto.default_account_id {
+            if !Self::owns_account(pool, user_id, account_id).await? {
+                return Err(AppError::NotFound(
+                    "Default account not found or access denied".to_string(),
+                ));
+            }
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query("INSERT INTO user_settings (user_id) VALUES ($1) ON CONFLICT DO NOTHING")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // Locked so concurrent updates to different fields don't overwrite each other
+        let current = sqlx::query_scalar::<_, sqlx::types::Json<Preferences>>(
+            "SELECT preferences FROM user_settings WHERE user_id = $1 FOR UPDATE",
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let preferences = dto.apply(current.0);
+        sqlx::query(
+            "UPDATE user_settings SET preferences = $2, updated_at = NOW() WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .bind(sqlx::types::Json(&preferences))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(preferences)
+    }
+
+    async fn owns_account(
+        pool: &PgPool,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> Result<bool, AppError> {
+        sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(account_id)
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+}
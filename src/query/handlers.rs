@@ -0,0 +1,48 @@
+use actix_web::{post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::scopes::Scope;
+use crate::budget::models::MonthBaseQuery;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{QueryRequest, QueryResponse};
+use super::service::QueryService;
+
+/// POST /query - Answer a natural-language question about transactions
+#[utoipa::path(
+    post,
+    path = "/query",
+    tag = "Query",
+    params(MonthBaseQuery),
+    request_body = QueryRequest,
+    responses(
+        (status = 200, description = "Answer and the structured filter used", body = QueryResponse),
+        (status = 400, description = "Validation error or question not understood", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/query")]
+pub async fn run_query(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    body: web::Json<QueryRequest>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    body.validate()?;
+
+    let mut response = QueryService::answer(pool.get_ref(), auth.user_id, &body.question).await?;
+    response.filter.month = response.filter.month.map(|m| month_base.to_external(m));
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Register the natural-language query route
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(run_query);
+}
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+/// What a natural-language question asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryMetric {
+    /// Sum of expenses
+    Spending,
+    /// Sum of income
+    Income,
+    /// Income minus expenses
+    Net,
+    /// Number of transactions
+    Count,
+}
+
+/// Request body for a natural-language question
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    /// Question in plain English
+    #[validate(length(min = 1, max = 500, message = "question must be 1-500 characters"))]
+    #[schema(example = "How much did I spend on food in March?")]
+    pub question: String,
+}
+
+/// Structured filter a question was translated into
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StructuredQuery {
+    /// Figure being asked for
+    pub metric: QueryMetric,
+    /// Category name the question refers to (all categories when omitted)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Food")]
+    pub category: Option<String>,
+    /// Calendar month in the user's timezone (0-11, or 1-12 with monthBase=1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2)]
+    pub month: Option<i16>,
+    /// Calendar year
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2026)]
+    pub year: Option<i16>,
+    /// Start of a whole-year range (UTC)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<DateTime<Utc>>,
+    /// End of a whole-year range (UTC, inclusive)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<DateTime<Utc>>,
+}
+
+/// Answer to a natural-language question
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResponse {
+    /// The original question
+    pub question: String,
+    /// Human-readable answer
+    #[schema(example = "You spent 412.50 on Food in March 2026.")]
+    pub answer: String,
+    /// Numeric answer (an amount, or a count for count questions)
+    #[schema(example = 412.50)]
+    pub value: Decimal,
+    /// Structured filter that produced the answer
+    pub filter: StructuredQuery,
+}
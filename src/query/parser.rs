@@ -0,0 +1,189 @@
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+
+use super::models::{QueryMetric, StructuredQuery};
+
+pub const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Lowercase words of the question, punctuation removed
+fn words(question: &str) -> Vec<String> {
+    question
+        .split(|c: char| !c.is_alphanumeric() && c != '\'')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+fn detect_metric(question: &str, words: &[String]) -> Option<QueryMetric> {
+    let has = |candidates: &[&str]| words.iter().any(|w| candidates.contains(&w.as_str()));
+
+    if question.contains("how many") || has(&["count", "number"]) {
+        Some(QueryMetric::Count)
+    } else if has(&["net", "save", "saved", "savings", "left"]) {
+        Some(QueryMetric::Net)
+    } else if has(&["earn", "earned", "income", "made", "received"]) && !has(&["spend", "spent"]) {
+        Some(QueryMetric::Income)
+    } else if has(&[
+        "spend", "spent", "spending", "cost", "costs", "expenses", "pay", "paid",
+    ]) {
+        Some(QueryMetric::Spending)
+    } else {
+        None
+    }
+}
+
+/// Month (0-11) named in the question, matching full names and 3-letter abbreviations
+fn detect_month(words: &[String]) -> Option<i16> {
+    words.iter().find_map(|w| {
+        MONTHS
+            .iter()
+            .position(|m| w == m || (w.len() == 3 && m.starts_with(w.as_str())))
+            .map(|i| i as i16)
+    })
+}
+
+fn detect_year(words: &[String]) -> Option<i16> {
+    words
+        .iter()
+        .filter(|w| w.len() == 4)
+        .find_map(|w| w.parse::<i16>().ok())
+        .filter(|y| (2000..=2100).contains(y))
+}
+
+/// Longest known category name appearing as whole words in the question
+fn detect_category(question: &str, categories: &[String]) -> Option<String> {
+    let padded = format!(" {} ", words(question).join(" "));
+    categories
+        .iter()
+        .filter(|name| {
+            let needle = words(name).join(" ");
+            !needle.is_empty() && padded.contains(&format!(" {} ", needle))
+        })
+        .max_by_key(|name| name.len())
+        .cloned()
+}
+
+/// Translate a question into a structured filter.
+///
+/// `today` is the current date in the user's timezone; it resolves relative
+/// periods ("this month", "last month") and months named without a year,
+/// which refer to their most recent occurrence.
+pub fn parse_question(
+    question: &str,
+    categories: &[String],
+    today: NaiveDate,
+) -> Option<StructuredQuery> {
+    let lower = question.to_lowercase();
+    let words = words(question);
+    let metric = detect_metric(&lower, &words)?;
+
+    let current_month = today.month0() as i16;
+    let current_year = today.year() as i16;
+
+    let (month, year) = if lower.contains("this month") {
+        (Some(current_month), Some(current_year))
+    } else if lower.contains("last month") {
+        if current_month == 0 {
+            (Some(11), Some(current_year - 1))
+        } else {
+            (Some(current_month - 1), Some(current_year))
+        }
+    } else {
+        match (detect_month(&words), detect_year(&words)) {
+            (Some(month), Some(year)) => (Some(month), Some(year)),
+            (Some(month), None) if month > current_month => (Some(month), Some(current_year - 1)),
+            (Some(month), None) => (Some(month), Some(current_year)),
+            (None, Some(year)) => (None, Some(year)),
+            (None, None) if lower.contains("this year") => (None, Some(current_year)),
+            (None, None) if lower.contains("last year") => (None, Some(current_year - 1)),
+            (None, None) => (None, None),
+        }
+    };
+
+    // A year without a month covers the whole calendar year
+    let (start_date, end_date) = match (month, year) {
+        (None, Some(year)) => (
+            Utc.with_ymd_and_hms(year as i32, 1, 1, 0, 0, 0).single(),
+            Utc.with_ymd_and_hms(year as i32, 12, 31, 23, 59, 59)
+                .single(),
+        ),
+        _ => (None, None),
+    };
+
+    Some(StructuredQuery {
+        metric,
+        category: detect_category(question, categories),
+        month,
+        year: if month.is_some() { year } else { None },
+        start_date,
+        end_date,
+    })
+}
+
+/// Display name for a structured period, e.g. "March 2026"
+pub fn describe_period(query: &StructuredQuery) -> Option<String> {
+    match (query.month, query.year, query.start_date) {
+        (Some(month), Some(year), _) => {
+            let name = MONTHS[month as usize];
+            Some(format!(
+                "{}{} {}",
+                name[..1].to_uppercase(),
+                &name[1..],
+                year
+            ))
+        }
+        (None, _, Some(start)) => Some(start.year().to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 2, 15).unwrap()
+    }
+
+    #[test]
+    fn test_parse_spending_on_category_in_month() {
+        let categories = vec!["Food".to_string(), "Fast Food".to_string()];
+        let parsed = parse_question(
+            "How much did I spend on fast food in March?",
+            &categories,
+            today(),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.metric, QueryMetric::Spending);
+        assert_eq!(parsed.category.as_deref(), Some("Fast Food"));
+        // March hasn't happened yet this year, so it means last March
+        assert_eq!((parsed.month, parsed.year), (Some(2), Some(2025)));
+    }
+
+    #[test]
+    fn test_parse_relative_periods_and_years() {
+        let parsed = parse_question("what did I earn last month", &[], today()).unwrap();
+        assert_eq!(parsed.metric, QueryMetric::Income);
+        assert_eq!((parsed.month, parsed.year), (Some(0), Some(2026)));
+
+        let parsed = parse_question("How many transactions in 2025?", &[], today()).unwrap();
+        assert_eq!(parsed.metric, QueryMetric::Count);
+        assert_eq!(parsed.month, None);
+        assert_eq!(describe_period(&parsed).as_deref(), Some("2025"));
+
+        assert!(parse_question("hello there", &[], today()).is_none());
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{QueryMetric, QueryResponse, StructuredQuery};
+use super::parser::{describe_period, parse_question};
+use crate::errors::AppError;
+use crate::transaction::models::SummaryFilters;
+use crate::transaction::service::TransactionService;
+
+/// Service layer for natural-language queries.
+pub struct QueryService;
+
+impl QueryService {
+    /// Translate a question into a structured filter and answer it with the
+    /// transaction summary. The returned filter uses internal 0-11 months.
+    pub async fn answer(
+        pool: &PgPool,
+        user_id: Uuid,
+        question: &str,
+    ) -> Result<QueryResponse, AppError> {
+        let today = sqlx::query_scalar::<_, NaiveDate>(
+            "SELECT (NOW() AT TIME ZONE timezone)::DATE FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let categories = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT c.name
+            FROM categories c
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let filter = parse_question(question, &categories, today).ok_or_else(|| {
+            AppError::ValidationError(
+                "Could not understand the question; try e.g. \"How much did I spend on food in March?\""
+                    .to_string(),
+            )
+        })?;
+
+        let summary_filters = SummaryFilters {
+            start_date: filter.start_date,
+            end_date: filter.end_date,
+            account_id: None,
+            tag_ids: None,
+            month: filter.month,
+            year: filter.year,
+        };
+
+        let (total_income, total_expenses, transaction_count, by_category) =
+            TransactionService::get_summary(pool, user_id, &summary_filters).await?;
+
+        let value = match (&filter.category, filter.metric) {
+            // The summary breaks down expenses by category; categories recur
+            // monthly, so every category with the name counts
+            (Some(name), metric) => {
+                let rows = by_category
+                    .iter()
+                    .filter(|row| row.category_name.eq_ignore_ascii_case(name));
+                match metric {
+                    QueryMetric::Count => {
+                        Decimal::from(rows.map(|r| r.transaction_count).sum::<i64>())
+                    }
+                    _ => rows.map(|r| r.total_amount).sum(),
+                }
+            }
+            (None, QueryMetric::Spending) => total_expenses,
+            (None, QueryMetric::Income) => total_income,
+            (None, QueryMetric::Net) => total_income - total_expenses,
+            (None, QueryMetric::Count) => Decimal::from(transaction_count),
+        };
+
+        Ok(QueryResponse {
+            question: question.to_string(),
+            answer: Self::phrase(&filter, value),
+            value,
+            filter,
+        })
+    }
+
+    fn phrase(filter: &StructuredQuery, value: Decimal) -> String {
+        let category = filter
+            .category
+            .as_ref()
+            .map(|c| format!(" on {}", c))
+            .unwrap_or_default();
+        let period = describe_period(filter)
+            .map(|p| format!(" in {}", p))
+            .unwrap_or_else(|| " in total".to_string());
+        let amount = value.round_dp(2);
+
+        match (filter.metric, filter.category.is_some()) {
+            (QueryMetric::Count, _) => {
+                format!("You had {} transactions{}{}.", value, category, period)
+            }
+            (_, true) | (QueryMetric::Spending, false) => {
+                format!("You spent {}{}{}.", amount, category, period)
+            }
+            (QueryMetric::Income, false) => format!("You earned {}{}.", amount, period),
+            (QueryMetric::Net, false) => format!("Your net change was {}{}.", amount, period),
+        }
+    }
+}
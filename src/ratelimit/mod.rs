@@ -0,0 +1,235 @@
+pub mod redis;
+
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_governor::governor::clock::{Clock, DefaultClock};
+use actix_governor::governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{http::header, web, Error, HttpResponse};
+use secrecy::Secret;
+use tracing::warn;
+use uuid::Uuid;
+
+use self::redis::{RedisClient, RespValue};
+use crate::auth::decode_token;
+use crate::errors::{AppError, ErrorCode, ErrorResponse};
+
+/// GCRA in one round trip, timed by the Redis clock so every instance agrees.
+/// The key holds the theoretical arrival time in milliseconds; the reply is 0
+/// when the request is allowed, otherwise the milliseconds until it would be.
+const GCRA_SCRIPT: &str = r#"
+local t = redis.call('TIME')
+local now = tonumber(t[1]) * 1000 + math.floor(tonumber(t[2]) / 1000)
+local period = tonumber(ARGV[1])
+local burst = tonumber(ARGV[2])
+local tat = tonumber(redis.call('GET', KEYS[1]) or now)
+if tat < now then tat = now end
+local allow_at = tat + period - burst * period
+if now < allow_at then return allow_at - now end
+local new_tat = tat + period
+redis.call('SET', KEYS[1], new_tat, 'PX', new_tat - now)
+return 0
+"#;
+
+/// A rate limit of `burst` requests that replenish one per `period`
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitQuota {
+    pub period: Duration,
+    pub burst: u32,
+}
+
+/// Rate limiter whose counters live in Redis, so limits hold across instances.
+///
+/// Without `REDIS_URL` the app keeps the per-process Governor limiter.
+pub struct RedisRateLimiter {
+    client: RedisClient,
+    key_prefix: String,
+}
+
+impl RedisRateLimiter {
+    /// Connect to the Redis at `url`, keeping counters under `key_prefix`
+    pub fn new(url: &str, key_prefix: String) -> Result<Self, AppError> {
+        Ok(Self {
+            client: RedisClient::new(url)?,
+            key_prefix,
+        })
+    }
+
+    /// Take one request from the bucket for `key`.
+    /// Returns the time until the next request is allowed when limited.
+    pub async fn check(
+        &self,
+        scope: &str,
+        key: &str,
+        quota: RateLimitQuota,
+    ) -> Result<Option<Duration>, AppError> {
+        let redis_key = format!("{}:{}:{}", self.key_prefix, scope, key);
+        let period_ms = quota.period.as_millis().max(1).to_string();
+        let burst = quota.burst.max(1).to_string();
+
+        let reply = self
+            .client
+            .command(&[
+                b"EVAL",
+                GCRA_SCRIPT.as_bytes(),
+                b"1",
+                redis_key.as_bytes(),
+                period_ms.as_bytes(),
+                burst.as_bytes(),
+            ])
+            .await?;
+
+        match reply {
+            RespValue::Integer(0) => Ok(None),
+            RespValue::Integer(wait_ms) => Ok(Some(Duration::from_millis(wait_ms as u64))),
+            other => Err(AppError::InternalError(format!(
+                "Unexpected rate limit reply: {other:?}"
+            ))),
+        }
+    }
+}
+
+/// Middleware body enforcing `quota` per client IP through Redis.
+///
+/// Passes everything through when no limiter is configured, and fails open
+/// when Redis is unreachable so an outage doesn't take the endpoints down.
+pub async fn enforce(
+    limiter: Option<Arc<RedisRateLimiter>>,
+    scope: &'static str,
+    quota: RateLimitQuota,
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let Some(limiter) = limiter else {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_left_body);
+    };
+
+    // Same key as Governor's peer IP extractor
+    let key = req
+        .peer_addr()
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    match limiter.check(scope, &key, quota).await {
+        Ok(Some(wait)) => {
+            return Ok(req
+                .into_response(too_many_requests(wait))
+                .map_into_right_body())
+        }
+        Ok(None) => {}
+        Err(e) => warn!("Rate limit check failed, allowing request: {}", e),
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+}
+
+/// 429 telling the client how long to wait, in whole seconds
+fn too_many_requests(wait: Duration) -> HttpResponse {
+    let wait_secs = wait.as_secs_f64().ceil().max(1.0) as u64;
+    HttpResponse::TooManyRequests()
+        .insert_header((header::RETRY_AFTER, wait_secs.to_string()))
+        .json(ErrorResponse::new(
+            ErrorCode::RateLimited,
+            format!("Too many requests, retry in {wait_secs}s"),
+        ))
+}
+
+/// Limits writes (POST, PUT, PATCH, DELETE) per authenticated user, so one
+/// account can't flood the database however many addresses it comes from.
+///
+/// Counters live in Redis when configured, otherwise in this process.
+#[derive(Clone)]
+pub struct UserWriteLimiter {
+    quota: RateLimitQuota,
+    local: Arc<DefaultKeyedRateLimiter<Uuid>>,
+    redis: Option<Arc<RedisRateLimiter>>,
+}
+
+impl UserWriteLimiter {
+    pub fn new(quota: RateLimitQuota, redis: Option<Arc<RedisRateLimiter>>) -> Self {
+        let local_quota = Quota::with_period(quota.period)
+            .expect("Rate limit period must be non-zero")
+            .allow_burst(NonZeroU32::new(quota.burst).expect("Rate limit burst must be non-zero"));
+        Self {
+            quota,
+            local: Arc::new(RateLimiter::keyed(local_quota)),
+            redis,
+        }
+    }
+
+    /// Take one write from the user's bucket.
+    /// Returns the time until the next write is allowed when limited.
+    async fn check(&self, user_id: Uuid) -> Option<Duration> {
+        if let Some(redis) = &self.redis {
+            match redis
+                .check("writes", &user_id.to_string(), self.quota)
+                .await
+            {
+                Ok(wait) => return wait,
+                // Fall back to this process's counters rather than not limiting at all
+                Err(e) => warn!("Rate limit check failed, counting locally: {}", e),
+            }
+        }
+
+        self.local
+            .check_key(&user_id)
+            .err()
+            .map(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+}
+
+/// Middleware body enforcing the per-user write limit.
+///
+/// Reads and requests without a valid bearer token pass through untouched:
+/// the handlers reject bad tokens, and the credential endpoints have their
+/// own limit per client IP.
+pub async fn enforce_user_writes(
+    limiter: UserWriteLimiter,
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let is_write = matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    );
+    let user_id = if is_write {
+        authenticated_user(&req)
+    } else {
+        None
+    };
+
+    if let Some(user_id) = user_id {
+        if let Some(wait) = limiter.check(user_id).await {
+            return Ok(req
+                .into_response(too_many_requests(wait))
+                .map_into_right_body());
+        }
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+}
+
+/// User of the request's bearer token, when it carries a valid one
+fn authenticated_user(req: &ServiceRequest) -> Option<Uuid> {
+    let jwt_secret = req.app_data::<web::Data<Secret<String>>>()?;
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))?;
+    decode_token(token, jwt_secret)
+        .ok()
+        .map(|claims| claims.sub)
+}
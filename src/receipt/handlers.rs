@@ -0,0 +1,247 @@
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse};
+use chrono::Utc;
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+use crate::webhook::signature::{verify, SIGNATURE_HEADER};
+
+use super::models::{
+    ConfirmReceiptDto, ConfirmReceiptResponse, InboundEmailConfig, InboundEmailPayload,
+    InboundEmailResponse, ReceiptAddressResponse, ReceiptDraft, ReceiptDraftIdPath,
+    ReceiptDraftStatus, ReceiptDraftsQuery,
+};
+use super::parser::parse_receipt;
+use super::service::ReceiptService;
+
+/// GET /receipts/address - The user's receipt ingestion address
+#[utoipa::path(
+    get,
+    path = "/receipts/address",
+    tag = "Receipts",
+    responses(
+        (status = 200, description = "Address to forward receipts to", body = ReceiptAddressResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/receipts/address")]
+pub async fn get_receipt_address(
+    pool: web::Data<PgPool>,
+    config: web::Data<InboundEmailConfig>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let token = ReceiptService::inbox_token(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(ReceiptAddressResponse {
+        address: config.address(&token),
+    }))
+}
+
+/// POST /receipts/address/rotate - Replace the ingestion address
+#[utoipa::path(
+    post,
+    path = "/receipts/address/rotate",
+    tag = "Receipts",
+    responses(
+        (status = 200, description = "New address; mail to the old one is rejected", body = ReceiptAddressResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/receipts/address/rotate")]
+pub async fn rotate_receipt_address(
+    pool: web::Data<PgPool>,
+    config: web::Data<InboundEmailConfig>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let token = ReceiptService::rotate_inbox_token(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(ReceiptAddressResponse {
+        address: config.address(&token),
+    }))
+}
+
+/// GET /receipts/drafts - Receipt drafts, newest first
+#[utoipa::path(
+    get,
+    path = "/receipts/drafts",
+    tag = "Receipts",
+    params(ReceiptDraftsQuery),
+    responses(
+        (status = 200, description = "Drafts with the requested status", body = Vec<ReceiptDraft>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/receipts/drafts")]
+pub async fn list_receipt_drafts(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<ReceiptDraftsQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let drafts = ReceiptService::list_drafts(
+        pool.get_ref(),
+        auth.user_id,
+        query.status.unwrap_or(ReceiptDraftStatus::Pending),
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(drafts))
+}
+
+/// POST /receipts/drafts/{id}/confirm - Create the expense for a draft
+#[utoipa::path(
+    post,
+    path = "/receipts/drafts/{id}/confirm",
+    tag = "Receipts",
+    params(ReceiptDraftIdPath),
+    request_body = ConfirmReceiptDto,
+    responses(
+        (status = 200, description = "Expense created and the draft confirmed", body = ConfirmReceiptResponse),
+        (status = 400, description = "Validation error or no amount available", body = ErrorResponse),
+        (status = 404, description = "Draft, category or account not found", body = ErrorResponse),
+        (status = 409, description = "Draft was already confirmed", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/receipts/drafts/{id}/confirm")]
+pub async fn confirm_receipt_draft(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<ReceiptDraftIdPath>,
+    body: web::Json<ConfirmReceiptDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let (draft, transaction) =
+        ReceiptService::confirm_draft(pool.get_ref(), path.id, auth.user_id, body.into_inner())
+            .await?;
+
+    let response = ConfirmReceiptResponse {
+        draft,
+        transaction: TransactionResponse::from(transaction),
+    };
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &response.transaction,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// DELETE /receipts/drafts/{id} - Dismiss a draft
+#[utoipa::path(
+    delete,
+    path = "/receipts/drafts/{id}",
+    tag = "Receipts",
+    params(ReceiptDraftIdPath),
+    responses(
+        (status = 200, description = "Draft dismissed; a confirmed draft's transaction is kept", body = DeleteResponse),
+        (status = 404, description = "Draft not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/receipts/drafts/{id}")]
+pub async fn delete_receipt_draft(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<ReceiptDraftIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    ReceiptService::delete_draft(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Receipt draft deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// POST /inbound/email - Inbound email from the mail relay
+///
+/// The relay signs the raw body with `INBOUND_EMAIL_SECRET` in an
+/// `X-Signature` header, in the same `t=<unix seconds>,v1=<hex digest>` format
+/// as outgoing webhooks.
+#[utoipa::path(
+    post,
+    path = "/inbound/email",
+    tag = "Receipts",
+    request_body = InboundEmailPayload,
+    responses(
+        (status = 201, description = "Receipt stored as a pending draft", body = InboundEmailResponse),
+        (status = 400, description = "Malformed payload", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid signature", body = ErrorResponse),
+        (status = 404, description = "No ingestion address among the recipients", body = ErrorResponse)
+    )
+)]
+#[post("/inbound/email")]
+pub async fn receive_inbound_email(
+    pool: web::Data<PgPool>,
+    config: web::Data<InboundEmailConfig>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> Result<HttpResponse, AppError> {
+    let signed = match (&config.secret, req.headers().get(SIGNATURE_HEADER)) {
+        (Some(secret), Some(header)) => header
+            .to_str()
+            .is_ok_and(|header| verify(header, secret, &body, Utc::now().timestamp())),
+        _ => false,
+    };
+    if !signed {
+        return Err(AppError::Unauthorized(
+            "Invalid inbound email signature".to_string(),
+        ));
+    }
+
+    let payload: InboundEmailPayload = serde_json::from_slice(&body)
+        .map_err(|e| AppError::ValidationError(format!("Invalid inbound email: {e}")))?;
+
+    let user_id = match config.token_in(&payload.to) {
+        Some(token) => ReceiptService::user_for_token(pool.get_ref(), token).await?,
+        None => None,
+    }
+    .ok_or_else(|| AppError::NotFound("Unknown receipt ingestion address".to_string()))?;
+
+    let parsed = parse_receipt(&payload.from, &payload.subject, &payload.text);
+    let draft =
+        ReceiptService::create_draft(pool.get_ref(), user_id, &parsed, &payload.subject).await?;
+
+    WebhookService::publish(pool.get_ref(), user_id, events::RECEIPT_RECEIVED, &draft).await;
+
+    Ok(HttpResponse::Created().json(InboundEmailResponse { draft_id: draft.id }))
+}
+
+/// Register the receipt routes and the inbound email webhook
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_receipt_address)
+        .service(rotate_receipt_address)
+        .service(list_receipt_drafts)
+        .service(confirm_receipt_draft)
+        .service(delete_receipt_draft)
+        .service(receive_inbound_email);
+}
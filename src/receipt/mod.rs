@@ -0,0 +1,6 @@
+pub mod handlers;
+pub mod models;
+pub mod parser;
+pub mod service;
+
+pub use handlers::*;
@@ -0,0 +1,194 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+use crate::transaction::models::TransactionResponse;
+
+fn validate_positive_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount <= Decimal::ZERO {
+        return Err(ValidationError::new("amount_must_be_positive"));
+    }
+    Ok(())
+}
+
+/// Settings for inbound receipt email
+#[derive(Debug, Clone)]
+pub struct InboundEmailConfig {
+    /// Domain of the ingestion addresses
+    pub domain: String,
+    /// Secret the mail relay signs inbound payloads with; unset rejects them all
+    pub secret: Option<String>,
+}
+
+impl Default for InboundEmailConfig {
+    fn default() -> Self {
+        Self {
+            domain: "receipts.localhost".to_string(),
+            secret: None,
+        }
+    }
+}
+
+impl InboundEmailConfig {
+    /// Ingestion address for an inbox token
+    pub fn address(&self, token: &str) -> String {
+        format!("receipts+{}@{}", token, self.domain)
+    }
+
+    /// Inbox token of the first ingestion address among the recipients
+    pub fn token_in<'a>(&self, recipients: &'a str) -> Option<&'a str> {
+        recipients
+            .split([',', ';', ' ', '<', '>'])
+            .filter_map(|address| {
+                let (local, domain) = address.trim().trim_matches('"').split_once('@')?;
+                let token = local.strip_prefix("receipts+")?;
+                (domain.eq_ignore_ascii_case(&self.domain)
+                    && !token.is_empty()
+                    && token.chars().all(|c| c.is_ascii_hexdigit()))
+                .then_some(token)
+            })
+            .next()
+    }
+}
+
+/// Where a draft stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReceiptDraftStatus {
+    /// Waiting for the user to confirm or dismiss it
+    Pending,
+    /// Turned into a transaction
+    Confirmed,
+}
+
+impl ReceiptDraftStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReceiptDraftStatus::Pending => "pending",
+            ReceiptDraftStatus::Confirmed => "confirmed",
+        }
+    }
+}
+
+/// Database entity for receipt drafts
+#[derive(Debug, Clone, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptDraft {
+    pub id: Uuid,
+    /// Merchant read from the receipt
+    #[schema(example = "Corner Cafe")]
+    pub merchant: Option<String>,
+    /// Amount charged, if found
+    #[schema(example = 8.37)]
+    pub amount: Option<Decimal>,
+    /// Purchase date, if found
+    pub receipt_date: Option<NaiveDate>,
+    /// Original sender of the receipt
+    #[schema(example = "Corner Cafe <receipts@cornercafe.com>")]
+    pub sender: Option<String>,
+    pub subject: Option<String>,
+    #[schema(value_type = ReceiptDraftStatus)]
+    pub status: String,
+    /// Transaction created on confirmation
+    pub transaction_id: Option<Uuid>,
+    pub received_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ReceiptDraft {
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.merchant = decrypt_optional(self.merchant)?;
+        self.sender = decrypt_optional(self.sender)?;
+        self.subject = decrypt_optional(self.subject)?;
+        Ok(self)
+    }
+}
+
+/// A user's ingestion address
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptAddressResponse {
+    /// Forward receipts here to create drafts
+    #[schema(example = "receipts+3f9a1c07d2b84e61@receipts.example.com")]
+    pub address: String,
+}
+
+/// Payload posted by the mail relay for each inbound email
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct InboundEmailPayload {
+    /// Recipient addresses (the envelope or `To` header)
+    #[schema(example = "receipts+3f9a1c07d2b84e61@receipts.example.com")]
+    pub to: String,
+    /// Sender address, usually the user forwarding the receipt
+    #[schema(example = "me@example.com")]
+    pub from: String,
+    #[serde(default)]
+    pub subject: String,
+    /// Plain-text body
+    #[serde(default)]
+    pub text: String,
+}
+
+/// Response to the mail relay
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InboundEmailResponse {
+    /// Draft created for the receipt
+    pub draft_id: Uuid,
+}
+
+/// Query parameters for listing drafts
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReceiptDraftsQuery {
+    /// Drafts with this status (default pending)
+    pub status: Option<ReceiptDraftStatus>,
+}
+
+/// Request body for confirming a draft; omitted fields use the parsed values
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmReceiptDto {
+    /// Category for the expense
+    pub category_id: Uuid,
+
+    /// Account paid from
+    pub account_id: Option<Uuid>,
+
+    /// Amount, required when none was found in the receipt
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 8.37)]
+    pub amount: Option<Decimal>,
+
+    /// Transaction date (defaults to the receipt's date, else when it was received)
+    pub transaction_date: Option<DateTime<Utc>>,
+
+    /// Description (defaults to the merchant, max 200 chars)
+    #[validate(length(max = 200, message = "Description cannot exceed 200 characters"))]
+    #[schema(example = "Coffee with Sam")]
+    pub description: Option<String>,
+}
+
+/// Response for confirming a draft
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfirmReceiptResponse {
+    pub draft: ReceiptDraft,
+    pub transaction: TransactionResponse,
+}
+
+/// Path parameters for a receipt draft
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ReceiptDraftIdPath {
+    /// Draft UUID
+    pub id: Uuid,
+}
@@ -0,0 +1,196 @@
+//! Best-effort extraction of merchant, amount and date from a forwarded
+//! receipt email. Anything that can't be found is left for the user to fill
+//! in when confirming the draft.
+
+use chrono::NaiveDate;
+use lazy_static::lazy_static;
+use regex::Regex;
+use rust_decimal::Decimal;
+
+lazy_static! {
+    /// Money amounts such as `$1,234.56`, `EUR 12.00` or `12.00`
+    static ref AMOUNT: Regex = Regex::new(
+        r"(?i)(?:[$€£¥]|\b(?:usd|eur|gbp|cad|aud)\b)?\s?(\d{1,3}(?:,\d{3})+\.\d{2}|\d+\.\d{2})\b"
+    )
+    .expect("valid amount pattern");
+    /// Lines naming the amount charged
+    static ref TOTAL_LINE: Regex =
+        Regex::new(r"(?i)\b(grand total|total|amount paid|amount charged|amount due|charged)\b")
+            .expect("valid total pattern");
+    static ref SUBTOTAL_LINE: Regex =
+        Regex::new(r"(?i)\bsub-?total\b").expect("valid subtotal pattern");
+    /// The original sender inside a forwarded message
+    static ref FORWARDED_FROM: Regex =
+        Regex::new(r"(?im)^\s*from:\s*(.+)$").expect("valid forwarded-from pattern");
+    /// Merchant named in the subject: "Your receipt from Acme", "Order from Acme"
+    static ref SUBJECT_MERCHANT: Regex = Regex::new(
+        r"(?i)\b(?:receipt|order|purchase|payment|invoice)s?\b.*?\bfrom\s+(.+?)\s*(?:[#(\[-].*)?$"
+    )
+    .expect("valid subject pattern");
+    static ref FORWARD_PREFIX: Regex =
+        Regex::new(r"(?i)^\s*((fwd?|fw|re)\s*:\s*)+").expect("valid prefix pattern");
+    static ref ISO_DATE: Regex =
+        Regex::new(r"\b(\d{4}-\d{2}-\d{2})\b").expect("valid ISO date pattern");
+    static ref US_DATE: Regex =
+        Regex::new(r"\b(\d{1,2}/\d{1,2}/\d{4})\b").expect("valid US date pattern");
+    static ref MONTH_DAY_YEAR: Regex = Regex::new(
+        r"(?i)\b((?:jan|feb|mar|apr|may|jun|jul|aug|sep|sept|oct|nov|dec)[a-z]*\.?\s+\d{1,2},?\s+\d{4})\b"
+    )
+    .expect("valid month-day-year pattern");
+    static ref DAY_MONTH_YEAR: Regex = Regex::new(
+        r"(?i)\b(\d{1,2}\s+(?:jan|feb|mar|apr|may|jun|jul|aug|sep|sept|oct|nov|dec)[a-z]*\.?\s+\d{4})\b"
+    )
+    .expect("valid day-month-year pattern");
+}
+
+/// What could be read from a receipt
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedReceipt {
+    pub merchant: Option<String>,
+    pub amount: Option<Decimal>,
+    pub date: Option<NaiveDate>,
+    /// Original sender: the forwarded message's `From:` line, else the email's sender
+    pub sender: String,
+}
+
+fn parse_amount(text: &str) -> Option<Decimal> {
+    text.replace(',', "").parse::<Decimal>().ok()
+}
+
+fn amounts_in(line: &str) -> Vec<Decimal> {
+    AMOUNT
+        .captures_iter(line)
+        .filter_map(|c| parse_amount(&c[1]))
+        .filter(|amount| *amount > Decimal::ZERO)
+        .collect()
+}
+
+/// The last amount on the last "total" line, else the largest amount anywhere
+fn find_amount(text: &str) -> Option<Decimal> {
+    text.lines()
+        .rev()
+        .filter(|line| TOTAL_LINE.is_match(line) && !SUBTOTAL_LINE.is_match(line))
+        .find_map(|line| amounts_in(line).last().copied())
+        .or_else(|| amounts_in(text).into_iter().max())
+}
+
+fn find_date(text: &str) -> Option<NaiveDate> {
+    // Month names are normalized to their three-letter form for parsing
+    let short_month = |s: &str| {
+        let mut words: Vec<String> = s
+            .replace([',', '.'], " ")
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        for word in words.iter_mut() {
+            if word.chars().next().is_some_and(|c| c.is_alphabetic()) {
+                *word = word.chars().take(3).collect();
+            }
+        }
+        words.join(" ")
+    };
+
+    let candidates = [
+        ISO_DATE
+            .captures(text)
+            .and_then(|c| NaiveDate::parse_from_str(&c[1], "%Y-%m-%d").ok()),
+        MONTH_DAY_YEAR
+            .captures(text)
+            .and_then(|c| NaiveDate::parse_from_str(&short_month(&c[1]), "%b %d %Y").ok()),
+        DAY_MONTH_YEAR
+            .captures(text)
+            .and_then(|c| NaiveDate::parse_from_str(&short_month(&c[1]), "%d %b %Y").ok()),
+        US_DATE
+            .captures(text)
+            .and_then(|c| NaiveDate::parse_from_str(&c[1], "%m/%d/%Y").ok()),
+    ];
+    candidates.into_iter().flatten().next()
+}
+
+/// Display name of an address (`"Acme Store" <a@b.c>`), else the mail domain's
+/// first label (`receipts@acme.com` gives `Acme`)
+fn merchant_from_sender(sender: &str) -> Option<String> {
+    let sender = sender.trim();
+    if let Some((name, _)) = sender.split_once('<') {
+        let name = name.trim().trim_matches('"').trim();
+        if !name.is_empty() {
+            return Some(name.to_string());
+        }
+    }
+
+    let address = sender.trim_matches(|c| c == '<' || c == '>');
+    let domain = address.split_once('@')?.1;
+    let labels: Vec<&str> = domain.split('.').collect();
+    // Skip subdomains like mail.acme.com and email.receipts.acme.co.uk
+    let label = labels
+        .iter()
+        .rev()
+        .nth(1)
+        .filter(|l| !matches!(**l, "co" | "com"))
+        .or_else(|| labels.iter().rev().nth(2))?;
+    let mut chars = label.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+/// Extract what we can from an inbound receipt email
+pub fn parse_receipt(from: &str, subject: &str, text: &str) -> ParsedReceipt {
+    let sender = FORWARDED_FROM
+        .captures(text)
+        .map(|c| c[1].trim().to_string())
+        .unwrap_or_else(|| from.trim().to_string());
+
+    let subject = FORWARD_PREFIX.replace(subject, "");
+    let merchant = SUBJECT_MERCHANT
+        .captures(&subject)
+        .map(|c| c[1].trim().trim_end_matches(['.', '!']).to_string())
+        .filter(|m| !m.is_empty())
+        .or_else(|| merchant_from_sender(&sender))
+        .map(|m| m.chars().take(100).collect());
+
+    ParsedReceipt {
+        merchant,
+        amount: find_amount(text),
+        date: find_date(text),
+        sender,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forwarded_receipt() {
+        let text = "---------- Forwarded message ---------\n\
+                    From: Corner Cafe <receipts@mail.cornercafe.com>\n\
+                    Date: Oct 14, 2026 at 9:12 AM\n\
+                    Subject: Thanks for your visit\n\n\
+                    Latte            $4.50\n\
+                    Croissant        $3.25\n\
+                    Subtotal         $7.75\n\
+                    Tax              $0.62\n\
+                    Total            $8.37\n";
+        let parsed = parse_receipt("me@example.com", "Fwd: Thanks for your visit", text);
+        assert_eq!(parsed.amount, Some(Decimal::new(837, 2)));
+        assert_eq!(parsed.date, NaiveDate::from_ymd_opt(2026, 10, 14));
+        assert_eq!(parsed.merchant.as_deref(), Some("Corner Cafe"));
+        assert_eq!(parsed.sender, "Corner Cafe <receipts@mail.cornercafe.com>");
+
+        // Merchant from the subject, date in ISO form, largest amount as fallback
+        let parsed = parse_receipt(
+            "no-reply@shop.example.co.uk",
+            "Your receipt from Acme Hardware #10423",
+            "Order date: 2026-09-30\nHammer 1,204.00\nNails 12.00",
+        );
+        assert_eq!(parsed.merchant.as_deref(), Some("Acme Hardware"));
+        assert_eq!(parsed.amount, Some(Decimal::new(120400, 2)));
+        assert_eq!(parsed.date, NaiveDate::from_ymd_opt(2026, 9, 30));
+
+        assert_eq!(
+            merchant_from_sender("billing@email.streamco.com").as_deref(),
+            Some("Streamco")
+        );
+        assert_eq!(parse_receipt("a@b.io", "Hi", "no numbers").amount, None);
+    }
+}
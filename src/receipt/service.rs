@@ -0,0 +1,246 @@
+use chrono::{NaiveTime, Utc};
+use rand::Rng;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::models::{ConfirmReceiptDto, ReceiptDraft, ReceiptDraftStatus};
+use super::parser::ParsedReceipt;
+use crate::crypto::encrypt_optional;
+use crate::errors::{AppError, ErrorCode};
+use crate::transaction::models::{CreateTransactionDto, Transaction, TransactionType};
+use crate::transaction::service::TransactionService;
+
+const DRAFT_COLUMNS: &str = "id, merchant, amount, receipt_date, sender, subject, status, transaction_id, received_at, updated_at";
+
+/// Service layer for receipt ingestion.
+pub struct ReceiptService;
+
+impl ReceiptService {
+    fn new_token() -> String {
+        let bytes: [u8; 8] = rand::thread_rng().gen();
+        hex::encode(bytes)
+    }
+
+    /// The user's inbox token, created on first use
+    pub async fn inbox_token(pool: &PgPool, user_id: Uuid) -> Result<String, AppError> {
+        sqlx::query_scalar::<_, String>(
+            r#"
+            INSERT INTO receipt_inboxes (user_id, token) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET token = receipt_inboxes.token
+            RETURNING token
+            "#,
+        )
+        .bind(user_id)
+        .bind(Self::new_token())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Replace the user's inbox token; mail to the old address is rejected
+    pub async fn rotate_inbox_token(pool: &PgPool, user_id: Uuid) -> Result<String, AppError> {
+        sqlx::query_scalar::<_, String>(
+            r#"
+            INSERT INTO receipt_inboxes (user_id, token) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET token = EXCLUDED.token, created_at = NOW()
+            RETURNING token
+            "#,
+        )
+        .bind(user_id)
+        .bind(Self::new_token())
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Owner of an inbox token
+    pub async fn user_for_token(pool: &PgPool, token: &str) -> Result<Option<Uuid>, AppError> {
+        sqlx::query_scalar::<_, Uuid>("SELECT user_id FROM receipt_inboxes WHERE token = $1")
+            .bind(token.to_lowercase())
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Store a parsed receipt as a pending draft
+    pub async fn create_draft(
+        pool: &PgPool,
+        user_id: Uuid,
+        parsed: &ParsedReceipt,
+        subject: &str,
+    ) -> Result<ReceiptDraft, AppError> {
+        let subject: String = subject.trim().chars().take(200).collect();
+        let sender: String = parsed.sender.chars().take(200).collect();
+
+        sqlx::query_as::<_, ReceiptDraft>(&format!(
+            r#"
+            INSERT INTO receipt_drafts (user_id, merchant, amount, receipt_date, sender, subject)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {DRAFT_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(encrypt_optional(parsed.merchant.as_deref())?)
+        .bind(parsed.amount)
+        .bind(parsed.date)
+        .bind(encrypt_optional(
+            Some(sender.as_str()).filter(|s| !s.is_empty()),
+        )?)
+        .bind(encrypt_optional(
+            Some(subject.as_str()).filter(|s| !s.is_empty()),
+        )?)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .decrypted()
+    }
+
+    /// A user's drafts with the given status, newest first
+    pub async fn list_drafts(
+        pool: &PgPool,
+        user_id: Uuid,
+        status: ReceiptDraftStatus,
+    ) -> Result<Vec<ReceiptDraft>, AppError> {
+        sqlx::query_as::<_, ReceiptDraft>(&format!(
+            "SELECT {DRAFT_COLUMNS} FROM receipt_drafts WHERE user_id = $1 AND status = $2 ORDER BY received_at DESC"
+        ))
+        .bind(user_id)
+        .bind(status.as_str())
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(ReceiptDraft::decrypted)
+        .collect()
+    }
+
+    async fn get_draft(
+        pool: &PgPool,
+        draft_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<ReceiptDraft, AppError> {
+        sqlx::query_as::<_, ReceiptDraft>(&format!(
+            "SELECT {DRAFT_COLUMNS} FROM receipt_drafts WHERE id = $1 AND user_id = $2"
+        ))
+        .bind(draft_id)
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Receipt draft not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Turn a pending draft into an expense. Fails with 409 if the draft was
+    /// already confirmed, including by a concurrent request.
+    pub async fn confirm_draft(
+        pool: &PgPool,
+        draft_id: Uuid,
+        user_id: Uuid,
+        dto: ConfirmReceiptDto,
+    ) -> Result<(ReceiptDraft, Transaction), AppError> {
+        let draft = Self::get_draft(pool, draft_id, user_id).await?;
+        if draft.status != ReceiptDraftStatus::Pending.as_str() {
+            return Err(AppError::Coded(
+                ErrorCode::ReceiptAlreadyConfirmed,
+                "Receipt draft was already confirmed".to_string(),
+            ));
+        }
+
+        let amount = dto.amount.or(draft.amount).ok_or_else(|| {
+            AppError::ValidationError("No amount was found in the receipt; provide one".to_string())
+        })?;
+        // A date-only receipt is booked at midday UTC to stay on that date
+        let transaction_date = dto.transaction_date.unwrap_or_else(|| {
+            draft.receipt_date.map_or(draft.received_at, |date| {
+                date.and_time(NaiveTime::from_hms_opt(12, 0, 0).expect("valid time"))
+                    .and_utc()
+                    .min(Utc::now())
+            })
+        });
+        let description = dto
+            .description
+            .or_else(|| draft.merchant.clone())
+            .filter(|d| !d.trim().is_empty());
+
+        let transaction = TransactionService::create_transaction(
+            pool,
+            user_id,
+            CreateTransactionDto {
+                reimbursable: false,
+                reimbursement_payer: None,
+                tax_category: None,
+                currency: None,
+                exchange_rate: None,
+                pending: false,
+                payee_id: None,
+                linked_transaction_id: None,
+                fee_amount: None,
+                destination_amount: None,
+                category_id: dto.category_id,
+                account_id: dto.account_id,
+                destination_account_id: None,
+                amount,
+                transaction_date,
+                description,
+                transaction_type: TransactionType::Expense,
+            },
+        )
+        .await?;
+
+        let confirmed = sqlx::query_as::<_, ReceiptDraft>(&format!(
+            r#"
+            UPDATE receipt_drafts SET status = 'confirmed', transaction_id = $3
+            WHERE id = $1 AND user_id = $2 AND status = 'pending'
+            RETURNING {DRAFT_COLUMNS}
+            "#
+        ))
+        .bind(draft_id)
+        .bind(user_id)
+        .bind(transaction.id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()));
+
+        match confirmed {
+            Ok(Some(draft)) => Ok((draft.decrypted()?, transaction)),
+            other => {
+                // Don't leave behind a transaction for a draft that wasn't confirmed
+                if let Err(undo) =
+                    TransactionService::delete_transaction(pool, user_id, transaction.id).await
+                {
+                    warn!(
+                        transaction_id = %transaction.id,
+                        "Failed to remove transaction of unconfirmed receipt: {}", undo
+                    );
+                }
+                Err(other.err().unwrap_or_else(|| {
+                    AppError::Coded(
+                        ErrorCode::ReceiptAlreadyConfirmed,
+                        "Receipt draft was already confirmed".to_string(),
+                    )
+                }))
+            }
+        }
+    }
+
+    /// Dismiss (delete) a draft
+    pub async fn delete_draft(
+        pool: &PgPool,
+        draft_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM receipt_drafts WHERE id = $1 AND user_id = $2")
+            .bind(draft_id)
+            .bind(user_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Receipt draft not found".to_string()));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::scopes::Scope;
+use crate::budget::models::MonthBaseQuery;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::household::{HouseholdQuery, HouseholdReport};
+use super::models::{
+    tax_report_csv, CompareQuery, MonthComparisonResponse, ReimbursementReport, ReportFormat,
+    TaxReport, TaxReportQuery, TaxYearPath,
+};
+use super::service::ReportService;
+
+/// GET /reports/compare - Compare a month's spending with the previous month and last year
+#[utoipa::path(
+    get,
+    path = "/reports/compare",
+    tag = "Reports",
+    params(CompareQuery, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Per-category and total spending with deltas", body = MonthComparisonResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/reports/compare")]
+pub async fn compare_months(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    query: web::Query<CompareQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let month = month_base.to_internal(query.month)?;
+
+    let comparison =
+        ReportService::compare_months(pool.get_ref(), auth.user_id, month, query.year).await?;
+
+    Ok(HttpResponse::Ok().json(comparison.with_month_base(&month_base)))
+}
+
+/// GET /reports/household - Spending and net worth across the household's accounts
+#[utoipa::path(
+    get,
+    path = "/reports/household",
+    tag = "Reports",
+    params(HouseholdQuery, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Household totals per currency with each member's contribution", body = HouseholdReport),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/reports/household")]
+pub async fn household_report(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+    query: web::Query<HouseholdQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let month = month_base.to_internal(query.month)?;
+
+    let report = ReportService::household(pool.get_ref(), auth.user_id, month, query.year).await?;
+
+    Ok(HttpResponse::Ok().json(report.with_month_base(&month_base)))
+}
+
+/// GET /reports/reimbursements - Outstanding reimbursable expenses per payer
+#[utoipa::path(
+    get,
+    path = "/reports/reimbursements",
+    tag = "Reports",
+    responses(
+        (status = 200, description = "Amounts still owed back, per payer", body = ReimbursementReport),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/reports/reimbursements")]
+pub async fn reimbursements(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let report = ReportService::reimbursements(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(report))
+}
+
+/// GET /reports/tax/{year} - Deductible expenses per tax category for a year
+#[utoipa::path(
+    get,
+    path = "/reports/tax/{year}",
+    tag = "Reports",
+    params(TaxYearPath, TaxReportQuery),
+    responses(
+        (status = 200, description = "Deductible totals per tax category, or with format=csv every deductible transaction as an attachment",
+            content((TaxReport = "application/json"), (String = "text/csv"))),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/reports/tax/{year}")]
+pub async fn tax_report(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TaxYearPath>,
+    query: web::Query<TaxReportQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    path.validate()?;
+
+    let items = ReportService::tax_line_items(pool.get_ref(), auth.user_id, path.year).await?;
+
+    match query.format {
+        ReportFormat::Json => Ok(HttpResponse::Ok().json(TaxReport::from_items(path.year, &items))),
+        ReportFormat::Csv => Ok(HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header(ContentDisposition {
+                disposition: DispositionType::Attachment,
+                parameters: vec![DispositionParam::Filename(format!(
+                    "tax-report-{}.csv",
+                    path.year
+                ))],
+            })
+            .body(tax_report_csv(&items))),
+    }
+}
+
+/// Register the report routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(compare_months)
+        .service(household_report)
+        .service(reimbursements)
+        .service(tax_report);
+}
@@ -0,0 +1,206 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::models::MonthRef;
+use crate::budget::models::MonthBaseQuery;
+
+/// Query parameters for the household roll-up
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct HouseholdQuery {
+    /// Month (0-11, or 1-12 with monthBase=1)
+    #[param(example = 2)]
+    pub month: i16,
+    /// Year
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[param(example = 2026)]
+    pub year: i16,
+}
+
+/// One member's household-visible accounts in one currency, with the month's
+/// flows through them
+#[derive(Debug, Clone, FromRow)]
+pub struct HouseholdRow {
+    pub user_id: Uuid,
+    pub member_name: String,
+    pub currency: String,
+    pub accounts_included: i64,
+    pub net_worth: Decimal,
+    pub spending: Decimal,
+    pub income: Decimal,
+}
+
+/// What one member contributes to a household currency total
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberContribution {
+    pub user_id: Uuid,
+    /// Full name, or email when none is set
+    #[schema(example = "Alex Doe")]
+    pub name: String,
+    /// Whether this member is the caller
+    pub is_you: bool,
+    /// Accounts the member has made visible to the household
+    #[schema(example = 3)]
+    pub accounts_included: i64,
+    /// Expenses from those accounts in the month, net of refunds
+    #[schema(example = 1240.50)]
+    pub spending: Decimal,
+    /// Income into those accounts in the month
+    #[schema(example = 3200.00)]
+    pub income: Decimal,
+    /// Sum of those accounts' balances
+    #[schema(example = 15800.00)]
+    pub net_worth: Decimal,
+    /// Percentage of the household's spending
+    #[schema(example = 62.5)]
+    pub spending_share: Decimal,
+}
+
+/// Household totals in one currency, broken down per member
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HouseholdCurrencyTotals {
+    #[schema(example = "USD")]
+    pub currency: String,
+    #[schema(example = 1984.80)]
+    pub spending: Decimal,
+    #[schema(example = 6400.00)]
+    pub income: Decimal,
+    #[schema(example = 24150.00)]
+    pub net_worth: Decimal,
+    /// Largest spender first
+    pub members: Vec<MemberContribution>,
+}
+
+/// Response for GET /reports/household
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HouseholdReport {
+    /// The reported month
+    pub period: MonthRef,
+    /// People in the household, including the caller
+    #[schema(example = 2)]
+    pub members_count: usize,
+    /// Totals per account currency; amounts in different currencies are not
+    /// added together
+    pub currencies: Vec<HouseholdCurrencyTotals>,
+}
+
+impl HouseholdReport {
+    /// Group member rows by currency and work out each member's share of spending
+    pub fn from_rows(
+        period: MonthRef,
+        viewer_id: Uuid,
+        members_count: usize,
+        rows: Vec<HouseholdRow>,
+    ) -> Self {
+        let mut currencies: Vec<HouseholdCurrencyTotals> = Vec::new();
+        for row in rows {
+            let index = match currencies.iter().position(|c| c.currency == row.currency) {
+                Some(index) => index,
+                None => {
+                    currencies.push(HouseholdCurrencyTotals {
+                        currency: row.currency.clone(),
+                        spending: Decimal::ZERO,
+                        income: Decimal::ZERO,
+                        net_worth: Decimal::ZERO,
+                        members: Vec::new(),
+                    });
+                    currencies.len() - 1
+                }
+            };
+            let totals = &mut currencies[index];
+            totals.spending += row.spending;
+            totals.income += row.income;
+            totals.net_worth += row.net_worth;
+            totals.members.push(MemberContribution {
+                is_you: row.user_id == viewer_id,
+                user_id: row.user_id,
+                name: row.member_name,
+                accounts_included: row.accounts_included,
+                spending: row.spending,
+                income: row.income,
+                net_worth: row.net_worth,
+                spending_share: Decimal::ZERO,
+            });
+        }
+
+        for totals in &mut currencies {
+            for member in &mut totals.members {
+                if totals.spending > Decimal::ZERO {
+                    member.spending_share =
+                        (member.spending / totals.spending * Decimal::from(100)).round_dp(1);
+                }
+            }
+            totals.members.sort_by(|a, b| {
+                b.spending
+                    .cmp(&a.spending)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+        }
+        currencies.sort_by(|a, b| a.currency.cmp(&b.currency));
+
+        Self {
+            period,
+            members_count,
+            currencies,
+        }
+    }
+
+    /// Report the month in the caller's numbering convention
+    pub fn with_month_base(mut self, month_base: &MonthBaseQuery) -> Self {
+        self.period = MonthRef::new(month_base.to_external(self.period.month), self.period.year);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(user_id: Uuid, name: &str, currency: &str, spending: i64) -> HouseholdRow {
+        HouseholdRow {
+            user_id,
+            member_name: name.to_string(),
+            currency: currency.to_string(),
+            accounts_included: 1,
+            net_worth: Decimal::from(1000),
+            spending: Decimal::from(spending),
+            income: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_household_report_groups_by_currency_with_shares() {
+        let you = Uuid::new_v4();
+        let partner = Uuid::new_v4();
+        let report = HouseholdReport::from_rows(
+            MonthRef::new(0, 2026),
+            you,
+            2,
+            vec![
+                row(you, "You", "USD", 100),
+                row(partner, "Partner", "USD", 300),
+                row(partner, "Partner", "EUR", 0),
+            ],
+        );
+
+        assert_eq!(report.currencies.len(), 2);
+        let eur = &report.currencies[0];
+        assert_eq!(eur.currency, "EUR");
+        assert_eq!(eur.members[0].spending_share, Decimal::ZERO);
+
+        let usd = &report.currencies[1];
+        assert_eq!(usd.spending, Decimal::from(400));
+        assert_eq!(usd.net_worth, Decimal::from(2000));
+        assert_eq!(usd.members[0].name, "Partner");
+        assert_eq!(usd.members[0].spending_share, Decimal::from(75));
+        assert!(usd.members[1].is_you);
+        assert_eq!(usd.members[1].spending_share, Decimal::from(25));
+    }
+}
@@ -0,0 +1,6 @@
+pub mod handlers;
+pub mod household;
+pub mod models;
+pub mod service;
+
+pub use handlers::*;
@@ -0,0 +1,581 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::budget::models::MonthBaseQuery;
+use crate::transaction::reimbursement::OutstandingReimbursement;
+
+/// Query parameters for comparing a month against earlier periods
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct CompareQuery {
+    /// Month (0-11, or 1-12 with monthBase=1)
+    #[param(example = 2)]
+    pub month: i16,
+    /// Year
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[param(example = 2026)]
+    pub year: i16,
+}
+
+/// Expenses for one category name in one compared period
+#[derive(Debug, FromRow)]
+pub struct CompareRow {
+    /// "current", "previous" or "last_year"
+    pub period: String,
+    pub category_name: String,
+    pub spent: Decimal,
+}
+
+/// A calendar month referenced by a comparison
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct MonthRef {
+    /// Month (0-11, or 1-12 with monthBase=1)
+    #[schema(example = 1)]
+    pub month: i16,
+    /// Year
+    #[schema(example = 2026)]
+    pub year: i16,
+}
+
+impl MonthRef {
+    pub fn new(month: i16, year: i16) -> Self {
+        Self { month, year }
+    }
+
+    /// The calendar month before this one
+    pub fn previous(self) -> Self {
+        if self.month == 0 {
+            Self::new(11, self.year - 1)
+        } else {
+            Self::new(self.month - 1, self.year)
+        }
+    }
+
+    /// The same month one year earlier
+    pub fn last_year(self) -> Self {
+        Self::new(self.month, self.year - 1)
+    }
+
+    fn with_month_base(self, month_base: &MonthBaseQuery) -> Self {
+        Self::new(month_base.to_external(self.month), self.year)
+    }
+}
+
+/// Change from an earlier period to the compared month
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendDelta {
+    /// Difference in spending (positive means more was spent this month)
+    #[schema(example = 48.25)]
+    pub amount: Decimal,
+    /// Difference as a percentage of the earlier period, absent when it had no spending
+    #[schema(example = 12.5)]
+    pub percent: Option<Decimal>,
+}
+
+impl SpendDelta {
+    fn between(current: Decimal, earlier: Decimal) -> Self {
+        let amount = current - earlier;
+        let percent = if earlier > Decimal::ZERO {
+            Some((amount / earlier * Decimal::ONE_HUNDRED).round_dp(1))
+        } else {
+            None
+        };
+        Self { amount, percent }
+    }
+}
+
+/// Spending across the three compared periods, with deltas
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpendComparison {
+    /// Spending in the requested month
+    #[schema(example = 434.25)]
+    pub current: Decimal,
+    /// Spending in the previous month
+    #[schema(example = 386.00)]
+    pub previous: Decimal,
+    /// Spending in the same month last year
+    #[schema(example = 402.10)]
+    pub last_year: Decimal,
+    pub vs_previous: SpendDelta,
+    pub vs_last_year: SpendDelta,
+}
+
+impl SpendComparison {
+    fn new(current: Decimal, previous: Decimal, last_year: Decimal) -> Self {
+        Self {
+            current,
+            previous,
+            last_year,
+            vs_previous: SpendDelta::between(current, previous),
+            vs_last_year: SpendDelta::between(current, last_year),
+        }
+    }
+}
+
+/// Comparison for categories sharing a name across budgets
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategoryComparison {
+    /// Category name
+    #[schema(example = "Groceries")]
+    pub name: String,
+    #[serde(flatten)]
+    pub spending: SpendComparison,
+}
+
+/// Response for GET /reports/compare
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthComparisonResponse {
+    /// The requested month
+    pub period: MonthRef,
+    /// The month before it
+    pub previous_period: MonthRef,
+    /// The same month one year earlier
+    pub last_year_period: MonthRef,
+    /// Total expenses across all categories
+    pub totals: SpendComparison,
+    /// Per-category expenses, largest current spend first
+    pub categories: Vec<CategoryComparison>,
+}
+
+impl MonthComparisonResponse {
+    /// Pivot per-period rows into one comparison per category name
+    pub fn from_rows(period: MonthRef, rows: Vec<CompareRow>) -> Self {
+        let mut categories: Vec<CategoryComparison> = Vec::new();
+        let mut amounts: Vec<[Decimal; 3]> = Vec::new();
+
+        for row in rows {
+            let index = match categories
+                .iter()
+                .position(|c| c.name.to_lowercase() == row.category_name.to_lowercase())
+            {
+                Some(index) => index,
+                None => {
+                    categories.push(CategoryComparison {
+                        name: row.category_name.clone(),
+                        spending: SpendComparison::new(Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
+                    });
+                    amounts.push([Decimal::ZERO; 3]);
+                    categories.len() - 1
+                }
+            };
+            let slot = match row.period.as_str() {
+                "current" => 0,
+                "previous" => 1,
+                _ => 2,
+            };
+            amounts[index][slot] += row.spent;
+        }
+
+        let mut totals = [Decimal::ZERO; 3];
+        for (category, [current, previous, last_year]) in categories.iter_mut().zip(amounts) {
+            category.spending = SpendComparison::new(current, previous, last_year);
+            totals[0] += current;
+            totals[1] += previous;
+            totals[2] += last_year;
+        }
+
+        categories.sort_by(|a, b| {
+            b.spending
+                .current
+                .cmp(&a.spending.current)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Self {
+            period,
+            previous_period: period.previous(),
+            last_year_period: period.last_year(),
+            totals: SpendComparison::new(totals[0], totals[1], totals[2]),
+            categories,
+        }
+    }
+
+    /// Report months in the caller's numbering convention
+    pub fn with_month_base(mut self, month_base: &MonthBaseQuery) -> Self {
+        self.period = self.period.with_month_base(month_base);
+        self.previous_period = self.previous_period.with_month_base(month_base);
+        self.last_year_period = self.last_year_period.with_month_base(month_base);
+        self
+    }
+}
+
+/// What one payer still owes
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PayerReimbursements {
+    /// Payer name, absent for items without one
+    #[schema(example = "Acme Corp")]
+    pub payer: Option<String>,
+    /// Total still owed
+    #[schema(example = 240.00)]
+    pub outstanding_amount: Decimal,
+    /// Part of the total not yet claimed
+    #[schema(example = 40.00)]
+    pub pending_amount: Decimal,
+    /// Part of the total claimed and awaiting payment
+    #[schema(example = 200.00)]
+    pub submitted_amount: Decimal,
+    /// Number of outstanding items
+    #[schema(example = 2)]
+    pub item_count: i64,
+    /// Date of the oldest outstanding item
+    pub oldest_transaction_date: DateTime<Utc>,
+}
+
+/// Response for GET /reports/reimbursements
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReimbursementReport {
+    /// Total owed across all payers
+    #[schema(example = 240.00)]
+    pub total_outstanding: Decimal,
+    /// Per-payer totals, largest first
+    pub payers: Vec<PayerReimbursements>,
+}
+
+impl ReimbursementReport {
+    /// Group outstanding items by payer, matching names case-insensitively
+    pub fn from_items(items: Vec<OutstandingReimbursement>) -> Self {
+        let mut payers: Vec<(Option<String>, PayerReimbursements)> = Vec::new();
+
+        for item in items {
+            let key = item.payer_key();
+            let index = match payers.iter().position(|(k, _)| *k == key) {
+                Some(index) => index,
+                None => {
+                    payers.push((
+                        key,
+                        PayerReimbursements {
+                            payer: item.payer.as_deref().map(|p| p.trim().to_string()),
+                            outstanding_amount: Decimal::ZERO,
+                            pending_amount: Decimal::ZERO,
+                            submitted_amount: Decimal::ZERO,
+                            item_count: 0,
+                            oldest_transaction_date: item.transaction_date,
+                        },
+                    ));
+                    payers.len() - 1
+                }
+            };
+            let entry = &mut payers[index].1;
+            entry.outstanding_amount += item.amount;
+            if item.status == "submitted" {
+                entry.submitted_amount += item.amount;
+            } else {
+                entry.pending_amount += item.amount;
+            }
+            entry.item_count += 1;
+            entry.oldest_transaction_date =
+                entry.oldest_transaction_date.min(item.transaction_date);
+        }
+
+        let mut payers: Vec<PayerReimbursements> = payers.into_iter().map(|(_, p)| p).collect();
+        payers.sort_by(|a, b| {
+            b.outstanding_amount
+                .cmp(&a.outstanding_amount)
+                .then_with(|| a.payer.cmp(&b.payer))
+        });
+
+        Self {
+            total_outstanding: payers.iter().map(|p| p.outstanding_amount).sum(),
+            payers,
+        }
+    }
+}
+
+/// Path parameters for the yearly tax report
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+pub struct TaxYearPath {
+    /// Calendar year, in the user's timezone
+    #[validate(range(min = 2000, max = 2100, message = "Year must be between 2000 and 2100"))]
+    #[param(example = 2026)]
+    pub year: i16,
+}
+
+/// Output format for downloadable reports
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    /// Comma-separated line items, sent as an attachment
+    Csv,
+}
+
+/// Query parameters for the yearly tax report
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TaxReportQuery {
+    /// json (default) for totals, csv for every deductible transaction
+    #[serde(default)]
+    pub format: ReportFormat,
+}
+
+/// A deductible expense (or a refund netted against one) in the tax year
+#[derive(Debug, Clone, FromRow)]
+pub struct TaxLineItem {
+    /// The transaction's own tax category, else its category's
+    pub tax_category: String,
+    pub category_name: String,
+    /// Local date in the user's timezone
+    pub transaction_date: NaiveDate,
+    pub description: Option<String>,
+    /// Negative for refunds
+    pub amount: Decimal,
+}
+
+/// Deductible total for one tax category
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxCategoryTotal {
+    #[schema(example = "Charitable donations")]
+    pub tax_category: String,
+    /// Expenses net of refunds
+    #[schema(example = 1250.00)]
+    pub total: Decimal,
+    /// Number of transactions counted, refunds included
+    #[schema(example = 12)]
+    pub transaction_count: i64,
+}
+
+/// Response for GET /reports/tax/{year}
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxReport {
+    #[schema(example = 2026)]
+    pub year: i16,
+    /// Sum of all tax categories
+    #[schema(example = 1250.00)]
+    pub total_deductible: Decimal,
+    /// Per tax category, alphabetically
+    pub categories: Vec<TaxCategoryTotal>,
+}
+
+/// Group line items by tax category, matching labels case-insensitively and
+/// keeping the first spelling seen
+fn group_tax_items(items: &[TaxLineItem]) -> Vec<(String, Vec<&TaxLineItem>)> {
+    let mut groups: Vec<(String, String, Vec<&TaxLineItem>)> = Vec::new();
+    for item in items {
+        let key = item.tax_category.trim().to_lowercase();
+        match groups.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, _, group)) => group.push(item),
+            None => groups.push((key, item.tax_category.trim().to_string(), vec![item])),
+        }
+    }
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+    groups
+        .into_iter()
+        .map(|(_, label, group)| (label, group))
+        .collect()
+}
+
+impl TaxReport {
+    pub fn from_items(year: i16, items: &[TaxLineItem]) -> Self {
+        let categories: Vec<TaxCategoryTotal> = group_tax_items(items)
+            .into_iter()
+            .map(|(tax_category, group)| TaxCategoryTotal {
+                tax_category,
+                total: group.iter().map(|item| item.amount).sum(),
+                transaction_count: group.len() as i64,
+            })
+            .collect();
+
+        Self {
+            year,
+            total_deductible: categories.iter().map(|c| c.total).sum(),
+            categories,
+        }
+    }
+}
+
+/// Quote a CSV field when needed. Text that a spreadsheet would evaluate as a
+/// formula is prefixed with an apostrophe.
+pub(crate) fn csv_field(value: &str, text: bool) -> String {
+    let value = if text && value.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{value}")
+    } else {
+        value.to_string()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+/// Line items grouped by tax category, each group followed by its subtotal
+/// and the whole followed by the grand total
+pub fn tax_report_csv(items: &[TaxLineItem]) -> String {
+    let mut out = String::from("Tax category,Date,Category,Description,Amount\r\n");
+    let mut total = Decimal::ZERO;
+
+    for (tax_category, group) in group_tax_items(items) {
+        let mut subtotal = Decimal::ZERO;
+        for item in group {
+            out.push_str(&format!(
+                "{},{},{},{},{}\r\n",
+                csv_field(&tax_category, true),
+                item.transaction_date,
+                csv_field(&item.category_name, true),
+                csv_field(item.description.as_deref().unwrap_or_default(), true),
+                item.amount.round_dp(2),
+            ));
+            subtotal += item.amount;
+        }
+        out.push_str(&format!(
+            "{},,,Subtotal,{}\r\n",
+            csv_field(&tax_category, true),
+            subtotal.round_dp(2)
+        ));
+        total += subtotal;
+    }
+
+    out.push_str(&format!("Total,,,,{}\r\n", total.round_dp(2)));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(period: &str, name: &str, spent: i64) -> CompareRow {
+        CompareRow {
+            period: period.to_string(),
+            category_name: name.to_string(),
+            spent: Decimal::from(spent),
+        }
+    }
+
+    #[test]
+    fn test_comparison_pivots_rows_and_computes_deltas() {
+        let response = MonthComparisonResponse::from_rows(
+            MonthRef::new(0, 2026),
+            vec![
+                row("current", "Groceries", 300),
+                row("previous", "groceries", 250),
+                row("last_year", "Groceries", 200),
+                row("previous", "Travel", 120),
+            ],
+        );
+
+        assert_eq!(response.previous_period, MonthRef::new(11, 2025));
+        assert_eq!(response.last_year_period, MonthRef::new(0, 2025));
+        assert_eq!(response.categories.len(), 2);
+
+        let groceries = &response.categories[0];
+        assert_eq!(groceries.name, "Groceries");
+        assert_eq!(groceries.spending.vs_previous.amount, Decimal::from(50));
+        assert_eq!(
+            groceries.spending.vs_previous.percent,
+            Some(Decimal::new(200, 1))
+        );
+        assert_eq!(
+            groceries.spending.vs_last_year.percent,
+            Some(Decimal::new(500, 1))
+        );
+
+        let travel = &response.categories[1];
+        assert_eq!(travel.spending.current, Decimal::ZERO);
+        assert_eq!(
+            travel.spending.vs_previous.percent,
+            Some(Decimal::new(-1000, 1))
+        );
+        assert_eq!(travel.spending.vs_last_year.percent, None);
+
+        assert_eq!(response.totals.current, Decimal::from(300));
+        assert_eq!(response.totals.previous, Decimal::from(370));
+        assert_eq!(response.totals.last_year, Decimal::from(200));
+    }
+
+    #[test]
+    fn test_reimbursement_report_groups_by_payer() {
+        use chrono::TimeZone;
+        use uuid::Uuid;
+
+        let item =
+            |amount: i64, payer: Option<&str>, status: &str, day: u32| OutstandingReimbursement {
+                id: Uuid::new_v4(),
+                amount: Decimal::from(amount),
+                payer: payer.map(str::to_string),
+                status: status.to_string(),
+                transaction_date: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+            };
+
+        let report = ReimbursementReport::from_items(vec![
+            item(200, Some("Acme Corp"), "submitted", 5),
+            item(40, Some(" acme corp"), "pending", 2),
+            item(15, None, "pending", 3),
+        ]);
+
+        assert_eq!(report.total_outstanding, Decimal::from(255));
+        assert_eq!(report.payers.len(), 2);
+        let acme = &report.payers[0];
+        assert_eq!(acme.payer.as_deref(), Some("Acme Corp"));
+        assert_eq!(acme.outstanding_amount, Decimal::from(240));
+        assert_eq!(acme.submitted_amount, Decimal::from(200));
+        assert_eq!(acme.pending_amount, Decimal::from(40));
+        assert_eq!(acme.item_count, 2);
+        assert_eq!(acme.oldest_transaction_date.format("%d").to_string(), "02");
+        assert_eq!(report.payers[1].payer, None);
+    }
+
+    #[test]
+    fn test_tax_report_groups_and_exports_csv() {
+        let item =
+            |tax: &str, category: &str, day: u32, description: &str, amount: i64| TaxLineItem {
+                tax_category: tax.to_string(),
+                category_name: category.to_string(),
+                transaction_date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+                description: Some(description.to_string()),
+                amount: Decimal::from(amount),
+            };
+
+        let items = vec![
+            item("Medical", "Health", 1, "Dentist, checkup", 120),
+            item("Charity", "Giving", 2, "=HYPERLINK(\"x\")", 50),
+            item("medical", "Groceries", 3, "Pharmacy", 30),
+            item("Medical", "Health", 4, "Refund", -20),
+        ];
+
+        let report = TaxReport::from_items(2026, &items);
+        assert_eq!(report.total_deductible, Decimal::from(180));
+        assert_eq!(
+            report.categories,
+            vec![
+                TaxCategoryTotal {
+                    tax_category: "Charity".to_string(),
+                    total: Decimal::from(50),
+                    transaction_count: 1,
+                },
+                TaxCategoryTotal {
+                    tax_category: "Medical".to_string(),
+                    total: Decimal::from(130),
+                    transaction_count: 3,
+                },
+            ]
+        );
+
+        let csv = tax_report_csv(&items);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "Tax category,Date,Category,Description,Amount");
+        assert_eq!(
+            lines[1],
+            "Charity,2026-03-02,Giving,\"'=HYPERLINK(\"\"x\"\")\",50"
+        );
+        assert_eq!(lines[2], "Charity,,,Subtotal,50");
+        assert_eq!(
+            lines[3],
+            "Medical,2026-03-01,Health,\"Dentist, checkup\",120"
+        );
+        assert_eq!(lines[5], "Medical,2026-03-04,Health,Refund,-20");
+        assert_eq!(lines[6], "Medical,,,Subtotal,130");
+        assert_eq!(lines[7], "Total,,,,180");
+    }
+}
@@ -0,0 +1,209 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+use crate::summary::service::SummaryService;
+use crate::transaction::service::TransactionService;
+
+use super::household::{HouseholdReport, HouseholdRow};
+use super::models::{
+    CompareRow, MonthComparisonResponse, MonthRef, ReimbursementReport, TaxLineItem,
+};
+
+pub struct ReportService;
+
+impl ReportService {
+    /// Compare expenses for a month with the previous month and the same month
+    /// last year. Months are calendar months in the user's timezone, and
+    /// categories are matched by name across budgets. Reads the monthly
+    /// summary tables rather than scanning transactions.
+    pub async fn compare_months(
+        pool: &PgPool,
+        user_id: Uuid,
+        month: i16,
+        year: i16,
+    ) -> Result<MonthComparisonResponse, AppError> {
+        SummaryService::refresh_dirty(pool, Some(user_id)).await?;
+
+        let period = MonthRef::new(month, year);
+        let previous = period.previous();
+        let last_year = period.last_year();
+
+        let rows = sqlx::query_as::<_, CompareRow>(
+            r#"
+            WITH periods (period, year, month) AS (
+                VALUES ('current', $2::SMALLINT, $3::SMALLINT),
+                       ('previous', $4::SMALLINT, $5::SMALLINT),
+                       ('last_year', $6::SMALLINT, $7::SMALLINT)
+            )
+            SELECT
+                p.period,
+                MIN(c.name) AS category_name,
+                SUM(s.total) AS spent
+            FROM periods p
+            INNER JOIN monthly_category_totals s
+                ON s.owner_id = $1
+                AND s.year = p.year
+                AND s.month = p.month
+                AND s.transaction_type = 'expense'
+            INNER JOIN categories c ON s.category_id = c.id
+            GROUP BY p.period, LOWER(c.name)
+            "#,
+        )
+        .bind(user_id)
+        .bind(period.year)
+        .bind(period.month)
+        .bind(previous.year)
+        .bind(previous.month)
+        .bind(last_year.year)
+        .bind(last_year.month)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(MonthComparisonResponse::from_rows(period, rows))
+    }
+
+    /// People whose household-visible accounts the user's household report
+    /// covers: the user plus everyone they share a budget with, either as
+    /// the owner of a budget the user is a member of or as a member of one
+    /// of the user's budgets.
+    pub async fn household_member_ids(pool: &PgPool, user_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT $1::UUID
+            UNION
+            SELECT b.owner_id
+            FROM budget_members m
+            INNER JOIN budgets b ON b.id = m.budget_id
+            WHERE m.user_id = $1
+            UNION
+            SELECT m.user_id
+            FROM budget_members m
+            INNER JOIN budgets b ON b.id = m.budget_id
+            WHERE b.owner_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Spending, income and net worth across the household's visible
+    /// accounts, per member and currency. The month is the calendar month in
+    /// the caller's timezone; transfers between accounts are left out.
+    pub async fn household(
+        pool: &PgPool,
+        user_id: Uuid,
+        month: i16,
+        year: i16,
+    ) -> Result<HouseholdReport, AppError> {
+        let members = Self::household_member_ids(pool, user_id).await?;
+
+        let rows = sqlx::query_as::<_, HouseholdRow>(
+            r#"
+            WITH period AS (
+                SELECT
+                    make_timestamp($3, $4 + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone AS start_at,
+                    (make_timestamp($3, $4 + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone AS end_at
+                FROM users u
+                WHERE u.id = $2
+            )
+            SELECT
+                a.owner_id AS user_id,
+                COALESCE(NULLIF(TRIM(u.full_name), ''), u.email) AS member_name,
+                a.currency,
+                COUNT(*) AS accounts_included,
+                COALESCE(SUM(a.balance), 0) AS net_worth,
+                COALESCE(SUM(f.spending), 0) AS spending,
+                COALESCE(SUM(f.income), 0) AS income
+            FROM accounts a
+            INNER JOIN users u ON u.id = a.owner_id
+            CROSS JOIN period p
+            LEFT JOIN LATERAL (
+                SELECT
+                    SUM(t.reporting_amount) FILTER (WHERE t.reporting_type = 'expense') AS spending,
+                    SUM(t.reporting_amount) FILTER (WHERE t.reporting_type = 'income') AS income
+                FROM transactions t
+                WHERE t.account_id = a.id
+                  AND t.deleted_at IS NULL AND t.status = 'posted'
+                  AND t.transaction_date >= p.start_at
+                  AND t.transaction_date < p.end_at
+            ) f ON TRUE
+            WHERE a.owner_id = ANY($1) AND a.household_visible
+            GROUP BY a.owner_id, u.full_name, u.email, a.currency
+            "#,
+        )
+        .bind(&members)
+        .bind(user_id)
+        .bind(year as i32)
+        .bind(month as i32)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(HouseholdReport::from_rows(
+            MonthRef::new(month, year),
+            user_id,
+            members.len(),
+            rows,
+        ))
+    }
+
+    /// Reimbursable expenses still owed, totalled per payer
+    pub async fn reimbursements(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<ReimbursementReport, AppError> {
+        let items = TransactionService::outstanding_reimbursements(pool, user_id, None).await?;
+
+        Ok(ReimbursementReport::from_items(items))
+    }
+
+    /// Deductible transactions in a calendar year (in the user's timezone),
+    /// with descriptions decrypted. A transaction's own tax category wins over
+    /// its category's; refunds of deductible expenses come back negative.
+    pub async fn tax_line_items(
+        pool: &PgPool,
+        user_id: Uuid,
+        year: i16,
+    ) -> Result<Vec<TaxLineItem>, AppError> {
+        let items = sqlx::query_as::<_, TaxLineItem>(
+            r#"
+            SELECT
+                COALESCE(t.tax_category, c.tax_category) AS tax_category,
+                c.name AS category_name,
+                (t.transaction_date AT TIME ZONE u.timezone)::DATE AS transaction_date,
+                t.description,
+                t.reporting_amount AS amount
+            FROM transactions t
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id AND b.owner_id = $1
+            INNER JOIN users u ON u.id = b.owner_id
+            WHERE t.reporting_type = 'expense'
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND COALESCE(t.tax_category, c.tax_category) IS NOT NULL
+              AND t.transaction_date >= make_timestamp($2, 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+              AND t.transaction_date < make_timestamp($2 + 1, 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+            ORDER BY t.transaction_date, t.created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(year as i32)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        items
+            .into_iter()
+            .map(|item| {
+                Ok(TaxLineItem {
+                    description: decrypt_optional(item.description)?,
+                    ..item
+                })
+            })
+            .collect()
+    }
+}
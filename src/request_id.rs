@@ -0,0 +1,135 @@
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::error::InternalError;
+use actix_web::http::header::{HeaderMap, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage};
+use tracing::Span;
+use tracing_actix_web::{DefaultRootSpanBuilder, RootSpanBuilder};
+use uuid::Uuid;
+
+/// Header a request ID is read from and echoed back in
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest incoming request ID kept; longer ones are replaced
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    /// ID of the request being handled, for code without access to it
+    /// (error responses are rendered from `AppError` alone)
+    static CURRENT: RequestId;
+}
+
+/// Identifies one request across the response, its error body and the logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    fn generate() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+
+    /// The caller's `X-Request-Id`, when it's short and plain enough to log
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let id = headers.get(REQUEST_ID_HEADER)?.to_str().ok()?.trim();
+        let plain = id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+        (!id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && plain).then(|| Self(id.to_string()))
+    }
+
+    /// ID of the request currently being handled, if any
+    pub fn current() -> Option<String> {
+        CURRENT.try_with(|id| id.0.clone()).ok()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Middleware assigning each request an ID: the caller's `X-Request-Id` if
+/// usable, a new UUID otherwise. The ID is stored in the request extensions
+/// for the tracing span, available through `RequestId::current()` while the
+/// request is handled, and returned in the `X-Request-Id` response header.
+pub async fn assign(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let request_id = RequestId::from_headers(req.headers()).unwrap_or_else(RequestId::generate);
+    req.extensions_mut().insert(request_id.clone());
+
+    CURRENT
+        .scope(request_id.clone(), async move {
+            let header = HeaderValue::from_str(request_id.as_str()).ok();
+            match next.call(req).await {
+                Ok(res) => {
+                    let mut res = res.map_into_boxed_body();
+                    if let Some(value) = header {
+                        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+                    }
+                    Ok(res)
+                }
+                // Errors from inner middleware are rendered here, so their
+                // body carries the ID too
+                Err(err) => {
+                    let mut response = err.error_response();
+                    if let Some(value) = header {
+                        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                    }
+                    Err(InternalError::from_response(err, response).into())
+                }
+            }
+        })
+        .await
+}
+
+/// Root span for `TracingLogger` recording the ID assigned by `assign`, so
+/// every log line of a request can be found by the ID its caller was given
+pub struct RequestIdRootSpan;
+
+impl RootSpanBuilder for RequestIdRootSpan {
+    fn on_request_start(request: &ServiceRequest) -> Span {
+        let request_id = request
+            .extensions()
+            .get::<RequestId>()
+            .map(|id| id.0.clone())
+            .unwrap_or_default();
+        tracing::info_span!(
+            "HTTP request",
+            http.method = %request.method(),
+            http.target = %request.uri(),
+            http.status_code = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            exception.message = tracing::field::Empty,
+            exception.details = tracing::field::Empty,
+            request_id = %request_id,
+        )
+    }
+
+    fn on_request_end<B: MessageBody>(span: Span, outcome: &Result<ServiceResponse<B>, Error>) {
+        DefaultRootSpanBuilder::on_request_end(span, outcome);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_incoming_request_id_is_kept_only_when_plain() {
+        assert_eq!(
+            RequestId::from_headers(&headers("req-42.a_b:1")),
+            Some(RequestId("req-42.a_b:1".to_string()))
+        );
+        assert_eq!(RequestId::from_headers(&headers("two words")), None);
+        assert_eq!(RequestId::from_headers(&headers(&"a".repeat(129))), None);
+        assert_eq!(RequestId::from_headers(&HeaderMap::new()), None);
+    }
+}
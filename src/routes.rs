@@ -0,0 +1,49 @@
+use actix_web::web;
+
+use crate::{
+    account, audit, auth, bank_sync, bill, budget, category, currency, db, debug, errors, event,
+    funding, health, holding, import, jobs, notification, payee, preferences, query, receipt,
+    report, search, settings, tag, template, transaction, webhook, widget,
+};
+
+/// Register every API route, shared by the server and the integration test app so
+/// the two cannot drift apart. Order matters: fixed paths go ahead of the `{id}`
+/// routes that would otherwise capture them. The rate-limited credential routes
+/// are left to the caller, which mounts them last behind its own limiter.
+pub fn configure_api(cfg: &mut web::ServiceConfig) {
+    cfg
+        // Health and metrics endpoints (no rate limiting)
+        .configure(health::configure_routes)
+        .configure(errors::configure_routes)
+        .configure(db::configure_routes)
+        // Auth endpoints without rate limiting
+        .configure(auth::configure_routes)
+        .configure(budget::configure_routes)
+        .configure(account::configure_routes)
+        .configure(holding::configure_routes)
+        .configure(category::configure_routes)
+        .configure(transaction::configure_routes)
+        .configure(template::configure_routes)
+        .configure(import::configure_routes)
+        .configure(payee::configure_routes)
+        .configure(funding::configure_routes)
+        .configure(tag::configure_routes)
+        .configure(notification::configure_routes)
+        .configure(receipt::configure_routes)
+        .configure(bank_sync::configure_routes)
+        .configure(bill::configure_routes)
+        .configure(widget::configure_routes)
+        .configure(settings::configure_routes)
+        .configure(preferences::configure_routes)
+        .configure(audit::configure_routes)
+        .configure(query::configure_routes)
+        .configure(report::configure_routes)
+        .configure(search::configure_routes)
+        .configure(webhook::configure_routes)
+        .configure(event::configure_routes)
+        .configure(currency::configure_routes)
+        // Admin endpoints
+        .configure(jobs::configure_routes)
+        // Debug endpoints (404 unless enabled)
+        .configure(debug::configure_routes);
+}
@@ -0,0 +1,76 @@
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{SearchQuery, SearchResponse, SearchResultType};
+use super::service::SearchService;
+
+/// GET /search - Search transactions, categories, accounts, payees and budgets
+///
+/// Groups are only searched when the token holds the matching read scope.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "Search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matching results grouped by type, best group first", body = SearchResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/search")]
+pub async fn search(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, AppError> {
+    query.validate()?;
+
+    let pool = pool.get_ref();
+    let q = query.q.trim();
+    let limit = query.limit as usize;
+    let mut groups = Vec::new();
+
+    if auth.scopes.contains(&Scope::TransactionsRead) {
+        let rows = SearchService::recent_transactions(pool, auth.user_id).await?;
+        groups.push((
+            SearchResultType::Transaction,
+            SearchService::match_transactions(&rows, q),
+        ));
+        groups.push((
+            SearchResultType::Payee,
+            SearchService::match_payees(&rows, q, limit),
+        ));
+    }
+    if auth.scopes.contains(&Scope::CategoriesRead) {
+        groups.push((
+            SearchResultType::Category,
+            SearchService::search_categories(pool, auth.user_id, q).await?,
+        ));
+    }
+    if auth.scopes.contains(&Scope::AccountsRead) {
+        groups.push((
+            SearchResultType::Account,
+            SearchService::search_accounts(pool, auth.user_id, q).await?,
+        ));
+    }
+    if auth.scopes.contains(&Scope::BudgetsRead) {
+        groups.push((
+            SearchResultType::Budget,
+            SearchService::search_budgets(pool, auth.user_id, q).await?,
+        ));
+    }
+
+    Ok(HttpResponse::Ok().json(SearchResponse::new(q, groups, limit)))
+}
+
+/// Register the search route
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(search);
+}
@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+
+fn default_search_limit() -> i64 {
+    5
+}
+
+/// Query parameters for global search
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchQuery {
+    /// Search text
+    #[validate(length(min = 1, max = 100, message = "q must be 1-100 characters"))]
+    #[param(example = "groc")]
+    pub q: String,
+
+    /// Maximum results per group (1-20)
+    #[validate(range(min = 1, max = 20))]
+    #[serde(default = "default_search_limit")]
+    #[param(example = 5)]
+    pub limit: i64,
+}
+
+/// Kind of entity a search result points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchResultType {
+    Transaction,
+    Category,
+    Account,
+    Payee,
+    Budget,
+}
+
+/// A single search hit
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    /// Entity ID (absent for payees, which are not stored separately yet)
+    pub id: Option<Uuid>,
+    /// Primary display text
+    #[schema(example = "Groceries")]
+    pub title: String,
+    /// Secondary display text
+    #[schema(example = "October 2026")]
+    pub subtitle: Option<String>,
+    /// Relevance between 0 and 1
+    #[schema(example = 0.8)]
+    pub score: f64,
+}
+
+/// Results of one entity type, best match first
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchGroup {
+    #[serde(rename = "type")]
+    pub result_type: SearchResultType,
+    pub results: Vec<SearchResult>,
+}
+
+/// Response for GET /search
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    #[schema(example = "groc")]
+    pub query: String,
+    /// Non-empty groups, ordered by their best match
+    pub groups: Vec<SearchGroup>,
+}
+
+impl SearchResponse {
+    /// Sort and trim each group, drop empty ones, and order groups by best score
+    pub fn new(
+        query: &str,
+        groups: Vec<(SearchResultType, Vec<SearchResult>)>,
+        limit: usize,
+    ) -> Self {
+        let mut groups: Vec<SearchGroup> = groups
+            .into_iter()
+            .filter(|(_, results)| !results.is_empty())
+            .map(|(result_type, mut results)| {
+                results.sort_by(|a, b| b.score.total_cmp(&a.score));
+                results.truncate(limit);
+                SearchGroup {
+                    result_type,
+                    results,
+                }
+            })
+            .collect();
+        groups.sort_by(|a, b| b.results[0].score.total_cmp(&a.results[0].score));
+
+        Self {
+            query: query.to_string(),
+            groups,
+        }
+    }
+}
+
+/// How well `text` matches `query` (both compared case-insensitively):
+/// exact 1.0, prefix 0.8, word prefix 0.6, substring 0.4
+pub fn relevance(text: &str, query: &str) -> Option<f64> {
+    let text = text.trim().to_lowercase();
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    if text == query {
+        Some(1.0)
+    } else if text.starts_with(&query) {
+        Some(0.8)
+    } else if text
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|w| w.starts_with(&query))
+    {
+        Some(0.6)
+    } else if text.contains(&query) {
+        Some(0.4)
+    } else {
+        None
+    }
+}
+
+/// A recent transaction considered by search
+#[derive(Debug, FromRow)]
+pub struct SearchTransactionRow {
+    pub id: Uuid,
+    pub description: Option<String>,
+    pub amount: Decimal,
+    pub category_name: String,
+    pub transaction_date: DateTime<Utc>,
+}
+
+impl SearchTransactionRow {
+    /// Decrypt the description after loading from the database
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        Ok(self)
+    }
+}
+
+/// Category candidate (latest budget per name)
+#[derive(Debug, FromRow)]
+pub struct SearchCategoryRow {
+    pub id: Uuid,
+    pub name: String,
+    pub month: i16,
+    pub year: i16,
+}
+
+/// Budget candidate
+#[derive(Debug, FromRow)]
+pub struct SearchBudgetRow {
+    pub id: Uuid,
+    pub month: i16,
+    pub year: i16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relevance_prefers_closer_matches() {
+        assert_eq!(relevance("Groceries", "groceries"), Some(1.0));
+        assert_eq!(relevance("Groceries", "gro"), Some(0.8));
+        assert_eq!(relevance("Whole Foods", "foo"), Some(0.6));
+        assert_eq!(relevance("Seafood", "food"), Some(0.4));
+        assert_eq!(relevance("Fuel", "food"), None);
+    }
+
+    #[test]
+    fn test_search_response_orders_groups_by_best_match() {
+        let result = |score: f64| SearchResult {
+            id: None,
+            title: String::new(),
+            subtitle: None,
+            score,
+        };
+        let response = SearchResponse::new(
+            "q",
+            vec![
+                (
+                    SearchResultType::Transaction,
+                    vec![result(0.4), result(0.6), result(0.5)],
+                ),
+                (SearchResultType::Account, vec![]),
+                (SearchResultType::Category, vec![result(1.0)]),
+            ],
+            2,
+        );
+
+        assert_eq!(response.groups.len(), 2);
+        assert_eq!(response.groups[0].result_type, SearchResultType::Category);
+        assert_eq!(response.groups[1].results.len(), 2);
+        assert_eq!(response.groups[1].results[0].score, 0.6);
+    }
+}
@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::account::service::AccountService;
+use crate::errors::AppError;
+use crate::payee::models::PayeeSuggestion;
+use crate::query::parser::MONTHS;
+use crate::transaction::models::DescriptionUsageRow;
+use crate::transaction::service::DESCRIPTION_SCAN_WINDOW;
+
+use super::models::{
+    relevance, SearchBudgetRow, SearchCategoryRow, SearchResult, SearchTransactionRow,
+};
+
+/// Display name for a 0-based month, e.g. "March"
+fn month_name(month: i16) -> String {
+    let name = MONTHS[month.clamp(0, 11) as usize];
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub struct SearchService;
+
+impl SearchService {
+    /// Recent transactions with decrypted descriptions. Descriptions may be
+    /// encrypted, so matching happens in Rust over a bounded window.
+    pub async fn recent_transactions(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<Vec<SearchTransactionRow>, AppError> {
+        sqlx::query_as::<_, SearchTransactionRow>(
+            r#"
+            SELECT t.id, t.description, t.amount, c.name AS category_name, t.transaction_date
+            FROM transactions t
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND t.deleted_at IS NULL AND t.status = 'posted'
+            ORDER BY t.transaction_date DESC, t.created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(DESCRIPTION_SCAN_WINDOW)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(SearchTransactionRow::decrypted)
+        .collect()
+    }
+
+    /// Transactions whose description matches, or whose amount equals a numeric query
+    pub fn match_transactions(rows: &[SearchTransactionRow], q: &str) -> Vec<SearchResult> {
+        let amount = Decimal::from_str(q.trim()).ok();
+
+        rows.iter()
+            .filter_map(|row| {
+                let score = if amount == Some(row.amount) {
+                    Some(1.0)
+                } else {
+                    row.description.as_deref().and_then(|d| relevance(d, q))
+                }?;
+                Some(SearchResult {
+                    id: Some(row.id),
+                    title: row
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| row.category_name.clone()),
+                    subtitle: Some(format!(
+                        "{} · {} · {}",
+                        row.category_name,
+                        row.amount,
+                        row.transaction_date.format("%Y-%m-%d")
+                    )),
+                    score,
+                })
+            })
+            .collect()
+    }
+
+    /// Payees derived from matching transaction descriptions
+    pub fn match_payees(rows: &[SearchTransactionRow], q: &str, limit: usize) -> Vec<SearchResult> {
+        let usage = rows
+            .iter()
+            .map(|row| DescriptionUsageRow {
+                description: row.description.clone(),
+                amount: row.amount,
+                category_name: row.category_name.clone(),
+                transaction_date: row.transaction_date,
+            })
+            .collect();
+
+        PayeeSuggestion::rank(usage, q, limit)
+            .into_iter()
+            .filter_map(|payee| {
+                let score = relevance(&payee.name, q)?;
+                Some(SearchResult {
+                    id: None,
+                    subtitle: Some(format!(
+                        "{} · {} transaction{}",
+                        payee.category_name,
+                        payee.transaction_count,
+                        if payee.transaction_count == 1 {
+                            ""
+                        } else {
+                            "s"
+                        }
+                    )),
+                    title: payee.name,
+                    score,
+                })
+            })
+            .collect()
+    }
+
+    /// Categories matching by name, one per name from the most recent budget
+    pub async fn search_categories(
+        pool: &PgPool,
+        user_id: Uuid,
+        q: &str,
+    ) -> Result<Vec<SearchResult>, AppError> {
+        let pattern = format!(
+            "%{}%",
+            q.trim()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+
+        let rows = sqlx::query_as::<_, SearchCategoryRow>(
+            r#"
+            SELECT DISTINCT ON (LOWER(c.name)) c.id, c.name, b.month, b.year
+            FROM categories c
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND c.name ILIKE $2
+            ORDER BY LOWER(c.name), b.year DESC, b.month DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(&pattern)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let score = relevance(&row.name, q)?;
+                Some(SearchResult {
+                    id: Some(row.id),
+                    title: row.name,
+                    subtitle: Some(format!("{} {}", month_name(row.month), row.year)),
+                    score,
+                })
+            })
+            .collect())
+    }
+
+    /// Accounts matching by name
+    pub async fn search_accounts(
+        pool: &PgPool,
+        user_id: Uuid,
+        q: &str,
+    ) -> Result<Vec<SearchResult>, AppError> {
+        let accounts = AccountService::list_accounts(pool, user_id).await?;
+
+        Ok(accounts
+            .into_iter()
+            .filter_map(|account| {
+                let score = relevance(&account.name, q)?;
+                Some(SearchResult {
+                    id: Some(account.id),
+                    subtitle: Some(format!(
+                        "{} · {} {}",
+                        account.account_type, account.balance, account.currency
+                    )),
+                    title: account.name,
+                    score,
+                })
+            })
+            .collect())
+    }
+
+    /// Budgets matching by their "Month Year" title
+    pub async fn search_budgets(
+        pool: &PgPool,
+        user_id: Uuid,
+        q: &str,
+    ) -> Result<Vec<SearchResult>, AppError> {
+        let rows = sqlx::query_as::<_, SearchBudgetRow>(
+            "SELECT id, month, year FROM budgets WHERE owner_id = $1 ORDER BY year DESC, month DESC",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let title = format!("{} {}", month_name(row.month), row.year);
+                let score = relevance(&title, q)?;
+                Some(SearchResult {
+                    id: Some(row.id),
+                    title,
+                    subtitle: None,
+                    score,
+                })
+            })
+            .collect())
+    }
+}
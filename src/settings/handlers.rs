@@ -0,0 +1,71 @@
+use actix_web::{get, put, web, HttpRequest, HttpResponse};
+use secrecy::Secret;
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::auth::decode_first_party_token;
+use crate::errors::{AppError, ErrorResponse};
+
+use super::models::{DashboardLayout, DashboardSettingsResponse};
+use super::service::SettingsService;
+
+/// GET /settings/dashboard - Get the saved dashboard layout
+#[utoipa::path(
+    get,
+    path = "/settings/dashboard",
+    tag = "Settings",
+    responses(
+        (status = 200, description = "Saved layout, or an empty default", body = DashboardSettingsResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/settings/dashboard")]
+pub async fn get_dashboard_settings(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+) -> Result<HttpResponse, AppError> {
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let settings = SettingsService::get(pool.get_ref(), claims.sub).await?;
+
+    Ok(HttpResponse::Ok().json(DashboardSettingsResponse::from_settings(settings)))
+}
+
+/// PUT /settings/dashboard - Replace the dashboard layout
+#[utoipa::path(
+    put,
+    path = "/settings/dashboard",
+    tag = "Settings",
+    request_body = DashboardLayout,
+    responses(
+        (status = 200, description = "Layout saved", body = DashboardSettingsResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Scoped integration tokens are not accepted", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/settings/dashboard")]
+pub async fn update_dashboard_settings(
+    req: HttpRequest,
+    pool: web::Data<PgPool>,
+    jwt_secret: web::Data<Secret<String>>,
+    body: web::Json<DashboardLayout>,
+) -> Result<HttpResponse, AppError> {
+    body.validate()?;
+
+    let claims = decode_first_party_token(&req, jwt_secret.get_ref())?;
+
+    let settings = SettingsService::save_dashboard(pool.get_ref(), claims.sub, &body).await?;
+
+    Ok(HttpResponse::Ok().json(DashboardSettingsResponse::from_settings(Some(settings))))
+}
+
+/// Register the dashboard settings routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_dashboard_settings)
+        .service(update_dashboard_settings);
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+/// Largest serialized chart preferences object accepted, in bytes
+const MAX_CHART_PREFERENCES_BYTES: usize = 16 * 1024;
+
+/// Widget and card identifiers must be short, non-empty strings
+fn validate_identifiers(ids: &[String]) -> Result<(), ValidationError> {
+    if ids.iter().any(|id| id.is_empty() || id.len() > 64) {
+        return Err(ValidationError::new("invalid_identifier"));
+    }
+    Ok(())
+}
+
+fn validate_chart_preferences(prefs: &Map<String, Value>) -> Result<(), ValidationError> {
+    let size = serde_json::to_vec(prefs)
+        .map(|v| v.len())
+        .unwrap_or(usize::MAX);
+    if size > MAX_CHART_PREFERENCES_BYTES {
+        return Err(ValidationError::new("too_large"));
+    }
+    Ok(())
+}
+
+/// Database entity for user settings
+#[derive(Debug, FromRow)]
+pub struct UserSettings {
+    pub dashboard_layout: sqlx::types::Json<DashboardLayout>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Dashboard configuration shared by web and mobile clients
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardLayout {
+    /// Widget identifiers in display order
+    #[validate(length(max = 50, message = "At most 50 widgets are allowed"))]
+    #[validate(custom(
+        function = "validate_identifiers",
+        message = "Widget identifiers must be 1-64 characters"
+    ))]
+    #[serde(default)]
+    #[schema(example = json!(["netWorth", "spending", "recentTransactions"]))]
+    pub widget_order: Vec<String>,
+
+    /// Summary cards shown on the dashboard
+    #[validate(length(max = 50, message = "At most 50 cards are allowed"))]
+    #[validate(custom(
+        function = "validate_identifiers",
+        message = "Card identifiers must be 1-64 characters"
+    ))]
+    #[serde(default)]
+    #[schema(example = json!(["income", "expenses"]))]
+    pub visible_cards: Vec<String>,
+
+    /// Free-form chart options keyed by chart (stored as given, max 16 KB)
+    #[validate(custom(
+        function = "validate_chart_preferences",
+        message = "Chart preferences must be at most 16 KB"
+    ))]
+    #[serde(default)]
+    #[schema(value_type = Object, example = json!({"spending": {"type": "bar", "months": 6}}))]
+    pub chart_preferences: Map<String, Value>,
+}
+
+/// Response for GET/PUT /settings/dashboard
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DashboardSettingsResponse {
+    pub layout: DashboardLayout,
+    /// When the layout was last saved; absent until the first save
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl DashboardSettingsResponse {
+    pub fn from_settings(settings: Option<UserSettings>) -> Self {
+        match settings {
+            Some(settings) => Self {
+                layout: settings.dashboard_layout.0,
+                updated_at: Some(settings.updated_at),
+            },
+            None => Self {
+                layout: DashboardLayout::default(),
+                updated_at: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dashboard_layout_validation() {
+        let layout: DashboardLayout =
+            serde_json::from_value(serde_json::json!({ "widgetOrder": ["netWorth"] })).unwrap();
+        assert!(layout.validate().is_ok());
+        assert!(layout.visible_cards.is_empty());
+
+        let blank = DashboardLayout {
+            widget_order: vec![String::new()],
+            ..DashboardLayout::default()
+        };
+        assert!(blank.validate().is_err());
+
+        let mut chart_preferences = Map::new();
+        chart_preferences.insert("huge".to_string(), Value::String("x".repeat(20_000)));
+        let oversized = DashboardLayout {
+            chart_preferences,
+            ..DashboardLayout::default()
+        };
+        assert!(oversized.validate().is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+use super::models::{DashboardLayout, UserSettings};
+
+/// Service layer for per-user client settings.
+pub struct SettingsService;
+
+impl SettingsService {
+    /// Fetch the user's saved settings, if any have been stored
+    pub async fn get(pool: &PgPool, user_id: Uuid) -> Result<Option<UserSettings>, AppError> {
+        sqlx::query_as::<_, UserSettings>(
+            "SELECT dashboard_layout, updated_at FROM user_settings WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Replace the user's dashboard layout
+    pub async fn save_dashboard(
+        pool: &PgPool,
+        user_id: Uuid,
+        layout: &DashboardLayout,
+    ) -> Result<UserSettings, AppError> {
+        sqlx::query_as::<_, UserSettings>(
+            r#"
+            INSERT INTO user_settings (user_id, dashboard_layout)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET dashboard_layout = EXCLUDED.dashboard_layout, updated_at = NOW()
+            RETURNING dashboard_layout, updated_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(sqlx::types::Json(layout))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+}
@@ -0,0 +1,89 @@
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::time::Interval;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+use tracing::{info, warn};
+
+/// Process-wide shutdown state, created on first use
+static SHUTDOWN: OnceLock<Shutdown> = OnceLock::new();
+
+struct Shutdown {
+    token: CancellationToken,
+    tasks: TaskTracker,
+}
+
+fn state() -> &'static Shutdown {
+    SHUTDOWN.get_or_init(|| Shutdown {
+        token: CancellationToken::new(),
+        tasks: TaskTracker::new(),
+    })
+}
+
+/// Spawn background work that shutdown waits for, instead of dropping it
+/// mid-transaction. Loops should wait through `tick` so they stop between runs.
+pub fn spawn<F>(task: F)
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    state().tasks.spawn(task);
+}
+
+/// Wait for the next tick of a periodic task; false once shutdown has begun,
+/// so the task exits after finishing its current run instead of starting another
+pub async fn tick(ticker: &mut Interval) -> bool {
+    let token = &state().token;
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => false,
+        _ = ticker.tick() => true,
+    }
+}
+
+/// Resolves once shutdown has begun
+pub async fn cancelled() {
+    state().token.cancelled().await
+}
+
+/// Resolves on SIGTERM (how orchestrators stop a container) or SIGINT (Ctrl+C)
+pub async fn signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate = signal(SignalKind::terminate()).expect("Failed to trap SIGTERM");
+        tokio::select! {
+            _ = terminate.recv() => info!("Received SIGTERM"),
+            _ = tokio::signal::ctrl_c() => info!("Received SIGINT"),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        info!("Received Ctrl+C");
+    }
+}
+
+/// Tell background tasks to stop: periodic jobs exit after their current run
+/// and live event streams end. Tasks spawned afterwards are still awaited.
+pub fn begin() {
+    let state = state();
+    state.token.cancel();
+    state.tasks.close();
+}
+
+/// Wait up to `timeout` for background tasks to finish. Returns false when
+/// some were still running, and are abandoned as the process exits.
+pub async fn wait(timeout: Duration) -> bool {
+    let tasks = &state().tasks;
+    match tokio::time::timeout(timeout, tasks.wait()).await {
+        Ok(()) => true,
+        Err(_) => {
+            warn!(
+                remaining = tasks.len(),
+                "Background tasks still running at shutdown timeout"
+            );
+            false
+        }
+    }
+}
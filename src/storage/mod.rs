@@ -0,0 +1,143 @@
+use std::path::PathBuf;
+
+use crate::errors::AppError;
+
+/// Google Cloud Storage JSON API endpoints
+const GCS_UPLOAD_URL: &str = "https://storage.googleapis.com/upload/storage/v1/b";
+const GCS_API_URL: &str = "https://storage.googleapis.com/storage/v1/b";
+const GCS_PUBLIC_URL: &str = "https://storage.googleapis.com";
+/// Metadata server endpoint for the runtime service account's access token
+const GCP_TOKEN_URL: &str =
+    "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Object storage for user-uploaded files.
+///
+/// `Local` writes under a directory served by the app at `/media` (development);
+/// `Gcs` writes to a Google Cloud Storage bucket using the service account
+/// credentials available on Cloud Run.
+#[derive(Debug, Clone)]
+pub enum ObjectStorage {
+    Local { root: PathBuf, public_url: String },
+    Gcs { bucket: String },
+}
+
+#[derive(serde::Deserialize)]
+struct GcpAccessToken {
+    access_token: String,
+}
+
+impl ObjectStorage {
+    /// Directory to serve at `/media` when using local storage
+    pub fn local_root(&self) -> Option<&PathBuf> {
+        match self {
+            ObjectStorage::Local { root, .. } => Some(root),
+            ObjectStorage::Gcs { .. } => None,
+        }
+    }
+
+    /// Public URL for a stored object
+    pub fn url(&self, key: &str) -> String {
+        match self {
+            ObjectStorage::Local { public_url, .. } => format!("{}/{}", public_url, key),
+            ObjectStorage::Gcs { bucket } => format!("{}/{}/{}", GCS_PUBLIC_URL, bucket, key),
+        }
+    }
+
+    /// Store an object under `key`, replacing any existing object, and return its public URL
+    pub async fn put(
+        &self,
+        key: &str,
+        bytes: Vec<u8>,
+        content_type: &str,
+    ) -> Result<String, AppError> {
+        match self {
+            ObjectStorage::Local { root, .. } => {
+                let path = root.join(key);
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent)
+                        .await
+                        .map_err(|e| AppError::InternalError(format!("Storage error: {e}")))?;
+                }
+                tokio::fs::write(&path, bytes)
+                    .await
+                    .map_err(|e| AppError::InternalError(format!("Storage error: {e}")))?;
+            }
+            ObjectStorage::Gcs { bucket } => {
+                let token = gcp_access_token().await?;
+                let response = reqwest::Client::new()
+                    .post(format!("{}/{}/o", GCS_UPLOAD_URL, bucket))
+                    .query(&[("uploadType", "media"), ("name", key)])
+                    .bearer_auth(token)
+                    .header(reqwest::header::CONTENT_TYPE, content_type)
+                    .body(bytes)
+                    .send()
+                    .await
+                    .map_err(|e| AppError::InternalError(format!("Storage error: {e}")))?;
+
+                if !response.status().is_success() {
+                    return Err(AppError::InternalError(format!(
+                        "Storage upload failed with status {}",
+                        response.status()
+                    )));
+                }
+            }
+        }
+
+        Ok(self.url(key))
+    }
+
+    /// Delete an object; missing objects are not an error
+    pub async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match self {
+            ObjectStorage::Local { root, .. } => match tokio::fs::remove_file(root.join(key)).await
+            {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(AppError::InternalError(format!("Storage error: {e}")))
+                }
+                _ => Ok(()),
+            },
+            ObjectStorage::Gcs { bucket } => {
+                let token = gcp_access_token().await?;
+                let mut url = reqwest::Url::parse(GCS_API_URL)
+                    .map_err(|e| AppError::InternalError(format!("Storage error: {e}")))?;
+                url.path_segments_mut()
+                    .map_err(|_| {
+                        AppError::InternalError("Storage error: bad base URL".to_string())
+                    })?
+                    .extend([bucket.as_str(), "o", key]);
+
+                let response = reqwest::Client::new()
+                    .delete(url)
+                    .bearer_auth(token)
+                    .send()
+                    .await
+                    .map_err(|e| AppError::InternalError(format!("Storage error: {e}")))?;
+
+                let status = response.status();
+                if status.is_success() || status == reqwest::StatusCode::NOT_FOUND {
+                    Ok(())
+                } else {
+                    Err(AppError::InternalError(format!(
+                        "Storage delete failed with status {}",
+                        status
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Fetch an OAuth token for the runtime service account from the metadata server
+async fn gcp_access_token() -> Result<String, AppError> {
+    let token = reqwest::Client::new()
+        .get(GCP_TOKEN_URL)
+        .header("Metadata-Flavor", "Google")
+        .send()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to get storage credentials: {e}")))?
+        .json::<GcpAccessToken>()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to get storage credentials: {e}")))?;
+
+    Ok(token.access_token)
+}
@@ -0,0 +1,152 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::errors::AppError;
+
+/// Dirty months recomputed per database transaction
+const REFRESH_BATCH_SIZE: i64 = 100;
+
+/// Maintains the monthly summary tables (`monthly_category_totals` and
+/// `monthly_account_totals`).
+///
+/// Triggers on `transactions` queue each affected (user, month) in
+/// `summary_dirty_months`; refreshing recomputes only the queued months.
+pub struct SummaryService;
+
+impl SummaryService {
+    /// Recompute queued months of every owner whose budgets the user can read:
+    /// their own and those of budgets shared with them.
+    pub async fn refresh_dirty_for_reader(pool: &PgPool, user_id: Uuid) -> Result<u64, AppError> {
+        let owners = sqlx::query_scalar::<_, Uuid>(
+            "SELECT DISTINCT owner_id FROM budgets WHERE budget_access(id, $1, FALSE)",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let mut refreshed = 0;
+        for owner_id in owners {
+            refreshed += Self::refresh_dirty(pool, Some(owner_id)).await?;
+        }
+        Ok(refreshed)
+    }
+
+    /// Recompute queued months, for one user or for everyone.
+    /// Returns the number of months refreshed.
+    ///
+    /// Report reads call this for the requesting user first, so totals always
+    /// include the user's own latest writes. Months being refreshed
+    /// concurrently elsewhere are skipped rather than waited on.
+    pub async fn refresh_dirty(pool: &PgPool, owner_id: Option<Uuid>) -> Result<u64, AppError> {
+        let mut refreshed = 0;
+        loop {
+            let mut tx = pool
+                .begin()
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            let months = sqlx::query_as::<_, (Uuid, i16, i16)>(
+                r#"
+                DELETE FROM summary_dirty_months
+                WHERE (owner_id, year, month) IN (
+                    SELECT owner_id, year, month
+                    FROM summary_dirty_months
+                    WHERE $1::UUID IS NULL OR owner_id = $1
+                    LIMIT $2
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING owner_id, year, month
+                "#,
+            )
+            .bind(owner_id)
+            .bind(REFRESH_BATCH_SIZE)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            for &(owner_id, year, month) in &months {
+                Self::recompute_month(&mut tx, owner_id, year, month).await?;
+            }
+
+            tx.commit()
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            refreshed += months.len() as u64;
+            if (months.len() as i64) < REFRESH_BATCH_SIZE {
+                return Ok(refreshed);
+            }
+        }
+    }
+
+    /// Replace one month's rows in both summary tables from `transactions`.
+    /// Refunds are netted against the type they reverse.
+    async fn recompute_month(
+        conn: &mut PgConnection,
+        owner_id: Uuid,
+        year: i16,
+        month: i16,
+    ) -> Result<(), AppError> {
+        for table in ["monthly_category_totals", "monthly_account_totals"] {
+            sqlx::query(&format!(
+                "DELETE FROM {table} WHERE owner_id = $1 AND year = $2 AND month = $3"
+            ))
+            .bind(owner_id)
+            .bind(year)
+            .bind(month)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO monthly_category_totals
+                (owner_id, year, month, category_id, transaction_type, total, transaction_count)
+            SELECT $1, $2, $3, t.category_id, t.reporting_type, SUM(t.reporting_amount), COUNT(*)
+            FROM users u
+            INNER JOIN budgets b ON b.owner_id = u.id
+            INNER JOIN categories c ON c.budget_id = b.id
+            INNER JOIN transactions t ON t.category_id = c.id
+            WHERE u.id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.transaction_date >= make_timestamp($2, $3 + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+              AND t.transaction_date < (make_timestamp($2, $3 + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+            GROUP BY t.category_id, t.reporting_type
+            "#,
+        )
+        .bind(owner_id)
+        .bind(i32::from(year))
+        .bind(i32::from(month))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO monthly_account_totals
+                (owner_id, year, month, account_id, category_id, transaction_type, total, transaction_count)
+            SELECT $1, $2, $3, t.account_id, t.category_id, t.reporting_type, SUM(t.reporting_amount + COALESCE(t.fee_amount, 0)), COUNT(*)
+            FROM users u
+            INNER JOIN budgets b ON b.owner_id = u.id
+            INNER JOIN categories c ON c.budget_id = b.id
+            INNER JOIN transactions t ON t.category_id = c.id
+            WHERE u.id = $1
+              AND t.account_id IS NOT NULL
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.transaction_date >= make_timestamp($2, $3 + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone
+              AND t.transaction_date < (make_timestamp($2, $3 + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+            GROUP BY t.account_id, t.category_id, t.reporting_type
+            "#,
+        )
+        .bind(owner_id)
+        .bind(i32::from(year))
+        .bind(i32::from(month))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+}
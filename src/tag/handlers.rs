@@ -0,0 +1,202 @@
+use actix_web::{delete, get, patch, post, put, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionIdPath;
+
+use super::models::{CreateTagDto, SetTransactionTagsDto, TagIdPath, TagResponse, UpdateTagDto};
+use super::service::TagService;
+
+/// GET /tags - List tags by name
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "List of tags", body = Vec<TagResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/tags")]
+pub async fn list_tags(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let tags = TagService::list(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(tags.into_iter().map(TagResponse::from).collect::<Vec<_>>()))
+}
+
+/// POST /tags - Create a tag
+#[utoipa::path(
+    post,
+    path = "/tags",
+    tag = "Tags",
+    request_body = CreateTagDto,
+    responses(
+        (status = 201, description = "Tag created", body = TagResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 409, description = "A tag with this name already exists", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/tags")]
+pub async fn create_tag(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateTagDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let tag = TagService::create(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(TagResponse::from(tag)))
+}
+
+/// PATCH /tags/{id} - Rename or recolor a tag
+#[utoipa::path(
+    patch,
+    path = "/tags/{id}",
+    tag = "Tags",
+    params(TagIdPath),
+    request_body = UpdateTagDto,
+    responses(
+        (status = 200, description = "Tag updated", body = TagResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse),
+        (status = 409, description = "A tag with this name already exists", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/tags/{id}")]
+pub async fn update_tag(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TagIdPath>,
+    body: web::Json<UpdateTagDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+    body.validate_fields()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let tag = TagService::update(pool.get_ref(), path.id, auth.user_id, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(TagResponse::from(tag)))
+}
+
+/// DELETE /tags/{id} - Delete a tag (transactions keep everything but the tag)
+#[utoipa::path(
+    delete,
+    path = "/tags/{id}",
+    tag = "Tags",
+    params(TagIdPath),
+    responses(
+        (status = 200, description = "Tag deleted", body = DeleteResponse),
+        (status = 404, description = "Tag not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/tags/{id}")]
+pub async fn delete_tag(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TagIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    TagService::delete(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Tag deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// GET /transactions/{id}/tags - Tags on a transaction
+#[utoipa::path(
+    get,
+    path = "/transactions/{id}/tags",
+    tag = "Tags",
+    params(TransactionIdPath),
+    responses(
+        (status = 200, description = "The transaction's tags", body = Vec<TagResponse>),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/{id}/tags")]
+pub async fn get_transaction_tags(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let tags = TagService::for_transaction(pool.get_ref(), auth.user_id, path.id).await?;
+
+    Ok(HttpResponse::Ok().json(tags.into_iter().map(TagResponse::from).collect::<Vec<_>>()))
+}
+
+/// PUT /transactions/{id}/tags - Replace the tags on a transaction
+#[utoipa::path(
+    put,
+    path = "/transactions/{id}/tags",
+    tag = "Tags",
+    params(TransactionIdPath),
+    request_body = SetTransactionTagsDto,
+    responses(
+        (status = 200, description = "The transaction's tags after the change", body = Vec<TagResponse>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Transaction or tag not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/transactions/{id}/tags")]
+pub async fn set_transaction_tags(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+    body: web::Json<SetTransactionTagsDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let tags =
+        TagService::set_for_transaction(pool.get_ref(), auth.user_id, path.id, &body.tag_ids)
+            .await?;
+
+    Ok(HttpResponse::Ok().json(tags.into_iter().map(TagResponse::from).collect::<Vec<_>>()))
+}
+
+/// Register the tag routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_tags)
+        .service(create_tag)
+        .service(update_tag)
+        .service(delete_tag)
+        .service(get_transaction_tags)
+        .service(set_transaction_tags);
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+/// Custom validator for hex color format (#RRGGBB)
+fn validate_color_hex(color: &str) -> Result<(), ValidationError> {
+    if color.len() != 7 {
+        return Err(ValidationError::new("invalid_length"));
+    }
+    if !color.starts_with('#') {
+        return Err(ValidationError::new("missing_hash"));
+    }
+    if !color[1..].chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ValidationError::new("invalid_hex_chars"));
+    }
+    Ok(())
+}
+
+/// Database entity for tags
+#[derive(Debug, Clone, FromRow)]
+pub struct Tag {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub name: String,
+    pub color_hex: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for creating a tag
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTagDto {
+    /// Tag name (1-30 characters, unique per user ignoring case)
+    #[validate(length(min = 1, max = 30, message = "Name must be 1-30 characters"))]
+    #[schema(example = "vacation")]
+    pub name: String,
+
+    /// Display color in hex format
+    #[validate(custom(
+        function = "validate_color_hex",
+        message = "Color must be in #RRGGBB format"
+    ))]
+    #[schema(example = "#03A9F4")]
+    pub color_hex: String,
+}
+
+/// Request body for updating a tag (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTagDto {
+    /// Tag name (1-30 characters)
+    #[validate(length(min = 1, max = 30, message = "Name must be 1-30 characters"))]
+    #[schema(example = "summer vacation")]
+    pub name: Option<String>,
+
+    /// Display color in hex format
+    #[schema(example = "#FF9800")]
+    pub color_hex: Option<String>,
+}
+
+impl UpdateTagDto {
+    /// Validate the color if provided
+    pub fn validate_fields(&self) -> Result<(), ValidationError> {
+        if let Some(color) = &self.color_hex {
+            validate_color_hex(color)?;
+        }
+        Ok(())
+    }
+}
+
+/// Request body for replacing a transaction's tags
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTransactionTagsDto {
+    /// The transaction's tags; an empty list removes them all
+    #[validate(length(max = 20, message = "A transaction can have at most 20 tags"))]
+    pub tag_ids: Vec<Uuid>,
+}
+
+/// Tag response DTO
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TagResponse {
+    pub id: Uuid,
+    #[schema(example = "vacation")]
+    pub name: String,
+    #[schema(example = "#03A9F4")]
+    pub color_hex: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id,
+            name: tag.name,
+            color_hex: tag.color_hex,
+            created_at: tag.created_at,
+            updated_at: tag.updated_at,
+        }
+    }
+}
+
+/// Path parameters for tag ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TagIdPath {
+    /// Tag UUID
+    pub id: Uuid,
+}
+
+/// Tag ids in a query string, given comma-separated (`tagIds=a,b`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagIds(pub Vec<Uuid>);
+
+impl TagIds {
+    /// Parse a comma-separated list, ignoring blank entries
+    pub fn parse(value: &str) -> Result<Self, uuid::Error> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(Uuid::parse_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map(TagIds)
+    }
+}
+
+impl<'de> Deserialize<'de> for TagIds {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        TagIds::parse(&value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl std::ops::Deref for TagIds {
+    type Target = [Uuid];
+
+    fn deref(&self) -> &[Uuid] {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_ids_parse_comma_separated() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+
+        assert_eq!(
+            TagIds::parse(&format!("{a}, {b},")).unwrap(),
+            TagIds(vec![a, b])
+        );
+        assert_eq!(TagIds::parse("").unwrap(), TagIds::default());
+        assert!(TagIds::parse("vacation").is_err());
+    }
+}
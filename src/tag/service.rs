@@ -0,0 +1,230 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{CreateTagDto, Tag, UpdateTagDto};
+use crate::errors::{AppError, ErrorCode};
+
+const TAG_COLUMNS: &str = "id, owner_id, name, color_hex, created_at, updated_at";
+
+/// Map a write error, reporting a duplicate name as a conflict
+fn write_error(e: sqlx::Error) -> AppError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => AppError::Coded(
+            ErrorCode::DuplicateTag,
+            "A tag with this name already exists".to_string(),
+        ),
+        _ => AppError::InternalError(e.to_string()),
+    }
+}
+
+/// Service layer for transaction tags.
+pub struct TagService;
+
+impl TagService {
+    /// List the user's tags by name
+    pub async fn list(pool: &PgPool, owner_id: Uuid) -> Result<Vec<Tag>, AppError> {
+        sqlx::query_as::<_, Tag>(&format!(
+            "SELECT {TAG_COLUMNS} FROM tags WHERE owner_id = $1 ORDER BY LOWER(name)"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Get a tag by ID
+    pub async fn get(pool: &PgPool, tag_id: Uuid, owner_id: Uuid) -> Result<Tag, AppError> {
+        sqlx::query_as::<_, Tag>(&format!(
+            "SELECT {TAG_COLUMNS} FROM tags WHERE id = $1 AND owner_id = $2"
+        ))
+        .bind(tag_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Tag not found".to_string()))
+    }
+
+    /// Create a tag
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateTagDto,
+    ) -> Result<Tag, AppError> {
+        let name = dto.name.trim();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Name cannot be empty".to_string(),
+            ));
+        }
+
+        sqlx::query_as::<_, Tag>(&format!(
+            "INSERT INTO tags (owner_id, name, color_hex) VALUES ($1, $2, $3) \
+             RETURNING {TAG_COLUMNS}"
+        ))
+        .bind(owner_id)
+        .bind(name)
+        .bind(&dto.color_hex)
+        .fetch_one(pool)
+        .await
+        .map_err(write_error)
+    }
+
+    /// Update a tag (partial update - PATCH semantics)
+    pub async fn update(
+        pool: &PgPool,
+        tag_id: Uuid,
+        owner_id: Uuid,
+        dto: UpdateTagDto,
+    ) -> Result<Tag, AppError> {
+        let current = Self::get(pool, tag_id, owner_id).await?;
+
+        let name = match dto.name {
+            Some(n) => {
+                let trimmed = n.trim().to_string();
+                if trimmed.is_empty() {
+                    return Err(AppError::ValidationError(
+                        "Name cannot be empty".to_string(),
+                    ));
+                }
+                trimmed
+            }
+            None => current.name,
+        };
+
+        sqlx::query_as::<_, Tag>(&format!(
+            "UPDATE tags SET name = $3, color_hex = $4 WHERE id = $1 AND owner_id = $2 \
+             RETURNING {TAG_COLUMNS}"
+        ))
+        .bind(tag_id)
+        .bind(owner_id)
+        .bind(&name)
+        .bind(dto.color_hex.unwrap_or(current.color_hex))
+        .fetch_one(pool)
+        .await
+        .map_err(write_error)
+    }
+
+    /// Delete a tag, removing it from every transaction
+    pub async fn delete(pool: &PgPool, tag_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM tags WHERE id = $1 AND owner_id = $2")
+            .bind(tag_id)
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Tag not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Tags on one of the user's transactions
+    pub async fn for_transaction(
+        pool: &PgPool,
+        owner_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<Vec<Tag>, AppError> {
+        Self::verify_transaction(pool, owner_id, transaction_id).await?;
+
+        sqlx::query_as::<_, Tag>(
+            r#"
+            SELECT g.id, g.owner_id, g.name, g.color_hex, g.created_at, g.updated_at
+            FROM tags g
+            JOIN transaction_tags tt ON tt.tag_id = g.id
+            WHERE tt.transaction_id = $1 AND g.owner_id = $2
+            ORDER BY LOWER(g.name)
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Replace the tags on one of the user's transactions
+    pub async fn set_for_transaction(
+        pool: &PgPool,
+        owner_id: Uuid,
+        transaction_id: Uuid,
+        tag_ids: &[Uuid],
+    ) -> Result<Vec<Tag>, AppError> {
+        Self::verify_transaction(pool, owner_id, transaction_id).await?;
+
+        let mut tag_ids = tag_ids.to_vec();
+        tag_ids.sort();
+        tag_ids.dedup();
+
+        let owned = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM tags WHERE id = ANY($1) AND owner_id = $2",
+        )
+        .bind(&tag_ids)
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if owned as usize != tag_ids.len() {
+            return Err(AppError::NotFound("Tag not found".to_string()));
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query("DELETE FROM transaction_tags WHERE transaction_id = $1")
+            .bind(transaction_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO transaction_tags (transaction_id, tag_id)
+            SELECT $1, UNNEST($2::UUID[])
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(&tag_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Self::for_transaction(pool, owner_id, transaction_id).await
+    }
+
+    /// Fail with 404 unless the transaction is the user's and not in the trash
+    async fn verify_transaction(
+        pool: &PgPool,
+        owner_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<(), AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM transactions t
+                JOIN categories c ON t.category_id = c.id
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE t.id = $1 AND b.owner_id = $2 AND t.deleted_at IS NULL
+            )
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !exists {
+            return Err(AppError::NotFound("Transaction not found".to_string()));
+        }
+        Ok(())
+    }
+}
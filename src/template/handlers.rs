@@ -0,0 +1,224 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+use crate::transaction::models::TransactionResponse;
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
+
+use super::models::{
+    CreateTemplateDto, TemplateIdPath, TemplateResponse, UpdateTemplateDto, UseTemplateDto,
+};
+use super::service::TemplateService;
+
+/// GET /transaction-templates - List templates, most used first
+#[utoipa::path(
+    get,
+    path = "/transaction-templates",
+    tag = "Templates",
+    responses(
+        (status = 200, description = "List of templates", body = Vec<TemplateResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transaction-templates")]
+pub async fn list_templates(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let templates = TemplateService::list(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        templates
+            .into_iter()
+            .map(TemplateResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// GET /transaction-templates/{id} - Get a template
+#[utoipa::path(
+    get,
+    path = "/transaction-templates/{id}",
+    tag = "Templates",
+    params(TemplateIdPath),
+    responses(
+        (status = 200, description = "Template details", body = TemplateResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transaction-templates/{id}")]
+pub async fn get_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TemplateIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let template = TemplateService::get(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(TemplateResponse::from(template)))
+}
+
+/// POST /transaction-templates - Save a template
+#[utoipa::path(
+    post,
+    path = "/transaction-templates",
+    tag = "Templates",
+    request_body = CreateTemplateDto,
+    responses(
+        (status = 201, description = "Template created", body = TemplateResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Category or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transaction-templates")]
+pub async fn create_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateTemplateDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let template = TemplateService::create(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(TemplateResponse::from(template)))
+}
+
+/// PATCH /transaction-templates/{id} - Update a template
+#[utoipa::path(
+    patch,
+    path = "/transaction-templates/{id}",
+    tag = "Templates",
+    params(TemplateIdPath),
+    request_body = UpdateTemplateDto,
+    responses(
+        (status = 200, description = "Template updated", body = TemplateResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Template, category or account not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/transaction-templates/{id}")]
+pub async fn update_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TemplateIdPath>,
+    body: web::Json<UpdateTemplateDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+    body.validate_fields()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let template =
+        TemplateService::update(pool.get_ref(), path.id, auth.user_id, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(TemplateResponse::from(template)))
+}
+
+/// DELETE /transaction-templates/{id} - Delete a template
+#[utoipa::path(
+    delete,
+    path = "/transaction-templates/{id}",
+    tag = "Templates",
+    params(TemplateIdPath),
+    responses(
+        (status = 200, description = "Template deleted", body = DeleteResponse),
+        (status = 404, description = "Template not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/transaction-templates/{id}")]
+pub async fn delete_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TemplateIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    TemplateService::delete(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Template deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// POST /transactions/from-template/{id} - Create a transaction from a template
+#[utoipa::path(
+    post,
+    path = "/transactions/from-template/{id}",
+    tag = "Templates",
+    params(TemplateIdPath),
+    request_body = UseTemplateDto,
+    responses(
+        (status = 201, description = "Transaction created", body = TransactionResponse),
+        (status = 400, description = "Validation error or amount missing", body = ErrorResponse),
+        (status = 404, description = "Template, current budget or matching category not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/from-template/{id}")]
+pub async fn create_from_template(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TemplateIdPath>,
+    body: web::Json<UseTemplateDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let transaction = TemplateService::create_transaction(
+        pool.get_ref(),
+        path.id,
+        auth.user_id,
+        body.into_inner(),
+    )
+    .await?;
+
+    let response = TransactionResponse::from(transaction);
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &response,
+    )
+    .await;
+
+    Ok(HttpResponse::Created().json(response))
+}
+
+/// Register the transaction template routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(create_from_template)
+        .service(list_templates)
+        .service(get_template)
+        .service(create_template)
+        .service(update_template)
+        .service(delete_template);
+}
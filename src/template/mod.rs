@@ -0,0 +1,5 @@
+pub mod handlers;
+pub mod models;
+pub mod service;
+
+pub use handlers::*;
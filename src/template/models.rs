@@ -0,0 +1,241 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+use crate::transaction::models::TransactionType;
+
+/// Distinguish an explicit `null` (clear the field) from an absent field
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Templates may only prefill expenses and income
+fn validate_template_type(transaction_type: &TransactionType) -> Result<(), ValidationError> {
+    if *transaction_type == TransactionType::Transfer {
+        return Err(ValidationError::new("transfer_not_allowed"));
+    }
+    Ok(())
+}
+
+fn validate_positive_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount <= Decimal::ZERO {
+        return Err(ValidationError::new("amount_must_be_positive"));
+    }
+    Ok(())
+}
+
+/// Database entity for transaction templates
+#[derive(Debug, Clone, FromRow)]
+pub struct TransactionTemplate {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub name: String,
+    pub category_name: String,
+    pub account_id: Option<Uuid>,
+    pub amount: Option<Decimal>,
+    pub transaction_type: String,
+    pub description: Option<String>,
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TransactionTemplate {
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        Ok(self)
+    }
+
+    /// Stored transaction type, defaulting to expense
+    pub fn transaction_type(&self) -> TransactionType {
+        TransactionType::parse(&self.transaction_type).unwrap_or_default()
+    }
+}
+
+/// Transaction template returned in responses
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateResponse {
+    pub id: Uuid,
+    /// Display name
+    #[schema(example = "Morning coffee")]
+    pub name: String,
+    /// Category name, matched against the current month's budget when used
+    #[schema(example = "Dining")]
+    pub category_name: String,
+    pub account_id: Option<Uuid>,
+    /// Prefilled amount; absent when it must be entered on use
+    #[schema(example = 4.50)]
+    pub amount: Option<Decimal>,
+    #[schema(example = "expense")]
+    pub transaction_type: String,
+    #[schema(example = "Coffee")]
+    pub description: Option<String>,
+    /// Number of transactions created from this template
+    #[schema(example = 42)]
+    pub use_count: i32,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<TransactionTemplate> for TemplateResponse {
+    fn from(t: TransactionTemplate) -> Self {
+        Self {
+            id: t.id,
+            name: t.name,
+            category_name: t.category_name,
+            account_id: t.account_id,
+            amount: t.amount,
+            transaction_type: t.transaction_type,
+            description: t.description,
+            use_count: t.use_count,
+            last_used_at: t.last_used_at,
+            created_at: t.created_at,
+            updated_at: t.updated_at,
+        }
+    }
+}
+
+/// Request body for creating a transaction template
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateTemplateDto {
+    /// Display name (1-50 characters)
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    #[schema(example = "Morning coffee")]
+    pub name: String,
+
+    /// Category whose name the template files transactions under
+    pub category_id: Uuid,
+
+    /// Account to use (optional)
+    pub account_id: Option<Uuid>,
+
+    /// Prefilled amount (optional)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 4.50)]
+    pub amount: Option<Decimal>,
+
+    /// Transaction type (expense or income, defaults to expense)
+    #[validate(custom(
+        function = "validate_template_type",
+        message = "Templates cannot be transfers"
+    ))]
+    #[serde(default)]
+    pub transaction_type: TransactionType,
+
+    /// Optional description (max 200 chars)
+    #[validate(length(max = 200, message = "Description cannot exceed 200 characters"))]
+    #[schema(example = "Coffee")]
+    pub description: Option<String>,
+}
+
+/// Request body for updating a transaction template (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateTemplateDto {
+    /// Display name
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: Option<String>,
+
+    /// Category whose name the template files transactions under
+    pub category_id: Option<Uuid>,
+
+    /// Account ID (use null to remove account association)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub account_id: Option<Option<Uuid>>,
+
+    /// Prefilled amount (use null to require it on each use)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Decimal>)]
+    pub amount: Option<Option<Decimal>>,
+
+    /// Transaction type (expense or income)
+    pub transaction_type: Option<TransactionType>,
+
+    /// Description (use null to remove)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+}
+
+impl UpdateTemplateDto {
+    /// Validate fields the derive macro can't reach through the nested options
+    pub fn validate_fields(&self) -> Result<(), ValidationError> {
+        if let Some(Some(amount)) = &self.amount {
+            validate_positive_amount(amount)?;
+        }
+        if let Some(transaction_type) = &self.transaction_type {
+            validate_template_type(transaction_type)?;
+        }
+        if let Some(Some(description)) = &self.description {
+            if description.len() > 200 {
+                return Err(ValidationError::new("description_too_long"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Request body for creating a transaction from a template; send `{}` to use it as saved
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UseTemplateDto {
+    /// Amount (required when the template has none)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 5.25)]
+    pub amount: Option<Decimal>,
+
+    /// Transaction date (defaults to now)
+    pub transaction_date: Option<DateTime<Utc>>,
+
+    /// Description overriding the template's
+    #[validate(length(max = 200, message = "Description cannot exceed 200 characters"))]
+    pub description: Option<String>,
+}
+
+/// Path parameters for template ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TemplateIdPath {
+    /// Template UUID
+    pub id: Uuid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_template_distinguishes_null_from_absent() {
+        let dto: UpdateTemplateDto =
+            serde_json::from_value(serde_json::json!({ "amount": null, "name": "Fuel" })).unwrap();
+        assert_eq!(dto.amount, Some(None));
+        assert_eq!(dto.account_id, None);
+        assert!(dto.validate_fields().is_ok());
+
+        let transfer: UpdateTemplateDto =
+            serde_json::from_value(serde_json::json!({ "transactionType": "transfer" })).unwrap();
+        assert!(transfer.validate_fields().is_err());
+    }
+}
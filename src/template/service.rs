@@ -0,0 +1,250 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::models::{CreateTemplateDto, TransactionTemplate, UpdateTemplateDto, UseTemplateDto};
+use crate::category::service::CategoryService;
+use crate::crypto::encrypt_optional;
+use crate::errors::AppError;
+use crate::transaction::models::{CreateTransactionDto, Transaction};
+use crate::transaction::service::TransactionService;
+
+const TEMPLATE_COLUMNS: &str = "id, owner_id, name, category_name, account_id, amount, transaction_type, description, use_count, last_used_at, created_at, updated_at";
+
+/// Service layer for transaction templates.
+pub struct TemplateService;
+
+impl TemplateService {
+    /// List templates, most used first
+    pub async fn list(pool: &PgPool, owner_id: Uuid) -> Result<Vec<TransactionTemplate>, AppError> {
+        sqlx::query_as::<_, TransactionTemplate>(&format!(
+            "SELECT {TEMPLATE_COLUMNS} FROM transaction_templates WHERE owner_id = $1 \
+             ORDER BY use_count DESC, last_used_at DESC NULLS LAST, name"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(TransactionTemplate::decrypted)
+        .collect()
+    }
+
+    /// Get a template by ID
+    pub async fn get(
+        pool: &PgPool,
+        template_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<TransactionTemplate, AppError> {
+        sqlx::query_as::<_, TransactionTemplate>(&format!(
+            "SELECT {TEMPLATE_COLUMNS} FROM transaction_templates WHERE id = $1 AND owner_id = $2"
+        ))
+        .bind(template_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Template not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Fail with 404 unless the account belongs to the user
+    async fn verify_account(
+        pool: &PgPool,
+        account_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<(), AppError> {
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+        )
+        .bind(account_id)
+        .bind(owner_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !exists {
+            return Err(AppError::NotFound("Account not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Create a template
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateTemplateDto,
+    ) -> Result<TransactionTemplate, AppError> {
+        let name = dto.name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::ValidationError(
+                "Name cannot be empty".to_string(),
+            ));
+        }
+
+        let category = CategoryService::get_by_id(pool, dto.category_id, owner_id).await?;
+        if let Some(account_id) = dto.account_id {
+            Self::verify_account(pool, account_id, owner_id).await?;
+        }
+
+        sqlx::query_as::<_, TransactionTemplate>(&format!(
+            r#"
+            INSERT INTO transaction_templates
+                (owner_id, name, category_name, account_id, amount, transaction_type, description)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {TEMPLATE_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(&name)
+        .bind(&category.name)
+        .bind(dto.account_id)
+        .bind(dto.amount)
+        .bind(dto.transaction_type.as_str())
+        .bind(encrypt_optional(dto.description.as_deref())?)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .decrypted()
+    }
+
+    /// Update a template (partial update - PATCH semantics)
+    pub async fn update(
+        pool: &PgPool,
+        template_id: Uuid,
+        owner_id: Uuid,
+        dto: UpdateTemplateDto,
+    ) -> Result<TransactionTemplate, AppError> {
+        let current = Self::get(pool, template_id, owner_id).await?;
+
+        let name = match dto.name {
+            Some(n) => {
+                let trimmed = n.trim().to_string();
+                if trimmed.is_empty() {
+                    return Err(AppError::ValidationError(
+                        "Name cannot be empty".to_string(),
+                    ));
+                }
+                trimmed
+            }
+            None => current.name,
+        };
+        let category_name = match dto.category_id {
+            Some(category_id) => {
+                CategoryService::get_by_id(pool, category_id, owner_id)
+                    .await?
+                    .name
+            }
+            None => current.category_name,
+        };
+        let account_id = dto.account_id.unwrap_or(current.account_id);
+        if let Some(account_id) = account_id {
+            if Some(account_id) != current.account_id {
+                Self::verify_account(pool, account_id, owner_id).await?;
+            }
+        }
+        let amount = dto.amount.unwrap_or(current.amount);
+        let transaction_type = dto
+            .transaction_type
+            .map(|t| t.as_str().to_string())
+            .unwrap_or(current.transaction_type);
+        let description = dto.description.unwrap_or(current.description);
+
+        sqlx::query_as::<_, TransactionTemplate>(&format!(
+            r#"
+            UPDATE transaction_templates SET
+                name = $3,
+                category_name = $4,
+                account_id = $5,
+                amount = $6,
+                transaction_type = $7,
+                description = $8
+            WHERE id = $1 AND owner_id = $2
+            RETURNING {TEMPLATE_COLUMNS}
+            "#
+        ))
+        .bind(template_id)
+        .bind(owner_id)
+        .bind(&name)
+        .bind(&category_name)
+        .bind(account_id)
+        .bind(amount)
+        .bind(&transaction_type)
+        .bind(encrypt_optional(description.as_deref())?)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .decrypted()
+    }
+
+    /// Delete a template
+    pub async fn delete(pool: &PgPool, template_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result =
+            sqlx::query("DELETE FROM transaction_templates WHERE id = $1 AND owner_id = $2")
+                .bind(template_id)
+                .bind(owner_id)
+                .execute(pool)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Template not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Create a transaction from a template. The category is the same-named
+    /// category in the current month's budget.
+    pub async fn create_transaction(
+        pool: &PgPool,
+        template_id: Uuid,
+        owner_id: Uuid,
+        dto: UseTemplateDto,
+    ) -> Result<Transaction, AppError> {
+        let template = Self::get(pool, template_id, owner_id).await?;
+
+        let amount = dto.amount.or(template.amount).ok_or_else(|| {
+            AppError::ValidationError(
+                "Amount is required because the template has none".to_string(),
+            )
+        })?;
+
+        let category_id =
+            CategoryService::current_id_by_name(pool, owner_id, &template.category_name).await?;
+
+        let transaction = TransactionService::create_transaction(
+            pool,
+            owner_id,
+            CreateTransactionDto {
+                reimbursable: false,
+                reimbursement_payer: None,
+                tax_category: None,
+                currency: None,
+                exchange_rate: None,
+                pending: false,
+                payee_id: None,
+                linked_transaction_id: None,
+                fee_amount: None,
+                destination_amount: None,
+                category_id,
+                account_id: template.account_id,
+                destination_account_id: None,
+                amount,
+                transaction_date: dto.transaction_date.unwrap_or_else(Utc::now),
+                description: dto.description.or(template.description.clone()),
+                transaction_type: template.transaction_type(),
+            },
+        )
+        .await?;
+
+        sqlx::query(
+            "UPDATE transaction_templates SET use_count = use_count + 1, last_used_at = NOW() WHERE id = $1",
+        )
+        .bind(template_id)
+        .execute(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(transaction)
+    }
+}
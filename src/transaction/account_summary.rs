@@ -0,0 +1,136 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+use crate::tag::models::TagIds;
+
+/// Query parameters for the per-account summary
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSummaryFilters {
+    /// Filter by start date
+    pub start_date: Option<DateTime<Utc>>,
+    /// Filter by end date
+    pub end_date: Option<DateTime<Utc>>,
+    /// Filter by tags, comma-separated; matches transactions with any of them
+    #[param(value_type = Option<String>, example = "3fa85f64-5717-4562-b3fc-2c963f66afa6")]
+    pub tag_ids: Option<TagIds>,
+    /// Calendar month in the user's timezone (0-11, or 1-12 with monthBase=1; requires year)
+    #[param(example = 0)]
+    pub month: Option<i16>,
+    /// Calendar year for the month filter
+    #[validate(range(min = 2000, max = 2100, message = "Year must be 2000-2100"))]
+    #[param(example = 2025)]
+    pub year: Option<i16>,
+}
+
+impl AccountSummaryFilters {
+    /// Month and year must be supplied together
+    pub fn validate_period(&self) -> Result<(), ValidationError> {
+        if self.month.is_some() != self.year.is_some() {
+            return Err(ValidationError::new(
+                "month and year must be provided together",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Database row for the per-account summary query. Transactions without an
+/// account are grouped under a row with no account.
+#[derive(Debug, FromRow)]
+pub struct AccountSummaryRow {
+    pub account_id: Option<Uuid>,
+    pub account_name: Option<String>,
+    pub account_color_hex: Option<String>,
+    pub currency: Option<String>,
+    pub total_income: Decimal,
+    pub total_expenses: Decimal,
+    pub transfers_out: Decimal,
+    pub transfers_in: Decimal,
+    pub transaction_count: i64,
+}
+
+/// Income, expenses and transfers of one account over the period
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSpendingSummary {
+    /// Account ID (absent for transactions not linked to an account)
+    pub account_id: Option<Uuid>,
+    /// Account name
+    #[schema(example = "My Checking")]
+    pub account_name: Option<String>,
+    /// Account color
+    #[schema(example = "#4CAF50")]
+    pub account_color_hex: Option<String>,
+    /// Account currency, which all of its amounts are in
+    #[schema(example = "USD")]
+    pub currency: Option<String>,
+    /// Income received into the account (net of refunds)
+    #[schema(example = 5000.00)]
+    pub total_income: Decimal,
+    /// Expenses paid from the account (net of refunds)
+    #[schema(example = 3500.00)]
+    pub total_expenses: Decimal,
+    /// Transfers out of the account
+    #[schema(example = 500.00)]
+    pub transfers_out: Decimal,
+    /// Transfers into the account
+    #[schema(example = 0.00)]
+    pub transfers_in: Decimal,
+    /// Net effect on the balance (income - expenses - transfers out + transfers in)
+    #[schema(example = 1000.00)]
+    pub net_change: Decimal,
+    /// Number of transactions touching the account
+    #[schema(example = 45)]
+    pub transaction_count: i64,
+}
+
+impl AccountSpendingSummary {
+    /// Build the summary from a row, decrypting the account name
+    pub fn from_row(row: AccountSummaryRow) -> Result<Self, AppError> {
+        Ok(Self {
+            account_id: row.account_id,
+            account_name: decrypt_optional(row.account_name)?,
+            account_color_hex: row.account_color_hex,
+            currency: row.currency.map(|c| c.trim().to_string()),
+            net_change: row.total_income - row.total_expenses - row.transfers_out
+                + row.transfers_in,
+            total_income: row.total_income,
+            total_expenses: row.total_expenses,
+            transfers_out: row.transfers_out,
+            transfers_in: row.transfers_in,
+            transaction_count: row.transaction_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_summary_net_change() {
+        let summary = AccountSpendingSummary::from_row(AccountSummaryRow {
+            account_id: None,
+            account_name: None,
+            account_color_hex: None,
+            currency: Some("USD".to_string()),
+            total_income: Decimal::from(1000),
+            total_expenses: Decimal::from(350),
+            transfers_out: Decimal::from(200),
+            transfers_in: Decimal::from(50),
+            transaction_count: 6,
+        })
+        .unwrap();
+
+        assert_eq!(summary.net_change, Decimal::from(500));
+        assert!(summary.account_name.is_none());
+    }
+}
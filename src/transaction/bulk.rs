@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::models::{TransactionResponse, UpdateTransactionDto};
+
+/// Request body for POST /transactions/bulk-delete
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkDeleteTransactionsDto {
+    /// Transactions to move to the trash (1-500)
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Between 1 and 500 transactions can be deleted at once"
+    ))]
+    pub transaction_ids: Vec<Uuid>,
+}
+
+/// Request body for POST /transactions/bulk-update
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateTransactionsDto {
+    /// Transactions to change (1-500)
+    #[validate(length(
+        min = 1,
+        max = 500,
+        message = "Between 1 and 500 transactions can be updated at once"
+    ))]
+    pub transaction_ids: Vec<Uuid>,
+
+    /// Move every transaction to this category
+    pub category_id: Option<Uuid>,
+
+    /// Move every transaction to this (source) account
+    pub account_id: Option<Uuid>,
+}
+
+impl BulkUpdateTransactionsDto {
+    /// At least one change to apply
+    pub fn validate_changes(&self) -> Result<(), ValidationError> {
+        if self.category_id.is_none() && self.account_id.is_none() {
+            return Err(ValidationError::new("category_or_account_required"));
+        }
+        Ok(())
+    }
+
+    /// The single-transaction update applied to each selected transaction
+    pub fn to_update(&self) -> UpdateTransactionDto {
+        UpdateTransactionDto {
+            category_id: self.category_id,
+            account_id: self.account_id.map(Some),
+            destination_account_id: None,
+            amount: None,
+            transaction_date: None,
+            description: None,
+            transaction_type: None,
+            tax_category: None,
+            payee_id: None,
+            fee_amount: None,
+            destination_amount: None,
+        }
+    }
+}
+
+/// Response for POST /transactions/bulk-delete
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkDeleteResponse {
+    /// Number of transactions moved to the trash
+    #[schema(example = 12)]
+    pub deleted: usize,
+}
+
+/// Response for POST /transactions/bulk-update
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUpdateResponse {
+    /// Number of transactions changed
+    #[schema(example = 12)]
+    pub updated: usize,
+    /// The transactions after the change
+    pub data: Vec<TransactionResponse>,
+}
+
+/// Order locked rows for deletion: refunds before the transactions they
+/// refund, so deleting both in one request doesn't trip the refund check.
+/// Each row is `(id, reversal_of_id)`.
+pub fn deletion_order(mut rows: Vec<(Uuid, Option<Uuid>)>) -> Vec<Uuid> {
+    rows.sort_by_key(|(id, reversal_of)| (reversal_of.is_none(), *id));
+    rows.into_iter().map(|(id, _)| id).collect()
+}
+
+/// Drop repeated ids, keeping a stable order for lock acquisition
+pub fn unique_ids(ids: &[Uuid]) -> Vec<Uuid> {
+    let mut ids = ids.to_vec();
+    ids.sort();
+    ids.dedup();
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deletion_order_puts_refunds_first() {
+        let original = Uuid::from_u128(1);
+        let refund = Uuid::from_u128(2);
+        let other = Uuid::from_u128(3);
+
+        assert_eq!(
+            deletion_order(vec![
+                (original, None),
+                (other, None),
+                (refund, Some(original))
+            ]),
+            vec![refund, original, other]
+        );
+        assert_eq!(unique_ids(&[other, original, other]), vec![original, other]);
+    }
+
+    #[test]
+    fn test_bulk_update_needs_a_change() {
+        let dto = BulkUpdateTransactionsDto {
+            transaction_ids: vec![Uuid::new_v4()],
+            category_id: None,
+            account_id: None,
+        };
+        assert!(dto.validate_changes().is_err());
+
+        let dto = BulkUpdateTransactionsDto {
+            account_id: Some(Uuid::new_v4()),
+            ..dto
+        };
+        assert!(dto.validate_changes().is_ok());
+        assert_eq!(dto.to_update().account_id, Some(dto.account_id));
+    }
+}
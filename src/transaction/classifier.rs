@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+use sqlx::{FromRow, PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::models::{CategorySuggestion, SuggestCategoryQuery, Transaction};
+use crate::errors::AppError;
+
+/// Naive Bayes category classifier trained on each user's own transactions.
+///
+/// The model is updated incrementally as transactions are created, updated
+/// and deleted, and is keyed by lowercase category name so history carries
+/// across monthly budgets.
+pub struct CategoryClassifier;
+
+#[derive(Debug, FromRow)]
+struct CategoryStatsRow {
+    category_key: String,
+    transaction_count: i32,
+    token_count: i32,
+    log_amount_sum: f64,
+}
+
+#[derive(Debug, FromRow)]
+struct TokenCountRow {
+    category_key: String,
+    token_hash: String,
+    count: i32,
+}
+
+#[derive(Debug, FromRow)]
+struct CandidateCategory {
+    id: Uuid,
+    name: String,
+}
+
+/// Split a description into lowercase word tokens, ignoring numbers and single characters
+fn tokenize(description: &str) -> Vec<String> {
+    description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| t.chars().count() > 1 && !t.chars().all(|c| c.is_ascii_digit()))
+        .map(|t| t.to_lowercase())
+        .collect()
+}
+
+/// Salted token hash so stored tokens can't be matched across users
+fn hash_token(user_id: Uuid, token: &str) -> String {
+    let digest = Sha256::new()
+        .chain_update(user_id.as_bytes())
+        .chain_update(token.as_bytes())
+        .finalize();
+    hex::encode(&digest[..8])
+}
+
+fn log_amount(amount: Decimal) -> f64 {
+    amount.abs().to_f64().unwrap_or(0.0).max(0.01).ln()
+}
+
+impl CategoryClassifier {
+    /// Add (`weight = 1`) or remove (`weight = -1`) a transaction from the model.
+    pub async fn learn(
+        conn: &mut PgConnection,
+        user_id: Uuid,
+        category_id: Uuid,
+        description: Option<&str>,
+        amount: Decimal,
+        weight: i32,
+    ) -> Result<(), AppError> {
+        let tokens: Vec<String> = description
+            .map(tokenize)
+            .unwrap_or_default()
+            .iter()
+            .map(|t| hash_token(user_id, t))
+            .collect();
+
+        let category_key = sqlx::query_scalar::<_, String>(
+            "SELECT LOWER(TRIM(name)) FROM categories WHERE id = $1",
+        )
+        .bind(category_id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // The category may already be gone (e.g. cascade delete); nothing to learn
+        let Some(category_key) = category_key else {
+            return Ok(());
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO category_model_stats (user_id, category_key, transaction_count, token_count, log_amount_sum)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, category_key) DO UPDATE SET
+                transaction_count = category_model_stats.transaction_count + EXCLUDED.transaction_count,
+                token_count = category_model_stats.token_count + EXCLUDED.token_count,
+                log_amount_sum = category_model_stats.log_amount_sum + EXCLUDED.log_amount_sum
+            "#,
+        )
+        .bind(user_id)
+        .bind(&category_key)
+        .bind(weight)
+        .bind(weight * tokens.len() as i32)
+        .bind(f64::from(weight) * log_amount(amount))
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if !tokens.is_empty() {
+            sqlx::query(
+                r#"
+                INSERT INTO category_model_tokens (user_id, category_key, token_hash, count)
+                SELECT $1, $2, token_hash, COUNT(*)::INTEGER * $4
+                FROM UNNEST($3::TEXT[]) AS token_hash
+                GROUP BY token_hash
+                ON CONFLICT (user_id, category_key, token_hash) DO UPDATE SET
+                    count = category_model_tokens.count + EXCLUDED.count
+                "#,
+            )
+            .bind(user_id)
+            .bind(&category_key)
+            .bind(&tokens)
+            .bind(weight)
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        if weight < 0 {
+            sqlx::query("DELETE FROM category_model_tokens WHERE user_id = $1 AND category_key = $2 AND count <= 0")
+                .bind(user_id)
+                .bind(&category_key)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+            sqlx::query("DELETE FROM category_model_stats WHERE user_id = $1 AND category_key = $2 AND transaction_count <= 0")
+                .bind(user_id)
+                .bind(&category_key)
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Train the model from existing history for users whose model is empty
+    /// (transactions created before the classifier existed).
+    async fn ensure_trained(pool: &PgPool, user_id: Uuid) -> Result<(), AppError> {
+        let has_model = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM category_model_stats WHERE user_id = $1)",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if has_model {
+            return Ok(());
+        }
+
+        let transactions = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND t.deleted_at IS NULL AND t.status = 'posted'
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if transactions.is_empty() {
+            return Ok(());
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        for transaction in transactions {
+            let transaction = transaction.decrypted()?;
+            Self::learn(
+                &mut tx,
+                user_id,
+                transaction.category_id,
+                transaction.description.as_deref(),
+                transaction.amount,
+                1,
+            )
+            .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Rank the target budget's categories for a description and amount.
+    pub async fn suggest(
+        pool: &PgPool,
+        user_id: Uuid,
+        query: &SuggestCategoryQuery,
+    ) -> Result<Vec<CategorySuggestion>, AppError> {
+        Self::ensure_trained(pool, user_id).await?;
+
+        // Candidate categories: the requested budget, or the user's most recent one
+        let candidates = sqlx::query_as::<_, CandidateCategory>(
+            r#"
+            SELECT c.id, c.name
+            FROM categories c
+            WHERE c.budget_id = (
+                SELECT b.id FROM budgets b
+                WHERE b.owner_id = $1 AND ($2::UUID IS NULL OR b.id = $2)
+                ORDER BY b.year DESC, b.month DESC
+                LIMIT 1
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(query.budget_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stats = sqlx::query_as::<_, CategoryStatsRow>(
+            "SELECT category_key, transaction_count, token_count, log_amount_sum FROM category_model_stats WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let tokens: Vec<String> = tokenize(&query.description)
+            .iter()
+            .map(|t| hash_token(user_id, t))
+            .collect();
+
+        let token_counts = sqlx::query_as::<_, TokenCountRow>(
+            "SELECT category_key, token_hash, count FROM category_model_tokens WHERE user_id = $1 AND token_hash = ANY($2)",
+        )
+        .bind(user_id)
+        .bind(&tokens)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let vocabulary_size = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(DISTINCT token_hash) FROM category_model_tokens WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let model = Model {
+            stats: stats
+                .into_iter()
+                .map(|s| (s.category_key.clone(), s))
+                .collect(),
+            token_counts: token_counts
+                .into_iter()
+                .map(|t| ((t.category_key, t.token_hash), t.count))
+                .collect(),
+            vocabulary_size: vocabulary_size.max(1) as f64,
+        };
+
+        let scores: Vec<f64> = candidates
+            .iter()
+            .map(|c| model.log_score(&c.name.trim().to_lowercase(), &tokens, query.amount))
+            .collect();
+
+        // Softmax over log scores gives per-candidate confidence
+        let max_score = scores.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = scores.iter().map(|s| (s - max_score).exp()).collect();
+        let total: f64 = weights.iter().sum();
+
+        let mut suggestions: Vec<CategorySuggestion> = candidates
+            .into_iter()
+            .zip(weights)
+            .map(|(c, w)| CategorySuggestion {
+                category_id: c.id,
+                name: c.name,
+                confidence: (w / total * 10000.0).round() / 10000.0,
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        suggestions.truncate(query.limit as usize);
+
+        Ok(suggestions)
+    }
+}
+
+/// In-memory slice of a user's model needed to score one description
+struct Model {
+    stats: HashMap<String, CategoryStatsRow>,
+    token_counts: HashMap<(String, String), i32>,
+    vocabulary_size: f64,
+}
+
+impl Model {
+    /// Log prior + Laplace-smoothed token likelihoods + an amount closeness term
+    fn log_score(&self, category_key: &str, tokens: &[String], amount: Option<Decimal>) -> f64 {
+        let total_transactions: i32 = self.stats.values().map(|s| s.transaction_count).sum();
+        let category_count = self.stats.len().max(1) as f64;
+        let stats = self.stats.get(category_key);
+
+        let transaction_count = stats.map(|s| s.transaction_count).unwrap_or(0) as f64;
+        let token_count = stats.map(|s| s.token_count).unwrap_or(0) as f64;
+
+        let mut score =
+            ((transaction_count + 1.0) / (total_transactions as f64 + category_count)).ln();
+
+        for token in tokens {
+            let count = self
+                .token_counts
+                .get(&(category_key.to_string(), token.clone()))
+                .copied()
+                .unwrap_or(0) as f64;
+            score += ((count + 1.0) / (token_count + self.vocabulary_size)).ln();
+        }
+
+        // Penalize amounts far (in log scale) from the category's typical amount
+        if let (Some(amount), Some(stats)) = (amount, stats) {
+            if stats.transaction_count > 0 {
+                let typical = stats.log_amount_sum / f64::from(stats.transaction_count);
+                score -= (log_amount(amount) - typical).abs();
+            }
+        }
+
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_drops_numbers_and_punctuation() {
+        assert_eq!(
+            tokenize("WHOLE FOODS #1234, Seattle"),
+            vec!["whole", "foods", "seattle"]
+        );
+    }
+
+    #[test]
+    fn test_model_prefers_category_with_matching_tokens() {
+        let user_id = Uuid::nil();
+        let token = hash_token(user_id, "coffee");
+        let stats = |key: &str, count: i32, log_amount_sum: f64| CategoryStatsRow {
+            category_key: key.to_string(),
+            transaction_count: count,
+            token_count: count,
+            log_amount_sum,
+        };
+
+        let model = Model {
+            stats: HashMap::from([
+                ("dining".to_string(), stats("dining", 5, 5.0 * 5f64.ln())),
+                ("rent".to_string(), stats("rent", 5, 5.0 * 1500f64.ln())),
+            ]),
+            token_counts: HashMap::from([(("dining".to_string(), token.clone()), 5)]),
+            vocabulary_size: 2.0,
+        };
+
+        let amount = Some(Decimal::from(4));
+        let tokens = vec![token];
+        assert!(
+            model.log_score("dining", &tokens, amount) > model.log_score("rent", &tokens, amount)
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::models::{Transaction, TransactionResponse};
+
+/// Pairs fetched before descriptions are compared; amount, account and date
+/// must already agree, so this is far above what a real ledger returns
+pub const DUPLICATE_SCAN_LIMIT: i64 = 1000;
+
+fn default_window() -> i64 {
+    3
+}
+
+fn default_min_similarity() -> f64 {
+    0.5
+}
+
+fn default_duplicate_limit() -> i64 {
+    50
+}
+
+/// Query parameters for finding likely duplicate transactions
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateQuery {
+    /// Days apart two transactions may still be duplicates (0-30, default 3)
+    #[validate(range(min = 0, max = 30, message = "window must be between 0 and 30 days"))]
+    #[serde(default = "default_window")]
+    #[param(example = 3)]
+    pub window: i64,
+
+    /// Lowest description similarity reported (0-1, default 0.5)
+    #[validate(range(
+        min = 0.0,
+        max = 1.0,
+        message = "minSimilarity must be between 0 and 1"
+    ))]
+    #[serde(default = "default_min_similarity")]
+    #[param(example = 0.5)]
+    pub min_similarity: f64,
+
+    /// Only look at transactions from this account
+    pub account_id: Option<Uuid>,
+
+    /// Maximum number of pairs (1-100, default 50)
+    #[validate(range(min = 1, max = 100))]
+    #[serde(default = "default_duplicate_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+}
+
+/// Two transactions on the same account with the same amount and type,
+/// close together in time
+#[derive(Debug, FromRow)]
+pub struct DuplicatePairRow {
+    pub original_id: Uuid,
+    pub duplicate_id: Uuid,
+    pub days_apart: i32,
+}
+
+/// A likely duplicate: `duplicate` was entered after `original`
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePair {
+    pub original: TransactionResponse,
+    pub duplicate: TransactionResponse,
+    /// Days between the two transaction dates
+    #[schema(example = 1)]
+    pub days_apart: i64,
+    /// Description similarity between 0 and 1
+    #[schema(example = 0.86)]
+    pub similarity: f64,
+}
+
+impl DuplicatePair {
+    /// Compare the descriptions of each pair and keep those alike enough,
+    /// most similar first. `transactions` holds both sides of every pair,
+    /// decrypted.
+    pub fn rank(
+        rows: Vec<DuplicatePairRow>,
+        transactions: &HashMap<Uuid, Transaction>,
+        query: &DuplicateQuery,
+    ) -> Vec<Self> {
+        let mut pairs: Vec<Self> = rows
+            .into_iter()
+            .filter_map(|row| {
+                let original = transactions.get(&row.original_id)?;
+                let duplicate = transactions.get(&row.duplicate_id)?;
+                let similarity = description_similarity(
+                    original.description.as_deref(),
+                    duplicate.description.as_deref(),
+                );
+                (similarity >= query.min_similarity).then(|| Self {
+                    original: TransactionResponse::from(original.clone()),
+                    duplicate: TransactionResponse::from(duplicate.clone()),
+                    days_apart: row.days_apart as i64,
+                    similarity,
+                })
+            })
+            .collect();
+
+        pairs.sort_by(|a, b| {
+            b.similarity
+                .total_cmp(&a.similarity)
+                .then_with(|| a.days_apart.cmp(&b.days_apart))
+        });
+        pairs.truncate(query.limit as usize);
+        pairs
+    }
+}
+
+/// Request body for merging a duplicate into a transaction
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeTransactionDto {
+    /// Transaction to move to the trash; its description and payee fill
+    /// in what the kept transaction lacks
+    pub duplicate_id: Uuid,
+}
+
+/// Adjacent letter and digit pairs of a description, ignoring case,
+/// punctuation and spacing
+fn bigrams(description: &str) -> Vec<(char, char)> {
+    let chars: Vec<char> = description
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    chars.windows(2).map(|w| (w[0], w[1])).collect()
+}
+
+/// Dice coefficient of the descriptions' character pairs: 1 for the same
+/// text, 0 for nothing in common. Two missing descriptions are alike; one
+/// missing description is like nothing.
+pub fn description_similarity(a: Option<&str>, b: Option<&str>) -> f64 {
+    let (a, b) = match (a, b) {
+        (None, None) => return 1.0,
+        (Some(a), Some(b)) => (bigrams(a), bigrams(b)),
+        _ => return 0.0,
+    };
+    if a.is_empty() || b.is_empty() {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+
+    let mut remaining = b.clone();
+    let mut shared = 0;
+    for pair in &a {
+        if let Some(index) = remaining.iter().position(|p| p == pair) {
+            remaining.swap_remove(index);
+            shared += 1;
+        }
+    }
+    let similarity = 2.0 * shared as f64 / (a.len() + b.len()) as f64;
+
+    (similarity * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_description_similarity() {
+        assert_eq!(
+            description_similarity(Some("UBER *TRIP"), Some("Uber trip")),
+            1.0
+        );
+        assert_eq!(
+            description_similarity(Some("Whole Foods"), Some("WHOLEFDS MKT")),
+            0.63
+        );
+        assert_eq!(description_similarity(Some("Shell"), Some("Netflix")), 0.0);
+        assert_eq!(description_similarity(None, None), 1.0);
+        assert_eq!(description_similarity(Some("Rent"), None), 0.0);
+    }
+}
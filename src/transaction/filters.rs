@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use super::models::{TransactionFilters, TransactionFiltersDetailed};
+
+/// Optional transaction filters rendered as plain `AND` predicates on `t`.
+///
+/// Only the filters that are set end up in the SQL, so each combination gets
+/// its own plan and can use the matching index, unlike `($n IS NULL OR ...)`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TransactionFilterClauses<'a> {
+    /// Inclusive lower bound on `transaction_date`
+    pub start_date: Option<DateTime<Utc>>,
+    /// Inclusive upper bound on `transaction_date`
+    pub end_date: Option<DateTime<Utc>>,
+    /// Inclusive start of a resolved calendar period
+    pub period_start: Option<DateTime<Utc>>,
+    /// Exclusive end of a resolved calendar period
+    pub period_end: Option<DateTime<Utc>>,
+    pub category_id: Option<Uuid>,
+    pub account_id: Option<Uuid>,
+    pub transaction_type: Option<&'a str>,
+    /// Transactions carrying any of these tags
+    pub tag_ids: Option<&'a [Uuid]>,
+}
+
+impl<'a> TransactionFilterClauses<'a> {
+    /// Append the set filters to a query whose transactions are aliased `t`
+    pub fn push(&self, qb: &mut QueryBuilder<'a, Postgres>) {
+        if let Some(start) = self.start_date {
+            qb.push(" AND t.transaction_date >= ").push_bind(start);
+        }
+        if let Some(end) = self.end_date {
+            qb.push(" AND t.transaction_date <= ").push_bind(end);
+        }
+        if let Some(start) = self.period_start {
+            qb.push(" AND t.transaction_date >= ").push_bind(start);
+        }
+        if let Some(end) = self.period_end {
+            qb.push(" AND t.transaction_date < ").push_bind(end);
+        }
+        if let Some(category_id) = self.category_id {
+            qb.push(" AND t.category_id = ").push_bind(category_id);
+        }
+        if let Some(account_id) = self.account_id {
+            qb.push(" AND t.account_id = ").push_bind(account_id);
+        }
+        if let Some(transaction_type) = self.transaction_type {
+            qb.push(" AND t.transaction_type = ")
+                .push_bind(transaction_type);
+        }
+        if let Some(tag_ids) = self.tag_ids {
+            qb.push(
+                " AND EXISTS (SELECT 1 FROM transaction_tags tt \
+                 WHERE tt.transaction_id = t.id AND tt.tag_id = ANY(",
+            )
+            .push_bind(tag_ids)
+            .push("))");
+        }
+    }
+}
+
+impl<'a> From<&'a TransactionFilters> for TransactionFilterClauses<'a> {
+    fn from(filters: &'a TransactionFilters) -> Self {
+        Self {
+            start_date: filters.start_date,
+            end_date: filters.end_date,
+            category_id: filters.category_id,
+            account_id: filters.account_id,
+            transaction_type: filters.transaction_type.as_deref(),
+            tag_ids: filters.tag_ids.as_deref(),
+            ..Self::default()
+        }
+    }
+}
+
+impl<'a> From<&'a TransactionFiltersDetailed> for TransactionFilterClauses<'a> {
+    fn from(filters: &'a TransactionFiltersDetailed) -> Self {
+        Self {
+            start_date: filters.start_date,
+            end_date: filters.end_date,
+            category_id: filters.category_id,
+            account_id: filters.account_id,
+            transaction_type: filters.transaction_type.as_deref(),
+            tag_ids: filters.tag_ids.as_deref(),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_only_set_filters() {
+        let clauses = TransactionFilterClauses {
+            start_date: Some(Utc::now()),
+            account_id: Some(Uuid::new_v4()),
+            transaction_type: Some("expense"),
+            ..Default::default()
+        };
+
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT 1 FROM transactions t WHERE TRUE");
+        clauses.push(&mut qb);
+
+        assert_eq!(
+            qb.sql(),
+            "SELECT 1 FROM transactions t WHERE TRUE \
+             AND t.transaction_date >= $1 AND t.account_id = $2 AND t.transaction_type = $3"
+        );
+
+        let tag_ids = [Uuid::new_v4()];
+        let clauses = TransactionFilterClauses {
+            tag_ids: Some(&tag_ids),
+            ..Default::default()
+        };
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT 1 FROM transactions t WHERE TRUE");
+        clauses.push(&mut qb);
+        assert_eq!(
+            qb.sql(),
+            "SELECT 1 FROM transactions t WHERE TRUE AND EXISTS (SELECT 1 FROM transaction_tags tt \
+             WHERE tt.transaction_id = t.id AND tt.tag_id = ANY($1))"
+        );
+
+        let mut qb = QueryBuilder::<Postgres>::new("SELECT 1 FROM transactions t WHERE TRUE");
+        TransactionFilterClauses::default().push(&mut qb);
+        assert_eq!(qb.sql(), "SELECT 1 FROM transactions t WHERE TRUE");
+    }
+}
@@ -1,17 +1,46 @@
-use actix_web::{delete, get, patch, post, web, HttpResponse};
+use actix_web::http::header;
+use actix_web::{delete, get, patch, post, put, web, HttpResponse};
 use sqlx::PgPool;
+use uuid::Uuid;
 use validator::Validate;
 
+use crate::account::service::AccountService;
+use crate::auth::scopes::Scope;
+use crate::budget::models::MonthBaseQuery;
+use crate::config::Config;
 use crate::errors::{AppError, ErrorResponse};
-use crate::extractors::AuthenticatedUser;
+use crate::extractors::{etag, AuthenticatedUser, IfMatch};
+use crate::webhook::models::events;
+use crate::webhook::service::WebhookService;
 
+use super::account_summary::{AccountSpendingSummary, AccountSummaryFilters};
+use super::bulk::{
+    BulkDeleteResponse, BulkDeleteTransactionsDto, BulkUpdateResponse, BulkUpdateTransactionsDto,
+};
+use super::classifier::CategoryClassifier;
+use super::duplicates::{DuplicatePair, DuplicateQuery, MergeTransactionDto};
+use super::matching::{MatchQuery, TransactionMatch};
 use super::models::{
-    AccountIdPath, CategoriesQueryDto, CategoryIdPath, CreateTransactionDto,
-    PaginatedDetailedTransactionResponse, PaginatedTransactionResponse, SummaryFilters,
-    TransactionFilters, TransactionFiltersDetailed, TransactionIdPath, TransactionResponse,
-    TransactionSummary, UpdateTransactionDto,
+    AccountIdPath, CategoriesQueryDto, CategoryIdPath, CategorySuggestion, CreateTransactionDto,
+    DescriptionSuggestQuery, DescriptionSuggestion, PaginatedDetailedTransactionResponse,
+    PaginatedTransactionResponse, QuickTransactionDto, QuickTransactionResponse,
+    RefundTransactionDto, SuggestCategoryQuery, SummaryFilters, TransactionFilters,
+    TransactionFiltersDetailed, TransactionIdPath, TransactionResponse, TransactionSummary,
+    UpdateTransactionDto,
 };
+use super::reimbursement::UpdateReimbursementDto;
 use super::service::TransactionService;
+use super::trash::{PaginatedTrashResponse, TrashQuery, TrashedTransactionResponse};
+use crate::jobs::models::TRANSACTION_TRASH_RETENTION;
+
+/// Accounts a transaction moves money in or out of
+fn accounts_of(transaction: &TransactionResponse) -> Vec<Uuid> {
+    transaction
+        .account_id
+        .into_iter()
+        .chain(transaction.destination_account_id)
+        .collect()
+}
 
 /// GET /transactions - List transactions with optional filters
 /// Use ?detailed=true to include full account/category info in response
@@ -22,7 +51,8 @@ use super::service::TransactionService;
     params(TransactionFiltersDetailed),
     responses(
         (status = 200, description = "Paginated list of transactions", body = PaginatedTransactionResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -32,9 +62,9 @@ pub async fn list_transactions(
     auth: AuthenticatedUser,
     query: web::Query<TransactionFiltersDetailed>,
 ) -> Result<HttpResponse, AppError> {
-    query
-        .validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
 
     if query.detailed {
         // Return detailed response with embedded account/category info
@@ -63,6 +93,7 @@ pub async fn list_transactions(
             category_id: query.category_id,
             account_id: query.account_id,
             transaction_type: query.transaction_type.clone(),
+            tag_ids: query.tag_ids.clone(),
             limit: query.limit,
             offset: query.offset,
         };
@@ -90,7 +121,8 @@ pub async fn list_transactions(
     responses(
         (status = 200, description = "List of transactions for category", body = Vec<TransactionResponse>),
         (status = 404, description = "Category not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -100,6 +132,8 @@ pub async fn get_by_category(
     auth: AuthenticatedUser,
     path: web::Path<CategoryIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
     let transactions =
         TransactionService::get_by_category(pool.get_ref(), auth.user_id, path.category_id).await?;
 
@@ -116,7 +150,8 @@ pub async fn get_by_category(
     request_body = CategoriesQueryDto,
     responses(
         (status = 200, description = "Transactions for specified categories", body = Vec<TransactionResponse>),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -126,6 +161,8 @@ pub async fn get_by_categories(
     auth: AuthenticatedUser,
     body: web::Json<CategoriesQueryDto>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
     let transactions = TransactionService::get_by_categories(
         pool.get_ref(),
         auth.user_id,
@@ -147,7 +184,8 @@ pub async fn get_by_categories(
     responses(
         (status = 200, description = "Paginated list of transactions for account", body = PaginatedTransactionResponse),
         (status = 404, description = "Account not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -158,9 +196,9 @@ pub async fn get_by_account(
     path: web::Path<AccountIdPath>,
     query: web::Query<TransactionFilters>,
 ) -> Result<HttpResponse, AppError> {
-    query
-        .validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
 
     let (transactions, total) =
         TransactionService::get_by_account(pool.get_ref(), auth.user_id, path.account_id, &query)
@@ -177,14 +215,17 @@ pub async fn get_by_account(
 }
 
 /// GET /transactions/summary - Get transaction summary with totals and category breakdown
+/// Use ?month=&year= to summarize a calendar month in the user's timezone
 #[utoipa::path(
     get,
     path = "/transactions/summary",
     tag = "Transactions",
-    params(SummaryFilters),
+    params(SummaryFilters, MonthBaseQuery),
     responses(
         (status = 200, description = "Transaction summary", body = TransactionSummary),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -193,9 +234,23 @@ pub async fn get_summary(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
     query: web::Query<SummaryFilters>,
+    month_base: MonthBaseQuery,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+    query
+        .validate_period()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let mut filters = query.into_inner();
+    filters.month = filters
+        .month
+        .map(|m| month_base.to_internal(m))
+        .transpose()?;
+
     let (total_income, total_expenses, transaction_count, by_category) =
-        TransactionService::get_summary(pool.get_ref(), auth.user_id, &query).await?;
+        TransactionService::get_summary(pool.get_ref(), auth.user_id, &filters).await?;
 
     let net_change = total_income - total_expenses;
 
@@ -208,6 +263,241 @@ pub async fn get_summary(
     }))
 }
 
+/// GET /transactions/summary/by-account - Income, expense and transfer totals per account
+/// Use ?month=&year= to summarize a calendar month in the user's timezone
+#[utoipa::path(
+    get,
+    path = "/transactions/summary/by-account",
+    tag = "Transactions",
+    params(AccountSummaryFilters, MonthBaseQuery),
+    responses(
+        (status = 200, description = "Totals per account, largest spenders first", body = Vec<AccountSpendingSummary>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/summary/by-account")]
+pub async fn get_summary_by_account(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<AccountSummaryFilters>,
+    month_base: MonthBaseQuery,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+    query
+        .validate_period()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let mut filters = query.into_inner();
+    filters.month = filters
+        .month
+        .map(|m| month_base.to_internal(m))
+        .transpose()?;
+
+    let summary =
+        TransactionService::get_summary_by_account(pool.get_ref(), auth.user_id, &filters).await?;
+
+    Ok(HttpResponse::Ok().json(summary))
+}
+
+/// GET /transactions/suggest - Suggest descriptions starting with a prefix
+#[utoipa::path(
+    get,
+    path = "/transactions/suggest",
+    tag = "Transactions",
+    params(DescriptionSuggestQuery),
+    responses(
+        (status = 200, description = "Frequent matching descriptions with typical amount and category", body = Vec<DescriptionSuggestion>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/suggest")]
+pub async fn suggest_descriptions(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<DescriptionSuggestQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let suggestions = TransactionService::suggest_descriptions(
+        pool.get_ref(),
+        auth.user_id,
+        &query.prefix,
+        query.limit,
+    )
+    .await?;
+
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+/// GET /transactions/suggest-category - Rank categories for a description
+#[utoipa::path(
+    get,
+    path = "/transactions/suggest-category",
+    tag = "Transactions",
+    params(SuggestCategoryQuery),
+    responses(
+        (status = 200, description = "Category suggestions, most likely first", body = Vec<CategorySuggestion>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/suggest-category")]
+pub async fn suggest_category(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<SuggestCategoryQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let suggestions = CategoryClassifier::suggest(pool.get_ref(), auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+/// GET /transactions/match - Existing transactions near an amount and date
+#[utoipa::path(
+    get,
+    path = "/transactions/match",
+    tag = "Transactions",
+    params(MatchQuery),
+    responses(
+        (status = 200, description = "Candidate matches, closest first", body = Vec<TransactionMatch>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/match")]
+pub async fn match_transactions(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<MatchQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let matches = TransactionService::find_matches(pool.get_ref(), auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(matches))
+}
+
+/// GET /transactions/duplicates - Likely duplicate transactions
+#[utoipa::path(
+    get,
+    path = "/transactions/duplicates",
+    tag = "Transactions",
+    params(DuplicateQuery),
+    responses(
+        (status = 200, description = "Likely duplicates, most similar first", body = Vec<DuplicatePair>),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/duplicates")]
+pub async fn find_duplicate_transactions(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    query: web::Query<DuplicateQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    query.validate()?;
+
+    let duplicates =
+        TransactionService::find_duplicates(pool.get_ref(), auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(duplicates))
+}
+
+/// GET /transactions/trash - List deleted transactions that can still be restored
+#[utoipa::path(
+    get,
+    path = "/transactions/trash",
+    tag = "Transactions",
+    params(TrashQuery),
+    responses(
+        (status = 200, description = "Paginated list of trashed transactions", body = PaginatedTrashResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/trash")]
+pub async fn list_trash(
+    pool: web::Data<PgPool>,
+    config: web::Data<Config>,
+    auth: AuthenticatedUser,
+    query: web::Query<TrashQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+    query.validate()?;
+
+    let (rows, total) =
+        TransactionService::list_trash(pool.get_ref(), auth.user_id, query.limit, query.offset)
+            .await?;
+    let retention_days = config
+        .jobs
+        .retention
+        .retention_days(&TRANSACTION_TRASH_RETENTION);
+
+    Ok(HttpResponse::Ok().json(PaginatedTrashResponse {
+        data: rows
+            .into_iter()
+            .map(|row| TrashedTransactionResponse::new(row, retention_days))
+            .collect(),
+        total,
+        limit: query.limit,
+        offset: query.offset,
+    }))
+}
+
+/// GET /transactions/pending - List imported transactions waiting for approval
+#[utoipa::path(
+    get,
+    path = "/transactions/pending",
+    tag = "Transactions",
+    responses(
+        (status = 200, description = "Pending transactions, newest first; none affect balances yet", body = Vec<TransactionResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/transactions/pending")]
+pub async fn list_pending_transactions(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let transactions = TransactionService::list_pending(pool.get_ref(), auth.user_id).await?;
+    let response: Vec<TransactionResponse> = transactions
+        .into_iter()
+        .map(TransactionResponse::from)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 /// GET /transactions/{id} - Get a specific transaction by ID
 #[utoipa::path(
     get,
@@ -217,7 +507,8 @@ pub async fn get_summary(
     responses(
         (status = 200, description = "Transaction details", body = TransactionResponse),
         (status = 404, description = "Transaction not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -227,10 +518,15 @@ pub async fn get_transaction(
     auth: AuthenticatedUser,
     path: web::Path<TransactionIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsRead)?;
+
     let transaction =
         TransactionService::get_transaction(pool.get_ref(), auth.user_id, path.id).await?;
 
-    Ok(HttpResponse::Ok().json(TransactionResponse::from(transaction)))
+    let response = TransactionResponse::from(transaction);
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
 /// POST /transactions - Create a new transaction (atomically updates account balance)
@@ -243,7 +539,9 @@ pub async fn get_transaction(
         (status = 201, description = "Transaction created", body = TransactionResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Category or account not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 409, description = "Would take an account that doesn't allow it below zero", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -253,14 +551,119 @@ pub async fn create_transaction(
     auth: AuthenticatedUser,
     body: web::Json<CreateTransactionDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
 
     let transaction =
         TransactionService::create_transaction(pool.get_ref(), auth.user_id, body.into_inner())
             .await?;
 
-    Ok(HttpResponse::Created().json(TransactionResponse::from(transaction)))
+    let response = TransactionResponse::from(transaction);
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &response,
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts_of(&response)).await;
+
+    Ok(HttpResponse::Created()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
+}
+
+/// POST /transactions/quick - Create a transaction from an amount and description
+#[utoipa::path(
+    post,
+    path = "/transactions/quick",
+    tag = "Transactions",
+    request_body = QuickTransactionDto,
+    responses(
+        (status = 201, description = "Transaction created with inferred category, account, date and type", body = QuickTransactionResponse),
+        (status = 400, description = "Validation error or no categories to choose from", body = ErrorResponse),
+        (status = 404, description = "No budget for the current month", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/quick")]
+pub async fn quick_create_transaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<QuickTransactionDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let (transaction, category) =
+        TransactionService::quick_create(pool.get_ref(), auth.user_id, body.into_inner()).await?;
+
+    let transaction = TransactionResponse::from(transaction);
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &transaction,
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts_of(&transaction))
+        .await;
+
+    Ok(HttpResponse::Created().json(QuickTransactionResponse {
+        transaction,
+        category_name: category.name,
+        category_confidence: category.confidence,
+    }))
+}
+
+/// POST /transactions/{id}/refund - Refund or reverse a transaction
+/// Creates a linked transaction of the opposite type; summaries count it against the original's type
+#[utoipa::path(
+    post,
+    path = "/transactions/{id}/refund",
+    tag = "Transactions",
+    params(TransactionIdPath),
+    request_body = RefundTransactionDto,
+    responses(
+        (status = 201, description = "Refund created", body = TransactionResponse),
+        (status = 400, description = "Validation error, transfer, or amount above what remains refundable", body = ErrorResponse),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 409, description = "Transaction already fully refunded", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/{id}/refund")]
+pub async fn refund_transaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+    body: Option<web::Json<RefundTransactionDto>>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let body = body.map(web::Json::into_inner).unwrap_or_default();
+    body.validate()?;
+
+    let refund =
+        TransactionService::refund_transaction(pool.get_ref(), auth.user_id, path.id, body).await?;
+
+    let response = TransactionResponse::from(refund);
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &response,
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts_of(&response)).await;
+
+    Ok(HttpResponse::Created().json(response))
 }
 
 /// PATCH /transactions/{id} - Update a transaction (handles balance adjustments atomically)
@@ -274,7 +677,10 @@ pub async fn create_transaction(
         (status = 200, description = "Transaction updated", body = TransactionResponse),
         (status = 400, description = "Validation error", body = ErrorResponse),
         (status = 404, description = "Transaction not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 409, description = "Would take an account that doesn't allow it below zero", body = ErrorResponse),
+        (status = 412, description = "If-Match doesn't name the current version", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -282,35 +688,273 @@ pub async fn create_transaction(
 pub async fn update_transaction(
     pool: web::Data<PgPool>,
     auth: AuthenticatedUser,
+    if_match: IfMatch,
     path: web::Path<TransactionIdPath>,
     body: web::Json<UpdateTransactionDto>,
 ) -> Result<HttpResponse, AppError> {
-    body.validate()
-        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
     body.validate_amount()
         .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
+    // The update may move the transaction off these accounts
+    let mut accounts =
+        TransactionService::account_ids(pool.get_ref(), auth.user_id, &[path.id]).await?;
     let transaction = TransactionService::update_transaction(
         pool.get_ref(),
         auth.user_id,
         path.id,
         body.into_inner(),
+        &if_match,
     )
     .await?;
 
-    Ok(HttpResponse::Ok().json(TransactionResponse::from(transaction)))
+    let response = TransactionResponse::from(transaction);
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_UPDATED,
+        &response,
+    )
+    .await;
+    accounts.extend(accounts_of(&response));
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts).await;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, etag(&response.updated_at)))
+        .json(response))
 }
 
-/// DELETE /transactions/{id} - Delete a transaction (atomically restores account balance)
+/// PUT /transactions/{id}/reimbursement - Flag an expense as reimbursable and track its status
+#[utoipa::path(
+    put,
+    path = "/transactions/{id}/reimbursement",
+    tag = "Transactions",
+    params(TransactionIdPath),
+    request_body = UpdateReimbursementDto,
+    responses(
+        (status = 200, description = "Reimbursement updated", body = TransactionResponse),
+        (status = 400, description = "Validation error, not an expense, or status change not allowed", body = ErrorResponse),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[put("/transactions/{id}/reimbursement")]
+pub async fn update_reimbursement(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+    body: web::Json<UpdateReimbursementDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let transaction = TransactionService::update_reimbursement(
+        pool.get_ref(),
+        auth.user_id,
+        path.id,
+        body.into_inner(),
+    )
+    .await?;
+
+    let response = TransactionResponse::from(transaction);
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_UPDATED,
+        &response,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /transactions/{id}/restore - Bring a transaction back from the trash (atomically re-applies account balance)
+#[utoipa::path(
+    post,
+    path = "/transactions/{id}/restore",
+    tag = "Transactions",
+    params(TransactionIdPath),
+    responses(
+        (status = 200, description = "Transaction restored", body = TransactionResponse),
+        (status = 400, description = "Refunded transaction is not restored or has no refundable amount left", body = ErrorResponse),
+        (status = 404, description = "Transaction not found in trash", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/{id}/restore")]
+pub async fn restore_transaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let transaction =
+        TransactionService::restore_transaction(pool.get_ref(), auth.user_id, path.id).await?;
+    let response = TransactionResponse::from(transaction);
+
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_CREATED,
+        &response,
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts_of(&response)).await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /transactions/{id}/approve - Post a pending transaction (atomically applies its balance effects)
+#[utoipa::path(
+    post,
+    path = "/transactions/{id}/approve",
+    tag = "Transactions",
+    params(TransactionIdPath),
+    responses(
+        (status = 200, description = "Transaction posted", body = TransactionResponse),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 409, description = "Transaction is not pending, or would take an account that doesn't allow it below zero", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/{id}/approve")]
+pub async fn approve_transaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let transaction =
+        TransactionService::approve_transaction(pool.get_ref(), auth.user_id, path.id).await?;
+    let response = TransactionResponse::from(transaction);
+
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_UPDATED,
+        &response,
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts_of(&response)).await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /transactions/{id}/reject - Turn down a pending transaction
+#[utoipa::path(
+    post,
+    path = "/transactions/{id}/reject",
+    tag = "Transactions",
+    params(TransactionIdPath),
+    responses(
+        (status = 200, description = "Transaction rejected; it never affects a balance", body = TransactionResponse),
+        (status = 404, description = "Transaction not found", body = ErrorResponse),
+        (status = 409, description = "Transaction is not pending", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/{id}/reject")]
+pub async fn reject_transaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let transaction =
+        TransactionService::reject_transaction(pool.get_ref(), auth.user_id, path.id).await?;
+    let response = TransactionResponse::from(transaction);
+
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_UPDATED,
+        &response,
+    )
+    .await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /transactions/{id}/merge - Merge a duplicate into this transaction (atomically restores account balance)
+#[utoipa::path(
+    post,
+    path = "/transactions/{id}/merge",
+    tag = "Transactions",
+    params(TransactionIdPath),
+    request_body = MergeTransactionDto,
+    responses(
+        (status = 200, description = "Duplicate moved to the trash; the kept transaction", body = TransactionResponse),
+        (status = 400, description = "Transactions differ in accounts, amount or type", body = ErrorResponse),
+        (status = 404, description = "Transaction or duplicate not found", body = ErrorResponse),
+        (status = 409, description = "Duplicate has refunds", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/{id}/merge")]
+pub async fn merge_transaction(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<TransactionIdPath>,
+    body: web::Json<MergeTransactionDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let transaction = TransactionService::merge_transaction(
+        pool.get_ref(),
+        auth.user_id,
+        path.id,
+        body.duplicate_id,
+    )
+    .await?;
+    let response = TransactionResponse::from(transaction);
+
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_DELETED,
+        &serde_json::json!({ "id": body.duplicate_id }),
+    )
+    .await;
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_UPDATED,
+        &response,
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts_of(&response)).await;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// DELETE /transactions/{id} - Move a transaction to the trash (atomically restores account balance)
 #[utoipa::path(
     delete,
     path = "/transactions/{id}",
     tag = "Transactions",
     params(TransactionIdPath),
     responses(
-        (status = 204, description = "Transaction deleted"),
+        (status = 204, description = "Transaction moved to the trash"),
         (status = 404, description = "Transaction not found", body = ErrorResponse),
-        (status = 401, description = "Unauthorized", body = ErrorResponse)
+        (status = 409, description = "Transaction has refunds", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -320,7 +964,154 @@ pub async fn delete_transaction(
     auth: AuthenticatedUser,
     path: web::Path<TransactionIdPath>,
 ) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    let accounts =
+        TransactionService::account_ids(pool.get_ref(), auth.user_id, &[path.id]).await?;
     TransactionService::delete_transaction(pool.get_ref(), auth.user_id, path.id).await?;
 
+    WebhookService::publish(
+        pool.get_ref(),
+        auth.user_id,
+        events::TRANSACTION_DELETED,
+        &serde_json::json!({ "id": path.id }),
+    )
+    .await;
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts).await;
+
     Ok(HttpResponse::NoContent().finish())
 }
+
+/// POST /transactions/bulk-delete - Move several transactions to the trash in one atomic operation
+#[utoipa::path(
+    post,
+    path = "/transactions/bulk-delete",
+    tag = "Transactions",
+    request_body = BulkDeleteTransactionsDto,
+    responses(
+        (status = 200, description = "Transactions moved to the trash", body = BulkDeleteResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Some transactions not found; nothing was deleted", body = ErrorResponse),
+        (status = 409, description = "A transaction has refunds that are not deleted with it", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/bulk-delete")]
+pub async fn bulk_delete_transactions(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<BulkDeleteTransactionsDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+
+    let accounts =
+        TransactionService::account_ids(pool.get_ref(), auth.user_id, &body.transaction_ids)
+            .await?;
+    let deleted =
+        TransactionService::bulk_delete(pool.get_ref(), auth.user_id, &body.transaction_ids)
+            .await?;
+
+    for id in &deleted {
+        WebhookService::publish(
+            pool.get_ref(),
+            auth.user_id,
+            events::TRANSACTION_DELETED,
+            &serde_json::json!({ "id": id }),
+        )
+        .await;
+    }
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts).await;
+
+    Ok(HttpResponse::Ok().json(BulkDeleteResponse {
+        deleted: deleted.len(),
+    }))
+}
+
+/// POST /transactions/bulk-update - Move several transactions to another category or account in one atomic operation
+#[utoipa::path(
+    post,
+    path = "/transactions/bulk-update",
+    tag = "Transactions",
+    request_body = BulkUpdateTransactionsDto,
+    responses(
+        (status = 200, description = "Transactions updated", body = BulkUpdateResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Some transactions, the category or the account not found; nothing was changed", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/transactions/bulk-update")]
+pub async fn bulk_update_transactions(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<BulkUpdateTransactionsDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::TransactionsWrite)?;
+
+    body.validate()?;
+    body.validate_changes()
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+    let mut accounts =
+        TransactionService::account_ids(pool.get_ref(), auth.user_id, &body.transaction_ids)
+            .await?;
+    let transactions = TransactionService::bulk_update(pool.get_ref(), auth.user_id, &body).await?;
+    let data: Vec<TransactionResponse> = transactions
+        .into_iter()
+        .map(TransactionResponse::from)
+        .collect();
+
+    for response in &data {
+        WebhookService::publish(
+            pool.get_ref(),
+            auth.user_id,
+            events::TRANSACTION_UPDATED,
+            response,
+        )
+        .await;
+        accounts.extend(accounts_of(response));
+    }
+    AccountService::publish_balances(pool.get_ref(), auth.user_id, &accounts).await;
+
+    Ok(HttpResponse::Ok().json(BulkUpdateResponse {
+        updated: data.len(),
+        data,
+    }))
+}
+
+/// Register the transaction routes. `/transactions/summary`, `/trash` and the
+/// other fixed paths must come before `/transactions/{id}`, which would
+/// otherwise match them.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_transactions)
+        .service(get_by_category)
+        .service(get_by_categories)
+        .service(get_by_account)
+        .service(get_summary)
+        .service(get_summary_by_account)
+        .service(suggest_category)
+        .service(suggest_descriptions)
+        .service(match_transactions)
+        .service(find_duplicate_transactions)
+        .service(list_trash)
+        .service(list_pending_transactions)
+        .service(get_transaction)
+        .service(quick_create_transaction)
+        .service(create_transaction)
+        .service(bulk_delete_transactions)
+        .service(bulk_update_transactions)
+        .service(refund_transaction)
+        .service(update_transaction)
+        .service(update_reimbursement)
+        .service(restore_transaction)
+        .service(approve_transaction)
+        .service(reject_transaction)
+        .service(merge_transaction)
+        .service(delete_transaction);
+}
@@ -0,0 +1,176 @@
+use chrono::NaiveDate;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use super::models::{Transaction, TransactionResponse, TransactionType};
+
+/// Candidates scanned before scoring; the amount and date filters keep this
+/// far above what a real match query returns
+pub const MATCH_SCAN_LIMIT: i64 = 500;
+
+fn validate_positive(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount <= Decimal::ZERO {
+        return Err(ValidationError::new("amount_must_be_positive"));
+    }
+    Ok(())
+}
+
+fn validate_tolerance(tolerance: &Decimal) -> Result<(), ValidationError> {
+    if *tolerance < Decimal::ZERO {
+        return Err(ValidationError::new("tolerance_must_not_be_negative"));
+    }
+    Ok(())
+}
+
+fn default_window() -> i64 {
+    3
+}
+
+fn default_match_limit() -> i64 {
+    10
+}
+
+/// Query parameters for finding existing transactions near an amount and date
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchQuery {
+    /// Amount to match (positive)
+    #[validate(custom(function = "validate_positive", message = "amount must be positive"))]
+    #[param(example = 54.20)]
+    pub amount: Decimal,
+
+    /// Largest amount difference still matched (defaults to 0: exact amounts)
+    #[validate(custom(
+        function = "validate_tolerance",
+        message = "tolerance must not be negative"
+    ))]
+    #[serde(default)]
+    #[param(example = 2.50)]
+    pub tolerance: Decimal,
+
+    /// Date to match, in the user's timezone
+    #[param(example = "2026-10-15")]
+    pub date: NaiveDate,
+
+    /// Days either side of `date` still matched (0-30, default 3)
+    #[validate(range(min = 0, max = 30, message = "window must be between 0 and 30 days"))]
+    #[serde(default = "default_window")]
+    #[param(example = 3)]
+    pub window: i64,
+
+    /// Only match transactions from or to this account
+    pub account_id: Option<Uuid>,
+
+    /// Only match transactions of this type
+    pub transaction_type: Option<TransactionType>,
+
+    /// Maximum number of candidates (1-50, default 10)
+    #[validate(range(min = 1, max = 50))]
+    #[serde(default = "default_match_limit")]
+    #[param(example = 10)]
+    pub limit: i64,
+}
+
+/// A transaction in the match window with its date in the owner's timezone
+#[derive(Debug, FromRow)]
+pub struct MatchCandidateRow {
+    #[sqlx(flatten)]
+    pub transaction: Transaction,
+    pub local_date: NaiveDate,
+}
+
+/// An existing transaction that may be the same as the one being matched
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMatch {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    /// Candidate amount minus the queried amount
+    #[schema(example = -0.80)]
+    pub amount_difference: Decimal,
+    /// Days between the candidate and the queried date
+    #[schema(example = 1)]
+    pub days_apart: i64,
+    /// Closeness between 0 and 1; exact amount on the same day scores 1
+    #[schema(example = 0.71)]
+    pub score: f64,
+}
+
+/// Closeness of a candidate: amount and date each count for half, falling
+/// linearly to zero at the edge of the tolerance and the window
+pub fn match_score(
+    amount_difference: Decimal,
+    tolerance: Decimal,
+    days_apart: i64,
+    window: i64,
+) -> f64 {
+    let amount_part = if tolerance > Decimal::ZERO {
+        (amount_difference.abs() / tolerance)
+            .to_f64()
+            .unwrap_or(1.0)
+    } else {
+        0.0
+    };
+    let date_part = if window > 0 {
+        days_apart.abs() as f64 / window as f64
+    } else {
+        0.0
+    };
+    let score = 1.0 - 0.5 * amount_part.min(1.0) - 0.5 * date_part.min(1.0);
+
+    (score * 100.0).round() / 100.0
+}
+
+impl TransactionMatch {
+    /// Score candidates against the query, best first
+    pub fn rank(rows: Vec<MatchCandidateRow>, query: &MatchQuery) -> Vec<Self> {
+        let mut matches: Vec<Self> = rows
+            .into_iter()
+            .map(|row| {
+                let amount_difference = row.transaction.amount - query.amount;
+                let days_apart = (row.local_date - query.date).num_days();
+                Self {
+                    score: match_score(
+                        amount_difference,
+                        query.tolerance,
+                        days_apart,
+                        query.window,
+                    ),
+                    amount_difference,
+                    days_apart,
+                    transaction: TransactionResponse::from(row.transaction),
+                }
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.amount_difference.abs().cmp(&b.amount_difference.abs()))
+                .then_with(|| a.days_apart.abs().cmp(&b.days_apart.abs()))
+        });
+        matches.truncate(query.limit as usize);
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_score() {
+        let tolerance = Decimal::from(2);
+        assert_eq!(match_score(Decimal::ZERO, tolerance, 0, 3), 1.0);
+        assert_eq!(match_score(Decimal::from(-1), tolerance, 0, 3), 0.75);
+        assert_eq!(match_score(Decimal::ZERO, tolerance, 3, 3), 0.5);
+        assert_eq!(match_score(Decimal::from(2), tolerance, -3, 3), 0.0);
+        // Exact amount and same-day queries don't penalize what they allow
+        assert_eq!(match_score(Decimal::ZERO, Decimal::ZERO, 0, 0), 1.0);
+    }
+}
@@ -1,5 +1,13 @@
+pub mod account_summary;
+pub mod bulk;
+pub mod classifier;
+pub mod duplicates;
+pub mod filters;
 pub mod handlers;
+pub mod matching;
 pub mod models;
+pub mod reimbursement;
 pub mod service;
+pub mod trash;
 
 pub use handlers::*;
@@ -6,6 +6,11 @@ use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use validator::{Validate, ValidationError};
 
+use crate::crypto::{decrypt_optional, encrypt_optional};
+use crate::errors::AppError;
+use crate::tag::models::TagIds;
+use crate::template::models::double_option;
+
 /// Transaction type enum
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "lowercase")]
@@ -36,6 +41,62 @@ impl TransactionType {
             _ => None,
         }
     }
+
+    /// Type of a refund reversing this one (transfers can't be refunded)
+    pub fn reversal(&self) -> Option<Self> {
+        match self {
+            TransactionType::Expense => Some(TransactionType::Income),
+            TransactionType::Income => Some(TransactionType::Expense),
+            TransactionType::Transfer => None,
+        }
+    }
+}
+
+/// Where a transaction stands in review. Imported transactions may start
+/// pending; only posted ones touch balances, spending and reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionStatus {
+    /// Waiting for approval; no balance effects yet
+    Pending,
+    /// Counted everywhere
+    Posted,
+    /// Turned down during review; kept out of everything
+    Rejected,
+}
+
+impl TransactionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransactionStatus::Pending => "pending",
+            TransactionStatus::Posted => "posted",
+            TransactionStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(TransactionStatus::Pending),
+            "posted" => Some(TransactionStatus::Posted),
+            "rejected" => Some(TransactionStatus::Rejected),
+            _ => None,
+        }
+    }
+
+    /// Only pending transactions move, to posted or rejected; both are final
+    pub fn can_transition_to(&self, next: TransactionStatus) -> bool {
+        matches!(
+            (self, next),
+            (
+                TransactionStatus::Pending,
+                TransactionStatus::Posted | TransactionStatus::Rejected
+            )
+        )
+    }
+}
+
+fn posted_status() -> String {
+    TransactionStatus::Posted.as_str().to_string()
 }
 
 /// Validate that amount is positive
@@ -57,6 +118,34 @@ pub struct Transaction {
     pub transaction_date: DateTime<Utc>,
     pub description: Option<String>,
     pub transaction_type: String,
+    pub reversal_of_id: Option<Uuid>,
+    /// Absent from audit snapshots taken before reimbursements existed
+    #[serde(default)]
+    pub reimbursable: bool,
+    pub reimbursement_status: Option<String>,
+    pub reimbursement_payer: Option<String>,
+    pub reimbursed_by_id: Option<Uuid>,
+    pub tax_category: Option<String>,
+    /// Amount as entered, in `original_currency` (only for foreign-currency transactions)
+    #[serde(default)]
+    pub original_amount: Option<Decimal>,
+    #[serde(default)]
+    pub original_currency: Option<String>,
+    /// Rate applied to convert `original_amount` into `amount`
+    #[serde(default)]
+    pub exchange_rate: Option<Decimal>,
+    /// Absent from audit snapshots taken before the review queue existed
+    #[serde(default = "posted_status")]
+    pub status: String,
+    /// Absent from audit snapshots taken before payees existed
+    #[serde(default)]
+    pub payee_id: Option<Uuid>,
+    /// Charged to the source account on top of `amount` (transfers only)
+    #[serde(default)]
+    pub fee_amount: Option<Decimal>,
+    /// Credited to the destination account instead of `amount` (transfers only)
+    #[serde(default)]
+    pub destination_amount: Option<Decimal>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -65,6 +154,22 @@ impl Transaction {
     pub fn get_type(&self) -> TransactionType {
         TransactionType::parse(&self.transaction_type).unwrap_or_default()
     }
+
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        self.reimbursement_payer = decrypt_optional(self.reimbursement_payer)?;
+        Ok(self)
+    }
+
+    /// Re-encrypt sensitive columns of a decrypted row (for audit snapshots)
+    pub fn encrypted(self) -> Result<Self, AppError> {
+        Ok(Self {
+            description: encrypt_optional(self.description.as_deref())?,
+            reimbursement_payer: encrypt_optional(self.reimbursement_payer.as_deref())?,
+            ..self
+        })
+    }
 }
 
 /// Transaction information returned in responses
@@ -90,6 +195,49 @@ pub struct TransactionResponse {
     /// Transaction type (expense, income, transfer)
     #[schema(example = "expense")]
     pub transaction_type: String,
+    /// Transaction this one refunds or reverses (only present for refunds)
+    pub reversal_of_id: Option<Uuid>,
+    /// Whether someone owes this expense back
+    pub reimbursable: bool,
+    /// Reimbursement status (pending, submitted, settled, rejected; only for reimbursables)
+    #[schema(example = "pending")]
+    pub reimbursement_status: Option<String>,
+    /// Who owes the expense back
+    #[schema(example = "Acme Corp")]
+    pub reimbursement_payer: Option<String>,
+    /// Income transaction that paid the expense back
+    pub reimbursed_by_id: Option<Uuid>,
+    /// Tax category overriding the category's (omitted when not set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "Medical expenses")]
+    pub tax_category: Option<String>,
+    /// Amount as entered, before conversion into the account currency
+    /// (omitted for transactions entered in the account currency)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 46.00)]
+    pub original_amount: Option<Decimal>,
+    /// Currency the amount was entered in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = "EUR")]
+    pub original_currency: Option<String>,
+    /// Rate applied to convert the original amount into `amount`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 1.0870)]
+    pub exchange_rate: Option<Decimal>,
+    /// Review state; only posted transactions affect balances
+    #[schema(value_type = TransactionStatus)]
+    pub status: String,
+    /// Payee the transaction was made with
+    pub payee_id: Option<Uuid>,
+    /// Fee charged to the source account on top of `amount`
+    /// (omitted for transfers without a fee and for other types)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 2.50)]
+    pub fee_amount: Option<Decimal>,
+    /// Amount credited to the destination account when it differs from `amount`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(example = 46.00)]
+    pub destination_amount: Option<Decimal>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
@@ -107,6 +255,19 @@ impl From<Transaction> for TransactionResponse {
             transaction_date: t.transaction_date,
             description: t.description,
             transaction_type: t.transaction_type,
+            reversal_of_id: t.reversal_of_id,
+            reimbursable: t.reimbursable,
+            reimbursement_status: t.reimbursement_status,
+            reimbursement_payer: t.reimbursement_payer,
+            reimbursed_by_id: t.reimbursed_by_id,
+            tax_category: t.tax_category,
+            original_amount: t.original_amount,
+            original_currency: t.original_currency,
+            exchange_rate: t.exchange_rate.map(|rate| rate.normalize()),
+            status: t.status,
+            payee_id: t.payee_id,
+            fee_amount: t.fee_amount,
+            destination_amount: t.destination_amount,
             created_at: t.created_at,
             updated_at: t.updated_at,
         }
@@ -145,9 +306,115 @@ pub struct CreateTransactionDto {
     /// Transaction type (defaults to expense)
     #[serde(default)]
     pub transaction_type: TransactionType,
+
+    /// Whether someone owes this expense back (expenses only)
+    #[serde(default)]
+    pub reimbursable: bool,
+
+    /// Who owes the expense back (max 100 chars, only for reimbursables)
+    #[validate(length(max = 100, message = "Payer cannot exceed 100 characters"))]
+    #[schema(example = "Acme Corp")]
+    pub reimbursement_payer: Option<String>,
+
+    /// Tax category for this transaction alone, overriding the category's
+    #[validate(length(max = 50, message = "Tax category cannot exceed 50 characters"))]
+    #[schema(example = "Medical expenses")]
+    pub tax_category: Option<String>,
+
+    /// Currency `amount` is given in, when it differs from the account's;
+    /// the amount is converted into the account currency when posted
+    #[validate(length(equal = 3, message = "Currency code must be 3 characters"))]
+    #[schema(example = "EUR")]
+    pub currency: Option<String>,
+
+    /// Rate from `currency` into the account currency, overriding the stored rate
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Exchange rate must be positive"
+    ))]
+    #[schema(example = 1.0870)]
+    pub exchange_rate: Option<Decimal>,
+
+    /// Hold the transaction for review: it has no balance effects until
+    /// approved (for imports)
+    #[serde(default)]
+    pub pending: bool,
+
+    /// Payee the transaction was made with; filled in by a matching
+    /// categorization rule when omitted
+    pub payee_id: Option<Uuid>,
+
+    /// Transaction this one refunds: income linked to an expense (or the
+    /// reverse) in the same category nets against it in spending and reports
+    pub linked_transaction_id: Option<Uuid>,
+
+    /// Fee charged to the source account on top of `amount` (transfers only)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Fee amount must be positive"
+    ))]
+    #[schema(example = 2.50)]
+    pub fee_amount: Option<Decimal>,
+
+    /// Amount credited to the destination account, e.g. after conversion
+    /// into its currency (transfers only; defaults to `amount`)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Destination amount must be positive"
+    ))]
+    #[schema(example = 46.00)]
+    pub destination_amount: Option<Decimal>,
 }
 
 impl CreateTransactionDto {
+    /// Validate that an exchange rate only comes with a currency
+    pub fn validate_currency(&self) -> Result<(), ValidationError> {
+        if self.exchange_rate.is_some() && self.currency.is_none() {
+            return Err(ValidationError::new(
+                "exchange_rate is only allowed together with currency",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate that only expenses are reimbursable, and only reimbursables have a payer
+    pub fn validate_reimbursement(&self) -> Result<(), ValidationError> {
+        if self.reimbursable && self.transaction_type != TransactionType::Expense {
+            return Err(ValidationError::new(
+                "only expenses can be marked reimbursable",
+            ));
+        }
+        if !self.reimbursable && self.reimbursement_payer.is_some() {
+            return Err(ValidationError::new(
+                "reimbursement_payer is only allowed for reimbursable expenses",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate that only plain, posted incomes and expenses are linked as refunds
+    pub fn validate_link(&self) -> Result<(), ValidationError> {
+        if self.linked_transaction_id.is_none() {
+            return Ok(());
+        }
+        if self.transaction_type == TransactionType::Transfer {
+            return Err(ValidationError::new(
+                "transfers cannot be linked as refunds",
+            ));
+        }
+        if self.reimbursable {
+            return Err(ValidationError::new(
+                "a linked refund cannot be reimbursable",
+            ));
+        }
+        if self.pending {
+            return Err(ValidationError::new(
+                "a linked refund cannot be held for review",
+            ));
+        }
+        Ok(())
+    }
+
     /// Validate transfer-specific constraints
     pub fn validate_transfer(&self) -> Result<(), ValidationError> {
         // destination_account_id is only allowed for transfers
@@ -168,10 +435,30 @@ impl CreateTransactionDto {
                 }
             }
         }
-        Ok(())
+        validate_transfer_amounts(
+            self.transaction_type,
+            self.fee_amount,
+            self.destination_amount,
+        )
     }
 }
 
+/// Validate that only transfers carry a fee or a separate destination amount
+fn validate_transfer_amounts(
+    transaction_type: TransactionType,
+    fee_amount: Option<Decimal>,
+    destination_amount: Option<Decimal>,
+) -> Result<(), ValidationError> {
+    if transaction_type != TransactionType::Transfer
+        && (fee_amount.is_some() || destination_amount.is_some())
+    {
+        return Err(ValidationError::new(
+            "fee_amount and destination_amount are only allowed for transfer transactions",
+        ));
+    }
+    Ok(())
+}
+
 /// Request body for updating a transaction (PATCH - all fields optional)
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -199,23 +486,52 @@ pub struct UpdateTransactionDto {
 
     /// Transaction type
     pub transaction_type: Option<TransactionType>,
+
+    /// Tax category override; an empty string falls back to the category's
+    #[validate(length(max = 50, message = "Tax category cannot exceed 50 characters"))]
+    #[schema(example = "Medical expenses")]
+    pub tax_category: Option<String>,
+
+    /// Payee ID (use null to remove)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Uuid>)]
+    pub payee_id: Option<Option<Uuid>>,
+
+    /// Fee charged to the source account of a transfer (use null to remove)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Decimal>, example = 2.50)]
+    pub fee_amount: Option<Option<Decimal>>,
+
+    /// Amount credited to the destination account of a transfer
+    /// (use null to credit `amount`)
+    #[serde(default, deserialize_with = "double_option")]
+    #[schema(value_type = Option<Decimal>, example = 46.00)]
+    pub destination_amount: Option<Option<Decimal>>,
 }
 
 impl UpdateTransactionDto {
-    /// Validate amount if provided
+    /// Validate amounts if provided
     pub fn validate_amount(&self) -> Result<(), ValidationError> {
-        if let Some(amount) = &self.amount {
+        let amounts = [
+            self.amount,
+            self.fee_amount.flatten(),
+            self.destination_amount.flatten(),
+        ];
+        for amount in amounts.iter().flatten() {
             validate_positive_amount(amount)?;
         }
         Ok(())
     }
 
-    /// Validate transfer-specific constraints (requires knowing the final transaction type)
+    /// Validate transfer-specific constraints (requires knowing the final
+    /// transaction type and the final fee and destination amount)
     pub fn validate_transfer(
         &self,
         final_type: TransactionType,
         final_account_id: Option<Uuid>,
         final_destination_id: Option<Uuid>,
+        final_fee_amount: Option<Decimal>,
+        final_destination_amount: Option<Decimal>,
     ) -> Result<(), ValidationError> {
         // destination_account_id is only allowed for transfers
         if final_type != TransactionType::Transfer && final_destination_id.is_some() {
@@ -233,10 +549,31 @@ impl UpdateTransactionDto {
                 }
             }
         }
-        Ok(())
+        validate_transfer_amounts(final_type, final_fee_amount, final_destination_amount)
     }
 }
 
+/// Request body for refunding a transaction
+#[derive(Debug, Default, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RefundTransactionDto {
+    /// Amount to refund (defaults to whatever has not been refunded yet)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 20.00)]
+    pub amount: Option<Decimal>,
+
+    /// Date of the refund (defaults to now)
+    pub transaction_date: Option<DateTime<Utc>>,
+
+    /// Optional description (max 200 chars, defaults to the original's)
+    #[validate(length(max = 200, message = "Description cannot exceed 200 characters"))]
+    #[schema(example = "Returned damaged item")]
+    pub description: Option<String>,
+}
+
 /// Query parameters for listing transactions
 #[derive(Debug, Deserialize, Validate, IntoParams)]
 #[serde(rename_all = "camelCase")]
@@ -252,6 +589,9 @@ pub struct TransactionFilters {
     /// Filter by type (expense, income, transfer)
     #[param(example = "expense")]
     pub transaction_type: Option<String>,
+    /// Filter by tags, comma-separated; matches transactions with any of them
+    #[param(value_type = Option<String>, example = "3fa85f64-5717-4562-b3fc-2c963f66afa6")]
+    pub tag_ids: Option<TagIds>,
 
     /// Maximum results (1-100)
     #[validate(range(min = 1, max = 100))]
@@ -325,7 +665,7 @@ pub struct EmbeddedAccountInfo {
     /// Account name
     #[schema(example = "My Checking")]
     pub name: String,
-    /// Account type (checking, savings, credit)
+    /// Account type (checking, savings, credit, investment, loan)
     #[serde(rename = "type")]
     #[schema(example = "checking")]
     pub account_type: String,
@@ -374,12 +714,36 @@ pub struct TransactionDetailResponse {
     /// Optional description
     #[schema(example = "Weekly groceries")]
     pub description: Option<String>,
+    /// Transaction this one refunds or reverses (only present for refunds)
+    pub reversal_of_id: Option<Uuid>,
+    /// Whether someone owes this expense back
+    pub reimbursable: bool,
+    /// Reimbursement status (pending, submitted, settled, rejected; only for reimbursables)
+    #[schema(example = "pending")]
+    pub reimbursement_status: Option<String>,
     /// Creation timestamp
     pub created_at: DateTime<Utc>,
     /// Last update timestamp
     pub updated_at: DateTime<Utc>,
 }
 
+/// Page row carrying the total match count from `COUNT(*) OVER()`
+#[derive(Debug, FromRow)]
+pub struct CountedRow<T> {
+    #[sqlx(flatten)]
+    pub row: T,
+    pub total_count: i64,
+}
+
+impl<T> CountedRow<T> {
+    /// Split a page into its rows and the total match count.
+    /// An empty page carries no count, so the total is `None`.
+    pub fn split(rows: Vec<Self>) -> (Vec<T>, Option<i64>) {
+        let total = rows.first().map(|r| r.total_count);
+        (rows.into_iter().map(|r| r.row).collect(), total)
+    }
+}
+
 /// Database row for detailed transaction query with JOINs
 #[derive(Debug, FromRow)]
 pub struct TransactionDetailRow {
@@ -388,6 +752,9 @@ pub struct TransactionDetailRow {
     pub transaction_type: String,
     pub transaction_date: DateTime<Utc>,
     pub description: Option<String>,
+    pub reversal_of_id: Option<Uuid>,
+    pub reimbursable: bool,
+    pub reimbursement_status: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     // Category fields
@@ -409,6 +776,14 @@ pub struct TransactionDetailRow {
 }
 
 impl TransactionDetailRow {
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        self.account_name = decrypt_optional(self.account_name)?;
+        self.dest_account_name = decrypt_optional(self.dest_account_name)?;
+        Ok(self)
+    }
+
     pub fn into_response(self) -> TransactionDetailResponse {
         let category = EmbeddedCategoryInfo {
             id: self.category_id,
@@ -463,6 +838,9 @@ impl TransactionDetailRow {
             transaction_type: self.transaction_type,
             transaction_date: self.transaction_date,
             description: self.description,
+            reversal_of_id: self.reversal_of_id,
+            reimbursable: self.reimbursable,
+            reimbursement_status: self.reimbursement_status,
             created_at: self.created_at,
             updated_at: self.updated_at,
         }
@@ -558,6 +936,28 @@ pub struct SummaryFilters {
     pub end_date: Option<DateTime<Utc>>,
     /// Filter by account
     pub account_id: Option<Uuid>,
+    /// Filter by tags, comma-separated; matches transactions with any of them
+    #[param(value_type = Option<String>, example = "3fa85f64-5717-4562-b3fc-2c963f66afa6")]
+    pub tag_ids: Option<TagIds>,
+    /// Calendar month in the user's timezone (0-11, or 1-12 with monthBase=1; requires year)
+    #[param(example = 0)]
+    pub month: Option<i16>,
+    /// Calendar year for the month filter
+    #[validate(range(min = 2000, max = 2100, message = "Year must be 2000-2100"))]
+    #[param(example = 2025)]
+    pub year: Option<i16>,
+}
+
+impl SummaryFilters {
+    /// Month and year must be supplied together
+    pub fn validate_period(&self) -> Result<(), ValidationError> {
+        if self.month.is_some() != self.year.is_some() {
+            return Err(ValidationError::new(
+                "month and year must be provided together",
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// Query parameters for listing transactions (with detailed option)
@@ -575,6 +975,9 @@ pub struct TransactionFiltersDetailed {
     /// Filter by type (expense, income, transfer)
     #[param(example = "expense")]
     pub transaction_type: Option<String>,
+    /// Filter by tags, comma-separated; matches transactions with any of them
+    #[param(value_type = Option<String>, example = "3fa85f64-5717-4562-b3fc-2c963f66afa6")]
+    pub tag_ids: Option<TagIds>,
 
     /// Maximum results (1-100)
     #[validate(range(min = 1, max = 100))]
@@ -593,3 +996,278 @@ pub struct TransactionFiltersDetailed {
     #[param(example = false)]
     pub detailed: bool,
 }
+
+fn default_suggestion_limit() -> i64 {
+    3
+}
+
+/// Query parameters for category suggestions
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestCategoryQuery {
+    /// Transaction description to classify
+    #[validate(length(min = 1, max = 255, message = "description must be 1-255 characters"))]
+    #[param(example = "Whole Foods Market")]
+    pub description: String,
+
+    /// Transaction amount (optional, improves ranking)
+    #[param(example = 54.20)]
+    pub amount: Option<Decimal>,
+
+    /// Budget whose categories are suggested (defaults to the most recent budget)
+    pub budget_id: Option<Uuid>,
+
+    /// Maximum number of suggestions (1-10)
+    #[validate(range(min = 1, max = 10))]
+    #[serde(default = "default_suggestion_limit")]
+    #[param(example = 3)]
+    pub limit: i64,
+}
+
+/// A ranked category suggestion
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CategorySuggestion {
+    /// Category UUID in the target budget
+    pub category_id: Uuid,
+    /// Category name
+    #[schema(example = "Groceries")]
+    pub name: String,
+    /// Model confidence between 0 and 1
+    #[schema(example = 0.87)]
+    pub confidence: f64,
+}
+
+/// Request body for quick-adding a transaction with inferred defaults
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickTransactionDto {
+    /// Transaction amount (must be positive)
+    #[validate(custom(
+        function = "validate_positive_amount",
+        message = "Amount must be positive"
+    ))]
+    #[schema(example = 4.50)]
+    pub amount: Decimal,
+
+    /// Description used to infer the category (1-200 chars)
+    #[validate(length(min = 1, max = 200, message = "Description must be 1-200 characters"))]
+    #[schema(example = "Coffee at Blue Bottle")]
+    pub description: String,
+}
+
+/// A quick-added transaction and the category chosen for it
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickTransactionResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    /// Name of the inferred category
+    #[schema(example = "Dining")]
+    pub category_name: String,
+    /// Model confidence for the inferred category between 0 and 1
+    #[schema(example = 0.82)]
+    pub category_confidence: f64,
+}
+
+fn default_description_limit() -> i64 {
+    5
+}
+
+/// Query parameters for description autocomplete
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptionSuggestQuery {
+    /// Beginning of the description typed so far
+    #[validate(length(min = 1, max = 100, message = "prefix must be 1-100 characters"))]
+    #[param(example = "cof")]
+    pub prefix: String,
+
+    /// Maximum number of suggestions (1-20)
+    #[validate(range(min = 1, max = 20))]
+    #[serde(default = "default_description_limit")]
+    #[param(example = 5)]
+    pub limit: i64,
+}
+
+/// A past transaction's description with the details autocomplete needs
+#[derive(Debug, FromRow)]
+pub struct DescriptionUsageRow {
+    pub description: Option<String>,
+    pub amount: Decimal,
+    pub category_name: String,
+    pub transaction_date: DateTime<Utc>,
+}
+
+impl DescriptionUsageRow {
+    /// Decrypt the description after loading from the database
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        Ok(self)
+    }
+}
+
+/// A frequently used description matching the typed prefix
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct DescriptionSuggestion {
+    /// Description as entered
+    #[schema(example = "Coffee")]
+    pub description: String,
+    /// Number of transactions with this description
+    #[schema(example = 18)]
+    pub usage_count: i64,
+    /// Median amount
+    #[schema(example = 4.50)]
+    pub typical_amount: Decimal,
+    /// Category most often used with this description
+    #[schema(example = "Dining")]
+    pub category_name: String,
+    pub last_used_at: DateTime<Utc>,
+}
+
+impl DescriptionSuggestion {
+    /// Group decrypted rows by case-insensitive description and keep those
+    /// starting with the prefix, most frequent first. Mirrors the SQL used
+    /// when descriptions are stored in plaintext.
+    pub fn aggregate(rows: Vec<DescriptionUsageRow>, prefix: &str, limit: usize) -> Vec<Self> {
+        use std::collections::HashMap;
+
+        let prefix = prefix.trim().to_lowercase();
+        let mut groups: HashMap<String, Vec<DescriptionUsageRow>> = HashMap::new();
+        for row in rows {
+            let Some(key) = row.description.as_deref().map(|d| d.trim().to_lowercase()) else {
+                continue;
+            };
+            if key.starts_with(&prefix) {
+                groups.entry(key).or_default().push(row);
+            }
+        }
+
+        let mut suggestions: Vec<Self> = groups
+            .into_values()
+            .map(|mut rows| {
+                rows.sort_by_key(|r| r.amount);
+                let mid = rows.len() / 2;
+                let typical_amount = if rows.len() % 2 == 0 {
+                    ((rows[mid - 1].amount + rows[mid].amount) / Decimal::from(2)).round_dp(2)
+                } else {
+                    rows[mid].amount
+                };
+
+                let mut categories: HashMap<&str, i64> = HashMap::new();
+                for row in &rows {
+                    *categories.entry(row.category_name.as_str()).or_insert(0) += 1;
+                }
+                let category_name = categories
+                    .into_iter()
+                    .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)))
+                    .map(|(name, _)| name.to_string())
+                    .unwrap_or_default();
+
+                let latest = rows
+                    .iter()
+                    .max_by_key(|r| r.transaction_date)
+                    .expect("groups are never empty");
+
+                Self {
+                    description: latest
+                        .description
+                        .clone()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string(),
+                    usage_count: rows.len() as i64,
+                    typical_amount,
+                    category_name,
+                    last_used_at: latest.transaction_date,
+                }
+            })
+            .collect();
+
+        suggestions.sort_by(|a, b| {
+            b.usage_count
+                .cmp(&a.usage_count)
+                .then_with(|| b.last_used_at.cmp(&a.last_used_at))
+        });
+        suggestions.truncate(limit);
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn usage(description: &str, amount: i64, category: &str, day: u32) -> DescriptionUsageRow {
+        DescriptionUsageRow {
+            description: Some(description.to_string()),
+            amount: Decimal::from(amount),
+            category_name: category.to_string(),
+            transaction_date: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_aggregate_descriptions_by_prefix() {
+        let rows = vec![
+            usage("Coffee", 4, "Dining", 1),
+            usage("coffee", 6, "Dining", 2),
+            usage("Coffee", 5, "Groceries", 3),
+            usage("Coffee", 9, "Dining", 4),
+            usage("Cosmetics", 30, "Shopping", 5),
+            usage("Fuel", 50, "Car", 6),
+        ];
+
+        let suggestions = DescriptionSuggestion::aggregate(rows, "CO", 5);
+
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].description, "Coffee");
+        assert_eq!(suggestions[0].usage_count, 4);
+        assert_eq!(suggestions[0].typical_amount, Decimal::new(550, 2));
+        assert_eq!(suggestions[0].category_name, "Dining");
+        assert_eq!(suggestions[1].description, "Cosmetics");
+    }
+
+    #[test]
+    fn test_counted_row_split() {
+        let rows = vec![
+            CountedRow {
+                row: "a",
+                total_count: 7,
+            },
+            CountedRow {
+                row: "b",
+                total_count: 7,
+            },
+        ];
+        assert_eq!(CountedRow::split(rows), (vec!["a", "b"], Some(7)));
+        assert_eq!(CountedRow::<&str>::split(Vec::new()), (Vec::new(), None));
+    }
+
+    #[test]
+    fn test_only_transfers_carry_fee_and_destination_amount() {
+        let fee = Some(Decimal::new(250, 2));
+        assert!(validate_transfer_amounts(TransactionType::Transfer, fee, fee).is_ok());
+        assert!(validate_transfer_amounts(TransactionType::Expense, None, None).is_ok());
+        assert!(validate_transfer_amounts(TransactionType::Expense, fee, None).is_err());
+        assert!(validate_transfer_amounts(TransactionType::Income, None, fee).is_err());
+    }
+
+    #[test]
+    fn test_transaction_status_transitions() {
+        use TransactionStatus::*;
+
+        assert!(Pending.can_transition_to(Posted));
+        assert!(Pending.can_transition_to(Rejected));
+        for final_status in [Posted, Rejected] {
+            for next in [Pending, Posted, Rejected] {
+                assert!(!final_status.can_transition_to(next));
+            }
+        }
+        assert!(!Pending.can_transition_to(Pending));
+        assert_eq!(TransactionStatus::parse("posted"), Some(Posted));
+        assert_eq!(TransactionStatus::parse("void"), None);
+    }
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+
+/// Where a reimbursable expense is in being paid back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ReimbursementStatus {
+    /// Waiting to be claimed
+    Pending,
+    /// Claimed from the payer, waiting for the money
+    Submitted,
+    /// Paid back
+    Settled,
+    /// The payer declined; no longer outstanding
+    Rejected,
+}
+
+impl ReimbursementStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReimbursementStatus::Pending => "pending",
+            ReimbursementStatus::Submitted => "submitted",
+            ReimbursementStatus::Settled => "settled",
+            ReimbursementStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(ReimbursementStatus::Pending),
+            "submitted" => Some(ReimbursementStatus::Submitted),
+            "settled" => Some(ReimbursementStatus::Settled),
+            "rejected" => Some(ReimbursementStatus::Rejected),
+            _ => None,
+        }
+    }
+
+    /// Allowed moves: forward through the lifecycle, or back to pending to
+    /// reopen a settled or rejected item
+    pub fn can_transition_to(&self, next: ReimbursementStatus) -> bool {
+        use ReimbursementStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Submitted | Settled | Rejected)
+                | (Submitted, Pending | Settled | Rejected)
+                | (Settled, Pending)
+                | (Rejected, Pending)
+        ) || *self == next
+    }
+}
+
+/// Request body for flagging a transaction as reimbursable and moving it
+/// through the reimbursement lifecycle
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateReimbursementDto {
+    /// Whether the expense is owed back; false clears payer and status
+    pub reimbursable: bool,
+
+    /// Who owes the money (max 100 chars); omitted keeps the current payer
+    #[validate(length(max = 100, message = "Payer cannot exceed 100 characters"))]
+    #[schema(example = "Acme Corp")]
+    pub payer: Option<String>,
+
+    /// New status (defaults to the current one, or pending when newly flagged)
+    pub status: Option<ReimbursementStatus>,
+}
+
+/// An outstanding reimbursable expense, with the amount still owed after refunds
+#[derive(Debug, Clone, FromRow)]
+pub struct OutstandingReimbursement {
+    pub id: Uuid,
+    pub amount: Decimal,
+    pub payer: Option<String>,
+    pub status: String,
+    pub transaction_date: DateTime<Utc>,
+}
+
+impl OutstandingReimbursement {
+    /// Decrypt sensitive columns after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.payer = decrypt_optional(self.payer)?;
+        Ok(self)
+    }
+
+    /// Payer name normalized for grouping and matching
+    pub fn payer_key(&self) -> Option<String> {
+        self.payer
+            .as_deref()
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+    }
+}
+
+/// Pick the outstanding items an incoming income pays back.
+///
+/// When the income's description names payers, only their items are
+/// considered: all of them if they add up to the income, otherwise the oldest
+/// single item of exactly that amount. Without a named payer, an item is only
+/// matched when it is the one item of exactly that amount. Anything ambiguous
+/// is left for the user to settle by hand.
+pub fn match_reimbursement(
+    amount: Decimal,
+    description: Option<&str>,
+    outstanding: &[OutstandingReimbursement],
+) -> Vec<Uuid> {
+    let description = description.unwrap_or_default().to_lowercase();
+    let mut named: Vec<&OutstandingReimbursement> = outstanding
+        .iter()
+        .filter(|item| {
+            item.payer_key()
+                .is_some_and(|payer| description.contains(&payer))
+        })
+        .collect();
+    named.sort_by_key(|item| item.transaction_date);
+
+    if !named.is_empty() {
+        if named.iter().map(|item| item.amount).sum::<Decimal>() == amount {
+            return named.iter().map(|item| item.id).collect();
+        }
+        return named
+            .iter()
+            .find(|item| item.amount == amount)
+            .map(|item| vec![item.id])
+            .unwrap_or_default();
+    }
+
+    let mut exact = outstanding.iter().filter(|item| item.amount == amount);
+    match (exact.next(), exact.next()) {
+        (Some(item), None) => vec![item.id],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn item(amount: i64, payer: Option<&str>, day: u32) -> OutstandingReimbursement {
+        OutstandingReimbursement {
+            id: Uuid::new_v4(),
+            amount: Decimal::from(amount),
+            payer: payer.map(str::to_string),
+            status: "pending".to_string(),
+            transaction_date: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_match_reimbursement_and_transitions() {
+        let hotel = item(200, Some("Acme Corp"), 2);
+        let taxi = item(40, Some("acme corp"), 3);
+        let dinner = item(40, Some("Sam"), 4);
+        let items = vec![hotel.clone(), taxi.clone(), dinner.clone()];
+
+        // Named payer: everything owed when the totals agree
+        let matched =
+            match_reimbursement(Decimal::from(240), Some("ACME CORP expense payout"), &items);
+        assert_eq!(matched, vec![hotel.id, taxi.id]);
+
+        // Named payer: a single item of the exact amount
+        let matched = match_reimbursement(Decimal::from(40), Some("Acme Corp"), &items);
+        assert_eq!(matched, vec![taxi.id]);
+
+        // No payer named and two items of that amount: ambiguous
+        assert!(match_reimbursement(Decimal::from(40), Some("Transfer"), &items).is_empty());
+        assert_eq!(
+            match_reimbursement(Decimal::from(200), None, &items),
+            vec![hotel.id]
+        );
+
+        use ReimbursementStatus::*;
+        assert!(Pending.can_transition_to(Submitted));
+        assert!(Settled.can_transition_to(Pending));
+        assert!(!Settled.can_transition_to(Rejected));
+        assert!(!Rejected.can_transition_to(Settled));
+    }
+}
@@ -1,12 +1,56 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde_json::json;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+use super::account_summary::{AccountSpendingSummary, AccountSummaryFilters, AccountSummaryRow};
+use super::bulk::{deletion_order, unique_ids, BulkUpdateTransactionsDto};
+use super::classifier::CategoryClassifier;
+use super::duplicates::{DuplicatePair, DuplicatePairRow, DuplicateQuery, DUPLICATE_SCAN_LIMIT};
+use super::filters::TransactionFilterClauses;
+use super::matching::{MatchCandidateRow, MatchQuery, TransactionMatch, MATCH_SCAN_LIMIT};
 use super::models::{
-    CategorySummaryRow, CreateTransactionDto, SummaryFilters, Transaction, TransactionDetailRow,
-    TransactionFilters, TransactionFiltersDetailed, TransactionType, UpdateTransactionDto,
+    CategorySuggestion, CategorySummaryRow, CountedRow, CreateTransactionDto,
+    DescriptionSuggestion, DescriptionUsageRow, QuickTransactionDto, RefundTransactionDto,
+    SuggestCategoryQuery, SummaryFilters, Transaction, TransactionDetailRow, TransactionFilters,
+    TransactionFiltersDetailed, TransactionStatus, TransactionType, UpdateTransactionDto,
+};
+use super::reimbursement::{
+    match_reimbursement, OutstandingReimbursement, ReimbursementStatus, UpdateReimbursementDto,
 };
-use crate::errors::AppError;
+use super::trash::TrashedTransactionRow;
+use crate::account::redenomination::convert;
+use crate::audit::models::{actions, entities};
+use crate::audit::service::AuditService;
+use crate::budget::service::BudgetService;
+use crate::cache::ResponseCache;
+use crate::category::models::normalize_tax_category;
+use crate::crypto::{self, encrypt_optional};
+use crate::currency::service::CurrencyService;
+use crate::errors::{AppError, ErrorCode};
+use crate::extractors::IfMatch;
+use crate::funding::models::{plan_distribution, AppliedFunding, FundingRule};
+use crate::funding::service::FundingService;
+use crate::notification::service::NotificationService;
+use crate::payee::service::PayeeService;
+use crate::summary::service::SummaryService;
+
+/// Number of recent transactions scanned when descriptions must be matched
+/// after decryption
+pub const DESCRIPTION_SCAN_WINDOW: i64 = 2000;
+
+/// Decimal places kept for the rate of a foreign-currency transaction
+const EXCHANGE_RATE_DECIMAL_PLACES: u32 = 10;
+
+/// What was entered for a transaction in a currency other than its account's
+struct OriginalAmount {
+    amount: Decimal,
+    currency: String,
+    rate: Decimal,
+}
 
 /// Service layer for transaction business logic.
 /// CRITICAL: All balance updates must be atomic to prevent data inconsistency.
@@ -19,6 +63,110 @@ enum BalanceOperation {
     Reverse,
 }
 
+/// What a transaction moves between balances: a transfer's source also pays
+/// the fee, and its destination may receive a different amount
+#[derive(Debug, Clone, Copy)]
+struct BalanceAmounts {
+    amount: Decimal,
+    fee: Decimal,
+    destination_amount: Decimal,
+}
+
+impl BalanceAmounts {
+    /// The same amount on both sides, without a fee
+    fn plain(amount: Decimal) -> Self {
+        Self {
+            amount,
+            fee: Decimal::ZERO,
+            destination_amount: amount,
+        }
+    }
+
+    fn of(transaction: &Transaction) -> Self {
+        Self {
+            amount: transaction.amount,
+            fee: transaction.fee_amount.unwrap_or_default(),
+            destination_amount: transaction.destination_amount.unwrap_or(transaction.amount),
+        }
+    }
+}
+
+/// What undoing a transaction change left behind, so the caller can announce it
+/// once the undo is committed
+#[derive(Debug)]
+pub struct RestoredTransaction {
+    pub transaction_id: Uuid,
+    /// The transaction as restored, or `None` if undo removed it
+    pub transaction: Option<Transaction>,
+    /// Whether the transaction was brought back rather than changed in place
+    pub recreated: bool,
+    /// Funding-rule transfers removed along with an income
+    pub removed_transfers: Vec<Uuid>,
+    /// Every account whose balance moved
+    pub accounts: Vec<Uuid>,
+}
+
+/// Balances of the overdraft-protected accounts an operation touches, taken
+/// before it moves any money. Checking or savings accounts that don't allow a
+/// negative balance may only end the operation below zero if it didn't lower
+/// them, so edits that leave an already negative account no worse still go
+/// through.
+struct OverdraftGuard {
+    before: Vec<(Uuid, Decimal)>,
+}
+
+impl OverdraftGuard {
+    /// Lock the protected accounts among `account_ids` and note their balances
+    async fn watch(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        account_ids: impl IntoIterator<Item = Option<Uuid>>,
+    ) -> Result<Self, AppError> {
+        let account_ids: Vec<Uuid> = account_ids.into_iter().flatten().collect();
+        let before = sqlx::query_as::<_, (Uuid, Decimal)>(
+            r#"
+            SELECT id, balance
+            FROM accounts
+            WHERE id = ANY($1)
+              AND NOT allow_negative_balance
+              AND account_type IN ('checking', 'savings')
+            ORDER BY id
+            FOR UPDATE
+            "#,
+        )
+        .bind(&account_ids)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(Self { before })
+    }
+
+    /// Fail with the shortfall if a watched account ended below zero and
+    /// lower than it started
+    async fn check(self, tx: &mut sqlx::Transaction<'_, sqlx::Postgres>) -> Result<(), AppError> {
+        for (account_id, before) in self.before {
+            let (balance, currency) = sqlx::query_as::<_, (Decimal, String)>(
+                "SELECT balance, currency FROM accounts WHERE id = $1",
+            )
+            .bind(account_id)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            if balance < Decimal::ZERO && balance < before {
+                let shortfall = -balance;
+                let currency = currency.trim().to_string();
+                return Err(AppError::Detailed(
+                    ErrorCode::InsufficientFunds,
+                    format!("The account is short {shortfall} {currency} for this transaction"),
+                    json!({ "accountId": account_id, "shortfall": shortfall, "currency": currency }),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 impl TransactionService {
     /// Create a transaction with atomic balance update.
     /// CRITICAL: This operation MUST be atomic.
@@ -26,11 +174,17 @@ impl TransactionService {
     pub async fn create_transaction(
         pool: &PgPool,
         user_id: Uuid,
-        dto: CreateTransactionDto,
+        mut dto: CreateTransactionDto,
     ) -> Result<Transaction, AppError> {
-        // Validate transfer constraints
+        // Validate transfer and reimbursement constraints
         dto.validate_transfer()
             .map_err(|e| AppError::ValidationError(e.to_string()))?;
+        dto.validate_reimbursement()
+            .map_err(|e| AppError::ValidationError(e.to_string()))?;
+        dto.validate_currency()
+            .map_err(|e| AppError::ValidationError(e.to_string()))?;
+        dto.validate_link()
+            .map_err(|e| AppError::ValidationError(e.to_string()))?;
 
         // Start a database transaction
         let mut tx = pool
@@ -44,7 +198,7 @@ impl TransactionService {
             SELECT EXISTS(
                 SELECT 1 FROM categories c
                 JOIN budgets b ON c.budget_id = b.id
-                WHERE c.id = $1 AND b.owner_id = $2
+                WHERE c.id = $1 AND budget_access(b.id, $2, TRUE)
             )
             "#,
         )
@@ -106,51 +260,379 @@ impl TransactionService {
             }
         }
 
-        // 4. Insert the transaction
+        // 4. Verify the payee, then let the user's rules file the transaction
+        if let Some(payee_id) = dto.payee_id {
+            PayeeService::verify(&mut *tx, payee_id, user_id).await?;
+        }
+        // (a linked refund stays next to what it refunds)
+        if dto.linked_transaction_id.is_none() {
+            let rules = PayeeService::rule_set(&mut tx, user_id).await?;
+            (dto.category_id, dto.payee_id) = PayeeService::categorize(
+                &mut tx,
+                &rules,
+                dto.category_id,
+                dto.payee_id,
+                dto.description.as_deref(),
+            )
+            .await?;
+        }
+
+        // 5. Amounts entered in another currency are posted in the account's
+        let (amount, original) = Self::posted_amount(pool, &mut tx, user_id, &dto).await?;
+
+        // 6. A linked refund must reverse the original within what remains
+        // refundable, in its category, so spending nets out
+        if let Some(original_id) = dto.linked_transaction_id {
+            let (original, refund_type, remaining) =
+                Self::lock_refundable(&mut tx, user_id, original_id).await?;
+            if dto.transaction_type != refund_type {
+                return Err(AppError::ValidationError(format!(
+                    "A refund of an {} must be {}",
+                    original.transaction_type,
+                    refund_type.as_str()
+                )));
+            }
+            if dto.category_id != original.category_id {
+                return Err(AppError::ValidationError(
+                    "A refund must be in the same category as the transaction it refunds"
+                        .to_string(),
+                ));
+            }
+            if amount > remaining {
+                return Err(AppError::Coded(
+                    ErrorCode::RefundExceedsRemaining,
+                    format!(
+                        "Refund cannot exceed the remaining refundable amount of {}",
+                        remaining
+                    ),
+                ));
+            }
+        }
+
+        // 7. Insert the transaction
         let transaction_type_str = dto.transaction_type.as_str();
 
         let transaction = sqlx::query_as::<_, Transaction>(
             r#"
             INSERT INTO transactions
-                (category_id, account_id, destination_account_id, amount, transaction_date, description, transaction_type)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
+                (category_id, account_id, destination_account_id, amount, transaction_date, description, transaction_type,
+                 reimbursable, reimbursement_status, reimbursement_payer, tax_category,
+                 original_amount, original_currency, exchange_rate, status, payee_id, reversal_of_id,
+                 fee_amount, destination_amount)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, CASE WHEN $8 THEN 'pending' END, $9, $10,
+                    $11, $12, $13, CASE WHEN $14 THEN 'pending' ELSE 'posted' END, $15, $16, $17, $18)
             RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
-                      transaction_type, created_at, updated_at
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
             "#,
         )
         .bind(dto.category_id)
         .bind(dto.account_id)
         .bind(dto.destination_account_id)
-        .bind(dto.amount)
+        .bind(amount)
         .bind(dto.transaction_date)
-        .bind(&dto.description)
+        .bind(encrypt_optional(dto.description.as_deref())?)
         .bind(transaction_type_str)
+        .bind(dto.reimbursable)
+        .bind(encrypt_optional(dto.reimbursement_payer.as_deref())?)
+        .bind(normalize_tax_category(dto.tax_category.as_deref()))
+        .bind(original.as_ref().map(|o| o.amount))
+        .bind(original.as_ref().map(|o| o.currency.as_str()))
+        .bind(original.as_ref().map(|o| o.rate))
+        .bind(dto.pending)
+        .bind(dto.payee_id)
+        .bind(dto.linked_transaction_id)
+        .bind(dto.fee_amount)
+        .bind(dto.destination_amount)
         .fetch_one(&mut *tx)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        // 5. Update account balances
+        // 8. Pending transactions wait for approval before touching anything
+        if !dto.pending {
+            Self::post_in_tx(&mut tx, user_id, &transaction, dto.description.as_deref()).await?;
+        }
+
+        // 9. Commit the transaction
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        transaction.decrypted()
+    }
+
+    /// Everything posting a transaction sets off: balance effects, the
+    /// category suggestion model, reimbursement settling, the audit entry,
+    /// income funding rules and spending alerts. `description` is the
+    /// decrypted description of the encrypted `transaction` row.
+    async fn post_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        transaction: &Transaction,
+        description: Option<&str>,
+    ) -> Result<(), AppError> {
+        let transaction_type = transaction.get_type();
+
+        // 1. Update account balances
+        let guard = OverdraftGuard::watch(
+            tx,
+            [transaction.account_id, transaction.destination_account_id],
+        )
+        .await?;
         Self::apply_transaction_balance_effects(
-            &mut tx,
-            dto.account_id,
-            dto.destination_account_id,
-            dto.amount,
-            dto.transaction_type,
+            tx,
+            transaction.account_id,
+            transaction.destination_account_id,
+            BalanceAmounts::of(transaction),
+            transaction_type,
             BalanceOperation::Apply,
         )
         .await?;
 
-        // 6. Commit the transaction
+        // 2. Update the category suggestion model
+        CategoryClassifier::learn(
+            tx,
+            user_id,
+            transaction.category_id,
+            description,
+            transaction.amount,
+            1,
+        )
+        .await?;
+
+        // 3. Income may pay back outstanding reimbursable expenses; a refund
+        // only gives back what was spent
+        let is_refund = transaction.reversal_of_id.is_some();
+        if transaction_type == TransactionType::Income && !is_refund {
+            Self::settle_reimbursements(
+                tx,
+                user_id,
+                transaction.id,
+                transaction.amount,
+                transaction.transaction_date,
+                description,
+            )
+            .await?;
+        }
+
+        // 4. Record the change so it can be undone
+        AuditService::record_change(
+            tx,
+            user_id,
+            actions::TRANSACTION_CREATE,
+            entities::TRANSACTION,
+            transaction.id,
+            None,
+            Some(transaction),
+        )
+        .await?;
+
+        // 5. Income is split across categories and accounts by the funding rules
+        if transaction_type == TransactionType::Income && !is_refund {
+            Self::distribute_income(tx, user_id, transaction).await?;
+        }
+        guard.check(tx).await?;
+
+        // 6. Notify about spending alert thresholds the category reached
+        NotificationService::evaluate_thresholds(tx, &[transaction.category_id]).await?;
+
+        Ok(())
+    }
+
+    /// Pending transactions awaiting review, newest first
+    pub async fn list_pending(pool: &PgPool, user_id: Uuid) -> Result<Vec<Transaction>, AppError> {
+        let rows = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE budget_access(b.id, $1, FALSE) AND t.deleted_at IS NULL AND t.status = 'pending'
+            ORDER BY t.transaction_date DESC, t.id
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        rows.into_iter().map(Transaction::decrypted).collect()
+    }
+
+    /// Approve a pending transaction, applying its balance effects now.
+    /// CRITICAL: This operation MUST be atomic.
+    pub async fn approve_transaction(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<Transaction, AppError> {
+        Self::transition(pool, user_id, transaction_id, TransactionStatus::Posted).await
+    }
+
+    /// Reject a pending transaction; it never touches a balance
+    pub async fn reject_transaction(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<Transaction, AppError> {
+        Self::transition(pool, user_id, transaction_id, TransactionStatus::Rejected).await
+    }
+
+    /// Move a transaction to `next` if its current status allows it, posting
+    /// it when it becomes posted
+    async fn transition(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        next: TransactionStatus,
+    ) -> Result<Transaction, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 1. Fetch and lock the transaction row
+        let current = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT t.status
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
+
+        // 2. Only pending transactions can be reviewed
+        let current = TransactionStatus::parse(&current).unwrap_or(TransactionStatus::Posted);
+        if !current.can_transition_to(next) {
+            return Err(AppError::Coded(
+                ErrorCode::TransactionNotPending,
+                format!("Transaction is already {}", current.as_str()),
+            ));
+        }
+
+        // 3. Move it to the new status
+        let transaction = sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions SET status = $2, updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(next.as_str())
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 4. Approval applies everything creating it would have
+        if next == TransactionStatus::Posted {
+            let description = transaction.clone().decrypted()?.description;
+            Self::post_in_tx(&mut tx, user_id, &transaction, description.as_deref()).await?;
+        }
+
         tx.commit()
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        transaction.decrypted()
+    }
+
+    /// The amount to post in the account currency (the user's default currency
+    /// without an account), and what was entered when `dto.currency` differs.
+    /// Uses the given rate, or the stored rate for the transaction date.
+    async fn posted_amount(
+        pool: &PgPool,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        dto: &CreateTransactionDto,
+    ) -> Result<(Decimal, Option<OriginalAmount>), AppError> {
+        let Some(currency) = dto.currency.as_deref().map(str::to_uppercase) else {
+            return Ok((dto.amount, None));
+        };
+
+        let (account_currency, decimal_places) = sqlx::query_as::<_, (String, i16)>(
+            r#"
+            SELECT cur.code, cur.decimal_places
+            FROM users u
+            LEFT JOIN accounts a ON a.id = $2 AND a.owner_id = u.id
+            JOIN currencies cur ON cur.code = COALESCE(a.currency, u.default_currency)
+            WHERE u.id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(dto.account_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if currency == account_currency.trim() {
+            return Ok((dto.amount, None));
+        }
+        if !CurrencyService::validate_currency(pool, &currency).await? {
+            return Err(AppError::ValidationError(format!(
+                "Currency '{currency}' is not supported"
+            )));
+        }
+
+        let rate = match dto.exchange_rate {
+            Some(rate) => rate,
+            None => CurrencyService::conversion_rate(
+                pool,
+                &currency,
+                &account_currency,
+                dto.transaction_date.date_naive(),
+            )
+            .await?
+            .map(|(rate, _)| rate)
+            .ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "No exchange rate from {currency} to {}; provide exchangeRate",
+                    account_currency.trim()
+                ))
+            })?,
+        };
+        // Stored at the column's precision, so the rate shown reproduces the amount
+        let rate = rate.round_dp(EXCHANGE_RATE_DECIMAL_PLACES).normalize();
+
+        let amount = convert(dto.amount, rate, decimal_places.max(0) as u32);
+        if amount <= Decimal::ZERO {
+            return Err(AppError::ValidationError(
+                "Amount is too small to convert into the account currency".to_string(),
+            ));
+        }
 
-        Ok(transaction)
+        Ok((
+            amount,
+            Some(OriginalAmount {
+                amount: dto.amount,
+                currency,
+                rate,
+            }),
+        ))
     }
 
-    /// Delete a transaction with atomic balance restoration.
+    /// Move a transaction to the trash with atomic balance restoration.
     /// CRITICAL: Must restore account balance before deleting.
     /// For transfers: restores both source and destination account balances.
+    /// Trashed transactions are hidden everywhere until restored or purged.
     pub async fn delete_transaction(
         pool: &PgPool,
         user_id: Uuid,
@@ -161,231 +643,1561 @@ impl TransactionService {
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
+        Self::delete_in_tx(&mut tx, user_id, transaction_id).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(())
+    }
+
+    /// Move one transaction to the trash inside the caller's database transaction
+    async fn delete_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<(), AppError> {
         // 1. Fetch and lock the transaction row
         let transaction = sqlx::query_as::<_, Transaction>(
             r#"
             SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
-                   t.description, t.transaction_type, t.created_at, t.updated_at
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
             FROM transactions t
             JOIN categories c ON t.category_id = c.id
             JOIN budgets b ON c.budget_id = b.id
-            WHERE t.id = $1 AND b.owner_id = $2
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL AND t.status = 'posted'
             FOR UPDATE OF t
             "#,
         )
         .bind(transaction_id)
         .bind(user_id)
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
         .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
 
+        // Refunds only make sense next to what they refund
+        if Self::refunded_total(tx, transaction_id, None).await? > Decimal::ZERO {
+            return Err(AppError::Coded(
+                ErrorCode::TransactionHasRefunds,
+                "Transaction has refunds; delete them first".to_string(),
+            ));
+        }
+
         // 2. Restore account balances (reverse the effects)
+        let guard = OverdraftGuard::watch(
+            tx,
+            [transaction.account_id, transaction.destination_account_id],
+        )
+        .await?;
         Self::apply_transaction_balance_effects_with_existence_check(
-            &mut tx,
+            tx,
             transaction.account_id,
             transaction.destination_account_id,
-            transaction.amount,
+            BalanceAmounts::of(&transaction),
             transaction.get_type(),
             BalanceOperation::Reverse,
         )
         .await?;
+        guard.check(tx).await?;
 
-        // 3. Delete the transaction
-        sqlx::query("DELETE FROM transactions WHERE id = $1")
+        // 3. Move the transaction to the trash; the retention job purges it later
+        sqlx::query("UPDATE transactions SET deleted_at = NOW() WHERE id = $1")
             .bind(transaction_id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        tx.commit()
+        // Items this income paid back are outstanding again, as if it were gone
+        sqlx::query("UPDATE transactions SET reimbursed_by_id = NULL WHERE reimbursed_by_id = $1")
+            .bind(transaction_id)
+            .execute(&mut **tx)
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
+        // 4. Record the change so it can be undone
+        AuditService::record_change(
+            tx,
+            user_id,
+            actions::TRANSACTION_DELETE,
+            entities::TRANSACTION,
+            transaction_id,
+            Some(&transaction),
+            None,
+        )
+        .await?;
+
+        // 5. Remove it from the category suggestion model
+        let transaction = transaction.decrypted()?;
+        CategoryClassifier::learn(
+            tx,
+            user_id,
+            transaction.category_id,
+            transaction.description.as_deref(),
+            transaction.amount,
+            -1,
+        )
+        .await?;
+
         Ok(())
     }
 
-    /// Update a transaction with atomic balance adjustments.
-    /// COMPLEX SCENARIOS:
-    /// 1. Amount change only: adjust difference
-    /// 2. Account change: reverse old account, apply to new account
-    /// 3. Type change: reverse old effect, apply new effect
-    /// 4. Transfer destination change: reverse old destination, apply to new destination
-    pub async fn update_transaction(
+    /// Move several transactions to the trash at once. Every balance reversal
+    /// happens in one database transaction: either all are deleted or none,
+    /// and a single undo brings them all back.
+    pub async fn bulk_delete(
         pool: &PgPool,
         user_id: Uuid,
-        transaction_id: Uuid,
-        dto: UpdateTransactionDto,
-    ) -> Result<Transaction, AppError> {
+        transaction_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, AppError> {
         let mut tx = pool
             .begin()
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        // 1. Fetch and lock the existing transaction
-        let old_transaction = sqlx::query_as::<_, Transaction>(
-            r#"
-            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
-                   t.description, t.transaction_type, t.created_at, t.updated_at
-            FROM transactions t
-            JOIN categories c ON t.category_id = c.id
-            JOIN budgets b ON c.budget_id = b.id
-            WHERE t.id = $1 AND b.owner_id = $2
-            FOR UPDATE OF t
-            "#,
+        let rows = Self::lock_for_bulk(&mut tx, user_id, transaction_ids).await?;
+        let ids = deletion_order(rows);
+        for &id in &ids {
+            Self::delete_in_tx(&mut tx, user_id, id).await?;
+        }
+        AuditService::record_group(
+            &mut tx,
+            user_id,
+            actions::TRANSACTION_BULK_DELETE,
+            entities::TRANSACTION,
+            &ids,
         )
-        .bind(transaction_id)
-        .bind(user_id)
-        .fetch_optional(&mut *tx)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?
-        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
+        .await?;
 
-        // 2. Validate new category if changing
-        let new_category_id = dto.category_id.unwrap_or(old_transaction.category_id);
-        if dto.category_id.is_some() {
-            let category_valid = sqlx::query_scalar::<_, bool>(
-                r#"
-                SELECT EXISTS(
-                    SELECT 1 FROM categories c
-                    JOIN budgets b ON c.budget_id = b.id
-                    WHERE c.id = $1 AND b.owner_id = $2
-                )
-                "#,
-            )
-            .bind(new_category_id)
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(ids)
+    }
+
+    /// Apply the same category and/or account change to several transactions.
+    /// Every balance adjustment happens in one database transaction, and a
+    /// single undo reverses them all.
+    pub async fn bulk_update(
+        pool: &PgPool,
+        user_id: Uuid,
+        dto: &BulkUpdateTransactionsDto,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let rows = Self::lock_for_bulk(&mut tx, user_id, &dto.transaction_ids).await?;
+        let mut updated = Vec::with_capacity(rows.len());
+        for (id, _) in rows {
+            updated.push(
+                Self::update_in_tx(&mut tx, user_id, id, dto.to_update(), &IfMatch::default())
+                    .await?,
+            );
+        }
+        let ids: Vec<Uuid> = updated.iter().map(|transaction| transaction.id).collect();
+        AuditService::record_group(
+            &mut tx,
+            user_id,
+            actions::TRANSACTION_BULK_UPDATE,
+            entities::TRANSACTION,
+            &ids,
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        updated.into_iter().map(Transaction::decrypted).collect()
+    }
+
+    /// Lock the user's live transactions among `transaction_ids` in id order,
+    /// failing if any is missing. Returns `(id, reversal_of_id)` pairs.
+    async fn lock_for_bulk(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        transaction_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, Option<Uuid>)>, AppError> {
+        let ids = unique_ids(transaction_ids);
+        let rows = sqlx::query_as::<_, (Uuid, Option<Uuid>)>(
+            r#"
+            SELECT t.id, t.reversal_of_id
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = ANY($1) AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL AND t.status = 'posted'
+            ORDER BY t.id
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(&ids)
+        .bind(user_id)
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if rows.len() != ids.len() {
+            return Err(AppError::NotFound(format!(
+                "{} of {} transactions not found",
+                ids.len() - rows.len(),
+                ids.len()
+            )));
+        }
+
+        Ok(rows)
+    }
+
+    /// Bring a transaction back from the trash, re-applying its balance effects.
+    /// CRITICAL: This operation MUST be atomic.
+    /// A refund comes back only next to its original and within what remains refundable.
+    pub async fn restore_transaction(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<Transaction, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 1. Fetch and lock the trashed row
+        let trashed = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT t.id
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NOT NULL
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if trashed.is_none() {
+            return Err(AppError::NotFound(
+                "Transaction not found in trash".to_string(),
+            ));
+        }
+
+        // 2. Take it out of the trash
+        let restored = sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions SET deleted_at = NULL
+            WHERE id = $1
+            RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            "#,
+        )
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 3. A refund needs its original back, with room left to refund
+        if let Some(original_id) = restored.reversal_of_id {
+            let original_amount = sqlx::query_scalar::<_, Decimal>(
+                "SELECT amount FROM transactions WHERE id = $1 AND deleted_at IS NULL AND status = 'posted' FOR UPDATE",
+            )
+            .bind(original_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?
+            .ok_or_else(|| {
+                AppError::ValidationError("Restore the refunded transaction first".to_string())
+            })?;
+
+            let refunded = Self::refunded_total(&mut tx, original_id, None).await?;
+            if refunded > original_amount {
+                return Err(AppError::Coded(
+                    ErrorCode::RefundExceedsRemaining,
+                    format!(
+                        "Refund exceeds the remaining refundable amount of {}",
+                        original_amount - (refunded - restored.amount)
+                    ),
+                ));
+            }
+        }
+
+        // 4. Re-apply account balances (accounts deleted meanwhile are skipped)
+        let guard = OverdraftGuard::watch(
+            &mut tx,
+            [restored.account_id, restored.destination_account_id],
+        )
+        .await?;
+        Self::apply_transaction_balance_effects_with_existence_check(
+            &mut tx,
+            restored.account_id,
+            restored.destination_account_id,
+            BalanceAmounts::of(&restored),
+            restored.get_type(),
+            BalanceOperation::Apply,
+        )
+        .await?;
+        guard.check(&mut tx).await?;
+
+        // 5. Record the restore
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::TRANSACTION_RESTORE,
+            entities::TRANSACTION,
+            transaction_id,
+            None,
+            Some(&restored),
+        )
+        .await?;
+
+        // 6. Put it back into the category suggestion model
+        let restored = restored.decrypted()?;
+        CategoryClassifier::learn(
+            &mut tx,
+            user_id,
+            restored.category_id,
+            restored.description.as_deref(),
+            restored.amount,
+            1,
+        )
+        .await?;
+
+        // 7. A restored income settles what it pays back again
+        if restored.get_type() == TransactionType::Income && restored.reversal_of_id.is_none() {
+            Self::settle_reimbursements(
+                &mut tx,
+                user_id,
+                restored.id,
+                restored.amount,
+                restored.transaction_date,
+                restored.description.as_deref(),
+            )
+            .await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(restored)
+    }
+
+    /// List the user's trashed transactions, most recently deleted first
+    pub async fn list_trash(
+        pool: &PgPool,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TrashedTransactionRow>, i64), AppError> {
+        let rows = sqlx::query_as::<_, CountedRow<TrashedTransactionRow>>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at, t.deleted_at,
+                   COUNT(*) OVER() AS total_count
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE budget_access(b.id, $1, FALSE) AND t.deleted_at IS NOT NULL
+            ORDER BY t.deleted_at DESC, t.id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let (rows, total) = CountedRow::split(rows);
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                Ok(TrashedTransactionRow {
+                    transaction: row.transaction.decrypted()?,
+                    deleted_at: row.deleted_at,
+                })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+
+        let total = match total {
+            Some(total) => total,
+            None if offset == 0 => 0,
+            None => sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT COUNT(*)
+                FROM transactions t
+                JOIN categories c ON t.category_id = c.id
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE budget_access(b.id, $1, FALSE) AND t.deleted_at IS NOT NULL
+                "#,
+            )
             .bind(user_id)
-            .fetch_one(&mut *tx)
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?,
+        };
+
+        Ok((rows, total))
+    }
+
+    /// Update a transaction with atomic balance adjustments.
+    /// COMPLEX SCENARIOS:
+    /// 1. Amount change only: adjust difference
+    /// 2. Account change: reverse old account, apply to new account
+    /// 3. Type change: reverse old effect, apply new effect
+    /// 4. Transfer destination change: reverse old destination, apply to new destination
+    pub async fn update_transaction(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        dto: UpdateTransactionDto,
+        if_match: &IfMatch,
+    ) -> Result<Transaction, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let updated = Self::update_in_tx(&mut tx, user_id, transaction_id, dto, if_match).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        updated.decrypted()
+    }
+
+    /// Update one transaction inside the caller's database transaction.
+    /// Returns the row as stored (sensitive columns encrypted).
+    pub async fn update_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        dto: UpdateTransactionDto,
+        if_match: &IfMatch,
+    ) -> Result<Transaction, AppError> {
+        // 1. Fetch and lock the existing transaction
+        let old_transaction = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL AND t.status = 'posted'
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?
+        .decrypted()?;
+        if_match.check(&old_transaction.updated_at)?;
+
+        // 2. Validate new category if changing
+        let new_category_id = dto.category_id.unwrap_or(old_transaction.category_id);
+        if dto.category_id.is_some() {
+            let category_valid = sqlx::query_scalar::<_, bool>(
+                r#"
+                SELECT EXISTS(
+                    SELECT 1 FROM categories c
+                    JOIN budgets b ON c.budget_id = b.id
+                    WHERE c.id = $1 AND budget_access(b.id, $2, TRUE)
+                )
+                "#,
+            )
+            .bind(new_category_id)
+            .bind(user_id)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            if !category_valid {
+                return Err(AppError::NotFound(
+                    "New category not found or access denied".to_string(),
+                ));
+            }
+        }
+
+        // 3. Validate and determine new account (source)
+        let new_account_id = match &dto.account_id {
+            Some(Some(id)) => {
+                let account_valid = sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+                )
+                .bind(id)
+                .bind(user_id)
+                .fetch_one(&mut **tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+                if !account_valid {
+                    return Err(AppError::NotFound(
+                        "New source account not found or access denied".to_string(),
+                    ));
+                }
+                Some(*id)
+            }
+            Some(None) => None,                 // Explicitly set to NULL
+            None => old_transaction.account_id, // Keep existing
+        };
+
+        // 4. Validate and determine new destination account
+        let new_destination_account_id = match &dto.destination_account_id {
+            Some(Some(id)) => {
+                let account_valid = sqlx::query_scalar::<_, bool>(
+                    "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+                )
+                .bind(id)
+                .bind(user_id)
+                .fetch_one(&mut **tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+                if !account_valid {
+                    return Err(AppError::NotFound(
+                        "New destination account not found or access denied".to_string(),
+                    ));
+                }
+                Some(*id)
+            }
+            Some(None) => None, // Explicitly set to NULL
+            None => old_transaction.destination_account_id, // Keep existing
+        };
+
+        let new_payee_id = match dto.payee_id {
+            Some(Some(id)) => {
+                PayeeService::verify(&mut **tx, id, user_id).await?;
+                Some(id)
+            }
+            Some(None) => None,
+            None => old_transaction.payee_id,
+        };
+
+        // Determine final values
+        let new_amount = dto.amount.unwrap_or(old_transaction.amount);
+        let new_type = dto.transaction_type.unwrap_or(old_transaction.get_type());
+        let new_date = dto
+            .transaction_date
+            .unwrap_or(old_transaction.transaction_date);
+        let new_fee_amount = dto.fee_amount.unwrap_or(old_transaction.fee_amount);
+        let new_destination_amount = dto
+            .destination_amount
+            .unwrap_or(old_transaction.destination_amount);
+
+        // 5. Validate transfer constraints (before consuming dto.description)
+        dto.validate_transfer(
+            new_type,
+            new_account_id,
+            new_destination_account_id,
+            new_fee_amount,
+            new_destination_amount,
+        )
+        .map_err(|e| AppError::ValidationError(e.to_string()))?;
+
+        // Refunds keep their type and, together, never exceed the original
+        Self::validate_refund_update(tx, &old_transaction, new_amount, new_type).await?;
+        if old_transaction.reimbursable && new_type != TransactionType::Expense {
+            return Err(AppError::ValidationError(
+                "Reimbursable transactions must stay expenses".to_string(),
+            ));
+        }
+
+        let new_description = dto
+            .description
+            .or_else(|| old_transaction.description.clone());
+        let new_tax_category = match &dto.tax_category {
+            Some(t) => normalize_tax_category(Some(t)),
+            None => old_transaction.tax_category.clone(),
+        };
+
+        // 6. CRITICAL: Handle balance adjustments
+        Self::handle_balance_update_for_modification_with_destination(
+            tx,
+            &old_transaction,
+            new_account_id,
+            new_destination_account_id,
+            BalanceAmounts {
+                amount: new_amount,
+                fee: new_fee_amount.unwrap_or_default(),
+                destination_amount: new_destination_amount.unwrap_or(new_amount),
+            },
+            new_type,
+        )
+        .await?;
+
+        // 7. Build and execute update query. A new amount or account no longer
+        // matches what was entered in another currency, so that is dropped.
+        let new_type_str = new_type.as_str();
+        let converted_amount_changed =
+            new_amount != old_transaction.amount || new_account_id != old_transaction.account_id;
+
+        let updated = sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions SET
+                category_id = $2,
+                account_id = $3,
+                destination_account_id = $4,
+                amount = $5,
+                transaction_date = $6,
+                description = $7,
+                transaction_type = $8,
+                tax_category = $9,
+                original_amount = CASE WHEN $10 THEN NULL ELSE original_amount END,
+                original_currency = CASE WHEN $10 THEN NULL ELSE original_currency END,
+                exchange_rate = CASE WHEN $10 THEN NULL ELSE exchange_rate END,
+                payee_id = $11,
+                fee_amount = $12,
+                destination_amount = $13,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(new_category_id)
+        .bind(new_account_id)
+        .bind(new_destination_account_id)
+        .bind(new_amount)
+        .bind(new_date)
+        .bind(encrypt_optional(new_description.as_deref())?)
+        .bind(new_type_str)
+        .bind(&new_tax_category)
+        .bind(converted_amount_changed)
+        .bind(new_payee_id)
+        .bind(new_fee_amount)
+        .bind(new_destination_amount)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 8. Retrain the category suggestion model with the new values
+        CategoryClassifier::learn(
+            tx,
+            user_id,
+            old_transaction.category_id,
+            old_transaction.description.as_deref(),
+            old_transaction.amount,
+            -1,
+        )
+        .await?;
+        CategoryClassifier::learn(
+            tx,
+            user_id,
+            new_category_id,
+            new_description.as_deref(),
+            new_amount,
+            1,
+        )
+        .await?;
+
+        // 9. Record the change (sensitive columns stay encrypted in the log)
+        let old_category_id = old_transaction.category_id;
+        let before = old_transaction.encrypted()?;
+        AuditService::record_change(
+            tx,
+            user_id,
+            actions::TRANSACTION_UPDATE,
+            entities::TRANSACTION,
+            transaction_id,
+            Some(&before),
+            Some(&updated),
+        )
+        .await?;
+
+        // 10. Notify about spending alert thresholds either category reached
+        NotificationService::evaluate_thresholds(tx, &[old_category_id, new_category_id]).await?;
+
+        Ok(updated)
+    }
+
+    /// Refund (or reverse) a transaction: creates a transaction of the opposite
+    /// type in the same category and account, linked through `reversal_of_id`.
+    /// Refunds can be partial but never add up to more than the original.
+    pub async fn refund_transaction(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        dto: RefundTransactionDto,
+    ) -> Result<Transaction, AppError> {
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 1. Lock the original so concurrent refunds are serialized, and
+        // default to, and cap at, what hasn't been refunded yet
+        let (original, refund_type, remaining) =
+            Self::lock_refundable(&mut tx, user_id, transaction_id).await?;
+        let amount = dto.amount.unwrap_or(remaining);
+        if amount > remaining {
+            return Err(AppError::Coded(
+                ErrorCode::RefundExceedsRemaining,
+                format!(
+                    "Refund cannot exceed the remaining refundable amount of {}",
+                    remaining
+                ),
+            ));
+        }
+        let description = dto.description.or(original.description);
+
+        // 2. Insert the refund
+        let refund = sqlx::query_as::<_, Transaction>(
+            r#"
+            INSERT INTO transactions
+                (category_id, account_id, amount, transaction_date, description, transaction_type, reversal_of_id,
+                 tax_category)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            "#,
+        )
+        .bind(original.category_id)
+        .bind(original.account_id)
+        .bind(amount)
+        .bind(dto.transaction_date.unwrap_or_else(Utc::now))
+        .bind(encrypt_optional(description.as_deref())?)
+        .bind(refund_type.as_str())
+        .bind(transaction_id)
+        .bind(&original.tax_category)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 3. Money comes back to (or leaves) the original account
+        let guard = OverdraftGuard::watch(&mut tx, [refund.account_id]).await?;
+        Self::apply_transaction_balance_effects(
+            &mut tx,
+            refund.account_id,
+            None,
+            BalanceAmounts::plain(amount),
+            refund_type,
+            BalanceOperation::Apply,
+        )
+        .await?;
+        guard.check(&mut tx).await?;
+
+        // 4. Keep the category suggestion model in step with delete, which unlearns it
+        CategoryClassifier::learn(
+            &mut tx,
+            user_id,
+            refund.category_id,
+            description.as_deref(),
+            amount,
+            1,
+        )
+        .await?;
+
+        // 5. Record the change so it can be undone
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::TRANSACTION_REFUND,
+            entities::TRANSACTION,
+            refund.id,
+            None,
+            Some(&refund),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        refund.decrypted()
+    }
+
+    /// Lock a transaction about to be refunded. Returns it (decrypted), the
+    /// type its refunds take and how much of it is still refundable; refunds,
+    /// transfers and fully refunded transactions can't be refunded.
+    async fn lock_refundable(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> Result<(Transaction, TransactionType, Decimal), AppError> {
+        let original = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL AND t.status = 'posted'
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?
+        .decrypted()?;
+
+        if original.reversal_of_id.is_some() {
+            return Err(AppError::ValidationError(
+                "A refund cannot itself be refunded".to_string(),
+            ));
+        }
+        let refund_type = original
+            .get_type()
+            .reversal()
+            .ok_or_else(|| AppError::ValidationError("Transfers cannot be refunded".to_string()))?;
+
+        let remaining = original.amount - Self::refunded_total(tx, transaction_id, None).await?;
+        if remaining <= Decimal::ZERO {
+            return Err(AppError::Coded(
+                ErrorCode::AlreadyRefunded,
+                "Transaction has already been fully refunded".to_string(),
+            ));
+        }
+
+        Ok((original, refund_type, remaining))
+    }
+
+    /// Sum of the refunds linked to a transaction, optionally leaving one out
+    async fn refunded_total(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        exclude_id: Option<Uuid>,
+    ) -> Result<Decimal, AppError> {
+        sqlx::query_scalar::<_, Decimal>(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)
+            FROM transactions
+            WHERE reversal_of_id = $1 AND ($2::UUID IS NULL OR id <> $2) AND deleted_at IS NULL AND status = 'posted'
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(exclude_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Reject edits that would break a refund link: a refund's type is fixed
+    /// and refunds may not exceed the original, and an original with refunds
+    /// keeps its type and can't drop below what has been refunded.
+    async fn validate_refund_update(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        old: &Transaction,
+        new_amount: Decimal,
+        new_type: TransactionType,
+    ) -> Result<(), AppError> {
+        if let Some(original_id) = old.reversal_of_id {
+            if new_type != old.get_type() {
+                return Err(AppError::ValidationError(
+                    "A refund's type cannot be changed".to_string(),
+                ));
+            }
+            if new_amount > old.amount {
+                let original_amount = sqlx::query_scalar::<_, Decimal>(
+                    "SELECT amount FROM transactions WHERE id = $1 FOR UPDATE",
+                )
+                .bind(original_id)
+                .fetch_one(&mut **tx)
+                .await
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+                let other_refunds = Self::refunded_total(tx, original_id, Some(old.id)).await?;
+                if other_refunds + new_amount > original_amount {
+                    return Err(AppError::ValidationError(format!(
+                        "Refund cannot exceed the remaining refundable amount of {}",
+                        original_amount - other_refunds
+                    )));
+                }
+            }
+            return Ok(());
+        }
+
+        let refunded = Self::refunded_total(tx, old.id, None).await?;
+        if refunded > Decimal::ZERO {
+            if new_type != old.get_type() {
+                return Err(AppError::ValidationError(
+                    "Cannot change the type of a transaction with refunds".to_string(),
+                ));
+            }
+            if new_amount < refunded {
+                return Err(AppError::ValidationError(format!(
+                    "Amount cannot be less than the {} already refunded",
+                    refunded
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Flag or unflag a transaction as reimbursable, change its payer, and
+    /// move it through the reimbursement lifecycle
+    pub async fn update_reimbursement(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        dto: UpdateReimbursementDto,
+    ) -> Result<Transaction, AppError> {
+        let mut tx = pool
+            .begin()
             .await
             .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-            if !category_valid {
-                return Err(AppError::NotFound(
-                    "New category not found or access denied".to_string(),
-                ));
+        let old = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL AND t.status = 'posted'
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?;
+
+        let (status, payer, reimbursed_by_id) = if dto.reimbursable {
+            if old.get_type() != TransactionType::Expense || old.reversal_of_id.is_some() {
+                return Err(AppError::ValidationError(
+                    "Only expenses can be marked reimbursable".to_string(),
+                ));
+            }
+            let current = old
+                .reimbursement_status
+                .as_deref()
+                .and_then(ReimbursementStatus::parse);
+            let status = dto
+                .status
+                .or(current)
+                .unwrap_or(ReimbursementStatus::Pending);
+            if let Some(current) = current {
+                if !current.can_transition_to(status) {
+                    return Err(AppError::ValidationError(format!(
+                        "Cannot move a reimbursement from {} to {}",
+                        current.as_str(),
+                        status.as_str()
+                    )));
+                }
+            }
+            let payer = match dto.payer {
+                Some(payer) => encrypt_optional(Some(payer.trim()).filter(|p| !p.is_empty()))?,
+                None => old.reimbursement_payer.clone(),
+            };
+            // Only a settled item keeps the income that paid it back
+            let reimbursed_by_id = old
+                .reimbursed_by_id
+                .filter(|_| status == ReimbursementStatus::Settled);
+            (Some(status.as_str()), payer, reimbursed_by_id)
+        } else {
+            (None, None, None)
+        };
+
+        let updated = sqlx::query_as::<_, Transaction>(
+            r#"
+            UPDATE transactions SET
+                reimbursable = $2,
+                reimbursement_status = $3,
+                reimbursement_payer = $4,
+                reimbursed_by_id = $5,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(dto.reimbursable)
+        .bind(status)
+        .bind(payer)
+        .bind(reimbursed_by_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        AuditService::record_change(
+            &mut tx,
+            user_id,
+            actions::TRANSACTION_UPDATE,
+            entities::TRANSACTION,
+            transaction_id,
+            Some(&old),
+            Some(&updated),
+        )
+        .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        updated.decrypted()
+    }
+
+    /// The user's reimbursable expenses still owed, oldest first, with the
+    /// amount owed reduced by any refunds
+    pub async fn outstanding_reimbursements<'e, E>(
+        executor: E,
+        user_id: Uuid,
+        until: Option<DateTime<Utc>>,
+    ) -> Result<Vec<OutstandingReimbursement>, AppError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as::<_, OutstandingReimbursement>(
+            r#"
+            SELECT
+                t.id,
+                t.amount - COALESCE((
+                    SELECT SUM(r.amount) FROM transactions r
+                    WHERE r.reversal_of_id = t.id AND r.deleted_at IS NULL AND r.status = 'posted'
+                ), 0) AS amount,
+                t.reimbursement_payer AS payer,
+                t.reimbursement_status AS status,
+                t.transaction_date
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.reimbursement_status IN ('pending', 'submitted')
+              AND ($2::TIMESTAMPTZ IS NULL OR t.transaction_date <= $2)
+            ORDER BY t.transaction_date, t.created_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(until)
+        .fetch_all(executor)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(OutstandingReimbursement::decrypted)
+        .collect()
+    }
+
+    /// Mark the outstanding items an income pays back as settled by it
+    async fn settle_reimbursements(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        income_id: Uuid,
+        amount: Decimal,
+        income_date: DateTime<Utc>,
+        description: Option<&str>,
+    ) -> Result<(), AppError> {
+        let outstanding =
+            Self::outstanding_reimbursements(&mut **tx, user_id, Some(income_date)).await?;
+        let matched = match_reimbursement(amount, description, &outstanding);
+        if matched.is_empty() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE transactions
+            SET reimbursement_status = 'settled', reimbursed_by_id = $2, updated_at = NOW()
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(&matched)
+        .bind(income_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Split a new income by the owner's active funding rules: category
+    /// targets raise the same-named category's allocation in the income's
+    /// budget, account targets become transfers out of the income's account.
+    /// Rules whose target can't be reached are passed over, leaving their
+    /// share to later rules.
+    async fn distribute_income(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        income: &Transaction,
+    ) -> Result<(), AppError> {
+        let rules = FundingService::active_rules(tx, user_id, income.account_id).await?;
+        if rules.is_empty() {
+            return Ok(());
+        }
+
+        let mut targets = Vec::with_capacity(rules.len());
+        for rule in rules {
+            let category_id = match &rule.category_name {
+                Some(name) => {
+                    let category_id = sqlx::query_scalar::<_, Uuid>(
+                        r#"
+                        SELECT c.id
+                        FROM categories c
+                        INNER JOIN categories income_category ON income_category.budget_id = c.budget_id
+                        WHERE income_category.id = $1 AND LOWER(c.name) = LOWER($2)
+                        LIMIT 1
+                        "#,
+                    )
+                    .bind(income.category_id)
+                    .bind(name)
+                    .fetch_optional(&mut **tx)
+                    .await
+                    .map_err(|e| AppError::InternalError(e.to_string()))?;
+                    match category_id {
+                        Some(category_id) => Some(category_id),
+                        None => continue,
+                    }
+                }
+                None => None,
+            };
+            if category_id.is_none()
+                && (income.account_id.is_none() || income.account_id == rule.target_account_id)
+            {
+                continue;
             }
+            targets.push((rule, category_id));
         }
 
-        // 3. Validate and determine new account (source)
-        let new_account_id = match &dto.account_id {
-            Some(Some(id)) => {
-                let account_valid = sqlx::query_scalar::<_, bool>(
-                    "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+        let rules: Vec<FundingRule> = targets.iter().map(|(rule, _)| rule.clone()).collect();
+        let amounts = plan_distribution(income.amount, &rules);
+
+        let mut applied = Vec::new();
+        for ((rule, category_id), amount) in targets.into_iter().zip(amounts) {
+            if amount <= Decimal::ZERO {
+                continue;
+            }
+            let mut split = AppliedFunding {
+                rule_id: rule.id,
+                amount,
+                category_id,
+                transfer_id: None,
+            };
+
+            if let Some(category_id) = category_id {
+                BudgetService::adjust_allocation(tx, category_id, amount).await?;
+            } else {
+                let transfer_id = sqlx::query_scalar::<_, Uuid>(
+                    r#"
+                    INSERT INTO transactions
+                        (category_id, account_id, destination_account_id, amount, transaction_date,
+                         description, transaction_type)
+                    VALUES ($1, $2, $3, $4, $5, $6, 'transfer')
+                    RETURNING id
+                    "#,
                 )
-                .bind(id)
-                .bind(user_id)
-                .fetch_one(&mut *tx)
+                .bind(income.category_id)
+                .bind(income.account_id)
+                .bind(rule.target_account_id)
+                .bind(amount)
+                .bind(income.transaction_date)
+                .bind(encrypt_optional(Some(rule.name.as_str()))?)
+                .fetch_one(&mut **tx)
                 .await
                 .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-                if !account_valid {
-                    return Err(AppError::NotFound(
-                        "New source account not found or access denied".to_string(),
-                    ));
-                }
-                Some(*id)
+                Self::apply_transaction_balance_effects(
+                    tx,
+                    income.account_id,
+                    rule.target_account_id,
+                    BalanceAmounts::plain(amount),
+                    TransactionType::Transfer,
+                    BalanceOperation::Apply,
+                )
+                .await?;
+                split.transfer_id = Some(transfer_id);
             }
-            Some(None) => None,                 // Explicitly set to NULL
-            None => old_transaction.account_id, // Keep existing
+            applied.push(split);
+        }
+
+        if applied.is_empty() {
+            return Ok(());
+        }
+        let total: Decimal = applied.iter().map(|split| split.amount).sum();
+        AuditService::record(
+            tx,
+            user_id,
+            actions::TRANSACTION_DISTRIBUTE,
+            entities::TRANSACTION,
+            income.id,
+            json!({ "amount": total, "splits": applied }),
+        )
+        .await
+    }
+
+    /// Take back the funding-rule splits of an income whose creation is being
+    /// undone: the transfers it made are removed along with their balance
+    /// effects, and the allocations it raised are lowered again. Returns the
+    /// transfers that were still live.
+    async fn reverse_distribution(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        income_id: Uuid,
+    ) -> Result<Vec<Transaction>, AppError> {
+        let distribution = sqlx::query_as::<_, (Uuid, serde_json::Value)>(
+            r#"
+            SELECT id, details
+            FROM audit_log
+            WHERE entity_type = $1 AND entity_id = $2 AND action = $3 AND undone_at IS NULL
+            FOR UPDATE
+            "#,
+        )
+        .bind(entities::TRANSACTION)
+        .bind(income_id)
+        .bind(actions::TRANSACTION_DISTRIBUTE)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let Some((entry_id, details)) = distribution else {
+            return Ok(Vec::new());
         };
+        let splits: Vec<AppliedFunding> = serde_json::from_value(details["splits"].clone())
+            .map_err(|e| AppError::InternalError(format!("Corrupt audit entry: {}", e)))?;
 
-        // 4. Validate and determine new destination account
-        let new_destination_account_id = match &dto.destination_account_id {
-            Some(Some(id)) => {
-                let account_valid = sqlx::query_scalar::<_, bool>(
-                    "SELECT EXISTS(SELECT 1 FROM accounts WHERE id = $1 AND owner_id = $2)",
+        let mut removed = Vec::new();
+        for split in splits {
+            if let Some(category_id) = split.category_id {
+                BudgetService::adjust_allocation(tx, category_id, -split.amount).await?;
+            }
+            let Some(transfer_id) = split.transfer_id else {
+                continue;
+            };
+
+            // A transfer already in the trash has had its balance effects reversed
+            let transfer = sqlx::query_as::<_, Transaction>(
+                r#"
+                SELECT id, category_id, account_id, destination_account_id, amount, transaction_date,
+                       description, transaction_type, reversal_of_id,
+                       reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                       tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount,
+                       created_at, updated_at
+                FROM transactions
+                WHERE id = $1 AND deleted_at IS NULL AND status = 'posted'
+                FOR UPDATE
+                "#,
+            )
+            .bind(transfer_id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+            if let Some(transfer) = transfer {
+                let guard = OverdraftGuard::watch(
+                    tx,
+                    [transfer.account_id, transfer.destination_account_id],
                 )
-                .bind(id)
-                .bind(user_id)
-                .fetch_one(&mut *tx)
+                .await?;
+                Self::apply_transaction_balance_effects_with_existence_check(
+                    tx,
+                    transfer.account_id,
+                    transfer.destination_account_id,
+                    BalanceAmounts::of(&transfer),
+                    transfer.get_type(),
+                    BalanceOperation::Reverse,
+                )
+                .await?;
+                guard.check(tx).await?;
+                removed.push(transfer);
+            }
+
+            sqlx::query("DELETE FROM transactions WHERE id = $1")
+                .bind(transfer_id)
+                .execute(&mut **tx)
                 .await
                 .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
 
-                if !account_valid {
-                    return Err(AppError::NotFound(
-                        "New destination account not found or access denied".to_string(),
-                    ));
+        sqlx::query("UPDATE audit_log SET undone_at = NOW() WHERE id = $1")
+            .bind(entry_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok(removed)
+    }
+
+    /// Put a transaction back to an audited snapshot (used by undo), reversing
+    /// the balance effects of its current state and applying those of the
+    /// snapshot. `None` means the transaction didn't exist, so it is removed.
+    pub async fn restore_snapshot(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        snapshot: Option<&Transaction>,
+    ) -> Result<RestoredTransaction, AppError> {
+        let current = sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            WHERE t.id = $1 AND budget_access(b.id, $2, TRUE) AND t.deleted_at IS NULL AND t.status = 'posted'
+            FOR UPDATE OF t
+            "#,
+        )
+        .bind(transaction_id)
+        .bind(user_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let recreated = current.is_none();
+        let touched = [
+            current.as_ref().and_then(|t| t.account_id),
+            current.as_ref().and_then(|t| t.destination_account_id),
+            snapshot.and_then(|t| t.account_id),
+            snapshot.and_then(|t| t.destination_account_id),
+        ];
+        let guard = OverdraftGuard::watch(tx, touched).await?;
+        let mut restored = RestoredTransaction {
+            transaction_id,
+            transaction: None,
+            recreated,
+            removed_transfers: Vec::new(),
+            accounts: touched.into_iter().flatten().collect(),
+        };
+        if let Some(current) = current {
+            // An income being taken back takes its funding-rule splits with it
+            if snapshot.is_none() {
+                for transfer in Self::reverse_distribution(tx, transaction_id).await? {
+                    restored.removed_transfers.push(transfer.id);
+                    restored.accounts.extend(
+                        transfer
+                            .account_id
+                            .into_iter()
+                            .chain(transfer.destination_account_id),
+                    );
                 }
-                Some(*id)
             }
-            Some(None) => None, // Explicitly set to NULL
-            None => old_transaction.destination_account_id, // Keep existing
-        };
-
-        // Determine final values
-        let new_amount = dto.amount.unwrap_or(old_transaction.amount);
-        let new_type = dto.transaction_type.unwrap_or(old_transaction.get_type());
-        let new_date = dto
-            .transaction_date
-            .unwrap_or(old_transaction.transaction_date);
 
-        // 5. Validate transfer constraints (before consuming dto.description)
-        dto.validate_transfer(new_type, new_account_id, new_destination_account_id)
-            .map_err(|e| AppError::ValidationError(e.to_string()))?;
+            Self::apply_transaction_balance_effects_with_existence_check(
+                tx,
+                current.account_id,
+                current.destination_account_id,
+                BalanceAmounts::of(&current),
+                current.get_type(),
+                BalanceOperation::Reverse,
+            )
+            .await?;
+
+            let current = current.decrypted()?;
+            CategoryClassifier::learn(
+                tx,
+                user_id,
+                current.category_id,
+                current.description.as_deref(),
+                current.amount,
+                -1,
+            )
+            .await?;
+
+            if snapshot.is_none() {
+                sqlx::query("DELETE FROM transactions WHERE id = $1")
+                    .bind(transaction_id)
+                    .execute(&mut **tx)
+                    .await
+                    .map_err(|e| AppError::InternalError(e.to_string()))?;
+            }
+        }
 
-        let new_description = dto
-            .description
-            .or_else(|| old_transaction.description.clone());
+        let Some(snapshot) = snapshot else {
+            guard.check(tx).await?;
+            return Ok(restored);
+        };
 
-        // 6. CRITICAL: Handle balance adjustments
-        Self::handle_balance_update_for_modification_with_destination(
-            &mut tx,
-            &old_transaction,
-            new_account_id,
-            new_destination_account_id,
-            new_amount,
-            new_type,
+        let category_valid = sqlx::query_scalar::<_, bool>(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM categories c
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE c.id = $1 AND budget_access(b.id, $2, TRUE)
+            )
+            "#,
         )
-        .await?;
+        .bind(snapshot.category_id)
+        .bind(user_id)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        // 7. Build and execute update query
-        let new_type_str = new_type.as_str();
+        if !category_valid {
+            return Err(AppError::Coded(
+                ErrorCode::UndoConflict,
+                "Cannot undo: the category no longer exists".to_string(),
+            ));
+        }
 
-        let updated = sqlx::query_as::<_, Transaction>(
+        // Accounts and payees deleted since fall back to NULL, as ON DELETE SET NULL would have done
+        let transaction = sqlx::query_as::<_, Transaction>(
             r#"
-            UPDATE transactions SET
-                category_id = $2,
-                account_id = $3,
-                destination_account_id = $4,
-                amount = $5,
-                transaction_date = $6,
-                description = $7,
-                transaction_type = $8,
+            INSERT INTO transactions
+                (id, category_id, account_id, destination_account_id, amount, transaction_date,
+                 description, transaction_type, created_at, reversal_of_id,
+                 reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                 tax_category, original_amount, original_currency, exchange_rate, payee_id,
+                 fee_amount, destination_amount)
+            VALUES (
+                $1, $2,
+                (SELECT id FROM accounts WHERE id = $3),
+                (SELECT id FROM accounts WHERE id = $4),
+                $5, $6, $7, $8, $9,
+                (SELECT id FROM transactions WHERE id = $10),
+                $11, $12, $13,
+                (SELECT id FROM transactions WHERE id = $14),
+                $15, $16, $17, $18,
+                (SELECT id FROM payees WHERE id = $19),
+                $20, $21
+            )
+            ON CONFLICT (id) DO UPDATE SET
+                category_id = EXCLUDED.category_id,
+                account_id = EXCLUDED.account_id,
+                destination_account_id = EXCLUDED.destination_account_id,
+                amount = EXCLUDED.amount,
+                transaction_date = EXCLUDED.transaction_date,
+                description = EXCLUDED.description,
+                transaction_type = EXCLUDED.transaction_type,
+                reversal_of_id = EXCLUDED.reversal_of_id,
+                reimbursable = EXCLUDED.reimbursable,
+                reimbursement_status = EXCLUDED.reimbursement_status,
+                reimbursement_payer = EXCLUDED.reimbursement_payer,
+                reimbursed_by_id = EXCLUDED.reimbursed_by_id,
+                tax_category = EXCLUDED.tax_category,
+                original_amount = EXCLUDED.original_amount,
+                original_currency = EXCLUDED.original_currency,
+                exchange_rate = EXCLUDED.exchange_rate,
+                payee_id = EXCLUDED.payee_id,
+                fee_amount = EXCLUDED.fee_amount,
+                destination_amount = EXCLUDED.destination_amount,
+                status = 'posted',
+                deleted_at = NULL,
                 updated_at = NOW()
-            WHERE id = $1
             RETURNING id, category_id, account_id, destination_account_id, amount, transaction_date, description,
-                      transaction_type, created_at, updated_at
+                      transaction_type, reversal_of_id,
+                      reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                      tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
             "#,
         )
-        .bind(transaction_id)
-        .bind(new_category_id)
-        .bind(new_account_id)
-        .bind(new_destination_account_id)
-        .bind(new_amount)
-        .bind(new_date)
-        .bind(&new_description)
-        .bind(new_type_str)
-        .fetch_one(&mut *tx)
+        .bind(snapshot.id)
+        .bind(snapshot.category_id)
+        .bind(snapshot.account_id)
+        .bind(snapshot.destination_account_id)
+        .bind(snapshot.amount)
+        .bind(snapshot.transaction_date)
+        .bind(&snapshot.description)
+        .bind(&snapshot.transaction_type)
+        .bind(snapshot.created_at)
+        .bind(snapshot.reversal_of_id)
+        .bind(snapshot.reimbursable)
+        .bind(&snapshot.reimbursement_status)
+        .bind(&snapshot.reimbursement_payer)
+        .bind(snapshot.reimbursed_by_id)
+        .bind(&snapshot.tax_category)
+        .bind(snapshot.original_amount)
+        .bind(&snapshot.original_currency)
+        .bind(snapshot.exchange_rate)
+        .bind(snapshot.payee_id)
+        .bind(snapshot.fee_amount)
+        .bind(snapshot.destination_amount)
+        .fetch_one(&mut **tx)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        tx.commit()
-            .await
-            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        Self::apply_transaction_balance_effects(
+            tx,
+            transaction.account_id,
+            transaction.destination_account_id,
+            BalanceAmounts::of(&transaction),
+            transaction.get_type(),
+            BalanceOperation::Apply,
+        )
+        .await?;
 
-        Ok(updated)
+        let transaction = transaction.decrypted()?;
+        CategoryClassifier::learn(
+            tx,
+            user_id,
+            transaction.category_id,
+            transaction.description.as_deref(),
+            transaction.amount,
+            1,
+        )
+        .await?;
+
+        // A deleted income reopened what it paid back; bringing it back settles again
+        if recreated && transaction.get_type() == TransactionType::Income {
+            Self::settle_reimbursements(
+                tx,
+                user_id,
+                transaction.id,
+                transaction.amount,
+                transaction.transaction_date,
+                transaction.description.as_deref(),
+            )
+            .await?;
+        }
+
+        guard.check(tx).await?;
+        restored.transaction = Some(transaction);
+        Ok(restored)
     }
 
     /// Apply balance effects for a transaction (create/delete)
-    /// For transfers: source account decreases, destination account increases
+    /// For transfers: source account decreases by the amount plus any fee,
+    /// destination account increases by the destination amount.
+    /// Callers wrap this in an `OverdraftGuard` for the accounts involved.
     async fn apply_transaction_balance_effects(
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         source_account_id: Option<Uuid>,
         destination_account_id: Option<Uuid>,
-        amount: Decimal,
+        amounts: BalanceAmounts,
         transaction_type: TransactionType,
         operation: BalanceOperation,
     ) -> Result<(), AppError> {
+        let BalanceAmounts {
+            amount,
+            fee,
+            destination_amount,
+        } = amounts;
         match transaction_type {
             TransactionType::Expense => {
                 // Expense: only affects source account (decreases balance)
@@ -400,15 +2212,18 @@ impl TransactionService {
                 }
             }
             TransactionType::Transfer => {
-                // Transfer: source decreases, destination increases
+                // Transfer: source decreases (fee included), destination increases
                 if let Some(src) = source_account_id {
-                    Self::update_single_account_balance(tx, src, -amount, operation).await?;
+                    Self::update_single_account_balance(tx, src, -(amount + fee), operation)
+                        .await?;
                 }
                 if let Some(dst) = destination_account_id {
-                    Self::update_single_account_balance(tx, dst, amount, operation).await?;
+                    Self::update_single_account_balance(tx, dst, destination_amount, operation)
+                        .await?;
                 }
             }
         }
+
         Ok(())
     }
 
@@ -417,10 +2232,15 @@ impl TransactionService {
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         source_account_id: Option<Uuid>,
         destination_account_id: Option<Uuid>,
-        amount: Decimal,
+        amounts: BalanceAmounts,
         transaction_type: TransactionType,
         operation: BalanceOperation,
     ) -> Result<(), AppError> {
+        let BalanceAmounts {
+            amount,
+            fee,
+            destination_amount,
+        } = amounts;
         match transaction_type {
             TransactionType::Expense => {
                 if let Some(account_id) = source_account_id {
@@ -441,12 +2261,14 @@ impl TransactionService {
             TransactionType::Transfer => {
                 if let Some(src) = source_account_id {
                     if Self::account_exists(tx, src).await? {
-                        Self::update_single_account_balance(tx, src, -amount, operation).await?;
+                        Self::update_single_account_balance(tx, src, -(amount + fee), operation)
+                            .await?;
                     }
                 }
                 if let Some(dst) = destination_account_id {
                     if Self::account_exists(tx, dst).await? {
-                        Self::update_single_account_balance(tx, dst, amount, operation).await?;
+                        Self::update_single_account_balance(tx, dst, destination_amount, operation)
+                            .await?;
                     }
                 }
             }
@@ -474,20 +2296,31 @@ impl TransactionService {
         old: &Transaction,
         new_account_id: Option<Uuid>,
         new_destination_account_id: Option<Uuid>,
-        new_amount: Decimal,
+        new_amounts: BalanceAmounts,
         new_type: TransactionType,
     ) -> Result<(), AppError> {
         let old_type = old.get_type();
 
         // Strategy: Reverse all old effects, then apply all new effects
-        // This handles all complex scenarios cleanly
+        // This handles all complex scenarios cleanly. Only the net change of
+        // each account counts against the overdraft guard.
+        let guard = OverdraftGuard::watch(
+            tx,
+            [
+                old.account_id,
+                old.destination_account_id,
+                new_account_id,
+                new_destination_account_id,
+            ],
+        )
+        .await?;
 
         // Reverse old effects
         Self::apply_transaction_balance_effects_with_existence_check(
             tx,
             old.account_id,
             old.destination_account_id,
-            old.amount,
+            BalanceAmounts::of(old),
             old_type,
             BalanceOperation::Reverse,
         )
@@ -498,13 +2331,13 @@ impl TransactionService {
             tx,
             new_account_id,
             new_destination_account_id,
-            new_amount,
+            new_amounts,
             new_type,
             BalanceOperation::Apply,
         )
         .await?;
 
-        Ok(())
+        guard.check(tx).await
     }
 
     /// Update a single account balance atomically
@@ -519,18 +2352,227 @@ impl TransactionService {
             BalanceOperation::Reverse => -effect,
         };
 
-        if adjustment != Decimal::ZERO {
-            sqlx::query(
-                "UPDATE accounts SET balance = balance + $1, updated_at = NOW() WHERE id = $2",
-            )
-            .bind(adjustment)
-            .bind(account_id)
-            .execute(&mut **tx)
-            .await
-            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if adjustment != Decimal::ZERO {
+            sqlx::query(
+                "UPDATE accounts SET balance = balance + $1, updated_at = NOW() WHERE id = $2",
+            )
+            .bind(adjustment)
+            .bind(account_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Create a transaction from just an amount and description. The category
+    /// comes from the classifier over the current month's budget, the type from
+    /// how that category has been used before, the account from the one used
+    /// most often, and the date is now.
+    pub async fn quick_create(
+        pool: &PgPool,
+        user_id: Uuid,
+        dto: QuickTransactionDto,
+    ) -> Result<(Transaction, CategorySuggestion), AppError> {
+        let budget_id = BudgetService::current_budget_id(pool, user_id).await?;
+
+        let description = dto.description.trim().to_string();
+        let category = CategoryClassifier::suggest(
+            pool,
+            user_id,
+            &SuggestCategoryQuery {
+                description: description.clone(),
+                amount: Some(dto.amount),
+                budget_id: Some(budget_id),
+                limit: 1,
+            },
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            AppError::ValidationError("The current month's budget has no categories".to_string())
+        })?;
+
+        // Income or expense, whichever this category name has been used for most
+        let transaction_type = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT t.transaction_type
+            FROM transactions t
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND LOWER(c.name) = LOWER($2)
+              AND t.transaction_type IN ('expense', 'income')
+            GROUP BY t.transaction_type
+            ORDER BY COUNT(*) DESC, t.transaction_type
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(&category.name)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .and_then(|t| TransactionType::parse(&t))
+        .unwrap_or_default();
+
+        let account_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT a.id
+            FROM accounts a
+            LEFT JOIN transactions t ON t.account_id = a.id AND t.deleted_at IS NULL AND t.status = 'posted'
+            WHERE a.owner_id = $1
+            GROUP BY a.id
+            ORDER BY COUNT(t.id) DESC, MAX(t.transaction_date) DESC NULLS LAST, a.created_at
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let transaction = Self::create_transaction(
+            pool,
+            user_id,
+            CreateTransactionDto {
+                reimbursable: false,
+                reimbursement_payer: None,
+                tax_category: None,
+                currency: None,
+                exchange_rate: None,
+                pending: false,
+                payee_id: None,
+                linked_transaction_id: None,
+                fee_amount: None,
+                destination_amount: None,
+                category_id: category.category_id,
+                account_id,
+                destination_account_id: None,
+                amount: dto.amount,
+                transaction_date: Utc::now(),
+                description: Some(description),
+                transaction_type,
+            },
+        )
+        .await?;
+
+        Ok((transaction, category))
+    }
+
+    /// Most recent transactions with a description, newest first and decrypted
+    pub async fn recent_descriptions(
+        pool: &PgPool,
+        user_id: Uuid,
+        window: i64,
+    ) -> Result<Vec<DescriptionUsageRow>, AppError> {
+        sqlx::query_as::<_, DescriptionUsageRow>(
+            r#"
+            SELECT t.description, t.amount, c.name AS category_name, t.transaction_date
+            FROM transactions t
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.description IS NOT NULL
+              AND t.transaction_type IN ('expense', 'income')
+            ORDER BY t.transaction_date DESC, t.created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(window)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(DescriptionUsageRow::decrypted)
+        .collect()
+    }
+
+    /// Most frequent descriptions starting with a prefix. Plaintext descriptions
+    /// are matched in SQL through the trigram index; encrypted ones can't be, so
+    /// with encryption enabled the most recent transactions are matched after
+    /// decryption instead.
+    pub async fn suggest_descriptions(
+        pool: &PgPool,
+        user_id: Uuid,
+        prefix: &str,
+        limit: i64,
+    ) -> Result<Vec<DescriptionSuggestion>, AppError> {
+        if crypto::is_enabled() {
+            let rows = Self::recent_descriptions(pool, user_id, DESCRIPTION_SCAN_WINDOW).await?;
+            return Ok(DescriptionSuggestion::aggregate(
+                rows,
+                prefix,
+                limit as usize,
+            ));
         }
 
-        Ok(())
+        let pattern = format!(
+            "{}%",
+            prefix
+                .trim()
+                .to_lowercase()
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+
+        sqlx::query_as::<_, DescriptionSuggestion>(
+            r#"
+            SELECT
+                (ARRAY_AGG(TRIM(t.description) ORDER BY t.transaction_date DESC))[1] AS description,
+                COUNT(*) AS usage_count,
+                (percentile_cont(0.5) WITHIN GROUP (ORDER BY t.amount))::NUMERIC(12, 2) AS typical_amount,
+                mode() WITHIN GROUP (ORDER BY c.name) AS category_name,
+                MAX(t.transaction_date) AS last_used_at
+            FROM transactions t
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.description IS NOT NULL
+              AND t.description NOT LIKE 'enc:v1:%'
+              AND LOWER(t.description) LIKE $2 ESCAPE '\'
+              AND t.transaction_type IN ('expense', 'income')
+            GROUP BY LOWER(TRIM(t.description))
+            ORDER BY usage_count DESC, last_used_at DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(pattern)
+        .bind(limit)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Accounts the given transactions move money in or out of
+    pub async fn account_ids(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, AppError> {
+        sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT DISTINCT a.id
+            FROM transactions t
+            JOIN categories c ON t.category_id = c.id
+            JOIN budgets b ON c.budget_id = b.id
+            CROSS JOIN LATERAL (VALUES (t.account_id), (t.destination_account_id)) AS a(id)
+            WHERE t.id = ANY($1) AND budget_access(b.id, $2, FALSE) AND a.id IS NOT NULL
+            "#,
+        )
+        .bind(transaction_ids)
+        .bind(user_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
     }
 
     /// Get a single transaction by ID
@@ -542,11 +2584,14 @@ impl TransactionService {
         sqlx::query_as::<_, Transaction>(
             r#"
             SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
-                   t.description, t.transaction_type, t.created_at, t.updated_at
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
             FROM transactions t
             JOIN categories c ON t.category_id = c.id
             JOIN budgets b ON c.budget_id = b.id
-            WHERE t.id = $1 AND b.owner_id = $2
+            WHERE t.id = $1 AND budget_access(b.id, $2, FALSE) AND t.deleted_at IS NULL
             "#,
         )
         .bind(transaction_id)
@@ -554,7 +2599,8 @@ impl TransactionService {
         .fetch_optional(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
-        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))
+        .ok_or_else(|| AppError::NotFound("Transaction not found".to_string()))?
+        .decrypted()
     }
 
     /// List transactions with filters
@@ -565,63 +2611,76 @@ impl TransactionService {
     ) -> Result<(Vec<Transaction>, i64), AppError> {
         let limit = filters.limit.min(100);
         let offset = filters.offset;
+        let clauses = TransactionFilterClauses::from(filters);
 
-        // Execute list query with filters
-        let transactions = sqlx::query_as::<_, Transaction>(
+        // Page and total in one round trip via a window count
+        let mut qb = QueryBuilder::<Postgres>::new(
             r#"
             SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
-                   t.description, t.transaction_type, t.created_at, t.updated_at
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at,
+                   COUNT(*) OVER() AS total_count
             FROM transactions t
             JOIN categories c ON t.category_id = c.id
             JOIN budgets b ON c.budget_id = b.id
-            WHERE b.owner_id = $1
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.category_id = $4)
-              AND ($5::uuid IS NULL OR t.account_id = $5)
-              AND ($6::text IS NULL OR t.transaction_type = $6)
-            ORDER BY t.transaction_date DESC, t.created_at DESC
-            LIMIT $7 OFFSET $8
-            "#,
-        )
-        .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.category_id)
-        .bind(filters.account_id)
-        .bind(&filters.transaction_type)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+            WHERE t.deleted_at IS NULL AND t.status = 'posted' AND budget_access(b.id, "#,
+        );
+        qb.push_bind(user_id).push(", FALSE)");
+        clauses.push(&mut qb);
+        qb.push(" ORDER BY t.transaction_date DESC, t.created_at DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = qb
+            .build_query_as::<CountedRow<Transaction>>()
+            .fetch_all(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        // Execute count query
-        let total = sqlx::query_scalar::<_, i64>(
+        let (rows, total) = CountedRow::split(rows);
+        let transactions = rows
+            .into_iter()
+            .map(Transaction::decrypted)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let total = match total {
+            Some(total) => total,
+            None => Self::count_filtered(pool, user_id, &clauses, offset).await?,
+        };
+
+        Ok((transactions, total))
+    }
+
+    /// Total for a filtered list whose page came back empty. Only a page past
+    /// the end needs the extra query; an empty first page means no matches.
+    async fn count_filtered(
+        pool: &PgPool,
+        user_id: Uuid,
+        clauses: &TransactionFilterClauses<'_>,
+        offset: i64,
+    ) -> Result<i64, AppError> {
+        if offset == 0 {
+            return Ok(0);
+        }
+
+        let mut qb = QueryBuilder::<Postgres>::new(
             r#"
             SELECT COUNT(*)
             FROM transactions t
             JOIN categories c ON t.category_id = c.id
             JOIN budgets b ON c.budget_id = b.id
-            WHERE b.owner_id = $1
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.category_id = $4)
-              AND ($5::uuid IS NULL OR t.account_id = $5)
-              AND ($6::text IS NULL OR t.transaction_type = $6)
-            "#,
-        )
-        .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.category_id)
-        .bind(filters.account_id)
-        .bind(&filters.transaction_type)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+            WHERE t.deleted_at IS NULL AND t.status = 'posted' AND budget_access(b.id, "#,
+        );
+        qb.push_bind(user_id).push(", FALSE)");
+        clauses.push(&mut qb);
 
-        Ok((transactions, total))
+        qb.build_query_scalar::<i64>()
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))
     }
 
     /// Get transactions by category
@@ -636,7 +2695,7 @@ impl TransactionService {
             SELECT EXISTS(
                 SELECT 1 FROM categories c
                 JOIN budgets b ON c.budget_id = b.id
-                WHERE c.id = $1 AND b.owner_id = $2
+                WHERE c.id = $1 AND budget_access(b.id, $2, FALSE)
             )
             "#,
         )
@@ -655,16 +2714,21 @@ impl TransactionService {
         sqlx::query_as::<_, Transaction>(
             r#"
             SELECT id, category_id, account_id, destination_account_id, amount, transaction_date, description,
-                   transaction_type, created_at, updated_at
+                   transaction_type, reversal_of_id,
+                   reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                   tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
             FROM transactions
-            WHERE category_id = $1
+            WHERE category_id = $1 AND deleted_at IS NULL AND status = 'posted'
             ORDER BY transaction_date DESC, created_at DESC
             "#,
         )
         .bind(category_id)
         .fetch_all(pool)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(Transaction::decrypted)
+        .collect()
     }
 
     /// Get transactions by multiple categories
@@ -683,7 +2747,7 @@ impl TransactionService {
             SELECT COUNT(DISTINCT c.id)
             FROM categories c
             JOIN budgets b ON c.budget_id = b.id
-            WHERE c.id = ANY($1) AND b.owner_id = $2
+            WHERE c.id = ANY($1) AND budget_access(b.id, $2, FALSE)
             "#,
         )
         .bind(&category_ids)
@@ -701,16 +2765,21 @@ impl TransactionService {
         sqlx::query_as::<_, Transaction>(
             r#"
             SELECT id, category_id, account_id, destination_account_id, amount, transaction_date, description,
-                   transaction_type, created_at, updated_at
+                   transaction_type, reversal_of_id,
+                   reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                   tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
             FROM transactions
-            WHERE category_id = ANY($1)
+            WHERE category_id = ANY($1) AND deleted_at IS NULL AND status = 'posted'
             ORDER BY transaction_date DESC, created_at DESC
             "#,
         )
         .bind(&category_ids)
         .fetch_all(pool)
         .await
-        .map_err(|e| AppError::InternalError(e.to_string()))
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(Transaction::decrypted)
+        .collect()
     }
 
     /// List transactions with detailed account/category info
@@ -721,69 +2790,51 @@ impl TransactionService {
     ) -> Result<(Vec<TransactionDetailRow>, i64), AppError> {
         let limit = filters.limit.min(100);
         let offset = filters.offset;
+        let clauses = TransactionFilterClauses::from(filters);
 
-        // Execute list query with JOINs for detailed info (including destination account)
-        let transactions = sqlx::query_as::<_, TransactionDetailRow>(
+        // Page and total in one round trip, with JOINs for detailed info (including destination account)
+        let mut qb = QueryBuilder::<Postgres>::new(
             r#"
             SELECT
                 t.id, t.amount, t.transaction_type, t.transaction_date,
-                t.description, t.created_at, t.updated_at,
+                t.description, t.reversal_of_id, t.reimbursable, t.reimbursement_status,
+                t.created_at, t.updated_at,
                 c.id as category_id, c.name as category_name, c.color_hex as category_color_hex,
                 a.id as account_id, a.name as account_name, a.account_type,
                 a.color_hex as account_color_hex, a.currency as account_currency,
                 da.id as dest_account_id, da.name as dest_account_name, da.account_type as dest_account_type,
-                da.color_hex as dest_account_color_hex, da.currency as dest_account_currency
+                da.color_hex as dest_account_color_hex, da.currency as dest_account_currency,
+                COUNT(*) OVER() AS total_count
             FROM transactions t
             JOIN categories c ON t.category_id = c.id
             JOIN budgets b ON c.budget_id = b.id
             LEFT JOIN accounts a ON t.account_id = a.id
             LEFT JOIN accounts da ON t.destination_account_id = da.id
-            WHERE b.owner_id = $1
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.category_id = $4)
-              AND ($5::uuid IS NULL OR t.account_id = $5)
-              AND ($6::text IS NULL OR t.transaction_type = $6)
-            ORDER BY t.transaction_date DESC, t.created_at DESC
-            LIMIT $7 OFFSET $8
-            "#,
-        )
-        .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.category_id)
-        .bind(filters.account_id)
-        .bind(&filters.transaction_type)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+            WHERE t.deleted_at IS NULL AND t.status = 'posted' AND budget_access(b.id, "#,
+        );
+        qb.push_bind(user_id).push(", FALSE)");
+        clauses.push(&mut qb);
+        qb.push(" ORDER BY t.transaction_date DESC, t.created_at DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let rows = qb
+            .build_query_as::<CountedRow<TransactionDetailRow>>()
+            .fetch_all(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
 
-        // Execute count query
-        let total = sqlx::query_scalar::<_, i64>(
-            r#"
-            SELECT COUNT(*)
-            FROM transactions t
-            JOIN categories c ON t.category_id = c.id
-            JOIN budgets b ON c.budget_id = b.id
-            WHERE b.owner_id = $1
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.category_id = $4)
-              AND ($5::uuid IS NULL OR t.account_id = $5)
-              AND ($6::text IS NULL OR t.transaction_type = $6)
-            "#,
-        )
-        .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.category_id)
-        .bind(filters.account_id)
-        .bind(&filters.transaction_type)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+        let (rows, total) = CountedRow::split(rows);
+        let transactions = rows
+            .into_iter()
+            .map(TransactionDetailRow::decrypted)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let total = match total {
+            Some(total) => total,
+            None => Self::count_filtered(pool, user_id, &clauses, offset).await?,
+        };
 
         Ok((transactions, total))
     }
@@ -819,158 +2870,538 @@ impl TransactionService {
         let limit = filters.limit.min(100);
         let offset = filters.offset;
 
+        // The path account is matched as source or destination below
+        let clauses = TransactionFilterClauses {
+            account_id: None,
+            ..TransactionFilterClauses::from(filters)
+        };
+
         // Execute list query (include transactions where this account is source OR destination)
-        let transactions = sqlx::query_as::<_, Transaction>(
+        let mut qb = QueryBuilder::<Postgres>::new(
             r#"
             SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
-                   t.description, t.transaction_type, t.created_at, t.updated_at
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at
             FROM transactions t
-            WHERE (t.account_id = $1 OR t.destination_account_id = $1)
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.category_id = $4)
-              AND ($5::text IS NULL OR t.transaction_type = $5)
-            ORDER BY t.transaction_date DESC, t.created_at DESC
-            LIMIT $6 OFFSET $7
-            "#,
-        )
-        .bind(account_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.category_id)
-        .bind(&filters.transaction_type)
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+            WHERE t.deleted_at IS NULL AND t.status = 'posted' AND (t.account_id = "#,
+        );
+        qb.push_bind(account_id)
+            .push(" OR t.destination_account_id = ")
+            .push_bind(account_id)
+            .push(")");
+        clauses.push(&mut qb);
+        qb.push(" ORDER BY t.transaction_date DESC, t.created_at DESC LIMIT ")
+            .push_bind(limit)
+            .push(" OFFSET ")
+            .push_bind(offset);
+
+        let transactions = qb
+            .build_query_as::<Transaction>()
+            .fetch_all(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?
+            .into_iter()
+            .map(Transaction::decrypted)
+            .collect::<Result<Vec<_>, _>>()?;
 
         // Execute count query
-        let total = sqlx::query_scalar::<_, i64>(
+        let mut qb = QueryBuilder::<Postgres>::new(
             r#"
             SELECT COUNT(*)
             FROM transactions t
-            WHERE (t.account_id = $1 OR t.destination_account_id = $1)
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.category_id = $4)
-              AND ($5::text IS NULL OR t.transaction_type = $5)
-            "#,
-        )
-        .bind(account_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.category_id)
-        .bind(&filters.transaction_type)
-        .fetch_one(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+            WHERE t.deleted_at IS NULL AND t.status = 'posted' AND (t.account_id = "#,
+        );
+        qb.push_bind(account_id)
+            .push(" OR t.destination_account_id = ")
+            .push_bind(account_id)
+            .push(")");
+        clauses.push(&mut qb);
+
+        let total = qb
+            .build_query_scalar::<i64>()
+            .fetch_one(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
 
         Ok((transactions, total))
     }
 
-    /// Get transaction summary with totals and category breakdown
-    pub async fn get_summary(
+    /// Existing transactions within the query's amount tolerance and date
+    /// window, scored by closeness, best first. Used by imports and
+    /// reconciliation to spot a transaction that was already entered with a
+    /// slightly different posted amount or date.
+    pub async fn find_matches(
         pool: &PgPool,
         user_id: Uuid,
-        filters: &SummaryFilters,
-    ) -> Result<(Decimal, Decimal, i64, Vec<CategorySummaryRow>), AppError> {
-        // Get total income
-        let total_income = sqlx::query_scalar::<_, Option<Decimal>>(
+        query: &MatchQuery,
+    ) -> Result<Vec<TransactionMatch>, AppError> {
+        let rows = sqlx::query_as::<_, MatchCandidateRow>(
             r#"
-            SELECT COALESCE(SUM(t.amount), 0)
+            SELECT t.id, t.category_id, t.account_id, t.destination_account_id, t.amount, t.transaction_date,
+                   t.description, t.transaction_type, t.reversal_of_id,
+                   t.reimbursable, t.reimbursement_status, t.reimbursement_payer, t.reimbursed_by_id,
+                   t.tax_category, t.original_amount, t.original_currency, t.exchange_rate, t.status, t.payee_id, t.fee_amount, t.destination_amount,
+                   t.created_at, t.updated_at,
+                   (t.transaction_date AT TIME ZONE u.timezone)::DATE AS local_date
             FROM transactions t
-            JOIN categories c ON t.category_id = c.id
-            JOIN budgets b ON c.budget_id = b.id
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id
+            INNER JOIN users u ON u.id = b.owner_id
             WHERE b.owner_id = $1
-              AND t.transaction_type = 'income'
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.account_id = $4)
+              AND t.deleted_at IS NULL AND t.status = 'posted'
+              AND t.amount BETWEEN $2 - $3 AND $2 + $3
+              AND t.transaction_date >= ($4::DATE - $5::INT)::TIMESTAMP AT TIME ZONE u.timezone
+              AND t.transaction_date < ($4::DATE + $5::INT + 1)::TIMESTAMP AT TIME ZONE u.timezone
+              AND ($6::UUID IS NULL OR t.account_id = $6 OR t.destination_account_id = $6)
+              AND ($7::TEXT IS NULL OR t.transaction_type = $7)
+            ORDER BY ABS(t.amount - $2), t.transaction_date DESC
+            LIMIT $8
             "#,
         )
         .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.account_id)
-        .fetch_one(pool)
+        .bind(query.amount)
+        .bind(query.tolerance)
+        .bind(query.date)
+        .bind(query.window as i32)
+        .bind(query.account_id)
+        .bind(query.transaction_type.map(|t| t.as_str()))
+        .bind(MATCH_SCAN_LIMIT)
+        .fetch_all(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
-        .unwrap_or(Decimal::ZERO);
+        .into_iter()
+        .map(|row| {
+            Ok(MatchCandidateRow {
+                transaction: row.transaction.decrypted()?,
+                local_date: row.local_date,
+            })
+        })
+        .collect::<Result<Vec<_>, AppError>>()?;
+
+        Ok(TransactionMatch::rank(rows, query))
+    }
 
-        // Get total expenses
-        let total_expenses = sqlx::query_scalar::<_, Option<Decimal>>(
+    /// Likely duplicates among the user's posted transactions: pairs on the
+    /// same account with the same amount and type within `window` days of
+    /// each other. Descriptions are encrypted at rest, so they are compared
+    /// after the self-join narrows the candidates down.
+    pub async fn find_duplicates(
+        pool: &PgPool,
+        user_id: Uuid,
+        query: &DuplicateQuery,
+    ) -> Result<Vec<DuplicatePair>, AppError> {
+        let rows = sqlx::query_as::<_, DuplicatePairRow>(
             r#"
-            SELECT COALESCE(SUM(t.amount), 0)
-            FROM transactions t
-            JOIN categories c ON t.category_id = c.id
-            JOIN budgets b ON c.budget_id = b.id
-            WHERE b.owner_id = $1
-              AND t.transaction_type = 'expense'
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.account_id = $4)
+            WITH candidates AS (
+                SELECT t.id, t.account_id, t.amount, t.transaction_type, t.transaction_date,
+                       t.reversal_of_id, t.created_at
+                FROM transactions t
+                INNER JOIN categories c ON t.category_id = c.id
+                INNER JOIN budgets b ON c.budget_id = b.id
+                WHERE b.owner_id = $1
+                  AND t.deleted_at IS NULL AND t.status = 'posted'
+                  AND t.account_id IS NOT NULL
+                  AND ($2::UUID IS NULL OR t.account_id = $2)
+            )
+            SELECT o.id AS original_id, d.id AS duplicate_id,
+                   (ABS(EXTRACT(EPOCH FROM d.transaction_date - o.transaction_date)) / 86400)::INT AS days_apart
+            FROM candidates o
+            INNER JOIN candidates d
+                ON d.account_id = o.account_id
+               AND d.amount = o.amount
+               AND d.transaction_type = o.transaction_type
+               AND (o.created_at, o.id) < (d.created_at, d.id)
+               AND d.transaction_date BETWEEN o.transaction_date - make_interval(days => $3)
+                                          AND o.transaction_date + make_interval(days => $3)
+               AND d.reversal_of_id IS DISTINCT FROM o.id
+               AND o.reversal_of_id IS DISTINCT FROM d.id
+            ORDER BY d.transaction_date DESC, d.id
+            LIMIT $4
             "#,
         )
         .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.account_id)
-        .fetch_one(pool)
+        .bind(query.account_id)
+        .bind(query.window as i32)
+        .bind(DUPLICATE_SCAN_LIMIT)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let ids: Vec<Uuid> = rows
+            .iter()
+            .flat_map(|row| [row.original_id, row.duplicate_id])
+            .collect();
+        let transactions = Self::fetch_by_ids(pool, &ids).await?;
+
+        Ok(DuplicatePair::rank(rows, &transactions, query))
+    }
+
+    /// Load transactions by id, decrypted, without any access check
+    async fn fetch_by_ids<'e, E>(
+        executor: E,
+        ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Transaction>, AppError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        sqlx::query_as::<_, Transaction>(
+            r#"
+            SELECT id, category_id, account_id, destination_account_id, amount, transaction_date, description,
+                   transaction_type, reversal_of_id,
+                   reimbursable, reimbursement_status, reimbursement_payer, reimbursed_by_id,
+                   tax_category, original_amount, original_currency, exchange_rate, status, payee_id, fee_amount, destination_amount, created_at, updated_at
+            FROM transactions
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(executor)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?
-        .unwrap_or(Decimal::ZERO);
+        .into_iter()
+        .map(|t| Ok((t.id, t.decrypted()?)))
+        .collect()
+    }
+
+    /// Merge a duplicate into the transaction kept. The duplicate moves to
+    /// the trash, reversing its own balance effects, so balances end up as if
+    /// it had never been entered; its description and payee fill in what the
+    /// kept transaction lacks.
+    /// CRITICAL: This operation MUST be atomic.
+    pub async fn merge_transaction(
+        pool: &PgPool,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        duplicate_id: Uuid,
+    ) -> Result<Transaction, AppError> {
+        if transaction_id == duplicate_id {
+            return Err(AppError::ValidationError(
+                "A transaction cannot be merged into itself".to_string(),
+            ));
+        }
+
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        // 1. Lock both; either missing is a 404
+        Self::lock_for_bulk(&mut tx, user_id, &[transaction_id, duplicate_id]).await?;
+        let mut transactions =
+            Self::fetch_by_ids(&mut *tx, &[transaction_id, duplicate_id]).await?;
+        let (Some(kept), Some(duplicate)) = (
+            transactions.remove(&transaction_id),
+            transactions.remove(&duplicate_id),
+        ) else {
+            return Err(AppError::NotFound("Transaction not found".to_string()));
+        };
+
+        // 2. Only the same money moving the same way is a duplicate
+        if kept.account_id != duplicate.account_id
+            || kept.destination_account_id != duplicate.destination_account_id
+            || kept.amount != duplicate.amount
+            || kept.transaction_type != duplicate.transaction_type
+        {
+            return Err(AppError::ValidationError(
+                "Only transactions with the same accounts, amount and type can be merged"
+                    .to_string(),
+            ));
+        }
+
+        // 3. Trash the duplicate, reversing its balance effects
+        Self::delete_in_tx(&mut tx, user_id, duplicate_id).await?;
+
+        // 4. Carry over what only the duplicate had
+        let description = match (&kept.description, duplicate.description) {
+            (None, Some(description)) => Some(description),
+            _ => None,
+        };
+        let payee_id = match (kept.payee_id, duplicate.payee_id) {
+            (None, Some(payee_id)) => Some(Some(payee_id)),
+            _ => None,
+        };
+        let merged = if description.is_some() || payee_id.is_some() {
+            Self::update_in_tx(
+                &mut tx,
+                user_id,
+                transaction_id,
+                UpdateTransactionDto {
+                    category_id: None,
+                    account_id: None,
+                    destination_account_id: None,
+                    amount: None,
+                    transaction_date: None,
+                    description,
+                    transaction_type: None,
+                    tax_category: None,
+                    payee_id,
+                    fee_amount: None,
+                    destination_amount: None,
+                },
+                &IfMatch::default(),
+            )
+            .await?
+            .decrypted()?
+        } else {
+            kept
+        };
+
+        tx.commit()
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        ResponseCache::invalidate_user(user_id);
+
+        Ok(merged)
+    }
+
+    /// Get transaction summary with totals and category breakdown
+    pub async fn get_summary(
+        pool: &PgPool,
+        user_id: Uuid,
+        filters: &SummaryFilters,
+    ) -> Result<(Decimal, Decimal, i64, Vec<CategorySummaryRow>), AppError> {
+        // Whole months without a date range or tag filter are served from the
+        // summary tables
+        if let (Some(month), Some(year), None, None, None) = (
+            filters.month,
+            filters.year,
+            filters.start_date,
+            filters.end_date,
+            &filters.tag_ids,
+        ) {
+            return Self::get_month_summary(pool, user_id, month, year, filters.account_id).await;
+        }
 
-        // Get total transaction count
-        let transaction_count = sqlx::query_scalar::<_, i64>(
+        // Resolve a calendar month to UTC bounds using the user's timezone, so
+        // late-evening transactions land in the month the user saw them in
+        let (period_start, period_end) =
+            Self::month_bounds(pool, user_id, filters.month, filters.year).await?;
+
+        let clauses = TransactionFilterClauses {
+            start_date: filters.start_date,
+            end_date: filters.end_date,
+            period_start,
+            period_end,
+            account_id: filters.account_id,
+            tag_ids: filters.tag_ids.as_deref(),
+            ..TransactionFilterClauses::default()
+        };
+
+        // Totals in one pass; independent of the category breakdown, so the two
+        // queries run concurrently
+        let mut totals_qb = QueryBuilder::<Postgres>::new(
             r#"
-            SELECT COUNT(*)
+            SELECT
+                COALESCE(SUM(t.reporting_amount) FILTER (WHERE t.reporting_type = 'income'), 0),
+                COALESCE(SUM(t.reporting_amount) FILTER (WHERE t.reporting_type = 'expense'), 0),
+                COUNT(*)
             FROM transactions t
             JOIN categories c ON t.category_id = c.id
             JOIN budgets b ON c.budget_id = b.id
-            WHERE b.owner_id = $1
-              AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-              AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-              AND ($4::uuid IS NULL OR t.account_id = $4)
+            WHERE t.deleted_at IS NULL AND t.status = 'posted' AND budget_access(b.id, "#,
+        );
+        totals_qb.push_bind(user_id).push(", FALSE)");
+        clauses.push(&mut totals_qb);
+        let totals = totals_qb
+            .build_query_as::<(Decimal, Decimal, i64)>()
+            .fetch_one(pool);
+
+        // Get category breakdown (expenses net of refunds, as that's most useful for spending analysis)
+        let mut by_category_qb = QueryBuilder::<Postgres>::new(
+            r#"
+            SELECT
+                c.id as category_id,
+                c.name as category_name,
+                c.color_hex as category_color_hex,
+                COALESCE(SUM(t.reporting_amount), 0) as total_amount,
+                COUNT(t.id) as transaction_count
+            FROM categories c
+            JOIN budgets b ON c.budget_id = b.id
+            JOIN transactions t ON t.category_id = c.id
+            WHERE t.reporting_type = 'expense' AND t.deleted_at IS NULL AND t.status = 'posted' AND budget_access(b.id, "#,
+        );
+        by_category_qb.push_bind(user_id).push(", FALSE)");
+        clauses.push(&mut by_category_qb);
+        by_category_qb.push(" GROUP BY c.id, c.name, c.color_hex ORDER BY total_amount DESC");
+        let by_category = by_category_qb
+            .build_query_as::<CategorySummaryRow>()
+            .fetch_all(pool);
+
+        let ((total_income, total_expenses, transaction_count), by_category) =
+            tokio::try_join!(totals, by_category)
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((total_income, total_expenses, transaction_count, by_category))
+    }
+
+    /// UTC bounds of a calendar month in the user's timezone, when both month
+    /// and year are given
+    async fn month_bounds(
+        pool: &PgPool,
+        user_id: Uuid,
+        month: Option<i16>,
+        year: Option<i16>,
+    ) -> Result<(Option<DateTime<Utc>>, Option<DateTime<Utc>>), AppError> {
+        let (Some(month), Some(year)) = (month, year) else {
+            return Ok((None, None));
+        };
+        let (start, end) = sqlx::query_as::<_, (DateTime<Utc>, DateTime<Utc>)>(
+            r#"
+            SELECT
+                make_timestamp($2, $3 + 1, 1, 0, 0, 0) AT TIME ZONE u.timezone,
+                (make_timestamp($2, $3 + 1, 1, 0, 0, 0) + INTERVAL '1 month') AT TIME ZONE u.timezone
+            FROM users u
+            WHERE u.id = $1
             "#,
         )
         .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.account_id)
+        .bind(year as i32)
+        .bind(month as i32)
         .fetch_one(pool)
         .await
         .map_err(|e| AppError::InternalError(e.to_string()))?;
+        Ok((Some(start), Some(end)))
+    }
+
+    /// Income, expense and transfer totals per account over a period, largest
+    /// spenders first. Transfers count out of the source account and into the
+    /// destination; transactions without an account are grouped together.
+    pub async fn get_summary_by_account(
+        pool: &PgPool,
+        user_id: Uuid,
+        filters: &AccountSummaryFilters,
+    ) -> Result<Vec<AccountSpendingSummary>, AppError> {
+        let (period_start, period_end) =
+            Self::month_bounds(pool, user_id, filters.month, filters.year).await?;
+        let clauses = TransactionFilterClauses {
+            start_date: filters.start_date,
+            end_date: filters.end_date,
+            period_start,
+            period_end,
+            tag_ids: filters.tag_ids.as_deref(),
+            ..TransactionFilterClauses::default()
+        };
+
+        // One leg per account a transaction touches: the account itself, plus
+        // the destination of a transfer
+        let mut qb = QueryBuilder::<Postgres>::new(
+            r#"
+            WITH legs AS (
+                SELECT t.account_id, t.reporting_type AS leg_type, t.reporting_amount + COALESCE(t.fee_amount, 0) AS amount
+                FROM transactions t
+                JOIN categories c ON t.category_id = c.id
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE t.deleted_at IS NULL AND t.status = 'posted' AND b.owner_id = "#,
+        );
+        qb.push_bind(user_id);
+        clauses.push(&mut qb);
+        qb.push(
+            r#"
+                UNION ALL
+                SELECT t.destination_account_id, 'transfer_in', COALESCE(t.destination_amount, t.amount)
+                FROM transactions t
+                JOIN categories c ON t.category_id = c.id
+                JOIN budgets b ON c.budget_id = b.id
+                WHERE t.deleted_at IS NULL AND t.status = 'posted' AND t.transaction_type = 'transfer'
+                  AND t.destination_account_id IS NOT NULL AND b.owner_id = "#,
+        );
+        qb.push_bind(user_id);
+        clauses.push(&mut qb);
+        qb.push(
+            r#"
+            )
+            SELECT
+                l.account_id,
+                a.name AS account_name,
+                a.color_hex AS account_color_hex,
+                a.currency,
+                COALESCE(SUM(l.amount) FILTER (WHERE l.leg_type = 'income'), 0) AS total_income,
+                COALESCE(SUM(l.amount) FILTER (WHERE l.leg_type = 'expense'), 0) AS total_expenses,
+                COALESCE(SUM(l.amount) FILTER (WHERE l.leg_type = 'transfer'), 0) AS transfers_out,
+                COALESCE(SUM(l.amount) FILTER (WHERE l.leg_type = 'transfer_in'), 0) AS transfers_in,
+                COUNT(*) AS transaction_count
+            FROM legs l
+            LEFT JOIN accounts a ON a.id = l.account_id
+            GROUP BY l.account_id, a.name, a.color_hex, a.currency
+            ORDER BY total_expenses DESC, transaction_count DESC
+            "#,
+        );
+
+        qb.build_query_as::<AccountSummaryRow>()
+            .fetch_all(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?
+            .into_iter()
+            .map(AccountSpendingSummary::from_row)
+            .collect()
+    }
+
+    /// Summary for one calendar month, read from the monthly summary tables.
+    /// Per-account totals come from `monthly_account_totals`, which is also
+    /// broken down by category. Shared budgets are summed under their owner,
+    /// so members read them through the category's budget.
+    async fn get_month_summary(
+        pool: &PgPool,
+        user_id: Uuid,
+        month: i16,
+        year: i16,
+        account_id: Option<Uuid>,
+    ) -> Result<(Decimal, Decimal, i64, Vec<CategorySummaryRow>), AppError> {
+        SummaryService::refresh_dirty_for_reader(pool, user_id).await?;
+
+        let (table, account_filter) = match account_id {
+            Some(_) => ("monthly_account_totals", "AND s.account_id = $4"),
+            None => ("monthly_category_totals", "AND $4::UUID IS NULL"),
+        };
+
+        let totals_sql = format!(
+            r#"
+                SELECT
+                    COALESCE(SUM(s.total) FILTER (WHERE s.transaction_type = 'income'), 0),
+                    COALESCE(SUM(s.total) FILTER (WHERE s.transaction_type = 'expense'), 0),
+                    COALESCE(SUM(s.transaction_count), 0)::BIGINT
+                FROM {table} s
+                JOIN categories c ON s.category_id = c.id
+                WHERE budget_access(c.budget_id, $1, FALSE)
+                  AND s.year = $2 AND s.month = $3 {account_filter}
+                "#
+        );
+        let totals = sqlx::query_as::<_, (Decimal, Decimal, i64)>(&totals_sql)
+            .bind(user_id)
+            .bind(year)
+            .bind(month)
+            .bind(account_id)
+            .fetch_one(pool);
 
-        // Get category breakdown (expenses only, as that's most useful for spending analysis)
-        let by_category = sqlx::query_as::<_, CategorySummaryRow>(
+        let by_category_sql = format!(
             r#"
             SELECT
                 c.id as category_id,
                 c.name as category_name,
                 c.color_hex as category_color_hex,
-                COALESCE(SUM(t.amount), 0) as total_amount,
-                COUNT(t.id) as transaction_count
-            FROM categories c
-            JOIN budgets b ON c.budget_id = b.id
-            LEFT JOIN transactions t ON t.category_id = c.id
-                AND t.transaction_type = 'expense'
-                AND ($2::timestamptz IS NULL OR t.transaction_date >= $2)
-                AND ($3::timestamptz IS NULL OR t.transaction_date <= $3)
-                AND ($4::uuid IS NULL OR t.account_id = $4)
-            WHERE b.owner_id = $1
+                SUM(s.total) as total_amount,
+                SUM(s.transaction_count)::BIGINT as transaction_count
+            FROM {table} s
+            JOIN categories c ON s.category_id = c.id
+            WHERE budget_access(c.budget_id, $1, FALSE)
+              AND s.year = $2 AND s.month = $3 {account_filter}
+              AND s.transaction_type = 'expense'
             GROUP BY c.id, c.name, c.color_hex
-            HAVING COUNT(t.id) > 0
             ORDER BY total_amount DESC
-            "#,
-        )
-        .bind(user_id)
-        .bind(filters.start_date)
-        .bind(filters.end_date)
-        .bind(filters.account_id)
-        .fetch_all(pool)
-        .await
-        .map_err(|e| AppError::InternalError(e.to_string()))?;
+            "#
+        );
+        let by_category = sqlx::query_as::<_, CategorySummaryRow>(&by_category_sql)
+            .bind(user_id)
+            .bind(year)
+            .bind(month)
+            .bind(account_id)
+            .fetch_all(pool);
+
+        let ((total_income, total_expenses, transaction_count), by_category) =
+            tokio::try_join!(totals, by_category)
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
 
         Ok((total_income, total_expenses, transaction_count, by_category))
     }
@@ -0,0 +1,123 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use super::models::{Transaction, TransactionResponse};
+
+fn default_limit() -> i64 {
+    50
+}
+
+/// Query parameters for listing the trash
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashQuery {
+    /// Maximum results (1-100)
+    #[validate(range(min = 1, max = 100))]
+    #[serde(default = "default_limit")]
+    #[param(example = 50)]
+    pub limit: i64,
+
+    /// Number of results to skip
+    #[validate(range(min = 0))]
+    #[serde(default)]
+    #[param(example = 0)]
+    pub offset: i64,
+}
+
+/// A deleted transaction with the time it was deleted
+#[derive(Debug, FromRow)]
+pub struct TrashedTransactionRow {
+    #[sqlx(flatten)]
+    pub transaction: Transaction,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// A transaction in the trash
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashedTransactionResponse {
+    #[serde(flatten)]
+    pub transaction: TransactionResponse,
+    /// When the transaction was deleted
+    pub deleted_at: DateTime<Utc>,
+    /// When it will be purged for good (absent when the trash is kept indefinitely)
+    pub purge_at: Option<DateTime<Utc>>,
+}
+
+impl TrashedTransactionResponse {
+    /// Build from a row, given the trash retention window in days (None keeps it forever)
+    pub fn new(row: TrashedTransactionRow, retention_days: Option<i32>) -> Self {
+        Self {
+            transaction: TransactionResponse::from(row.transaction),
+            deleted_at: row.deleted_at,
+            purge_at: retention_days.map(|days| row.deleted_at + Duration::days(i64::from(days))),
+        }
+    }
+}
+
+/// Paginated trash listing
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PaginatedTrashResponse {
+    /// Deleted transactions, most recently deleted first
+    pub data: Vec<TrashedTransactionResponse>,
+    /// Total count in the trash
+    #[schema(example = 3)]
+    pub total: i64,
+    /// Limit used
+    #[schema(example = 50)]
+    pub limit: i64,
+    /// Offset used
+    #[schema(example = 0)]
+    pub offset: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_purge_at_follows_retention_window() {
+        let deleted_at = "2026-03-01T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let row = || TrashedTransactionRow {
+            transaction: Transaction {
+                id: Uuid::new_v4(),
+                category_id: Uuid::new_v4(),
+                account_id: None,
+                destination_account_id: None,
+                amount: Decimal::from(25),
+                transaction_date: deleted_at,
+                description: None,
+                transaction_type: "expense".to_string(),
+                reversal_of_id: None,
+                reimbursable: false,
+                reimbursement_status: None,
+                reimbursement_payer: None,
+                reimbursed_by_id: None,
+                tax_category: None,
+                original_amount: None,
+                original_currency: None,
+                exchange_rate: None,
+                status: "posted".to_string(),
+                payee_id: None,
+                fee_amount: None,
+                destination_amount: None,
+                created_at: deleted_at,
+                updated_at: deleted_at,
+            },
+            deleted_at,
+        };
+
+        let response = TrashedTransactionResponse::new(row(), Some(30));
+        assert_eq!(
+            response.purge_at,
+            Some("2026-03-31T12:00:00Z".parse::<DateTime<Utc>>().unwrap())
+        );
+        assert_eq!(TrashedTransactionResponse::new(row(), None).purge_at, None);
+    }
+}
@@ -0,0 +1,46 @@
+use image::imageops::FilterType;
+use image::ImageFormat;
+use std::io::Cursor;
+
+use crate::errors::AppError;
+
+/// Decode an image and render centered square crops at each size, encoded as PNG.
+/// CPU-bound; call from `web::block` or `spawn_blocking`.
+pub fn square_thumbnails(bytes: &[u8], sizes: &[u32]) -> Result<Vec<(u32, Vec<u8>)>, AppError> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| AppError::ValidationError(format!("Unreadable image: {e}")))?;
+
+    sizes
+        .iter()
+        .map(|&size| {
+            let mut encoded = Vec::new();
+            image
+                .resize_to_fill(size, size, FilterType::Lanczos3)
+                .write_to(&mut Cursor::new(&mut encoded), ImageFormat::Png)
+                .map_err(|e| AppError::InternalError(format!("Failed to encode image: {e}")))?;
+            Ok((size, encoded))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, GenericImageView, RgbImage};
+
+    #[test]
+    fn test_square_thumbnails_crop_to_requested_sizes() {
+        let mut source = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(300, 120))
+            .write_to(&mut Cursor::new(&mut source), ImageFormat::Png)
+            .unwrap();
+
+        let thumbnails = square_thumbnails(&source, &[64, 128]).unwrap();
+
+        assert_eq!(thumbnails.len(), 2);
+        for (size, bytes) in thumbnails {
+            let decoded = image::load_from_memory(&bytes).unwrap();
+            assert_eq!(decoded.dimensions(), (size, size));
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod images;
+pub mod scanner;
+pub mod validation;
@@ -0,0 +1,168 @@
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::errors::AppError;
+
+/// Upper bound on a single scan so a hung scanner can't stall uploads
+const SCAN_TIMEOUT: Duration = Duration::from_secs(60);
+/// Chunk size for the clamd INSTREAM protocol
+const CLAMD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Result of scanning a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// Threat detected, with the scanner's signature name
+    Infected(String),
+}
+
+/// Pluggable malware scanning hook, run on an upload before it is stored
+#[derive(Debug, Clone)]
+pub enum Scanner {
+    /// No scanning; every file is treated as clean
+    Disabled,
+    /// External command reading the file on stdin.
+    /// Exit code 0 means clean and 1 means infected (clamscan/clamdscan convention).
+    Command { program: String, args: Vec<String> },
+    /// clamd daemon reached over TCP using the INSTREAM protocol
+    Clamd { addr: String },
+}
+
+impl Scanner {
+    /// Scan file contents. Scanner failures are errors, never an implicit "clean".
+    pub async fn scan(&self, bytes: &[u8]) -> Result<ScanVerdict, AppError> {
+        let scan = async {
+            match self {
+                Scanner::Disabled => Ok(ScanVerdict::Clean),
+                Scanner::Command { program, args } => scan_with_command(program, args, bytes).await,
+                Scanner::Clamd { addr } => scan_with_clamd(addr, bytes).await,
+            }
+        };
+
+        timeout(SCAN_TIMEOUT, scan)
+            .await
+            .map_err(|_| AppError::InternalError("Malware scan timed out".to_string()))?
+    }
+}
+
+async fn scan_with_command(
+    program: &str,
+    args: &[String],
+    bytes: &[u8],
+) -> Result<ScanVerdict, AppError> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| AppError::InternalError(format!("Failed to start scanner: {e}")))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // A scanner may exit before consuming all input; its exit code still decides
+        match stdin.write_all(bytes).await {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                return Err(AppError::InternalError(format!(
+                    "Failed to send file to scanner: {e}"
+                )));
+            }
+            _ => {}
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| AppError::InternalError(format!("Scanner failed: {e}")))?;
+
+    match output.status.code() {
+        Some(0) => Ok(ScanVerdict::Clean),
+        Some(1) => Ok(ScanVerdict::Infected(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        )),
+        code => Err(AppError::InternalError(format!(
+            "Scanner exited with unexpected status {:?}",
+            code
+        ))),
+    }
+}
+
+async fn scan_with_clamd(addr: &str, bytes: &[u8]) -> Result<ScanVerdict, AppError> {
+    let io_err = |e: std::io::Error| AppError::InternalError(format!("clamd error: {e}"));
+
+    let mut stream = TcpStream::connect(addr).await.map_err(io_err)?;
+    stream.write_all(b"zINSTREAM\0").await.map_err(io_err)?;
+    for chunk in bytes.chunks(CLAMD_CHUNK_SIZE) {
+        stream
+            .write_all(&(chunk.len() as u32).to_be_bytes())
+            .await
+            .map_err(io_err)?;
+        stream.write_all(chunk).await.map_err(io_err)?;
+    }
+    stream
+        .write_all(&0u32.to_be_bytes())
+        .await
+        .map_err(io_err)?;
+
+    let mut reply = Vec::new();
+    stream.read_to_end(&mut reply).await.map_err(io_err)?;
+
+    parse_clamd_reply(&String::from_utf8_lossy(&reply))
+}
+
+/// Parse replies like `stream: OK` or `stream: Eicar-Signature FOUND`
+fn parse_clamd_reply(reply: &str) -> Result<ScanVerdict, AppError> {
+    let reply = reply.trim_end_matches('\0').trim();
+    let result = reply.strip_prefix("stream:").unwrap_or(reply).trim();
+
+    if result == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(signature) = result.strip_suffix("FOUND") {
+        Ok(ScanVerdict::Infected(signature.trim().to_string()))
+    } else {
+        Err(AppError::InternalError(format!(
+            "Unexpected clamd reply: {reply}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clamd_reply() {
+        assert_eq!(
+            parse_clamd_reply("stream: OK\0").unwrap(),
+            ScanVerdict::Clean
+        );
+        assert_eq!(
+            parse_clamd_reply("stream: Eicar-Test-Signature FOUND\0").unwrap(),
+            ScanVerdict::Infected("Eicar-Test-Signature".to_string())
+        );
+        assert!(parse_clamd_reply("INSTREAM size limit exceeded. ERROR\0").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_command_scanner_exit_codes() {
+        let clean = Scanner::Command {
+            program: "true".to_string(),
+            args: vec![],
+        };
+        assert_eq!(clean.scan(b"data").await.unwrap(), ScanVerdict::Clean);
+
+        let infected = Scanner::Command {
+            program: "false".to_string(),
+            args: vec![],
+        };
+        assert!(matches!(
+            infected.scan(b"data").await.unwrap(),
+            ScanVerdict::Infected(_)
+        ));
+    }
+}
@@ -0,0 +1,151 @@
+use image::{ImageFormat, ImageReader};
+use std::io::Cursor;
+
+use crate::errors::AppError;
+
+/// Limits applied to an uploaded file before it is stored
+#[derive(Debug, Clone, Copy)]
+pub struct UploadPolicy {
+    /// MIME types accepted, detected from file content rather than client headers
+    pub allowed_mime_types: &'static [&'static str],
+    /// Maximum file size in bytes
+    pub max_bytes: usize,
+    /// Maximum image width in pixels (images only)
+    pub max_image_width: u32,
+    /// Maximum image height in pixels (images only)
+    pub max_image_height: u32,
+}
+
+/// Limits for profile avatars
+pub const AVATAR_POLICY: UploadPolicy = UploadPolicy {
+    allowed_mime_types: &["image/jpeg", "image/png", "image/webp"],
+    max_bytes: 5 * 1024 * 1024,
+    max_image_width: 4096,
+    max_image_height: 4096,
+};
+
+/// Metadata for an upload that passed validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatedUpload {
+    /// MIME type detected from the file's magic bytes
+    pub mime_type: &'static str,
+    /// Image dimensions (None for non-image files)
+    pub dimensions: Option<(u32, u32)>,
+}
+
+/// Detect a MIME type from magic bytes; None when the format is unrecognised
+fn sniff_mime_type(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"%PDF-") {
+        return Some("application/pdf");
+    }
+    match image::guess_format(bytes).ok()? {
+        ImageFormat::Jpeg => Some("image/jpeg"),
+        ImageFormat::Png => Some("image/png"),
+        ImageFormat::WebP => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Check size, content type and image dimensions against a policy
+pub fn validate_upload(bytes: &[u8], policy: &UploadPolicy) -> Result<ValidatedUpload, AppError> {
+    if bytes.is_empty() {
+        return Err(AppError::ValidationError(
+            "Uploaded file is empty".to_string(),
+        ));
+    }
+    if bytes.len() > policy.max_bytes {
+        return Err(AppError::ValidationError(format!(
+            "File exceeds maximum size of {} bytes",
+            policy.max_bytes
+        )));
+    }
+
+    let mime_type = sniff_mime_type(bytes)
+        .filter(|mime| policy.allowed_mime_types.contains(mime))
+        .ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "Unsupported file type. Allowed types: {}",
+                policy.allowed_mime_types.join(", ")
+            ))
+        })?;
+
+    // Reads only the header, so oversized images are rejected before decoding
+    let dimensions = if mime_type.starts_with("image/") {
+        let (width, height) = ImageReader::new(Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(|e| AppError::ValidationError(format!("Unreadable image: {e}")))?
+            .into_dimensions()
+            .map_err(|e| AppError::ValidationError(format!("Unreadable image: {e}")))?;
+
+        if width > policy.max_image_width || height > policy.max_image_height {
+            return Err(AppError::ValidationError(format!(
+                "Image dimensions {}x{} exceed maximum of {}x{}",
+                width, height, policy.max_image_width, policy.max_image_height
+            )));
+        }
+        Some((width, height))
+    } else {
+        None
+    };
+
+    Ok(ValidatedUpload {
+        mime_type,
+        dimensions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, RgbImage};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(width, height))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_accepts_png_and_reports_dimensions() {
+        let upload = validate_upload(&png(40, 20), &AVATAR_POLICY).unwrap();
+        assert_eq!(upload.mime_type, "image/png");
+        assert_eq!(upload.dimensions, Some((40, 20)));
+    }
+
+    #[test]
+    fn test_accepts_pdf_where_allowed() {
+        let policy = UploadPolicy {
+            allowed_mime_types: &["application/pdf"],
+            ..AVATAR_POLICY
+        };
+        assert!(validate_upload(b"%PDF-1.7\n...", &AVATAR_POLICY).is_err());
+        let upload = validate_upload(b"%PDF-1.7\n...", &policy).unwrap();
+        assert_eq!(upload.mime_type, "application/pdf");
+        assert_eq!(upload.dimensions, None);
+    }
+
+    #[test]
+    fn test_rejects_unknown_content() {
+        assert!(validate_upload(b"MZ\x90\x00 not an image", &AVATAR_POLICY).is_err());
+    }
+
+    #[test]
+    fn test_rejects_oversized_file() {
+        let policy = UploadPolicy {
+            max_bytes: 16,
+            ..AVATAR_POLICY
+        };
+        assert!(validate_upload(&png(4, 4), &policy).is_err());
+    }
+
+    #[test]
+    fn test_rejects_oversized_dimensions() {
+        let policy = UploadPolicy {
+            max_image_width: 10,
+            ..AVATAR_POLICY
+        };
+        assert!(validate_upload(&png(11, 5), &policy).is_err());
+    }
+}
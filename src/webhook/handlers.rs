@@ -0,0 +1,303 @@
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+use sqlx::PgPool;
+use validator::Validate;
+
+use crate::account::models::DeleteResponse;
+use crate::auth::scopes::Scope;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::{
+    CreateWebhookDto, DeliveryHistoryQuery, ReplayQuery, ReplayResponse, UpdateWebhookDto,
+    WebhookDelivery, WebhookDeliveryListResponse, WebhookIdPath, WebhookResponse,
+    WebhookSecretResponse,
+};
+use super::service::WebhookService;
+
+/// GET /webhooks - List webhook endpoints
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "Webhooks",
+    responses(
+        (status = 200, description = "List of webhook endpoints", body = Vec<WebhookResponse>),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/webhooks")]
+pub async fn list_webhooks(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksRead)?;
+
+    let webhooks = WebhookService::list(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(
+        webhooks
+            .into_iter()
+            .map(WebhookResponse::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// GET /webhooks/{id} - Get a webhook endpoint
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "Webhooks",
+    params(WebhookIdPath),
+    responses(
+        (status = 200, description = "Webhook details", body = WebhookResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/webhooks/{id}")]
+pub async fn get_webhook(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksRead)?;
+
+    let webhook = WebhookService::get(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(WebhookResponse::from(webhook)))
+}
+
+/// POST /webhooks - Register a webhook endpoint
+///
+/// The response contains the signing secret, which is not shown again.
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "Webhooks",
+    request_body = CreateWebhookDto,
+    responses(
+        (status = 201, description = "Webhook registered", body = WebhookSecretResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/webhooks")]
+pub async fn create_webhook(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    body: web::Json<CreateWebhookDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksWrite)?;
+
+    body.validate()?;
+
+    let webhook = WebhookService::create(pool.get_ref(), auth.user_id, &body).await?;
+
+    Ok(HttpResponse::Created().json(WebhookSecretResponse::from(webhook)))
+}
+
+/// POST /webhooks/{id}/rotate-secret - Issue a new signing secret
+///
+/// Deliveries are signed with both the new and the old secret for 24 hours.
+#[utoipa::path(
+    post,
+    path = "/webhooks/{id}/rotate-secret",
+    tag = "Webhooks",
+    params(WebhookIdPath),
+    responses(
+        (status = 200, description = "New secret issued", body = WebhookSecretResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/webhooks/{id}/rotate-secret")]
+pub async fn rotate_webhook_secret(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksWrite)?;
+
+    let webhook = WebhookService::rotate_secret(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(WebhookSecretResponse::from(webhook)))
+}
+
+/// PATCH /webhooks/{id} - Update a webhook endpoint
+#[utoipa::path(
+    patch,
+    path = "/webhooks/{id}",
+    tag = "Webhooks",
+    params(WebhookIdPath),
+    request_body = UpdateWebhookDto,
+    responses(
+        (status = 200, description = "Webhook updated", body = WebhookResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/webhooks/{id}")]
+pub async fn update_webhook(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+    body: web::Json<UpdateWebhookDto>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksWrite)?;
+
+    body.validate()?;
+
+    let webhook =
+        WebhookService::update(pool.get_ref(), path.id, auth.user_id, body.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(WebhookResponse::from(webhook)))
+}
+
+/// DELETE /webhooks/{id} - Delete a webhook endpoint and its delivery history
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "Webhooks",
+    params(WebhookIdPath),
+    responses(
+        (status = 200, description = "Webhook deleted", body = DeleteResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/webhooks/{id}")]
+pub async fn delete_webhook(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksWrite)?;
+
+    WebhookService::delete(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DeleteResponse {
+        message: "Webhook deleted successfully".to_string(),
+        id: path.id,
+    }))
+}
+
+/// GET /webhooks/{id}/deliveries - Delivery history with response codes
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "Webhooks",
+    params(WebhookIdPath, DeliveryHistoryQuery),
+    responses(
+        (status = 200, description = "Delivery attempts, newest first", body = WebhookDeliveryListResponse),
+        (status = 400, description = "Validation error", body = ErrorResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/webhooks/{id}/deliveries")]
+pub async fn list_webhook_deliveries(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+    query: web::Query<DeliveryHistoryQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksRead)?;
+
+    query.validate()?;
+
+    let (data, total) =
+        WebhookService::list_deliveries(pool.get_ref(), path.id, auth.user_id, &query).await?;
+
+    Ok(HttpResponse::Ok().json(WebhookDeliveryListResponse {
+        data,
+        total,
+        limit: query.limit,
+        offset: query.offset,
+    }))
+}
+
+/// POST /webhooks/{id}/test - Send a signed `webhook.test` event
+///
+/// Delivers immediately, even to disabled endpoints, and returns the attempt.
+#[utoipa::path(
+    post,
+    path = "/webhooks/{id}/test",
+    tag = "Webhooks",
+    params(WebhookIdPath),
+    responses(
+        (status = 200, description = "Delivery attempt; check succeeded and statusCode", body = WebhookDelivery),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/webhooks/{id}/test")]
+pub async fn test_webhook(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksWrite)?;
+
+    let delivery = WebhookService::send_test(pool.get_ref(), path.id, auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(delivery))
+}
+
+/// POST /webhooks/{id}/replay - Redeliver logged events from a point in time
+///
+/// Deliveries run in the background; follow progress in the delivery history.
+#[utoipa::path(
+    post,
+    path = "/webhooks/{id}/replay",
+    tag = "Webhooks",
+    params(WebhookIdPath, ReplayQuery),
+    responses(
+        (status = 202, description = "Events queued for redelivery", body = ReplayResponse),
+        (status = 404, description = "Webhook not found", body = ErrorResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/webhooks/{id}/replay")]
+pub async fn replay_webhook(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    path: web::Path<WebhookIdPath>,
+    query: web::Query<ReplayQuery>,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::WebhooksWrite)?;
+
+    let replay = WebhookService::replay(pool.get_ref(), path.id, auth.user_id, query.from).await?;
+
+    Ok(HttpResponse::Accepted().json(replay))
+}
+
+/// Register the webhook routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(list_webhooks)
+        .service(create_webhook)
+        .service(get_webhook)
+        .service(update_webhook)
+        .service(delete_webhook)
+        .service(list_webhook_deliveries)
+        .service(rotate_webhook_secret)
+        .service(test_webhook)
+        .service(replay_webhook);
+}
@@ -0,0 +1,6 @@
+pub mod handlers;
+pub mod models;
+pub mod service;
+pub mod signature;
+
+pub use handlers::*;
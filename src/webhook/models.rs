@@ -0,0 +1,371 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::crypto::{decrypt_field, decrypt_optional};
+use crate::errors::AppError;
+use crate::event::models::EventResponse;
+
+/// Event types delivered to webhooks
+pub mod events {
+    pub const TRANSACTION_CREATED: &str = "transaction.created";
+    pub const TRANSACTION_UPDATED: &str = "transaction.updated";
+    pub const TRANSACTION_DELETED: &str = "transaction.deleted";
+    /// An account's balance moved, through a transaction or a manual change
+    pub const ACCOUNT_BALANCE_CHANGED: &str = "account.balance_changed";
+    /// Raised by the integrity audit when a stored balance disagrees with history
+    pub const ACCOUNT_BALANCE_DRIFT: &str = "account.balance_drift";
+    /// A bill's reminder window opened
+    pub const BILL_DUE: &str = "bill.due";
+    /// A bill's due date passed without a payment
+    pub const BILL_OVERDUE: &str = "bill.overdue";
+    /// A bill payment was recorded, by hand or by autopay
+    pub const BILL_PAID: &str = "bill.paid";
+    /// A forwarded receipt was stored as a draft awaiting confirmation
+    pub const RECEIPT_RECEIVED: &str = "receipt.received";
+    /// Sent on demand to check an endpoint; not subscribable
+    pub const WEBHOOK_TEST: &str = "webhook.test";
+
+    /// Every event type an endpoint can subscribe to
+    pub const ALL: &[&str] = &[
+        TRANSACTION_CREATED,
+        TRANSACTION_UPDATED,
+        TRANSACTION_DELETED,
+        ACCOUNT_BALANCE_CHANGED,
+        ACCOUNT_BALANCE_DRIFT,
+        BILL_DUE,
+        BILL_OVERDUE,
+        BILL_PAID,
+        RECEIPT_RECEIVED,
+    ];
+}
+
+/// How long the previous secret keeps signing deliveries after a rotation
+pub const PREVIOUS_SECRET_GRACE_HOURS: i64 = 24;
+
+fn validate_event_types(event_types: &[String]) -> Result<(), ValidationError> {
+    if event_types
+        .iter()
+        .all(|t| events::ALL.contains(&t.as_str()))
+    {
+        Ok(())
+    } else {
+        Err(ValidationError::new("unknown_event_type"))
+    }
+}
+
+fn default_delivery_limit() -> i64 {
+    20
+}
+
+fn validate_webhook_url(url: &str) -> Result<(), ValidationError> {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        Ok(())
+    } else {
+        Err(ValidationError::new("unsupported_scheme"))
+    }
+}
+
+/// Database entity for webhook endpoints
+#[derive(Debug, Clone, FromRow)]
+pub struct WebhookEndpoint {
+    pub id: Uuid,
+    #[allow(dead_code)]
+    pub owner_id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub previous_secret: Option<String>,
+    pub secret_rotated_at: Option<DateTime<Utc>>,
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookEndpoint {
+    /// Decrypt the signing secrets after loading the row
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.secret = decrypt_field(&self.secret)?;
+        self.previous_secret = decrypt_optional(self.previous_secret)?;
+        Ok(self)
+    }
+
+    /// When the previous secret stops signing deliveries, if one is still valid
+    pub fn previous_secret_expires_at(&self) -> Option<DateTime<Utc>> {
+        self.previous_secret.as_ref()?;
+        self.secret_rotated_at
+            .map(|at| at + Duration::hours(PREVIOUS_SECRET_GRACE_HOURS))
+    }
+
+    /// Secrets to sign with: the current one, plus the previous one during the grace period
+    pub fn signing_secrets(&self, now: DateTime<Utc>) -> Vec<&str> {
+        let mut secrets = vec![self.secret.as_str()];
+        if let (Some(previous), Some(expires_at)) =
+            (&self.previous_secret, self.previous_secret_expires_at())
+        {
+            if now < expires_at {
+                secrets.push(previous);
+            }
+        }
+        secrets
+    }
+}
+
+/// Webhook endpoint returned in responses (secrets are never echoed back)
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookResponse {
+    pub id: Uuid,
+    #[schema(example = "https://example.com/hooks/budget")]
+    pub url: String,
+    /// Subscribed event types; empty means every event
+    #[schema(example = json!(["transaction.created"]))]
+    pub event_types: Vec<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<WebhookEndpoint> for WebhookResponse {
+    fn from(w: WebhookEndpoint) -> Self {
+        Self {
+            id: w.id,
+            url: w.url,
+            event_types: w.event_types,
+            enabled: w.enabled,
+            created_at: w.created_at,
+            updated_at: w.updated_at,
+        }
+    }
+}
+
+/// Webhook endpoint with its signing secret, returned only when the secret is issued
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookSecretResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+    /// Secret used to sign deliveries; store it now, it is not shown again
+    #[schema(example = "whsec_3f1c...")]
+    pub secret: String,
+    /// Until when deliveries are also signed with the replaced secret
+    pub previous_secret_expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookEndpoint> for WebhookSecretResponse {
+    fn from(w: WebhookEndpoint) -> Self {
+        let previous_secret_expires_at = w.previous_secret_expires_at();
+        let secret = w.secret.clone();
+        Self {
+            webhook: WebhookResponse::from(w),
+            secret,
+            previous_secret_expires_at,
+        }
+    }
+}
+
+/// Request body for registering a webhook endpoint
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookDto {
+    /// Destination URL (http or https)
+    #[validate(url(message = "url must be a valid URL"))]
+    #[validate(custom(
+        function = "validate_webhook_url",
+        message = "url must use http or https"
+    ))]
+    #[validate(length(max = 2000, message = "url cannot exceed 2000 characters"))]
+    #[schema(example = "https://example.com/hooks/budget")]
+    pub url: String,
+
+    /// Event types to deliver (defaults to every event)
+    #[validate(custom(function = "validate_event_types", message = "Unknown event type"))]
+    #[serde(default)]
+    #[schema(example = json!(["transaction.created", "transaction.deleted"]))]
+    pub event_types: Vec<String>,
+}
+
+/// Request body for updating a webhook endpoint (PATCH - all fields optional)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateWebhookDto {
+    /// Destination URL (http or https)
+    #[validate(url(message = "url must be a valid URL"))]
+    #[validate(custom(
+        function = "validate_webhook_url",
+        message = "url must use http or https"
+    ))]
+    #[validate(length(max = 2000, message = "url cannot exceed 2000 characters"))]
+    pub url: Option<String>,
+
+    /// Event types to deliver (empty for every event)
+    #[validate(custom(function = "validate_event_types", message = "Unknown event type"))]
+    pub event_types: Option<Vec<String>>,
+
+    /// Pause or resume deliveries
+    pub enabled: Option<bool>,
+}
+
+/// Path parameters for webhook ID
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WebhookIdPath {
+    /// Webhook UUID
+    pub id: Uuid,
+}
+
+/// Query parameters for delivery history
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryHistoryQuery {
+    /// Maximum results (1-100)
+    #[validate(range(min = 1, max = 100))]
+    #[serde(default = "default_delivery_limit")]
+    #[param(example = 20)]
+    pub limit: i64,
+
+    /// Number of results to skip
+    #[validate(range(min = 0))]
+    #[serde(default)]
+    #[param(example = 0)]
+    pub offset: i64,
+}
+
+/// One delivery attempt to a webhook endpoint
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    /// ID of the delivered event
+    pub event_id: Uuid,
+    #[schema(example = "transaction.created")]
+    pub event_type: String,
+    /// HTTP status returned by the receiver, absent when it could not be reached
+    #[schema(example = 200)]
+    pub status_code: Option<i32>,
+    /// Whether the receiver answered with a 2xx status
+    pub succeeded: bool,
+    /// Transport error when the receiver could not be reached
+    #[schema(example = "operation timed out")]
+    pub error: Option<String>,
+    #[schema(example = 142)]
+    pub duration_ms: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Paginated delivery history
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDeliveryListResponse {
+    /// Deliveries, newest first
+    pub data: Vec<WebhookDelivery>,
+    /// Total deliveries for the endpoint
+    #[schema(example = 100)]
+    pub total: i64,
+    /// Limit used
+    #[schema(example = 20)]
+    pub limit: i64,
+    /// Offset used
+    #[schema(example = 0)]
+    pub offset: i64,
+}
+
+/// Envelope POSTed to webhook endpoints
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+    /// Unique event ID, stable across retries and replays
+    pub id: Uuid,
+    /// Position in the event log; absent for test events
+    #[schema(example = 1042)]
+    pub sequence: Option<i64>,
+    /// Event type
+    #[serde(rename = "type")]
+    #[schema(example = "transaction.created")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    /// Event-specific payload
+    pub data: Value,
+}
+
+impl From<EventResponse> for WebhookEvent {
+    fn from(e: EventResponse) -> Self {
+        Self {
+            id: e.id,
+            sequence: Some(e.sequence),
+            event_type: e.event_type,
+            created_at: e.created_at,
+            data: e.data,
+        }
+    }
+}
+
+/// Query parameters for replaying logged events to a webhook
+#[derive(Debug, Deserialize, IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayQuery {
+    /// Replay events recorded at or after this time
+    #[param(example = "2026-10-01T00:00:00Z")]
+    pub from: DateTime<Utc>,
+}
+
+/// Response for POST /webhooks/{id}/replay
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayResponse {
+    pub webhook_id: Uuid,
+    /// Number of events queued for redelivery, oldest first
+    #[schema(example = 12)]
+    pub queued: usize,
+    /// Set when the replay was capped; replay again from this time for the rest
+    pub resume_from: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoint() -> WebhookEndpoint {
+        WebhookEndpoint {
+            id: Uuid::nil(),
+            owner_id: Uuid::nil(),
+            url: "https://example.com".to_string(),
+            secret: "whsec_new".to_string(),
+            previous_secret: Some("whsec_old".to_string()),
+            secret_rotated_at: Some(Utc::now()),
+            event_types: vec![events::TRANSACTION_CREATED.to_string()],
+            enabled: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_update_webhook_rejects_unknown_event_types() {
+        let dto: UpdateWebhookDto = serde_json::from_value(serde_json::json!({
+            "eventTypes": [events::TRANSACTION_CREATED, events::WEBHOOK_TEST]
+        }))
+        .unwrap();
+        assert!(dto.validate().is_err());
+
+        let dto: UpdateWebhookDto =
+            serde_json::from_value(serde_json::json!({ "enabled": false, "eventTypes": [] }))
+                .unwrap();
+        assert!(dto.validate().is_ok());
+    }
+
+    #[test]
+    fn test_previous_secret_signs_only_during_grace_period() {
+        let webhook = endpoint();
+        let now = Utc::now();
+        assert_eq!(webhook.signing_secrets(now), vec!["whsec_new", "whsec_old"]);
+        assert_eq!(
+            webhook.signing_secrets(now + Duration::hours(PREVIOUS_SECRET_GRACE_HOURS + 1)),
+            vec!["whsec_new"]
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::models::{
+    events, CreateWebhookDto, DeliveryHistoryQuery, ReplayResponse, UpdateWebhookDto,
+    WebhookDelivery, WebhookEndpoint, WebhookEvent,
+};
+use super::signature::{generate_secret, signature_header, SIGNATURE_HEADER};
+use crate::crypto::encrypt_field;
+use crate::errors::AppError;
+use crate::event::service::EventService;
+use crate::shutdown;
+
+const DELIVERY_COLUMNS: &str = "id, webhook_id, event_id, event_type, status_code, COALESCE(status_code BETWEEN 200 AND 299, FALSE) AS succeeded, error, duration_ms, created_at";
+
+const WEBHOOK_COLUMNS: &str = "id, owner_id, url, secret, previous_secret, secret_rotated_at, event_types, enabled, created_at, updated_at";
+
+/// Most events redelivered by one replay request
+const REPLAY_LIMIT: i64 = 1000;
+
+/// How long to wait for a receiver before giving up on a delivery
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Service layer for outgoing webhooks.
+pub struct WebhookService;
+
+impl WebhookService {
+    /// Register an endpoint with a freshly generated signing secret
+    pub async fn create(
+        pool: &PgPool,
+        owner_id: Uuid,
+        dto: &CreateWebhookDto,
+    ) -> Result<WebhookEndpoint, AppError> {
+        let mut event_types = dto.event_types.clone();
+        event_types.sort();
+        event_types.dedup();
+
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            r#"
+            INSERT INTO webhook_endpoints (owner_id, url, secret, event_types)
+            VALUES ($1, $2, $3, $4)
+            RETURNING {WEBHOOK_COLUMNS}
+            "#
+        ))
+        .bind(owner_id)
+        .bind(dto.url.trim())
+        .bind(encrypt_field(&generate_secret())?)
+        .bind(&event_types)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .decrypted()
+    }
+
+    /// List a user's endpoints, oldest first
+    pub async fn list(pool: &PgPool, owner_id: Uuid) -> Result<Vec<WebhookEndpoint>, AppError> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {WEBHOOK_COLUMNS} FROM webhook_endpoints WHERE owner_id = $1 ORDER BY created_at"
+        ))
+        .bind(owner_id)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(WebhookEndpoint::decrypted)
+        .collect()
+    }
+
+    /// Get an endpoint by ID
+    pub async fn get(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<WebhookEndpoint, AppError> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {WEBHOOK_COLUMNS} FROM webhook_endpoints WHERE id = $1 AND owner_id = $2"
+        ))
+        .bind(webhook_id)
+        .bind(owner_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Webhook not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Update an endpoint (partial update - PATCH semantics)
+    pub async fn update(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        owner_id: Uuid,
+        dto: UpdateWebhookDto,
+    ) -> Result<WebhookEndpoint, AppError> {
+        let event_types = dto.event_types.map(|mut types| {
+            types.sort();
+            types.dedup();
+            types
+        });
+
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            r#"
+            UPDATE webhook_endpoints SET
+                url = COALESCE($3, url),
+                event_types = COALESCE($4, event_types),
+                enabled = COALESCE($5, enabled)
+            WHERE id = $1 AND owner_id = $2
+            RETURNING {WEBHOOK_COLUMNS}
+            "#
+        ))
+        .bind(webhook_id)
+        .bind(owner_id)
+        .bind(dto.url.as_deref().map(str::trim))
+        .bind(event_types)
+        .bind(dto.enabled)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Webhook not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Delete an endpoint and its delivery history
+    pub async fn delete(pool: &PgPool, webhook_id: Uuid, owner_id: Uuid) -> Result<(), AppError> {
+        let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1 AND owner_id = $2")
+            .bind(webhook_id)
+            .bind(owner_id)
+            .execute(pool)
+            .await
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound("Webhook not found".to_string()));
+        }
+        Ok(())
+    }
+
+    /// An endpoint's delivery attempts, newest first, with the total count
+    pub async fn list_deliveries(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        owner_id: Uuid,
+        query: &DeliveryHistoryQuery,
+    ) -> Result<(Vec<WebhookDelivery>, i64), AppError> {
+        Self::get(pool, webhook_id, owner_id).await?;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            SELECT {DELIVERY_COLUMNS}
+            FROM webhook_deliveries
+            WHERE webhook_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#
+        ))
+        .bind(webhook_id)
+        .bind(query.limit)
+        .bind(query.offset)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        let total = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM webhook_deliveries WHERE webhook_id = $1",
+        )
+        .bind(webhook_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?;
+
+        Ok((deliveries, total))
+    }
+
+    /// Replace an endpoint's secret. The old secret keeps signing deliveries
+    /// for a grace period so receivers can switch without dropping events.
+    pub async fn rotate_secret(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<WebhookEndpoint, AppError> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            r#"
+            UPDATE webhook_endpoints SET
+                previous_secret = secret,
+                secret = $3,
+                secret_rotated_at = NOW()
+            WHERE id = $1 AND owner_id = $2
+            RETURNING {WEBHOOK_COLUMNS}
+            "#
+        ))
+        .bind(webhook_id)
+        .bind(owner_id)
+        .bind(encrypt_field(&generate_secret())?)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("Webhook not found".to_string()))?
+        .decrypted()
+    }
+
+    /// Enabled endpoints of a user subscribed to an event type
+    async fn subscribers(
+        pool: &PgPool,
+        owner_id: Uuid,
+        event_type: &str,
+    ) -> Result<Vec<WebhookEndpoint>, AppError> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            r#"
+            SELECT {WEBHOOK_COLUMNS}
+            FROM webhook_endpoints
+            WHERE owner_id = $1 AND enabled
+              AND (cardinality(event_types) = 0 OR $2 = ANY(event_types))
+            "#
+        ))
+        .bind(owner_id)
+        .bind(event_type)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(WebhookEndpoint::decrypted)
+        .collect()
+    }
+
+    /// POST a signed event to one endpoint and record the attempt in its
+    /// delivery history. Receiver failures are recorded, not returned as errors.
+    pub async fn deliver(
+        pool: &PgPool,
+        client: &reqwest::Client,
+        endpoint: &WebhookEndpoint,
+        event: &WebhookEvent,
+    ) -> Result<WebhookDelivery, AppError> {
+        let body = serde_json::to_vec(event).map_err(|e| AppError::InternalError(e.to_string()))?;
+        let now = Utc::now();
+        let signature = signature_header(&endpoint.signing_secrets(now), now.timestamp(), &body);
+
+        let started = Instant::now();
+        let result = client
+            .post(&endpoint.url)
+            .timeout(DELIVERY_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header("X-Webhook-Event", &event.event_type)
+            .header("X-Webhook-Id", event.id.to_string())
+            .body(body)
+            .send()
+            .await;
+        let duration_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+        let (status_code, error) = match result {
+            Ok(response) => (Some(i32::from(response.status().as_u16())), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            INSERT INTO webhook_deliveries
+                (webhook_id, event_id, event_type, status_code, error, duration_ms)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(endpoint.id)
+        .bind(event.id)
+        .bind(&event.event_type)
+        .bind(status_code)
+        .bind(error)
+        .bind(duration_ms)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))
+    }
+
+    /// Send a test event to an endpoint, whether or not it is enabled, and
+    /// return the recorded delivery
+    pub async fn send_test(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        owner_id: Uuid,
+    ) -> Result<WebhookDelivery, AppError> {
+        let endpoint = Self::get(pool, webhook_id, owner_id).await?;
+        let event = WebhookEvent {
+            id: Uuid::new_v4(),
+            sequence: None,
+            event_type: events::WEBHOOK_TEST.to_string(),
+            created_at: Utc::now(),
+            data: serde_json::json!({ "webhookId": webhook_id }),
+        };
+
+        Self::deliver(pool, &reqwest::Client::new(), &endpoint, &event).await
+    }
+
+    /// Deliver events to each endpoint in order, in the background; shutdown
+    /// waits for deliveries in progress
+    fn spawn_deliveries(pool: PgPool, endpoints: Vec<WebhookEndpoint>, events: Vec<WebhookEvent>) {
+        shutdown::spawn(async move {
+            let client = reqwest::Client::new();
+            for endpoint in &endpoints {
+                for event in &events {
+                    match Self::deliver(&pool, &client, endpoint, event).await {
+                        Ok(delivery) if !delivery.succeeded => warn!(
+                            webhook_id = %endpoint.id,
+                            status_code = delivery.status_code,
+                            error = delivery.error.as_deref(),
+                            "Webhook delivery failed"
+                        ),
+                        Ok(_) => {}
+                        Err(e) => warn!(
+                            webhook_id = %endpoint.id,
+                            "Failed to record webhook delivery: {}", e
+                        ),
+                    }
+                }
+            }
+        });
+    }
+
+    /// Record an event in the event log, then deliver it to every subscribed
+    /// endpoint in the background so slow receivers never hold up the request
+    /// that produced it. The change itself is already committed, so failures
+    /// are logged rather than returned.
+    pub async fn publish<T: Serialize>(
+        pool: &PgPool,
+        owner_id: Uuid,
+        event_type: &'static str,
+        data: &T,
+    ) {
+        let data = match serde_json::to_value(data) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(event_type, "Failed to serialize event payload: {}", e);
+                return;
+            }
+        };
+        let event = match EventService::append(pool, owner_id, event_type, &data).await {
+            Ok(event) => event,
+            Err(e) => {
+                warn!(%owner_id, event_type, "Failed to record event: {}", e);
+                return;
+            }
+        };
+        let endpoints = match Self::subscribers(pool, owner_id, event_type).await {
+            Ok(endpoints) => endpoints,
+            Err(e) => {
+                warn!(%owner_id, event_type, "Failed to load webhook endpoints: {}", e);
+                return;
+            }
+        };
+        if !endpoints.is_empty() {
+            Self::spawn_deliveries(pool.clone(), endpoints, vec![WebhookEvent::from(event)]);
+        }
+    }
+
+    /// Redeliver logged events the endpoint subscribes to, starting at `from`.
+    /// Events keep their original IDs so receivers can drop ones they already have.
+    pub async fn replay(
+        pool: &PgPool,
+        webhook_id: Uuid,
+        owner_id: Uuid,
+        from: DateTime<Utc>,
+    ) -> Result<ReplayResponse, AppError> {
+        let endpoint = Self::get(pool, webhook_id, owner_id).await?;
+        let events =
+            EventService::since(pool, owner_id, from, &endpoint.event_types, REPLAY_LIMIT).await?;
+
+        let queued = events.len();
+        let resume_from = if queued as i64 == REPLAY_LIMIT {
+            events.last().map(|e| e.created_at)
+        } else {
+            None
+        };
+        if queued > 0 {
+            Self::spawn_deliveries(
+                pool.clone(),
+                vec![endpoint],
+                events.into_iter().map(WebhookEvent::from).collect(),
+            );
+        }
+
+        Ok(ReplayResponse {
+            webhook_id,
+            queued,
+            resume_from,
+        })
+    }
+}
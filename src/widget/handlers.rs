@@ -0,0 +1,42 @@
+use actix_web::{get, web, HttpResponse};
+use sqlx::PgPool;
+
+use crate::auth::scopes::Scope;
+use crate::budget::models::MonthBaseQuery;
+use crate::errors::{AppError, ErrorResponse};
+use crate::extractors::AuthenticatedUser;
+
+use super::models::WidgetSummaryResponse;
+use super::service::WidgetService;
+
+/// GET /widgets/summary - Minimal summary for home-screen widgets
+#[utoipa::path(
+    get,
+    path = "/widgets/summary",
+    tag = "Widgets",
+    params(MonthBaseQuery),
+    responses(
+        (status = 200, description = "Net worth, this month's spending and recent transactions", body = WidgetSummaryResponse),
+        (status = 401, description = "Unauthorized", body = ErrorResponse),
+        (status = 403, description = "Missing required scope", body = ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/widgets/summary")]
+pub async fn get_widget_summary(
+    pool: web::Data<PgPool>,
+    auth: AuthenticatedUser,
+    month_base: MonthBaseQuery,
+) -> Result<HttpResponse, AppError> {
+    auth.require_scope(Scope::AccountsRead)?;
+    auth.require_scope(Scope::TransactionsRead)?;
+
+    let summary = WidgetService::get_summary(pool.get_ref(), auth.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(summary.with_month_base(&month_base)))
+}
+
+/// Register the widget routes
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_widget_summary);
+}
@@ -0,0 +1,99 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::budget::models::MonthBaseQuery;
+use crate::crypto::decrypt_optional;
+use crate::errors::AppError;
+
+/// Net worth and current-month figures in the user's timezone
+#[derive(Debug, FromRow)]
+pub struct WidgetTotalsRow {
+    pub currency: String,
+    pub net_worth: Decimal,
+    pub month: i16,
+    pub year: i16,
+    pub total_income: Option<Decimal>,
+    pub savings_rate: Option<Decimal>,
+    pub spent: Decimal,
+}
+
+/// A recent transaction with only the fields a widget displays
+#[derive(Debug, Serialize, ToSchema, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetTransaction {
+    pub id: Uuid,
+    #[schema(example = 12.50)]
+    pub amount: Decimal,
+    #[schema(example = "expense")]
+    pub transaction_type: String,
+    #[schema(example = "Coffee")]
+    pub description: Option<String>,
+    #[schema(example = "Dining")]
+    pub category_name: String,
+    pub transaction_date: DateTime<Utc>,
+}
+
+impl WidgetTransaction {
+    /// Decrypt the description after loading from the database
+    pub fn decrypted(mut self) -> Result<Self, AppError> {
+        self.description = decrypt_optional(self.description)?;
+        Ok(self)
+    }
+}
+
+/// Response for GET /widgets/summary
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetSummaryResponse {
+    /// Sum of all account balances
+    #[schema(example = 12500.00)]
+    pub net_worth: Decimal,
+    /// The user's default currency
+    #[schema(example = "USD")]
+    pub currency: String,
+    /// Current month (0-11, or 1-12 with monthBase=1)
+    #[schema(example = 9)]
+    pub month: i16,
+    #[schema(example = 2026)]
+    pub year: i16,
+    /// Expenses so far this month
+    #[schema(example = 1850.00)]
+    pub spent: Decimal,
+    /// Income minus savings target for this month's budget, absent without a budget
+    #[schema(example = 4000.00)]
+    pub spending_budget: Option<Decimal>,
+    /// Spending budget minus spent, absent without a budget
+    #[schema(example = 2150.00)]
+    pub remaining: Option<Decimal>,
+    /// The three most recent transactions
+    pub recent_transactions: Vec<WidgetTransaction>,
+}
+
+impl WidgetSummaryResponse {
+    pub fn new(totals: WidgetTotalsRow, recent_transactions: Vec<WidgetTransaction>) -> Self {
+        let spending_budget = totals.total_income.map(|income| {
+            income - income * totals.savings_rate.unwrap_or(Decimal::ZERO) / Decimal::from(100)
+        });
+
+        Self {
+            net_worth: totals.net_worth,
+            currency: totals.currency,
+            month: totals.month,
+            year: totals.year,
+            spent: totals.spent,
+            spending_budget,
+            remaining: spending_budget.map(|budget| budget - totals.spent),
+            recent_transactions,
+        }
+    }
+
+    /// Report the month in the caller's numbering convention
+    pub fn with_month_base(mut self, month_base: &MonthBaseQuery) -> Self {
+        self.month = month_base.to_external(self.month);
+        self
+    }
+}
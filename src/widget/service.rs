@@ -0,0 +1,84 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::AppError;
+use crate::summary::service::SummaryService;
+
+use super::models::{WidgetSummaryResponse, WidgetTotalsRow, WidgetTransaction};
+
+/// Number of transactions included in the widget summary
+const RECENT_TRANSACTIONS: i64 = 3;
+
+/// Service layer for home-screen widget data.
+pub struct WidgetService;
+
+impl WidgetService {
+    /// Build the widget summary with one aggregate query and one short list query
+    pub async fn get_summary(
+        pool: &PgPool,
+        user_id: Uuid,
+    ) -> Result<WidgetSummaryResponse, AppError> {
+        SummaryService::refresh_dirty(pool, Some(user_id)).await?;
+
+        let totals = sqlx::query_as::<_, WidgetTotalsRow>(
+            r#"
+            WITH period AS (
+                SELECT
+                    u.id AS user_id,
+                    u.default_currency,
+                    u.timezone,
+                    date_trunc('month', NOW() AT TIME ZONE u.timezone) AS month_start
+                FROM users u
+                WHERE u.id = $1
+            )
+            SELECT
+                p.default_currency AS currency,
+                (SELECT COALESCE(SUM(a.balance), 0) FROM accounts a WHERE a.owner_id = p.user_id) AS net_worth,
+                (EXTRACT(MONTH FROM p.month_start) - 1)::SMALLINT AS month,
+                EXTRACT(YEAR FROM p.month_start)::SMALLINT AS year,
+                b.total_income,
+                b.savings_rate,
+                (
+                    SELECT COALESCE(SUM(s.total), 0)
+                    FROM monthly_category_totals s
+                    WHERE s.owner_id = p.user_id
+                      AND s.year = EXTRACT(YEAR FROM p.month_start)
+                      AND s.month = EXTRACT(MONTH FROM p.month_start) - 1
+                      AND s.transaction_type = 'expense'
+                ) AS spent
+            FROM period p
+            LEFT JOIN budgets b ON b.owner_id = p.user_id
+                AND b.year = EXTRACT(YEAR FROM p.month_start)
+                AND b.month = EXTRACT(MONTH FROM p.month_start) - 1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let recent = sqlx::query_as::<_, WidgetTransaction>(
+            r#"
+            SELECT t.id, t.amount, t.transaction_type, t.description,
+                   c.name AS category_name, t.transaction_date
+            FROM transactions t
+            INNER JOIN categories c ON t.category_id = c.id
+            INNER JOIN budgets b ON c.budget_id = b.id
+            WHERE b.owner_id = $1 AND t.deleted_at IS NULL AND t.status = 'posted'
+            ORDER BY t.transaction_date DESC, t.created_at DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(RECENT_TRANSACTIONS)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| AppError::InternalError(e.to_string()))?
+        .into_iter()
+        .map(WidgetTransaction::decrypted)
+        .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(WidgetSummaryResponse::new(totals, recent))
+    }
+}
@@ -1,27 +1,70 @@
+use actix_web::{web, App, HttpResponse, HttpServer};
+use be_rust::bank_sync::provider::{BankSyncProvider, PlaidClient};
+use chrono::{Datelike, Months};
 use serde_json::{json, Value};
+use sqlx::PgPool;
 
 mod common;
-use common::TestApp;
+use common::{TestApp, TestUser};
 
-#[actix_rt::test]
-async fn test_health_endpoint() {
-    let app = TestApp::new().await;
+#[sqlx::test]
+async fn test_health_endpoint(pool: PgPool) {
+    let app = TestApp::new(pool);
 
     let response = app.get("/health").await;
 
     assert_eq!(response.status(), 200);
     let body: Value = response.json().await;
-    assert_eq!(body["status"], "healthy");
+    assert_eq!(body["status"], "up");
+
+    let response = app.get("/health/live").await;
+    assert_eq!(response.status(), 200);
+}
+
+#[sqlx::test]
+async fn test_readiness_reports_component_statuses(pool: PgPool) {
+    let app = TestApp::new(pool.clone());
+
+    // No rates fetched yet: ready to serve, but degraded
+    let response = app.get("/health/ready").await;
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await;
+    assert_eq!(body["status"], "degraded");
+    assert_eq!(body["components"]["database"]["status"], "up");
+    assert_eq!(body["components"]["migrations"]["status"], "up");
+    assert_eq!(body["components"]["pool"]["status"], "up");
+    assert_eq!(body["components"]["exchangeRates"]["status"], "degraded");
+
+    sqlx::query(
+        "INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
+         VALUES ('USD', 'EUR', 0.9, CURRENT_DATE)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let body: Value = app.get("/health/ready").await.json().await;
+    assert_eq!(body["status"], "ready");
+
+    // A migration recorded as failed takes the instance out of rotation
+    sqlx::query("UPDATE _sqlx_migrations SET success = false WHERE version = (SELECT MAX(version) FROM _sqlx_migrations)")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let response = app.get("/health/ready").await;
+    assert_eq!(response.status(), 503);
+    let body: Value = response.json().await;
+    assert_eq!(body["status"], "unavailable");
+    assert_eq!(body["components"]["migrations"]["status"], "down");
 }
 
-#[actix_rt::test]
-async fn test_register_success() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("newuser");
+#[sqlx::test]
+async fn test_register_success(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "newuser@test.com";
 
     let payload = json!({
         "email": email,
-        "password": "password123",
+        "password": "Password123",
         "full_name": "New User"
     });
 
@@ -36,14 +79,14 @@ async fn test_register_success() {
     assert_eq!(body["user"]["full_name"], "New User");
 }
 
-#[actix_rt::test]
-async fn test_register_duplicate_email() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("duplicate");
+#[sqlx::test]
+async fn test_register_duplicate_email(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "duplicate@test.com";
 
     let payload = json!({
         "email": email,
-        "password": "password123"
+        "password": "Password123"
     });
 
     // First registration should succeed
@@ -55,15 +98,16 @@ async fn test_register_duplicate_email() {
     assert_eq!(response2.status(), 409);
     let body: Value = response2.json().await;
     assert_eq!(body["error"], "CONFLICT");
+    assert_eq!(body["code"], "EMAIL_TAKEN");
 }
 
-#[actix_rt::test]
-async fn test_register_invalid_email() {
-    let app = TestApp::new().await;
+#[sqlx::test]
+async fn test_register_invalid_email(pool: PgPool) {
+    let app = TestApp::new(pool);
 
     let payload = json!({
         "email": "not-an-email",
-        "password": "password123"
+        "password": "Password123"
     });
 
     let response = app.post("/auth/register", &payload).await;
@@ -71,12 +115,100 @@ async fn test_register_invalid_email() {
     assert_eq!(response.status(), 400);
     let body: Value = response.json().await;
     assert_eq!(body["error"], "VALIDATION_ERROR");
+    assert_eq!(
+        body["fields"],
+        json!([{ "field": "email", "code": "email", "message": "email is invalid (email)" }])
+    );
+}
+
+#[sqlx::test]
+async fn test_request_id_is_echoed_in_header_and_error_body(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let payload = json!({ "email": "not-an-email", "password": "Password123" });
+
+    let response = app
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/auth/register")
+                .insert_header(("X-Request-Id", "test-req-1"))
+                .set_json(&payload),
+        )
+        .await;
+
+    assert_eq!(response.status(), 400);
+    assert_eq!(response.header("x-request-id"), Some("test-req-1"));
+    let body: Value = response.json().await;
+    assert_eq!(body["requestId"], "test-req-1");
+
+    // Without one (or with an unusable one) a UUID is generated
+    let response = app
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/auth/register")
+                .insert_header(("X-Request-Id", "has spaces"))
+                .set_json(&payload),
+        )
+        .await;
+    let generated = response.header("x-request-id").unwrap().to_string();
+    assert!(uuid::Uuid::parse_str(&generated).is_ok());
+    let body: Value = response.json().await;
+    assert_eq!(body["requestId"], generated.as_str());
+
+    let response = app.get("/health").await;
+    assert!(response.header("x-request-id").is_some());
+}
+
+#[sqlx::test]
+async fn test_undeserializable_values_get_json_422_naming_the_field(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("malformed@test.com").await;
+
+    let response = app.get_as(&user, "/accounts/not-a-uuid").await;
+    assert_eq!(response.status(), 422);
+    let body = response.json().await;
+    assert_eq!(body["error"], "MALFORMED_REQUEST");
+    assert_eq!(body["fields"][0]["field"], "id");
+    assert_eq!(body["fields"][0]["code"], "invalid");
+    assert!(body["requestId"].is_string());
+
+    let response = app.get_as(&user, "/transactions?limit=ten").await;
+    assert_eq!(response.status(), 422);
+    let body = response.json().await;
+    assert_eq!(body["fields"][0]["field"], "limit");
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid query parameter 'limit'"));
+
+    let response = app
+        .post_as(&user, "/transactions", &json!({ "amount": 10 }))
+        .await;
+    assert_eq!(response.status(), 422);
+    let body = response.json().await;
+    assert_eq!(body["fields"][0]["code"], "required");
+
+    let response = app
+        .request(
+            actix_web::test::TestRequest::post()
+                .uri("/transactions")
+                .insert_header(("Authorization", format!("Bearer {}", user.access_token)))
+                .insert_header(("Content-Type", "application/json"))
+                .set_payload("{\"amount\": "),
+        )
+        .await;
+    assert_eq!(response.status(), 422);
+    let body = response.json().await;
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid request body"));
+    assert!(body.get("fields").is_none());
 }
 
-#[actix_rt::test]
-async fn test_register_short_password() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("shortpass");
+#[sqlx::test]
+async fn test_register_short_password(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "shortpass@test.com";
 
     let payload = json!({
         "email": email,
@@ -89,17 +221,25 @@ async fn test_register_short_password() {
     let body: Value = response.json().await;
     assert_eq!(body["error"], "VALIDATION_ERROR");
     assert!(body["message"].as_str().unwrap().contains("8 characters"));
+    let codes: Vec<&str> = body["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["field"] == "password")
+        .map(|f| f["code"].as_str().unwrap())
+        .collect();
+    assert!(codes.contains(&"length"));
 }
 
-#[actix_rt::test]
-async fn test_login_success() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("login");
+#[sqlx::test]
+async fn test_login_success(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "login@test.com";
 
     // First register a user
     let register_payload = json!({
         "email": email,
-        "password": "password123",
+        "password": "Password123",
         "full_name": "Login Test"
     });
     app.post("/auth/register", &register_payload).await;
@@ -107,7 +247,7 @@ async fn test_login_success() {
     // Then login
     let login_payload = json!({
         "email": email,
-        "password": "password123"
+        "password": "Password123"
     });
 
     let response = app.post("/auth/login", &login_payload).await;
@@ -120,22 +260,22 @@ async fn test_login_success() {
     assert_eq!(body["user"]["email"], email);
 }
 
-#[actix_rt::test]
-async fn test_login_wrong_password() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("wrongpass");
+#[sqlx::test]
+async fn test_login_wrong_password(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "wrongpass@test.com";
 
     // Register a user (password must be at least 8 chars)
     let register_payload = json!({
         "email": email,
-        "password": "correct_password123"
+        "password": "Correct_password123"
     });
     app.post("/auth/register", &register_payload).await;
 
     // Try to login with wrong password
     let login_payload = json!({
         "email": email,
-        "password": "wrong_password123"
+        "password": "Wrong_password123"
     });
 
     let response = app.post("/auth/login", &login_payload).await;
@@ -143,16 +283,17 @@ async fn test_login_wrong_password() {
     assert_eq!(response.status(), 401);
     let body: Value = response.json().await;
     assert_eq!(body["error"], "UNAUTHORIZED");
+    assert_eq!(body["code"], "INVALID_CREDENTIALS");
 }
 
-#[actix_rt::test]
-async fn test_login_nonexistent_user() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("nonexistent");
+#[sqlx::test]
+async fn test_login_nonexistent_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "nonexistent@test.com";
 
     let payload = json!({
         "email": email,
-        "password": "password123"
+        "password": "Password123"
     });
 
     let response = app.post("/auth/login", &payload).await;
@@ -162,14 +303,213 @@ async fn test_login_nonexistent_user() {
     assert_eq!(body["error"], "UNAUTHORIZED");
 }
 
-#[actix_rt::test]
-async fn test_token_is_valid_jwt() {
-    let app = TestApp::new().await;
-    let email = app.unique_email("jwt");
+#[sqlx::test]
+async fn test_sessions_can_be_listed_and_revoked_individually(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("sessions@test.com").await;
+
+    let laptop = app
+        .post(
+            "/auth/login",
+            &json!({ "email": user.email, "password": "Password123", "device_name": "Laptop" }),
+        )
+        .await
+        .json()
+        .await;
+
+    let sessions = app.get_as(&user, "/auth/sessions").await.json().await;
+    let sessions = sessions.as_array().unwrap();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0]["device_name"], "Laptop");
+
+    // Refreshing keeps the device name on the rotated session
+    let refreshed = app
+        .post(
+            "/auth/refresh",
+            &json!({ "refresh_token": laptop["refresh_token"] }),
+        )
+        .await
+        .json()
+        .await;
+    let sessions = app.get_as(&user, "/auth/sessions").await.json().await;
+    let laptop_session = sessions
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["device_name"] == "Laptop")
+        .unwrap()
+        .clone();
+
+    let path = format!("/auth/sessions/{}", laptop_session["id"].as_str().unwrap());
+    assert_eq!(app.delete_as(&user, &path).await.status(), 200);
+    assert_eq!(app.delete_as(&user, &path).await.status(), 404);
+
+    // The revoked session can't be refreshed; the other one still can
+    let response = app
+        .post(
+            "/auth/refresh",
+            &json!({ "refresh_token": refreshed["refresh_token"] }),
+        )
+        .await;
+    assert_eq!(response.status(), 401);
+    let response = app
+        .post(
+            "/auth/refresh",
+            &json!({ "refresh_token": user.refresh_token }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    // Other users can't revoke someone else's session
+    let other = app.register_user("sessions_other@test.com").await;
+    let sessions = app.get_as(&other, "/auth/sessions").await.json().await;
+    assert_eq!(sessions.as_array().map(Vec::len), Some(1));
+    let path = format!("/auth/sessions/{}", sessions[0]["id"].as_str().unwrap());
+    assert_eq!(app.delete_as(&user, &path).await.status(), 404);
+}
+
+#[sqlx::test]
+async fn test_scoped_tokens_cannot_mint_tokens_or_manage_the_account(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("scoped@test.com").await;
+
+    // The requested 30 days is capped at the 15 minutes left on the session token
+    let response = app
+        .post_as(
+            &user,
+            "/auth/tokens",
+            &json!({ "scopes": ["budgets:read"], "expiresInMinutes": 43200 }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let body = response.json().await;
+    assert!(body["expiresIn"].as_u64().unwrap() <= 15 * 60);
+
+    let integration = TestUser {
+        id: user.id.clone(),
+        email: user.email.clone(),
+        access_token: body["accessToken"].as_str().unwrap().to_string(),
+        refresh_token: user.refresh_token.clone(),
+    };
+    assert_eq!(app.get_as(&integration, "/budgets").await.status(), 200);
+
+    // It can't renew itself or touch sessions and the profile
+    let response = app
+        .post_as(
+            &integration,
+            "/auth/tokens",
+            &json!({ "scopes": ["budgets:read"] }),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(response.json().await["code"], "MISSING_SCOPE");
+
+    assert_eq!(
+        app.get_as(&integration, "/auth/sessions").await.status(),
+        403
+    );
+    let sessions = app.get_as(&user, "/auth/sessions").await.json().await;
+    let path = format!("/auth/sessions/{}", sessions[0]["id"].as_str().unwrap());
+    assert_eq!(app.delete_as(&integration, &path).await.status(), 403);
+    let response = app
+        .put_as(
+            &integration,
+            "/auth/me/timezone",
+            &json!({ "timezone": "Europe/Berlin" }),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+    let response = app.post_as(&integration, "/auth/logout", &json!({})).await;
+    assert_eq!(response.status(), 403);
+
+    // The session itself is untouched
+    let sessions = app.get_as(&user, "/auth/sessions").await.json().await;
+    assert_eq!(sessions.as_array().map(Vec::len), Some(1));
+}
+
+#[sqlx::test]
+async fn test_scoped_tokens_cannot_read_or_change_preferences(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("scoped-preferences@test.com").await;
+    let integration = app.scoped_user(&user, &["budgets:read"]).await;
+
+    let response = app.get_as(&integration, "/users/me/preferences").await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(response.json().await["code"], "MISSING_SCOPE");
+    let response = app
+        .patch_as(
+            &integration,
+            "/users/me/preferences",
+            &json!({ "defaultAccountId": null }),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+
+    // The session token still can
+    assert_eq!(
+        app.get_as(&user, "/users/me/preferences").await.status(),
+        200
+    );
+}
+
+#[sqlx::test]
+async fn test_scoped_tokens_cannot_read_or_change_dashboard_settings(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("scoped-dashboard@test.com").await;
+    let integration = app.scoped_user(&user, &["budgets:read"]).await;
+
+    let response = app.get_as(&integration, "/settings/dashboard").await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(response.json().await["code"], "MISSING_SCOPE");
+    let response = app
+        .put_as(
+            &integration,
+            "/settings/dashboard",
+            &json!({ "widgetOrder": ["netWorth"] }),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+
+    // The session token still can
+    assert_eq!(app.get_as(&user, "/settings/dashboard").await.status(), 200);
+}
+
+#[sqlx::test]
+async fn test_scoped_tokens_of_admins_cannot_use_admin_endpoints(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let admin = app.register_user("scoped-admin@test.com").await;
+    sqlx::query("UPDATE users SET is_admin = TRUE WHERE id = $1::uuid")
+        .bind(&admin.id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let integration = app.scoped_user(&admin, &["budgets:read"]).await;
+
+    let response = app
+        .post_as(
+            &integration,
+            "/admin/jobs/refresh-token-cleanup",
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 403);
+    assert_eq!(response.json().await["code"], "ADMIN_REQUIRED");
+
+    // The admin's own session still can
+    let response = app
+        .post_as(&admin, "/admin/jobs/refresh-token-cleanup", &json!({}))
+        .await;
+    assert_eq!(response.status(), 200);
+}
+
+#[sqlx::test]
+async fn test_token_is_valid_jwt(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let email = "jwt@test.com";
 
     let payload = json!({
         "email": email,
-        "password": "password123"
+        "password": "Password123"
     });
 
     let response = app.post("/auth/register", &payload).await;
@@ -188,3 +528,4650 @@ async fn test_token_is_valid_jwt() {
         "Refresh token should be 64 hex characters"
     );
 }
+
+#[sqlx::test]
+async fn test_protected_route_requires_token(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let response = app.get("/budgets").await;
+
+    assert_eq!(response.status(), 401);
+    let body: Value = response.json().await;
+    assert_eq!(body["error"], "UNAUTHORIZED");
+}
+
+#[sqlx::test]
+async fn test_me_returns_registered_user(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("me@test.com").await;
+
+    let response = app.get_as(&user, "/auth/me").await;
+
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await;
+    assert_eq!(body["id"], user.id);
+    assert_eq!(body["email"], user.email);
+}
+
+#[sqlx::test]
+async fn test_password_reset_with_emailed_token(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("reset@test.com").await;
+
+    let response = app
+        .post("/auth/forgot-password", &json!({ "email": user.email }))
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let emails = app.sent_emails(1).await;
+    assert_eq!(emails.len(), 1);
+    assert_eq!(emails[0].to, user.email);
+    let token = emails[0]
+        .body
+        .split("token=")
+        .nth(1)
+        .and_then(|rest| rest.split_whitespace().next())
+        .expect("Reset link missing from email")
+        .to_string();
+
+    let payload = json!({ "token": token, "newPassword": "NewPassword456" });
+    let response = app.post("/auth/reset-password", &payload).await;
+    assert_eq!(response.status(), 200);
+
+    // Tokens work once
+    let response = app.post("/auth/reset-password", &payload).await;
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await;
+    assert_eq!(body["code"], "INVALID_RESET_TOKEN");
+
+    let response = app
+        .post(
+            "/auth/login",
+            &json!({ "email": user.email, "password": "NewPassword456" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    // Sessions from before the reset are signed out
+    let active_sessions = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM refresh_tokens WHERE user_id = $1::uuid AND revoked_at IS NULL",
+    )
+    .bind(&user.id)
+    .fetch_one(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(active_sessions, 1);
+}
+
+#[sqlx::test]
+async fn test_expense_updates_account_and_category(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("flow@test.com").await;
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 1000 }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({
+                "budgetId": budget_id,
+                "name": "Groceries",
+                "allocated": 300,
+                "colorHex": "#22AA66"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let category_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({
+                "name": "Everyday",
+                "type": "checking",
+                "balance": 500,
+                "colorHex": "#3366FF"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let account_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category_id,
+                "accountId": account_id,
+                "amount": 42.50,
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "description": "Weekly shop",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "457.50");
+
+    let transactions = app
+        .get_as(&user, &format!("/transactions/category/{category_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(transactions.as_array().map(Vec::len), Some(1));
+
+    // Other users can't see the account
+    let other = app.register_user("flow_other@test.com").await;
+    let response = app.get_as(&other, &format!("/accounts/{account_id}")).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn test_account_currency_change_converts_balance_and_history(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("mover@test.com").await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
+        VALUES ('USD', 'EUR', 0.90, '2026-01-01'), ('USD', 'EUR', 0.80, '2026-01-20')
+        "#,
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let account_id = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Home", "type": "checking", "balance": 1000, "colorHex": "#4CAF50", "currency": "USD" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let budget_id = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 500 }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let category_id = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Rent", "allocated": 500, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let transaction_id = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category_id,
+                "accountId": account_id,
+                "amount": 100,
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let path = format!("/accounts/{account_id}/currency");
+    let payload = json!({ "currency": "EUR", "convertTransactions": true });
+
+    // Without confirmation it is only a preview
+    let preview = app.patch_as(&user, &path, &payload).await;
+    assert_eq!(preview.status(), 200);
+    let preview = preview.json().await;
+    assert_eq!(preview["applied"], false);
+    assert_eq!(preview["balanceAfter"], "720.00");
+    assert_eq!(preview["transactionsConverted"], 1);
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["currency"], "USD");
+
+    let mut confirmed = payload.clone();
+    confirmed["confirm"] = json!(true);
+    let change = app.patch_as(&user, &path, &confirmed).await.json().await;
+    assert_eq!(change["applied"], true);
+
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["currency"], "EUR");
+    assert_eq!(account["balance"], "720.00");
+
+    // History is converted at the rate of its own date
+    let transaction = app
+        .get_as(&user, &format!("/transactions/{transaction_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(transaction["amount"], "90.00");
+    // ...and keeps what was entered, with the rate that reproduces it
+    assert_eq!(transaction["originalAmount"], "100.00");
+    assert_eq!(transaction["originalCurrency"], "USD");
+    assert_eq!(transaction["exchangeRate"], "0.9");
+
+    let check = app
+        .post_as(
+            &user,
+            &format!("/accounts/{account_id}/recompute-balance"),
+            &json!({}),
+        )
+        .await
+        .json()
+        .await;
+    assert_eq!(
+        check["drift"].as_str().unwrap().parse::<f64>().unwrap(),
+        0.0
+    );
+
+    // Moving to the same currency again is rejected
+    let response = app.patch_as(&user, &path, &confirmed).await;
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn test_stale_if_match_rejects_currency_change(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("stale-currency@test.com").await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
+        VALUES ('USD', 'EUR', 0.90, '2026-01-01')
+        "#,
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let account_id = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Home", "type": "checking", "balance": 1000, "colorHex": "#4CAF50", "currency": "USD" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let path = format!("/accounts/{account_id}/currency");
+    let payload = json!({ "currency": "EUR", "confirm": true });
+
+    let stale = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .header("etag")
+        .unwrap()
+        .to_string();
+
+    let renamed = app
+        .patch_if_match_as(
+            &user,
+            &format!("/accounts/{account_id}"),
+            &stale,
+            &json!({ "name": "Renamed" }),
+        )
+        .await;
+    assert_eq!(renamed.status(), 200);
+    let current = renamed.header("etag").unwrap().to_string();
+
+    // A version from before the rename no longer matches
+    let response = app.patch_if_match_as(&user, &path, &stale, &payload).await;
+    assert_eq!(response.status(), 412);
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["currency"], "USD");
+
+    let response = app
+        .patch_if_match_as(&user, &path, &current, &payload)
+        .await;
+    assert_eq!(response.status(), 200);
+    let changed = response.header("etag").unwrap().to_string();
+    assert_ne!(changed, current);
+    assert_eq!(response.json().await["applied"], true);
+}
+
+#[sqlx::test]
+async fn test_accounts_summary_converts_into_default_currency(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("worldly@test.com").await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
+        VALUES ('USD', 'EUR', 0.50, '2026-01-01')
+        "#,
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    for (name, account_type, balance, currency) in [
+        ("Checking", "checking", 100, "USD"),
+        ("Euro savings", "savings", 300, "EUR"),
+        ("Pound wallet", "checking", 50, "GBP"),
+    ] {
+        let response = app
+            .post_as(
+                &user,
+                "/accounts",
+                &json!({ "name": name, "type": account_type, "balance": balance, "colorHex": "#4CAF50", "currency": currency }),
+            )
+            .await;
+        assert_eq!(response.status(), 201);
+    }
+
+    let body = app.get_as(&user, "/accounts/summary").await.json().await;
+    let summary = &body["summary"];
+    assert_eq!(summary["currency"], "USD");
+    assert_eq!(summary["totalSavings"], "600.00");
+    assert_eq!(summary["totalSpending"], "100.00");
+    assert_eq!(summary["netWorth"], "700.00");
+    assert_eq!(summary["accountsCount"], 3);
+    assert_eq!(summary["unconvertedCurrencies"], json!(["GBP"]));
+
+    let summaries = body["summaries"].as_array().unwrap();
+    let eur = summaries.iter().find(|s| s["currency"] == "EUR").unwrap();
+    assert_eq!(eur["totalSavings"], "300.00");
+    assert_eq!(eur["conversion"]["rate"], "2");
+    assert_eq!(eur["conversion"]["netWorth"], "600.00");
+    let gbp = summaries.iter().find(|s| s["currency"] == "GBP").unwrap();
+    assert!(gbp["conversion"].is_null());
+}
+
+#[sqlx::test]
+async fn test_cached_reads_reflect_writes_immediately(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("cached@test.com").await;
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 1000 }),
+        )
+        .await;
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+    let response = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Everyday", "type": "checking", "balance": 500, "colorHex": "#3366FF" }),
+        )
+        .await;
+    let account_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    // Prime the cache
+    let body = app.get_as(&user, "/accounts/summary").await.json().await;
+    assert_eq!(body["summary"]["netWorth"], "500.00");
+    let categories = app.get_as(&user, "/categories").await.json().await;
+    assert_eq!(categories.as_array().map(Vec::len), Some(0));
+
+    let response = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "allocated": 300, "colorHex": "#22AA66" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let category_id = response.json().await["id"].as_str().unwrap().to_string();
+    let categories = app.get_as(&user, "/categories").await.json().await;
+    assert_eq!(categories.as_array().map(Vec::len), Some(1));
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category_id,
+                "accountId": account_id,
+                "amount": 42.50,
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let body = app.get_as(&user, "/accounts/summary").await.json().await;
+    assert_eq!(body["summary"]["netWorth"], "457.50");
+
+    // Another user's cached summary is their own
+    let other = app.register_user("cached_other@test.com").await;
+    let body = app.get_as(&other, "/accounts/summary").await.json().await;
+    assert_eq!(body["summary"]["accountsCount"], 0);
+}
+
+#[sqlx::test]
+async fn test_exchange_rate_history_is_paginated_by_date(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    sqlx::query(
+        r#"
+        INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
+        VALUES ('USD', 'EUR', 0.90, '2026-01-01'), ('USD', 'EUR', 0.85, '2026-01-02'),
+               ('USD', 'EUR', 0.80, '2026-01-03'), ('USD', 'GBP', 0.75, '2026-01-02')
+        "#,
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let body = app
+        .get("/currencies/rates?base=usd&target=EUR&start=2026-01-02&limit=1")
+        .await
+        .json()
+        .await;
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["data"].as_array().map(Vec::len), Some(1));
+    assert_eq!(body["data"][0]["rateDate"], "2026-01-02");
+    assert_eq!(body["data"][0]["targetCurrency"], "EUR");
+
+    let response = app
+        .get("/currencies/rates?base=USD&target=EUR&start=2026-02-01&end=2026-01-01")
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn test_foreign_currency_transaction_is_converted_into_account_currency(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("traveller@test.com").await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO exchange_rates (base_currency, target_currency, rate, effective_date)
+        VALUES ('USD', 'EUR', 0.80, '2026-01-01')
+        "#,
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let account_id = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Card", "type": "checking", "balance": 500, "colorHex": "#4CAF50", "currency": "USD" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let budget_id = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 500 }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let category_id = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Travel", "allocated": 500, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let expense = |amount: i64, extra: Value| {
+        let mut body = json!({
+            "categoryId": category_id,
+            "accountId": account_id,
+            "amount": amount,
+            "transactionDate": "2026-01-15T12:00:00Z",
+            "transactionType": "expense"
+        });
+        body.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        body
+    };
+
+    // The stored USD/EUR rate is inverted to convert EUR into USD
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &expense(40, json!({ "currency": "eur" })),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let created = response.json().await;
+    assert_eq!(created["amount"], "50.00");
+    assert_eq!(created["originalAmount"], "40.00");
+    assert_eq!(created["originalCurrency"], "EUR");
+    assert_eq!(created["exchangeRate"], "1.25");
+
+    // A given rate overrides the stored one
+    let created = app
+        .post_as(
+            &user,
+            "/transactions",
+            &expense(10, json!({ "currency": "EUR", "exchangeRate": 1.1 })),
+        )
+        .await
+        .json()
+        .await;
+    assert_eq!(created["amount"], "11.00");
+
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "439.00");
+
+    // Without a stored rate the caller has to give one
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &expense(10, json!({ "currency": "GBP" })),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    // Changing the amount drops what was entered in the other currency
+    let id = created["id"].as_str().unwrap();
+    let updated = app
+        .patch_as(
+            &user,
+            &format!("/transactions/{id}"),
+            &json!({ "amount": 12 }),
+        )
+        .await
+        .json()
+        .await;
+    assert_eq!(updated["amount"], "12.00");
+    assert!(updated.get("originalCurrency").is_none());
+}
+
+#[sqlx::test]
+async fn test_funding_rules_split_new_income(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("funder@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let checking = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 1000, "colorHex": "#4CAF50" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let savings = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Savings", "type": "savings", "balance": 0, "colorHex": "#4CAF50" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 2000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let salary = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Salary", "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let rent = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Rent", "allocatedAmount": 100, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let response = app
+        .post_as(
+            &user,
+            "/funding-rules",
+            &json!({ "name": "Rent", "categoryId": rent, "fixedAmount": 500 }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let response = app
+        .post_as(
+            &user,
+            "/funding-rules",
+            &json!({ "name": "Save", "targetAccountId": savings, "percentage": 20, "position": 1 }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    // A rule needs exactly one target
+    let response = app
+        .post_as(
+            &user,
+            "/funding-rules",
+            &json!({ "name": "Both", "categoryId": rent, "targetAccountId": savings, "percentage": 5 }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": salary,
+                "accountId": checking,
+                "amount": 2000,
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "transactionType": "income"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let category = app
+        .get_as(&user, &format!("/categories/{rent}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(category["allocatedAmount"], "600.00");
+
+    let balance = |account: Value| account["balance"].as_str().unwrap().parse::<f64>().unwrap();
+    let checking_id = checking;
+    let checking = app
+        .get_as(&user, &format!("/accounts/{checking_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(balance(checking), 2600.0);
+    let savings_account = app
+        .get_as(&user, &format!("/accounts/{savings}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(balance(savings_account), 400.0);
+
+    // Undoing the income takes its splits back with it
+    let cursor = app.get_as(&user, "/events?limit=500").await.json().await["nextCursor"].clone();
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["undoneAction"], "transaction.create");
+
+    // ...and announces the removals and every balance it moved
+    let events = app
+        .get_as(&user, &format!("/events?cursor={cursor}"))
+        .await
+        .json()
+        .await;
+    let events = events["data"].as_array().unwrap();
+    let deleted = events
+        .iter()
+        .filter(|event| event["type"] == "transaction.deleted")
+        .count();
+    assert_eq!(deleted, 2);
+    let mut changed: Vec<&str> = events
+        .iter()
+        .filter(|event| event["type"] == "account.balance_changed")
+        .map(|event| event["data"]["accountId"].as_str().unwrap())
+        .collect();
+    changed.sort_unstable();
+    let mut expected = vec![checking_id.as_str(), savings.as_str()];
+    expected.sort_unstable();
+    assert_eq!(changed, expected);
+
+    let category = app
+        .get_as(&user, &format!("/categories/{rent}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(category["allocatedAmount"], "100.00");
+    let checking = app
+        .get_as(&user, &format!("/accounts/{checking_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(balance(checking), 1000.0);
+    let savings_account = app
+        .get_as(&user, &format!("/accounts/{savings}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(balance(savings_account), 0.0);
+    let transactions = app.get_as(&user, "/transactions").await.json().await;
+    assert_eq!(transactions["data"].as_array().map(Vec::len), Some(0));
+}
+
+#[sqlx::test]
+async fn test_deleted_transaction_can_be_restored_from_trash(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("trash@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let account = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 1000, "colorHex": "#4CAF50" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 2000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "allocatedAmount": 300, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let transaction = id_of(
+        app.post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category,
+                "accountId": account,
+                "amount": 120,
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let balance = || async {
+        let account = app
+            .get_as(&user, &format!("/accounts/{account}"))
+            .await
+            .json()
+            .await;
+        account["balance"].as_str().unwrap().parse::<f64>().unwrap()
+    };
+    let spent = || async {
+        let category = app
+            .get_as(&user, &format!("/categories/{category}"))
+            .await
+            .json()
+            .await;
+        category["spentAmount"]
+            .as_str()
+            .unwrap()
+            .parse::<f64>()
+            .unwrap()
+    };
+    assert_eq!(balance().await, 880.0);
+    assert_eq!(spent().await, 120.0);
+
+    let response = app
+        .delete_as(&user, &format!("/transactions/{transaction}"))
+        .await;
+    assert_eq!(response.status(), 204);
+    assert_eq!(balance().await, 1000.0);
+    assert_eq!(spent().await, 0.0);
+    let response = app
+        .get_as(&user, &format!("/transactions/{transaction}"))
+        .await;
+    assert_eq!(response.status(), 404);
+
+    let trash = app.get_as(&user, "/transactions/trash").await.json().await;
+    assert_eq!(trash["total"], 1);
+    assert_eq!(trash["data"][0]["id"], transaction.as_str());
+    assert!(trash["data"][0]["deletedAt"].is_string());
+    assert!(trash["data"][0]["purgeAt"].is_string());
+
+    let response = app
+        .post_as(
+            &user,
+            &format!("/transactions/{transaction}/restore"),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(balance().await, 880.0);
+    assert_eq!(spent().await, 120.0);
+
+    let trash = app.get_as(&user, "/transactions/trash").await.json().await;
+    assert_eq!(trash["total"], 0);
+    // Only trashed transactions can be restored
+    let response = app
+        .post_as(
+            &user,
+            &format!("/transactions/{transaction}/restore"),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn test_retention_purges_old_audit_log_and_valuations(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("retention@test.com").await;
+    sqlx::query("UPDATE users SET is_admin = TRUE WHERE id = $1::uuid")
+        .bind(&user.id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let response = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Brokerage", "type": "investment", "balance": 0, "colorHex": "#3366FF" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let account_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    // One row on each side of the default two-year windows
+    for (action, age_days) in [("test.old", 800), ("test.recent", 30)] {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (user_id, action, entity_type, entity_id, created_at)
+            VALUES ($1::uuid, $2, 'account', $3::uuid, NOW() - make_interval(days => $4))
+            "#,
+        )
+        .bind(&user.id)
+        .bind(action)
+        .bind(&account_id)
+        .bind(age_days)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"
+            INSERT INTO account_valuations (account_id, valued_on, cash_balance, holdings_value, cost_basis)
+            VALUES ($1::uuid, CURRENT_DATE - $2::int, 0, 0, 0)
+            "#,
+        )
+        .bind(&account_id)
+        .bind(age_days)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    }
+
+    let affected = |report: &Value, policy: &str| {
+        report["policies"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|p| p["policy"] == policy)
+            .map(|p| (p["retentionDays"].clone(), p["affectedRows"].clone()))
+            .unwrap()
+    };
+
+    // A dry run reports the old rows without deleting them
+    let report = app
+        .post_as(&user, "/admin/jobs/retention?dryRun=true", &json!({}))
+        .await
+        .json()
+        .await;
+    assert_eq!(affected(&report, "audit_log"), (json!(730), json!(1)));
+    assert_eq!(
+        affected(&report, "account_valuations"),
+        (json!(730), json!(1))
+    );
+
+    let report = app
+        .post_as(&user, "/admin/jobs/retention", &json!({}))
+        .await
+        .json()
+        .await;
+    assert_eq!(affected(&report, "audit_log"), (json!(730), json!(1)));
+    assert_eq!(
+        affected(&report, "account_valuations"),
+        (json!(730), json!(1))
+    );
+
+    let actions: Vec<String> = sqlx::query_scalar(
+        "SELECT action FROM audit_log WHERE action LIKE 'test.%' ORDER BY action",
+    )
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(actions, vec!["test.recent"]);
+    let ages: Vec<i32> = sqlx::query_scalar(
+        "SELECT CURRENT_DATE - valued_on FROM account_valuations WHERE account_id = $1::uuid",
+    )
+    .bind(&account_id)
+    .fetch_all(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(ages, vec![30]);
+}
+
+#[sqlx::test]
+async fn test_bulk_update_and_delete_are_atomic(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("bulk@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let mut accounts = Vec::new();
+    for name in ["Checking", "Card"] {
+        accounts.push(id_of(
+            app.post_as(
+                &user,
+                "/accounts",
+                &json!({ "name": name, "type": "checking", "balance": 1000, "colorHex": "#4CAF50" }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 2000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let mut categories = Vec::new();
+    for name in ["Misc", "Dining"] {
+        categories.push(id_of(
+            app.post_as(
+                &user,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name, "colorHex": "#FF5722" }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    let mut transactions = Vec::new();
+    for amount in [40, 60] {
+        transactions.push(id_of(
+            app.post_as(
+                &user,
+                "/transactions",
+                &json!({
+                    "categoryId": categories[0],
+                    "accountId": accounts[0],
+                    "amount": amount,
+                    "transactionDate": "2026-01-15T12:00:00Z",
+                    "transactionType": "expense"
+                }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+
+    let balance = |index: usize| {
+        let (app, user) = (&app, &user);
+        let path = format!("/accounts/{}", accounts[index]);
+        async move {
+            let account = app.get_as(user, &path).await.json().await;
+            account["balance"].as_str().unwrap().parse::<f64>().unwrap()
+        }
+    };
+    assert_eq!(balance(0).await, 900.0);
+
+    // Nothing to change
+    let response = app
+        .post_as(
+            &user,
+            "/transactions/bulk-update",
+            &json!({ "transactionIds": transactions }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions/bulk-update",
+            &json!({ "transactionIds": transactions, "categoryId": categories[1], "accountId": accounts[1] }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let body = response.json().await;
+    assert_eq!(body["updated"], 2);
+    assert!(body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|t| t["categoryId"] == categories[1].as_str()));
+    assert_eq!(balance(0).await, 1000.0);
+    assert_eq!(balance(1).await, 900.0);
+
+    // One unknown id and nothing is deleted
+    let mut with_unknown = transactions.clone();
+    with_unknown.push(uuid::Uuid::new_v4().to_string());
+    let response = app
+        .post_as(
+            &user,
+            "/transactions/bulk-delete",
+            &json!({ "transactionIds": with_unknown }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+    assert_eq!(balance(1).await, 900.0);
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions/bulk-delete",
+            &json!({ "transactionIds": transactions }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["deleted"], 2);
+    assert_eq!(balance(1).await, 1000.0);
+
+    // One undo brings the whole batch back, and the next reverses the whole update
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json().await["undoneAction"],
+        "transaction.bulk_delete"
+    );
+    let listed = app.get_as(&user, "/transactions").await.json().await;
+    assert_eq!(listed["total"], 2);
+    assert_eq!(balance(1).await, 900.0);
+
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.json().await["undoneAction"],
+        "transaction.bulk_update"
+    );
+    assert_eq!(balance(0).await, 900.0);
+    assert_eq!(balance(1).await, 1000.0);
+    let listed = app.get_as(&user, "/transactions").await.json().await;
+    assert!(listed["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|t| t["categoryId"] == categories[0].as_str()));
+}
+
+#[sqlx::test]
+async fn test_tags_filter_transactions_and_summary(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("tags@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 2000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Dining", "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let mut transactions = Vec::new();
+    for amount in [30, 70] {
+        transactions.push(id_of(
+            app.post_as(
+                &user,
+                "/transactions",
+                &json!({
+                    "categoryId": category,
+                    "amount": amount,
+                    "transactionDate": "2026-01-15T12:00:00Z",
+                    "transactionType": "expense"
+                }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+
+    let response = app
+        .post_as(
+            &user,
+            "/tags",
+            &json!({ "name": "Vacation", "colorHex": "#03A9F4" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let tag = id_of(response.json().await);
+    let response = app
+        .post_as(
+            &user,
+            "/tags",
+            &json!({ "name": "vacation", "colorHex": "#03A9F4" }),
+        )
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["code"], "DUPLICATE_TAG");
+
+    let path = format!("/transactions/{}/tags", transactions[1]);
+    let response = app.put_as(&user, &path, &json!({ "tagIds": [tag] })).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await[0]["name"], "Vacation");
+    // Someone else's (or a made-up) tag is rejected
+    let response = app
+        .put_as(&user, &path, &json!({ "tagIds": [uuid::Uuid::new_v4()] }))
+        .await;
+    assert_eq!(response.status(), 404);
+
+    let list = app
+        .get_as(&user, &format!("/transactions?tagIds={tag}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(list["total"], 1);
+    assert_eq!(list["data"][0]["id"], transactions[1].as_str());
+
+    let summary = app
+        .get_as(
+            &user,
+            &format!("/transactions/summary?month=0&year=2026&tagIds={tag}"),
+        )
+        .await
+        .json()
+        .await;
+    assert_eq!(summary["totalExpenses"], "70.00");
+
+    let response = app.get_as(&user, "/transactions?tagIds=vacation").await;
+    assert_eq!(response.status(), 422);
+    assert_eq!(response.json().await["fields"][0]["field"], "tagIds");
+
+    // Deleting the tag untags the transaction
+    let response = app.delete_as(&user, &format!("/tags/{tag}")).await;
+    assert_eq!(response.status(), 200);
+    let tags = app.get_as(&user, &path).await.json().await;
+    assert_eq!(tags, json!([]));
+}
+
+#[sqlx::test]
+async fn test_summary_by_account_totals_income_expenses_and_transfers(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("by-account@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 2000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let mut accounts = Vec::new();
+    for name in ["Checking", "Savings"] {
+        accounts.push(id_of(
+            app.post_as(
+                &user,
+                "/accounts",
+                &json!({ "name": name, "type": "checking", "balance": 1000, "colorHex": "#3366FF" }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+
+    for payload in [
+        json!({ "amount": 120, "transactionType": "expense", "accountId": accounts[0] }),
+        json!({ "amount": 800, "transactionType": "income", "accountId": accounts[0] }),
+        json!({
+            "amount": 300,
+            "transactionType": "transfer",
+            "accountId": accounts[0],
+            "destinationAccountId": accounts[1]
+        }),
+        json!({ "amount": 45, "transactionType": "expense" }),
+    ] {
+        let mut payload = payload;
+        payload["categoryId"] = json!(category);
+        payload["transactionDate"] = json!("2026-01-15T12:00:00Z");
+        let response = app.post_as(&user, "/transactions", &payload).await;
+        assert_eq!(response.status(), 201);
+    }
+
+    let response = app
+        .get_as(&user, "/transactions/summary/by-account?month=0&year=2026")
+        .await;
+    assert_eq!(response.status(), 200);
+    let summary: Value = response.json().await;
+    let rows = summary.as_array().unwrap();
+    assert_eq!(rows.len(), 3);
+    let row = |account: Option<&str>| {
+        rows.iter()
+            .find(|r| r["accountId"].as_str() == account)
+            .unwrap()
+    };
+
+    let checking = row(Some(&accounts[0]));
+    assert_eq!(checking["accountName"], "Checking");
+    assert_eq!(checking["totalIncome"], "800.00");
+    assert_eq!(checking["totalExpenses"], "120.00");
+    assert_eq!(checking["transfersOut"], "300.00");
+    assert_eq!(checking["netChange"], "380.00");
+    assert_eq!(checking["transactionCount"], 3);
+
+    let savings = row(Some(&accounts[1]));
+    assert_eq!(savings["accountName"], "Savings");
+    assert_eq!(savings["transfersIn"], "300.00");
+    assert_eq!(savings["netChange"], "300.00");
+
+    let unlinked = row(None);
+    assert!(unlinked["accountName"].is_null());
+    assert_eq!(unlinked["totalExpenses"], "45.00");
+
+    // Month and year go together
+    let response = app
+        .get_as(&user, "/transactions/summary/by-account?month=0")
+        .await;
+    assert_eq!(response.status(), 400);
+
+    // Other users see none of it
+    let other = app.register_user("by-account-other@test.com").await;
+    let response = app.get_as(&other, "/transactions/summary/by-account").await;
+    assert_eq!(response.json().await, json!([]));
+}
+
+#[sqlx::test]
+async fn test_transfer_fee_and_destination_amount_move_balances(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("transfer-fee@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 2000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Savings", "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let mut accounts = Vec::new();
+    for name in ["Checking", "Abroad"] {
+        accounts.push(id_of(
+            app.post_as(
+                &user,
+                "/accounts",
+                &json!({ "name": name, "type": "checking", "balance": 1000, "colorHex": "#3366FF" }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    let balance = |account: &str| {
+        let path = format!("/accounts/{account}");
+        let app = &app;
+        let user = &user;
+        async move {
+            let account: Value = app.get_as(user, &path).await.json().await;
+            account["balance"].as_str().unwrap().to_string()
+        }
+    };
+
+    // The source pays amount + fee; the destination receives its own amount
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category,
+                "amount": 300,
+                "feeAmount": 2.50,
+                "destinationAmount": 270,
+                "transactionType": "transfer",
+                "accountId": accounts[0],
+                "destinationAccountId": accounts[1],
+                "transactionDate": "2026-01-15T12:00:00Z"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let transfer: Value = response.json().await;
+    assert_eq!(transfer["feeAmount"], "2.50");
+    assert_eq!(transfer["destinationAmount"], "270.00");
+    let transfer_id = transfer["id"].as_str().unwrap().to_string();
+    assert_eq!(balance(&accounts[0]).await, "697.50");
+    assert_eq!(balance(&accounts[1]).await, "1270.00");
+
+    // Recomputing from history agrees with the stored balances
+    for account in &accounts {
+        let check: Value = app
+            .post_as(
+                &user,
+                &format!("/accounts/{account}/recompute-balance"),
+                &json!({}),
+            )
+            .await
+            .json()
+            .await;
+        assert_eq!(
+            check["drift"].as_str().unwrap().parse::<f64>().unwrap(),
+            0.0
+        );
+    }
+
+    let summary: Value = app
+        .get_as(&user, "/transactions/summary/by-account?month=0&year=2026")
+        .await
+        .json()
+        .await;
+    let row = |account: &str| {
+        summary
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["accountId"] == account)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(row(&accounts[0])["transfersOut"], "302.50");
+    assert_eq!(row(&accounts[1])["transfersIn"], "270.00");
+
+    // Dropping the fee and destination amount moves the plain amount
+    let path = format!("/transactions/{transfer_id}");
+    let response = app
+        .patch_as(
+            &user,
+            &path,
+            &json!({ "feeAmount": null, "destinationAmount": null }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(balance(&accounts[0]).await, "700.00");
+    assert_eq!(balance(&accounts[1]).await, "1300.00");
+
+    // Only transfers carry them
+    let response = app
+        .patch_as(
+            &user,
+            &path,
+            &json!({ "feeAmount": 1, "transactionType": "expense", "destinationAccountId": null }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category,
+                "amount": 20,
+                "feeAmount": 1,
+                "accountId": accounts[0],
+                "transactionDate": "2026-01-15T12:00:00Z"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app.patch_as(&user, &path, &json!({ "feeAmount": 5 })).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(balance(&accounts[0]).await, "695.00");
+
+    // Deleting reverses both sides in full
+    let response = app.delete_as(&user, &path).await;
+    assert_eq!(response.status(), 204);
+    assert_eq!(balance(&accounts[0]).await, "1000.00");
+    assert_eq!(balance(&accounts[1]).await, "1000.00");
+}
+
+#[sqlx::test]
+async fn test_cors_preflight_allows_configured_origin(pool: PgPool) {
+    let app = TestApp::new(pool);
+
+    let response = app
+        .request(
+            actix_web::test::TestRequest::default()
+                .method(actix_web::http::Method::OPTIONS)
+                .uri("/budgets")
+                .insert_header(("Origin", common::ALLOWED_ORIGIN))
+                .insert_header(("Access-Control-Request-Method", "POST")),
+        )
+        .await;
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.header("access-control-allow-origin"),
+        Some(common::ALLOWED_ORIGIN)
+    );
+}
+
+#[sqlx::test]
+async fn test_auth_endpoints_are_rate_limited(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let payload = json!({
+        "email": "ratelimit@test.com",
+        "password": "Password123"
+    });
+
+    let mut statuses = Vec::new();
+    for _ in 0..6 {
+        statuses.push(app.post("/auth/login", &payload).await.status());
+    }
+
+    assert!(statuses[..5].iter().all(|status| *status == 401));
+    assert_eq!(statuses[5], 429);
+}
+
+#[sqlx::test]
+async fn test_writes_are_rate_limited_per_user(pool: PgPool) {
+    let app = TestApp::new(pool).with_write_quota(be_rust::ratelimit::RateLimitQuota {
+        period: std::time::Duration::from_secs(60),
+        burst: 2,
+    });
+    let user = app.register_user("busy@test.com").await;
+    let tag = |name: &str| json!({ "name": name, "colorHex": "#336699" });
+
+    assert_eq!(app.post_as(&user, "/tags", &tag("one")).await.status(), 201);
+    assert_eq!(app.post_as(&user, "/tags", &tag("two")).await.status(), 201);
+
+    let response = app.post_as(&user, "/tags", &tag("three")).await;
+    assert_eq!(response.status(), 429);
+    let retry_after: u64 = response.header("retry-after").unwrap().parse().unwrap();
+    assert!((1..=60).contains(&retry_after));
+    assert_eq!(response.json().await["code"], "RATE_LIMITED");
+
+    // Reads aren't counted, and other users have their own allowance
+    assert_eq!(app.get_as(&user, "/tags").await.status(), 200);
+    let other = app.register_user("calm@test.com").await;
+    assert_eq!(
+        app.post_as(&other, "/tags", &tag("one")).await.status(),
+        201
+    );
+}
+
+#[sqlx::test]
+async fn test_budget_is_shared_with_invited_editors_and_viewers(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let owner = app.register_user("share_owner@test.com").await;
+    let editor = app.register_user("share_editor@test.com").await;
+    let viewer = app.register_user("share_viewer@test.com").await;
+    let stranger = app.register_user("share_stranger@test.com").await;
+
+    let budget_id = app
+        .post_as(
+            &owner,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 500 }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let category_id = app
+        .post_as(
+            &owner,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "allocated": 300, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let invite_path = format!("/budgets/{budget_id}/invite");
+    for (user, role) in [(&editor, "editor"), (&viewer, "viewer")] {
+        let response = app
+            .post_as(
+                &owner,
+                &invite_path,
+                &json!({ "email": user.email.to_uppercase(), "role": role }),
+            )
+            .await;
+        assert_eq!(response.status(), 201);
+    }
+
+    // A second pending invitation to the same email is a conflict, and only
+    // the owner can invite
+    let response = app
+        .post_as(
+            &owner,
+            &invite_path,
+            &json!({ "email": editor.email, "role": "viewer" }),
+        )
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["code"], "DUPLICATE_INVITATION");
+    let response = app
+        .post_as(
+            &stranger,
+            &invite_path,
+            &json!({ "email": "someone@test.com", "role": "viewer" }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+
+    // Invitations can only be accepted by the invited user
+    for user in [&editor, &viewer] {
+        let invitations = app.get_as(user, "/budgets/invitations").await.json().await;
+        let invitations = invitations.as_array().unwrap();
+        assert_eq!(invitations.len(), 1);
+        let accept_path = format!(
+            "/budgets/invitations/{}/accept",
+            invitations[0]["id"].as_str().unwrap()
+        );
+        assert_eq!(
+            app.post_as(&stranger, &accept_path, &json!({}))
+                .await
+                .status(),
+            404
+        );
+        let accepted = app.post_as(user, &accept_path, &json!({})).await;
+        assert_eq!(accepted.status(), 200);
+        assert_eq!(accepted.json().await["id"], budget_id.as_str());
+        assert_eq!(
+            app.post_as(user, &accept_path, &json!({})).await.status(),
+            404
+        );
+    }
+
+    let members = app
+        .get_as(&viewer, &format!("/budgets/{budget_id}/members"))
+        .await
+        .json()
+        .await;
+    let roles: Vec<&str> = members
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["owner", "editor", "viewer"]);
+
+    // Both members can read the budget and its categories; strangers can't
+    let categories_path = format!("/categories/budget/{budget_id}");
+    for user in [&editor, &viewer] {
+        let budgets = app.get_as(user, "/budgets").await.json().await;
+        assert_eq!(budgets.as_array().map(Vec::len), Some(1));
+        let categories = app.get_as(user, &categories_path).await.json().await;
+        assert_eq!(categories.as_array().map(Vec::len), Some(1));
+    }
+    assert_eq!(
+        app.get_as(&stranger, &format!("/budgets/{budget_id}"))
+            .await
+            .status(),
+        404
+    );
+
+    // Only the editor can add transactions to the shared category
+    let transaction = json!({
+        "categoryId": category_id,
+        "amount": 40,
+        "transactionDate": "2026-01-15T12:00:00Z",
+        "transactionType": "expense"
+    });
+    assert_eq!(
+        app.post_as(&editor, "/transactions", &transaction)
+            .await
+            .status(),
+        201
+    );
+    assert_eq!(
+        app.post_as(&viewer, "/transactions", &transaction)
+            .await
+            .status(),
+        404
+    );
+    assert_eq!(
+        app.post_as(&stranger, "/transactions", &transaction)
+            .await
+            .status(),
+        404
+    );
+    let category = app
+        .get_as(&owner, &format!("/categories/{category_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(category["spentAmount"], "40.00");
+
+    // Members can leave; the viewer then loses access
+    let leave_path = format!("/budgets/{budget_id}/members/{}", viewer.id);
+    assert_eq!(app.delete_as(&editor, &leave_path).await.status(), 404);
+    assert_eq!(app.delete_as(&viewer, &leave_path).await.status(), 204);
+    assert_eq!(app.get_as(&viewer, &categories_path).await.status(), 404);
+}
+
+#[sqlx::test]
+async fn test_budget_members_see_shared_transactions_in_lists(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let owner = app.register_user("list-owner@test.com").await;
+    let editor = app.register_user("list-editor@test.com").await;
+    let stranger = app.register_user("list-stranger@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &owner,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 500 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let category_id = id_of(
+        app.post_as(
+            &owner,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "allocatedAmount": 300, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let response = app
+        .post_as(
+            &owner,
+            &format!("/budgets/{budget_id}/invite"),
+            &json!({ "email": editor.email, "role": "editor" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let invitations = app
+        .get_as(&editor, "/budgets/invitations")
+        .await
+        .json()
+        .await;
+    let accept_path = format!(
+        "/budgets/invitations/{}/accept",
+        invitations[0]["id"].as_str().unwrap()
+    );
+    assert_eq!(
+        app.post_as(&editor, &accept_path, &json!({}))
+            .await
+            .status(),
+        200
+    );
+
+    let expense = |amount: i64| {
+        json!({
+            "categoryId": category_id,
+            "amount": amount,
+            "transactionDate": "2026-01-15T12:00:00Z",
+            "transactionType": "expense"
+        })
+    };
+    let response = app.post_as(&owner, "/transactions", &expense(40)).await;
+    assert_eq!(response.status(), 201);
+    let response = app.post_as(&editor, "/transactions", &expense(25)).await;
+    assert_eq!(response.status(), 201);
+    let editor_transaction = id_of(response.json().await);
+
+    // Both members list and summarize everything in the shared budget
+    for user in [&owner, &editor] {
+        let list = app.get_as(user, "/transactions").await.json().await;
+        assert_eq!(list["total"], 2);
+        let list = app
+            .get_as(user, "/transactions?detailed=true")
+            .await
+            .json()
+            .await;
+        assert_eq!(list["total"], 2);
+        for path in [
+            "/transactions/summary?month=0&year=2026",
+            "/transactions/summary?startDate=2026-01-01T00:00:00Z&endDate=2026-02-01T00:00:00Z",
+        ] {
+            let summary = app.get_as(user, path).await.json().await;
+            assert_eq!(summary["totalExpenses"], "65.00", "{path}");
+        }
+        let categories = app.get_as(user, "/categories").await.json().await;
+        assert_eq!(categories.as_array().map(Vec::len), Some(1));
+    }
+    let list = app.get_as(&stranger, "/transactions").await.json().await;
+    assert_eq!(list["total"], 0);
+    let summary = app
+        .get_as(&stranger, "/transactions/summary?month=0&year=2026")
+        .await
+        .json()
+        .await;
+    assert_eq!(summary["totalExpenses"], "0");
+
+    // What the editor trashes shows up in their trash, ready to restore
+    let path = format!("/transactions/{editor_transaction}");
+    assert_eq!(app.delete_as(&editor, &path).await.status(), 204);
+    let trash = app
+        .get_as(&editor, "/transactions/trash")
+        .await
+        .json()
+        .await;
+    assert_eq!(trash["total"], 1);
+    assert_eq!(trash["data"][0]["id"], editor_transaction.as_str());
+    let response = app
+        .post_as(&editor, &format!("{path}/restore"), &json!({}))
+        .await;
+    assert_eq!(response.status(), 200);
+    let trash = app
+        .get_as(&stranger, "/transactions/trash")
+        .await
+        .json()
+        .await;
+    assert_eq!(trash["total"], 0);
+}
+
+#[sqlx::test]
+async fn test_audit_log_records_budget_and_account_changes(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("audit@test.com").await;
+    let other = app.register_user("audit_other@test.com").await;
+
+    let budget_id = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalIncome": 1000 }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/budgets/{budget_id}/income"),
+            &json!({ "totalIncome": 1500 }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let account_id = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Wallet", "type": "checking", "balance": 20, "colorHex": "#4CAF50" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let response = app
+        .delete_as(&user, &format!("/accounts/{account_id}"))
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let log = app
+        .get_as(&user, "/audit-log?entityType=budget")
+        .await
+        .json()
+        .await;
+    assert_eq!(log["total"], 2);
+    let updated = &log["data"][0];
+    assert_eq!(updated["action"], "budget.update");
+    assert_eq!(updated["actorId"], user.id.as_str());
+    assert_eq!(updated["before"]["total_income"], "1000.00");
+    assert_eq!(updated["after"]["total_income"], "1500.00");
+    assert_eq!(log["data"][1]["action"], "budget.create");
+    assert!(log["data"][1]["before"].is_null());
+
+    // Snapshots come back decrypted
+    let log = app
+        .get_as(
+            &user,
+            &format!("/audit-log?entityId={account_id}&action=account.delete"),
+        )
+        .await
+        .json()
+        .await;
+    assert_eq!(log["total"], 1);
+    assert_eq!(log["data"][0]["before"]["name"], "Wallet");
+    assert!(log["data"][0]["after"].is_null());
+
+    let response = app
+        .get_as(
+            &user,
+            "/audit-log?from=2026-02-01T00:00:00Z&to=2026-01-01T00:00:00Z",
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    // Users only see their own history
+    let log = app.get_as(&other, "/audit-log").await.json().await;
+    assert_eq!(log["total"], 0);
+}
+
+#[sqlx::test]
+async fn test_stale_if_match_rejects_update(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("etag@test.com").await;
+
+    let created = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalIncome": 1000 }),
+        )
+        .await;
+    let budget_id = created.json().await["id"].as_str().unwrap().to_string();
+    let path = format!("/budgets/{budget_id}/income");
+
+    let fetched = app.get_as(&user, &format!("/budgets/{budget_id}")).await;
+    let etag = fetched.header("etag").unwrap().to_string();
+    assert_eq!(created.header("etag"), Some(etag.as_str()));
+
+    // The first device updates with the current version
+    let response = app
+        .patch_if_match_as(&user, &path, &etag, &json!({ "totalIncome": 1200 }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let new_etag = response.header("etag").unwrap().to_string();
+    assert_ne!(new_etag, etag);
+
+    // The second device still holds the old version
+    let response = app
+        .patch_if_match_as(&user, &path, &etag, &json!({ "totalIncome": 900 }))
+        .await;
+    assert_eq!(response.status(), 412);
+    assert_eq!(response.json().await["code"], "PRECONDITION_FAILED");
+    let budget = app
+        .get_as(&user, &format!("/budgets/{budget_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(budget["totalIncome"], "1200.00");
+
+    // Without If-Match the update is unconditional
+    let response = app
+        .patch_as(&user, &path, &json!({ "totalIncome": 900 }))
+        .await;
+    assert_eq!(response.status(), 200);
+
+    // The same applies to the other resources
+    let category = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Rent", "allocated": 500, "colorHex": "#FF5722" }),
+        )
+        .await;
+    let category_etag = category.header("etag").unwrap().to_string();
+    let category_path = format!(
+        "/categories/{}",
+        category.json().await["id"].as_str().unwrap()
+    );
+    let response = app
+        .patch_as(&user, &category_path, &json!({ "name": "Housing" }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let response = app
+        .patch_if_match_as(
+            &user,
+            &category_path,
+            &category_etag,
+            &json!({ "name": "Home" }),
+        )
+        .await;
+    assert_eq!(response.status(), 412);
+}
+
+#[sqlx::test]
+async fn test_category_template_creates_budget_categories(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("templates@test.com").await;
+
+    let response = app
+        .post_as(
+            &user,
+            "/category-templates",
+            &json!({
+                "name": "Standard month",
+                "categories": [
+                    { "name": "Rent", "allocatedAmount": 1200, "colorHex": "#3366FF" },
+                    { "name": "Groceries", "allocatedAmount": 400 }
+                ]
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let template: Value = response.json().await;
+    let template_id = template["id"].as_str().unwrap().to_string();
+    assert_eq!(template["categories"][1]["colorHex"], "#64748b");
+
+    // Duplicate names within a template are rejected
+    let response = app
+        .post_as(
+            &user,
+            "/category-templates",
+            &json!({ "name": "Doubled", "categories": [{ "name": "Rent" }, { "name": "rent" }] }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalIncome": 3000, "templateId": template_id }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let budget: Value = response.json().await;
+    assert_eq!(budget["totals"]["totalAllocated"], "1600.00");
+    let budget_id = budget["id"].as_str().unwrap().to_string();
+
+    let categories: Value = app
+        .get_as(&user, &format!("/categories/budget/{budget_id}"))
+        .await
+        .json()
+        .await;
+    let names: Vec<&str> = categories
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["Groceries", "Rent"]);
+
+    // Applying to a budget that already has the categories adds nothing
+    let path = format!("/budgets/{budget_id}/apply-template");
+    let response = app
+        .post_as(&user, &path, &json!({ "templateId": template_id }))
+        .await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.json().await, json!([]));
+
+    let response = app
+        .post_as(&user, "/budgets", &json!({ "month": 1, "year": 2026 }))
+        .await;
+    let february = response.json().await["id"].as_str().unwrap().to_string();
+    let response = app
+        .post_as(
+            &user,
+            &format!("/budgets/{february}/apply-template"),
+            &json!({ "templateId": template_id }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.json().await.as_array().unwrap().len(), 2);
+
+    // Someone else's template can't be used, and the budget isn't created
+    let other = app.register_user("templates-other@test.com").await;
+    let response = app
+        .post_as(
+            &other,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "templateId": template_id }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+    let response = app
+        .post_as(&other, "/budgets", &json!({ "month": 0, "year": 2026 }))
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .delete_as(&user, &format!("/category-templates/{template_id}"))
+        .await;
+    assert_eq!(response.status(), 204);
+    let response = app.get_as(&user, "/category-templates").await;
+    assert_eq!(response.json().await, json!([]));
+}
+
+#[sqlx::test]
+async fn test_copy_budget_clones_categories_into_new_month(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("copy-budget@test.com").await;
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalIncome": 4000, "savingsRate": 10 }),
+        )
+        .await;
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+    for (name, amount, color) in [("Rent", 1500, "#3366FF"), ("Dining", 200, "#FF5722")] {
+        let response = app
+            .post_as(
+                &user,
+                "/categories",
+                &json!({
+                    "budgetId": budget_id,
+                    "name": name,
+                    "allocatedAmount": amount,
+                    "colorHex": color
+                }),
+            )
+            .await;
+        assert_eq!(response.status(), 201);
+    }
+
+    let copy =
+        json!({ "sourceMonth": 0, "sourceYear": 2026, "targetMonth": 1, "targetYear": 2026 });
+    let response = app.post_as(&user, "/budgets/copy", &copy).await;
+    assert_eq!(response.status(), 201);
+    let copied: Value = response.json().await;
+    assert_ne!(copied["id"], budget_id.as_str());
+    assert_eq!(copied["month"], 1);
+    assert_eq!(copied["totalIncome"], "4000.00");
+    assert_eq!(copied["savingsRate"], "10.00");
+    assert_eq!(copied["totals"]["totalAllocated"], "1700.00");
+    let categories = copied["categories"].as_array().unwrap();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0]["name"], "Dining");
+    assert_eq!(categories[0]["colorHex"], "#FF5722");
+    assert_eq!(categories[1]["allocatedAmount"], "1500.00");
+    assert_eq!(categories[1]["budgetId"], copied["id"]);
+
+    // The target month now has a budget
+    let response = app.post_as(&user, "/budgets/copy", &copy).await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["code"], "BUDGET_MONTH_CONFLICT");
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets/copy",
+            &json!({ "sourceMonth": 5, "sourceYear": 2026, "targetMonth": 6, "targetYear": 2026 }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets/copy",
+            &json!({ "sourceMonth": 0, "sourceYear": 2026, "targetMonth": 0, "targetYear": 2026 }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn test_year_summary_covers_every_month(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("yearly@test.com").await;
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalIncome": 3000, "savingsRate": 20 }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+    let response = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "General", "allocatedAmount": 2000, "colorHex": "#22AA66" }),
+        )
+        .await;
+    let category_id = response.json().await["id"].as_str().unwrap().to_string();
+    let response = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Everyday", "type": "checking", "balance": 0, "colorHex": "#3366FF" }),
+        )
+        .await;
+    let account_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    for (amount, kind) in [(3200, "income"), (1800, "expense")] {
+        let response = app
+            .post_as(
+                &user,
+                "/transactions",
+                &json!({
+                    "categoryId": category_id,
+                    "accountId": account_id,
+                    "amount": amount,
+                    "transactionDate": "2026-01-15T12:00:00Z",
+                    "transactionType": kind
+                }),
+            )
+            .await;
+        assert_eq!(response.status(), 201);
+    }
+
+    let response = app.get_as(&user, "/budgets/year/2026/summary").await;
+    assert_eq!(response.status(), 200);
+    let months = response.json().await;
+    let months = months.as_array().unwrap();
+    assert_eq!(months.len(), 12);
+
+    let january = &months[0];
+    assert_eq!(january["month"], 0);
+    assert_eq!(january["budgetId"], budget_id.as_str());
+    assert_eq!(january["budgetedIncome"], "3000.00");
+    assert_eq!(january["savingsTarget"], "600.0000");
+    assert_eq!(january["budgetedSpend"], "2000.00");
+    assert_eq!(january["income"], "3200.00");
+    assert_eq!(january["actualSpend"], "1800.00");
+    assert_eq!(january["savingsAchieved"], "1400.00");
+
+    let february = &months[1];
+    assert!(february["budgetId"].is_null());
+    assert_eq!(february["budgetedSpend"], "0");
+    assert_eq!(february["actualSpend"], "0");
+    assert_eq!(february["savingsAchieved"], "0");
+
+    let months = app
+        .get_as(&user, "/budgets/year/2026/summary?monthBase=1")
+        .await
+        .json()
+        .await;
+    assert_eq!(months[0]["month"], 1);
+    assert_eq!(months[11]["month"], 12);
+
+    // Other users only see their own (empty) year
+    let other = app.register_user("yearly_other@test.com").await;
+    let months = app
+        .get_as(&other, "/budgets/year/2026/summary")
+        .await
+        .json()
+        .await;
+    assert!(months[0]["budgetId"].is_null());
+
+    let response = app.get_as(&user, "/budgets/year/1999/summary").await;
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn test_months_follow_the_users_timezone(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("new-york@test.com").await;
+
+    let response = app
+        .put_as(
+            &user,
+            "/auth/me/timezone",
+            &json!({ "timezone": "America/New_York" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category_id = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Dining", "allocatedAmount": 200, "colorHex": "#22AA66" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    // 23:30 on January 31st in New York is already February in UTC
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category_id,
+                "amount": 50,
+                "transactionDate": "2026-01-31T23:30:00-05:00",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let amount = |value: &Value| value.as_str().unwrap().parse::<f64>().unwrap();
+    let january = app
+        .get_as(&user, "/transactions/summary?month=0&year=2026")
+        .await
+        .json()
+        .await;
+    assert_eq!(january["totalExpenses"], "50.00");
+    let february = app
+        .get_as(&user, "/transactions/summary?month=1&year=2026")
+        .await
+        .json()
+        .await;
+    assert_eq!(amount(&february["totalExpenses"]), 0.0);
+
+    let category = app
+        .get_as(&user, &format!("/categories/{category_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(category["spentAmount"], "50.00");
+
+    let report = app
+        .get_as(&user, "/reports/compare?month=0&year=2026")
+        .await
+        .json()
+        .await;
+    assert_eq!(report["totals"]["current"], "50.00");
+    let report = app
+        .get_as(&user, "/reports/compare?month=1&year=2026")
+        .await
+        .json()
+        .await;
+    assert_eq!(amount(&report["totals"]["current"]), 0.0);
+    assert_eq!(report["totals"]["previous"], "50.00");
+}
+
+#[sqlx::test]
+async fn test_budget_full_embeds_categories_and_totals(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("budget-full@test.com").await;
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 2, "year": 2026, "totalIncome": 3000, "savingsRate": 10 }),
+        )
+        .await;
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+    let mut categories = Vec::new();
+    for (name, amount) in [("Rent", 1500), ("Groceries", 500)] {
+        let response = app
+            .post_as(
+                &user,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name, "allocatedAmount": amount }),
+            )
+            .await;
+        categories.push(response.json().await["id"].as_str().unwrap().to_string());
+    }
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": categories[1],
+                "amount": 120,
+                "transactionDate": "2026-03-10T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let response = app
+        .get_as(&user, &format!("/budgets/{budget_id}/full"))
+        .await;
+    assert_eq!(response.status(), 200);
+    let full: Value = response.json().await;
+    assert_eq!(full["id"], budget_id.as_str());
+    assert_eq!(full["totals"]["totalAllocated"], "2000.00");
+    assert_eq!(full["totals"]["unallocated"], "700.0000");
+    assert_eq!(full["totalSpent"], "120.00");
+    assert_eq!(full["totalRemaining"], "1880.00");
+    let groceries = &full["categories"][0];
+    assert_eq!(groceries["name"], "Groceries");
+    assert_eq!(groceries["spentAmount"], "120.00");
+    assert_eq!(groceries["remainingAmount"], "380.00");
+
+    let other = app.register_user("budget-full-other@test.com").await;
+    let response = app
+        .get_as(&other, &format!("/budgets/{budget_id}/full"))
+        .await;
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test]
+async fn test_strict_mode_rejects_allocations_beyond_spending_budget(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("strict@test.com").await;
+
+    // Spending budget: 1000 - 20% savings = 800
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalIncome": 1000, "savingsRate": 20 }),
+        )
+        .await;
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+    let category = |name: &str, allocated: i64| json!({ "budgetId": budget_id, "name": name, "allocatedAmount": allocated, "colorHex": "#22AA66" });
+
+    let response = app
+        .post_as(&user, "/categories?strict=true", &category("Rent", 500))
+        .await;
+    assert_eq!(response.status(), 201);
+    let rent_id = response.json().await["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .post_as(&user, "/categories?strict=true", &category("Travel", 400))
+        .await;
+    assert_eq!(response.status(), 409);
+    let body = response.json().await;
+    assert_eq!(body["code"], "ALLOCATION_EXCEEDS_BUDGET");
+    assert_eq!(body["details"]["overAllocatedBy"], "100.00");
+
+    // Without strict mode the write goes through with a warning
+    let response = app
+        .post_as(&user, "/categories", &category("Travel", 400))
+        .await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(
+        response.json().await["warnings"][0]["code"],
+        "OVER_ALLOCATED"
+    );
+
+    let budget = app
+        .get_as(&user, &format!("/budgets/{budget_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(budget["totals"]["totalAllocated"], "900.00");
+    assert_eq!(budget["totals"]["unallocated"], "-100.0000");
+
+    // Already over budget: raising is rejected, renaming and cutting are not
+    let rent = format!("/categories/{rent_id}?strict=true");
+    let response = app
+        .patch_as(&user, &rent, &json!({ "allocatedAmount": 550 }))
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(
+        response.json().await["details"]["overAllocatedBy"],
+        "150.00"
+    );
+    let response = app
+        .patch_as(&user, &rent, &json!({ "name": "Housing" }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let response = app
+        .patch_as(&user, &rent, &json!({ "allocatedAmount": 400 }))
+        .await;
+    assert_eq!(response.status(), 200);
+}
+
+#[sqlx::test]
+async fn test_move_allocation_is_atomic_audited_and_needs_edit_access(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let owner = app.register_user("mover-owner@test.com").await;
+    let viewer = app.register_user("mover-viewer@test.com").await;
+    let stranger = app.register_user("mover-stranger@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &owner,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 1000 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let mut categories = Vec::new();
+    for (name, allocated) in [("Dining", 200), ("Groceries", 300)] {
+        categories.push(id_of(
+            app.post_as(
+                &owner,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name, "allocatedAmount": allocated, "colorHex": "#FF5722" }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    let (dining, groceries) = (&categories[0], &categories[1]);
+
+    let response = app
+        .post_as(
+            &owner,
+            &format!("/budgets/{budget_id}/invite"),
+            &json!({ "email": viewer.email, "role": "viewer" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let invitations = app
+        .get_as(&viewer, "/budgets/invitations")
+        .await
+        .json()
+        .await;
+    let accept_path = format!(
+        "/budgets/invitations/{}/accept",
+        invitations[0]["id"].as_str().unwrap()
+    );
+    assert_eq!(
+        app.post_as(&viewer, &accept_path, &json!({}))
+            .await
+            .status(),
+        200
+    );
+
+    let allocated = |category_id: &str| {
+        let (app, owner) = (&app, &owner);
+        let path = format!("/categories/{category_id}");
+        async move {
+            let category = app.get_as(owner, &path).await.json().await;
+            category["allocatedAmount"].as_str().unwrap().to_string()
+        }
+    };
+    let path = format!("/budgets/{budget_id}/move-allocation");
+    let move_of = |amount: i64| json!({ "fromCategoryId": dining, "toCategoryId": groceries, "amount": amount });
+
+    // More than the source holds moves nothing at all
+    let response = app.post_as(&owner, &path, &move_of(250)).await;
+    assert_eq!(response.status(), 400);
+    assert_eq!(response.json().await["code"], "INSUFFICIENT_ALLOCATION");
+    assert_eq!(allocated(dining).await, "200.00");
+    assert_eq!(allocated(groceries).await, "300.00");
+
+    // Viewers and outsiders can't move anything
+    for user in [&viewer, &stranger] {
+        let response = app.post_as(user, &path, &move_of(50)).await;
+        assert_eq!(response.status(), 404);
+    }
+    assert_eq!(allocated(dining).await, "200.00");
+
+    let response = app.post_as(&owner, &path, &move_of(50)).await;
+    assert_eq!(response.status(), 200);
+    let body = response.json().await;
+    assert_eq!(body["from"]["allocatedAmount"], "150.00");
+    assert_eq!(body["to"]["allocatedAmount"], "350.00");
+    assert_eq!(allocated(dining).await, "150.00");
+    assert_eq!(allocated(groceries).await, "350.00");
+
+    let log = app
+        .get_as(&owner, "/audit-log?action=budget.move_allocation")
+        .await
+        .json()
+        .await;
+    assert_eq!(log["total"], 1);
+    let entry = &log["data"][0];
+    assert_eq!(entry["entityId"], budget_id.as_str());
+    assert_eq!(entry["details"]["fromCategoryId"], dining.as_str());
+    assert_eq!(entry["details"]["toCategoryId"], groceries.as_str());
+    assert_eq!(
+        entry["details"]["amount"]
+            .as_str()
+            .unwrap()
+            .parse::<f64>()
+            .unwrap(),
+        50.0
+    );
+}
+
+#[sqlx::test]
+async fn test_auto_allocate_previews_then_applies_average_spending(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let owner = app.register_user("auto-owner@test.com").await;
+    let viewer = app.register_user("auto-viewer@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let december = id_of(
+        app.post_as(&owner, "/budgets", &json!({ "month": 11, "year": 2025 }))
+            .await
+            .json()
+            .await,
+    );
+    let past_groceries = id_of(
+        app.post_as(
+            &owner,
+            "/categories",
+            &json!({ "budgetId": december, "name": "Groceries", "allocatedAmount": 250, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let response = app
+        .post_as(
+            &owner,
+            "/transactions",
+            &json!({
+                "categoryId": past_groceries,
+                "amount": 320,
+                "transactionDate": "2025-12-10T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let january = id_of(
+        app.post_as(&owner, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let groceries = id_of(
+        app.post_as(
+            &owner,
+            "/categories",
+            &json!({ "budgetId": january, "name": "groceries", "allocatedAmount": 100, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let response = app
+        .post_as(
+            &owner,
+            &format!("/budgets/{january}/invite"),
+            &json!({ "email": viewer.email, "role": "viewer" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let invitations = app
+        .get_as(&viewer, "/budgets/invitations")
+        .await
+        .json()
+        .await;
+    let accept_path = format!(
+        "/budgets/invitations/{}/accept",
+        invitations[0]["id"].as_str().unwrap()
+    );
+    assert_eq!(
+        app.post_as(&viewer, &accept_path, &json!({}))
+            .await
+            .status(),
+        200
+    );
+
+    let allocated = || async {
+        app.get_as(&owner, &format!("/categories/{groceries}"))
+            .await
+            .json()
+            .await["allocatedAmount"]
+            .clone()
+    };
+    let path = format!("/budgets/{january}/auto-allocate");
+
+    // A preview suggests without changing anything, and viewers may look
+    for user in [&owner, &viewer] {
+        let response = app
+            .post_as(user, &format!("{path}?months=1&preview=true"), &json!({}))
+            .await;
+        assert_eq!(response.status(), 200);
+        let preview = response.json().await;
+        assert_eq!(preview["applied"], false);
+        assert_eq!(preview["suggestions"][0]["categoryId"], groceries.as_str());
+        assert_eq!(preview["suggestions"][0]["currentAllocated"], "100.00");
+        assert_eq!(preview["suggestions"][0]["suggestedAllocated"], "320.00");
+        assert_eq!(preview["suggestions"][0]["monthsWithData"], 1);
+        assert_eq!(preview["totalSuggested"], "320.00");
+    }
+    assert_eq!(allocated().await, "100.00");
+
+    // ...but only editors can apply
+    let response = app
+        .post_as(&viewer, &format!("{path}?months=1"), &json!({}))
+        .await;
+    assert_eq!(response.status(), 404);
+    assert_eq!(allocated().await, "100.00");
+
+    let response = app
+        .post_as(&owner, &format!("{path}?months=1"), &json!({}))
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["applied"], true);
+    assert_eq!(allocated().await, "320.00");
+
+    let response = app
+        .post_as(&owner, &format!("{path}?months=13"), &json!({}))
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn test_overdraft_guard_rejects_transactions_below_zero(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("overdraft@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Shopping" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let response = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({
+                "name": "Checking",
+                "type": "checking",
+                "balance": 100,
+                "colorHex": "#3366FF",
+                "allowNegativeBalance": false
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let account: Value = response.json().await;
+    assert_eq!(account["allowNegativeBalance"], false);
+    let account_id = id_of(account);
+
+    let expense = |amount: i64| {
+        json!({
+            "categoryId": category,
+            "accountId": account_id,
+            "amount": amount,
+            "transactionDate": "2026-01-15T12:00:00Z",
+            "transactionType": "expense"
+        })
+    };
+    let balance = || async {
+        app.get_as(&user, &format!("/accounts/{account_id}"))
+            .await
+            .json()
+            .await["balance"]
+            .clone()
+    };
+
+    let response = app.post_as(&user, "/transactions", &expense(150)).await;
+    assert_eq!(response.status(), 409);
+    let body: Value = response.json().await;
+    assert_eq!(body["code"], "INSUFFICIENT_FUNDS");
+    assert_eq!(body["details"]["accountId"], account_id.as_str());
+    assert_eq!(body["details"]["shortfall"], "50.00");
+    assert_eq!(balance().await, "100.00");
+
+    let response = app.post_as(&user, "/transactions", &expense(80)).await;
+    assert_eq!(response.status(), 201);
+    let transaction_id = id_of(response.json().await);
+
+    // Raising the amount past the balance is rejected too
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/transactions/{transaction_id}"),
+            &json!({ "amount": 120 }),
+        )
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["details"]["shortfall"], "20.00");
+    assert_eq!(balance().await, "20.00");
+
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/accounts/{account_id}"),
+            &json!({ "allowNegativeBalance": true }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let response = app.post_as(&user, "/transactions", &expense(150)).await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(balance().await, "-130.00");
+}
+
+#[sqlx::test]
+async fn test_overdraft_guard_compares_balances_before_and_after(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("overdraft-net@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Shopping" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({
+                "name": "Checking",
+                "type": "checking",
+                "balance": 0,
+                "colorHex": "#3366FF",
+                "allowNegativeBalance": false
+            }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let transaction = |amount: i64, kind: &str| {
+        json!({
+            "categoryId": category,
+            "accountId": account_id,
+            "amount": amount,
+            "transactionDate": "2026-01-15T12:00:00Z",
+            "transactionType": kind
+        })
+    };
+    let balance = || async {
+        app.get_as(&user, &format!("/accounts/{account_id}"))
+            .await
+            .json()
+            .await["balance"]
+            .as_str()
+            .unwrap()
+            .parse::<f64>()
+            .unwrap()
+    };
+
+    let income = id_of(
+        app.post_as(&user, "/transactions", &transaction(100, "income"))
+            .await
+            .json()
+            .await,
+    );
+    let spent = id_of(
+        app.post_as(&user, "/transactions", &transaction(100, "expense"))
+            .await
+            .json()
+            .await,
+    );
+    assert_eq!(balance().await, 0.0);
+
+    // Lowering the income takes back money that was already spent
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/transactions/{income}"),
+            &json!({ "amount": 10 }),
+        )
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["details"]["shortfall"], "90.00");
+    assert_eq!(balance().await, 0.0);
+
+    // Restoring a trashed expense needs the money to still be there
+    let response = app
+        .delete_as(&user, &format!("/transactions/{spent}"))
+        .await;
+    assert_eq!(response.status(), 204);
+    let groceries = id_of(
+        app.post_as(&user, "/transactions", &transaction(60, "expense"))
+            .await
+            .json()
+            .await,
+    );
+    let response = app
+        .post_as(&user, &format!("/transactions/{spent}/restore"), &json!({}))
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["details"]["shortfall"], "60.00");
+    assert_eq!(balance().await, 40.0);
+
+    // Once an account is below zero, edits that don't lower it further go through
+    sqlx::query("UPDATE accounts SET balance = -20 WHERE id = $1::uuid")
+        .bind(&account_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let path = format!("/transactions/{groceries}");
+    let response = app
+        .patch_as(&user, &path, &json!({ "description": "Weekly shop" }))
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(balance().await, -20.0);
+    let response = app.patch_as(&user, &path, &json!({ "amount": 70 })).await;
+    assert_eq!(response.status(), 409);
+    let response = app.patch_as(&user, &path, &json!({ "amount": 50 })).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(balance().await, -10.0);
+}
+
+#[sqlx::test]
+async fn test_accounts_keep_user_order_with_pinned_first(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("account-order@test.com").await;
+
+    let mut ids = Vec::new();
+    for name in ["Checking", "Savings", "Card"] {
+        let response = app
+            .post_as(
+                &user,
+                "/accounts",
+                &json!({ "name": name, "type": "checking", "colorHex": "#3366FF" }),
+            )
+            .await;
+        ids.push(response.json().await["id"].as_str().unwrap().to_string());
+    }
+    let names = |list: Value| -> Vec<String> {
+        list["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|a| a["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // New accounts go to the end of the list
+    let list = app.get_as(&user, "/accounts").await.json().await;
+    assert_eq!(names(list), ["Checking", "Savings", "Card"]);
+
+    let response = app
+        .patch_as(
+            &user,
+            "/accounts/reorder",
+            &json!({ "accountIds": [ids[2], ids[0], ids[1]] }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let list: Value = response.json().await;
+    assert_eq!(list["accounts"][0]["displayOrder"], 0);
+    assert_eq!(names(list), ["Card", "Checking", "Savings"]);
+
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/accounts/{}", ids[1]),
+            &json!({ "isPinned": true }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let list = app.get_as(&user, "/accounts").await.json().await;
+    assert_eq!(names(list), ["Savings", "Card", "Checking"]);
+
+    // The list must name every account exactly once
+    for account_ids in [
+        json!([ids[0], ids[1]]),
+        json!([ids[0], ids[1], ids[1]]),
+        json!([ids[0], ids[1], uuid::Uuid::new_v4()]),
+    ] {
+        let response = app
+            .patch_as(
+                &user,
+                "/accounts/reorder",
+                &json!({ "accountIds": account_ids }),
+            )
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+}
+
+#[sqlx::test]
+async fn test_live_stream_pushes_transaction_and_balance_events(pool: PgPool) {
+    use be_rust::event::stream::live_events;
+    use futures::StreamExt;
+    use std::time::Duration;
+
+    let app = TestApp::new(pool.clone());
+    let user = app.register_user("live-events@test.com").await;
+    let user_id: uuid::Uuid = user.id.parse().unwrap();
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    // Event frames as text, without the keep-alive comments
+    let frames = |resume_after: Option<i64>| {
+        live_events(pool.clone(), user_id, resume_after)
+            .map(|frame| String::from_utf8(frame.unwrap().to_vec()).unwrap())
+            .filter(|frame| futures::future::ready(!frame.starts_with(':')))
+            .boxed()
+    };
+    // Subscribed from here on, before the change is made
+    let live = frames(None);
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category,
+                "accountId": account_id,
+                "amount": 30,
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+
+    let pushed: Vec<String> = tokio::time::timeout(Duration::from_secs(5), live.take(2).collect())
+        .await
+        .expect("Events not pushed within 5s");
+    let (created, changed) = (&pushed[0], &pushed[1]);
+    assert!(
+        created.contains("event: transaction.created\n"),
+        "{created}"
+    );
+    assert!(
+        changed.contains("event: account.balance_changed\n"),
+        "{changed}"
+    );
+    assert!(changed.contains(&format!("\"accountId\":\"{account_id}\"")));
+    assert!(changed.contains("\"balance\":\"70"));
+
+    // Reconnecting with Last-Event-ID replays what was logged after it
+    let sequence = |frame: &str| frame.lines().next().unwrap().to_string();
+    let replayed: Vec<String> = frames(Some(0)).take(2).collect().await;
+    assert_eq!(sequence(&replayed[0]), sequence(created));
+    assert_eq!(sequence(&replayed[1]), sequence(changed));
+
+    let response = app
+        .request(
+            actix_web::test::TestRequest::get()
+                .uri("/events/stream")
+                .insert_header(("Authorization", format!("Bearer {}", user.access_token)))
+                .insert_header(("Last-Event-ID", "latest")),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+}
+
+#[sqlx::test]
+async fn test_category_alerts_notify_once_per_threshold_crossed(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("alerts@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "allocatedAmount": 100 }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let response = app
+        .put_as(
+            &user,
+            &format!("/categories/{category}/alerts"),
+            &json!({ "thresholds": [100, 80] }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["thresholds"], json!([80, 100]));
+
+    let response = app
+        .put_as(
+            &user,
+            &format!("/categories/{category}/alerts"),
+            &json!({ "thresholds": [0] }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let expense = |amount: i64| {
+        json!({
+            "categoryId": category,
+            "amount": amount,
+            "transactionDate": "2026-01-15T12:00:00Z",
+            "transactionType": "expense"
+        })
+    };
+    let notifications = || async { app.get_as(&user, "/notifications").await.json().await };
+
+    let first = id_of(
+        app.post_as(&user, "/transactions", &expense(50))
+            .await
+            .json()
+            .await,
+    );
+    assert_eq!(notifications().await["data"], json!([]));
+
+    // Raising the expense crosses 80%, only once however often it's edited
+    for amount in [85, 90] {
+        let response = app
+            .patch_as(
+                &user,
+                &format!("/transactions/{first}"),
+                &json!({ "amount": amount }),
+            )
+            .await;
+        assert_eq!(response.status(), 200);
+    }
+    let list = notifications().await;
+    assert_eq!(list["unreadCount"], 1);
+    assert_eq!(list["data"][0]["thresholdPercent"], 80);
+    assert_eq!(list["data"][0]["categoryName"], "Groceries");
+    assert_eq!(
+        list["data"][0]["message"],
+        "Groceries reached 80% of its budget (85.00 of 100.00)"
+    );
+
+    let response = app.post_as(&user, "/transactions", &expense(20)).await;
+    assert_eq!(response.status(), 201);
+    let list = notifications().await;
+    assert_eq!(list["unreadCount"], 2);
+    assert_eq!(list["data"][0]["thresholdPercent"], 100);
+
+    let notification_id = list["data"][0]["id"].as_str().unwrap().to_string();
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/notifications/{notification_id}"),
+            &json!({ "read": true }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["read"], true);
+    let unread = app
+        .get_as(&user, "/notifications?unreadOnly=true")
+        .await
+        .json()
+        .await;
+    assert_eq!(unread["unreadCount"], 1);
+    assert_eq!(unread["data"].as_array().unwrap().len(), 1);
+
+    let response = app
+        .post_as(&user, "/notifications/read-all", &json!({}))
+        .await;
+    assert_eq!(response.json().await["updated"], 1);
+    assert_eq!(notifications().await["unreadCount"], 0);
+
+    // Dropping back below a threshold rearms it
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/transactions/{first}"),
+            &json!({ "amount": 10 }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let response = app.post_as(&user, "/transactions", &expense(60)).await;
+    assert_eq!(response.status(), 201);
+    let list = notifications().await;
+    assert_eq!(list["unreadCount"], 1);
+    assert_eq!(list["data"][0]["thresholdPercent"], 80);
+}
+
+#[sqlx::test]
+async fn test_spending_digest_emails_opted_in_users_once_per_period(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("digest@test.com").await;
+    let bystander = app.register_user("nodigest@test.com").await;
+
+    let response = app.get_as(&user, "/notifications/preferences").await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["digestFrequency"], "off");
+
+    let response = app
+        .put_as(
+            &user,
+            "/notifications/preferences",
+            &json!({ "digestFrequency": "daily" }),
+        )
+        .await;
+    assert_eq!(response.status(), 422);
+    assert_eq!(response.json().await["code"], "MALFORMED_REQUEST");
+
+    let response = app
+        .put_as(
+            &user,
+            "/notifications/preferences",
+            &json!({ "digestFrequency": "monthly" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["digestFrequency"], "monthly");
+
+    // Spending in last month, which opting in this month doesn't cover yet
+    let today = chrono::Utc::now().date_naive();
+    let this_month = today.with_day(1).unwrap();
+    let last_month = this_month.checked_sub_months(Months::new(1)).unwrap();
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": last_month.month0(), "year": last_month.year() }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    for (name, allocated, spent) in [("Groceries", 100, 120), ("Transport", 50, 30)] {
+        let category = id_of(
+            app.post_as(
+                &user,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name, "allocatedAmount": allocated }),
+            )
+            .await
+            .json()
+            .await,
+        );
+        let response = app
+            .post_as(
+                &user,
+                "/transactions",
+                &json!({
+                    "categoryId": category,
+                    "amount": spent,
+                    "transactionDate": format!("{}T12:00:00Z", last_month.with_day(15).unwrap()),
+                    "transactionType": "expense"
+                }),
+            )
+            .await;
+        assert_eq!(response.status(), 201);
+    }
+
+    sqlx::query("UPDATE users SET is_admin = TRUE WHERE id = $1::uuid")
+        .bind(&bystander.id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+    let run_digests = || async {
+        app.post_as(&bystander, "/admin/jobs/digests", &json!({}))
+            .await
+            .json()
+            .await
+    };
+    assert_eq!(run_digests().await["sent"], 0);
+
+    // Once last month is due, it goes out exactly once
+    sqlx::query(
+        "UPDATE notification_preferences SET digest_period_end = $2 WHERE owner_id = $1::uuid",
+    )
+    .bind(&user.id)
+    .bind(last_month)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    assert_eq!(run_digests().await["sent"], 1);
+    assert_eq!(run_digests().await["sent"], 0);
+
+    let sent = app.sent_emails(1).await;
+    assert_eq!(sent.len(), 1);
+    let email = &sent[0];
+    assert_eq!(email.to, "digest@test.com");
+    assert!(email.subject.starts_with("Your monthly spending digest"));
+    assert!(email.body.contains("Spent: 150.00"));
+    assert!(email
+        .body
+        .contains("Top categories:\n  Groceries: 120.00\n  Transport: 30.00\n"));
+    assert!(email.body.contains("budget: 150.00 of 150.00 spent (100%)"));
+    assert!(email.body.contains("Over budget: Groceries"));
+
+    let response = app.get_as(&user, "/notifications/preferences").await;
+    assert!(response.json().await["lastDigestSentAt"].is_string());
+}
+
+/// Stand-in for the Plaid API: one login with a checking account, whose first
+/// sync returns `transactions` and later syncs nothing new
+async fn start_fake_plaid(transactions: Value) -> String {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+
+    let server = HttpServer::new(move || {
+        let transactions = transactions.clone();
+        App::new()
+            .route(
+                "/item/public_token/exchange",
+                web::post().to(|| async {
+                    HttpResponse::Ok()
+                        .json(json!({ "access_token": "access-sandbox-1", "item_id": "item-1" }))
+                }),
+            )
+            .route(
+                "/accounts/get",
+                web::post().to(|| async {
+                    HttpResponse::Ok().json(json!({
+                        "accounts": [{
+                            "account_id": "acc-checking",
+                            "name": "Plaid Checking",
+                            "mask": "0000",
+                            "balances": { "iso_currency_code": "USD" }
+                        }],
+                        "item": { "institution_name": "First Platypus Bank" }
+                    }))
+                }),
+            )
+            .route(
+                "/transactions/sync",
+                web::post().to(move |body: web::Json<Value>| {
+                    let added = if body["cursor"].is_null() {
+                        transactions.clone()
+                    } else {
+                        json!([])
+                    };
+                    async move {
+                        HttpResponse::Ok().json(json!({
+                            "added": added,
+                            "modified": [],
+                            "removed": [],
+                            "next_cursor": "cursor-1",
+                            "has_more": false
+                        }))
+                    }
+                }),
+            )
+    })
+    .workers(1)
+    .listen(listener)
+    .unwrap()
+    .run();
+    tokio::spawn(server);
+
+    format!("http://{address}")
+}
+
+#[sqlx::test]
+async fn test_bank_sync_queues_transactions_for_review_and_flags_duplicates(pool: PgPool) {
+    let bank_transaction = |id: &str, amount: f64, date: &str, name: &str, pending: bool| {
+        json!({
+            "transaction_id": id,
+            "account_id": "acc-checking",
+            "amount": amount,
+            "date": date,
+            "name": name,
+            "merchant_name": null,
+            "pending": pending
+        })
+    };
+    let plaid_url = start_fake_plaid(json!([
+        bank_transaction("tx-coffee", 4.75, "2026-01-10", "Corner Cafe", false),
+        bank_transaction("tx-groceries", 12.5, "2026-01-12", "Green Grocer", false),
+        bank_transaction("tx-salary", -1000.0, "2026-01-15", "Payroll", false),
+        bank_transaction("tx-pending", 9.99, "2026-01-16", "Bookshop", true),
+    ]))
+    .await;
+
+    let disabled = TestApp::new(pool.clone());
+    let user = disabled.register_user("banksync@test.com").await;
+    let response = disabled
+        .post_as(
+            &user,
+            "/bank-sync/connections",
+            &json!({ "publicToken": "public-sandbox-1" }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let app = TestApp::new(pool).with_bank_sync(BankSyncProvider::Plaid(PlaidClient::new(
+        plaid_url,
+        "client-id".to_string(),
+        "secret".to_string(),
+    )));
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Food" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    // Entered by hand two days after the bank's date
+    let manual = id_of(
+        app.post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": category,
+                "accountId": account_id,
+                "amount": 12.5,
+                "transactionDate": "2026-01-14T09:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let response = app
+        .post_as(
+            &user,
+            "/bank-sync/connections",
+            &json!({ "publicToken": "public-sandbox-1" }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let connection: Value = response.json().await;
+    assert_eq!(connection["institutionName"], "First Platypus Bank");
+    assert_eq!(connection["accounts"][0]["name"], "Plaid Checking");
+    let connection_id = id_of(connection.clone());
+    let link_id = id_of(connection["accounts"][0].clone());
+
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/bank-sync/accounts/{link_id}"),
+            &json!({ "accountId": account_id }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["accountId"], account_id);
+
+    // Pending bank transactions wait until they post; re-syncing adds nothing
+    let sync_path = format!("/bank-sync/connections/{connection_id}/sync");
+    let response = app.post_as(&user, &sync_path, &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["importsAdded"], 3);
+    let response = app.post_as(&user, &sync_path, &json!({})).await;
+    assert_eq!(response.json().await["importsAdded"], 0);
+
+    let imports: Value = app.get_as(&user, "/bank-sync/imports").await.json().await;
+    let imports = imports.as_array().unwrap().clone();
+    assert_eq!(imports.len(), 3);
+    let import_for = |description: &str| {
+        imports
+            .iter()
+            .find(|i| i["description"] == description)
+            .unwrap()
+            .clone()
+    };
+    let groceries = import_for("Green Grocer");
+    assert_eq!(groceries["duplicateOfId"], manual);
+    assert_eq!(import_for("Payroll")["transactionType"], "income");
+    assert!(import_for("Corner Cafe")["duplicateOfId"].is_null());
+
+    let approve_path =
+        |import: &Value| format!("/bank-sync/imports/{}/approve", id_of(import.clone()));
+    let response = app
+        .post_as(
+            &user,
+            &approve_path(&groceries),
+            &json!({ "categoryId": category }),
+        )
+        .await;
+    assert_eq!(response.status(), 409);
+    let body = response.json().await;
+    assert_eq!(body["code"], "POSSIBLE_DUPLICATE");
+    assert_eq!(body["details"]["duplicateOfId"], manual);
+
+    let response = app
+        .post_as(
+            &user,
+            &format!("/bank-sync/imports/{}/dismiss", id_of(groceries.clone())),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["status"], "dismissed");
+
+    let coffee = import_for("Corner Cafe");
+    let response = app
+        .post_as(
+            &user,
+            &approve_path(&coffee),
+            &json!({ "categoryId": category }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let approved = response.json().await;
+    assert_eq!(approved["import"]["status"], "imported");
+    assert_eq!(approved["transaction"]["amount"], "4.75");
+    assert_eq!(approved["transaction"]["accountId"], account_id);
+    assert_eq!(approved["transaction"]["description"], "Corner Cafe");
+
+    let response = app
+        .post_as(
+            &user,
+            &approve_path(&coffee),
+            &json!({ "categoryId": category }),
+        )
+        .await;
+    assert_eq!(response.json().await["code"], "IMPORT_ALREADY_REVIEWED");
+
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "82.75");
+
+    let pending: Value = app.get_as(&user, "/bank-sync/imports").await.json().await;
+    assert_eq!(pending.as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_pending_transactions_only_touch_balances_once_approved(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("pending@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let pending_expense = |amount: i64| {
+        json!({
+            "categoryId": category,
+            "accountId": account_id,
+            "amount": amount,
+            "transactionDate": "2026-01-15T12:00:00Z",
+            "transactionType": "expense",
+            "pending": true
+        })
+    };
+    let balance = || async {
+        app.get_as(&user, &format!("/accounts/{account_id}"))
+            .await
+            .json()
+            .await["balance"]
+            .clone()
+    };
+    let spent = || async {
+        app.get_as(&user, &format!("/categories/{category}"))
+            .await
+            .json()
+            .await["spentAmount"]
+            .clone()
+    };
+
+    let response = app
+        .post_as(&user, "/transactions", &pending_expense(30))
+        .await;
+    assert_eq!(response.status(), 201);
+    let approved: Value = response.json().await;
+    assert_eq!(approved["status"], "pending");
+    let approved_id = id_of(approved);
+    let rejected_id = id_of(
+        app.post_as(&user, "/transactions", &pending_expense(45))
+            .await
+            .json()
+            .await,
+    );
+
+    // Waiting for review: no balance or spending effect, and not listed yet
+    assert_eq!(balance().await, "100.00");
+    assert_eq!(spent().await, "0");
+    let pending: Value = app
+        .get_as(&user, "/transactions/pending")
+        .await
+        .json()
+        .await;
+    assert_eq!(pending.as_array().unwrap().len(), 2);
+    let listed: Value = app.get_as(&user, "/transactions").await.json().await;
+    assert_eq!(listed["total"], 0);
+    let response = app
+        .patch_as(
+            &user,
+            &format!("/transactions/{approved_id}"),
+            &json!({ "amount": 35 }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+
+    let response = app
+        .post_as(
+            &user,
+            &format!("/transactions/{approved_id}/approve"),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["status"], "posted");
+    assert_eq!(balance().await, "70.00");
+    assert_eq!(spent().await, "30.00");
+
+    let response = app
+        .post_as(
+            &user,
+            &format!("/transactions/{rejected_id}/reject"),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["status"], "rejected");
+    assert_eq!(balance().await, "70.00");
+    assert_eq!(spent().await, "30.00");
+
+    // Both are final
+    for (id, action) in [(&approved_id, "reject"), (&rejected_id, "approve")] {
+        let response = app
+            .post_as(&user, &format!("/transactions/{id}/{action}"), &json!({}))
+            .await;
+        assert_eq!(response.status(), 409);
+        assert_eq!(response.json().await["code"], "TRANSACTION_NOT_PENDING");
+    }
+    let pending: Value = app
+        .get_as(&user, "/transactions/pending")
+        .await
+        .json()
+        .await;
+    assert!(pending.as_array().unwrap().is_empty());
+    let listed: Value = app.get_as(&user, "/transactions").await.json().await;
+    assert_eq!(listed["total"], 1);
+    assert_eq!(listed["data"][0]["id"], approved_id);
+}
+
+#[sqlx::test]
+async fn test_statement_import_detects_format_and_skips_reimported_entries(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("import@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Imported" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let import_path = format!("/transactions/import?accountId={account_id}&categoryId={category}");
+
+    let ofx = "OFXHEADER:100\nDATA:OFXSGML\n\n<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><BANKTRANLIST>\n\
+               <STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>20260110\n<TRNAMT>-4.75\n<FITID>F1\n<NAME>Corner Cafe\n\
+               <STMTTRN>\n<TRNTYPE>CREDIT\n<DTPOSTED>20260115\n<TRNAMT>1000.00\n<FITID>F2\n<NAME>Payroll\n\
+               </BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+    let response = app.post_raw_as(&user, &import_path, ofx).await;
+    assert_eq!(response.status(), 200);
+    let body = response.json().await;
+    assert_eq!(body["format"], "ofx");
+    assert_eq!(body["parsed"], 2);
+    assert_eq!(body["imported"], 2);
+    let cafe = &body["transactions"][0];
+    assert_eq!(cafe["status"], "pending");
+    assert_eq!(cafe["transactionType"], "expense");
+    assert_eq!(cafe["amount"], "4.75");
+    assert_eq!(cafe["description"], "Corner Cafe");
+    assert_eq!(body["transactions"][1]["transactionType"], "income");
+
+    // An overlapping statement only adds what's new; nothing touches the balance yet
+    let ofx_again = ofx.replace(
+        "</BANKTRANLIST>",
+        "<STMTTRN>\n<DTPOSTED>20260120\n<TRNAMT>-12.50\n<FITID>F3\n<NAME>Green Grocer\n</BANKTRANLIST>",
+    );
+    let body = app
+        .post_raw_as(&user, &import_path, &ofx_again)
+        .await
+        .json()
+        .await;
+    assert_eq!(body["imported"], 1);
+    assert_eq!(body["duplicates"], 2);
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "100.00");
+
+    // QIF and CSV entries without bank ids dedupe by content; repeats in one file stay apart
+    let qif = "!Type:Bank\nD1/21'26\nT-3.00\nPBakery\n^\nD1/21'26\nT-3.00\nPBakery\n^\n";
+    let body = app.post_raw_as(&user, &import_path, qif).await.json().await;
+    assert_eq!(body["format"], "qif");
+    assert_eq!(body["imported"], 2);
+    let body = app.post_raw_as(&user, &import_path, qif).await.json().await;
+    assert_eq!(body["imported"], 0);
+    assert_eq!(body["duplicates"], 2);
+
+    let csv = "Date,Description,Amount\n2026-01-22,Bookshop,-20.00\n2026-01-22,Void,0\n";
+    let body = app.post_raw_as(&user, &import_path, csv).await.json().await;
+    assert_eq!(body["format"], "csv");
+    assert_eq!(body["imported"], 1);
+    assert_eq!(body["skipped"], 1);
+
+    let pending: Value = app
+        .get_as(&user, "/transactions/pending")
+        .await
+        .json()
+        .await;
+    assert_eq!(pending.as_array().unwrap().len(), 6);
+
+    let response = app
+        .post_as(
+            &user,
+            &format!("/transactions/{}/approve", cafe["id"].as_str().unwrap()),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "95.25");
+
+    let response = app
+        .post_raw_as(&user, &import_path, "Name,Value\nx,1\n")
+        .await;
+    assert_eq!(response.status(), 400);
+    let stranger = app.register_user("import-stranger@test.com").await;
+    let response = app.post_raw_as(&stranger, &import_path, csv).await;
+    assert_eq!(response.status(), 404);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_categorization_rules_file_new_and_existing_transactions(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("rules@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let mut categories = Vec::new();
+    for name in ["Misc", "Transport"] {
+        categories.push(id_of(
+            app.post_as(
+                &user,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    let (misc, transport) = (&categories[0], &categories[1]);
+
+    let response = app
+        .post_as(&user, "/payees", &json!({ "name": "Uber" }))
+        .await;
+    assert_eq!(response.status(), 201);
+    let uber = id_of(response.json().await);
+    let response = app
+        .post_as(&user, "/payees", &json!({ "name": "uber" }))
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["code"], "DUPLICATE_PAYEE");
+
+    let expense = |description: &str, amount: i64| {
+        json!({
+            "categoryId": misc,
+            "amount": amount,
+            "transactionDate": "2026-01-10T12:00:00Z",
+            "description": description,
+        })
+    };
+    let earlier = id_of(
+        app.post_as(&user, "/transactions", &expense("UBER *TRIP", 15))
+            .await
+            .json()
+            .await,
+    );
+
+    // A rule must do something
+    let response = app
+        .post_as(&user, "/rules", &json!({ "pattern": "uber" }))
+        .await;
+    assert_eq!(response.status(), 400);
+    let response = app
+        .post_as(
+            &user,
+            "/rules",
+            &json!({ "pattern": "uber", "categoryId": transport, "payeeId": uber }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    let rule = response.json().await;
+    assert_eq!(rule["categoryName"], "Transport");
+    let stranger = app.register_user("rules-stranger@test.com").await;
+    let response = app
+        .get_as(
+            &stranger,
+            &format!("/rules/{}", rule["id"].as_str().unwrap()),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+
+    // New transactions are filed by the rule
+    let created = app
+        .post_as(&user, "/transactions", &expense("Uber ride home", 20))
+        .await
+        .json()
+        .await;
+    assert_eq!(created["categoryId"], transport.as_str());
+    assert_eq!(created["payeeId"], uber.as_str());
+    let other = app
+        .post_as(&user, "/transactions", &expense("Bakery", 5))
+        .await
+        .json()
+        .await;
+    assert_eq!(other["categoryId"], misc.as_str());
+    assert!(other["payeeId"].is_null());
+
+    // Applying the rules refiles the earlier one, moving its spending along
+    let response = app.post_as(&user, "/rules/apply", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    let body = response.json().await;
+    assert_eq!(body["scanned"], 3);
+    assert_eq!(body["updated"], 1);
+    assert_eq!(body["data"][0]["id"], earlier.as_str());
+    assert_eq!(body["data"][0]["payeeId"], uber.as_str());
+    let category = app
+        .get_as(&user, &format!("/categories/{transport}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(category["spentAmount"], "35.00");
+    let body = app
+        .post_as(&user, "/rules/apply", &json!({}))
+        .await
+        .json()
+        .await;
+    assert_eq!(body["updated"], 0);
+
+    // Deleting the payee clears it from transactions and removes its rules
+    let response = app.delete_as(&user, &format!("/payees/{uber}")).await;
+    assert_eq!(response.status(), 200);
+    let transaction = app
+        .get_as(&user, &format!("/transactions/{earlier}"))
+        .await
+        .json()
+        .await;
+    assert!(transaction["payeeId"].is_null());
+    assert_eq!(transaction["categoryId"], transport.as_str());
+    let rules = app.get_as(&user, "/rules").await.json().await;
+    assert_eq!(rules.as_array().unwrap().len(), 0);
+}
+
+#[sqlx::test]
+async fn test_quick_transaction_infers_category_account_and_type(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("quick@test.com").await;
+
+    let payload = json!({ "amount": 4.5, "description": "Coffee at Blue Bottle" });
+
+    // There must be a budget for the current month to file it in
+    let response = app.post_as(&user, "/transactions/quick", &payload).await;
+    assert_eq!(response.status(), 404);
+
+    let today = chrono::Utc::now().date_naive();
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": today.month0(), "year": today.year() }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    // ...with a category to choose
+    let response = app.post_as(&user, "/transactions/quick", &payload).await;
+    assert_eq!(response.status(), 400);
+
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Everyday", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let mut categories = Vec::new();
+    for name in ["Coffee", "Rent"] {
+        categories.push(id_of(
+            app.post_as(
+                &user,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name, "allocatedAmount": 100, "colorHex": "#FF5722" }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    for (category, description) in [
+        (&categories[0], "Coffee at Blue Bottle"),
+        (&categories[1], "Rent for the flat"),
+    ] {
+        let response = app
+            .post_as(
+                &user,
+                "/transactions",
+                &json!({
+                    "categoryId": category,
+                    "accountId": account_id,
+                    "amount": 5,
+                    "transactionDate": chrono::Utc::now(),
+                    "description": description,
+                    "transactionType": "expense"
+                }),
+            )
+            .await;
+        assert_eq!(response.status(), 201);
+    }
+
+    let response = app
+        .post_as(
+            &user,
+            "/transactions/quick",
+            &json!({ "amount": 0, "description": "Coffee" }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+
+    let response = app.post_as(&user, "/transactions/quick", &payload).await;
+    assert_eq!(response.status(), 201);
+    let quick = response.json().await;
+    assert_eq!(quick["categoryId"], categories[0].as_str());
+    assert_eq!(quick["categoryName"], "Coffee");
+    assert!(quick["categoryConfidence"].as_f64().unwrap() > 0.0);
+    assert_eq!(quick["accountId"], account_id.as_str());
+    assert_eq!(quick["transactionType"], "expense");
+    assert_eq!(quick["amount"], "4.50");
+
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(
+        account["balance"].as_str().unwrap().parse::<f64>().unwrap(),
+        85.5
+    );
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_duplicate_transactions_are_found_and_merged(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("duplicates@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let category = id_of(
+        app.post_as(
+            &user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let payee = id_of(
+        app.post_as(&user, "/payees", &json!({ "name": "Green Grocer" }))
+            .await
+            .json()
+            .await,
+    );
+
+    let create = |amount: &str, day: u32, description: &str, payee: Option<&str>| {
+        let body = json!({
+            "categoryId": category,
+            "accountId": account_id,
+            "amount": amount,
+            "transactionDate": format!("2026-01-{day:02}T12:00:00Z"),
+            "description": description,
+            "payeeId": payee,
+        });
+        let app = &app;
+        let user = &user;
+        async move { id_of(app.post_as(user, "/transactions", &body).await.json().await) }
+    };
+    let original = create("12.50", 10, "Green Grocer", None).await;
+    let duplicate = create("12.50", 11, "GREEN GROCER #12", Some(&payee)).await;
+    create("12.50", 11, "Netflix", None).await;
+    let other = create("30.00", 10, "Green Grocer", None).await;
+
+    let response = app.get_as(&user, "/transactions/duplicates").await;
+    assert_eq!(response.status(), 200);
+    let pairs = response.json().await;
+    assert_eq!(pairs.as_array().unwrap().len(), 1);
+    assert_eq!(pairs[0]["original"]["id"], original.as_str());
+    assert_eq!(pairs[0]["duplicate"]["id"], duplicate.as_str());
+    assert_eq!(pairs[0]["daysApart"], 1);
+    let response = app.get_as(&user, "/transactions/duplicates?window=0").await;
+    assert_eq!(response.json().await.as_array().unwrap().len(), 0);
+
+    // Only the same money moving the same way can be merged
+    let merge_path = format!("/transactions/{original}/merge");
+    let response = app
+        .post_as(&user, &merge_path, &json!({ "duplicateId": other }))
+        .await;
+    assert_eq!(response.status(), 400);
+    let response = app
+        .post_as(&user, &merge_path, &json!({ "duplicateId": original }))
+        .await;
+    assert_eq!(response.status(), 400);
+
+    // Merging trashes the duplicate and gives its balance effect back
+    let response = app
+        .post_as(&user, &merge_path, &json!({ "duplicateId": duplicate }))
+        .await;
+    assert_eq!(response.status(), 200);
+    let merged = response.json().await;
+    assert_eq!(merged["id"], original.as_str());
+    assert_eq!(merged["description"], "Green Grocer");
+    assert_eq!(merged["payeeId"], payee.as_str());
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "45.00");
+    let response = app
+        .get_as(&user, &format!("/transactions/{duplicate}"))
+        .await;
+    assert_eq!(response.status(), 404);
+    let pairs = app
+        .get_as(&user, "/transactions/duplicates")
+        .await
+        .json()
+        .await;
+    assert_eq!(pairs.as_array().unwrap().len(), 0);
+}
+
+#[sqlx::test(migrations = "./migrations")]
+async fn test_linked_refunds_net_against_the_original_expense(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("linked-refund@test.com").await;
+
+    let id_of = |value: Value| value["id"].as_str().unwrap().to_string();
+    let budget_id = id_of(
+        app.post_as(&user, "/budgets", &json!({ "month": 0, "year": 2026 }))
+            .await
+            .json()
+            .await,
+    );
+    let mut categories = Vec::new();
+    for name in ["Groceries", "Dining"] {
+        categories.push(id_of(
+            app.post_as(
+                &user,
+                "/categories",
+                &json!({ "budgetId": budget_id, "name": name }),
+            )
+            .await
+            .json()
+            .await,
+        ));
+    }
+    let (groceries, dining) = (&categories[0], &categories[1]);
+    let account_id = id_of(
+        app.post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await,
+    );
+    let expense = id_of(
+        app.post_as(
+            &user,
+            "/transactions",
+            &json!({
+                "categoryId": groceries,
+                "accountId": account_id,
+                "amount": 80,
+                "transactionDate": "2026-01-10T12:00:00Z",
+                "description": "Weekly shop",
+            }),
+        )
+        .await
+        .json()
+        .await,
+    );
+
+    let refund = |category: &str, amount: i64, transaction_type: &str| {
+        json!({
+            "categoryId": category,
+            "accountId": account_id,
+            "amount": amount,
+            "transactionDate": "2026-01-12T12:00:00Z",
+            "description": "Returned items",
+            "transactionType": transaction_type,
+            "linkedTransactionId": expense,
+        })
+    };
+    let response = app
+        .post_as(&user, "/transactions", &refund(groceries, 30, "income"))
+        .await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.json().await["reversalOfId"], expense.as_str());
+
+    // The refund lowers spending instead of counting as income
+    let category = app
+        .get_as(&user, &format!("/categories/{groceries}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(category["spentAmount"], "50.00");
+    let summary = app
+        .get_as(&user, "/transactions/summary?month=0&year=2026")
+        .await
+        .json()
+        .await;
+    assert_eq!(summary["totalExpenses"], "50.00");
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "50.00");
+
+    // Links must reverse the original, in its category, within what is left
+    let response = app
+        .post_as(&user, "/transactions", &refund(groceries, 10, "expense"))
+        .await;
+    assert_eq!(response.status(), 400);
+    let response = app
+        .post_as(&user, "/transactions", &refund(dining, 10, "income"))
+        .await;
+    assert_eq!(response.status(), 400);
+    let response = app
+        .post_as(&user, "/transactions", &refund(groceries, 60, "income"))
+        .await;
+    assert_eq!(response.status(), 400);
+    assert_eq!(response.json().await["code"], "REFUND_EXCEEDS_REMAINING");
+
+    // Linked refunds and the refund endpoint share what remains refundable
+    let response = app
+        .post_as(
+            &user,
+            &format!("/transactions/{expense}/refund"),
+            &json!({}),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.json().await["amount"], "50.00");
+    let response = app
+        .post_as(&user, "/transactions", &refund(groceries, 1, "income"))
+        .await;
+    assert_eq!(response.status(), 409);
+    assert_eq!(response.json().await["code"], "ALREADY_REFUNDED");
+}
+
+#[sqlx::test]
+async fn test_preferences_default_then_update_partially(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("preferences@test.com").await;
+
+    let response = app.get_as(&user, "/users/me/preferences").await;
+    assert_eq!(response.status(), 200);
+    let preferences: Value = response.json().await;
+    assert_eq!(
+        preferences,
+        json!({
+            "firstDayOfWeek": "monday",
+            "dateFormat": "YYYY-MM-DD",
+            "defaultAccountId": null,
+            "hideArchivedAccounts": false,
+            "locale": "en-US"
+        })
+    );
+
+    let account: Value = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 100, "colorHex": "#3366FF" }),
+        )
+        .await
+        .json()
+        .await;
+    let account_id = account["id"].as_str().unwrap().to_string();
+
+    let response = app
+        .patch_as(
+            &user,
+            "/users/me/preferences",
+            &json!({ "dateFormat": "DD.MM.YYYY", "defaultAccountId": account_id }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    let response = app
+        .patch_as(
+            &user,
+            "/users/me/preferences",
+            &json!({ "firstDayOfWeek": "sunday", "locale": "de-DE" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+
+    let preferences: Value = app
+        .get_as(&user, "/users/me/preferences")
+        .await
+        .json()
+        .await;
+    assert_eq!(preferences["dateFormat"], "DD.MM.YYYY");
+    assert_eq!(preferences["defaultAccountId"], account_id.as_str());
+    assert_eq!(preferences["firstDayOfWeek"], "sunday");
+    assert_eq!(preferences["locale"], "de-DE");
+
+    // Validated server-side
+    let response = app
+        .patch_as(
+            &user,
+            "/users/me/preferences",
+            &json!({ "locale": "german" }),
+        )
+        .await;
+    assert_eq!(response.status(), 400);
+    let other = app.register_user("preferences-other@test.com").await;
+    let response = app
+        .patch_as(
+            &other,
+            "/users/me/preferences",
+            &json!({ "defaultAccountId": account_id }),
+        )
+        .await;
+    assert_eq!(response.status(), 404);
+
+    // A deleted default account is no longer reported
+    let response = app
+        .delete_as(&user, &format!("/accounts/{account_id}"))
+        .await;
+    assert_eq!(response.status(), 200);
+    let preferences: Value = app
+        .get_as(&user, "/users/me/preferences")
+        .await
+        .json()
+        .await;
+    assert!(preferences["defaultAccountId"].is_null());
+}
+
+#[sqlx::test]
+async fn test_test_app_serves_every_module(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("routes@test.com").await;
+
+    let account_id = app
+        .post_as(
+            &user,
+            "/accounts",
+            &json!({ "name": "Brokerage", "type": "investment", "balance": 0, "colorHex": "#4CAF50" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // One read per module that used to be registered only by the server
+    for path in [
+        "/payees/autocomplete?q=co".to_string(),
+        "/transaction-templates".to_string(),
+        "/webhooks".to_string(),
+        "/bills".to_string(),
+        "/bills/upcoming".to_string(),
+        "/search?q=rent".to_string(),
+        "/reports/reimbursements".to_string(),
+        "/receipts/drafts".to_string(),
+        format!("/accounts/{account_id}/holdings"),
+        "/activity".to_string(),
+        "/widgets/summary".to_string(),
+        "/settings/dashboard".to_string(),
+        "/events".to_string(),
+    ] {
+        let response = app.get_as(&user, &path).await;
+        assert_eq!(response.status(), 200, "GET {path}");
+    }
+
+    // Undo is reachable too; with no history there is nothing to undo
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.json().await["message"], "Nothing to undo");
+}
+
+/// A USD account starting at 1000 and a January 2026 category to spend from
+async fn create_account_and_category(app: &TestApp, user: &TestUser) -> (String, String) {
+    let account_id = app
+        .post_as(
+            user,
+            "/accounts",
+            &json!({ "name": "Checking", "type": "checking", "balance": 1000, "colorHex": "#4CAF50" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let budget_id = app
+        .post_as(
+            user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 500 }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let category_id = app
+        .post_as(
+            user,
+            "/categories",
+            &json!({ "budgetId": budget_id, "name": "Groceries", "allocatedAmount": 300, "colorHex": "#FF5722" }),
+        )
+        .await
+        .json()
+        .await["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    (account_id, category_id)
+}
+
+/// Current balance of the account and spent amount of the category
+async fn balance_and_spent(
+    app: &TestApp,
+    user: &TestUser,
+    account_id: &str,
+    category_id: &str,
+) -> (Value, Value) {
+    let account = app
+        .get_as(user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    let category = app
+        .get_as(user, &format!("/categories/{category_id}"))
+        .await
+        .json()
+        .await;
+    (account["balance"].clone(), category["spentAmount"].clone())
+}
+
+async fn create_expense(
+    app: &TestApp,
+    user: &TestUser,
+    account_id: &str,
+    category_id: &str,
+) -> String {
+    let response = app
+        .post_as(
+            user,
+            "/transactions",
+            &json!({
+                "categoryId": category_id,
+                "accountId": account_id,
+                "amount": 100,
+                "description": "Weekly shop",
+                "transactionDate": "2026-01-15T12:00:00Z",
+                "transactionType": "expense"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    response.json().await["id"].as_str().unwrap().to_string()
+}
+
+#[sqlx::test]
+async fn test_undo_transaction_create_removes_it_and_its_balance_effect(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("undo-create@test.com").await;
+    let (account_id, category_id) = create_account_and_category(&app, &user).await;
+
+    let transaction_id = create_expense(&app, &user, &account_id, &category_id).await;
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("900.00"), json!("100.00"))
+    );
+
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    let undone = response.json().await;
+    assert_eq!(undone["undoneAction"], "transaction.create");
+    assert_eq!(undone["entityId"], transaction_id.as_str());
+
+    let response = app
+        .get_as(&user, &format!("/transactions/{transaction_id}"))
+        .await;
+    assert_eq!(response.status(), 404);
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("1000.00"), json!("0"))
+    );
+
+    // The next undo moves on to the older category creation instead of
+    // reversing the transaction twice
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["undoneAction"], "category.create");
+    let account = app
+        .get_as(&user, &format!("/accounts/{account_id}"))
+        .await
+        .json()
+        .await;
+    assert_eq!(account["balance"], "1000.00");
+}
+
+#[sqlx::test]
+async fn test_undo_transaction_update_restores_the_previous_version(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("undo-update@test.com").await;
+    let (account_id, category_id) = create_account_and_category(&app, &user).await;
+
+    let transaction_id = create_expense(&app, &user, &account_id, &category_id).await;
+    let path = format!("/transactions/{transaction_id}");
+    let response = app
+        .patch_as(
+            &user,
+            &path,
+            &json!({ "amount": 250, "description": "Monthly shop" }),
+        )
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("750.00"), json!("250.00"))
+    );
+
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["undoneAction"], "transaction.update");
+
+    let transaction = app.get_as(&user, &path).await.json().await;
+    assert_eq!(transaction["amount"], "100.00");
+    assert_eq!(transaction["description"], "Weekly shop");
+    assert_eq!(transaction["accountId"], account_id.as_str());
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("900.00"), json!("100.00"))
+    );
+}
+
+#[sqlx::test]
+async fn test_undo_transaction_delete_brings_it_back(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("undo-delete@test.com").await;
+    let (account_id, category_id) = create_account_and_category(&app, &user).await;
+
+    let transaction_id = create_expense(&app, &user, &account_id, &category_id).await;
+    let path = format!("/transactions/{transaction_id}");
+    assert_eq!(app.delete_as(&user, &path).await.status(), 204);
+    assert_eq!(app.get_as(&user, &path).await.status(), 404);
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("1000.00"), json!("0"))
+    );
+
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json().await["undoneAction"], "transaction.delete");
+
+    let response = app.get_as(&user, &path).await;
+    assert_eq!(response.status(), 200);
+    let transaction = response.json().await;
+    assert_eq!(transaction["amount"], "100.00");
+    assert_eq!(transaction["description"], "Weekly shop");
+    assert_eq!(transaction["categoryId"], category_id.as_str());
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("900.00"), json!("100.00"))
+    );
+}
+
+#[sqlx::test]
+async fn test_undo_is_rejected_once_the_window_has_expired(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("undo-expired@test.com").await;
+    let (account_id, category_id) = create_account_and_category(&app, &user).await;
+
+    let transaction_id = create_expense(&app, &user, &account_id, &category_id).await;
+
+    // The default window is 15 minutes
+    sqlx::query(
+        "UPDATE audit_log SET created_at = NOW() - INTERVAL '16 minutes' WHERE user_id = $1::uuid",
+    )
+    .bind(&user.id)
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let response = app.post_as(&user, "/undo", &json!({})).await;
+    assert_eq!(response.status(), 404);
+    assert_eq!(response.json().await["message"], "Nothing to undo");
+
+    let response = app
+        .get_as(&user, &format!("/transactions/{transaction_id}"))
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        balance_and_spent(&app, &user, &account_id, &category_id).await,
+        (json!("900.00"), json!("100.00"))
+    );
+}
@@ -0,0 +1,320 @@
+//! Property test for the balance invariant: after any sequence of transaction
+//! creates, updates and deletes (transfers included), every account's stored
+//! balance equals its opening balance plus its replayed transaction history.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use be_rust::ratelimit::RateLimitQuota;
+
+use proptest::prelude::*;
+use proptest::test_runner::{Config as ProptestConfig, TestCaseError, TestRunner};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+
+mod common;
+use common::{TestApp, TestUser};
+
+const ACCOUNTS: usize = 3;
+const OPENING_CENTS: i64 = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Expense,
+    Income,
+    Transfer,
+}
+
+impl Kind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Kind::Expense => "expense",
+            Kind::Income => "income",
+            Kind::Transfer => "transfer",
+        }
+    }
+}
+
+/// Operations pick transactions by index into the live ones, modulo their count
+#[derive(Debug, Clone)]
+enum Op {
+    Create {
+        kind: Kind,
+        account: usize,
+        destination_offset: usize,
+        cents: i64,
+    },
+    Update {
+        index: usize,
+        account: Option<usize>,
+        cents: Option<i64>,
+    },
+    Delete {
+        index: usize,
+    },
+}
+
+fn op_strategy() -> impl Strategy<Value = Op> {
+    let kind = prop_oneof![
+        Just(Kind::Expense),
+        Just(Kind::Income),
+        Just(Kind::Transfer)
+    ];
+    let cents = 1i64..50_000;
+    prop_oneof![
+        3 => (kind, 0..ACCOUNTS, 1..ACCOUNTS, cents.clone()).prop_map(
+            |(kind, account, destination_offset, cents)| Op::Create {
+                kind,
+                account,
+                destination_offset,
+                cents,
+            }
+        ),
+        2 => (any::<usize>(), proptest::option::of(0..ACCOUNTS), proptest::option::of(cents))
+            .prop_map(|(index, account, cents)| Op::Update {
+                index,
+                account,
+                cents,
+            }),
+        1 => any::<usize>().prop_map(|index| Op::Delete { index }),
+    ]
+}
+
+/// A transaction as the model expects it to be stored
+#[derive(Debug, Clone)]
+struct ModelTransaction {
+    id: String,
+    kind: Kind,
+    account: usize,
+    destination: Option<usize>,
+    cents: i64,
+}
+
+/// Expected balances, replayed from the model's transactions
+fn replay(transactions: &[ModelTransaction]) -> [i64; ACCOUNTS] {
+    let mut balances = [OPENING_CENTS; ACCOUNTS];
+    for t in transactions {
+        match t.kind {
+            Kind::Expense => balances[t.account] -= t.cents,
+            Kind::Income => balances[t.account] += t.cents,
+            Kind::Transfer => {
+                balances[t.account] -= t.cents;
+                balances[t.destination.expect("transfer destination")] += t.cents;
+            }
+        }
+    }
+    balances
+}
+
+fn amount(cents: i64) -> Decimal {
+    Decimal::new(cents, 2)
+}
+
+async fn create_account(app: &TestApp, user: &TestUser, name: &str) -> String {
+    let response = app
+        .post_as(
+            user,
+            "/accounts",
+            &json!({
+                "name": name,
+                "type": "checking",
+                "balance": amount(OPENING_CENTS),
+                "colorHex": "#3366FF"
+            }),
+        )
+        .await;
+    assert_eq!(response.status(), 201);
+    response.json().await["id"].as_str().unwrap().to_string()
+}
+
+async fn run_case(
+    app: &TestApp,
+    user: &TestUser,
+    category_id: &str,
+    case: usize,
+    ops: Vec<Op>,
+) -> Result<(), TestCaseError> {
+    let mut accounts = Vec::with_capacity(ACCOUNTS);
+    for n in 0..ACCOUNTS {
+        accounts.push(create_account(app, user, &format!("Case {case} account {n}")).await);
+    }
+    let mut transactions: Vec<ModelTransaction> = Vec::new();
+
+    for op in ops {
+        match op {
+            Op::Create {
+                kind,
+                account,
+                destination_offset,
+                cents,
+            } => {
+                let destination =
+                    (kind == Kind::Transfer).then(|| (account + destination_offset) % ACCOUNTS);
+                let response = app
+                    .post_as(
+                        user,
+                        "/transactions",
+                        &json!({
+                            "categoryId": category_id,
+                            "accountId": accounts[account],
+                            "destinationAccountId": destination.map(|d| &accounts[d]),
+                            "amount": amount(cents),
+                            "transactionDate": "2026-01-15T12:00:00Z",
+                            "transactionType": kind.as_str()
+                        }),
+                    )
+                    .await;
+                prop_assert_eq!(response.status(), 201, "create {:?}", op);
+                transactions.push(ModelTransaction {
+                    id: response.json().await["id"].as_str().unwrap().to_string(),
+                    kind,
+                    account,
+                    destination,
+                    cents,
+                });
+            }
+            Op::Update {
+                index,
+                account,
+                cents,
+            } => {
+                if transactions.is_empty() {
+                    continue;
+                }
+                let index = index % transactions.len();
+                let current = &mut transactions[index];
+                // A transfer can't move onto its own destination
+                let account = account.filter(|a| Some(*a) != current.destination);
+
+                let mut body = json!({});
+                if let Some(account) = account {
+                    body["accountId"] = Value::from(accounts[account].as_str());
+                }
+                if let Some(cents) = cents {
+                    body["amount"] = serde_json::to_value(amount(cents)).unwrap();
+                }
+                let response = app
+                    .patch_as(user, &format!("/transactions/{}", current.id), &body)
+                    .await;
+                prop_assert_eq!(response.status(), 200, "update {:?}", op);
+                current.account = account.unwrap_or(current.account);
+                current.cents = cents.unwrap_or(current.cents);
+            }
+            Op::Delete { index } => {
+                if transactions.is_empty() {
+                    continue;
+                }
+                let removed = transactions.remove(index % transactions.len());
+                let response = app
+                    .delete_as(user, &format!("/transactions/{}", removed.id))
+                    .await;
+                prop_assert_eq!(response.status(), 204, "delete {:?}", op);
+            }
+        }
+    }
+
+    let expected = replay(&transactions);
+    for (n, account_id) in accounts.iter().enumerate() {
+        let account = app
+            .get_as(user, &format!("/accounts/{account_id}"))
+            .await
+            .json()
+            .await;
+        let balance: Decimal = account["balance"].as_str().unwrap().parse().unwrap();
+        prop_assert_eq!(balance, amount(expected[n]), "account {}", n);
+    }
+
+    let report = app
+        .get_as(user, "/debug/invariants/balances")
+        .await
+        .json()
+        .await;
+    prop_assert_eq!(&report["holds"], &Value::Bool(true), "{}", report);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_balances_match_replayed_history(pool: PgPool) {
+    // Every case replays its operations as one user, far past the write limit
+    let app = Arc::new(TestApp::new(pool).with_write_quota(RateLimitQuota {
+        period: Duration::from_millis(1),
+        burst: 100_000,
+    }));
+    let user = Arc::new(app.register_user("invariant@test.com").await);
+
+    let response = app
+        .post_as(
+            &user,
+            "/budgets",
+            &json!({ "month": 0, "year": 2026, "totalBudget": 1000 }),
+        )
+        .await;
+    let budget_id = response.json().await["id"].as_str().unwrap().to_string();
+    let response = app
+        .post_as(
+            &user,
+            "/categories",
+            &json!({
+                "budgetId": budget_id,
+                "name": "Anything",
+                "allocated": 1000,
+                "colorHex": "#22AA66"
+            }),
+        )
+        .await;
+    let category_id: Arc<str> = response.json().await["id"].as_str().unwrap().into();
+
+    // Each case makes dozens of requests, so keep the case count modest.
+    // The runner blocks, so it drives the async cases from a blocking thread.
+    let handle = tokio::runtime::Handle::current();
+    let result = tokio::task::spawn_blocking(move || {
+        let mut runner = TestRunner::new(ProptestConfig {
+            cases: 16,
+            max_shrink_iters: 64,
+            failure_persistence: None,
+            ..ProptestConfig::default()
+        });
+        let case = AtomicUsize::new(0);
+        runner.run(&proptest::collection::vec(op_strategy(), 1..20), |ops| {
+            let case = case.fetch_add(1, Ordering::Relaxed);
+            handle.block_on(run_case(&app, &user, &category_id, case, ops))
+        })
+    })
+    .await
+    .expect("Property test panicked");
+
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
+
+#[sqlx::test]
+async fn test_balance_invariant_reports_drift(pool: PgPool) {
+    let app = TestApp::new(pool);
+    let user = app.register_user("drift@test.com").await;
+    let account_id = create_account(&app, &user, "Drifting").await;
+
+    let report = app.get_as(&user, "/debug/invariants/balances").await;
+    assert_eq!(report.status(), 200);
+    assert_eq!(report.json().await["holds"], true);
+
+    // A balance written behind the transaction history's back
+    sqlx::query("UPDATE accounts SET balance = balance + 5 WHERE id = $1::uuid")
+        .bind(&account_id)
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let report = app
+        .get_as(&user, "/debug/invariants/balances")
+        .await
+        .json()
+        .await;
+    assert_eq!(report["holds"], false);
+    assert_eq!(report["checked"], 1);
+    assert_eq!(report["drifted"][0]["accountId"], account_id);
+    assert_eq!(report["drifted"][0]["drift"], "5.00");
+}